@@ -0,0 +1,32 @@
+use alloy::sol;
+
+sol! {
+    /// The ERC-4337 v0.7 EntryPoint, as far as sending user operations
+    /// needs it.
+    #[sol(rpc)]
+    interface IEntryPoint {
+        struct PackedUserOperation {
+            address sender;
+            uint256 nonce;
+            bytes initCode;
+            bytes callData;
+            bytes32 accountGasLimits;
+            uint256 preVerificationGas;
+            bytes32 gasFees;
+            bytes paymasterAndData;
+            bytes signature;
+        }
+
+        function getNonce(address sender, uint192 key) external view returns (uint256 nonce);
+        function getUserOpHash(PackedUserOperation calldata userOp) external view returns (bytes32);
+    }
+}
+
+sol! {
+    /// The single-call entry point most smart accounts share, including
+    /// SimpleAccount, Coinbase Smart Wallet and Simple7702Account.
+    #[sol(rpc)]
+    interface ISmartAccount {
+        function execute(address target, uint256 value, bytes calldata data) external;
+    }
+}
@@ -5,3 +5,33 @@ sol! {
     IERC20Minimal,
     "abi/IERC20Minimal.json"
 }
+
+sol! {
+    /// The optional metadata extension; not every token implements it.
+    #[sol(rpc)]
+    interface IERC20Metadata {
+        function decimals() external view returns (uint8);
+        function symbol() external view returns (string);
+    }
+}
+
+sol! {
+    /// Allowances plus the EIP-2612 permit extension. `nonces`,
+    /// `DOMAIN_SEPARATOR` and `permit` only exist on tokens that implement it.
+    #[allow(clippy::too_many_arguments)]
+    #[sol(rpc)]
+    interface IERC20Permit {
+        function allowance(address owner, address spender) external view returns (uint256);
+        function nonces(address owner) external view returns (uint256);
+        function DOMAIN_SEPARATOR() external view returns (bytes32);
+        function permit(
+            address owner,
+            address spender,
+            uint256 value,
+            uint256 deadline,
+            uint8 v,
+            bytes32 r,
+            bytes32 s
+        ) external;
+    }
+}
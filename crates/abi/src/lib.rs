@@ -1,9 +1,11 @@
+pub mod account;
 pub mod cca;
 pub mod erc20;
 pub mod factory;
 pub mod lens;
 
+pub use account::{IEntryPoint, ISmartAccount};
 pub use cca::IContinuousClearingAuction;
-pub use erc20::IERC20Minimal;
+pub use erc20::{IERC20Metadata, IERC20Minimal, IERC20Permit};
 pub use factory::IContinuousClearingAuctionFactory;
 pub use lens::IAuctionStateLens;
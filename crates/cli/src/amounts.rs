@@ -0,0 +1,96 @@
+// src/amounts.rs
+use alloy::primitives::{Address, U256};
+use eyre::Result;
+use flux_core::{ChainProfile, DisplayAmount, Price, Units};
+
+use crate::{
+    commands::bid::{self, NATIVE_DECIMALS},
+    provider::ChainContext,
+};
+
+/// How a command shows amounts: in whole units with the token's and
+/// currency's symbols, or, under `--raw`, as the raw integers and Q96 prices
+/// the contracts use.
+#[derive(Debug, Clone)]
+pub struct Amounts {
+    units: Option<Units>,
+    native_symbol: &'static str,
+}
+
+impl Amounts {
+    /// Shows amounts in `units`, or raw ones when `None`.
+    pub fn new(units: Option<Units>, native_symbol: &'static str) -> Self {
+        Self {
+            units,
+            native_symbol,
+        }
+    }
+
+    /// Reads the decimals and symbols of `auction`'s token and currency,
+    /// unless `raw`.
+    pub async fn fetch(ctx: &ChainContext, auction: Address, raw: bool) -> Result<Self> {
+        let native_symbol = ChainProfile::for_chain(ctx.chain_id).native_symbol;
+        if raw {
+            return Ok(Self::new(None, native_symbol));
+        }
+        let config = ctx.auction_config(auction).await?;
+        let units = bid::units(ctx, &config).await?;
+        Ok(Self::new(Some(units), native_symbol))
+    }
+
+    pub fn is_raw(&self) -> bool {
+        self.units.is_none()
+    }
+
+    pub fn currency(&self, amount: U256) -> String {
+        match &self.units {
+            Some(units) => {
+                DisplayAmount::new(amount, units.currency_decimals, self.currency_symbol(units))
+                    .to_string()
+            }
+            None => amount.to_string(),
+        }
+    }
+
+    pub fn tokens(&self, amount: U256) -> String {
+        match &self.units {
+            Some(units) => {
+                DisplayAmount::new(amount, units.token_decimals, token_symbol(units)).to_string()
+            }
+            None => amount.to_string(),
+        }
+    }
+
+    /// Currency per whole token, or the Q96 price when raw.
+    pub fn price(&self, price: Price) -> String {
+        match &self.units {
+            Some(units) => DisplayAmount::price(
+                price,
+                units.token_decimals,
+                units.currency_decimals,
+                format!("{}/{}", self.currency_symbol(units), token_symbol(units)),
+            )
+            .to_string(),
+            None => price.as_u256().to_string(),
+        }
+    }
+
+    /// Gas fees and balances in the native currency, in wei when raw.
+    pub fn native(&self, wei: U256) -> String {
+        match &self.units {
+            Some(_) => DisplayAmount::new(wei, NATIVE_DECIMALS, self.native_symbol).to_string(),
+            None => format!("{wei} wei"),
+        }
+    }
+
+    fn currency_symbol<'a>(&'a self, units: &'a Units) -> &'a str {
+        units
+            .currency_symbol
+            .as_deref()
+            .unwrap_or(self.native_symbol)
+    }
+}
+
+fn token_symbol(units: &Units) -> &str {
+    units.token_symbol.as_deref().unwrap_or("tokens")
+}
@@ -0,0 +1,314 @@
+// src/audit.rs
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+};
+
+use flux_core::{ActivityIntent, ActivityLog, ActivityRecord, Decision, DecisionLog};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum AuditError {
+    #[error("failed to access {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("line {line} of {path} is not an activity record: {source}")]
+    Corrupt {
+        path: PathBuf,
+        line: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("line {line} of {path} is not a recorded decision: {source}")]
+    CorruptDecision {
+        path: PathBuf,
+        line: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// An append-only JSONL file with one [`ActivityRecord`] per line, written
+/// as `run` executes intents.
+#[derive(Debug)]
+pub struct ActivityLogFile {
+    path: PathBuf,
+    file: File,
+}
+
+impl ActivityLogFile {
+    /// Opens `path` for appending, creating it and its directory if needed.
+    pub fn open(path: &Path) -> Result<Self, AuditError> {
+        Ok(Self {
+            path: path.to_path_buf(),
+            file: open_append(path)?,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl ActivityLog for ActivityLogFile {
+    fn record(&mut self, record: ActivityRecord) {
+        let mut line = serde_json::to_string(&record).expect("activity records serialize");
+        line.push('\n');
+        // One write per record, so an interrupted run never leaves half a line.
+        if let Err(error) = self.file.write_all(line.as_bytes()) {
+            tracing::warn!(%error, path = %self.path.display(), "failed to write activity log");
+        }
+    }
+}
+
+/// An append-only JSONL file with one [`Decision`] per line, written as
+/// `run --record` evaluates its strategy and read back by `replay`.
+#[derive(Debug)]
+pub struct DecisionLogFile {
+    path: PathBuf,
+    file: File,
+}
+
+impl DecisionLogFile {
+    /// Opens `path` for appending, creating it and its directory if needed.
+    pub fn open(path: &Path) -> Result<Self, AuditError> {
+        Ok(Self {
+            path: path.to_path_buf(),
+            file: open_append(path)?,
+        })
+    }
+}
+
+impl DecisionLog for DecisionLogFile {
+    fn record(&mut self, decision: Decision) {
+        let mut line = serde_json::to_string(&decision).expect("decisions serialize");
+        line.push('\n');
+        if let Err(error) = self.file.write_all(line.as_bytes()) {
+            tracing::warn!(%error, path = %self.path.display(), "failed to record decision");
+        }
+    }
+}
+
+/// The decisions recorded at `path`, in file order.
+pub fn read_decisions(path: &Path) -> Result<Vec<Decision>, AuditError> {
+    let io_error = |source| AuditError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    let reader = BufReader::new(File::open(path).map_err(io_error)?);
+
+    let mut decisions = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(io_error)?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let decision =
+            serde_json::from_str(&line).map_err(|source| AuditError::CorruptDecision {
+                path: path.to_path_buf(),
+                line: index + 1,
+                source,
+            })?;
+        decisions.push(decision);
+    }
+    Ok(decisions)
+}
+
+fn open_append(path: &Path) -> Result<File, AuditError> {
+    let io_error = |source| AuditError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).map_err(io_error)?;
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(io_error)
+}
+
+const CSV_HEADER: &str = "timestamp_ms,block,kind,outcome,execute_by,max_price,amount,bid_ids,\
+owner,from,tx_hash,gas_used,gas_fee,event,error";
+
+/// Converts the activity log at `log` to CSV on `out`, returning how many
+/// records were written. The decoded event is kept as JSON in its column.
+pub fn export_csv(log: &Path, out: &mut impl Write) -> Result<usize, AuditError> {
+    let io_error = |source| AuditError::Io {
+        path: log.to_path_buf(),
+        source,
+    };
+    let reader = BufReader::new(File::open(log).map_err(io_error)?);
+
+    writeln!(out, "{CSV_HEADER}").map_err(io_error)?;
+    let mut count = 0;
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(io_error)?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: ActivityRecord =
+            serde_json::from_str(&line).map_err(|source| AuditError::Corrupt {
+                path: log.to_path_buf(),
+                line: index + 1,
+                source,
+            })?;
+        writeln!(out, "{}", csv_row(&record)).map_err(io_error)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+fn csv_row(record: &ActivityRecord) -> String {
+    let (kind, max_price, amount, bid_ids, owner, from) = match &record.intent {
+        ActivityIntent::SubmitBid {
+            max_price,
+            amount,
+            owner,
+            from,
+        } => (
+            "submit_bid",
+            max_price.to_string(),
+            amount.to_string(),
+            String::new(),
+            owner.map(|owner| owner.to_string()).unwrap_or_default(),
+            from.map(|from| from.to_string()).unwrap_or_default(),
+        ),
+        ActivityIntent::Exit { bid_id } => (
+            "exit",
+            String::new(),
+            String::new(),
+            bid_id.to_string(),
+            String::new(),
+            String::new(),
+        ),
+        ActivityIntent::Claim { bid_ids } => (
+            "claim",
+            String::new(),
+            String::new(),
+            bid_ids
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(" "),
+            String::new(),
+            String::new(),
+        ),
+        ActivityIntent::Checkpoint => (
+            "checkpoint",
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+        ),
+    };
+    let fields = [
+        record.timestamp_ms.to_string(),
+        record.block.to_string(),
+        kind.to_string(),
+        record.outcome.clone(),
+        record
+            .execute_by
+            .map(|block| block.to_string())
+            .unwrap_or_default(),
+        max_price,
+        amount,
+        bid_ids,
+        owner,
+        from,
+        record
+            .tx_hash
+            .map(|hash| hash.to_string())
+            .unwrap_or_default(),
+        record
+            .gas_used
+            .map(|gas| gas.to_string())
+            .unwrap_or_default(),
+        record
+            .gas_fee
+            .map(|fee| fee.to_string())
+            .unwrap_or_default(),
+        record
+            .event
+            .as_ref()
+            .map(|event| serde_json::to_string(event).expect("activity events serialize"))
+            .unwrap_or_default(),
+        record.error.clone().unwrap_or_default(),
+    ];
+    fields
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Quotes `field` if it holds a separator, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{B256, U256};
+    use flux_core::ActivityEvent;
+
+    use super::*;
+
+    #[test]
+    fn exports_appended_records_as_csv() {
+        let dir = std::env::temp_dir().join(format!("flux-audit-{}", std::process::id()));
+        let path = dir.join("activity.jsonl");
+        let _ = fs::remove_file(&path);
+
+        let record = ActivityRecord {
+            timestamp_ms: 1_700_000_000_000,
+            block: 42,
+            intent: ActivityIntent::Claim {
+                bid_ids: vec![U256::from(1), U256::from(2)],
+            },
+            execute_by: None,
+            outcome: "success".into(),
+            tx_hash: Some(B256::repeat_byte(0xab)),
+            gas_used: Some(60_000),
+            gas_fee: Some(U256::from(60_000)),
+            event: Some(ActivityEvent::TokensClaimed {
+                bid_ids: vec![U256::from(1), U256::from(2)],
+                total_tokens: U256::from(500),
+                tx_hashes: Vec::new(),
+            }),
+            error: None,
+        };
+        let mut log = ActivityLogFile::open(&path).unwrap();
+        log.record(record.clone());
+        log.record(ActivityRecord {
+            outcome: "failed".into(),
+            tx_hash: None,
+            gas_used: None,
+            gas_fee: None,
+            event: None,
+            error: Some("bid not yet exited, \"3\"".into()),
+            ..record
+        });
+
+        let mut csv = Vec::new();
+        assert_eq!(export_csv(&path, &mut csv).unwrap(), 2);
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(lines[1].starts_with("1700000000000,42,claim,success,,,,1 2,"));
+        assert!(lines[1].contains(r#""{""name"":""TokensClaimed"""#));
+        assert!(lines[2].ends_with(r#","bid not yet exited, ""3""""#));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,33 @@
+use std::{path::Path, sync::Arc};
+
+use alloy::primitives::{Address, U256};
+use eyre::Result;
+use flux_core::{BidAnalytics, BidId, BlockNumber, NoopHook, ValidationHook};
+
+use crate::{provider::ChainContext, store::TrackedBidStore};
+
+/// Realized price, fill ratio and holding period of an exited bid, from the
+/// auction's events since `from_block` (its start block by default), with
+/// the tags its owner's store in `store_dir` recorded for it.
+pub async fn analyze(
+    ctx: &ChainContext,
+    auction: Address,
+    bid_id: U256,
+    from_block: Option<u64>,
+    store_dir: &Path,
+) -> Result<BidAnalytics> {
+    let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+    let client = ctx
+        .auction_client(auction, Address::ZERO, hook, Vec::new())
+        .await?;
+
+    let from = from_block.map_or(client.config().start_block, BlockNumber::new);
+    let analytics = BidAnalytics::fetch(&client, BidId::new(bid_id), from).await?;
+    let tags = TrackedBidStore::new(store_dir, ctx.chain_id, auction, analytics.owner)
+        .load()?
+        .into_iter()
+        .find(|bid| bid.id.as_u256() == bid_id)
+        .map(|bid| bid.tags)
+        .unwrap_or_default();
+    Ok(analytics.with_tags(tags))
+}
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use alloy::{
+    primitives::{Address, B256, U256},
+    providers::Provider,
+    signers::local::PrivateKeySigner,
+};
+use eyre::Result;
+use flux_core::{
+    Attestation, BidDiscovery, BidId, BlockNumber, NoopHook, SignedAttestation, TrackedBid,
+    ValidationHook,
+};
+
+use crate::provider::ChainContext;
+
+/// Which bids an attestation covers.
+#[derive(Debug, Clone)]
+pub struct AttestQuery {
+    pub auction: Address,
+    /// Owner whose bids are attested when `bid_ids` is empty.
+    pub owner: Address,
+    pub bid_ids: Vec<U256>,
+    pub from_block: u64,
+}
+
+/// Read the final auction state for the queried bids and sign it with `operator`.
+pub async fn attest(
+    ctx: &ChainContext,
+    operator: &PrivateKeySigner,
+    query: AttestQuery,
+) -> Result<SignedAttestation> {
+    let tracked: Vec<TrackedBid> = if query.bid_ids.is_empty() {
+        BidDiscovery::new(ctx.provider.clone())
+            .from_block(BlockNumber::new(query.from_block))
+            .bids_by_owner(query.auction, query.owner)
+            .await?
+            .into_iter()
+            .map(|id| TrackedBid {
+                id,
+                tx_hash: B256::ZERO,
+                tags: Vec::new(),
+            })
+            .collect()
+    } else {
+        query
+            .bid_ids
+            .into_iter()
+            .map(|id| TrackedBid {
+                id: BidId::new(id),
+                tx_hash: B256::ZERO,
+                tags: Vec::new(),
+            })
+            .collect()
+    };
+
+    let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+    let client = ctx
+        .auction_client(query.auction, query.owner, hook, tracked)
+        .await?;
+
+    let block = BlockNumber::new(ctx.provider.get_block_number().await?);
+    let attestation = Attestation::collect(&client, ctx.chain_id, block).await?;
+    Ok(attestation.sign(operator)?)
+}
@@ -0,0 +1,22 @@
+use alloy::primitives::Address;
+use eyre::Result;
+
+use crate::{commands::bid::decimals, config::AuctionEntry, provider::ChainContext};
+
+/// Registry entry for the auction at `auction` on the connected chain.
+/// Fetching its config first makes sure the address is an auction.
+pub async fn fetch_entry(
+    ctx: &ChainContext,
+    auction: Address,
+    lens: Option<Address>,
+) -> Result<AuctionEntry> {
+    let config = ctx.auction_config(auction).await?;
+    let token_decimals = decimals(ctx, config.token.as_address()).await?;
+
+    Ok(AuctionEntry {
+        chain_id: ctx.chain_id,
+        address: auction.to_string(),
+        lens: lens.map(|lens| lens.to_string()),
+        token_decimals,
+    })
+}
@@ -1,54 +1,198 @@
 // src/commands/bid.rs
-use crate::domain::price::q96_from_ratio;
-use crate::provider::ChainContext;
-use alloy::primitives::{Address, U256};
-use anyhow::Result;
-use flux_abi::IContinuousClearingAuction;
+use std::{path::Path, sync::Arc};
 
-pub struct BidArgs {
+use alloy::{
+    primitives::{Address, B256, U256, utils::parse_units},
+    providers::Provider,
+};
+use eyre::{Result, WrapErr};
+use flux_abi::IERC20Metadata;
+use flux_core::{
+    AuctionConfig, AuctionState, BlockNumber, ChainProfile, CurrencyAmount, NoopHook, Price,
+    SubmitBidInput, TickHint, TrackedBid, Units, ValidationHook, q96_from_ratio, validation,
+};
+
+use crate::{
+    confirm::{ActionSummary, Confirmation, human},
+    provider::ChainContext,
+    store::TrackedBidStore,
+};
+
+/// Decimals of the native currency.
+pub(crate) const NATIVE_DECIMALS: u8 = 18;
+
+/// A bid in human units, as written in `bids.toml`.
+#[derive(Debug, Clone)]
+pub struct BidRequest {
     pub auction: Address,
-    pub amount_wei: U256,     // amount in currency wei
-    pub max_price_human: f64, // currency_per_token
-    pub token_decimals: u8,
-    pub currency_decimals: u8,
-    pub owner: Address,
+    /// Currency per token.
+    pub max_price: f64,
+    /// Currency to commit.
+    pub amount: f64,
+    /// Forces the hinted `submitBid` overload with this `prevTickPrice` (Q96).
     pub prev_tick_price: Option<U256>,
-    pub hook_data: Vec<u8>,
+    /// Re-check the clearing price before sending and abort if it rose by
+    /// more than this (Q96).
+    pub max_clearing_drift: Option<U256>,
+    /// Place the bid for this address instead of the signer, who still pays.
+    pub beneficiary: Option<Address>,
+    /// Recorded with the bid, e.g. `campaign=launch`.
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SubmittedBid {
+    pub bid_id: U256,
+    pub tx_hash: B256,
+    pub max_price_q96: U256,
+    /// Raw currency units.
+    pub amount: U256,
+    /// Set when the bid belongs to someone other than the signer.
+    pub beneficiary: Option<Address>,
 }
 
-pub async fn submit_bid(ctx: &ChainContext, args: BidArgs) -> Result<U256> {
-    let max_price_q96 = q96_from_ratio(
-        args.max_price_human,
-        args.token_decimals,
-        args.currency_decimals,
+/// Submit a bid paid by the signer, wait for it to land and record it in
+/// `store_dir` under its owner.
+pub async fn submit_bid(
+    ctx: &ChainContext,
+    request: BidRequest,
+    store_dir: &Path,
+    confirmation: &Confirmation,
+) -> Result<SubmittedBid> {
+    let owner = ctx.sender()?;
+    let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+    let mut client = ctx
+        .auction_client(request.auction, owner, hook, Vec::new())
+        .await?;
+    if let Some(drift) = request.max_clearing_drift {
+        client = client.with_max_clearing_price_drift(Price::new(drift));
+    }
+    let config = client.config();
+
+    let units = units(ctx, config).await?;
+    let native_symbol = ChainProfile::for_chain(ctx.chain_id).native_symbol;
+    let max_price = q96_from_ratio(
+        request.max_price,
+        units.token_decimals,
+        units.currency_decimals,
     )?;
+    let amount = parse_units(&request.amount.to_string(), units.currency_decimals)
+        .wrap_err_with(|| format!("invalid bid amount {}", request.amount))?
+        .get_absolute();
 
-    let auction = IContinuousClearingAuction::new(args.auction, ctx.provider.clone());
+    let (checkpoint, graduation, tokens) = tokio::try_join!(
+        client.fetch_checkpoint(),
+        client.fetch_graduation(),
+        client.fetch_token_balance(),
+    )?;
+    let block = ctx.provider.get_block_number().await?;
+    let state = AuctionState::new(
+        BlockNumber::new(block),
+        checkpoint,
+        graduation,
+        tokens,
+        config,
+    );
 
-    // Alloy-style call builder; you’ll need to adapt to exact API version:
-    let call = if let Some(prev) = args.prev_tick_price {
-        auction.submitBid(
-            max_price_q96,
-            args.amount_wei.to::<u128>() as u128,
-            args.owner,
-            prev,
-            args.hook_data.clone(),
-        )
-    } else {
-        auction.submitBid(
-            max_price_q96,
-            args.amount_wei.to::<u128>() as u128,
-            args.owner,
-            Vec::<u8>::new(),
-        )
+    let input = SubmitBidInput {
+        max_price: Price::new(max_price),
+        amount: CurrencyAmount::new(amount),
+        owner: request.beneficiary.unwrap_or(owner),
+        hint: request
+            .prev_tick_price
+            .map(|prev| TickHint::PrevTick(Price::new(prev))),
     };
+    validation::validate_submit_bid(&input, &state, config)?;
+    let mut params = client.prepare_bid(input, &state).await?;
+    params.tags = request.tags.clone();
+    validation::validate_value(&params, config)?;
+    let (balances, gas_price) =
+        tokio::try_join!(client.fetch_balances(owner), client.fetch_gas_price())?;
+    validation::validate_balance(&params, &balances, gas_price)?;
+    client.hook().validate(&params, &state).await?;
 
-    // TODO: attach signer and send transaction.
-    // let tx = call.value(if is_native_currency { args.amount_wei } else { U256::ZERO });
-    // let pending = ctx.signer.send_transaction(tx).await?;
-    // let receipt = pending.get_receipt().await?;
+    let gas = client
+        .estimate_gas(client.build_submit_bid_tx(&params))
+        .await?;
+    let symbol = units.currency_symbol.as_deref().unwrap_or(native_symbol);
+    let hint = match params.hint {
+        TickHint::PrevTick(prev) => format!("after tick {}", prev.as_u256()),
+        TickHint::Unhinted => "none".to_string(),
+    };
+    let mut summary = ActionSummary::new("submitBid")
+        .with_detail("auction", request.auction)
+        .with_detail(
+            "max price",
+            format!("{} {symbol} per token", request.max_price),
+        )
+        .with_detail("amount", human(amount, units.currency_decimals, symbol))
+        .with_detail("owner", params.owner)
+        .with_detail("hint", hint);
+    if let Some(drift) = request.max_clearing_drift {
+        summary = summary.with_detail("max clearing drift (Q96)", drift);
+    }
+    if !params.value.is_zero() {
+        summary = summary.with_value(params.value.as_u256());
+    }
+    confirmation.confirm(&summary.with_gas(gas, gas_price, native_symbol))?;
+    let result = client.submit_bid(params).await?;
+
+    // The client only tracks the signer's own bids; one placed for someone
+    // else goes into their store so they can settle it with their key.
+    let bid_owner = result.beneficiary.unwrap_or(owner);
+    TrackedBidStore::new(store_dir, ctx.chain_id, request.auction, bid_owner).record_all(&[
+        TrackedBid {
+            id: result.bid_id,
+            tx_hash: result.tx_hash,
+            tags: result.tags,
+        },
+    ])?;
+
+    Ok(SubmittedBid {
+        bid_id: result.bid_id.as_u256(),
+        tx_hash: result.tx_hash,
+        max_price_q96: max_price,
+        amount,
+        beneficiary: result.beneficiary,
+    })
+}
+
+/// Decimals of the auction's token and currency, and the currency's symbol
+/// when it is an ERC-20 that reports one.
+pub(crate) async fn units(ctx: &ChainContext, config: &AuctionConfig) -> Result<Units> {
+    let token_decimals = decimals(ctx, config.token.as_address()).await?;
+    let token_symbol = symbol(ctx, config.token.as_address()).await;
+    if config.is_native_currency() {
+        return Ok(Units {
+            token_decimals,
+            currency_decimals: NATIVE_DECIMALS,
+            currency_symbol: None,
+            token_symbol,
+        });
+    }
+
+    let currency = config.currency.as_address();
+    Ok(Units {
+        token_decimals,
+        currency_decimals: decimals(ctx, currency).await?,
+        currency_symbol: symbol(ctx, currency).await,
+        token_symbol,
+    })
+}
+
+/// `symbol()` of `token`, which ERC-20 leaves optional.
+async fn symbol(ctx: &ChainContext, token: Address) -> Option<String> {
+    IERC20Metadata::new(token, &ctx.provider)
+        .symbol()
+        .call()
+        .await
+        .ok()
+}
 
-    // TODO: parse BidSubmitted event to get bidId.
-    // For now, just return 0 as a placeholder.
-    Ok(U256::from(0u64))
+pub(crate) async fn decimals(ctx: &ChainContext, token: Address) -> Result<u8> {
+    IERC20Metadata::new(token, &ctx.provider)
+        .decimals()
+        .call()
+        .await
+        .wrap_err_with(|| format!("failed to read decimals of {token}"))
 }
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use alloy::primitives::Address;
+use eyre::Result;
+use flux_core::{ChainProfile, CheckpointResult, NoopHook, ValidationHook};
+
+use crate::{
+    confirm::{ActionSummary, Confirmation},
+    provider::ChainContext,
+};
+
+/// Call `checkpoint()` on the auction so its state advances to the current
+/// block, once confirmed.
+pub async fn poke(
+    ctx: &ChainContext,
+    auction_addr: Address,
+    confirmation: &Confirmation,
+) -> Result<CheckpointResult> {
+    let owner = ctx.sender()?;
+    let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+    let client = ctx
+        .auction_client(auction_addr, owner, hook, Vec::new())
+        .await?;
+
+    let (gas, gas_price) = tokio::try_join!(
+        client.estimate_gas(client.build_checkpoint_tx()),
+        client.fetch_gas_price()
+    )?;
+    let summary = ActionSummary::new("checkpoint()")
+        .with_detail("auction", auction_addr)
+        .with_gas(
+            gas,
+            gas_price,
+            ChainProfile::for_chain(ctx.chain_id).native_symbol,
+        );
+    confirmation.confirm(&summary)?;
+
+    Ok(client.poke_checkpoint().await?)
+}
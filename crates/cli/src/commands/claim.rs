@@ -0,0 +1,230 @@
+use std::sync::Arc;
+
+use alloy::{
+    primitives::{Address, U256},
+    providers::Provider,
+};
+use eyre::{Report, Result};
+use flux_core::{
+    AuctionConfig, AuctionState, BidDiscovery, BidId, BlockNumber, ChainProfile, ClaimBatcher,
+    ClaimGasLimit, ClaimResult, ClaimSweeper, ColdStorage, CurrencyAmount, DustThreshold, NoopHook,
+    SweepResult, TokenAmount, TransferPlan, TransferResult, ValidationError, ValidationHook,
+    validate_claim,
+};
+
+use crate::{
+    confirm::{ActionSummary, Confirmation, human},
+    provider::ChainContext,
+};
+
+/// Claim every exited-but-unclaimed bid of the signer across `auctions`.
+///
+/// Bids whose tokens are worth less than `dust_threshold` (raw currency units,
+/// at the final clearing price) are left unclaimed and reported. Nothing is
+/// sent until the claims, and the move to `cold_storage` after them, are
+/// confirmed.
+pub async fn claim_all(
+    ctx: &ChainContext,
+    auctions: &[Address],
+    from_block: u64,
+    dust_threshold: Option<U256>,
+    gas_limit: Option<ClaimGasLimit>,
+    cold_storage: Option<&ColdStorageTarget>,
+    confirmation: &Confirmation,
+) -> Result<Vec<SweepResult>> {
+    let owner = ctx.sender()?;
+    let discovery =
+        BidDiscovery::new(ctx.provider.clone()).from_block(BlockNumber::new(from_block));
+
+    let mut sweeper = ClaimSweeper::new(ctx.provider.clone(), owner, auctions)
+        .await?
+        .with_discovery(discovery);
+    if let Some(threshold) = dust_threshold {
+        sweeper = sweeper.with_dust_threshold(DustThreshold::new(CurrencyAmount::new(threshold)));
+    }
+    if let Some(limit) = gas_limit {
+        sweeper = sweeper.with_claim_gas_limit(limit);
+    }
+
+    let plans = sweeper.plan().await?;
+    if plans.iter().any(|plan| !plan.bid_ids.is_empty()) {
+        let mut summary = ActionSummary::new("claimTokensBatch");
+        let mut gas = 0;
+        for plan in plans.iter().filter(|plan| !plan.bid_ids.is_empty()) {
+            let decimals = match sweeper.config(plan.auction) {
+                Some(config) => super::bid::decimals(ctx, config.token.as_address()).await?,
+                None => 0,
+            };
+            gas += sweeper.estimate_gas(plan).await?;
+            summary = summary.with_detail(
+                plan.auction.to_string(),
+                format!(
+                    "{} from {} bids",
+                    human(plan.tokens.as_u256(), decimals, "tokens"),
+                    plan.bid_ids.len()
+                ),
+            );
+        }
+        let gas_price = ctx.provider.get_gas_price().await?;
+        let summary = with_cold_storage(summary, cold_storage).with_gas(
+            gas,
+            gas_price,
+            ChainProfile::for_chain(ctx.chain_id).native_symbol,
+        );
+        confirmation.confirm(&summary)?;
+    }
+
+    Ok(sweeper.execute(plans).await)
+}
+
+/// Claim specific bids in one auction, validating locally before sending.
+/// With a `gas_limit` the claim may be split into several transactions,
+/// each reported on its own.
+pub async fn claim(
+    ctx: &ChainContext,
+    auction_addr: Address,
+    bid_ids: &[U256],
+    gas_limit: Option<ClaimGasLimit>,
+    cold_storage: Option<&ColdStorageTarget>,
+    confirmation: &Confirmation,
+) -> Result<Vec<Result<ClaimResult, flux_core::Error>>> {
+    let owner = ctx.sender()?;
+    let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+    let client = ctx
+        .auction_client(auction_addr, owner, hook, Vec::new())
+        .await?;
+
+    let bid_ids: Vec<BidId> = bid_ids.iter().copied().map(BidId::new).collect();
+    let block = BlockNumber::new(ctx.provider.get_block_number().await?);
+    let checkpoint = client.fetch_checkpoint().await?;
+    let graduation = client.fetch_graduation().await?;
+    let tokens_received = client.fetch_token_balance().await?;
+    let bids = client.fetch_bids(&bid_ids).await?;
+
+    let state = AuctionState::new(
+        block,
+        checkpoint,
+        graduation,
+        tokens_received,
+        client.config(),
+    );
+
+    validate_claim(&bids, owner, &state, client.config())
+        .map_err(|error| explain_claim_error(error, &state, client.config(), owner))?;
+
+    let mut batcher = ClaimBatcher::new();
+    if let Some(limit) = gas_limit {
+        batcher = batcher.with_gas_limit(limit);
+    }
+    let claims = batcher.plan(&client, owner, bid_ids).await?;
+
+    let mut gas = 0;
+    for claim in &claims {
+        gas += client.estimate_claim_gas(claim).await?;
+    }
+    let (decimals, gas_price) = futures::try_join!(
+        super::bid::decimals(ctx, client.config().token.as_address()),
+        async { Ok(client.fetch_gas_price().await?) },
+    )?;
+    let tokens = bids
+        .iter()
+        .fold(TokenAmount::ZERO, |total, bid| total + bid.tokens_filled);
+    let action = match bids.len() {
+        1 => "claimTokens",
+        _ => "claimTokensBatch",
+    };
+    let summary = ActionSummary::new(action)
+        .with_detail("auction", auction_addr)
+        .with_detail("bids", bids.len())
+        .with_detail("tokens", human(tokens.as_u256(), decimals, "tokens"))
+        .with_detail("transactions", claims.len());
+    let summary = with_cold_storage(summary, cold_storage).with_gas(
+        gas,
+        gas_price,
+        ChainProfile::for_chain(ctx.chain_id).native_symbol,
+    );
+    confirmation.confirm(&summary)?;
+
+    Ok(ClaimBatcher::send(&client, claims).await?)
+}
+
+/// Notes the transfer to cold storage that follows the claims.
+fn with_cold_storage(summary: ActionSummary, target: Option<&ColdStorageTarget>) -> ActionSummary {
+    match target {
+        Some(target) if target.dry_run => summary.with_detail(
+            "then",
+            format!("print a transfer to {} (dry run)", target.destination),
+        ),
+        Some(target) => summary.with_detail(
+            "then",
+            format!("transfer the claimed tokens to {}", target.destination),
+        ),
+        None => summary,
+    }
+}
+
+/// Where claimed tokens go after a claim.
+#[derive(Debug, Clone)]
+pub struct ColdStorageTarget {
+    pub cold_storage: ColdStorage,
+    pub destination: Address,
+    /// Plan the transfer but don't send it.
+    pub dry_run: bool,
+}
+
+#[derive(Debug)]
+pub enum ColdStorageMove {
+    Planned(TransferPlan),
+    Sent(TransferResult),
+}
+
+/// Move `amount` of `auction`'s token from the signer to the target address.
+pub async fn move_to_cold_storage(
+    ctx: &ChainContext,
+    auction: Address,
+    target: &ColdStorageTarget,
+    amount: TokenAmount,
+) -> Result<ColdStorageMove> {
+    let owner = ctx.sender()?;
+    let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+    let client = ctx.auction_client(auction, owner, hook, Vec::new()).await?;
+
+    let plan = target
+        .cold_storage
+        .plan(&client, target.destination, amount)
+        .await?;
+    if target.dry_run {
+        return Ok(ColdStorageMove::Planned(plan));
+    }
+    Ok(ColdStorageMove::Sent(
+        target.cold_storage.execute(&client, plan).await?,
+    ))
+}
+
+fn explain_claim_error(
+    error: ValidationError,
+    state: &AuctionState,
+    config: &AuctionConfig,
+    owner: Address,
+) -> Report {
+    match error {
+        ValidationError::ClaimBlockNotReached => eyre::eyre!(
+            "claiming opens at block {} (current block {}); try again later",
+            config.claim_block.as_u64(),
+            state.current_block.as_u64()
+        ),
+        ValidationError::NotGraduated => eyre::eyre!(
+            "auction did not graduate, so there are no tokens to claim; use `exit` to get a full refund"
+        ),
+        ValidationError::BidNotExited => {
+            eyre::eyre!("at least one bid has not been exited yet; run `exit` for it first")
+        }
+        ValidationError::NoTokensToClaim => eyre::eyre!(
+            "at least one bid has no tokens to claim (it was unfilled or already claimed)"
+        ),
+        ValidationError::OwnerMismatch => eyre::eyre!(
+            "at least one bid is not owned by {owner}; bids in a batch claim must share the signer as owner"
+        ),
+        other => Report::new(other),
+    }
+}
@@ -0,0 +1,128 @@
+use alloy::{
+    primitives::{Address, B256, U256},
+    providers::Provider,
+};
+use eyre::{Result, eyre};
+use flux_abi::IContinuousClearingAuctionFactory;
+use flux_core::{
+    AuctionParameters, ChainProfile, ParameterError, TokenAmount, lint_auction_params,
+};
+
+use crate::{
+    confirm::{ActionSummary, Confirmation},
+    provider::ChainContext,
+};
+
+/// An auction to create through the factory's `initializeDistribution`.
+#[derive(Debug, Clone)]
+pub struct CreateAuctionRequest {
+    pub factory: Address,
+    pub token: Address,
+    /// Raw token units to sell.
+    pub total_supply: U256,
+    pub params: AuctionParameters,
+    pub salt: B256,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreatedAuction {
+    pub auction: Address,
+    pub tx_hash: B256,
+}
+
+/// Everything the contracts would reject `request` for: what the lint
+/// finds, or, when it finds nothing, why creating it from the signer (or
+/// the zero address) reverts in a simulation. Empty when the auction can
+/// be created. Sends nothing.
+pub async fn lint(
+    ctx: &ChainContext,
+    request: &CreateAuctionRequest,
+) -> Result<Vec<ParameterError>> {
+    let issues = lint_auction_params(
+        &request.params,
+        request.token,
+        TokenAmount::new(request.total_supply),
+    );
+    if !issues.is_empty() {
+        return Ok(issues);
+    }
+
+    let factory = IContinuousClearingAuctionFactory::new(request.factory, &ctx.provider);
+    let simulated = factory
+        .initializeDistribution(
+            request.token,
+            request.total_supply,
+            request.params.encode_config_data()?,
+            request.salt,
+        )
+        .from(ctx.sender.unwrap_or_default())
+        .call()
+        .await;
+    match simulated {
+        Ok(_) => Ok(Vec::new()),
+        Err(error) => match error.as_revert_data() {
+            Some(data) => Ok(vec![ParameterError::from_revert(&data)]),
+            None => Err(error.into()),
+        },
+    }
+}
+
+/// Creates the auction after showing what will be sent and asking, and
+/// waits for its address. Run [`lint`] first; this does not.
+pub async fn create(
+    ctx: &ChainContext,
+    request: &CreateAuctionRequest,
+    confirmation: &Confirmation,
+) -> Result<CreatedAuction> {
+    let sender = ctx.sender()?;
+    let factory = IContinuousClearingAuctionFactory::new(request.factory, &ctx.provider);
+    let call = factory
+        .initializeDistribution(
+            request.token,
+            request.total_supply,
+            request.params.encode_config_data()?,
+            request.salt,
+        )
+        .from(sender);
+    let gas = call.estimate_gas().await?;
+    let gas_price = ctx.provider.get_gas_price().await?;
+
+    let params = &request.params;
+    let summary = ActionSummary::new("initializeDistribution")
+        .with_detail("factory", request.factory)
+        .with_detail("token", request.token)
+        .with_detail("total supply (raw)", request.total_supply)
+        .with_detail("currency", params.currency.as_address())
+        .with_detail(
+            "blocks",
+            format!(
+                "{} to {}, claims from {}",
+                params.start_block.as_u64(),
+                params.end_block.as_u64(),
+                params.claim_block.as_u64()
+            ),
+        )
+        .with_detail("floor price (Q96)", params.floor_price.as_u256())
+        .with_detail("tick spacing (Q96)", params.tick_spacing.as_u256())
+        .with_gas(
+            gas,
+            gas_price,
+            ChainProfile::for_chain(ctx.chain_id).native_symbol,
+        );
+    confirmation.confirm(&summary)?;
+
+    let receipt = call.send().await?.get_receipt().await?;
+    if !receipt.status() {
+        return Err(eyre!(
+            "initializeDistribution reverted (tx {})",
+            receipt.transaction_hash
+        ));
+    }
+    let created = receipt
+        .decoded_log::<IContinuousClearingAuctionFactory::AuctionCreated>()
+        .ok_or_else(|| eyre!("initializeDistribution emitted no AuctionCreated"))?;
+    Ok(CreatedAuction {
+        auction: created.auction,
+        tx_hash: receipt.transaction_hash,
+    })
+}
@@ -0,0 +1,233 @@
+use std::{
+    fs, io,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
+
+use alloy::primitives::Address;
+use eyre::Result;
+use flux_core::{ControlHandle, ControlState, ControlStore, Intent, OrchestratorResult};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use thiserror::Error;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+};
+
+use super::run::{self, RunOptions};
+use crate::provider::ChainContext;
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+#[derive(Debug, Error)]
+pub enum DaemonError {
+    #[error("failed to access daemon state at {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("daemon state at {path} is not valid JSON: {source}")]
+    Corrupt {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// Where `daemon` listens and keeps its state, on top of what `run` takes.
+#[derive(Debug, Clone)]
+pub struct DaemonOptions {
+    pub listen: SocketAddr,
+    /// Directory holding the per-campaign daemon state.
+    pub state_dir: PathBuf,
+}
+
+/// Whether the strategy is paused and the intents queued through the API,
+/// for one (chain, auction, owner) campaign, kept as a JSON file so a
+/// restarted daemon carries on where it stopped.
+#[derive(Debug, Clone)]
+pub struct ControlStateFile {
+    path: PathBuf,
+}
+
+impl ControlStateFile {
+    pub fn new(dir: &Path, chain_id: u64, auction: Address, owner: Address) -> Self {
+        Self {
+            path: dir.join(format!("{chain_id}-{auction:#x}-{owner:#x}.json")),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The saved state; unpaused with nothing queued if nothing was saved.
+    pub fn load(&self) -> Result<ControlState, DaemonError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                return Ok(ControlState::default());
+            }
+            Err(source) => {
+                return Err(DaemonError::Io {
+                    path: self.path.clone(),
+                    source,
+                });
+            }
+        };
+        serde_json::from_str(&contents).map_err(|source| DaemonError::Corrupt {
+            path: self.path.clone(),
+            source,
+        })
+    }
+}
+
+impl ControlStore for ControlStateFile {
+    fn save(&self, state: &ControlState) {
+        let json = serde_json::to_string_pretty(state).expect("control state serializes");
+        // Write beside the file and rename, so a crash never leaves it half written.
+        let tmp = self.path.with_extension("json.tmp");
+        let written = self
+            .path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(&tmp, json))
+            .and_then(|()| fs::rename(&tmp, &self.path));
+        if let Err(error) = written {
+            tracing::warn!(%error, path = %self.path.display(), "failed to save daemon state");
+        }
+    }
+}
+
+/// `<data dir>/flux/daemon`.
+pub fn default_state_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("flux")
+        .join("daemon")
+}
+
+/// Runs `run` in the background and serves a JSON-RPC API to steer it on
+/// `options.listen`, until the run finishes or Ctrl-C.
+///
+/// Requests and responses are JSON-RPC 2.0 objects, one per line:
+/// - `status`: block, phase, tracked bids and latest checkpoint the
+///   strategy last saw, whether it is paused and how many intents are
+///   queued; `null` before the first block
+/// - `tracked_bids`: the bid ids being settled
+/// - `submit_intent` with `{"intent": ...}`: queue an intent, in the form
+///   `run --record` writes them, for the next block
+/// - `pause` / `resume`: stop or restart calling the strategy, settling
+///   included; queued intents still go out while paused
+pub async fn daemon(
+    ctx: &ChainContext,
+    mut run_options: RunOptions,
+    options: DaemonOptions,
+) -> Result<OrchestratorResult> {
+    let owner = run_options.owner(ctx)?;
+    let state = ControlStateFile::new(&options.state_dir, ctx.chain_id, run_options.auction, owner);
+    let saved = state.load()?;
+    if saved.paused || !saved.queued.is_empty() {
+        tracing::info!(
+            path = %state.path().display(),
+            paused = saved.paused,
+            queued = saved.queued.len(),
+            "restored daemon state"
+        );
+    }
+    let handle = ControlHandle::from_state(saved).with_store(state);
+
+    let listener = TcpListener::bind(options.listen).await?;
+    tracing::info!(addr = %options.listen, "serving control API");
+    let server = tokio::spawn(serve(listener, handle.clone()));
+
+    run_options.control = Some(handle);
+    let result = run::run(ctx, run_options).await;
+    server.abort();
+    result
+}
+
+async fn serve(listener: TcpListener, handle: ControlHandle) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, peer)) => {
+                tracing::debug!(%peer, "control client connected");
+                tokio::spawn(connection(stream, handle.clone()));
+            }
+            Err(error) => tracing::warn!(%error, "failed to accept control client"),
+        }
+    }
+}
+
+async fn connection(stream: TcpStream, handle: ControlHandle) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut response = respond(&handle, &line).to_string();
+        response.push('\n');
+        if writer.write_all(response.as_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Request {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubmitIntentParams {
+    intent: Intent,
+}
+
+/// Answers one JSON-RPC request line.
+fn respond(handle: &ControlHandle, line: &str) -> Value {
+    let request: Request = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(error) => return failure(Value::Null, PARSE_ERROR, error.to_string()),
+    };
+    let result = match request.method.as_str() {
+        "status" => json!(handle.status()),
+        "tracked_bids" => json!(handle.status().map(|status| status.tracked_bids)),
+        "submit_intent" => match serde_json::from_value::<SubmitIntentParams>(request.params) {
+            Ok(params) => json!({ "queued": handle.submit(params.intent) }),
+            Err(error) => return failure(request.id, INVALID_PARAMS, error.to_string()),
+        },
+        "pause" => {
+            handle.pause();
+            json!({ "paused": true })
+        }
+        "resume" => {
+            handle.resume();
+            json!({ "paused": false })
+        }
+        method => {
+            return failure(
+                request.id,
+                METHOD_NOT_FOUND,
+                format!("unknown method `{method}`"),
+            );
+        }
+    };
+    json!({ "jsonrpc": "2.0", "id": request.id, "result": result })
+}
+
+fn failure(id: Value, code: i64, message: String) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use alloy::primitives::{Address, U256};
+use eyre::Result;
+use flux_core::{NoopHook, TickBook, ValidationHook};
+use serde::Serialize;
+
+use crate::provider::ChainContext;
+
+/// Demand resting above the clearing price, one row per initialized tick.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepthOutput {
+    pub auction: Address,
+    pub block: u64,
+    pub clearing_price: U256,
+    /// Ascending by price, starting at the next active tick.
+    pub levels: Vec<DepthLevel>,
+    /// More initialized ticks sit above the last level.
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepthLevel {
+    pub price_q96: U256,
+    pub demand: U256,
+    /// Demand at this level and every level below it.
+    pub cumulative: U256,
+}
+
+impl DepthOutput {
+    fn new(auction: Address, block: u64, clearing_price: U256, book: TickBook) -> Self {
+        let levels = book
+            .levels
+            .iter()
+            .map(|level| DepthLevel {
+                price_q96: level.price.as_u256(),
+                demand: level.demand.as_u256(),
+                cumulative: level.cumulative.as_u256(),
+            })
+            .collect();
+        Self {
+            auction,
+            block,
+            clearing_price,
+            levels,
+            truncated: book.truncated,
+        }
+    }
+}
+
+/// Read the latest checkpoint and up to `levels` ticks above it.
+pub async fn depth(ctx: &ChainContext, auction: Address, levels: usize) -> Result<DepthOutput> {
+    let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+    let client = ctx
+        .auction_client(auction, Address::ZERO, hook, Vec::new())
+        .await?;
+
+    let checkpoint = client.fetch_checkpoint().await?;
+    let book = client.fetch_tick_book(levels).await?;
+
+    Ok(DepthOutput::new(
+        auction,
+        checkpoint.block.as_u64(),
+        checkpoint.clearing_price.as_u256(),
+        book,
+    ))
+}
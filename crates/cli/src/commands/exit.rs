@@ -0,0 +1,145 @@
+use std::sync::Arc;
+
+use alloy::{
+    primitives::{Address, U256},
+    providers::Provider,
+};
+use eyre::Result;
+use flux_core::{
+    BidId, BlockNumber, ChainProfile, ExitBidParams, ExitPartiallyFilledParams, ExitPreview,
+    ExitResult, ExitVariant, Intent, IntentExecutor, IntentOutcome, IntentResult, NoopHook, Units,
+    ValidationHook,
+};
+
+use crate::{
+    confirm::{ActionSummary, Confirmation, human},
+    provider::ChainContext,
+};
+
+/// Both exits of a bid, with what is needed to show them in human units.
+#[derive(Debug)]
+pub struct ExitPlan {
+    pub auction: Address,
+    pub preview: ExitPreview,
+    pub units: Units,
+    /// Gas of the exit the auction would accept; `None` if neither would
+    /// go through.
+    pub gas: Option<u64>,
+    pub gas_price: u128,
+    pub native_symbol: &'static str,
+}
+
+impl ExitPlan {
+    /// What [`exit`] would send, for confirmation.
+    pub fn summary(&self) -> Option<ActionSummary> {
+        let estimate = self.preview.recommended()?;
+        let symbol = self
+            .units
+            .currency_symbol
+            .as_deref()
+            .unwrap_or(self.native_symbol);
+        let summary = ActionSummary::new(format!(
+            "{}({})",
+            estimate.variant,
+            self.preview.bid_id.as_u256()
+        ))
+        .with_detail("auction", self.auction)
+        .with_detail(
+            "tokens",
+            human(
+                estimate.fill.tokens_filled.as_u256(),
+                self.units.token_decimals,
+                "tokens",
+            ),
+        )
+        .with_detail(
+            "refund",
+            human(
+                estimate.fill.projected_refund.as_u256(),
+                self.units.currency_decimals,
+                symbol,
+            ),
+        );
+        Some(match self.gas {
+            Some(gas) => summary.with_gas(gas, self.gas_price, self.native_symbol),
+            None => summary,
+        })
+    }
+}
+
+/// Estimate both exits of a bid, and the gas of the one that would go
+/// through, without sending anything.
+pub async fn preview(ctx: &ChainContext, auction_addr: Address, bid_id: U256) -> Result<ExitPlan> {
+    let owner = ctx.sender().unwrap_or(Address::ZERO);
+    let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+    let client = ctx
+        .auction_client(auction_addr, owner, hook, Vec::new())
+        .await?;
+    let preview = client.preview_exit(BidId::new(bid_id)).await?;
+
+    let tx = preview
+        .recommended()
+        .map(|estimate| match estimate.variant {
+            ExitVariant::ExitBid => client.build_exit_tx(&ExitBidParams {
+                bid_id: preview.bid_id,
+            }),
+            ExitVariant::ExitPartiallyFilled => {
+                client.build_exit_partially_filled_tx(&ExitPartiallyFilledParams {
+                    bid_id: preview.bid_id,
+                    last_fully_filled_checkpoint_block: preview
+                        .hints
+                        .last_fully_filled_checkpoint_block,
+                    outbid_block: preview.hints.outbid_block,
+                })
+            }
+        });
+    let gas = match tx {
+        Some(tx) => Some(client.estimate_gas(tx).await?),
+        None => None,
+    };
+    let (units, gas_price) = futures::try_join!(super::bid::units(ctx, client.config()), async {
+        Ok(client.fetch_gas_price().await?)
+    })?;
+
+    Ok(ExitPlan {
+        auction: auction_addr,
+        preview,
+        units,
+        gas,
+        gas_price,
+        native_symbol: ChainProfile::for_chain(ctx.chain_id).native_symbol,
+    })
+}
+
+/// Exit a bid, picking `exitBid` or `exitPartiallyFilledBid` from its position
+/// relative to the current clearing price, once `plan` is confirmed.
+pub async fn exit(
+    ctx: &ChainContext,
+    plan: &ExitPlan,
+    confirmation: &Confirmation,
+) -> Result<ExitResult> {
+    let summary = plan
+        .summary()
+        .ok_or_else(|| eyre::eyre!("neither exit would go through now"))?;
+    confirmation.confirm(&summary)?;
+
+    let auction_addr = plan.auction;
+    let bid_id = plan.preview.bid_id.as_u256();
+    let owner = ctx.sender()?;
+    let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+    let client = ctx
+        .auction_client(auction_addr, owner, hook, Vec::new())
+        .await?;
+
+    let block = BlockNumber::new(ctx.provider.get_block_number().await?);
+    let mut executor = IntentExecutor::new(client);
+    let intent = Intent::Exit {
+        bid_id: BidId::new(bid_id),
+    };
+
+    match executor.execute(intent, block).await {
+        IntentOutcome::Success(IntentResult::BidExited(result)) => Ok(result),
+        IntentOutcome::Failed { error, .. } => Err(error.into()),
+        other => Err(eyre::eyre!("unexpected exit outcome: {other:?}")),
+    }
+}
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use alloy::{
+    primitives::{Address, U256},
+    providers::DynProvider,
+};
+use eyre::Result;
+use flux_core::{
+    AuctionClient, Backtest, BacktestReport, BlockProducer, ChainProfile, CurrencyAmount,
+    DemandCurve, EventProducer, IntentExecutor, Ladder, LadderStrategy, NoopHook, Orchestrator,
+    OrchestratorResult, Price, ValidationHook,
+};
+
+use crate::{
+    confirm::{ActionSummary, Confirmation, human},
+    provider::ChainContext,
+};
+
+/// A linear ladder to plan against an auction.
+#[derive(Debug, Clone)]
+pub struct LadderQuery {
+    pub auction: Address,
+    /// Lowest and highest rung prices (Q96).
+    pub low: U256,
+    pub high: U256,
+    pub rungs: usize,
+    /// Currency spread across all rungs (raw units).
+    pub total: U256,
+}
+
+/// A planned ladder next to the auction's current demand.
+#[derive(Debug, Clone)]
+pub struct LadderPreview {
+    pub ladder: Ladder,
+    pub curve: DemandCurve,
+    pub clearing_price: Price,
+    /// Expected fill fraction of bids resting at the clearing price, if any.
+    pub atm_fill: Option<f64>,
+}
+
+/// Plan the ladder and read the tick demand it would sit in, without sending anything.
+pub async fn preview(ctx: &ChainContext, query: &LadderQuery) -> Result<LadderPreview> {
+    let client = client(ctx, query.auction).await?;
+    let ladder = Ladder::linear(
+        client.config(),
+        Price::new(query.low),
+        Price::new(query.high),
+        query.rungs,
+        CurrencyAmount::new(query.total),
+    )?;
+    let curve = client.fetch_demand_curve().await?;
+    let clearing_price = client.fetch_checkpoint().await?.clearing_price;
+
+    let atm_fill = curve.fill_fraction_at(clearing_price, client.config().total_supply);
+
+    Ok(LadderPreview {
+        ladder,
+        curve,
+        clearing_price,
+        atm_fill,
+    })
+}
+
+/// Submit every rung once the auction is active, once the ladder is
+/// confirmed.
+pub async fn execute(
+    ctx: &ChainContext,
+    auction: Address,
+    ladder: Ladder,
+    confirmation: &Confirmation,
+) -> Result<OrchestratorResult> {
+    ctx.sender()?;
+    let client = client(ctx, auction).await?;
+
+    let units = super::bid::units(ctx, client.config()).await?;
+    let symbol = units
+        .currency_symbol
+        .as_deref()
+        .unwrap_or(ChainProfile::for_chain(ctx.chain_id).native_symbol);
+    let mut summary = ActionSummary::new(format!("{} submitBid calls", ladder.rungs.len()))
+        .with_detail("auction", auction)
+        .with_detail(
+            "total",
+            human(ladder.total().as_u256(), units.currency_decimals, symbol),
+        );
+    if let (Some(low), Some(high)) = (ladder.rungs.first(), ladder.rungs.last()) {
+        summary = summary.with_detail(
+            "prices (Q96)",
+            format!(
+                "{} to {}",
+                low.max_price.as_u256(),
+                high.max_price.as_u256()
+            ),
+        );
+    }
+    confirmation.confirm(&summary)?;
+    let blocks = BlockProducer::new(ctx.subscriptions.clone())
+        .into_stream()
+        .await?;
+    let executor =
+        IntentExecutor::new(client).with_chain_profile(ChainProfile::for_chain(ctx.chain_id));
+    let mut orchestrator = Orchestrator::new(executor, LadderStrategy::new(ladder));
+    Ok(orchestrator.run_until(blocks, super::ctrl_c()).await?)
+}
+
+/// Replay the auction's recorded events with the ladder, without sending anything.
+pub async fn backtest(ctx: &ChainContext, query: &LadderQuery) -> Result<BacktestReport> {
+    let client = client(ctx, query.auction).await?;
+    let config = client.config();
+    let ladder = Ladder::linear(
+        config,
+        Price::new(query.low),
+        Price::new(query.high),
+        query.rungs,
+        CurrencyAmount::new(query.total),
+    )?;
+    let events = EventProducer::new(ctx.subscriptions.clone(), query.auction)
+        .history(config.start_block, None)
+        .await?;
+
+    let backtest = Backtest::new(config.clone(), events)
+        .with_owner(client.owner())
+        .with_chain_profile(ChainProfile::for_chain(ctx.chain_id));
+    Ok(backtest.run(&mut LadderStrategy::new(ladder)).await)
+}
+
+async fn client(ctx: &ChainContext, auction: Address) -> Result<AuctionClient<DynProvider>> {
+    let owner = ctx.sender.unwrap_or_default();
+    let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+    ctx.auction_client(auction, owner, hook, Vec::new()).await
+}
@@ -0,0 +1,181 @@
+use std::sync::Arc;
+
+use alloy::primitives::{Address, U256};
+use eyre::Result;
+use flux_core::{
+    Bid, Checkpoint, CurrencyAmount, MarketStructure, NoopHook, TickSpacing, ValidationHook,
+};
+use futures::TryStreamExt;
+use serde::Serialize;
+
+use crate::provider::ChainContext;
+
+/// Which bids to read and how to summarize them.
+#[derive(Debug, Clone)]
+pub struct MarketQuery {
+    pub auction: Address,
+    /// Whose bids to place against the clearing price; left out of the
+    /// competing owners.
+    pub owner: Option<Address>,
+    pub page_size: usize,
+    pub concurrency: usize,
+    /// Competing owners to list, largest demand first.
+    pub top: usize,
+    pub buckets: usize,
+}
+
+/// Owner concentration, the max price histogram and the demand around the
+/// clearing price of an auction's active bids.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketOutput {
+    pub auction: Address,
+    pub block: u64,
+    pub clearing_price: U256,
+    /// Active bids; exited ones are left out throughout.
+    pub bids: usize,
+    pub owners: usize,
+    pub total_demand: U256,
+    pub demand_above_clearing: U256,
+    pub demand_at_or_below_clearing: U256,
+    /// Share of the demand from the `top_owners`, from 0 to 1.
+    pub top_share: f64,
+    /// Largest owners other than `owner`.
+    pub top_owners: Vec<MarketOwner>,
+    /// Ascending by price.
+    pub price_histogram: Vec<MarketBucket>,
+    pub owner: Option<Address>,
+    /// Share of the demand from `owner`'s bids, from 0 to 1.
+    pub owner_share: Option<f64>,
+    /// `owner`'s active bids, ascending by price.
+    pub owner_bids: Vec<MarketOwnBid>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketOwner {
+    pub owner: Address,
+    pub bids: usize,
+    pub demand: U256,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketBucket {
+    pub low_q96: U256,
+    pub high_q96: U256,
+    pub bids: usize,
+    pub demand: U256,
+}
+
+/// How far one of `owner`'s bids sits above the clearing price; both
+/// distances are `None` when it is at or below it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketOwnBid {
+    pub bid_id: U256,
+    pub max_price_q96: U256,
+    pub amount: U256,
+    /// Whole ticks between the clearing price and the bid's max price.
+    pub ticks_above_clearing: Option<U256>,
+    /// Gap to the clearing price as a fraction of it.
+    pub above_clearing: Option<f64>,
+}
+
+impl MarketOutput {
+    fn new(
+        query: &MarketQuery,
+        checkpoint: &Checkpoint,
+        tick_spacing: TickSpacing,
+        bids: &[Bid],
+    ) -> Self {
+        let market = MarketStructure::new(bids, checkpoint.clearing_price, query.buckets);
+        let me = query.owner.unwrap_or(Address::ZERO);
+        let top_owners: Vec<_> = market.competitors(me).take(query.top).collect();
+
+        let mut owner_bids: Vec<_> = bids
+            .iter()
+            .filter(|bid| Some(bid.owner) == query.owner && bid.exited_block.is_none())
+            .collect();
+        owner_bids.sort_by_key(|bid| bid.max_price.as_u256());
+        let owner_share = query.owner.map(|owner| {
+            market.share(
+                market
+                    .owners
+                    .iter()
+                    .find(|demand| demand.owner == owner)
+                    .map_or(CurrencyAmount::ZERO, |demand| demand.demand),
+            )
+        });
+
+        Self {
+            auction: query.auction,
+            block: checkpoint.block.as_u64(),
+            clearing_price: market.clearing_price.as_u256(),
+            bids: market.bids,
+            owners: market.owners.len(),
+            total_demand: market.total_demand.as_u256(),
+            demand_above_clearing: market.demand_above_clearing.as_u256(),
+            demand_at_or_below_clearing: market.demand_at_or_below_clearing.as_u256(),
+            top_share: market.share(
+                top_owners
+                    .iter()
+                    .fold(CurrencyAmount::ZERO, |top, owner| top + owner.demand),
+            ),
+            top_owners: top_owners
+                .iter()
+                .map(|owner| MarketOwner {
+                    owner: owner.owner,
+                    bids: owner.bids,
+                    demand: owner.demand.as_u256(),
+                })
+                .collect(),
+            price_histogram: market
+                .price_histogram
+                .iter()
+                .map(|bucket| MarketBucket {
+                    low_q96: bucket.low.as_u256(),
+                    high_q96: bucket.high.as_u256(),
+                    bids: bucket.bids,
+                    demand: bucket.demand.as_u256(),
+                })
+                .collect(),
+            owner: query.owner,
+            owner_share,
+            owner_bids: owner_bids
+                .into_iter()
+                .map(|bid| {
+                    let above = market.above_clearing(bid.max_price, tick_spacing);
+                    MarketOwnBid {
+                        bid_id: bid.id.as_u256(),
+                        max_price_q96: bid.max_price.as_u256(),
+                        amount: bid.amount.as_u256(),
+                        ticks_above_clearing: above.map(|(ticks, _)| ticks),
+                        above_clearing: above.map(|(_, fraction)| fraction),
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Read the latest checkpoint and every bid of the auction, in paged
+/// multicalls, and summarize the active ones.
+pub async fn market(ctx: &ChainContext, query: &MarketQuery) -> Result<MarketOutput> {
+    let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+    let client = ctx
+        .auction_client(query.auction, Address::ZERO, hook, Vec::new())
+        .await?
+        .with_bid_page_size(query.page_size)
+        .with_bid_page_concurrency(query.concurrency);
+
+    let checkpoint = client.fetch_checkpoint().await?;
+    let bids: Vec<_> = client.fetch_all_bids(..).try_collect().await?;
+
+    Ok(MarketOutput::new(
+        query,
+        &checkpoint,
+        client.config().tick_spacing,
+        &bids,
+    ))
+}
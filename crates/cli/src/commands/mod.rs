@@ -1 +1,31 @@
+pub mod analyze;
+pub mod attest;
+pub mod auctions;
+pub mod bid;
+pub mod checkpoint;
+pub mod claim;
+pub mod create_auction;
+pub mod daemon;
+pub mod depth;
+pub mod exit;
+pub mod ladder;
+pub mod market;
+pub mod portfolio;
+pub mod quote;
+pub mod replay;
+pub mod report;
+pub mod run;
+pub mod snapshot;
 pub mod status;
+pub mod wallet;
+pub mod watch;
+pub mod watchlist;
+
+/// Resolves on the first Ctrl-C; never, if the handler can't be installed.
+pub(crate) async fn ctrl_c() {
+    if let Err(error) = tokio::signal::ctrl_c().await {
+        tracing::warn!(%error, "can't listen for Ctrl-C");
+        std::future::pending::<()>().await;
+    }
+    tracing::info!("Ctrl-C received; stopping after the current block");
+}
@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+
+use alloy::primitives::Address;
+use eyre::Result;
+use flux_core::{BidDiscovery, BidId, BlockNumber};
+
+use crate::{
+    commands::status::{StatusOutput, fetch_auction, fetch_bid_statuses, lens_client},
+    provider::ChainContext,
+    store::TrackedBidStore,
+};
+
+#[derive(Debug, Clone)]
+pub struct PortfolioOutput {
+    pub owner: Address,
+    pub auctions: Vec<Address>,
+    pub bids: Vec<PortfolioBid>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PortfolioBid {
+    pub status: StatusOutput,
+    /// Tags its tracked-bid store recorded; empty for bids placed elsewhere.
+    pub tags: Vec<String>,
+}
+
+/// Where to look for auctions and bids.
+#[derive(Debug, Clone)]
+pub struct PortfolioQuery {
+    pub owner: Address,
+    pub lens: Address,
+    pub auctions: Vec<Address>,
+    pub factory: Option<Address>,
+    pub from_block: u64,
+    /// Directory of the tracked-bid stores the bids' tags are read from.
+    pub bids_dir: PathBuf,
+    /// Only list bids carrying every one of these tags.
+    pub tags: Vec<String>,
+}
+
+pub async fn portfolio(rpc_url: &str, query: PortfolioQuery) -> Result<PortfolioOutput> {
+    let ctx = ChainContext::read_only(rpc_url).await?;
+    let discovery =
+        BidDiscovery::new(ctx.provider.clone()).from_block(BlockNumber::new(query.from_block));
+
+    let mut auctions = query.auctions;
+    if let Some(factory) = query.factory {
+        for auction in discovery.auctions_from_factory(factory).await? {
+            if !auctions.contains(&auction) {
+                auctions.push(auction);
+            }
+        }
+    }
+
+    let mut bids = Vec::new();
+    for &auction in &auctions {
+        let tracked =
+            TrackedBidStore::new(&query.bids_dir, ctx.chain_id, auction, query.owner).load()?;
+        let tracked_bid = |id: &BidId| tracked.iter().find(|bid| bid.id.as_u256() == id.as_u256());
+        let mut bid_ids = discovery.bids_by_owner(auction, query.owner).await?;
+        bid_ids.retain(|id| {
+            tracked_bid(id).map_or(query.tags.is_empty(), |bid| bid.has_tags(&query.tags))
+        });
+        if bid_ids.is_empty() {
+            continue;
+        }
+
+        let client = lens_client(&ctx, auction, query.lens).await?;
+        let snapshot = fetch_auction(&client).await?;
+        for status in fetch_bid_statuses(&client, &snapshot, &bid_ids).await? {
+            let tags = tracked_bid(&status.bid.id)
+                .map(|bid| bid.tags.clone())
+                .unwrap_or_default();
+            bids.push(PortfolioBid { status, tags });
+        }
+    }
+
+    Ok(PortfolioOutput {
+        owner: query.owner,
+        auctions,
+        bids,
+    })
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use alloy::primitives::{Address, U256};
+use eyre::Result;
+use flux_core::{
+    BidSize, CurrencyAmount, NoopHook, Price, RiskLimits, SizeCalculator, TokenAmount,
+    ValidationHook,
+};
+
+use crate::provider::ChainContext;
+
+/// What to size a bid for; amounts and prices in raw units.
+#[derive(Debug, Clone)]
+pub struct QuoteQuery {
+    pub auction: Address,
+    pub target_tokens: U256,
+    /// Projected clearing price (Q96); the current one when `None`.
+    pub price: Option<U256>,
+    pub budget: Option<U256>,
+    pub max_bid_amount: Option<U256>,
+}
+
+/// The bid amount that buys the target at the projected price.
+#[derive(Debug, Clone)]
+pub struct QuoteOutput {
+    pub auction: Address,
+    pub block: u64,
+    pub price: Price,
+    pub remaining_supply: TokenAmount,
+    pub size: BidSize,
+}
+
+/// Reads the latest checkpoint and sizes a bid against what is left to sell.
+pub async fn quote(ctx: &ChainContext, query: &QuoteQuery) -> Result<QuoteOutput> {
+    let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+    let client = ctx
+        .auction_client(query.auction, Address::ZERO, hook, Vec::new())
+        .await?;
+    let checkpoint = client.fetch_checkpoint().await?;
+    let price = query.price.map_or(checkpoint.clearing_price, Price::new);
+
+    let mut calculator = SizeCalculator::new();
+    if let Some(budget) = query.budget {
+        calculator = calculator.with_budget(CurrencyAmount::new(budget));
+    }
+    if let Some(limit) = query.max_bid_amount {
+        calculator = calculator
+            .with_risk_limits(RiskLimits::new().with_max_bid_amount(CurrencyAmount::new(limit)));
+    }
+    let total_supply = client.config().total_supply;
+    let size = calculator
+        .size(
+            TokenAmount::new(query.target_tokens),
+            price,
+            total_supply,
+            checkpoint.cumulative_mps,
+        )
+        .map_err(|violation| eyre::eyre!("{violation}"))?;
+
+    Ok(QuoteOutput {
+        auction: query.auction,
+        block: checkpoint.block.as_u64(),
+        price,
+        remaining_supply: SizeCalculator::remaining_supply(total_supply, checkpoint.cumulative_mps),
+        size,
+    })
+}
@@ -0,0 +1,79 @@
+use alloy::primitives::Address;
+use eyre::Result;
+use flux_core::{Decision, Intent, Replay, ReplayReport, StrategyConfig, TimeTarget};
+
+use crate::provider::ChainContext;
+
+/// How the recorded `run` was started, so its strategy can be rebuilt.
+#[derive(Debug, Clone)]
+pub struct ReplayOptions {
+    /// The `[strategy]` to replay, possibly changed since the recording.
+    pub strategy: Option<StrategyConfig>,
+    pub owner: Address,
+    pub start_at: Option<TimeTarget>,
+    pub checkpoint_every: Option<u64>,
+}
+
+/// Rebuilds the strategy `run` drives from `options` and replays the
+/// recorded decisions through it. Token and currency decimals are read from
+/// the chain the recording was made on.
+pub async fn replay(
+    ctx: &ChainContext,
+    decisions: Vec<Decision>,
+    options: ReplayOptions,
+) -> Result<ReplayReport> {
+    let Some(first) = decisions.first() else {
+        eyre::bail!("the recording holds no decisions");
+    };
+    let recorded_on = first.context.chain.chain_id;
+    if recorded_on != ctx.chain_id {
+        eyre::bail!(
+            "the recording was made on chain {recorded_on}, but the RPC serves chain {}",
+            ctx.chain_id
+        );
+    }
+
+    let config = first.context.config.clone();
+    let configured = match &options.strategy {
+        Some(strategy) => {
+            let units = super::bid::units(ctx, &config).await?;
+            Some(strategy.build(&config, &units, options.owner)?)
+        }
+        None => None,
+    };
+    let mut strategy =
+        super::run::settling_strategy(configured, options.start_at, options.checkpoint_every);
+    Ok(Replay::new(decisions).run(&mut strategy).await)
+}
+
+/// One line for a block's intents, e.g. `submit_bid 1000 @ 2000 by 120, checkpoint`.
+pub fn describe(intents: &[Intent]) -> String {
+    if intents.is_empty() {
+        return "nothing".to_string();
+    }
+    intents
+        .iter()
+        .map(describe_intent)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn describe_intent(intent: &Intent) -> String {
+    match intent {
+        Intent::SubmitBid {
+            max_price, amount, ..
+        } => format!("submit_bid {} @ {}", amount.as_u256(), max_price.as_u256()),
+        Intent::Exit { bid_id } => format!("exit #{}", bid_id.as_u256()),
+        Intent::Claim { bid_ids } => {
+            let ids: Vec<_> = bid_ids
+                .iter()
+                .map(|id| format!("#{}", id.as_u256()))
+                .collect();
+            format!("claim {}", ids.join(" "))
+        }
+        Intent::Checkpoint => "checkpoint".to_string(),
+        Intent::Deadline { intent, execute_by } => {
+            format!("{} by {}", describe_intent(intent), execute_by.as_u64())
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use std::{fmt::Write, sync::Arc};
+
+use alloy::{primitives::Address, providers::Provider};
+use clap::ValueEnum;
+use eyre::Result;
+use flux_core::{AuctionReport, BlockNumber, NoopHook, ValidationHook};
+
+use crate::provider::ChainContext;
+
+/// How `report` renders the post-mortem.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    #[default]
+    Markdown,
+    Json,
+}
+
+/// Which auction to report on, and whose bids to include.
+#[derive(Debug, Clone)]
+pub struct ReportQuery {
+    pub auction: Address,
+    pub owner: Option<Address>,
+    pub from_block: u64,
+    /// How many of the most demanded ticks to list.
+    pub top_ticks: usize,
+}
+
+/// Read the ended auction's final state and event history into a report.
+pub async fn report(ctx: &ChainContext, query: &ReportQuery) -> Result<AuctionReport> {
+    let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+    let client = ctx
+        .auction_client(query.auction, Address::ZERO, hook, Vec::new())
+        .await?;
+
+    let block = BlockNumber::new(ctx.provider.get_block_number().await?);
+    Ok(AuctionReport::collect(
+        &client,
+        block,
+        BlockNumber::new(query.from_block),
+        query.owner,
+        query.top_ticks,
+    )
+    .await?)
+}
+
+pub fn render(report: &AuctionReport, format: ReportFormat) -> Result<String> {
+    Ok(match format {
+        ReportFormat::Markdown => markdown(report),
+        ReportFormat::Json => serde_json::to_string_pretty(report)?,
+    })
+}
+
+/// The report as markdown tables. Amounts are raw units and prices Q96.
+pub fn markdown(report: &AuctionReport) -> String {
+    let mut out = String::new();
+    // Writing to a `String` never fails.
+    let _ = write_markdown(&mut out, report);
+    out
+}
+
+fn write_markdown(out: &mut String, report: &AuctionReport) -> std::fmt::Result {
+    writeln!(out, "# Auction {}", report.auction)?;
+    writeln!(out)?;
+    writeln!(out, "| | |")?;
+    writeln!(out, "|---|---|")?;
+    writeln!(
+        out,
+        "| Blocks | {} to {} |",
+        report.start_block, report.end_block
+    )?;
+    writeln!(out, "| Clearing price (Q96) | {} |", report.clearing_price)?;
+    writeln!(out, "| Currency raised | {} |", report.currency_raised)?;
+    writeln!(
+        out,
+        "| Tokens cleared | {} of {} |",
+        report.total_cleared, report.total_supply
+    )?;
+    writeln!(
+        out,
+        "| Graduated | {} |",
+        if report.graduated { "yes" } else { "no" }
+    )?;
+    writeln!(out, "| Bids | {} |", report.total_bids)?;
+
+    writeln!(out)?;
+    writeln!(out, "## Supply per step")?;
+    writeln!(out)?;
+    writeln!(out, "| Blocks | mps | Supply | Clearing price (Q96) |")?;
+    writeln!(out, "|---|---:|---:|---:|")?;
+    for step in &report.steps {
+        writeln!(
+            out,
+            "| {} to {} | {} | {} | {} |",
+            step.start_block,
+            step.end_block,
+            step.mps,
+            step.supply,
+            step.clearing_price
+                .map_or("-".to_string(), |price| price.to_string())
+        )?;
+    }
+
+    writeln!(out)?;
+    writeln!(out, "## Top ticks by demand")?;
+    writeln!(out)?;
+    writeln!(out, "| Price (Q96) | Demand | Bids |")?;
+    writeln!(out, "|---:|---:|---:|")?;
+    for tick in &report.top_ticks {
+        writeln!(out, "| {} | {} | {} |", tick.price, tick.demand, tick.bids)?;
+    }
+
+    if let Some(mine) = &report.participation {
+        writeln!(out)?;
+        writeln!(out, "## Bids of {}", mine.owner)?;
+        writeln!(out)?;
+        writeln!(out, "| | |")?;
+        writeln!(out, "|---|---|")?;
+        writeln!(
+            out,
+            "| Bids | {} ({} exited, {} claimed) |",
+            mine.bids, mine.exited, mine.claimed
+        )?;
+        writeln!(out, "| Committed | {} |", mine.committed)?;
+        writeln!(
+            out,
+            "| Spent | {} ({} refunded) |",
+            mine.spent, mine.refunded
+        )?;
+        writeln!(out, "| Tokens filled | {} |", mine.tokens_filled)?;
+        writeln!(
+            out,
+            "| Average price (Q96) | {} |",
+            mine.average_price
+                .map_or("-".to_string(), |price| price.to_string())
+        )?;
+        writeln!(
+            out,
+            "| Share of raised | {:.2}% |",
+            mine.share_of_raised * 100.0
+        )?;
+    }
+    Ok(())
+}
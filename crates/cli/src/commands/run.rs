@@ -0,0 +1,313 @@
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+
+use alloy::primitives::{Address, B256, U256};
+use async_trait::async_trait;
+use clap::ValueEnum;
+use eyre::Result;
+use flux_core::{
+    AtmSettlement, AuctionPhase, AutoCheckpoint, BidDiscovery, BidId, BlockNumber, BlockProducer,
+    ChainProfile, CompletionReason, ControlHandle, Controlled, CurrencyAmount, DustThreshold,
+    EvaluationContext, EventProducer, Intent, IntentExecutor, NoopHook, Orchestrator,
+    OrchestratorResult, PrivateRelay, Scheduled, SendJournal, Sequence, SettleStrategy, ShadowFork,
+    SmartAccount, StalenessGuard, Strategy, StrategyConfig, TimeTarget, TrackedBid, ValidationHook,
+    metrics,
+};
+
+use crate::{
+    audit::{ActivityLogFile, DecisionLogFile},
+    lock::{CampaignLock, IfLocked, LockError},
+    provider::ChainContext,
+    store::{SendJournalFile, TrackedBidStore},
+};
+
+/// What `run` should manage.
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    pub auction: Address,
+    /// Bids to settle; discovered from `from_block` when empty.
+    pub bid_ids: Vec<U256>,
+    pub from_block: u64,
+    /// Poke `checkpoint()` when the last one is older than this many blocks.
+    pub checkpoint_every: Option<u64>,
+    pub dust_threshold: Option<U256>,
+    /// Hold exits of bids at the clearing price until this many blocks after the end.
+    pub atm_wait_blocks: Option<u64>,
+    /// Suppress an intent for this many blocks after it failed.
+    pub failure_cooldown: Option<u64>,
+    pub metrics_addr: Option<SocketAddr>,
+    /// Directory holding the per-campaign lockfiles.
+    pub lock_dir: PathBuf,
+    /// Directory holding the per-campaign tracked-bid stores.
+    pub bids_dir: PathBuf,
+    pub if_locked: IfLocked,
+    /// Strategy to run before settling, from the `[strategy]` config table.
+    pub strategy: Option<StrategyConfig>,
+    /// Recorded with every bid the run submits, besides `strategy=<kind>`
+    /// for the configured strategy.
+    pub tags: Vec<String>,
+    /// Hold `strategy` back until this time.
+    pub start_at: Option<TimeTarget>,
+    /// Relay for the transactions it routes, instead of the public mempool.
+    pub private_relay: Option<PrivateRelay>,
+    /// Smart account every transaction is sent from as a user operation,
+    /// which then owns the bids instead of the signer.
+    pub smart_account: Option<SmartAccount>,
+    /// Anvil fork every transaction is rehearsed on before it is sent.
+    pub shadow_fork: Option<Arc<ShadowFork>>,
+    /// Holds intents back while blocks trail the chain head.
+    pub staleness: Option<StalenessGuard>,
+    /// Only warn, rather than call `checkpoint()`, when the auction's
+    /// checkpoints stop short of the block before a partial exit.
+    pub no_checkpoint_fill: bool,
+    /// Read every transaction's effect back from the auction before
+    /// counting it as done.
+    pub verify_after_execute: bool,
+    /// JSONL file every executed intent is appended to.
+    pub activity_log: Option<PathBuf>,
+    /// JSONL file every block's strategy context and intents are appended
+    /// to, for `replay`.
+    pub record: Option<PathBuf>,
+    /// Lets `daemon` pause the strategy and queue intents while it runs.
+    pub control: Option<ControlHandle>,
+}
+
+impl RunOptions {
+    /// Who owns the bids: the smart account when there is one, else the
+    /// signer.
+    pub fn owner(&self, ctx: &ChainContext) -> Result<Address> {
+        match &self.smart_account {
+            Some(account) => Ok(account.address()),
+            None => ctx.sender(),
+        }
+    }
+}
+
+/// Intents `run --private-intents` can route through the private relay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PrivateIntent {
+    SubmitBid,
+    Exit,
+    Claim,
+    Checkpoint,
+}
+
+impl PrivateIntent {
+    /// The matching [`Intent::kind`].
+    pub fn kind(self) -> &'static str {
+        match self {
+            PrivateIntent::SubmitBid => "submit_bid",
+            PrivateIntent::Exit => "exit",
+            PrivateIntent::Claim => "claim",
+            PrivateIntent::Checkpoint => "checkpoint",
+        }
+    }
+}
+
+/// Follows the auction and logs phase changes without sending anything; used
+/// when another process holds the campaign lock.
+#[derive(Debug, Default)]
+struct Observe;
+
+#[async_trait]
+impl Strategy for Observe {
+    async fn evaluate(&mut self, _ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        Vec::new()
+    }
+
+    fn on_phase_change(
+        &mut self,
+        _previous: Option<&AuctionPhase>,
+        ctx: &EvaluationContext<'_>,
+    ) -> Vec<Intent> {
+        tracing::info!(block = ctx.block.as_u64(), phase = ?ctx.phase, "read-only: phase changed");
+        Vec::new()
+    }
+
+    fn is_complete(&self, ctx: &EvaluationContext<'_>) -> bool {
+        matches!(ctx.phase, AuctionPhase::Claimable)
+    }
+}
+
+/// What the lock holder drives: `configured`, held back until `start_at`,
+/// then settling, with checkpoints poked once older than `checkpoint_every`
+/// blocks. `replay` rebuilds it the same way.
+pub(crate) fn settling_strategy(
+    configured: Option<Box<dyn Strategy>>,
+    start_at: Option<TimeTarget>,
+    checkpoint_every: Option<u64>,
+) -> Box<dyn Strategy> {
+    let configured = configured.map(|strategy| -> Box<dyn Strategy> {
+        match start_at {
+            Some(target) => Box::new(Scheduled::new(strategy, target)),
+            None => strategy,
+        }
+    });
+    let settle: Box<dyn Strategy> = match configured {
+        Some(first) => Box::new(Sequence::new(first).then(SettleStrategy::new())),
+        None => Box::new(SettleStrategy::new()),
+    };
+    match checkpoint_every {
+        Some(max_age) => Box::new(AutoCheckpoint::new(settle, max_age)),
+        None => settle,
+    }
+}
+
+/// Follow the chain head and settle the signer's bids: exit once the auction
+/// ends, claim once it is claimable. A configured strategy runs first and
+/// hands over to settling once it is complete.
+pub async fn run(ctx: &ChainContext, options: RunOptions) -> Result<OrchestratorResult> {
+    if let Some(addr) = options.metrics_addr {
+        tokio::spawn(async move {
+            if let Err(error) = metrics::serve(addr).await {
+                tracing::error!(%error, %addr, "metrics exporter stopped");
+            }
+        });
+        tracing::info!(%addr, "serving metrics");
+    }
+
+    let owner = options.owner(ctx)?;
+    // Held until `run` returns.
+    let lock = match CampaignLock::acquire(&options.lock_dir, ctx.chain_id, options.auction, owner)
+    {
+        Ok(lock) => {
+            tracing::debug!(path = %lock.path().display(), "acquired campaign lock");
+            Some(lock)
+        }
+        Err(error @ LockError::Held { .. }) if options.if_locked == IfLocked::ReadOnly => {
+            tracing::warn!(%error, "campaign is locked; continuing read-only");
+            None
+        }
+        Err(error) => return Err(error.into()),
+    };
+
+    let store = TrackedBidStore::new(&options.bids_dir, ctx.chain_id, options.auction, owner);
+    // Recorded bids keep their submission hashes; discovered ones have none.
+    let (bid_ids, mut tracked) = if options.bid_ids.is_empty() {
+        let discovered = BidDiscovery::new(ctx.provider.clone())
+            .from_block(BlockNumber::new(options.from_block))
+            .bids_by_owner(options.auction, owner)
+            .await?;
+        (discovered, store.load()?)
+    } else {
+        let ids = options.bid_ids.into_iter().map(BidId::new).collect();
+        (ids, Vec::new())
+    };
+    for id in bid_ids {
+        if !tracked.iter().any(|bid| bid.id.as_u256() == id.as_u256()) {
+            tracked.push(TrackedBid {
+                id,
+                tx_hash: B256::ZERO,
+                tags: Vec::new(),
+            });
+        }
+    }
+
+    let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+    let mut client = ctx
+        .auction_client(options.auction, owner, hook, tracked)
+        .await?;
+    if let Some(relay) = options.private_relay.clone() {
+        client = client.with_private_relay(relay);
+    }
+    if let Some(fork) = options.shadow_fork.clone() {
+        client = client.with_shadow_fork(fork);
+    }
+    client = client.with_checkpoint_gap_fill(!options.no_checkpoint_fill);
+    if let Some(account) = options.smart_account.clone() {
+        if lock.is_some() {
+            account.delegate(&ctx.provider).await?;
+        }
+        client = client.with_smart_account(account);
+    }
+    // Only the lock holder sends, so only it journals and reconciles.
+    if lock.is_some() {
+        let file = SendJournalFile::new(&options.bids_dir, ctx.chain_id, options.auction, owner);
+        let journal = SendJournal::new(file.load()?).with_store(file);
+        client = client.with_journal(Arc::new(journal));
+        let landed = client.reconcile_journal().await?;
+        if landed > 0 {
+            tracing::info!(landed, "found transactions sent before a restart");
+        }
+    }
+
+    let configured = match &options.strategy {
+        Some(strategy) if lock.is_some() => {
+            let units = super::bid::units(ctx, client.config()).await?;
+            Some(strategy.build(client.config(), &units, owner)?)
+        }
+        _ => None,
+    };
+
+    let mut executor =
+        IntentExecutor::new(client).with_chain_profile(ChainProfile::for_chain(ctx.chain_id));
+    if let Some(threshold) = options.dust_threshold {
+        executor = executor.with_dust_threshold(DustThreshold::new(CurrencyAmount::new(threshold)));
+    }
+    if let Some(blocks) = options.failure_cooldown {
+        executor = executor.with_failure_cooldown(blocks);
+    }
+    if let Some(blocks) = options.atm_wait_blocks {
+        executor = executor.with_atm_settlement(AtmSettlement::WaitForCheckpoints { blocks });
+    }
+    let mut tags = options.tags.clone();
+    if let Some(strategy) = &options.strategy {
+        let tag = format!("strategy={}", strategy.kind());
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    executor = executor
+        .with_bid_tags(tags)
+        .with_verify_after_execute(options.verify_after_execute);
+
+    // Configured strategies may react to auction events, so they get a stream.
+    let needs_events = configured.is_some();
+    let strategy: Box<dyn Strategy> = match &lock {
+        None => Box::new(Observe),
+        Some(_) => settling_strategy(configured, options.start_at, options.checkpoint_every),
+    };
+    // Read-only followers send nothing, so there is nothing to control.
+    let strategy: Box<dyn Strategy> = match (options.control.clone(), &lock) {
+        (Some(handle), Some(_)) => Box::new(Controlled::new(strategy, handle)),
+        _ => strategy,
+    };
+
+    ctx.monitor_endpoints();
+    let blocks = BlockProducer::new(ctx.subscriptions.clone())
+        .into_stream()
+        .await?;
+    let mut orchestrator = Orchestrator::new(executor, strategy);
+    if let Some(guard) = options.staleness.clone() {
+        orchestrator = orchestrator.with_staleness_guard(guard);
+    }
+    if let Some(path) = &options.activity_log {
+        orchestrator = orchestrator.with_activity_log(ActivityLogFile::open(path)?);
+        tracing::info!(path = %path.display(), "writing activity log");
+    }
+    if let Some(path) = &options.record {
+        orchestrator = orchestrator.with_decision_log(DecisionLogFile::open(path)?);
+        tracing::info!(path = %path.display(), "recording decisions");
+    }
+    let result = if needs_events {
+        let events = EventProducer::new(ctx.subscriptions.clone(), options.auction)
+            .into_stream()
+            .await?;
+        orchestrator
+            .run_with_events_until(blocks, events, super::ctrl_c())
+            .await?
+    } else {
+        orchestrator.run_until(blocks, super::ctrl_c()).await?
+    };
+
+    // Read-only followers leave the store to the lock holder.
+    if lock.is_some() {
+        store.record_all(&orchestrator.executor().client().tracked_bids())?;
+    }
+    if result.reason == CompletionReason::Cancelled {
+        // Already in the store; logged so the operator sees what is still open.
+        for bid in orchestrator.executor().client().tracked_bids() {
+            tracing::info!(bid_id = %bid.id.as_u256(), tx = %bid.tx_hash, "still tracking bid");
+        }
+    }
+    Ok(result)
+}
@@ -0,0 +1,52 @@
+use std::{path::Path, sync::Arc};
+
+use alloy::primitives::Address;
+use eyre::{Result, WrapErr};
+use flux_core::{AuctionSnapshot, BidDiscovery, BlockNumber, NoopHook, ValidationHook};
+
+use crate::provider::ChainContext;
+
+/// What to capture in a snapshot.
+#[derive(Debug, Clone)]
+pub struct SnapshotQuery {
+    pub auction: Address,
+    /// Whose bids to include; none when `None`.
+    pub owner: Option<Address>,
+    /// First block to scan for the owner's bids.
+    pub from_block: u64,
+    /// Initialized ticks to include above the clearing price.
+    pub levels: usize,
+}
+
+/// Reads the auction's config, state, tick book and the owner's bids.
+pub async fn snapshot(ctx: &ChainContext, query: &SnapshotQuery) -> Result<AuctionSnapshot> {
+    let bid_ids = match query.owner {
+        Some(owner) => {
+            BidDiscovery::new(ctx.provider.clone())
+                .from_block(BlockNumber::new(query.from_block))
+                .bids_by_owner(query.auction, owner)
+                .await?
+        }
+        None => Vec::new(),
+    };
+
+    let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+    let client = ctx
+        .auction_client(
+            query.auction,
+            query.owner.unwrap_or(Address::ZERO),
+            hook,
+            Vec::new(),
+        )
+        .await?;
+    Ok(client
+        .fetch_snapshot(query.owner, &bid_ids, query.levels)
+        .await?)
+}
+
+/// Loads a snapshot written by `snapshot`.
+pub fn read(path: &Path) -> Result<AuctionSnapshot> {
+    let json = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&json).wrap_err_with(|| format!("{} is not a snapshot", path.display()))
+}
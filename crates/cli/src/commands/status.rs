@@ -1,66 +1,145 @@
-use alloy::{
-    primitives::{Address, U256},
-    providers::{Provider, ProviderBuilder},
-};
+use std::sync::Arc;
+
+use alloy::{primitives::Address, providers::DynProvider};
 use eyre::Result;
-use flux_abi::{IAuctionStateLens, IContinuousClearingAuction};
+use flux_core::{
+    AuctionClient, AuctionConfig, AuctionProgress, Bid, BidId, BidLifecycleStatus, FillEstimate,
+    NoopHook, StateSnapshot, ValidationHook,
+};
+use futures::{Stream, StreamExt, future, stream};
 
-use crate::domain::{AuctionInfo, BidInfo, BidStatus, ExtraAuctionInfo};
+use crate::provider::ChainContext;
 
 #[derive(Debug, Clone)]
 pub struct StatusOutput {
-    pub auction: AuctionInfo,
-    pub bid: BidInfo,
-    pub bid_status: BidStatus,
+    pub auction: AuctionConfig,
+    pub state: StateSnapshot,
+    pub bid: Bid,
+    pub bid_status: BidLifecycleStatus,
+    pub fill: FillEstimate,
     pub current_block: u64,
+    /// Only filled in by [`status_batch`], since it walks every tick.
+    pub competition: Option<CompetitionStats>,
 }
 
-pub async fn status(
-    rpc_url: &str,
-    auction_addr: Address,
-    lens_addr: Address,
-    bid_id: U256,
-) -> Result<StatusOutput> {
-    // 1. Build provider
-    let provider = ProviderBuilder::new().connect(rpc_url).await?;
-
-    // 2. Instantiate contracts / interfaces
-    let auction = IContinuousClearingAuction::new(auction_addr, provider.clone());
-    let lens = IAuctionStateLens::new(lens_addr, provider.clone());
+/// How contested the auction is, and how much of it this bid is.
+#[derive(Debug, Clone, Copy)]
+pub struct CompetitionStats {
+    pub total_bids: u64,
+    /// Fraction (0 to 1) of the resting demand that is this bid's.
+    pub bid_demand_share: Option<f64>,
+    /// Herfindahl index (0 to 1) of demand across ticks.
+    pub demand_concentration: Option<f64>,
+}
 
-    // 3. Get latest auction state via lens (this also checkpoints under the hood)
-    let state = lens.state(auction_addr).call().await?;
+/// Auction-level state shared by every bid status lookup in that auction.
+#[derive(Debug, Clone, Copy)]
+pub struct AuctionSnapshot {
+    pub state: StateSnapshot,
+    pub current_block: u64,
+}
 
-    // 4. Get extra info not in AuctionState from the other interfaces
-    let start_block = auction.startBlock().call().await?;
-    let end_block = auction.endBlock().call().await?;
-    let claim_block = auction.claimBlock().call().await?; // this one *is* on IContinuousClearingAuction
+/// Auctions [`status_batch`] looks up at once unless told otherwise.
+pub const DEFAULT_STATUS_CONCURRENCY: usize = 8;
 
-    let token = auction.token().call().await?;
-    let currency_addr: Address = auction.currency().call().await?;
+/// Bids of one auction to look up, and the lens to read its state through.
+#[derive(Debug, Clone)]
+pub struct StatusRequest {
+    pub auction: Address,
+    pub lens: Address,
+    pub bid_ids: Vec<BidId>,
+}
 
-    let extra = ExtraAuctionInfo {
-        start_block,
-        end_block,
-        claim_block,
-        token: token.into(),
-        currency: currency_addr,
-    };
+/// Looks up every request's bids, up to `concurrency` auctions at a time,
+/// yielding each auction's statuses as soon as they are in rather than in
+/// request order. An auction's bids are read in one multicall against a
+/// single lens snapshot, and a failing auction does not stop the others.
+pub fn status_batch(
+    ctx: &ChainContext,
+    requests: Vec<StatusRequest>,
+    concurrency: usize,
+) -> impl Stream<Item = (Address, Result<Vec<StatusOutput>>)> + '_ {
+    stream::iter(requests)
+        .map(move |request| async move { (request.auction, auction_status(ctx, &request).await) })
+        .buffer_unordered(concurrency.max(1))
+}
 
-    let auction_info = AuctionInfo::from_lens_state(auction_addr, state, extra);
+async fn auction_status(ctx: &ChainContext, request: &StatusRequest) -> Result<Vec<StatusOutput>> {
+    let client = lens_client(ctx, request.auction, request.lens).await?;
+    let snapshot = fetch_auction(&client).await?;
 
-    // 5. Fetch bid and map to domain
-    let abi_bid = auction.bids(bid_id).call().await?;
-    let bid_info: BidInfo = (auction_addr, bid_id, abi_bid).into();
+    // Bids and the demand walk are independent reads of the same auction.
+    let (mut outputs, competition) = futures::try_join!(
+        fetch_bid_statuses(&client, &snapshot, &request.bid_ids),
+        async { Ok(client.fetch_competition().await?) },
+    )?;
+    for output in &mut outputs {
+        output.competition = Some(CompetitionStats {
+            total_bids: competition.total_bids,
+            bid_demand_share: competition.demand_share(output.bid.amount),
+            demand_concentration: competition.concentration,
+        });
+    }
+    Ok(outputs)
+}
 
-    // 6. Get current block and derive bid status
-    let current_block = provider.get_block_number().await?;
-    let bid_status = bid_info.derive_status(current_block, &auction_info);
+/// A read-only client for `auction` that reads its state through the lens.
+pub async fn lens_client(
+    ctx: &ChainContext,
+    auction: Address,
+    lens: Address,
+) -> Result<AuctionClient<DynProvider>> {
+    let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+    let client = ctx
+        .auction_client(auction, Address::ZERO, hook, Vec::new())
+        .await?;
+    Ok(client.with_lens(lens))
+}
 
-    Ok(StatusOutput {
-        auction: auction_info,
-        bid: bid_info,
-        bid_status,
-        current_block,
+pub async fn fetch_auction(client: &AuctionClient<DynProvider>) -> Result<AuctionSnapshot> {
+    // The lens checkpoints as of the block it is read at.
+    let state = client.fetch_state_via_lens().await?;
+    Ok(AuctionSnapshot {
+        state,
+        current_block: state.checkpoint.block.as_u64(),
     })
 }
+
+/// Statuses of `bid_ids` at the snapshot block, in the same order. The bids
+/// are read in one multicall and their fills estimated concurrently.
+pub async fn fetch_bid_statuses(
+    client: &AuctionClient<DynProvider>,
+    snapshot: &AuctionSnapshot,
+    bid_ids: &[BidId],
+) -> Result<Vec<StatusOutput>> {
+    if bid_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    // 1. Fetch the bids
+    let bids = client.fetch_bids(bid_ids).await?;
+
+    // 2. Derive each bid's status at the snapshot block
+    let state = snapshot.state;
+    let block = state.checkpoint.block;
+    let progress = AuctionProgress::new(
+        block,
+        client.config(),
+        state.checkpoint.clearing_price,
+        state.graduation,
+    );
+
+    // 3. Estimate fills and refunds from checkpoint accumulators
+    future::try_join_all(bids.into_iter().map(|bid| async move {
+        let fill = client.estimate_fill(&bid, &state, block).await?;
+        Ok(StatusOutput {
+            auction: client.config().clone(),
+            state,
+            bid_status: bid.lifecycle_status(&progress),
+            bid,
+            fill,
+            current_block: snapshot.current_block,
+            competition: None,
+        })
+    }))
+    .await
+}
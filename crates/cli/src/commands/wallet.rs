@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use alloy::primitives::Address;
+use eyre::Result;
+use flux_core::{
+    ChainProfile, FundingNeed, FundingStatus, FundingWarning, NoopHook, StrategyConfig, Units,
+    ValidationHook,
+};
+
+use crate::provider::ChainContext;
+
+/// What `owner` holds towards bidding in one auction, and what would stop
+/// the configured strategy.
+#[derive(Debug, Clone)]
+pub struct WalletOutput {
+    pub auction: Address,
+    pub funding: FundingStatus,
+    /// Decimals and symbol to show currency amounts in.
+    pub units: Units,
+    /// The chain's native currency, which pays for gas.
+    pub native_symbol: &'static str,
+    /// What the configured strategy commits; nothing without one.
+    pub need: FundingNeed,
+    pub warnings: Vec<FundingWarning>,
+}
+
+/// Reads `owner`'s currency and native balances and allowance to `auction`,
+/// and checks them against what `strategy` would spend.
+pub async fn wallet(
+    ctx: &ChainContext,
+    auction: Address,
+    owner: Address,
+    strategy: Option<&StrategyConfig>,
+) -> Result<WalletOutput> {
+    let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+    let client = ctx.auction_client(auction, owner, hook, Vec::new()).await?;
+    let (funding, units) = futures::try_join!(
+        async { Ok(client.fetch_funding(owner).await?) },
+        super::bid::units(ctx, client.config()),
+    )?;
+    let need = match strategy {
+        Some(strategy) => strategy.funding_need(&units)?,
+        None => FundingNeed::default(),
+    };
+    Ok(WalletOutput {
+        auction,
+        warnings: funding.warnings(&need),
+        funding,
+        units,
+        native_symbol: ChainProfile::for_chain(ctx.chain_id).native_symbol,
+        need,
+    })
+}
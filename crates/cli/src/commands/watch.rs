@@ -0,0 +1,36 @@
+use alloy::primitives::{Address, U256};
+use eyre::Result;
+use flux_core::{CurrencyAmount, DemandAlert, DemandAlertDetector, EventProducer};
+use futures::StreamExt;
+
+use crate::provider::ChainContext;
+
+/// Stream auction events and report every bid whose amount meets `threshold`.
+///
+/// Runs until the event stream ends or errors.
+pub async fn watch(
+    rpc_url: &str,
+    auction_addr: Address,
+    threshold: U256,
+    ignore_owner: Option<Address>,
+    mut on_alert: impl FnMut(DemandAlert),
+) -> Result<()> {
+    let ctx = ChainContext::read_only(rpc_url).await?;
+
+    let mut detector = DemandAlertDetector::new(CurrencyAmount::new(threshold));
+    if let Some(owner) = ignore_owner {
+        detector = detector.ignore_owner(owner);
+    }
+
+    let mut events = EventProducer::new(ctx.subscriptions, auction_addr)
+        .into_stream()
+        .await?;
+
+    while let Some(event) = events.next().await {
+        if let Some(alert) = detector.inspect(&event?) {
+            on_alert(alert);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use alloy::primitives::Address;
+use eyre::Result;
+use flux_core::{AuctionWatchlist, BlockProducer, WatchlistEvent};
+use futures::StreamExt;
+
+use crate::provider::ChainContext;
+
+/// Follow `auctions` and report every phase transition as it happens.
+///
+/// Runs until the block stream ends. An auction that fails to read is
+/// logged and retried at the next block.
+pub async fn watchlist(
+    ctx: &ChainContext,
+    auctions: &[Address],
+    mut on_event: impl FnMut(WatchlistEvent),
+) -> Result<()> {
+    let watchlist = AuctionWatchlist::new(ctx.provider.clone(), auctions).await?;
+    let blocks = BlockProducer::new(ctx.subscriptions.clone())
+        .into_stream()
+        .await?;
+
+    let mut events = watchlist.into_stream(blocks);
+    while let Some(event) = events.next().await {
+        match event {
+            Ok(event) => on_event(event),
+            Err(error) => tracing::warn!(%error, "failed to read a watched auction"),
+        }
+    }
+    Ok(())
+}
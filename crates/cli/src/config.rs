@@ -1,11 +1,16 @@
 // src/config.rs
+use alloy::primitives::Address;
+use flux_core::StrategyConfig;
 use serde::Deserialize;
 use std::{
+    collections::BTreeMap,
     env, fs,
     path::{Path, PathBuf},
 };
 use thiserror::Error;
 
+use crate::parse::{self, FieldError};
+
 pub const DEFAULT_CONFIG_PATH: &str = "bids.toml";
 const ENV_EXAMPLE: &str = include_str!("./.env.example");
 const PRIVATE_KEY_ENV: &str = "PRIVATE_KEY";
@@ -13,6 +18,19 @@ const PRIVATE_KEY_ENV: &str = "PRIVATE_KEY";
 #[derive(Debug, Deserialize, PartialEq)]
 pub struct BidsConfig {
     pub bid: BidConfig,
+    #[serde(default)]
+    pub portfolio: PortfolioConfig,
+    #[serde(default)]
+    pub cold_storage: ColdStorageConfig,
+    /// Auctions that `--auction` accepts by name instead of address.
+    #[serde(default)]
+    pub auctions: BTreeMap<String, AuctionEntry>,
+    /// Built-in strategy for `run` to drive before settling.
+    pub strategy: Option<StrategyConfig>,
+    #[serde(default)]
+    pub schedule: ScheduleConfig,
+    #[serde(default)]
+    pub rpc: RpcConfig,
 }
 
 #[derive(Debug, Deserialize, PartialEq)]
@@ -22,6 +40,90 @@ pub struct BidConfig {
     pub owner: Option<String>,
 }
 
+/// Auctions tracked by `portfolio` when none are passed on the command line,
+/// by address or `[auctions]` name.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+pub struct PortfolioConfig {
+    #[serde(default)]
+    pub auctions: Vec<String>,
+    pub lens: Option<String>,
+    pub factory: Option<String>,
+}
+
+/// When `run` starts the `[strategy]`, in wall clock terms.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+pub struct ScheduleConfig {
+    /// `HH:MM UTC`, `@<unix seconds>`, `<duration> before end` or
+    /// `<duration> after start`.
+    pub start_at: Option<String>,
+}
+
+/// Endpoints used when `--rpc-url` is not given. All must serve the same
+/// chain; calls fail over between them in order.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+pub struct RpcConfig {
+    #[serde(default)]
+    pub urls: Vec<String>,
+}
+
+/// Addresses `claim --sweep-to` may send claimed tokens to.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+pub struct ColdStorageConfig {
+    #[serde(default)]
+    pub allowlist: Vec<String>,
+}
+
+/// An `[auctions.<name>]` entry, as written by `auctions add`.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct AuctionEntry {
+    pub chain_id: u64,
+    pub address: String,
+    pub lens: Option<String>,
+    pub token_decimals: u8,
+}
+
+impl AuctionEntry {
+    pub fn address(&self, name: &str) -> Result<Address, FieldError> {
+        parse::address(&self.address).map_err(|e| e.in_field(format!("[auctions.{name}].address")))
+    }
+
+    pub fn lens_address(&self, name: &str) -> Result<Option<Address>, FieldError> {
+        self.lens
+            .as_deref()
+            .map(|lens| {
+                parse::address(lens).map_err(|e| e.in_field(format!("[auctions.{name}].lens")))
+            })
+            .transpose()
+    }
+}
+
+impl ColdStorageConfig {
+    pub fn allowlist_addresses(&self) -> Result<Vec<Address>, FieldError> {
+        self.allowlist
+            .iter()
+            .map(|address| {
+                parse::address(address).map_err(|e| e.in_field("[cold_storage].allowlist"))
+            })
+            .collect()
+    }
+}
+
+impl PortfolioConfig {
+    pub fn lens_address(&self) -> Result<Option<Address>, FieldError> {
+        self.lens
+            .as_deref()
+            .map(|lens| parse::address(lens).map_err(|e| e.in_field("[portfolio].lens")))
+            .transpose()
+    }
+
+    pub fn factory_address(&self) -> Result<Option<Address>, FieldError> {
+        self.factory
+            .as_deref()
+            .map(|factory| parse::address(factory).map_err(|e| e.in_field("[portfolio].factory")))
+            .transpose()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Bid {
     pub max_bid: f64,
@@ -48,6 +150,24 @@ pub enum ConfigError {
         path: PathBuf,
         source: toml::de::Error,
     },
+    #[error("failed to edit toml at {path}: {source}")]
+    Edit {
+        path: PathBuf,
+        source: Box<toml_edit::TomlError>,
+    },
+    #[error("failed to write config at {path}: {source}")]
+    Write {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error(
+        "`{name}` is not a valid auction name: use a letter followed by letters, digits, `-` or `_`"
+    )]
+    InvalidAuctionName { name: String },
+    #[error("{path} already has an auction named `{name}`")]
+    DuplicateAuction { name: String, path: PathBuf },
+    #[error("`auctions` in {path} is not a table")]
+    AuctionsNotTable { path: PathBuf },
 }
 
 #[derive(Debug, Error, PartialEq)]
@@ -70,6 +190,57 @@ pub fn load_config(path: impl AsRef<Path>) -> Result<BidsConfig, ConfigError> {
     Ok(config)
 }
 
+/// Adds `entry` to the `[auctions]` registry of the config at `path` as
+/// `[auctions.<name>]`, keeping the rest of the file, comments included, as is.
+pub fn add_auction(path: &Path, name: &str, entry: &AuctionEntry) -> Result<(), ConfigError> {
+    if !parse::is_auction_name(name) {
+        return Err(ConfigError::InvalidAuctionName {
+            name: name.to_string(),
+        });
+    }
+    let contents = fs::read_to_string(path).map_err(|source| ConfigError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let mut document: toml_edit::DocumentMut =
+        contents.parse().map_err(|source| ConfigError::Edit {
+            path: path.to_path_buf(),
+            source: Box::new(source),
+        })?;
+
+    let auctions = document
+        .entry("auctions")
+        .or_insert_with(|| {
+            let mut table = toml_edit::Table::new();
+            table.set_implicit(true);
+            toml_edit::Item::Table(table)
+        })
+        .as_table_mut()
+        .ok_or_else(|| ConfigError::AuctionsNotTable {
+            path: path.to_path_buf(),
+        })?;
+    if auctions.contains_key(name) {
+        return Err(ConfigError::DuplicateAuction {
+            name: name.to_string(),
+            path: path.to_path_buf(),
+        });
+    }
+
+    let mut table = toml_edit::Table::new();
+    table["chain_id"] = toml_edit::value(entry.chain_id as i64);
+    table["address"] = toml_edit::value(&entry.address);
+    if let Some(lens) = &entry.lens {
+        table["lens"] = toml_edit::value(lens);
+    }
+    table["token_decimals"] = toml_edit::value(i64::from(entry.token_decimals));
+    auctions.insert(name, toml_edit::Item::Table(table));
+
+    fs::write(path, document.to_string()).map_err(|source| ConfigError::Write {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
 pub fn load_default_config() -> Result<BidsConfig, ConfigError> {
     load_config(DEFAULT_CONFIG_PATH)
 }
@@ -131,6 +302,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn added_auctions_load_back_from_the_registry() {
+        let example = Path::new(env!("CARGO_MANIFEST_DIR")).join("bids.example.toml");
+        let path = env::temp_dir().join(format!("flux-registry-{}.toml", std::process::id()));
+        fs::copy(&example, &path).unwrap();
+
+        let entry = AuctionEntry {
+            chain_id: 8453,
+            address: "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed".into(),
+            lens: None,
+            token_decimals: 18,
+        };
+        add_auction(&path, "mytoken-launch", &entry).unwrap();
+        assert!(matches!(
+            add_auction(&path, "mytoken-launch", &entry),
+            Err(ConfigError::DuplicateAuction { .. })
+        ));
+
+        let config = load_config(&path).unwrap();
+        assert_eq!(config.auctions.get("mytoken-launch"), Some(&entry));
+        assert_eq!(config.bid.max_bid, 10.5);
+        assert!(
+            fs::read_to_string(&path)
+                .unwrap()
+                .contains("# lens = \"0x...\"")
+        );
+
+        let _ = fs::remove_file(&path);
+    }
+
     #[test]
     fn resolves_owner_from_env_when_not_in_config() {
         let mut config = BidsConfig {
@@ -139,6 +340,12 @@ mod tests {
                 amount: 1.0,
                 owner: None,
             },
+            portfolio: PortfolioConfig::default(),
+            cold_storage: ColdStorageConfig::default(),
+            auctions: BTreeMap::new(),
+            strategy: None,
+            schedule: ScheduleConfig::default(),
+            rpc: RpcConfig::default(),
         };
         // SAFETY: test process controls its own environment and uses a unique key.
         unsafe { env::set_var(PRIVATE_KEY_ENV, "0xfromenv") };
@@ -0,0 +1,205 @@
+// src/config_cache.rs
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use alloy::primitives::{Address, U256};
+use flux_core::{
+    AuctionConfig, BlockNumber, CurrencyAddr, HookAddr, Price, TickSpacing, TokenAddr, TokenAmount,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[error("failed to access cached auction config at {path}: {source}")]
+pub struct ConfigCacheError {
+    path: PathBuf,
+    #[source]
+    source: io::Error,
+}
+
+/// One cache file: the auction's immutable parameters.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredConfig {
+    start_block: u64,
+    end_block: u64,
+    claim_block: u64,
+    total_supply: U256,
+    tick_spacing: U256,
+    floor_price: U256,
+    max_bid_price: U256,
+    currency: Address,
+    token: Address,
+    validation_hook: Address,
+}
+
+/// Auction configs kept on disk per (chain, auction), so repeated commands
+/// against the same auction skip fetching them.
+///
+/// A deployed auction's config never changes, so entries are only dropped by
+/// [`clear`](Self::clear); a chain that was reset can leave a stale one.
+#[derive(Debug, Clone)]
+pub struct ConfigCache {
+    dir: PathBuf,
+}
+
+impl ConfigCache {
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The cached config, or `None` if there is none or it cannot be read.
+    pub fn load(
+        &self,
+        chain_id: u64,
+        auction: Address,
+    ) -> Result<Option<AuctionConfig>, ConfigCacheError> {
+        let path = self.path(chain_id, auction);
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(source) => return Err(ConfigCacheError { path, source }),
+        };
+        let stored: StoredConfig = match serde_json::from_str(&contents) {
+            Ok(stored) => stored,
+            Err(error) => {
+                tracing::warn!(path = %path.display(), %error, "ignoring unreadable cached config");
+                return Ok(None);
+            }
+        };
+
+        Ok(Some(AuctionConfig {
+            address: auction,
+            start_block: BlockNumber::new(stored.start_block),
+            end_block: BlockNumber::new(stored.end_block),
+            claim_block: BlockNumber::new(stored.claim_block),
+            total_supply: TokenAmount::new(stored.total_supply),
+            tick_spacing: TickSpacing::new(stored.tick_spacing),
+            floor_price: Price::new(stored.floor_price),
+            max_bid_price: Price::new(stored.max_bid_price),
+            currency: CurrencyAddr::new(stored.currency),
+            token: TokenAddr::new(stored.token),
+            validation_hook: HookAddr::new(stored.validation_hook),
+        }))
+    }
+
+    pub fn store(&self, chain_id: u64, config: &AuctionConfig) -> Result<(), ConfigCacheError> {
+        let stored = StoredConfig {
+            start_block: config.start_block.as_u64(),
+            end_block: config.end_block.as_u64(),
+            claim_block: config.claim_block.as_u64(),
+            total_supply: config.total_supply.as_u256(),
+            tick_spacing: config.tick_spacing.as_u256(),
+            floor_price: config.floor_price.as_u256(),
+            max_bid_price: config.max_bid_price.as_u256(),
+            currency: config.currency.as_address(),
+            token: config.token.as_address(),
+            validation_hook: config.validation_hook.as_address(),
+        };
+        let json = serde_json::to_string_pretty(&stored).expect("auction config serializes");
+
+        // Write beside the entry and rename, so a crash never leaves it half written.
+        let path = self.path(chain_id, config.address);
+        let tmp = path.with_extension("json.tmp");
+        fs::create_dir_all(&self.dir)
+            .and_then(|()| fs::write(&tmp, json))
+            .and_then(|()| fs::rename(&tmp, &path))
+            .map_err(|source| ConfigCacheError { path, source })
+    }
+
+    /// Drops the cached configs of `auction` on every chain, or every cached
+    /// config when `None`. Returns how many were removed.
+    pub fn clear(&self, auction: Option<Address>) -> Result<usize, ConfigCacheError> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(source) => return Err(self.io_error(source)),
+        };
+        let suffix = auction.map(|auction| format!("-{auction:#x}.json"));
+
+        let mut removed = 0;
+        for entry in entries {
+            let path = entry.map_err(|source| self.io_error(source))?.path();
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let matches = match &suffix {
+                Some(suffix) => name.ends_with(suffix.as_str()),
+                None => name.ends_with(".json"),
+            };
+            if matches {
+                fs::remove_file(&path).map_err(|source| ConfigCacheError { path, source })?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    fn path(&self, chain_id: u64, auction: Address) -> PathBuf {
+        self.dir.join(format!("{chain_id}-{auction:#x}.json"))
+    }
+
+    fn io_error(&self, source: io::Error) -> ConfigCacheError {
+        ConfigCacheError {
+            path: self.dir.clone(),
+            source,
+        }
+    }
+}
+
+/// Default directory for cached auction configs.
+pub fn default_cache_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("flux")
+        .join("configs")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_and_clears_per_auction() {
+        let dir =
+            std::env::temp_dir().join(format!("flux-config-cache-test-{}", std::process::id()));
+        let cache = ConfigCache::new(&dir);
+        let config = |address: Address| AuctionConfig {
+            address,
+            start_block: BlockNumber::new(10),
+            end_block: BlockNumber::new(110),
+            claim_block: BlockNumber::new(120),
+            total_supply: TokenAmount::new(U256::from(1_000_000)),
+            tick_spacing: TickSpacing::new(U256::from(100)),
+            floor_price: Price::new(U256::from(1_000)),
+            max_bid_price: Price::new(U256::MAX),
+            currency: CurrencyAddr::new(Address::ZERO),
+            token: TokenAddr::new(Address::repeat_byte(0x11)),
+            validation_hook: HookAddr::new(Address::ZERO),
+        };
+        let (a, b) = (Address::repeat_byte(0xaa), Address::repeat_byte(0xbb));
+        assert!(cache.load(1, a).unwrap().is_none());
+
+        cache.store(1, &config(a)).unwrap();
+        cache.store(8453, &config(a)).unwrap();
+        cache.store(1, &config(b)).unwrap();
+        let loaded = cache.load(1, a).unwrap().unwrap();
+        assert_eq!(loaded.end_block, BlockNumber::new(110));
+        assert_eq!(loaded.max_bid_price, Price::new(U256::MAX));
+        assert!(cache.load(10, a).unwrap().is_none());
+
+        assert_eq!(cache.clear(Some(a)).unwrap(), 2);
+        assert!(cache.load(1, a).unwrap().is_none());
+        assert!(cache.load(1, b).unwrap().is_some());
+        assert_eq!(cache.clear(None).unwrap(), 1);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
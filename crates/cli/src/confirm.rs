@@ -0,0 +1,185 @@
+// src/confirm.rs
+use std::{
+    fmt,
+    io::{self, BufRead, IsTerminal, Write},
+};
+
+use alloy::primitives::{
+    U256,
+    utils::{format_ether, format_units},
+};
+use flux_core::DisplayAmount;
+
+use crate::commands::bid::NATIVE_DECIMALS;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ConfirmError {
+    #[error("cancelled")]
+    Declined,
+    #[error("stdin is not a terminal; pass --yes to send without confirming")]
+    NotInteractive,
+    #[error("failed to read the answer: {0}")]
+    Prompt(#[from] io::Error),
+}
+
+/// What a mutating command is about to send, shown before asking to go
+/// ahead.
+#[derive(Debug, Clone)]
+pub struct ActionSummary {
+    action: String,
+    details: Vec<(String, String)>,
+    /// Native currency sent along with the call, in wei.
+    value: U256,
+    gas: Option<GasCost>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct GasCost {
+    gas: u64,
+    gas_price: u128,
+    native_symbol: &'static str,
+}
+
+impl ActionSummary {
+    /// `action` is the decoded call, e.g. `exitBid(7)`.
+    pub fn new(action: impl Into<String>) -> Self {
+        Self {
+            action: action.into(),
+            details: Vec::new(),
+            value: U256::ZERO,
+            gas: None,
+        }
+    }
+
+    pub fn with_detail(mut self, label: impl Into<String>, value: impl fmt::Display) -> Self {
+        self.details.push((label.into(), value.to_string()));
+        self
+    }
+
+    /// Native currency the call sends as `msg.value`, shown with the total
+    /// it costs alongside the gas.
+    pub fn with_value(mut self, wei: U256) -> Self {
+        self.value = wei;
+        self
+    }
+
+    pub fn with_gas(mut self, gas: u64, gas_price: u128, native_symbol: &'static str) -> Self {
+        self.gas = Some(GasCost {
+            gas,
+            gas_price,
+            native_symbol,
+        });
+        self
+    }
+}
+
+/// `amount` raw units in whole units of `decimals`, followed by `symbol`.
+pub fn human(amount: U256, decimals: u8, symbol: &str) -> String {
+    DisplayAmount::new(amount, decimals, symbol).to_string()
+}
+
+impl fmt::Display for ActionSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "About to send {}", self.action)?;
+        let width = self
+            .details
+            .iter()
+            .map(|(label, _)| label.len())
+            .chain(self.gas.map(|_| "gas".len()))
+            .chain((!self.value.is_zero()).then_some("value".len()))
+            .max()
+            .unwrap_or_default();
+        for (label, value) in &self.details {
+            writeln!(f, "  {label:<width$}  {value}")?;
+        }
+        if let Some(cost) = self.gas {
+            let fee = U256::from(cost.gas) * U256::from(cost.gas_price);
+            if !self.value.is_zero() {
+                writeln!(
+                    f,
+                    "  {:<width$}  {} (~{} with gas)",
+                    "value",
+                    human(self.value, NATIVE_DECIMALS, cost.native_symbol),
+                    human(self.value + fee, NATIVE_DECIMALS, cost.native_symbol)
+                )?;
+            }
+            writeln!(
+                f,
+                "  {:<width$}  ~{} (~{} {} at {} gwei)",
+                "gas",
+                cost.gas,
+                format_ether(fee),
+                cost.native_symbol,
+                format_units(U256::from(cost.gas_price), "gwei").unwrap_or_default()
+            )?;
+        } else if !self.value.is_zero() {
+            writeln!(f, "  {:<width$}  {} wei", "value", self.value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Asks before a command sends anything, unless `--yes` was passed.
+#[derive(Debug, Clone, Copy)]
+pub struct Confirmation {
+    assume_yes: bool,
+}
+
+impl Confirmation {
+    pub fn new(assume_yes: bool) -> Self {
+        Self { assume_yes }
+    }
+
+    /// Shows `summary` on stderr and waits for a yes. Without a terminal to
+    /// ask on, only `--yes` goes ahead.
+    pub fn confirm(&self, summary: &ActionSummary) -> Result<(), ConfirmError> {
+        eprint!("{summary}");
+        if self.assume_yes {
+            return Ok(());
+        }
+        let stdin = io::stdin();
+        if !stdin.is_terminal() {
+            return Err(ConfirmError::NotInteractive);
+        }
+        eprint!("Proceed? [y/N] ");
+        io::stderr().flush()?;
+        let mut answer = String::new();
+        stdin.lock().read_line(&mut answer)?;
+        if is_yes(&answer) {
+            Ok(())
+        } else {
+            Err(ConfirmError::Declined)
+        }
+    }
+}
+
+fn is_yes(answer: &str) -> bool {
+    matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_lists_details_and_gas_cost() {
+        let summary = ActionSummary::new("exitBid(7)")
+            .with_detail("refund", human(U256::from(1_500_000), 6, "USDC"))
+            .with_gas(100_000, 2_000_000_000, "ETH");
+        assert_eq!(
+            summary.to_string(),
+            "About to send exitBid(7)\n  refund  1.5 USDC\n  gas     ~100000 (~0.000200000000000000 ETH at 2.000000000 gwei)\n"
+        );
+
+        let bid = ActionSummary::new("submitBid")
+            .with_value(U256::from(10).pow(U256::from(18)))
+            .with_gas(100_000, 2_000_000_000, "ETH");
+        assert!(
+            bid.to_string()
+                .contains("  value  1 ETH (~1.0002 ETH with gas)\n")
+        );
+        assert!(is_yes(" Yes\n"));
+        assert!(!is_yes("\n"));
+    }
+}
@@ -1,9 +0,0 @@
-pub mod auction;
-pub mod bid;
-pub mod currency;
-pub mod price;
-
-pub use auction::{AuctionInfo, AuctionPhase, ExtraAuctionInfo};
-pub use bid::{BidInfo, BidStatus};
-pub use currency::CurrencyInfo;
-pub use price::{PriceQ96, Q96, q96_from_ratio, ratio_from_q96};
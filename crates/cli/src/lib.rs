@@ -1,5 +1,16 @@
 // src/lib.rs
 
+pub mod amounts;
+pub mod audit;
 pub mod commands;
 pub mod config;
-pub mod domain;
+pub mod config_cache;
+pub mod confirm;
+pub mod lock;
+pub mod logging;
+pub mod parse;
+pub mod provider;
+pub mod secrets;
+pub mod settings;
+pub mod store;
+pub mod ui;
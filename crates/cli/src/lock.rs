@@ -0,0 +1,158 @@
+// src/lock.rs
+use std::{
+    fmt,
+    fs::{self, File, OpenOptions, TryLockError},
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use alloy::primitives::Address;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// What `run` does when another process already holds the campaign lock.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum IfLocked {
+    /// Report the holder and exit with an error
+    #[default]
+    Exit,
+    /// Follow the auction without sending transactions
+    ReadOnly,
+}
+
+/// Who holds a campaign lock, as written into the lockfile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockHolder {
+    pub pid: u32,
+    /// Unix timestamp (seconds) the lock was taken at.
+    pub since: u64,
+}
+
+impl fmt::Display for LockHolder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "pid {} (since unix time {})", self.pid, self.since)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum LockError {
+    #[error("failed to open lockfile {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("{path} is locked by {}", holder.as_ref().map_or("an unknown process".to_string(), ToString::to_string))]
+    Held {
+        path: PathBuf,
+        holder: Option<LockHolder>,
+    },
+}
+
+/// Exclusive advisory lock on one (chain, auction, owner) campaign.
+///
+/// The OS releases the lock when the process exits, so a crashed daemon never
+/// leaves a stale lock behind; the file itself is left in place.
+#[derive(Debug)]
+pub struct CampaignLock {
+    _file: File,
+    path: PathBuf,
+}
+
+impl CampaignLock {
+    /// Takes the lock for the campaign under `dir`, or reports who holds it.
+    pub fn acquire(
+        dir: &Path,
+        chain_id: u64,
+        auction: Address,
+        owner: Address,
+    ) -> Result<Self, LockError> {
+        let path = dir.join(format!("{chain_id}-{auction:#x}-{owner:#x}.lock"));
+        let io_error = |source| LockError::Io {
+            path: path.clone(),
+            source,
+        };
+
+        fs::create_dir_all(dir).map_err(io_error)?;
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(io_error)?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let mut contents = String::new();
+                let holder = file
+                    .read_to_string(&mut contents)
+                    .ok()
+                    .and_then(|_| serde_json::from_str(&contents).ok());
+                return Err(LockError::Held { path, holder });
+            }
+            Err(TryLockError::Error(source)) => return Err(io_error(source)),
+        }
+
+        let holder = LockHolder {
+            pid: std::process::id(),
+            since: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+        };
+        file.set_len(0)
+            .and_then(|()| file.seek(SeekFrom::Start(0)))
+            .and_then(|_| {
+                file.write_all(
+                    serde_json::to_string(&holder)
+                        .expect("holder serializes")
+                        .as_bytes(),
+                )
+            })
+            .map_err(io_error)?;
+
+        Ok(Self { _file: file, path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Default directory for campaign lockfiles.
+pub fn default_lock_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("flux")
+        .join("locks")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_acquire_reports_holder() {
+        let dir = std::env::temp_dir().join(format!("flux-lock-test-{}", std::process::id()));
+        let auction = Address::repeat_byte(0xaa);
+        let owner = Address::repeat_byte(0xbb);
+
+        let lock = CampaignLock::acquire(&dir, 1, auction, owner).unwrap();
+        match CampaignLock::acquire(&dir, 1, auction, owner) {
+            Err(LockError::Held { holder, .. }) => {
+                assert_eq!(holder.unwrap().pid, std::process::id());
+            }
+            other => panic!("expected the lock to be held, got {other:?}"),
+        }
+
+        // Other campaigns are unaffected.
+        CampaignLock::acquire(&dir, 2, auction, owner).unwrap();
+
+        drop(lock);
+        CampaignLock::acquire(&dir, 1, auction, owner).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
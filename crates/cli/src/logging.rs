@@ -0,0 +1,29 @@
+// src/logging.rs
+use clap::ValueEnum;
+use tracing_subscriber::{EnvFilter, fmt};
+
+/// Output format for log lines written to stderr.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per line, for log shippers
+    Json,
+}
+
+/// Install the global subscriber. `RUST_LOG` takes precedence over `level`
+/// when set.
+pub fn init(level: &str, format: LogFormat) -> eyre::Result<()> {
+    let filter = match EnvFilter::try_from_default_env() {
+        Ok(filter) => filter,
+        Err(_) => EnvFilter::try_new(level)?,
+    };
+
+    let builder = fmt().with_env_filter(filter).with_writer(std::io::stderr);
+    match format {
+        LogFormat::Text => builder.try_init(),
+        LogFormat::Json => builder.json().with_current_span(true).try_init(),
+    }
+    .map_err(|error| eyre::eyre!(error))
+}
@@ -1,35 +1,244 @@
-use std::path::PathBuf;
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    io::Write,
+    net::SocketAddr,
+    path::PathBuf,
+    sync::Arc,
+};
 
-use alloy::primitives::{Address, U256};
+use alloy::{
+    primitives::{Address, Bytes, U256},
+    providers::{Provider, ProviderBuilder},
+    signers::local::PrivateKeySigner,
+};
 use clap::{Args, Parser, Subcommand};
+use flux_core::{
+    AnvilFork, AuctionParameters, BacktestReport, BidAnalytics, BidId, BlockNumber, ClaimGasLimit,
+    ColdStorage, CurrencyAddr, CurrencyAmount, ExitPreview, FundingWarning, HookAddr,
+    OrchestratorResult, Paymaster, Price, PrivateRelay, ShadowFork, SimulatedFill, SizeLimit,
+    SmartAccount, StalenessGuard, StepSpec, StrategyConfig, TickSpacing, TimeTarget, TokenAmount,
+};
+use futures::StreamExt;
+use zeroize::Zeroizing;
 
 use flux_cli::{
-    commands::status as status_cmd,
-    config::{BidOverrides, BidsConfig, DEFAULT_CONFIG_PATH, load_config, resolve_bid},
+    amounts::Amounts,
+    audit,
+    commands::{
+        analyze as analyze_cmd,
+        attest::{self as attest_cmd, AttestQuery},
+        auctions as auctions_cmd,
+        bid::{self as bid_cmd, BidRequest},
+        checkpoint as checkpoint_cmd,
+        claim::{self as claim_cmd, ColdStorageMove, ColdStorageTarget},
+        create_auction::{self as create_auction_cmd, CreateAuctionRequest},
+        daemon::{self as daemon_cmd, DaemonOptions},
+        depth::{self as depth_cmd, DepthOutput},
+        exit as exit_cmd,
+        ladder::{self as ladder_cmd, LadderPreview, LadderQuery},
+        market::{self as market_cmd, MarketOutput, MarketQuery},
+        portfolio::{self as portfolio_cmd, PortfolioOutput, PortfolioQuery},
+        quote::{self as quote_cmd, QuoteOutput, QuoteQuery},
+        replay::{self as replay_cmd, ReplayOptions},
+        report::{self as report_cmd, ReportFormat, ReportQuery},
+        run::{self as run_cmd, PrivateIntent, RunOptions},
+        snapshot::{self as snapshot_cmd, SnapshotQuery},
+        status::{self as status_cmd, StatusRequest},
+        wallet::{self as wallet_cmd, WalletOutput},
+        watch as watch_cmd, watchlist as watchlist_cmd,
+    },
+    config::{self as bids_config, BidOverrides, BidsConfig, resolve_bid},
+    config_cache::{self, ConfigCache},
+    confirm::{ConfirmError, Confirmation},
+    lock::{self, IfLocked},
+    logging::{self, LogFormat},
+    parse::{self, AuctionRef, BidRef},
+    provider::ChainContext,
+    secrets,
+    settings::Settings,
+    store,
+    ui::tui::{self, PreparedBid, TuiOptions},
 };
 
 #[derive(Debug, Parser)]
 #[command(name = "flux-cli", about = "CCA bidding CLI", version)]
 struct Cli {
-    /// Path to the bids configuration file
-    #[arg(short, long, default_value = DEFAULT_CONFIG_PATH, value_name = "FILE")]
-    config: PathBuf,
+    /// Path to the bids configuration file [default: bids.toml]
+    ///
+    /// Only read by commands that need it; on-chain commands run from flags alone.
+    #[arg(short, long, value_name = "FILE")]
+    config: Option<PathBuf>,
 
     /// RPC URL for the target chain (only required for on-chain commands like `status`)
+    ///
+    /// A comma-separated list of endpoints fails over between them; falls back to
+    /// `[rpc] urls` in the config file.
     #[arg(long, env = "CCA_RPC_URL", value_name = "URL")]
     rpc_url: Option<String>,
 
+    /// Private key used to sign transactions (only required for commands that send them)
+    #[arg(long, env = "PRIVATE_KEY", hide_env_values = true, value_name = "KEY")]
+    private_key: Option<String>,
+
+    /// Log verbosity (`error`, `warn`, `info`, `debug`, `trace`, or a filter directive)
+    #[arg(long, global = true, default_value = "info", value_name = "LEVEL")]
+    log_level: String,
+
+    /// Log output format
+    #[arg(long, global = true, value_enum, default_value_t = LogFormat::Text)]
+    log_format: LogFormat,
+
+    /// Send transactions without asking to confirm their summary
+    #[arg(long, short = 'y', global = true)]
+    yes: bool,
+
+    /// Show raw integer amounts and Q96 prices instead of whole units with symbols
+    #[arg(long, global = true)]
+    raw: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
 
 #[derive(Debug, Subcommand)]
 enum Commands {
-    /// Submit or preview a bid from config (local only for now)
+    /// Preview a bid from config, or submit it to `--auction`
     Bids(BidArgs),
 
     /// Show on-chain status of a bid in an auction
     Status(StatusArgs),
+
+    /// Show an owner's currency and native balances and allowance to an auction, with what would block the configured strategy
+    Wallet(WalletArgs),
+
+    /// Watch an auction for large bids entering the tick book
+    Watch(WatchArgs),
+
+    /// Follow several auctions and report when they receive tokens, start, graduate, end and become claimable
+    Watchlist(WatchlistArgs),
+
+    /// List all of an owner's bids across tracked auctions
+    Portfolio(PortfolioArgs),
+
+    /// Claim filled tokens from exited bids
+    Claim(ClaimArgs),
+
+    /// Exit a bid and collect its refund
+    Exit(ExitArgs),
+
+    /// Advance the auction's checkpoint to the current block
+    Checkpoint(CheckpointArgs),
+
+    /// Create an auction through the factory, after checking its parameters the way the contracts will
+    CreateAuction(CreateAuctionArgs),
+
+    /// Show currency demand per tick above the clearing price
+    Depth(DepthArgs),
+
+    /// Read every bid of an auction and show who holds the demand and at which prices
+    Market(MarketArgs),
+
+    /// Size a bid to buy a target number of tokens at the projected clearing price
+    Quote(QuoteArgs),
+
+    /// Follow the chain and settle the signer's bids: exit after the end, claim when claimable
+    Run(RunArgs),
+
+    /// Preview a bid ladder against the auction's tick demand, and optionally submit it
+    Ladder(LadderArgs),
+
+    /// Export a signed attestation of an ended auction's final price and fills
+    Attest(AttestArgs),
+
+    /// Show what an exited bid actually paid: realized price, fill ratio and holding period
+    Analyze(AnalyzeArgs),
+
+    /// Post-mortem of an ended auction: clearing, supply per step, top ticks and your bids
+    Report(ReportArgs),
+
+    /// Save an auction's config, checkpoint, phase, tick book and your bids to a JSON file
+    Snapshot(SnapshotArgs),
+
+    /// Show what changed between two `snapshot` files
+    Diff(DiffArgs),
+
+    /// Live dashboard of an auction and your bids; with a signer, submit or exit bids from it
+    Tui(TuiArgs),
+
+    /// Like `run`, in the background, with a JSON-RPC API to check on it, pause it or queue intents
+    Daemon(DaemonArgs),
+
+    /// Replay a `run --record` file through the `[strategy]` and show where its decisions differ
+    Replay(ReplayArgs),
+
+    /// Forget cached auction configs so the next command fetches them again
+    ClearCache(ClearCacheArgs),
+
+    /// Work with the audit trail written by `run --activity-log`
+    #[command(subcommand)]
+    Audit(AuditCommand),
+
+    /// Encrypt a private key for the config, or check what a secret reference holds
+    #[command(subcommand)]
+    Secrets(SecretsCommand),
+
+    /// Manage the `[auctions]` registry of names accepted by `--auction`
+    #[command(subcommand)]
+    Auctions(AuctionsCommand),
+}
+
+#[derive(Debug, Subcommand)]
+enum AuctionsCommand {
+    /// Fetch an auction's chain and token decimals and save it in the config under NAME
+    Add {
+        /// Name to use for `--auction` (a letter, then letters, digits, `-` or `_`)
+        #[arg(value_name = "NAME")]
+        name: String,
+        /// Address of the ContinuousClearingAuction contract
+        #[arg(long, value_name = "ADDRESS", value_parser = parse::address)]
+        address: Address,
+        /// Address of the AuctionStateLens contract to use with this auction
+        #[arg(long, value_name = "ADDRESS", value_parser = parse::address)]
+        lens: Option<Address>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum AuditCommand {
+    /// Convert an activity log to CSV
+    Export(ExportActivityArgs),
+}
+
+#[derive(Debug, Subcommand)]
+enum SecretsCommand {
+    /// Prompt for a secret and print an `encrypted:` reference to it (passphrase from FLUX_PASSPHRASE or a prompt)
+    Encrypt {
+        /// Store the secret in the OS keyring under this name and print a `keyring:` reference instead
+        #[arg(long, value_name = "NAME")]
+        keyring: Option<String>,
+    },
+    /// Print the plaintext of an `encrypted:` or `keyring:` reference
+    Decrypt {
+        #[arg(value_name = "REFERENCE")]
+        reference: String,
+    },
+}
+
+#[derive(Debug, Args)]
+struct ExportActivityArgs {
+    /// Activity log written by `run --activity-log`
+    #[arg(long, value_name = "PATH")]
+    log: PathBuf,
+    /// Write the CSV here instead of stdout
+    #[arg(long, value_name = "PATH")]
+    out: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct ClearCacheArgs {
+    /// Only forget this auction's config (on every chain)
+    #[arg(long, value_name = "AUCTION", value_parser = parse::auction)]
+    auction: Option<AuctionRef>,
 }
 
 #[derive(Debug, Args)]
@@ -43,88 +252,2315 @@ struct BidArgs {
     /// Bid owner/private key
     #[arg(long, value_name = "KEY")]
     owner: Option<String>,
+    /// Submit the bid to this auction (address or `[auctions]` name), signed by `PRIVATE_KEY`, instead of only previewing it
+    #[arg(long, value_name = "AUCTION", value_parser = parse::auction)]
+    auction: Option<AuctionRef>,
+    /// Use the hinted `submitBid` with this `prevTickPrice` (Q96) instead of choosing automatically
+    #[arg(long, value_name = "PRICE", value_parser = parse::u256, requires = "auction")]
+    prev_tick_price: Option<U256>,
+    /// Abort instead of sending if the clearing price rose by more than this (Q96) since the bid was prepared
+    #[arg(long, value_name = "PRICE", value_parser = parse::u256, requires = "auction")]
+    max_clearing_drift: Option<U256>,
+    /// Place the bid for this address; the signer pays, but refunds and tokens go to it
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address, requires = "auction")]
+    beneficiary: Option<Address>,
+    /// Record the bid with this tag, e.g. `campaign=launch` (repeatable)
+    #[arg(long = "tag", value_name = "TAG", value_parser = parse::tag, requires = "auction")]
+    tags: Vec<String>,
+    /// Directory for tracked-bid stores [default: <data dir>/flux/bids]
+    #[arg(long, value_name = "DIR")]
+    bids_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, Args)]
 struct StatusArgs {
+    /// Address of the AuctionStateLens contract (defaults to each auction's `[auctions]` lens)
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address)]
+    lens: Option<Address>,
+
+    /// Auction that unqualified bid ids belong to: its address, or its name in `[auctions]`
+    #[arg(long, value_name = "AUCTION", value_parser = parse::auction)]
+    auction: Option<AuctionRef>,
+
+    /// Bid ids (decimal or 0x-prefixed hex), comma-separated or repeated; `AUCTION:ID` looks a bid up in another auction
+    #[arg(long = "bid-id", required = true, value_delimiter = ',', value_name = "ID", value_parser = parse::bid_ref)]
+    bid_ids: Vec<BidRef>,
+
+    /// Auctions looked up at the same time
+    #[arg(long, value_name = "N", default_value_t = status_cmd::DEFAULT_STATUS_CONCURRENCY)]
+    concurrency: usize,
+}
+
+#[derive(Debug, Args)]
+struct WalletArgs {
+    /// Address of the ContinuousClearingAuction contract, or its name in `[auctions]`
+    #[arg(long, value_name = "AUCTION", value_parser = parse::auction)]
+    auction: AuctionRef,
+
+    /// Owner to look at (defaults to the signing key's address)
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address)]
+    owner: Option<Address>,
+}
+
+#[derive(Debug, Args)]
+struct WatchArgs {
+    /// Address of the ContinuousClearingAuction contract, or its name in `[auctions]`
+    #[arg(long, value_name = "AUCTION", value_parser = parse::auction)]
+    auction: AuctionRef,
+
+    /// Minimum bid amount (raw currency units) that raises an alert
+    #[arg(long, value_name = "AMOUNT", value_parser = parse::u256)]
+    threshold: U256,
+
+    /// Owner whose bids never raise alerts (e.g. your own wallet)
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address)]
+    ignore_owner: Option<Address>,
+}
+
+#[derive(Debug, Args)]
+struct WatchlistArgs {
+    /// Auction to follow, by address or `[auctions]` name (repeatable; defaults to `[portfolio].auctions` in config)
+    #[arg(long = "auction", value_name = "AUCTION", value_parser = parse::auction)]
+    auctions: Vec<AuctionRef>,
+}
+
+#[derive(Debug, Args)]
+struct PortfolioArgs {
+    /// Bid owner whose bids are listed
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address)]
+    owner: Address,
+
+    /// Auction to include, by address or `[auctions]` name (repeatable; defaults to `[portfolio].auctions` in config)
+    #[arg(long = "auction", value_name = "AUCTION", value_parser = parse::auction)]
+    auctions: Vec<AuctionRef>,
+
+    /// Factory whose `AuctionCreated` logs are scanned for more auctions
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address)]
+    factory: Option<Address>,
+
     /// Address of the AuctionStateLens contract
-    #[arg(long, value_name = "ADDRESS")]
-    lens: String,
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address)]
+    lens: Option<Address>,
+
+    /// First block to scan for logs
+    #[arg(long, value_name = "BLOCK", default_value_t = 0)]
+    from_block: u64,
+
+    /// Only list bids recorded with this tag, e.g. `strategy=ladder` (repeatable; all must match)
+    #[arg(long = "tag", value_name = "TAG", value_parser = parse::tag)]
+    tags: Vec<String>,
+
+    /// Directory for tracked-bid stores [default: <data dir>/flux/bids]
+    #[arg(long, value_name = "DIR")]
+    bids_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct ClaimArgs {
+    /// Claim every claimable bid of the signer across the given auctions
+    #[arg(long, conflicts_with = "bid_ids")]
+    all: bool,
+
+    /// Comma-separated bid ids to claim from a single `--auction`
+    #[arg(long, value_delimiter = ',', value_name = "IDS", value_parser = parse::u256)]
+    bid_ids: Vec<U256>,
 
-    /// Address of the ContinuousClearingAuction contract
-    #[arg(long, value_name = "ADDRESS")]
-    auction: String,
+    /// Auction to sweep, by address or `[auctions]` name (repeatable; defaults to `[portfolio].auctions` in config)
+    #[arg(long = "auction", value_name = "AUCTION", value_parser = parse::auction)]
+    auctions: Vec<AuctionRef>,
+
+    /// First block to scan for the signer's bids
+    #[arg(long, value_name = "BLOCK", default_value_t = 0)]
+    from_block: u64,
+
+    /// Skip bids whose tokens are worth less than this (raw currency units)
+    #[arg(long, value_name = "AMOUNT", value_parser = parse::u256)]
+    dust_threshold: Option<U256>,
+
+    /// Move claimed tokens to this address; it must be in `[cold_storage].allowlist`
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address)]
+    sweep_to: Option<Address>,
+
+    /// With --sweep-to, print the transfers instead of sending them
+    #[arg(long, requires = "sweep_to")]
+    sweep_dry_run: bool,
+
+    /// Split claims into transactions estimated under this much gas, sent concurrently
+    #[arg(long, value_name = "GAS", conflicts_with = "max_block_gas_fraction")]
+    max_claim_gas: Option<u64>,
+
+    /// Split claims into transactions under this fraction (e.g. 0.25) of the block gas limit
+    #[arg(long, value_name = "FRACTION")]
+    max_block_gas_fraction: Option<f64>,
+}
+
+impl ClaimArgs {
+    fn gas_limit(&self) -> Option<ClaimGasLimit> {
+        self.max_claim_gas.map(ClaimGasLimit::PerTx).or(self
+            .max_block_gas_fraction
+            .map(ClaimGasLimit::BlockFraction))
+    }
+}
+
+#[derive(Debug, Args)]
+struct ExitArgs {
+    /// Address of the ContinuousClearingAuction contract, or its name in `[auctions]`
+    #[arg(long, value_name = "AUCTION", value_parser = parse::auction)]
+    auction: AuctionRef,
 
     /// Bid id (uint256, decimal or 0x-prefixed hex)
-    #[arg(long, value_name = "ID")]
-    bid_id: String,
+    #[arg(long, value_name = "ID", value_parser = parse::u256)]
+    bid_id: U256,
+
+    /// Only print the expected outcome of each exit
+    #[arg(long)]
+    preview: bool,
+}
+
+#[derive(Debug, Args)]
+struct LadderArgs {
+    /// Address of the ContinuousClearingAuction contract, or its name in `[auctions]`
+    #[arg(long, value_name = "AUCTION", value_parser = parse::auction)]
+    auction: AuctionRef,
+
+    /// Lowest rung price (Q96)
+    #[arg(long, value_name = "PRICE", value_parser = parse::u256)]
+    low: U256,
+
+    /// Highest rung price (Q96)
+    #[arg(long, value_name = "PRICE", value_parser = parse::u256)]
+    high: U256,
+
+    /// Number of rungs
+    #[arg(long, value_name = "N", default_value_t = 5)]
+    rungs: usize,
+
+    /// Total currency spread across the rungs (raw units)
+    #[arg(long, value_name = "AMOUNT", value_parser = parse::u256)]
+    total: U256,
+
+    /// Submit the ladder after printing the preview
+    #[arg(long)]
+    execute: bool,
+
+    /// Replay the auction's recorded events with the ladder instead of previewing live demand
+    #[arg(long, conflicts_with = "execute")]
+    backtest: bool,
+}
+
+#[derive(Debug, Args)]
+struct CheckpointArgs {
+    /// Address of the ContinuousClearingAuction contract, or its name in `[auctions]`
+    #[arg(long, value_name = "AUCTION", value_parser = parse::auction)]
+    auction: AuctionRef,
+}
+
+#[derive(Debug, Args)]
+struct CreateAuctionArgs {
+    /// Factory to create the auction through (defaults to `[portfolio].factory`)
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address)]
+    factory: Option<Address>,
+
+    /// Token to sell
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address)]
+    token: Address,
+
+    /// Tokens to sell (raw units)
+    #[arg(long, value_name = "AMOUNT", value_parser = parse::u256)]
+    total_supply: U256,
+
+    /// Currency bids pay in (defaults to the native currency)
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address)]
+    currency: Option<Address>,
+
+    /// Receives the tokens left unsold
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address)]
+    tokens_recipient: Address,
+
+    /// Receives the currency raised
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address)]
+    funds_recipient: Address,
+
+    #[arg(long, value_name = "BLOCK")]
+    start_block: u64,
+
+    #[arg(long, value_name = "BLOCK")]
+    end_block: u64,
+
+    /// First block tokens can be claimed at
+    #[arg(long, value_name = "BLOCK")]
+    claim_block: u64,
+
+    /// Supply release step `MPS:BLOCKS`: MPS of 10,000,000 released every block for BLOCKS blocks (repeatable, in order)
+    #[arg(long = "step", required = true, value_name = "MPS:BLOCKS", value_parser = parse::step)]
+    steps: Vec<StepSpec>,
+
+    /// Tick spacing (Q96)
+    #[arg(long, value_name = "PRICE", value_parser = parse::u256)]
+    tick_spacing: U256,
+
+    /// Floor price (Q96), a multiple of the tick spacing
+    #[arg(long, value_name = "PRICE", value_parser = parse::u256)]
+    floor_price: U256,
+
+    /// Hook every bid is validated by
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address)]
+    validation_hook: Option<Address>,
+
+    /// Currency (raw units) the auction must raise to graduate
+    #[arg(long, value_name = "AMOUNT", value_parser = parse::u256, default_value = "0")]
+    required_currency_raised: U256,
+
+    /// CREATE2 salt (uint256, decimal or 0x-prefixed hex)
+    #[arg(long, value_name = "SALT", value_parser = parse::u256, default_value = "0")]
+    salt: U256,
+
+    /// Only check the parameters and simulate the creation; send nothing
+    #[arg(long)]
+    lint_only: bool,
+}
+
+#[derive(Debug, Args)]
+struct DepthArgs {
+    /// Address of the ContinuousClearingAuction contract, or its name in `[auctions]`
+    #[arg(long, value_name = "AUCTION", value_parser = parse::auction)]
+    auction: AuctionRef,
+
+    /// Number of initialized ticks to show above the clearing price
+    #[arg(long, value_name = "N", default_value_t = 20)]
+    levels: usize,
+
+    /// Print JSON instead of a chart
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Args)]
+struct MarketArgs {
+    /// Address of the ContinuousClearingAuction contract, or its name in `[auctions]`
+    #[arg(long, value_name = "AUCTION", value_parser = parse::auction)]
+    auction: AuctionRef,
+
+    /// Whose bids to place against the clearing price (defaults to the signing key's address, if any)
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address)]
+    owner: Option<Address>,
+
+    /// Bids to read per multicall
+    #[arg(long, value_name = "N", default_value_t = 100)]
+    page_size: usize,
+
+    /// Multicalls to keep in flight
+    #[arg(long, value_name = "N", default_value_t = 4)]
+    concurrency: usize,
+
+    /// Number of largest competing owners to list
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    top: usize,
+
+    /// Number of max price buckets in the histogram
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    buckets: usize,
+
+    /// Print JSON instead of tables
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Args)]
+struct QuoteArgs {
+    /// Address of the ContinuousClearingAuction contract, or its name in `[auctions]`
+    #[arg(long, value_name = "AUCTION", value_parser = parse::auction)]
+    auction: AuctionRef,
+
+    /// Tokens the bid should buy (raw units)
+    #[arg(long, value_name = "AMOUNT", value_parser = parse::u256)]
+    target_tokens: U256,
+
+    /// Projected clearing price (Q96); defaults to the current one
+    #[arg(long, value_name = "PRICE", value_parser = parse::u256)]
+    price: Option<U256>,
+
+    /// Most currency to commit (raw units)
+    #[arg(long, value_name = "AMOUNT", value_parser = parse::u256)]
+    budget: Option<U256>,
+
+    /// Risk limit on a single bid's amount (raw units)
+    #[arg(long, value_name = "AMOUNT", value_parser = parse::u256)]
+    max_bid_amount: Option<U256>,
+}
+
+#[derive(Debug, Args)]
+struct RunArgs {
+    /// Address of the ContinuousClearingAuction contract, or its name in `[auctions]`
+    #[arg(long, value_name = "AUCTION", value_parser = parse::auction)]
+    auction: AuctionRef,
+
+    /// Comma-separated bid ids to settle (defaults to every bid of the signer)
+    #[arg(long, value_delimiter = ',', value_name = "IDS", value_parser = parse::u256)]
+    bid_ids: Vec<U256>,
+
+    /// First block to scan for the signer's bids
+    #[arg(long, value_name = "BLOCK", default_value_t = 0)]
+    from_block: u64,
+
+    /// Poke `checkpoint()` while the auction is active and the last one is older than N blocks
+    #[arg(long, value_name = "N")]
+    checkpoint_every: Option<u64>,
+
+    /// Skip bids whose tokens are worth less than this (raw currency units)
+    #[arg(long, value_name = "AMOUNT", value_parser = parse::u256)]
+    dust_threshold: Option<U256>,
+
+    /// Exit bids sitting at the clearing price only N blocks after the end, instead of right away
+    #[arg(long, value_name = "N")]
+    atm_wait_blocks: Option<u64>,
+
+    /// Don't resend an intent that failed for the next N blocks
+    #[arg(long, value_name = "N")]
+    failure_cooldown: Option<u64>,
+
+    /// Serve Prometheus metrics on this address (e.g. 0.0.0.0:9100)
+    #[arg(long, value_name = "ADDR")]
+    metrics_addr: Option<SocketAddr>,
+
+    /// Directory for per-campaign lockfiles [default: <data dir>/flux/locks]
+    #[arg(long, value_name = "DIR")]
+    lock_dir: Option<PathBuf>,
+
+    /// What to do when another process is already running this campaign
+    #[arg(long, value_enum, default_value_t = IfLocked::Exit)]
+    if_locked: IfLocked,
+
+    /// Directory for tracked-bid stores [default: <data dir>/flux/bids]
+    #[arg(long, value_name = "DIR")]
+    bids_dir: Option<PathBuf>,
+
+    /// Record every bid the run submits with this tag, e.g. `campaign=launch` (repeatable); `strategy=<kind>` is added for the `[strategy]`
+    #[arg(long = "tag", value_name = "TAG", value_parser = parse::tag)]
+    tags: Vec<String>,
+
+    /// Send bids through this private relay RPC (e.g. Flashbots Protect) instead of the public mempool
+    #[arg(long, value_name = "URL")]
+    private_rpc: Option<String>,
+
+    /// Comma-separated intents to send through --private-rpc [default: submit-bid]
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_enum,
+        value_name = "INTENTS",
+        requires = "private_rpc"
+    )]
+    private_intents: Vec<PrivateIntent>,
+
+    /// Resend publicly when the relay has not included a transaction after N blocks
+    #[arg(long, value_name = "N", default_value_t = PrivateRelay::DEFAULT_FALLBACK_BLOCKS)]
+    private_fallback_blocks: u64,
+
+    /// Send every transaction as an ERC-4337 user operation through this bundler RPC
+    #[arg(long, value_name = "URL")]
+    bundler_rpc: Option<String>,
+
+    /// Bid from this ERC-4337 smart account, owned by the signing key
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        value_parser = parse::address,
+        requires = "bundler_rpc",
+        conflicts_with = "eip7702_delegate"
+    )]
+    smart_account: Option<Address>,
+
+    /// Bid from the signing key's own address, delegated under EIP-7702 to this account implementation
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address, requires = "bundler_rpc")]
+    eip7702_delegate: Option<Address>,
+
+    /// EntryPoint the bundler serves [default: v0.7, or v0.8 with --eip7702-delegate]
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address, requires = "bundler_rpc")]
+    entry_point: Option<Address>,
+
+    /// Have this paymaster sponsor the user operations
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address, requires = "bundler_rpc")]
+    paymaster: Option<Address>,
+
+    /// Hex data for the paymaster, e.g. its sponsorship signature
+    #[arg(long, value_name = "HEX", requires = "paymaster")]
+    paymaster_data: Option<Bytes>,
+
+    /// Paymaster verification gas limit, instead of the bundler's estimate
+    #[arg(long, value_name = "GAS", requires = "paymaster")]
+    paymaster_verification_gas: Option<u128>,
+
+    /// Paymaster post-op gas limit, instead of the bundler's estimate
+    #[arg(long, value_name = "GAS", requires = "paymaster")]
+    paymaster_post_op_gas: Option<u128>,
+
+    /// Rehearse every transaction on a local anvil fork of the current block first, and send it only if it succeeds and moves balances as its events say
+    #[arg(long)]
+    shadow_fork: bool,
+
+    /// Hold intents back while the block being handled trails the chain head by more than N blocks
+    #[arg(long, value_name = "N")]
+    max_block_lag: Option<u64>,
+
+    /// Read the chain head for --max-block-lag from this second RPC instead of the main one
+    #[arg(long, value_name = "URL", requires = "max_block_lag")]
+    reference_rpc: Option<String>,
+
+    /// Only warn when the auction is not checkpointed up to date before a partial exit, instead of calling `checkpoint()`
+    #[arg(long)]
+    no_checkpoint_fill: bool,
+
+    /// After each receipt, read the bid, exit, claim or checkpoint back from the auction and fail the intent unless it is there, in case the node returns receipts for reorged-out transactions
+    #[arg(long)]
+    verify_after_execute: bool,
+
+    /// Append a JSON line for every intent executed to this file, for auditing
+    #[arg(long, value_name = "PATH")]
+    activity_log: Option<PathBuf>,
+
+    /// Hold the `[strategy]` back until `HH:MM UTC`, `@<unix seconds>`, `10m before end` or `1h after start` [default: `[schedule] start_at`]
+    #[arg(long, value_name = "TIME")]
+    start_at: Option<TimeTarget>,
+
+    /// Append a JSON line with the strategy's context and intents at every block to this file, for `replay`
+    #[arg(long, value_name = "PATH")]
+    record: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct DaemonArgs {
+    #[command(flatten)]
+    run: RunArgs,
+
+    /// Serve the control API on this address
+    #[arg(long, value_name = "ADDR", default_value = "127.0.0.1:8645")]
+    listen: SocketAddr,
+
+    /// Directory for per-campaign daemon state [default: <data dir>/flux/daemon]
+    #[arg(long, value_name = "DIR")]
+    state_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct ReplayArgs {
+    /// Decisions recorded by `run --record`
+    #[arg(long, value_name = "PATH")]
+    recording: PathBuf,
+
+    /// Owner the strategy bids as (defaults to the signing key's address)
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address)]
+    owner: Option<Address>,
+
+    /// The recorded run's `--checkpoint-every`
+    #[arg(long, value_name = "N")]
+    checkpoint_every: Option<u64>,
+
+    /// The recorded run's `--start-at` [default: `[schedule] start_at`]
+    #[arg(long, value_name = "TIME")]
+    start_at: Option<TimeTarget>,
+}
+
+#[derive(Debug, Args)]
+struct TuiArgs {
+    /// Address of the ContinuousClearingAuction contract, or its name in `[auctions]`
+    #[arg(long, value_name = "AUCTION", value_parser = parse::auction)]
+    auction: AuctionRef,
+
+    /// Whose bids to show (defaults to the signing key's address)
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address)]
+    owner: Option<Address>,
+
+    /// Comma-separated bid ids to show (defaults to every bid of the owner)
+    #[arg(long, value_delimiter = ',', value_name = "IDS", value_parser = parse::u256)]
+    bid_ids: Vec<U256>,
+
+    /// First block to scan for the owner's bids
+    #[arg(long, value_name = "BLOCK", default_value_t = 0)]
+    from_block: u64,
+
+    /// Max price (Q96) of the bid submitted with `b`
+    #[arg(long, value_name = "PRICE", value_parser = parse::u256, requires = "bid_amount")]
+    bid_price: Option<U256>,
+
+    /// Amount (raw currency units) of the bid submitted with `b`
+    #[arg(long, value_name = "AMOUNT", value_parser = parse::u256, requires = "bid_price")]
+    bid_amount: Option<U256>,
+}
+
+#[derive(Debug, Args)]
+struct AnalyzeArgs {
+    /// Address of the ContinuousClearingAuction contract, or its name in `[auctions]`
+    #[arg(long, value_name = "AUCTION", value_parser = parse::auction)]
+    auction: AuctionRef,
+
+    /// Bid id (uint256, decimal or 0x-prefixed hex)
+    #[arg(long, value_name = "ID", value_parser = parse::u256)]
+    bid_id: U256,
+
+    /// First block to scan for the bid's events [default: the auction's start block]
+    #[arg(long, value_name = "BLOCK")]
+    from_block: Option<u64>,
+
+    /// Directory for tracked-bid stores the bid's tags are read from [default: <data dir>/flux/bids]
+    #[arg(long, value_name = "DIR")]
+    bids_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct ReportArgs {
+    /// Address of the ContinuousClearingAuction contract, or its name in `[auctions]`
+    #[arg(long, value_name = "AUCTION", value_parser = parse::auction)]
+    auction: AuctionRef,
+
+    /// Whose participation to summarize (defaults to the signing key's address, if any)
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address)]
+    owner: Option<Address>,
+
+    /// First block to scan for the auction's events
+    #[arg(long, value_name = "BLOCK", default_value_t = 0)]
+    from_block: u64,
+
+    /// Number of most demanded ticks to list
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    top_ticks: usize,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = ReportFormat::Markdown)]
+    format: ReportFormat,
+
+    /// Write the report here instead of stdout
+    #[arg(long, value_name = "FILE")]
+    out: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct SnapshotArgs {
+    /// Address of the ContinuousClearingAuction contract, or its name in `[auctions]`
+    #[arg(long, value_name = "AUCTION", value_parser = parse::auction)]
+    auction: AuctionRef,
+
+    /// Whose bids to include (defaults to the signing key's address, if any)
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address)]
+    owner: Option<Address>,
+
+    /// First block to scan for the owner's bids
+    #[arg(long, value_name = "BLOCK", default_value_t = 0)]
+    from_block: u64,
+
+    /// Number of initialized ticks to include above the clearing price
+    #[arg(long, value_name = "N", default_value_t = 50)]
+    levels: usize,
+
+    /// Where to write the snapshot [default: snapshot-<BLOCK>.json]
+    #[arg(long, value_name = "FILE")]
+    out: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct DiffArgs {
+    /// The earlier snapshot
+    #[arg(value_name = "A")]
+    a: PathBuf,
+
+    /// The later snapshot
+    #[arg(value_name = "B")]
+    b: PathBuf,
+}
+
+#[derive(Debug, Args)]
+struct AttestArgs {
+    /// Address of the ContinuousClearingAuction contract, or its name in `[auctions]`
+    #[arg(long, value_name = "AUCTION", value_parser = parse::auction)]
+    auction: AuctionRef,
+
+    /// Bid owner to attest for (defaults to the signing key's address)
+    #[arg(long, value_name = "ADDRESS", value_parser = parse::address)]
+    owner: Option<Address>,
+
+    /// Comma-separated bid ids (defaults to every bid of the owner)
+    #[arg(long, value_delimiter = ',', value_name = "IDS", value_parser = parse::u256)]
+    bid_ids: Vec<U256>,
+
+    /// First block to scan for the owner's bids
+    #[arg(long, value_name = "BLOCK", default_value_t = 0)]
+    from_block: u64,
+
+    /// Write the attestation here instead of stdout
+    #[arg(long, value_name = "FILE")]
+    out: Option<PathBuf>,
 }
 
 #[tokio::main]
 async fn main() -> eyre::Result<()> {
     let cli = Cli::parse();
 
-    // Load config once; still useful for the Bids subcommand
-    let config = match load_config(&cli.config) {
-        Ok(config) => config,
-        Err(error) => {
-            eprintln!("{error}");
-            std::process::exit(1);
-        }
-    };
+    logging::init(&cli.log_level, cli.log_format)?;
+
+    let settings = Settings::new(cli.config, cli.rpc_url, cli.private_key);
+    let confirmation = Confirmation::new(cli.yes);
 
     match cli.command {
-        Some(Commands::Bids(args)) => handle_bids(&config, args),
-        Some(Commands::Status(args)) => {
-            let rpc_url = cli
-                .rpc_url
-                .as_deref()
-                .ok_or_else(|| eyre::eyre!("--rpc-url or CCA_RPC_URL is required for `status`"))?;
+        Some(command) => match run(command, &settings, confirmation, cli.raw).await {
+            Err(error) if matches!(error.downcast_ref(), Some(ConfirmError::Declined)) => {
+                eprintln!("Cancelled; nothing was sent");
+            }
+            result => result?,
+        },
+        None => {
+            settings.config("flux-cli")?;
+            println!("Loaded config from {}", settings.config_path().display());
+        }
+    }
 
-            handle_status(rpc_url, args).await?
+    Ok(())
+}
+
+async fn run(
+    command: Commands,
+    settings: &Settings,
+    confirmation: Confirmation,
+    raw: bool,
+) -> eyre::Result<()> {
+    match command {
+        Commands::Bids(args) => handle_bids(settings, args, &confirmation, raw).await?,
+        Commands::Status(args) => {
+            let rpc_url = settings.rpc_url("status")?;
+            let requests = status_requests(settings, &args)?;
+            let strategy = settings.strategy()?;
+            handle_status(&rpc_url, requests, args.concurrency, strategy, raw).await?
         }
-        None => {
-            println!("Loaded config from {}", cli.config.display());
+        Commands::Wallet(args) => {
+            let rpc_url = settings.rpc_url("wallet")?;
+            let owner = settings.owner(args.owner, "wallet")?;
+            let auction = settings.auction(&args.auction)?;
+            let ctx = ChainContext::read_only(&rpc_url).await?;
+            let strategy = settings.strategy()?;
+            let output = wallet_cmd::wallet(&ctx, auction, owner, strategy.as_ref()).await?;
+            print_wallet(&output, raw);
+        }
+        Commands::Watch(args) => {
+            let rpc_url = settings.rpc_url("watch")?;
+            handle_watch(&rpc_url, settings.auction(&args.auction)?, args, raw).await?
+        }
+        Commands::Watchlist(args) => {
+            let rpc_url = settings.rpc_url("watchlist")?;
+            let auctions = if args.auctions.is_empty() {
+                settings.portfolio_auctions()?
+            } else {
+                settings.auctions(&args.auctions)?
+            };
+            handle_watchlist(&rpc_url, auctions).await?
+        }
+        Commands::Portfolio(args) => {
+            let rpc_url = settings.rpc_url("portfolio")?;
+            handle_portfolio(&rpc_url, settings, args, raw).await?
+        }
+        Commands::Claim(args) => {
+            let rpc_url = settings.rpc_url("claim")?;
+            let private_key = settings.private_key("claim")?;
+            handle_claim(&rpc_url, &private_key, settings, args, &confirmation, raw).await?
+        }
+        Commands::Exit(args) => {
+            let rpc_url = settings.rpc_url("exit")?;
+            let private_key = settings.private_key("exit")?;
+            handle_exit(
+                &rpc_url,
+                &private_key,
+                settings.auction(&args.auction)?,
+                args,
+                &confirmation,
+                raw,
+            )
+            .await?
+        }
+        Commands::Checkpoint(args) => {
+            let rpc_url = settings.rpc_url("checkpoint")?;
+            let private_key = settings.private_key("checkpoint")?;
+            let auction = settings.auction(&args.auction)?;
+            handle_checkpoint(&rpc_url, &private_key, auction, &confirmation, raw).await?
+        }
+        Commands::CreateAuction(args) => {
+            let rpc_url = settings.rpc_url("create-auction")?;
+            handle_create_auction(&rpc_url, settings, args, &confirmation).await?
+        }
+        Commands::Depth(args) => {
+            let rpc_url = settings.rpc_url("depth")?;
+            handle_depth(&rpc_url, settings.auction(&args.auction)?, args, raw).await?
+        }
+        Commands::Market(args) => {
+            let rpc_url = settings.rpc_url("market")?;
+            let owner = settings.optional_owner(args.owner)?;
+            let query = MarketQuery {
+                auction: settings.auction(&args.auction)?,
+                owner,
+                page_size: args.page_size,
+                concurrency: args.concurrency,
+                top: args.top,
+                buckets: args.buckets,
+            };
+            handle_market(&rpc_url, &query, args.json, raw).await?
+        }
+        Commands::Quote(args) => {
+            let rpc_url = settings.rpc_url("quote")?;
+            handle_quote(&rpc_url, settings.auction(&args.auction)?, args, raw).await?
+        }
+        Commands::Attest(args) => {
+            let rpc_url = settings.rpc_url("attest")?;
+            let private_key = settings.private_key("attest")?;
+            let auction = settings.auction(&args.auction)?;
+            handle_attest(&rpc_url, &private_key, auction, args).await?
+        }
+        Commands::Analyze(args) => {
+            let rpc_url = settings.rpc_url("analyze")?;
+            handle_analyze(&rpc_url, settings.auction(&args.auction)?, args, raw).await?
+        }
+        Commands::Report(args) => {
+            let rpc_url = settings.rpc_url("report")?;
+            let owner = settings.optional_owner(args.owner)?;
+            let query = ReportQuery {
+                auction: settings.auction(&args.auction)?,
+                owner,
+                from_block: args.from_block,
+                top_ticks: args.top_ticks,
+            };
+            handle_report(&rpc_url, &query, args.format, args.out).await?
+        }
+        Commands::Snapshot(args) => {
+            let rpc_url = settings.rpc_url("snapshot")?;
+            let owner = settings.optional_owner(args.owner)?;
+            let query = SnapshotQuery {
+                auction: settings.auction(&args.auction)?,
+                owner,
+                from_block: args.from_block,
+                levels: args.levels,
+            };
+            handle_snapshot(&rpc_url, &query, args.out).await?
+        }
+        Commands::Diff(args) => handle_diff(&args)?,
+        Commands::Ladder(args) => {
+            let rpc_url = settings.rpc_url("ladder")?;
+            let private_key = if args.execute {
+                Some(settings.private_key("ladder")?)
+            } else {
+                None
+            };
+            let auction = settings.auction(&args.auction)?;
+            let private_key = private_key.as_ref().map(|key| key.as_str());
+            handle_ladder(&rpc_url, private_key, auction, args, &confirmation, raw).await?
+        }
+        Commands::Tui(args) => {
+            let rpc_url = settings.rpc_url("tui")?;
+            let private_key = settings.optional_private_key()?;
+            let auction = settings.auction(&args.auction)?;
+            let private_key = private_key.as_ref().map(|key| key.as_str());
+            handle_tui(&rpc_url, private_key, auction, args).await?
+        }
+        Commands::Run(args) => {
+            let rpc_url = settings.rpc_url("run")?;
+            let private_key = settings.private_key("run")?;
+            let strategy = settings.strategy()?;
+            let start_at = match args.start_at {
+                Some(target) => Some(target),
+                None => settings.start_at()?,
+            };
+            let auction = settings.auction(&args.auction)?;
+            handle_run(
+                &rpc_url,
+                &private_key,
+                auction,
+                args,
+                strategy,
+                start_at,
+                raw,
+            )
+            .await?
+        }
+        Commands::Daemon(args) => {
+            let rpc_url = settings.rpc_url("daemon")?;
+            let private_key = settings.private_key("daemon")?;
+            let strategy = settings.strategy()?;
+            let start_at = match args.run.start_at {
+                Some(target) => Some(target),
+                None => settings.start_at()?,
+            };
+            let auction = settings.auction(&args.run.auction)?;
+            handle_daemon(
+                &rpc_url,
+                &private_key,
+                auction,
+                args,
+                strategy,
+                start_at,
+                raw,
+            )
+            .await?
+        }
+        Commands::Replay(args) => {
+            let rpc_url = settings.rpc_url("replay")?;
+            let owner = settings.owner(args.owner, "replay")?;
+            let options = ReplayOptions {
+                strategy: settings.strategy()?,
+                owner,
+                start_at: match args.start_at {
+                    Some(target) => Some(target),
+                    None => settings.start_at()?,
+                },
+                checkpoint_every: args.checkpoint_every,
+            };
+            handle_replay(&rpc_url, &args.recording, options).await?
+        }
+        Commands::ClearCache(args) => {
+            let cache = ConfigCache::new(&config_cache::default_cache_dir());
+            let auction = args
+                .auction
+                .map(|auction| settings.auction(&auction))
+                .transpose()?;
+            let removed = cache.clear(auction)?;
+            println!(
+                "Removed {removed} cached auction configs from {}",
+                cache.dir().display()
+            );
+        }
+        Commands::Secrets(command) => handle_secrets(command)?,
+        Commands::Auctions(command) => handle_auctions(settings, command).await?,
+        Commands::Audit(command) => handle_audit(command)?,
+    }
+
+    Ok(())
+}
+
+fn handle_audit(command: AuditCommand) -> eyre::Result<()> {
+    match command {
+        AuditCommand::Export(args) => {
+            let count = match &args.out {
+                Some(path) => {
+                    let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+                    let count = audit::export_csv(&args.log, &mut file)?;
+                    file.flush()?;
+                    count
+                }
+                None => audit::export_csv(&args.log, &mut std::io::stdout().lock())?,
+            };
+            eprintln!("Exported {count} activity records");
+        }
+    }
+    Ok(())
+}
+
+async fn handle_auctions(settings: &Settings, command: AuctionsCommand) -> eyre::Result<()> {
+    match command {
+        AuctionsCommand::Add {
+            name,
+            address,
+            lens,
+        } => {
+            // Fail on a missing config before touching the chain.
+            settings.config("auctions add")?;
+            let ctx = ChainContext::read_only(&settings.rpc_url("auctions add")?).await?;
+            let entry = auctions_cmd::fetch_entry(&ctx, address, lens).await?;
+            bids_config::add_auction(settings.config_path(), &name, &entry)?;
+            println!(
+                "Added `{name}` ({address} on chain {}, token decimals {}) to {}",
+                entry.chain_id,
+                entry.token_decimals,
+                settings.config_path().display()
+            );
         }
     }
+    Ok(())
+}
 
+fn handle_secrets(command: SecretsCommand) -> eyre::Result<()> {
+    match command {
+        SecretsCommand::Encrypt { keyring } => {
+            let secret = Zeroizing::new(rpassword::prompt_password("Secret: ")?);
+            let reference = match keyring {
+                Some(name) => secrets::store_in_keyring(&name, &secret)?,
+                None => secrets::encrypt(&secret, &secrets::passphrase(true)?)?,
+            };
+            println!("{reference}");
+        }
+        SecretsCommand::Decrypt { reference } => {
+            println!("{}", *secrets::resolve(&reference)?);
+        }
+    }
     Ok(())
 }
 
-fn handle_bids(config: &BidsConfig, args: BidArgs) {
+async fn handle_bids(
+    settings: &Settings,
+    args: BidArgs,
+    confirmation: &Confirmation,
+    raw: bool,
+) -> eyre::Result<()> {
+    let config: &BidsConfig = settings.config("bids")?;
     let overrides = BidOverrides {
         max_bid: args.max_bid,
         amount: args.amount,
         owner: args.owner,
     };
 
-    match resolve_bid(config, overrides) {
-        Ok(bid) => println!(
-            "Bid ready (local): max_bid={}, amount={}, owner={}",
-            bid.max_bid, bid.amount, bid.owner
+    let Some(auction) = args.auction else {
+        match resolve_bid(config, overrides) {
+            Ok(bid) => println!(
+                "Bid ready (local): max_bid={}, amount={}, owner={}",
+                bid.max_bid, bid.amount, bid.owner
+            ),
+            Err(err) => {
+                eprintln!("{err}");
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    };
+
+    // The signer owns the bid; `owner` only matters for the local preview.
+    let auction = settings.auction(&auction)?;
+    let request = BidRequest {
+        auction,
+        max_price: overrides.max_bid.unwrap_or(config.bid.max_bid),
+        amount: overrides.amount.unwrap_or(config.bid.amount),
+        prev_tick_price: args.prev_tick_price,
+        max_clearing_drift: args.max_clearing_drift,
+        beneficiary: args.beneficiary,
+        tags: args.tags,
+    };
+    let private_key = settings.private_key("bids")?;
+    let ctx = ChainContext::with_signer(&settings.rpc_url("bids")?, &private_key).await?;
+    let store_dir = args.bids_dir.unwrap_or_else(store::default_store_dir);
+    let submitted = bid_cmd::submit_bid(&ctx, request, &store_dir, confirmation).await?;
+
+    let amounts = Amounts::fetch(&ctx, auction, raw).await?;
+    println!(
+        "Submitted bid {}: max_price={} amount={} (tx {})",
+        submitted.bid_id,
+        amounts.price(Price::new(submitted.max_price_q96)),
+        amounts.currency(submitted.amount),
+        submitted.tx_hash
+    );
+    if let Some(beneficiary) = submitted.beneficiary {
+        eprintln!(
+            "warning: bid {} belongs to {beneficiary}; exits and claims for it need that account's key",
+            submitted.bid_id
+        );
+    }
+    Ok(())
+}
+
+/// Groups the requested bids by auction, in the order auctions first
+/// appear, each with the lens to read it through.
+fn status_requests(settings: &Settings, args: &StatusArgs) -> eyre::Result<Vec<StatusRequest>> {
+    let mut requests: Vec<StatusRequest> = Vec::new();
+    for bid in &args.bid_ids {
+        let reference = bid
+            .auction
+            .as_ref()
+            .or(args.auction.as_ref())
+            .ok_or_else(|| {
+                eyre::eyre!("bid {} needs --auction or an `AUCTION:ID` prefix", bid.id)
+            })?;
+        let auction = settings.auction(reference)?;
+        let bid_id = BidId::new(bid.id);
+        if let Some(request) = requests.iter_mut().find(|r| r.auction == auction) {
+            request.bid_ids.push(bid_id);
+            continue;
+        }
+        let lens = match args.lens {
+            Some(lens) => lens,
+            None => settings.auction_lens(reference)?.ok_or_else(|| {
+                eyre::eyre!("--lens is required unless each auction's `[auctions]` entry has one")
+            })?,
+        };
+        requests.push(StatusRequest {
+            auction,
+            lens,
+            bid_ids: vec![bid_id],
+        });
+    }
+    Ok(requests)
+}
+
+async fn handle_status(
+    rpc_url: &str,
+    requests: Vec<StatusRequest>,
+    concurrency: usize,
+    strategy: Option<StrategyConfig>,
+    raw: bool,
+) -> eyre::Result<()> {
+    let ctx = ChainContext::read_only(rpc_url).await?;
+    let total = requests.len();
+    let mut failed = 0;
+    let results = status_cmd::status_batch(&ctx, requests, concurrency);
+    futures::pin_mut!(results);
+    while let Some((auction, result)) = results.next().await {
+        match result {
+            Ok(outputs) => {
+                let mut owners = Vec::new();
+                for output in outputs {
+                    if !owners.contains(&output.bid.owner) {
+                        owners.push(output.bid.owner);
+                    }
+                    println!("{output:?}");
+                }
+                for owner in owners {
+                    match wallet_cmd::wallet(&ctx, auction, owner, strategy.as_ref()).await {
+                        Ok(wallet) => print_wallet(&wallet, raw),
+                        Err(error) => eprintln!("{auction}: wallet of {owner}: {error:#}"),
+                    }
+                }
+            }
+            Err(error) => {
+                failed += 1;
+                eprintln!("{auction}: {error:#}");
+            }
+        }
+    }
+    if failed > 0 {
+        eyre::bail!("{failed} of {total} auctions failed");
+    }
+    Ok(())
+}
+
+async fn handle_watch(
+    rpc_url: &str,
+    auction: Address,
+    args: WatchArgs,
+    raw: bool,
+) -> eyre::Result<()> {
+    let WatchArgs {
+        threshold,
+        ignore_owner,
+        ..
+    } = args;
+
+    let ctx = ChainContext::read_only(rpc_url).await?;
+    let amounts = Amounts::fetch(&ctx, auction, raw).await?;
+    println!(
+        "Watching {auction} for bids >= {}",
+        amounts.currency(threshold)
+    );
+    watch_cmd::watch(rpc_url, auction, threshold, ignore_owner, |alert| {
+        println!(
+            "whale alert: block={} bid_id={} owner={} price={} amount={}",
+            alert.block.as_u64(),
+            alert.bid_id.as_u256(),
+            alert.owner,
+            amounts.price(alert.price),
+            amounts.currency(alert.amount.as_u256()),
+        )
+    })
+    .await
+}
+
+async fn handle_watchlist(rpc_url: &str, auctions: Vec<Address>) -> eyre::Result<()> {
+    if auctions.is_empty() {
+        eyre::bail!("--auction or [portfolio].auctions is required for `watchlist`");
+    }
+    let ctx = ChainContext::read_only(rpc_url).await?;
+    println!("Watching {} auctions for phase changes", auctions.len());
+    watchlist_cmd::watchlist(&ctx, &auctions, |event| {
+        println!(
+            "block={} auction={} {}",
+            event.block.as_u64(),
+            event.auction,
+            event.transition
+        )
+    })
+    .await
+}
+
+async fn handle_portfolio(
+    rpc_url: &str,
+    settings: &Settings,
+    args: PortfolioArgs,
+    raw: bool,
+) -> eyre::Result<()> {
+    let portfolio = settings.portfolio()?;
+    let auctions = if args.auctions.is_empty() {
+        settings.portfolio_auctions()?
+    } else {
+        settings.auctions(&args.auctions)?
+    };
+    let lens = match args.lens {
+        Some(lens) => lens,
+        None => portfolio
+            .lens_address()?
+            .ok_or_else(|| eyre::eyre!("--lens or [portfolio].lens is required for `portfolio`"))?,
+    };
+    let factory = match args.factory {
+        Some(factory) => Some(factory),
+        None => portfolio.factory_address()?,
+    };
+
+    let query = PortfolioQuery {
+        owner: args.owner,
+        lens,
+        auctions,
+        factory,
+        from_block: args.from_block,
+        bids_dir: args.bids_dir.unwrap_or_else(store::default_store_dir),
+        tags: args.tags,
+    };
+
+    let output = portfolio_cmd::portfolio(rpc_url, query).await?;
+    let ctx = ChainContext::read_only(rpc_url).await?;
+    let mut amounts = HashMap::new();
+    for bid in &output.bids {
+        let auction = bid.status.auction.address;
+        if let Entry::Vacant(entry) = amounts.entry(auction) {
+            entry.insert(Amounts::fetch(&ctx, auction, raw).await?);
+        }
+    }
+    print_portfolio(&output, &amounts);
+    Ok(())
+}
+
+fn print_wallet(output: &WalletOutput, raw: bool) {
+    let amounts = Amounts::new((!raw).then(|| output.units.clone()), output.native_symbol);
+    let currency = |amount: U256| amounts.currency(amount);
+    let funding = &output.funding;
+    println!("Wallet {} in auction {}", funding.owner, output.auction);
+    println!(
+        "  currency balance: {}",
+        currency(funding.balances.currency.as_u256())
+    );
+    match funding.allowance {
+        Some(allowance) => println!("  allowance:        {}", currency(allowance)),
+        None => println!("  allowance:        not needed (native currency)"),
+    }
+    println!(
+        "  native balance:   {}",
+        amounts.native(funding.balances.native)
+    );
+    if output.need.bids > 0 {
+        println!(
+            "  strategy needs:   {} over {} bids, ~{} for gas",
+            currency(output.need.currency.as_u256()),
+            output.need.bids,
+            amounts.native(output.need.gas_cost(funding.gas_price))
+        );
+    }
+    if output.warnings.is_empty() {
+        return;
+    }
+    println!("Warnings:");
+    for warning in &output.warnings {
+        let line = match *warning {
+            FundingWarning::Currency { needed, available } => format!(
+                "currency balance {} is below the {} the strategy bids",
+                currency(available),
+                currency(needed)
+            ),
+            FundingWarning::Allowance { needed, allowance } => format!(
+                "allowance {} is below the {} the strategy bids; approve the auction first",
+                currency(allowance),
+                currency(needed)
+            ),
+            FundingWarning::Gas { needed, available } => format!(
+                "native balance {} is below the ~{} needed for gas",
+                amounts.native(available),
+                amounts.native(needed)
+            ),
+        };
+        println!("  - {line}");
+    }
+}
+
+fn print_portfolio(output: &PortfolioOutput, amounts: &HashMap<Address, Amounts>) {
+    println!(
+        "Portfolio for {} ({} bids across {} auctions)",
+        output.owner,
+        output.bids.len(),
+        output.auctions.len()
+    );
+    println!(
+        "{:<42}  {:>8}  {:<24}  {:>24}  {:>24}  {:>24}  tags",
+        "auction", "bid_id", "status", "currency_locked", "tokens_filled", "pending_claim"
+    );
+    for bid in &output.bids {
+        let status = &bid.status;
+        let amounts = &amounts[&status.auction.address];
+        println!(
+            "{:<42}  {:>8}  {:<24}  {:>24}  {:>24}  {:>24}  {}",
+            status.auction.address.to_string(),
+            status.bid.id.as_u256(),
+            status.bid_status.to_string(),
+            amounts.currency(status.fill.currency_locked.as_u256()),
+            amounts.tokens(status.fill.tokens_filled.as_u256()),
+            amounts.tokens(status.bid.unclaimed_tokens().as_u256()),
+            bid.tags.join(","),
+        );
+    }
+}
+
+async fn handle_claim(
+    rpc_url: &str,
+    private_key: &str,
+    settings: &Settings,
+    args: ClaimArgs,
+    confirmation: &Confirmation,
+    raw: bool,
+) -> eyre::Result<()> {
+    // Check the destination before claiming anything.
+    let cold_storage = match args.sweep_to {
+        Some(destination) => {
+            let cold_storage = ColdStorage::new(settings.cold_storage()?.allowlist_addresses()?);
+            cold_storage.check(destination)?;
+            Some(ColdStorageTarget {
+                cold_storage,
+                destination,
+                dry_run: args.sweep_dry_run,
+            })
+        }
+        None => None,
+    };
+
+    if !args.all {
+        let auctions = settings.auctions(&args.auctions)?;
+        return handle_claim_bids(
+            rpc_url,
+            private_key,
+            &auctions,
+            args,
+            cold_storage.as_ref(),
+            confirmation,
+            raw,
+        )
+        .await;
+    }
+
+    let auctions = if args.auctions.is_empty() {
+        settings.portfolio_auctions()?
+    } else {
+        settings.auctions(&args.auctions)?
+    };
+
+    let ctx = ChainContext::with_signer(rpc_url, private_key).await?;
+    let results = claim_cmd::claim_all(
+        &ctx,
+        &auctions,
+        args.from_block,
+        args.dust_threshold,
+        args.gas_limit(),
+        cold_storage.as_ref(),
+        confirmation,
+    )
+    .await?;
+
+    if results.is_empty() {
+        println!("Nothing to claim");
+    }
+    for sweep in results {
+        let amounts = Amounts::fetch(&ctx, sweep.auction, raw).await?;
+        for claim in sweep.claims {
+            match claim {
+                Ok(claim) => {
+                    println!(
+                        "{}: claimed {} from {} bids (tx {})",
+                        sweep.auction,
+                        amounts.tokens(claim.total_tokens.as_u256()),
+                        claim.bid_ids.len(),
+                        claim.tx_hash
+                    );
+                    if let Some(target) = &cold_storage {
+                        let moved = claim_cmd::move_to_cold_storage(
+                            &ctx,
+                            sweep.auction,
+                            target,
+                            claim.total_tokens,
+                        )
+                        .await;
+                        match moved {
+                            Ok(moved) => print_cold_storage_move(sweep.auction, &moved, &amounts),
+                            Err(error) => {
+                                eprintln!("{}: cold storage sweep failed: {error}", sweep.auction)
+                            }
+                        }
+                    }
+                }
+                Err(error) => eprintln!("{}: claim failed: {error}", sweep.auction),
+            }
+        }
+        for skip in &sweep.skipped {
+            println!(
+                "{}: skipped dust bid {} (worth {})",
+                sweep.auction,
+                skip.bid_id.as_u256(),
+                amounts.currency(skip.value.as_u256())
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn handle_claim_bids(
+    rpc_url: &str,
+    private_key: &str,
+    auctions: &[Address],
+    args: ClaimArgs,
+    cold_storage: Option<&ColdStorageTarget>,
+    confirmation: &Confirmation,
+    raw: bool,
+) -> eyre::Result<()> {
+    let [auction_addr] = auctions[..] else {
+        eyre::bail!("pass exactly one --auction with --bid-ids, or use --all");
+    };
+    if args.bid_ids.is_empty() {
+        eyre::bail!("pass --bid-ids to claim specific bids, or --all to claim everything");
+    }
+
+    let ctx = ChainContext::with_signer(rpc_url, private_key).await?;
+    let claims = claim_cmd::claim(
+        &ctx,
+        auction_addr,
+        &args.bid_ids,
+        args.gas_limit(),
+        cold_storage,
+        confirmation,
+    )
+    .await?;
+
+    let amounts = Amounts::fetch(&ctx, auction_addr, raw).await?;
+    let mut claimed = TokenAmount::ZERO;
+    let mut failed = 0;
+    for claim in claims {
+        match claim {
+            Ok(claim) => {
+                println!(
+                    "{}: claimed {} from {} bids (tx {})",
+                    auction_addr,
+                    amounts.tokens(claim.total_tokens.as_u256()),
+                    claim.bid_ids.len(),
+                    claim.tx_hash
+                );
+                claimed += claim.total_tokens;
+            }
+            Err(error) => {
+                failed += 1;
+                eprintln!("{auction_addr}: claim failed: {error}");
+            }
+        }
+    }
+
+    if let Some(target) = cold_storage
+        && !claimed.is_zero()
+    {
+        let moved = claim_cmd::move_to_cold_storage(&ctx, auction_addr, target, claimed).await?;
+        print_cold_storage_move(auction_addr, &moved, &amounts);
+    }
+    if failed > 0 {
+        eyre::bail!("{failed} claim transactions failed");
+    }
+    Ok(())
+}
+
+fn print_cold_storage_move(auction: Address, moved: &ColdStorageMove, amounts: &Amounts) {
+    match moved {
+        ColdStorageMove::Planned(plan) => println!(
+            "{auction}: would move {} to {} (dry run)",
+            amounts.tokens(plan.amount.as_u256()),
+            plan.to
         ),
-        Err(err) => {
-            eprintln!("{err}");
-            std::process::exit(1);
+        ColdStorageMove::Sent(result) => println!(
+            "{auction}: moved {} to {} (tx {})",
+            amounts.tokens(result.plan.amount.as_u256()),
+            result.plan.to,
+            result.tx_hash
+        ),
+    }
+}
+
+async fn handle_exit(
+    rpc_url: &str,
+    private_key: &str,
+    auction: Address,
+    args: ExitArgs,
+    confirmation: &Confirmation,
+    raw: bool,
+) -> eyre::Result<()> {
+    let ctx = ChainContext::with_signer(rpc_url, private_key).await?;
+    let amounts = Amounts::fetch(&ctx, auction, raw).await?;
+    let plan = exit_cmd::preview(&ctx, auction, args.bid_id).await?;
+    print_exit_preview(&plan.preview, &amounts);
+    if args.preview {
+        return Ok(());
+    }
+
+    let result = exit_cmd::exit(&ctx, &plan, confirmation).await?;
+
+    println!(
+        "Exited bid {}: tokens_filled={} currency_refunded={} (tx {})",
+        result.bid_id.as_u256(),
+        amounts.tokens(result.tokens_filled.as_u256()),
+        amounts.currency(result.currency_refunded.as_u256()),
+        result.tx_hash
+    );
+    Ok(())
+}
+
+/// Each exit's estimated tokens and refund, and why the auction would
+/// refuse it if it would.
+fn print_exit_preview(preview: &ExitPreview, amounts: &Amounts) {
+    println!("Exit preview for bid {}", preview.bid_id.as_u256());
+    for estimate in [&preview.exit_bid, &preview.exit_partially_filled] {
+        let status = match &estimate.rejected {
+            Some(error) => format!("not allowed: {error}"),
+            None => "allowed".to_string(),
+        };
+        println!(
+            "  {:<24} tokens_filled={} currency_refunded={} ({status})",
+            estimate.variant.to_string(),
+            amounts.tokens(estimate.fill.tokens_filled.as_u256()),
+            amounts.currency(estimate.fill.projected_refund.as_u256()),
+        );
+    }
+    let hints = &preview.hints;
+    println!(
+        "  hints: last fully filled checkpoint {}, outbid at {}",
+        hints.last_fully_filled_checkpoint_block.as_u64(),
+        hints
+            .outbid_block
+            .map_or("none".to_string(), |block| block.as_u64().to_string())
+    );
+}
+
+async fn handle_checkpoint(
+    rpc_url: &str,
+    private_key: &str,
+    auction: Address,
+    confirmation: &Confirmation,
+    raw: bool,
+) -> eyre::Result<()> {
+    let ctx = ChainContext::with_signer(rpc_url, private_key).await?;
+    let result = checkpoint_cmd::poke(&ctx, auction, confirmation).await?;
+    let amounts = Amounts::fetch(&ctx, auction, raw).await?;
+
+    println!(
+        "Checkpointed at block {}: clearing_price={} cumulative_mps={} (tx {})",
+        result.checkpoint.block.as_u64(),
+        amounts.price(result.checkpoint.clearing_price),
+        result.checkpoint.cumulative_mps.as_u24(),
+        result.tx_hash
+    );
+    Ok(())
+}
+
+async fn handle_create_auction(
+    rpc_url: &str,
+    settings: &Settings,
+    args: CreateAuctionArgs,
+    confirmation: &Confirmation,
+) -> eyre::Result<()> {
+    let factory = match args.factory {
+        Some(factory) => factory,
+        None => settings.portfolio()?.factory_address()?.ok_or_else(|| {
+            eyre::eyre!("--factory or [portfolio].factory is required for `create-auction`")
+        })?,
+    };
+    let request = CreateAuctionRequest {
+        factory,
+        token: args.token,
+        total_supply: args.total_supply,
+        params: AuctionParameters {
+            currency: CurrencyAddr::new(args.currency.unwrap_or_default()),
+            tokens_recipient: args.tokens_recipient,
+            funds_recipient: args.funds_recipient,
+            start_block: BlockNumber::new(args.start_block),
+            end_block: BlockNumber::new(args.end_block),
+            claim_block: BlockNumber::new(args.claim_block),
+            tick_spacing: TickSpacing::new(args.tick_spacing),
+            validation_hook: HookAddr::new(args.validation_hook.unwrap_or_default()),
+            floor_price: Price::new(args.floor_price),
+            required_currency_raised: CurrencyAmount::new(args.required_currency_raised),
+            steps: args.steps,
+        },
+        salt: args.salt.into(),
+    };
+
+    // Linting simulates from the signer when there is one.
+    let private_key = if args.lint_only {
+        settings.optional_private_key()?
+    } else {
+        Some(settings.private_key("create-auction")?)
+    };
+    let ctx = match &private_key {
+        Some(key) => ChainContext::with_signer(rpc_url, key).await?,
+        None => ChainContext::read_only(rpc_url).await?,
+    };
+
+    let issues = create_auction_cmd::lint(&ctx, &request).await?;
+    if !issues.is_empty() {
+        println!("The contracts would reject this auction:");
+        for issue in &issues {
+            match issue.contract_error() {
+                Some(error) => println!("  - {issue} ({error})"),
+                None => println!("  - {issue}"),
+            }
         }
+        eyre::bail!(
+            "{} problem(s) with the auction parameters; nothing was sent",
+            issues.len()
+        );
+    }
+    println!("Auction parameters pass every check the contracts make");
+    if args.lint_only {
+        return Ok(());
+    }
+
+    let created = create_auction_cmd::create(&ctx, &request, confirmation).await?;
+    println!(
+        "Created auction {} (tx {}); transfer it the supply and call onTokensReceived() before block {}",
+        created.auction, created.tx_hash, args.start_block
+    );
+    Ok(())
+}
+
+async fn handle_depth(
+    rpc_url: &str,
+    auction: Address,
+    args: DepthArgs,
+    raw: bool,
+) -> eyre::Result<()> {
+    let ctx = ChainContext::read_only(rpc_url).await?;
+    let output = depth_cmd::depth(&ctx, auction, args.levels).await?;
+
+    if args.json {
+        println!("{}", serde_json::to_string(&output)?);
+    } else {
+        let amounts = Amounts::fetch(&ctx, auction, raw).await?;
+        print_depth(&output, &amounts);
+    }
+    Ok(())
+}
+
+/// Highest price first, down to the clearing price, with a bar for the
+/// demand at each tick and the cumulative demand from the clearing price up.
+fn print_depth(output: &DepthOutput, amounts: &Amounts) {
+    const BAR_WIDTH: usize = 30;
+
+    println!(
+        "Depth of {} at block {} ({} levels)",
+        output.auction,
+        output.block,
+        output.levels.len()
+    );
+    println!(
+        "{:>40}  {:>24}  {:>24}  demand",
+        price_header(amounts),
+        "demand",
+        "cumulative"
+    );
+    if output.truncated {
+        println!("{:>40}", "...");
     }
+    let max_demand = output
+        .levels
+        .iter()
+        .map(|level| level.demand)
+        .max()
+        .unwrap_or_default()
+        .max(U256::from(1));
+    for level in output.levels.iter().rev() {
+        let bar = (level.demand * U256::from(BAR_WIDTH) / max_demand).saturating_to::<usize>();
+        println!(
+            "{:>40}  {:>24}  {:>24}  {}",
+            amounts.price(Price::new(level.price_q96)),
+            amounts.currency(level.demand),
+            amounts.currency(level.cumulative),
+            "#".repeat(bar)
+        );
+    }
+    println!(
+        "{:>40}  clearing price",
+        amounts.price(Price::new(output.clearing_price))
+    );
 }
 
-async fn handle_status(rpc_url: &str, args: StatusArgs) -> eyre::Result<()> {
-    // Parse addresses and bid id
-    let lens_addr: Address = args.lens.parse()?;
-    let auction_addr: Address = args.auction.parse()?;
-    let bid_id_u256: U256 = parse_u256(&args.bid_id)?;
+/// Header of a price column, naming the Q96 form when amounts are raw.
+async fn handle_market(
+    rpc_url: &str,
+    query: &MarketQuery,
+    json: bool,
+    raw: bool,
+) -> eyre::Result<()> {
+    let ctx = ChainContext::read_only(rpc_url).await?;
+    let output = market_cmd::market(&ctx, query).await?;
 
-    let output = status_cmd::status(rpc_url, auction_addr, lens_addr, bid_id_u256).await?;
-    println!("{output:?}");
+    if json {
+        println!("{}", serde_json::to_string(&output)?);
+    } else {
+        let amounts = Amounts::fetch(&ctx, query.auction, raw).await?;
+        print_market(&output, &amounts);
+    }
     Ok(())
 }
 
-fn parse_u256(s: &str) -> eyre::Result<U256> {
-    if let Some(stripped) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
-        Ok(U256::from_str_radix(stripped, 16)?)
+/// The demand around the clearing price, the largest competing owners with
+/// their share of it, a bar per max price bucket, highest first like
+/// `depth`, and the owner's own bids.
+fn print_market(output: &MarketOutput, amounts: &Amounts) {
+    const BAR_WIDTH: usize = 30;
+
+    println!(
+        "{} active bids from {} owners on {} at block {}, {} in total",
+        output.bids,
+        output.owners,
+        output.auction,
+        output.block,
+        amounts.currency(output.total_demand)
+    );
+    println!(
+        "Clearing price {}: {} above, {} at or below",
+        amounts.price(Price::new(output.clearing_price)),
+        amounts.currency(output.demand_above_clearing),
+        amounts.currency(output.demand_at_or_below_clearing)
+    );
+    if output.bids == 0 {
+        return;
+    }
+
+    println!(
+        "\nTop {} competing owners hold {:.1}% of the demand",
+        output.top_owners.len(),
+        output.top_share * 100.0
+    );
+    println!("{:<42}  {:>6}  {:>24}", "owner", "bids", "demand");
+    for owner in &output.top_owners {
+        println!(
+            "{:<42}  {:>6}  {:>24}",
+            owner.owner.to_string(),
+            owner.bids,
+            amounts.currency(owner.demand)
+        );
+    }
+
+    println!(
+        "\n{:>40}  {:>6}  {:>24}  demand",
+        price_header(amounts),
+        "bids",
+        "demand"
+    );
+    let max_demand = output
+        .price_histogram
+        .iter()
+        .map(|bucket| bucket.demand)
+        .max()
+        .unwrap_or_default()
+        .max(U256::from(1));
+    for bucket in output.price_histogram.iter().rev() {
+        let bar = (bucket.demand * U256::from(BAR_WIDTH) / max_demand).saturating_to::<usize>();
+        println!(
+            "{:>40}  {:>6}  {:>24}  {}",
+            format!("from {}", amounts.price(Price::new(bucket.low_q96))),
+            bucket.bids,
+            amounts.currency(bucket.demand),
+            "#".repeat(bar)
+        );
+    }
+
+    let (Some(owner), Some(share)) = (output.owner, output.owner_share) else {
+        return;
+    };
+    println!(
+        "\n{} has {} active bids, {:.1}% of the demand",
+        owner,
+        output.owner_bids.len(),
+        share * 100.0
+    );
+    for bid in &output.owner_bids {
+        let distance = match (bid.ticks_above_clearing, bid.above_clearing) {
+            (Some(ticks), Some(fraction)) => {
+                format!("{ticks} ticks ({:.2}%) above clearing", fraction * 100.0)
+            }
+            _ => "at or below clearing".to_string(),
+        };
+        println!(
+            "  #{:<6}  {:>40}  {:>24}  {}",
+            bid.bid_id,
+            amounts.price(Price::new(bid.max_price_q96)),
+            amounts.currency(bid.amount),
+            distance
+        );
+    }
+}
+
+fn price_header(amounts: &Amounts) -> &'static str {
+    if amounts.is_raw() {
+        "price_q96"
     } else {
-        Ok(U256::from_str_radix(s, 10)?)
+        "price"
     }
 }
+
+async fn handle_quote(
+    rpc_url: &str,
+    auction: Address,
+    args: QuoteArgs,
+    raw: bool,
+) -> eyre::Result<()> {
+    let query = QuoteQuery {
+        auction,
+        target_tokens: args.target_tokens,
+        price: args.price,
+        budget: args.budget,
+        max_bid_amount: args.max_bid_amount,
+    };
+    let ctx = ChainContext::read_only(rpc_url).await?;
+    let output = quote_cmd::quote(&ctx, &query).await?;
+    let amounts = Amounts::fetch(&ctx, auction, raw).await?;
+    print_quote(&output, &amounts);
+    Ok(())
+}
+
+fn print_quote(output: &QuoteOutput, amounts: &Amounts) {
+    println!("Quote for {} at block {}", output.auction, output.block);
+    println!("  projected price:  {}", amounts.price(output.price));
+    println!(
+        "  remaining supply: {}",
+        amounts.tokens(output.remaining_supply.as_u256())
+    );
+    println!(
+        "  bid amount:       {}",
+        amounts.currency(output.size.amount.as_u256())
+    );
+    println!(
+        "  expected tokens:  {}",
+        amounts.tokens(output.size.tokens.as_u256())
+    );
+    if let Some(limit) = output.size.limited_by {
+        let reason = match limit {
+            SizeLimit::Supply => "only the remaining supply is left to buy",
+            SizeLimit::Budget => "capped by the budget",
+            SizeLimit::BidAmount => "capped by the max bid amount",
+            SizeLimit::TotalSpend => "capped by the total spend limit",
+        };
+        println!("  below target: {reason}");
+    }
+}
+
+async fn handle_ladder(
+    rpc_url: &str,
+    private_key: Option<&str>,
+    auction: Address,
+    args: LadderArgs,
+    confirmation: &Confirmation,
+    raw: bool,
+) -> eyre::Result<()> {
+    let query = LadderQuery {
+        auction,
+        low: args.low,
+        high: args.high,
+        rungs: args.rungs,
+        total: args.total,
+    };
+
+    let ctx = match private_key {
+        Some(private_key) => ChainContext::with_signer(rpc_url, private_key).await?,
+        None => ChainContext::read_only(rpc_url).await?,
+    };
+    let amounts = Amounts::fetch(&ctx, auction, raw).await?;
+    if args.backtest {
+        let report = ladder_cmd::backtest(&ctx, &query).await?;
+        print_backtest_report(&report, &amounts);
+        return Ok(());
+    }
+
+    let preview = ladder_cmd::preview(&ctx, &query).await?;
+    print_ladder_preview(&preview, &amounts);
+
+    if !args.execute {
+        println!("Preview only; pass --execute to submit these bids");
+        return Ok(());
+    }
+
+    let rungs = preview.ladder.rungs.len();
+    let result = ladder_cmd::execute(&ctx, auction, preview.ladder, confirmation).await?;
+    println!(
+        "Submitted {} of {rungs} rungs ({} skipped)",
+        result.bids_submitted, result.skipped
+    );
+    print_spend(&result, &amounts);
+    Ok(())
+}
+
+/// Rungs and initialized ticks in one table, highest price first, with a bar
+/// for the demand at or above each row's price.
+fn print_ladder_preview(preview: &LadderPreview, amounts: &Amounts) {
+    const BAR_WIDTH: usize = 30;
+
+    let ticks = preview
+        .curve
+        .ticks
+        .iter()
+        .map(|tick| (tick.price, "tick".to_string(), tick.demand));
+    let rungs = preview
+        .ladder
+        .rungs
+        .iter()
+        .enumerate()
+        .map(|(index, rung)| (rung.max_price, format!("rung {}", index + 1), rung.amount));
+    let mut rows: Vec<_> = ticks.chain(rungs).collect();
+    rows.sort_by_key(|(price, ..)| std::cmp::Reverse(price.as_u256()));
+
+    let clearing_price = preview.clearing_price;
+    let max_demand = preview
+        .curve
+        .demand_at_or_above(Price::ZERO)
+        .as_u256()
+        .max(U256::from(1));
+
+    println!(
+        "Ladder of {} rungs totalling {} (clearing price {})",
+        preview.ladder.rungs.len(),
+        amounts.currency(preview.ladder.total().as_u256()),
+        amounts.price(clearing_price)
+    );
+    println!(
+        "{:<8}  {:>40}  {:>24}  {:>24}  {:<8}  demand",
+        "row",
+        price_header(amounts),
+        "amount",
+        "demand_at_or_above",
+        "vs_cp"
+    );
+    for (price, label, amount) in rows {
+        let demand = preview.curve.demand_at_or_above(price).as_u256();
+        let bar = (demand * U256::from(BAR_WIDTH) / max_demand).saturating_to::<usize>();
+        let position = if price > clearing_price {
+            "above".to_string()
+        } else if price == clearing_price {
+            // ATM bids are only partially filled; show how much.
+            preview
+                .atm_fill
+                .map_or("at".to_string(), |fill| format!("at {:.0}%", fill * 100.0))
+        } else {
+            "below".to_string()
+        };
+        println!(
+            "{label:<8}  {:>40}  {:>24}  {:>24}  {position:<8}  {}",
+            amounts.price(price),
+            amounts.currency(amount.as_u256()),
+            amounts.currency(demand),
+            "#".repeat(bar)
+        );
+    }
+}
+
+fn print_backtest_report(report: &BacktestReport, amounts: &Amounts) {
+    println!(
+        "Replayed {} blocks; final clearing price {}",
+        report.blocks_replayed,
+        report
+            .clearing_price
+            .map_or("unknown".to_string(), |price| amounts.price(price))
+    );
+    println!(
+        "{:<6}  {:>10}  {:>40}  {:>24}  fill",
+        "bid",
+        "block",
+        price_header(amounts),
+        "amount"
+    );
+    for (index, bid) in report.bids.iter().enumerate() {
+        let fill = match bid.fill {
+            SimulatedFill::Filled { tokens, .. } => {
+                format!("filled {}", amounts.tokens(tokens.as_u256()))
+            }
+            SimulatedFill::AtClearing => "at clearing (partial)".to_string(),
+            SimulatedFill::Outbid => "outbid".to_string(),
+        };
+        println!(
+            "{:<6}  {:>10}  {:>40}  {:>24}  {fill}",
+            index + 1,
+            bid.block.as_u64(),
+            amounts.price(bid.max_price),
+            amounts.currency(bid.amount.as_u256())
+        );
+    }
+    println!(
+        "Committed {}, spent {}, tokens {}, utilization {:.1}%",
+        amounts.currency(report.committed().as_u256()),
+        amounts.currency(report.spent().as_u256()),
+        amounts.tokens(report.tokens_filled().as_u256()),
+        report.utilization() * 100.0
+    );
+    if let Some(price) = report.average_price() {
+        println!("Average price {}", amounts.price(price));
+    }
+    if report.rejected > 0 {
+        println!("{} bids would have been rejected", report.rejected);
+    }
+}
+
+async fn handle_run(
+    rpc_url: &str,
+    private_key: &str,
+    auction: Address,
+    args: RunArgs,
+    strategy: Option<StrategyConfig>,
+    start_at: Option<TimeTarget>,
+    raw: bool,
+) -> eyre::Result<()> {
+    let options = run_options(rpc_url, private_key, auction, args, strategy, start_at).await?;
+    let ctx = ChainContext::with_signer(rpc_url, private_key).await?;
+    let result = run_cmd::run(&ctx, options).await?;
+    print_run_result(&result, &Amounts::fetch(&ctx, auction, raw).await?);
+    Ok(())
+}
+
+async fn handle_daemon(
+    rpc_url: &str,
+    private_key: &str,
+    auction: Address,
+    args: DaemonArgs,
+    strategy: Option<StrategyConfig>,
+    start_at: Option<TimeTarget>,
+    raw: bool,
+) -> eyre::Result<()> {
+    let options = DaemonOptions {
+        listen: args.listen,
+        state_dir: args.state_dir.unwrap_or_else(daemon_cmd::default_state_dir),
+    };
+    let run = run_options(rpc_url, private_key, auction, args.run, strategy, start_at).await?;
+    let ctx = ChainContext::with_signer(rpc_url, private_key).await?;
+    let result = daemon_cmd::daemon(&ctx, run, options).await?;
+    print_run_result(&result, &Amounts::fetch(&ctx, auction, raw).await?);
+    Ok(())
+}
+
+/// What `run` and `daemon` share, from the `run` flags.
+async fn run_options(
+    rpc_url: &str,
+    private_key: &str,
+    auction: Address,
+    args: RunArgs,
+    strategy: Option<StrategyConfig>,
+    start_at: Option<TimeTarget>,
+) -> eyre::Result<RunOptions> {
+    let private_relay = match &args.private_rpc {
+        Some(url) => {
+            let relay = ChainContext::with_signer(url, private_key).await?;
+            let mut relay =
+                PrivateRelay::new(relay.provider).with_fallback_after(args.private_fallback_blocks);
+            if !args.private_intents.is_empty() {
+                let kinds: Vec<_> = args.private_intents.iter().map(|i| i.kind()).collect();
+                relay = relay.for_intents(&kinds);
+            }
+            Some(relay)
+        }
+        None => None,
+    };
+    let smart_account = match &args.bundler_rpc {
+        Some(url) => Some(smart_account(url, private_key, &args).await?),
+        None => None,
+    };
+    let shadow_fork = if args.shadow_fork {
+        // Forks the first endpoint given; anvil needs no more than one.
+        let fork_url = rpc_url.split(',').next().unwrap_or(rpc_url).trim();
+        let anvil = AnvilFork::new(fork_url);
+        let fork = tokio::task::spawn_blocking(move || ShadowFork::spawn(&anvil)).await??;
+        Some(Arc::new(fork))
+    } else {
+        None
+    };
+    let staleness = match args.max_block_lag {
+        Some(max_lag) => {
+            let mut guard = StalenessGuard::new(max_lag);
+            if let Some(url) = &args.reference_rpc {
+                let reference = ProviderBuilder::new().connect(url).await?.erased();
+                guard = guard.with_reference(reference);
+            }
+            Some(guard)
+        }
+        None => None,
+    };
+    let options = RunOptions {
+        auction,
+        bid_ids: args.bid_ids,
+        from_block: args.from_block,
+        checkpoint_every: args.checkpoint_every,
+        dust_threshold: args.dust_threshold,
+        atm_wait_blocks: args.atm_wait_blocks,
+        failure_cooldown: args.failure_cooldown,
+        metrics_addr: args.metrics_addr,
+        lock_dir: args.lock_dir.unwrap_or_else(lock::default_lock_dir),
+        bids_dir: args.bids_dir.unwrap_or_else(store::default_store_dir),
+        if_locked: args.if_locked,
+        strategy,
+        tags: args.tags,
+        start_at,
+        private_relay,
+        smart_account,
+        shadow_fork,
+        staleness,
+        no_checkpoint_fill: args.no_checkpoint_fill,
+        verify_after_execute: args.verify_after_execute,
+        activity_log: args.activity_log,
+        record: args.record,
+        control: None,
+    };
+    Ok(options)
+}
+
+/// The account `--bundler-rpc` sends from, signed for by `private_key`.
+async fn smart_account(url: &str, private_key: &str, args: &RunArgs) -> eyre::Result<SmartAccount> {
+    let signer: Arc<PrivateKeySigner> = Arc::new(private_key.parse()?);
+    let bundler = ProviderBuilder::new().connect(url).await?.erased();
+    let mut account = match (args.smart_account, args.eip7702_delegate) {
+        (Some(address), _) => SmartAccount::erc4337(bundler, address, signer),
+        (None, Some(delegate)) => SmartAccount::eip7702(bundler, delegate, signer),
+        (None, None) => {
+            eyre::bail!("--bundler-rpc needs --smart-account or --eip7702-delegate")
+        }
+    };
+    if let Some(entry_point) = args.entry_point {
+        account = account.with_entry_point(entry_point);
+    }
+    if let Some(address) = args.paymaster {
+        let mut paymaster = Paymaster::new(address);
+        if let Some(data) = args.paymaster_data.clone() {
+            paymaster = paymaster.with_data(data);
+        }
+        if let Some(gas) = args.paymaster_verification_gas {
+            paymaster = paymaster.with_verification_gas_limit(gas);
+        }
+        if let Some(gas) = args.paymaster_post_op_gas {
+            paymaster = paymaster.with_post_op_gas_limit(gas);
+        }
+        account = account.with_paymaster(paymaster);
+    }
+    Ok(account)
+}
+
+fn print_run_result(result: &OrchestratorResult, amounts: &Amounts) {
+    println!(
+        "Finished ({:?}) after {} blocks: {} submitted, {} exited, {} claims, {} checkpoints, {} skipped, {} expired, {} suppressed",
+        result.reason,
+        result.blocks_processed,
+        result.bids_submitted,
+        result.bids_exited,
+        result.claims,
+        result.checkpoints,
+        result.skipped,
+        result.expired,
+        result.suppressed
+    );
+    if result.stale_blocks > 0 {
+        println!(
+            "Held intents back on {} blocks that trailed the chain head",
+            result.stale_blocks
+        );
+    }
+    print_spend(result, amounts);
+}
+
+async fn handle_replay(
+    rpc_url: &str,
+    recording: &std::path::Path,
+    options: ReplayOptions,
+) -> eyre::Result<()> {
+    let decisions = audit::read_decisions(recording)?;
+    let ctx = ChainContext::read_only(rpc_url).await?;
+    let report = replay_cmd::replay(&ctx, decisions, options).await?;
+
+    if report.is_identical() {
+        println!(
+            "Replayed {} blocks: every decision matches the recording",
+            report.blocks_replayed
+        );
+        return Ok(());
+    }
+    println!(
+        "Replayed {} blocks: {} decided differently",
+        report.blocks_replayed,
+        report.diffs.len()
+    );
+    for diff in &report.diffs {
+        println!("block {}:", diff.block.as_u64());
+        println!("  recorded: {}", replay_cmd::describe(&diff.recorded));
+        println!("  replayed: {}", replay_cmd::describe(&diff.replayed));
+    }
+    Ok(())
+}
+
+/// Gas and currency a run spent.
+fn print_spend(result: &OrchestratorResult, amounts: &Amounts) {
+    println!(
+        "Spent {} gas ({} in fees) and {} ({} committed, {} refunded)",
+        result.gas_used,
+        amounts.native(result.gas_fees),
+        amounts.currency(result.currency_spent().as_u256()),
+        amounts.currency(result.currency_committed.as_u256()),
+        amounts.currency(result.currency_refunded.as_u256())
+    );
+}
+
+async fn handle_tui(
+    rpc_url: &str,
+    private_key: Option<&str>,
+    auction: Address,
+    args: TuiArgs,
+) -> eyre::Result<()> {
+    let prepared = args
+        .bid_price
+        .zip(args.bid_amount)
+        .map(|(price, amount)| PreparedBid {
+            max_price: Price::new(price),
+            amount: CurrencyAmount::new(amount),
+        });
+    let options = TuiOptions {
+        auction,
+        owner: args.owner,
+        bid_ids: args.bid_ids,
+        from_block: args.from_block,
+        prepared,
+    };
+
+    let ctx = match private_key {
+        Some(private_key) => ChainContext::with_signer(rpc_url, private_key).await?,
+        None => ChainContext::read_only(rpc_url).await?,
+    };
+    tui::run(&ctx, options).await
+}
+
+async fn handle_analyze(
+    rpc_url: &str,
+    auction: Address,
+    args: AnalyzeArgs,
+    raw: bool,
+) -> eyre::Result<()> {
+    let ctx = ChainContext::read_only(rpc_url).await?;
+    let store_dir = args.bids_dir.unwrap_or_else(store::default_store_dir);
+    let analytics =
+        analyze_cmd::analyze(&ctx, auction, args.bid_id, args.from_block, &store_dir).await?;
+    print_analytics(&analytics, &Amounts::fetch(&ctx, auction, raw).await?);
+    Ok(())
+}
+
+fn print_analytics(analytics: &BidAnalytics, amounts: &Amounts) {
+    let price = |price: Option<Price>| price.map_or("-".to_string(), |p| amounts.price(p));
+    println!("Bid {} of {}", analytics.bid_id.as_u256(), analytics.owner);
+    println!(
+        "  amount:           {} (max price {})",
+        amounts.currency(analytics.amount.as_u256()),
+        amounts.price(analytics.max_price)
+    );
+    println!(
+        "  tokens filled:    {}",
+        amounts.tokens(analytics.tokens_filled.as_u256())
+    );
+    println!(
+        "  currency spent:   {} ({} refunded)",
+        amounts.currency(analytics.currency_spent().as_u256()),
+        amounts.currency(analytics.currency_refunded.as_u256())
+    );
+    println!("  fill ratio:       {:.2}%", analytics.fill_ratio() * 100.0);
+    println!("  realized price:   {}", price(analytics.realized_price()));
+    println!(
+        "  clearing price:   {} at entry, {} at exit",
+        price(analytics.entry_clearing_price),
+        price(analytics.exit_clearing_price)
+    );
+    println!(
+        "  holding period:   {} blocks ({} to {})",
+        analytics.holding_period(),
+        analytics.submitted_block.as_u64(),
+        analytics.exited_block.as_u64()
+    );
+    if let Some(block) = analytics.claimed_block {
+        println!("  claimed at block: {}", block.as_u64());
+    }
+    if !analytics.tags.is_empty() {
+        println!("  tags:             {}", analytics.tags.join(", "));
+    }
+}
+
+async fn handle_report(
+    rpc_url: &str,
+    query: &ReportQuery,
+    format: ReportFormat,
+    out: Option<PathBuf>,
+) -> eyre::Result<()> {
+    let ctx = ChainContext::read_only(rpc_url).await?;
+    let report = report_cmd::report(&ctx, query).await?;
+    let rendered = report_cmd::render(&report, format)?;
+
+    match out {
+        Some(path) => {
+            std::fs::write(&path, rendered)?;
+            println!("Wrote report on {} to {}", query.auction, path.display());
+        }
+        None => println!("{rendered}"),
+    }
+    Ok(())
+}
+
+async fn handle_snapshot(
+    rpc_url: &str,
+    query: &SnapshotQuery,
+    out: Option<PathBuf>,
+) -> eyre::Result<()> {
+    let ctx = ChainContext::read_only(rpc_url).await?;
+    let snapshot = snapshot_cmd::snapshot(&ctx, query).await?;
+    let path = out.unwrap_or_else(|| {
+        PathBuf::from(format!(
+            "snapshot-{}.json",
+            snapshot.state.current_block.as_u64()
+        ))
+    });
+    std::fs::write(&path, serde_json::to_string_pretty(&snapshot)?)?;
+    println!(
+        "Wrote snapshot of {} at block {} with {} bids to {}",
+        query.auction,
+        snapshot.state.current_block.as_u64(),
+        snapshot.bids.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+fn handle_diff(args: &DiffArgs) -> eyre::Result<()> {
+    let before = snapshot_cmd::read(&args.a)?;
+    let after = snapshot_cmd::read(&args.b)?;
+    let changes = before.diff(&after);
+    if changes.is_empty() {
+        println!("No changes");
+    }
+    for change in changes {
+        println!("{change}");
+    }
+    Ok(())
+}
+
+async fn handle_attest(
+    rpc_url: &str,
+    private_key: &str,
+    auction: Address,
+    args: AttestArgs,
+) -> eyre::Result<()> {
+    let operator: PrivateKeySigner = private_key.parse()?;
+    let query = AttestQuery {
+        auction,
+        owner: args.owner.unwrap_or_else(|| operator.address()),
+        bid_ids: args.bid_ids,
+        from_block: args.from_block,
+    };
+
+    let ctx = ChainContext::read_only(rpc_url).await?;
+    let signed = attest_cmd::attest(&ctx, &operator, query).await?;
+    let json = serde_json::to_string(&signed)?;
+
+    match args.out {
+        Some(path) => {
+            std::fs::write(&path, json)?;
+            println!(
+                "Wrote attestation for {} bids to {}",
+                signed.attestation.bids.len(),
+                path.display()
+            );
+        }
+        None => println!("{json}"),
+    }
+    Ok(())
+}
@@ -0,0 +1,257 @@
+// src/parse.rs
+//! Parsing for addresses and numbers typed by users, shared by CLI flags and
+//! config fields so both report the same errors.
+use alloy::primitives::{Address, U256, aliases::U24};
+use flux_core::{Mps, StepSpec};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ParseError {
+    #[error("`{input}` is not an address: expected 40 hex characters, with or without 0x")]
+    Address { input: String },
+    #[error("`{input}` fails its EIP-55 checksum; did you mean {expected}?")]
+    Checksum { input: String, expected: Address },
+    #[error("`{input}` is not a number: expected decimal or 0x-prefixed hex")]
+    Number { input: String },
+    #[error("`{input}` is not an auction: expected an address or a name from `[auctions]`")]
+    Auction { input: String },
+    #[error(
+        "`{input}` is not a tag: expected a label like `campaign=launch`, without spaces or commas"
+    )]
+    Tag { input: String },
+    #[error("`{input}` is not a step: expected `MPS:BLOCKS`, with MPS below 2^24")]
+    Step { input: String },
+}
+
+/// An auction as typed by the user: an address, or the name of an entry in
+/// the config's `[auctions]` registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuctionRef {
+    Address(Address),
+    Name(String),
+}
+
+/// A bid id, optionally qualified with the auction it belongs to as
+/// `AUCTION:ID`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BidRef {
+    pub auction: Option<AuctionRef>,
+    pub id: U256,
+}
+
+/// A [`ParseError`] for a named config field.
+#[derive(Debug, Error)]
+#[error("{field}: {source}")]
+pub struct FieldError {
+    pub field: String,
+    #[source]
+    pub source: ParseError,
+}
+
+impl ParseError {
+    pub fn in_field(self, field: impl Into<String>) -> FieldError {
+        FieldError {
+            field: field.into(),
+            source: self,
+        }
+    }
+}
+
+/// Parses an address, with or without `0x`.
+///
+/// Mixed-case input is taken as EIP-55 checksummed and rejected if the
+/// checksum does not match; all-lowercase or all-uppercase input carries no
+/// checksum and is accepted as is.
+pub fn address(input: &str) -> Result<Address, ParseError> {
+    let trimmed = input.trim();
+    let hex = strip_hex_prefix(trimmed).unwrap_or(trimmed);
+    let invalid = || ParseError::Address {
+        input: input.to_string(),
+    };
+
+    if hex.len() != 40 {
+        return Err(invalid());
+    }
+    let address: Address = hex.parse().map_err(|_| invalid())?;
+
+    let has_lower = hex.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = hex.chars().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper && address.to_checksum(None)[2..] != *hex {
+        return Err(ParseError::Checksum {
+            input: input.to_string(),
+            expected: address,
+        });
+    }
+
+    Ok(address)
+}
+
+/// Parses an auction address, or a registry name: a letter followed by
+/// letters, digits, `-` or `_`. Forty hex digits are always an address.
+pub fn auction(input: &str) -> Result<AuctionRef, ParseError> {
+    let trimmed = input.trim();
+    let looks_like_address = strip_hex_prefix(trimmed).is_some()
+        || (trimmed.len() == 40 && trimmed.chars().all(|c| c.is_ascii_hexdigit()));
+    if !looks_like_address && is_auction_name(trimmed) {
+        return Ok(AuctionRef::Name(trimmed.to_string()));
+    }
+    address(input)
+        .map(AuctionRef::Address)
+        .map_err(|error| match error {
+            ParseError::Address { input } => ParseError::Auction { input },
+            error => error,
+        })
+}
+
+/// Whether `name` can name an auction in `[auctions]`.
+pub fn is_auction_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Parses a bid id, or `AUCTION:ID` for a bid in a specific auction.
+pub fn bid_ref(input: &str) -> Result<BidRef, ParseError> {
+    match input.split_once(':') {
+        Some((auction_input, id)) => Ok(BidRef {
+            auction: Some(auction(auction_input)?),
+            id: u256(id)?,
+        }),
+        None => Ok(BidRef {
+            auction: None,
+            id: u256(input)?,
+        }),
+    }
+}
+
+/// Parses a `uint256` from decimal or `0x`-prefixed hex.
+pub fn u256(input: &str) -> Result<U256, ParseError> {
+    let trimmed = input.trim();
+    let parsed = match strip_hex_prefix(trimmed) {
+        Some(hex) => U256::from_str_radix(hex, 16),
+        None => U256::from_str_radix(trimmed, 10),
+    };
+    parsed.map_err(|_| ParseError::Number {
+        input: input.to_string(),
+    })
+}
+
+/// Parses a bid tag: any label without whitespace or commas, by convention
+/// `key=value`.
+pub fn tag(input: &str) -> Result<String, ParseError> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() || trimmed.contains(|c: char| c.is_whitespace() || c == ',') {
+        return Err(ParseError::Tag {
+            input: input.to_string(),
+        });
+    }
+    Ok(trimmed.to_string())
+}
+
+/// Parses a supply schedule step, `MPS:BLOCKS`.
+pub fn step(input: &str) -> Result<StepSpec, ParseError> {
+    let invalid = || ParseError::Step {
+        input: input.to_string(),
+    };
+    let (mps, blocks) = input.trim().split_once(':').ok_or_else(invalid)?;
+    let mps: u32 = mps.trim().parse().map_err(|_| invalid())?;
+    Ok(StepSpec {
+        mps: Mps::new(U24::try_from(mps).map_err(|_| invalid())?),
+        blocks: blocks.trim().parse().map_err(|_| invalid())?,
+    })
+}
+
+fn strip_hex_prefix(input: &str) -> Option<&str> {
+    input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHECKSUMMED: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+
+    #[test]
+    fn addresses_parse_with_or_without_prefix() {
+        let expected: Address = CHECKSUMMED.parse().unwrap();
+        assert_eq!(address(CHECKSUMMED), Ok(expected));
+        assert_eq!(address(&CHECKSUMMED[2..]), Ok(expected));
+        assert_eq!(address(&CHECKSUMMED.to_lowercase()), Ok(expected));
+        assert_eq!(address(&format!(" {CHECKSUMMED} ")), Ok(expected));
+    }
+
+    #[test]
+    fn bad_checksum_is_rejected() {
+        let bad = CHECKSUMMED.replace("aA", "Aa");
+        assert!(matches!(address(&bad), Err(ParseError::Checksum { .. })));
+        assert!(matches!(address("0x1234"), Err(ParseError::Address { .. })));
+    }
+
+    #[test]
+    fn auctions_parse_as_addresses_or_names() {
+        let expected: Address = CHECKSUMMED.parse().unwrap();
+        assert_eq!(auction(CHECKSUMMED), Ok(AuctionRef::Address(expected)));
+        assert_eq!(
+            auction(&CHECKSUMMED[2..].to_lowercase()),
+            Ok(AuctionRef::Address(expected))
+        );
+        assert_eq!(
+            auction("mytoken-launch"),
+            Ok(AuctionRef::Name("mytoken-launch".into()))
+        );
+        assert!(matches!(auction("0x1234"), Err(ParseError::Auction { .. })));
+        assert!(matches!(
+            auction("my token"),
+            Err(ParseError::Auction { .. })
+        ));
+    }
+
+    #[test]
+    fn numbers_parse_as_decimal_or_hex() {
+        assert_eq!(u256("42"), Ok(U256::from(42)));
+        assert_eq!(u256("0x2a"), Ok(U256::from(42)));
+        assert!(matches!(u256("2a"), Err(ParseError::Number { .. })));
+    }
+
+    #[test]
+    fn bid_refs_may_name_their_auction() {
+        assert_eq!(
+            bid_ref("7"),
+            Ok(BidRef {
+                auction: None,
+                id: U256::from(7)
+            })
+        );
+        assert_eq!(
+            bid_ref("launch:0x2a"),
+            Ok(BidRef {
+                auction: Some(AuctionRef::Name("launch".into())),
+                id: U256::from(42)
+            })
+        );
+        assert!(matches!(
+            bid_ref("launch:abc"),
+            Err(ParseError::Number { .. })
+        ));
+    }
+
+    #[test]
+    fn tags_are_single_labels() {
+        assert_eq!(tag(" campaign=launch "), Ok("campaign=launch".into()));
+        assert!(matches!(tag(""), Err(ParseError::Tag { .. })));
+        assert!(matches!(tag("a,b"), Err(ParseError::Tag { .. })));
+        assert!(matches!(tag("my tag"), Err(ParseError::Tag { .. })));
+    }
+
+    #[test]
+    fn steps_parse_as_mps_and_blocks() {
+        let parsed = step("100000:100").unwrap();
+        assert_eq!(parsed.mps.as_u24(), U24::from(100_000));
+        assert_eq!(parsed.blocks, 100);
+        assert!(matches!(step("16777216:1"), Err(ParseError::Step { .. })));
+        assert!(matches!(step("100000"), Err(ParseError::Step { .. })));
+    }
+}
@@ -1,23 +1,209 @@
-use alloy::primitives::Address;
-use alloy::providers::{Provider, ReqwestProvider};
-use alloy::transports::http::Http;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
-pub type HttpProvider = Provider<Http<ReqwestProvider>>;
+use alloy::{
+    primitives::Address,
+    providers::{DynProvider, Provider, ProviderBuilder, WsConnect},
+    pubsub::PubSubConnect,
+    rpc::client::{ClientBuilder, RpcClient},
+    signers::local::PrivateKeySigner,
+    transports::{BoxTransport, http::Http, utils::guess_local_url},
+};
+use flux_core::{
+    AuctionClient, AuctionConfig, TrackedBid, ValidationHook,
+    failover::{FailoverLayer, FailoverService},
+};
+use tower::Layer;
 
+use crate::config_cache::{ConfigCache, default_cache_dir};
+
+/// Anvil and Hardhat's chain id. Local chains are reset and redeployed
+/// constantly, so their auction configs are never cached.
+const LOCAL_CHAIN_ID: u64 = 31337;
+
+/// How often [`ChainContext::monitor_endpoints`] probes the endpoints.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Connection to a chain, optionally with a signing wallet attached.
+///
+/// The RPC URL may be a comma-separated list of endpoints for the same
+/// chain. Calls then go to the first healthy one, failing over to the next
+/// on errors and timeouts, and the first websocket endpoint serves
+/// subscriptions.
 #[derive(Clone)]
 pub struct ChainContext {
-    pub provider: Arc<HttpProvider>,
+    pub provider: DynProvider,
+    /// Provider for block and log subscriptions: a websocket endpoint when
+    /// one was given, else `provider`, which then polls.
+    pub subscriptions: DynProvider,
     pub chain_id: u64,
+    /// Address of the attached signer, if any.
+    pub sender: Option<Address>,
+    /// Where auction configs are cached; `None` fetches them every time.
+    pub config_cache: Option<ConfigCache>,
+    /// The endpoints behind `provider`, when more than one was given.
+    failover: Option<FailoverService<BoxTransport>>,
 }
 
 impl ChainContext {
-    pub fn new(rpc_url: &str, chain_id: u64) -> eyre::Result<Self> {
-        let transport = Http::new(reqwest::Client::new(), rpc_url.parse()?);
-        let provider = Provider::new(transport);
+    /// Connect without a signer; only read calls will succeed.
+    pub async fn read_only(rpc_url: &str) -> eyre::Result<Self> {
+        let endpoints = Endpoints::connect(rpc_url).await?;
+        let provider = ProviderBuilder::new()
+            .connect_client(endpoints.calls.clone())
+            .erased();
+        Self::new(provider, endpoints, None).await
+    }
+
+    /// Connect with `private_key` as the sending wallet.
+    pub async fn with_signer(rpc_url: &str, private_key: &str) -> eyre::Result<Self> {
+        let signer: PrivateKeySigner = private_key.parse()?;
+        let sender = signer.address();
+        let endpoints = Endpoints::connect(rpc_url).await?;
+        let provider = ProviderBuilder::new()
+            .wallet(signer)
+            .connect_client(endpoints.calls.clone())
+            .erased();
+        Self::new(provider, endpoints, Some(sender)).await
+    }
+
+    async fn new(
+        provider: DynProvider,
+        endpoints: Endpoints,
+        sender: Option<Address>,
+    ) -> eyre::Result<Self> {
+        let chain_id = provider.get_chain_id().await?;
+        let subscriptions = match endpoints.subscriptions {
+            Some(client) => ProviderBuilder::new().connect_client(client).erased(),
+            None => provider.clone(),
+        };
         Ok(Self {
-            provider: Arc::new(provider),
+            provider,
+            subscriptions,
             chain_id,
+            sender,
+            config_cache: Some(ConfigCache::new(&default_cache_dir())),
+            failover: endpoints.failover,
+        })
+    }
+
+    /// Health-checks the endpoints in the background, so an outage is
+    /// noticed and a recovered primary used again without waiting for
+    /// requests to fail. Does nothing with a single endpoint.
+    pub fn monitor_endpoints(&self) {
+        if let Some(failover) = self.failover.clone() {
+            tokio::spawn(failover.monitor(HEALTH_CHECK_INTERVAL));
+        }
+    }
+
+    /// Always fetch auction configs instead of using the cache.
+    pub fn without_config_cache(mut self) -> Self {
+        self.config_cache = None;
+        self
+    }
+
+    /// The auction's config, from the cache when it holds one.
+    pub async fn auction_config(&self, auction: Address) -> eyre::Result<AuctionConfig> {
+        let cache = match &self.config_cache {
+            Some(cache) if self.chain_id != LOCAL_CHAIN_ID => cache,
+            _ => return Ok(AuctionClient::fetch_config(&self.provider, auction).await?),
+        };
+        if let Some(config) = cache.load(self.chain_id, auction)? {
+            tracing::debug!(%auction, "using cached auction config");
+            return Ok(config);
+        }
+
+        let config = AuctionClient::fetch_config(&self.provider, auction).await?;
+        if let Err(error) = cache.store(self.chain_id, &config) {
+            tracing::warn!(%error, "failed to cache auction config");
+        }
+        Ok(config)
+    }
+
+    /// An [`AuctionClient`] for `auction`, built from its cached config when
+    /// there is one.
+    pub async fn auction_client(
+        &self,
+        auction: Address,
+        owner: Address,
+        hook: Arc<dyn ValidationHook>,
+        tracked_bids: Vec<TrackedBid>,
+    ) -> eyre::Result<AuctionClient<DynProvider>> {
+        let config = self.auction_config(auction).await?;
+        Ok(AuctionClient::from_config(
+            self.provider.clone(),
+            config,
+            owner,
+            hook,
+            tracked_bids,
+        ))
+    }
+
+    pub fn sender(&self) -> eyre::Result<Address> {
+        self.sender
+            .ok_or_else(|| eyre::eyre!("a private key is required to send transactions"))
+    }
+}
+
+/// RPC clients for a comma-separated list of endpoints.
+struct Endpoints {
+    calls: RpcClient,
+    subscriptions: Option<RpcClient>,
+    failover: Option<FailoverService<BoxTransport>>,
+}
+
+impl Endpoints {
+    /// A single endpoint is connected to directly. Several are put behind a
+    /// [`FailoverLayer`], HTTP ones first in the order given so calls use
+    /// HTTP while it is up; the first websocket that connects also serves
+    /// subscriptions.
+    async fn connect(rpc_url: &str) -> eyre::Result<Self> {
+        let urls: Vec<&str> = rpc_url
+            .split(',')
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .collect();
+        match urls[..] {
+            [] => eyre::bail!("no RPC URL given"),
+            [url] => {
+                return Ok(Self {
+                    calls: ClientBuilder::default().connect(url).await?,
+                    subscriptions: None,
+                    failover: None,
+                });
+            }
+            _ => {}
+        }
+
+        let (websockets, http): (Vec<&str>, Vec<&str>) = urls
+            .iter()
+            .partition(|url| url.starts_with("ws://") || url.starts_with("wss://"));
+        let mut transports = http
+            .iter()
+            .map(|url| Ok(BoxTransport::new(Http::new(url.parse()?))))
+            .collect::<eyre::Result<Vec<_>>>()?;
+        let mut subscriptions = None;
+        for url in websockets {
+            match WsConnect::new(url).into_service().await {
+                Ok(frontend) => {
+                    subscriptions.get_or_insert_with(|| RpcClient::new(frontend.clone(), false));
+                    transports.push(BoxTransport::new(frontend));
+                }
+                Err(error) => tracing::warn!(%error, "skipping unreachable websocket endpoint"),
+            }
+        }
+        if transports.is_empty() {
+            eyre::bail!(
+                "none of the {} RPC endpoints could be connected",
+                urls.len()
+            );
+        }
+
+        let is_local = urls.iter().all(guess_local_url);
+        let failover = FailoverLayer::new().layer(transports);
+        Ok(Self {
+            calls: RpcClient::new(failover.clone(), is_local),
+            subscriptions,
+            failover: Some(failover),
         })
     }
 }
@@ -0,0 +1,212 @@
+// src/secrets.rs
+use std::{env, io};
+
+use aes_gcm::{
+    Aes256Gcm, KeyInit, Nonce,
+    aead::{Aead, AeadCore, OsRng, rand_core::RngCore},
+};
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+/// Prefix of a secret encrypted with a passphrase by `secrets encrypt`.
+pub const ENCRYPTED_PREFIX: &str = "encrypted:";
+/// Prefix of a secret kept in the OS keyring under the given name.
+pub const KEYRING_PREFIX: &str = "keyring:";
+/// Passphrase for `encrypted:` secrets; prompted for when unset.
+pub const PASSPHRASE_ENV: &str = "FLUX_PASSPHRASE";
+
+/// Keyring service the secrets are stored under.
+#[cfg(feature = "keyring")]
+const KEYRING_SERVICE: &str = "flux-cli";
+
+const VERSION: u8 = 1;
+/// scrypt cost of new secrets: N = 2^15, about 32 MiB and a fraction of a second.
+const LOG_N: u8 = 15;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug, Error)]
+pub enum SecretError {
+    #[error("encrypted secret is malformed: {0}")]
+    Malformed(&'static str),
+    #[error("wrong passphrase, or the encrypted secret was altered")]
+    Decrypt,
+    #[error("passphrases do not match")]
+    PassphraseMismatch,
+    #[error("failed to read passphrase: {0}")]
+    Prompt(#[from] io::Error),
+    #[cfg(feature = "keyring")]
+    #[error("keyring entry `{name}`: {source}")]
+    Keyring {
+        name: String,
+        #[source]
+        source: keyring::Error,
+    },
+    #[cfg(not(feature = "keyring"))]
+    #[error("`keyring:{0}` needs flux-cli built with the `keyring` feature")]
+    KeyringDisabled(String),
+}
+
+/// Whether `value` points at a secret rather than holding it.
+pub fn is_reference(value: &str) -> bool {
+    value.starts_with(ENCRYPTED_PREFIX) || value.starts_with(KEYRING_PREFIX)
+}
+
+/// The plaintext of a secret from flags or config: `encrypted:` secrets are
+/// decrypted and `keyring:` ones read from the OS keyring, anything else is
+/// returned as is. The result is wiped from memory when dropped.
+pub fn resolve(value: &str) -> Result<Zeroizing<String>, SecretError> {
+    if value.starts_with(ENCRYPTED_PREFIX) {
+        let passphrase = passphrase(false)?;
+        decrypt(value, &passphrase)
+    } else if let Some(name) = value.strip_prefix(KEYRING_PREFIX) {
+        keyring_secret(name)
+    } else {
+        Ok(Zeroizing::new(value.to_string()))
+    }
+}
+
+/// Encrypts `secret` with a key derived from `passphrase`, as an
+/// `encrypted:` reference to put in config instead of the secret.
+pub fn encrypt(secret: &str, passphrase: &str) -> Result<String, SecretError> {
+    encrypt_with_cost(secret, passphrase, LOG_N)
+}
+
+/// Decrypts an `encrypted:` reference made by [`encrypt`].
+pub fn decrypt(reference: &str, passphrase: &str) -> Result<Zeroizing<String>, SecretError> {
+    let encoded = reference
+        .strip_prefix(ENCRYPTED_PREFIX)
+        .ok_or(SecretError::Malformed("missing `encrypted:` prefix"))?;
+    let bytes = hex::decode(encoded).map_err(|_| SecretError::Malformed("not hex"))?;
+    let (&[version, log_n], rest) = bytes
+        .split_first_chunk::<2>()
+        .ok_or(SecretError::Malformed("too short"))?;
+    if version != VERSION {
+        return Err(SecretError::Malformed("unknown version"));
+    }
+    let (salt, rest) = rest
+        .split_first_chunk::<SALT_LEN>()
+        .ok_or(SecretError::Malformed("too short"))?;
+    let (nonce, ciphertext) = rest
+        .split_first_chunk::<NONCE_LEN>()
+        .ok_or(SecretError::Malformed("too short"))?;
+
+    let plaintext = cipher(passphrase, salt, log_n)?
+        .decrypt(&Nonce::from(*nonce), ciphertext)
+        .map_err(|_| SecretError::Decrypt)?;
+    String::from_utf8(plaintext)
+        .map(Zeroizing::new)
+        .map_err(|_| SecretError::Malformed("secret is not UTF-8"))
+}
+
+/// The passphrase from `FLUX_PASSPHRASE`, or else prompted for on the
+/// terminal; `confirm` asks twice, for encrypting.
+pub fn passphrase(confirm: bool) -> Result<Zeroizing<String>, SecretError> {
+    if let Ok(passphrase) = env::var(PASSPHRASE_ENV) {
+        return Ok(Zeroizing::new(passphrase));
+    }
+    let passphrase = Zeroizing::new(rpassword::prompt_password("Passphrase: ")?);
+    if confirm {
+        let again = Zeroizing::new(rpassword::prompt_password("Repeat passphrase: ")?);
+        if *again != *passphrase {
+            return Err(SecretError::PassphraseMismatch);
+        }
+    }
+    Ok(passphrase)
+}
+
+/// Reads the secret stored under `name`.
+#[cfg(feature = "keyring")]
+pub fn keyring_secret(name: &str) -> Result<Zeroizing<String>, SecretError> {
+    keyring_entry(name)?
+        .get_password()
+        .map(Zeroizing::new)
+        .map_err(|source| SecretError::Keyring {
+            name: name.to_string(),
+            source,
+        })
+}
+
+#[cfg(not(feature = "keyring"))]
+pub fn keyring_secret(name: &str) -> Result<Zeroizing<String>, SecretError> {
+    Err(SecretError::KeyringDisabled(name.to_string()))
+}
+
+/// Stores `secret` under `name`, returning the `keyring:` reference to it.
+#[cfg(feature = "keyring")]
+pub fn store_in_keyring(name: &str, secret: &str) -> Result<String, SecretError> {
+    keyring_entry(name)?
+        .set_password(secret)
+        .map_err(|source| SecretError::Keyring {
+            name: name.to_string(),
+            source,
+        })?;
+    Ok(format!("{KEYRING_PREFIX}{name}"))
+}
+
+#[cfg(not(feature = "keyring"))]
+pub fn store_in_keyring(name: &str, _secret: &str) -> Result<String, SecretError> {
+    Err(SecretError::KeyringDisabled(name.to_string()))
+}
+
+#[cfg(feature = "keyring")]
+fn keyring_entry(name: &str) -> Result<keyring::Entry, SecretError> {
+    keyring::Entry::new(KEYRING_SERVICE, name).map_err(|source| SecretError::Keyring {
+        name: name.to_string(),
+        source,
+    })
+}
+
+fn encrypt_with_cost(secret: &str, passphrase: &str, log_n: u8) -> Result<String, SecretError> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+    let ciphertext = cipher(passphrase, &salt, log_n)?
+        .encrypt(&nonce, secret.as_bytes())
+        .expect("AES-GCM encrypts secrets of any practical length");
+
+    let mut bytes = vec![VERSION, log_n];
+    bytes.extend_from_slice(&salt);
+    bytes.extend_from_slice(&nonce);
+    bytes.extend_from_slice(&ciphertext);
+    Ok(format!("{ENCRYPTED_PREFIX}{}", hex::encode(bytes)))
+}
+
+/// AES-256-GCM keyed with the scrypt hash of `passphrase`.
+fn cipher(passphrase: &str, salt: &[u8], log_n: u8) -> Result<Aes256Gcm, SecretError> {
+    let params = scrypt::Params::new(log_n, 8, 1, 32)
+        .map_err(|_| SecretError::Malformed("invalid scrypt cost"))?;
+    let mut key = Zeroizing::new([0u8; 32]);
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, key.as_mut())
+        .expect("32 bytes is a valid scrypt output length");
+    Ok(Aes256Gcm::new(&(*key).into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypted_secrets_round_trip_with_the_right_passphrase() {
+        // Cheap scrypt cost so the test stays fast.
+        let reference = encrypt_with_cost("0xsecret", "hunter2", 4).unwrap();
+        assert!(reference.starts_with(ENCRYPTED_PREFIX));
+        assert!(!reference.contains("secret"));
+
+        assert_eq!(*decrypt(&reference, "hunter2").unwrap(), "0xsecret");
+        assert!(matches!(
+            decrypt(&reference, "hunter3"),
+            Err(SecretError::Decrypt)
+        ));
+        assert!(matches!(
+            decrypt("encrypted:zz", "hunter2"),
+            Err(SecretError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn plain_values_pass_through() {
+        assert_eq!(*resolve("0xabc").unwrap(), "0xabc");
+    }
+}
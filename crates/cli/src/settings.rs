@@ -0,0 +1,292 @@
+// src/settings.rs
+use alloy::{
+    primitives::Address,
+    signers::local::{LocalSignerError, PrivateKeySigner},
+};
+use flux_core::{ScheduleError, StrategyConfig, TimeTarget};
+use std::{
+    cell::OnceCell,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+use crate::{
+    config::{
+        AuctionEntry, BidsConfig, ColdStorageConfig, ConfigError, DEFAULT_CONFIG_PATH,
+        PortfolioConfig, load_config,
+    },
+    parse::{self, AuctionRef, FieldError},
+    secrets::{self, SecretError},
+};
+
+/// Global flags layered over the config file.
+///
+/// The config file is only read the first time a command asks for it, so
+/// on-chain commands that get everything from flags work without `bids.toml`.
+#[derive(Debug)]
+pub struct Settings {
+    config_path: PathBuf,
+    /// Whether `config_path` was passed explicitly rather than defaulted.
+    config_explicit: bool,
+    rpc_url: Option<String>,
+    private_key: Option<String>,
+    config: OnceCell<Option<BidsConfig>>,
+}
+
+#[derive(Debug, Error)]
+pub enum SettingsError {
+    #[error("--rpc-url, CCA_RPC_URL or `[rpc] urls` is required for `{command}`")]
+    MissingRpcUrl { command: &'static str },
+    #[error("--private-key, PRIVATE_KEY or an encrypted `[bid] owner` is required for `{command}`")]
+    MissingPrivateKey { command: &'static str },
+    #[error("--owner or a signing key is required for `{command}`")]
+    MissingOwner { command: &'static str },
+    #[error("invalid private key: {0}")]
+    PrivateKey(#[from] LocalSignerError),
+    #[error("`{command}` needs a config file, but none was found at {path}")]
+    MissingConfig {
+        command: &'static str,
+        path: PathBuf,
+    },
+    #[error("no auction named `{name}` in `[auctions]` of {path}")]
+    UnknownAuction { name: String, path: PathBuf },
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error(transparent)]
+    Field(#[from] FieldError),
+    #[error(transparent)]
+    Secret(#[from] SecretError),
+    #[error(transparent)]
+    Schedule(#[from] ScheduleError),
+}
+
+impl Settings {
+    pub fn new(
+        config_path: Option<PathBuf>,
+        rpc_url: Option<String>,
+        private_key: Option<String>,
+    ) -> Self {
+        let config_explicit = config_path.is_some();
+        Self {
+            config_path: config_path.unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_PATH)),
+            config_explicit,
+            rpc_url,
+            private_key,
+            config: OnceCell::new(),
+        }
+    }
+
+    pub fn config_path(&self) -> &Path {
+        &self.config_path
+    }
+
+    /// The RPC endpoints, comma-separated: the flag's, else `[rpc] urls`
+    /// from the config file.
+    pub fn rpc_url(&self, command: &'static str) -> Result<String, SettingsError> {
+        if let Some(rpc_url) = &self.rpc_url {
+            return Ok(rpc_url.clone());
+        }
+        self.optional_config()?
+            .map(|config| config.rpc.urls.join(","))
+            .filter(|urls| !urls.is_empty())
+            .ok_or(SettingsError::MissingRpcUrl { command })
+    }
+
+    pub fn private_key(&self, command: &'static str) -> Result<Zeroizing<String>, SettingsError> {
+        self.optional_private_key()?
+            .ok_or(SettingsError::MissingPrivateKey { command })
+    }
+
+    /// The signing key from the flag, else from `[bid] owner` in the config
+    /// file when that is an `encrypted:` or `keyring:` reference. References
+    /// are resolved here, so the key only exists in memory.
+    pub fn optional_private_key(&self) -> Result<Option<Zeroizing<String>>, SettingsError> {
+        let value = match &self.private_key {
+            Some(key) => Some(key.as_str()),
+            None => self
+                .optional_config()?
+                .and_then(|config| config.bid.owner.as_deref())
+                .filter(|owner| secrets::is_reference(owner)),
+        };
+        Ok(value.map(secrets::resolve).transpose()?)
+    }
+
+    /// The owner to look at: `flag` (an `--owner` argument), else the
+    /// signing key's address.
+    pub fn owner(
+        &self,
+        flag: Option<Address>,
+        command: &'static str,
+    ) -> Result<Address, SettingsError> {
+        self.optional_owner(flag)?
+            .ok_or(SettingsError::MissingOwner { command })
+    }
+
+    /// Like [`Settings::owner`], for commands that also run without one.
+    pub fn optional_owner(&self, flag: Option<Address>) -> Result<Option<Address>, SettingsError> {
+        if flag.is_some() {
+            return Ok(flag);
+        }
+        let signer = self
+            .optional_private_key()?
+            .map(|key| key.parse::<PrivateKeySigner>())
+            .transpose()?;
+        Ok(signer.map(|signer| signer.address()))
+    }
+
+    /// The config file, for commands that cannot run without it.
+    pub fn config(&self, command: &'static str) -> Result<&BidsConfig, SettingsError> {
+        self.optional_config()?
+            .ok_or_else(|| SettingsError::MissingConfig {
+                command,
+                path: self.config_path.clone(),
+            })
+    }
+
+    /// The `[portfolio]` section, or its defaults when there is no config file.
+    pub fn portfolio(&self) -> Result<PortfolioConfig, SettingsError> {
+        Ok(self
+            .optional_config()?
+            .map(|config| config.portfolio.clone())
+            .unwrap_or_default())
+    }
+
+    /// The address of `auction`, looking names up in `[auctions]`.
+    pub fn auction(&self, auction: &AuctionRef) -> Result<Address, SettingsError> {
+        match auction {
+            AuctionRef::Address(address) => Ok(*address),
+            AuctionRef::Name(name) => Ok(self.auction_entry(name)?.address(name)?),
+        }
+    }
+
+    /// The addresses of `auctions`.
+    pub fn auctions(&self, auctions: &[AuctionRef]) -> Result<Vec<Address>, SettingsError> {
+        auctions
+            .iter()
+            .map(|auction| self.auction(auction))
+            .collect()
+    }
+
+    /// The lens registered for `auction`, when it was given by name and its
+    /// entry has one.
+    pub fn auction_lens(&self, auction: &AuctionRef) -> Result<Option<Address>, SettingsError> {
+        match auction {
+            AuctionRef::Address(_) => Ok(None),
+            AuctionRef::Name(name) => Ok(self.auction_entry(name)?.lens_address(name)?),
+        }
+    }
+
+    /// `[portfolio].auctions`, which may also name `[auctions]` entries.
+    pub fn portfolio_auctions(&self) -> Result<Vec<Address>, SettingsError> {
+        let auctions = self
+            .portfolio()?
+            .auctions
+            .iter()
+            .map(|auction| parse::auction(auction).map_err(|e| e.in_field("[portfolio].auctions")))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.auctions(&auctions)
+    }
+
+    fn auction_entry(&self, name: &str) -> Result<&AuctionEntry, SettingsError> {
+        self.optional_config()?
+            .and_then(|config| config.auctions.get(name))
+            .ok_or_else(|| SettingsError::UnknownAuction {
+                name: name.to_string(),
+                path: self.config_path.clone(),
+            })
+    }
+
+    /// The `[strategy]` section, or `None` when there is no config file.
+    pub fn strategy(&self) -> Result<Option<StrategyConfig>, SettingsError> {
+        Ok(self
+            .optional_config()?
+            .and_then(|config| config.strategy.clone()))
+    }
+
+    /// `[schedule] start_at`, or `None` when it or the config file is
+    /// missing.
+    pub fn start_at(&self) -> Result<Option<TimeTarget>, SettingsError> {
+        let start_at = self
+            .optional_config()?
+            .and_then(|config| config.schedule.start_at.clone());
+        Ok(start_at.map(|start_at| start_at.parse()).transpose()?)
+    }
+
+    /// The `[cold_storage]` section, or an empty allowlist when there is no
+    /// config file.
+    pub fn cold_storage(&self) -> Result<ColdStorageConfig, SettingsError> {
+        Ok(self
+            .optional_config()?
+            .map(|config| config.cold_storage.clone())
+            .unwrap_or_default())
+    }
+
+    /// Loads the config file once. A missing file is only an error when its
+    /// path was given explicitly.
+    fn optional_config(&self) -> Result<Option<&BidsConfig>, SettingsError> {
+        if let Some(config) = self.config.get() {
+            return Ok(config.as_ref());
+        }
+
+        let loaded = match load_config(&self.config_path) {
+            Ok(config) => Some(config),
+            Err(ConfigError::Read { source, .. })
+                if source.kind() == ErrorKind::NotFound && !self.config_explicit =>
+            {
+                None
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        Ok(self.config.get_or_init(|| loaded).as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_default_config_is_not_an_error() {
+        let mut settings = Settings::new(None, None, None);
+        settings.config_path = PathBuf::from("does-not-exist.toml");
+
+        assert_eq!(settings.portfolio().unwrap(), PortfolioConfig::default());
+        assert!(matches!(
+            settings.config("bids"),
+            Err(SettingsError::MissingConfig { .. })
+        ));
+    }
+
+    #[test]
+    fn missing_explicit_config_is_an_error() {
+        let settings = Settings::new(Some(PathBuf::from("does-not-exist.toml")), None, None);
+
+        assert!(matches!(
+            settings.portfolio(),
+            Err(SettingsError::Config(ConfigError::Read { .. }))
+        ));
+    }
+
+    #[test]
+    fn owner_is_the_flag_else_the_signers_address() {
+        let mut settings = Settings::new(None, None, None);
+        settings.config_path = PathBuf::from("does-not-exist.toml");
+        let flag = Address::repeat_byte(7);
+        assert_eq!(settings.owner(Some(flag), "wallet").unwrap(), flag);
+        assert!(matches!(
+            settings.owner(None, "wallet"),
+            Err(SettingsError::MissingOwner { command: "wallet" })
+        ));
+
+        let signer = PrivateKeySigner::random();
+        let key = alloy::hex::encode_prefixed(signer.to_bytes());
+        let settings = Settings::new(None, None, Some(key));
+        assert_eq!(
+            settings.optional_owner(None).unwrap(),
+            Some(signer.address())
+        );
+    }
+}
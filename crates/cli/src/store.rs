@@ -0,0 +1,235 @@
+// src/store.rs
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use alloy::primitives::{Address, B256, U256};
+use flux_core::{BidId, JournalEntry, JournalStore, TrackedBid};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum StoreError {
+    #[error("failed to access tracked bids at {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("tracked bids at {path} are not valid JSON: {source}")]
+    Corrupt {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// One entry of the store file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredBid {
+    id: U256,
+    tx_hash: B256,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+}
+
+/// Bids submitted from this machine for one (chain, auction, owner)
+/// campaign, kept as a JSON file so later runs find them without scanning
+/// logs.
+#[derive(Debug, Clone)]
+pub struct TrackedBidStore {
+    path: PathBuf,
+}
+
+impl TrackedBidStore {
+    pub fn new(dir: &Path, chain_id: u64, auction: Address, owner: Address) -> Self {
+        Self {
+            path: dir.join(format!("{chain_id}-{auction:#x}-{owner:#x}.json")),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Every recorded bid, oldest first; empty if nothing was recorded yet.
+    pub fn load(&self) -> Result<Vec<TrackedBid>, StoreError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(source) => return Err(self.io_error(source)),
+        };
+        let stored: Vec<StoredBid> =
+            serde_json::from_str(&contents).map_err(|source| StoreError::Corrupt {
+                path: self.path.clone(),
+                source,
+            })?;
+
+        Ok(stored
+            .into_iter()
+            .map(|bid| TrackedBid {
+                id: BidId::new(bid.id),
+                tx_hash: bid.tx_hash,
+                tags: bid.tags,
+            })
+            .collect())
+    }
+
+    /// Adds `bid` unless its id is already recorded.
+    pub fn record(&self, bid: &TrackedBid) -> Result<(), StoreError> {
+        self.record_all(std::slice::from_ref(bid))
+    }
+
+    /// Adds every bid whose id is not recorded yet, and the tags of
+    /// recorded ones that they lack.
+    pub fn record_all(&self, new: &[TrackedBid]) -> Result<(), StoreError> {
+        let mut bids = self.load()?;
+        let mut changed = false;
+        for bid in new {
+            match bids
+                .iter_mut()
+                .find(|seen| seen.id.as_u256() == bid.id.as_u256())
+            {
+                Some(seen) => {
+                    for tag in &bid.tags {
+                        if !seen.tags.contains(tag) {
+                            seen.tags.push(tag.clone());
+                            changed = true;
+                        }
+                    }
+                }
+                None => {
+                    bids.push(bid.clone());
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            return Ok(());
+        }
+
+        let stored: Vec<_> = bids
+            .into_iter()
+            .map(|bid| StoredBid {
+                id: bid.id.as_u256(),
+                tx_hash: bid.tx_hash,
+                tags: bid.tags,
+            })
+            .collect();
+        let json = serde_json::to_string_pretty(&stored).expect("tracked bids serialize");
+
+        // Write beside the store and rename, so a crash never leaves it half written.
+        let tmp = self.path.with_extension("json.tmp");
+        self.path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(&tmp, json))
+            .and_then(|()| fs::rename(&tmp, &self.path))
+            .map_err(|source| self.io_error(source))
+    }
+
+    fn io_error(&self, source: io::Error) -> StoreError {
+        StoreError::Io {
+            path: self.path.clone(),
+            source,
+        }
+    }
+}
+
+/// Transactions sent for one campaign, journaled before each is sent so a
+/// restarted run neither loses nor repeats one; kept beside the campaign's
+/// tracked bids.
+#[derive(Debug, Clone)]
+pub struct SendJournalFile {
+    path: PathBuf,
+}
+
+impl SendJournalFile {
+    pub fn new(dir: &Path, chain_id: u64, auction: Address, owner: Address) -> Self {
+        Self {
+            path: dir.join(format!("{chain_id}-{auction:#x}-{owner:#x}.sends.json")),
+        }
+    }
+
+    /// Every journaled transaction; empty if nothing was journaled yet.
+    pub fn load(&self) -> Result<Vec<JournalEntry>, StoreError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(source) => {
+                return Err(StoreError::Io {
+                    path: self.path.clone(),
+                    source,
+                });
+            }
+        };
+        serde_json::from_str(&contents).map_err(|source| StoreError::Corrupt {
+            path: self.path.clone(),
+            source,
+        })
+    }
+}
+
+impl JournalStore for SendJournalFile {
+    fn save(&self, entries: &[JournalEntry]) {
+        let json = serde_json::to_string_pretty(entries).expect("journal entries serialize");
+        // Write beside the file and rename, so a crash never leaves it half written.
+        let tmp = self.path.with_extension("json.tmp");
+        let written = self
+            .path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(&tmp, json))
+            .and_then(|()| fs::rename(&tmp, &self.path));
+        if let Err(error) = written {
+            tracing::warn!(%error, path = %self.path.display(), "failed to save send journal");
+        }
+    }
+}
+
+/// Default directory for tracked-bid stores.
+pub fn default_store_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("flux")
+        .join("bids")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_each_bid_once() {
+        let dir = std::env::temp_dir().join(format!("flux-store-test-{}", std::process::id()));
+        let store = TrackedBidStore::new(&dir, 1, Address::repeat_byte(0xaa), Address::ZERO);
+        assert!(store.load().unwrap().is_empty());
+
+        let bid = |id: u64, tags: &[&str]| TrackedBid {
+            id: BidId::new(U256::from(id)),
+            tx_hash: B256::repeat_byte(id as u8),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        };
+        store.record(&bid(1, &["strategy=ladder"])).unwrap();
+        store.record(&bid(2, &[])).unwrap();
+        // Seen again, a bid keeps its tags and gains new ones.
+        store.record(&bid(1, &["campaign=launch"])).unwrap();
+
+        let bids: Vec<_> = store
+            .load()
+            .unwrap()
+            .into_iter()
+            .map(|bid| (bid.id.as_u256().to::<u64>(), bid.tags))
+            .collect();
+        assert_eq!(
+            bids,
+            [
+                (1, vec!["strategy=ladder".into(), "campaign=launch".into()]),
+                (2, vec![])
+            ]
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,608 @@
+//! Live auction dashboard.
+//!
+//! Follows the block and event streams, redraws on every update, shows phase
+//! transitions on the status line and turns a couple of keys into intents
+//! for the [`IntentExecutor`].
+
+use std::{
+    collections::VecDeque,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+use alloy::{
+    primitives::{Address, B256, U256},
+    providers::DynProvider,
+};
+use eyre::Result;
+use flux_core::{
+    AuctionClient, AuctionEvent, AuctionPhase, AuctionWatchlist, Bid, BidDiscovery, BidId,
+    BlockNumber, BlockProducer, BoxBlockStream, BoxEventStream, ChainProfile, CurrencyAmount,
+    EventProducer, Intent, IntentExecutor, IntentOutcome, IntentResult, Moneyness, NoopHook, Price,
+    TrackedBid, ValidationHook, WatchlistEvent,
+};
+use futures::{
+    StreamExt,
+    channel::mpsc,
+    stream::{BoxStream, Fuse},
+};
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, List, ListItem, Paragraph, Row, Sparkline, Table, TableState},
+};
+
+use crate::provider::ChainContext;
+
+/// Clearing prices kept for the sparkline.
+const PRICE_HISTORY: usize = 120;
+/// Events kept in the recent events pane.
+const EVENT_HISTORY: usize = 50;
+
+/// What the dashboard follows.
+#[derive(Debug, Clone)]
+pub struct TuiOptions {
+    pub auction: Address,
+    /// Whose bids to list; defaults to the signer.
+    pub owner: Option<Address>,
+    /// Bids to list; discovered from `from_block` when empty.
+    pub bid_ids: Vec<U256>,
+    pub from_block: u64,
+    /// Bid sent when `b` is pressed.
+    pub prepared: Option<PreparedBid>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PreparedBid {
+    pub max_price: Price,
+    pub amount: CurrencyAmount,
+}
+
+/// Everything on screen.
+struct Dashboard {
+    auction: Address,
+    chain: ChainProfile,
+    block: Option<BlockNumber>,
+    phase: Option<AuctionPhase>,
+    clearing_prices: VecDeque<Price>,
+    bids: Vec<Bid>,
+    selected: usize,
+    events: VecDeque<AuctionEvent>,
+    prepared: Option<PreparedBid>,
+    can_send: bool,
+    status: String,
+}
+
+impl Dashboard {
+    fn new(
+        auction: Address,
+        chain: ChainProfile,
+        prepared: Option<PreparedBid>,
+        can_send: bool,
+    ) -> Self {
+        Self {
+            auction,
+            chain,
+            block: None,
+            phase: None,
+            clearing_prices: VecDeque::with_capacity(PRICE_HISTORY),
+            bids: Vec::new(),
+            selected: 0,
+            events: VecDeque::with_capacity(EVENT_HISTORY),
+            prepared,
+            can_send,
+            status: "waiting for the next block".to_string(),
+        }
+    }
+
+    fn on_block(&mut self, block: BlockNumber, phase: AuctionPhase, clearing_price: Option<Price>) {
+        self.block = Some(block);
+        self.phase = Some(phase);
+        if let Some(price) = clearing_price {
+            if self.clearing_prices.len() == PRICE_HISTORY {
+                self.clearing_prices.pop_front();
+            }
+            self.clearing_prices.push_back(price);
+        }
+    }
+
+    fn on_event(&mut self, event: AuctionEvent) {
+        if self.events.len() == EVENT_HISTORY {
+            self.events.pop_back();
+        }
+        self.events.push_front(event);
+    }
+
+    fn set_bids(&mut self, bids: Vec<Bid>) {
+        self.bids = bids;
+        self.selected = self.selected.min(self.bids.len().saturating_sub(1));
+    }
+
+    fn select_next(&mut self) {
+        if !self.bids.is_empty() {
+            self.selected = (self.selected + 1) % self.bids.len();
+        }
+    }
+
+    fn select_previous(&mut self) {
+        if !self.bids.is_empty() {
+            self.selected = (self.selected + self.bids.len() - 1) % self.bids.len();
+        }
+    }
+
+    fn selected_bid(&self) -> Option<&Bid> {
+        self.bids.get(self.selected)
+    }
+
+    fn clearing_price(&self) -> Option<Price> {
+        self.clearing_prices.back().copied()
+    }
+
+    fn render(&self, frame: &mut Frame) {
+        let [header, chart, body, footer] = Layout::vertical([
+            Constraint::Length(4),
+            Constraint::Length(7),
+            Constraint::Min(6),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [bids_area, events_area] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .areas(body);
+
+        let header_lines = vec![
+            Line::from(format!(
+                "block {}  phase {}",
+                self.block
+                    .map_or("-".to_string(), |b| b.as_u64().to_string()),
+                self.phase
+                    .as_ref()
+                    .map_or("-".to_string(), |phase| phase_label(phase, &self.chain))
+            )),
+            Line::from(format!(
+                "clearing price (q96) {}",
+                self.clearing_price()
+                    .map_or("-".to_string(), |p| p.as_u256().to_string())
+            )),
+        ];
+        frame.render_widget(
+            Paragraph::new(header_lines).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" auction {} ", self.auction)),
+            ),
+            header,
+        );
+
+        let points = sparkline_points(self.clearing_prices.iter().copied().collect());
+        frame.render_widget(
+            Sparkline::default()
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(" clearing price "),
+                )
+                .data(&points),
+            chart,
+        );
+
+        let clearing_price = self.clearing_price();
+        let rows = self.bids.iter().map(|bid| {
+            Row::new([
+                bid.id.as_u256().to_string(),
+                bid.max_price.as_u256().to_string(),
+                bid.amount.as_u256().to_string(),
+                bid.tokens_filled.as_u256().to_string(),
+                bid_label(bid, clearing_price),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Fill(3),
+                Constraint::Fill(2),
+                Constraint::Fill(2),
+                Constraint::Length(8),
+            ],
+        )
+        .header(
+            Row::new(["bid", "max_price_q96", "amount", "tokens", "status"])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .block(Block::default().borders(Borders::ALL).title(" my bids "));
+        let mut state =
+            TableState::default().with_selected((!self.bids.is_empty()).then_some(self.selected));
+        frame.render_stateful_widget(table, bids_area, &mut state);
+
+        let events: Vec<ListItem> = self
+            .events
+            .iter()
+            .map(|event| ListItem::new(describe_event(event)))
+            .collect();
+        frame.render_widget(
+            List::new(events).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" recent events "),
+            ),
+            events_area,
+        );
+
+        let mut keys = "q quit  ↑/↓ select".to_string();
+        if self.can_send {
+            if self.prepared.is_some() {
+                keys.push_str("  b submit bid");
+            }
+            keys.push_str("  x exit bid");
+        }
+        frame.render_widget(
+            Paragraph::new(format!("{keys}  |  {}", self.status)),
+            footer,
+        );
+    }
+}
+
+/// Scales prices into the window's own range so small moves stay visible.
+/// Every point is at least 1 so the line never disappears.
+fn sparkline_points(prices: Vec<Price>) -> Vec<u64> {
+    let Some(min) = prices.iter().map(|p| p.as_u256()).min() else {
+        return Vec::new();
+    };
+    let max = prices.iter().map(|p| p.as_u256()).max().unwrap_or(min);
+    let range = (max - min).max(U256::from(1));
+    prices
+        .iter()
+        .map(|price| ((price.as_u256() - min) * U256::from(99) / range).to::<u64>() + 1)
+        .collect()
+}
+
+fn phase_label(phase: &AuctionPhase, chain: &ChainProfile) -> String {
+    let eta = |blocks: u64| approx_duration(chain.duration_of(blocks));
+    match phase {
+        AuctionPhase::PreStart { blocks_until_start } => {
+            format!(
+                "starts in {blocks_until_start} blocks (~{})",
+                eta(*blocks_until_start)
+            )
+        }
+        AuctionPhase::PreTokens => "waiting for tokens".to_string(),
+        AuctionPhase::Active { blocks_remaining } => {
+            format!(
+                "active, {blocks_remaining} blocks left (~{})",
+                eta(*blocks_remaining)
+            )
+        }
+        AuctionPhase::Ended { blocks_until_claim } => {
+            format!(
+                "ended, claimable in {blocks_until_claim} blocks (~{})",
+                eta(*blocks_until_claim)
+            )
+        }
+        AuctionPhase::Claimable => "claimable".to_string(),
+    }
+}
+
+/// `duration` in its largest whole unit, e.g. `3h` or `45s`.
+fn approx_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3_600 => format!("{}m", secs / 60),
+        3_600..86_400 => format!("{}h", secs / 3_600),
+        _ => format!("{}d", secs / 86_400),
+    }
+}
+
+fn bid_label(bid: &Bid, clearing_price: Option<Price>) -> String {
+    if bid.exited_block.is_some() {
+        return "exited".to_string();
+    }
+    let status = clearing_price.map(|price| match bid.moneyness(price) {
+        Moneyness::ITM => "in",
+        Moneyness::ATM => "at",
+        Moneyness::OTM => "outbid",
+    });
+    status.unwrap_or("-").to_string()
+}
+
+fn describe_event(event: &AuctionEvent) -> String {
+    let block = event.block().as_u64();
+    match event {
+        AuctionEvent::BidSubmitted {
+            bid_id,
+            price,
+            amount,
+            ..
+        } => format!(
+            "{block}  bid {} at {} for {}",
+            bid_id.as_u256(),
+            price.as_u256(),
+            amount.as_u256()
+        ),
+        AuctionEvent::BidExited {
+            bid_id,
+            tokens_filled,
+            ..
+        } => format!(
+            "{block}  bid {} exited with {} tokens",
+            bid_id.as_u256(),
+            tokens_filled.as_u256()
+        ),
+        AuctionEvent::TokensClaimed {
+            bid_id,
+            tokens_filled,
+            ..
+        } => format!(
+            "{block}  bid {} claimed {} tokens",
+            bid_id.as_u256(),
+            tokens_filled.as_u256()
+        ),
+        AuctionEvent::CheckpointUpdated { clearing_price, .. } => {
+            format!("{block}  checkpoint at {}", clearing_price.as_u256())
+        }
+        AuctionEvent::ClearingPriceUpdated { clearing_price, .. } => {
+            format!("{block}  clearing price {}", clearing_price.as_u256())
+        }
+        AuctionEvent::TokensReceived { total_supply, .. } => {
+            format!("{block}  {} tokens received", total_supply.as_u256())
+        }
+        AuctionEvent::AuctionStepRecorded { end_block, mps, .. } => format!(
+            "{block}  step of {} mps until {}",
+            mps.as_u24(),
+            end_block.as_u64()
+        ),
+    }
+}
+
+fn describe_outcome(outcome: &IntentOutcome) -> String {
+    match outcome {
+        IntentOutcome::Success(IntentResult::BidSubmitted(result)) => {
+            format!(
+                "submitted bid {} (tx {})",
+                result.bid_id.as_u256(),
+                result.tx_hash
+            )
+        }
+        IntentOutcome::Success(IntentResult::BidExited(result)) => {
+            format!(
+                "exited bid {} (tx {})",
+                result.bid_id.as_u256(),
+                result.tx_hash
+            )
+        }
+        IntentOutcome::Success(result) => format!("{result:?}"),
+        IntentOutcome::Skipped { .. } => "skipped: below the dust threshold".to_string(),
+        IntentOutcome::Deferred { until, .. } => {
+            format!("deferred until block {}", until.as_u64())
+        }
+        IntentOutcome::Expired { execute_by, .. } => {
+            format!("expired: deadline was block {}", execute_by.as_u64())
+        }
+        IntentOutcome::Suppressed { until, .. } => {
+            format!(
+                "suppressed until block {}: failed just before",
+                until.as_u64()
+            )
+        }
+        IntentOutcome::Failed { error, .. } => format!("failed: {error}"),
+    }
+}
+
+/// Open the dashboard and run it until the user quits.
+pub async fn run(ctx: &ChainContext, options: TuiOptions) -> Result<()> {
+    let owner = options.owner.or(ctx.sender).unwrap_or_default();
+    let bid_ids = if !options.bid_ids.is_empty() {
+        options.bid_ids.into_iter().map(BidId::new).collect()
+    } else if owner.is_zero() {
+        Vec::new()
+    } else {
+        BidDiscovery::new(ctx.provider.clone())
+            .from_block(BlockNumber::new(options.from_block))
+            .bids_by_owner(options.auction, owner)
+            .await?
+    };
+    let tracked = bid_ids
+        .into_iter()
+        .map(|id| TrackedBid {
+            id,
+            tx_hash: B256::ZERO,
+            tags: Vec::new(),
+        })
+        .collect();
+
+    let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+    let client = ctx
+        .auction_client(options.auction, owner, hook, tracked)
+        .await?;
+    let chain = ChainProfile::for_chain(ctx.chain_id);
+    let executor = IntentExecutor::new(client).with_chain_profile(chain);
+
+    let blocks = BlockProducer::new(ctx.subscriptions.clone())
+        .into_stream()
+        .await?;
+    let events = EventProducer::new(ctx.subscriptions.clone(), options.auction)
+        .into_stream()
+        .await?;
+    let transitions = AuctionWatchlist::new(ctx.provider.clone(), &[options.auction])
+        .await?
+        .into_stream(
+            BlockProducer::new(ctx.subscriptions.clone())
+                .into_stream()
+                .await?,
+        );
+    let dashboard = Dashboard::new(
+        options.auction,
+        chain,
+        options.prepared,
+        ctx.sender.is_some_and(|sender| sender == owner),
+    );
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let keys = spawn_key_reader(stop.clone());
+    let mut terminal = ratatui::init();
+    let result = event_loop(
+        &mut terminal,
+        dashboard,
+        executor,
+        blocks.fuse(),
+        events.fuse(),
+        transitions.fuse(),
+        keys,
+    )
+    .await;
+    ratatui::restore();
+    stop.store(true, Ordering::Relaxed);
+    result
+}
+
+/// Crossterm's reader blocks, so keys come from a thread over a channel.
+fn spawn_key_reader(stop: Arc<AtomicBool>) -> mpsc::UnboundedReceiver<KeyEvent> {
+    let (sender, receiver) = mpsc::unbounded();
+    thread::spawn(move || {
+        while !stop.load(Ordering::Relaxed) {
+            match event::poll(Duration::from_millis(100)) {
+                Ok(false) => continue,
+                Ok(true) => {}
+                Err(_) => break,
+            }
+            match event::read() {
+                Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => {
+                    if sender.unbounded_send(key).is_err() {
+                        break;
+                    }
+                }
+                Ok(_) => {}
+                Err(_) => break,
+            }
+        }
+    });
+    receiver
+}
+
+async fn event_loop(
+    terminal: &mut DefaultTerminal,
+    mut dashboard: Dashboard,
+    mut executor: IntentExecutor<AuctionClient<DynProvider>>,
+    mut blocks: Fuse<BoxBlockStream>,
+    mut events: Fuse<BoxEventStream>,
+    mut transitions: Fuse<BoxStream<'static, Result<WatchlistEvent, flux_core::Error>>>,
+    mut keys: mpsc::UnboundedReceiver<KeyEvent>,
+) -> Result<()> {
+    loop {
+        terminal.draw(|frame| dashboard.render(frame))?;
+
+        futures::select! {
+            block = blocks.next() => {
+                let Some(block) = block else { break };
+                let block = block?;
+                executor.refresh(block).await?;
+                let ctx = executor.context(block, &[]);
+                let phase = ctx.phase.clone();
+                let ids = ctx.tracked_bids.clone();
+                let clearing_price = executor
+                    .cache()
+                    .latest_checkpoint
+                    .map(|checkpoint| checkpoint.clearing_price);
+                dashboard.on_block(block, phase, clearing_price);
+                if !ids.is_empty() {
+                    dashboard.set_bids(executor.client().fetch_bids(&ids).await?);
+                }
+            }
+            event = events.next() => {
+                if let Some(event) = event {
+                    dashboard.on_event(event?);
+                }
+            }
+            transition = transitions.next() => {
+                match transition {
+                    Some(Ok(event)) => {
+                        dashboard.status = format!(
+                            "auction {} at block {}",
+                            event.transition,
+                            event.block.as_u64()
+                        );
+                    }
+                    Some(Err(error)) => tracing::warn!(%error, "failed to read phase changes"),
+                    None => {}
+                }
+            }
+            key = keys.next() => {
+                let Some(key) = key else { break };
+                let intent = match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => break,
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        dashboard.select_next();
+                        None
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        dashboard.select_previous();
+                        None
+                    }
+                    KeyCode::Char('b') if dashboard.can_send => {
+                        dashboard.prepared.map(|bid| Intent::SubmitBid {
+                            max_price: bid.max_price,
+                            amount: bid.amount,
+                            owner: None,
+                            from: None,
+                        })
+                    }
+                    KeyCode::Char('x') if dashboard.can_send => dashboard
+                        .selected_bid()
+                        .map(|bid| Intent::Exit { bid_id: bid.id }),
+                    _ => None,
+                };
+                let (Some(intent), Some(block)) = (intent, dashboard.block) else {
+                    continue;
+                };
+
+                dashboard.status = format!("sending {}...", intent.kind());
+                terminal.draw(|frame| dashboard.render(frame))?;
+                let outcome = executor.execute(intent, block).await;
+                dashboard.status = describe_outcome(&outcome);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparkline_uses_the_window_range() {
+        let prices = [100u64, 150, 200]
+            .map(|p| Price::new(U256::from(p)))
+            .to_vec();
+        assert_eq!(sparkline_points(prices), [1, 50, 100]);
+
+        let flat = vec![Price::new(U256::from(7)); 3];
+        assert_eq!(sparkline_points(flat), [1, 1, 1]);
+        assert!(sparkline_points(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn phase_label_estimates_time_at_the_chain_block_time() {
+        let active = AuctionPhase::Active {
+            blocks_remaining: 600,
+        };
+        assert_eq!(
+            phase_label(&active, &ChainProfile::ETHEREUM),
+            "active, 600 blocks left (~2h)"
+        );
+        assert_eq!(
+            phase_label(&active, &ChainProfile::ARBITRUM),
+            "active, 600 blocks left (~2m)"
+        );
+    }
+}
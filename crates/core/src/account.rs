@@ -0,0 +1,539 @@
+use std::{fmt, sync::Arc, time::Duration};
+
+use alloy::{
+    eips::eip7702::Authorization,
+    network::{TransactionBuilder, TransactionBuilder7702},
+    primitives::{Address, B256, Bytes, U256, address, aliases::U192, bytes},
+    providers::{DynProvider, Provider},
+    rpc::types::{Log, TransactionReceipt, TransactionRequest},
+    signers::Signer,
+    sol_types::SolCall,
+};
+use flux_abi::{IEntryPoint, ISmartAccount};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+use crate::error::{Error, StateError, TransactionError};
+
+/// The ERC-4337 v0.7 EntryPoint, deployed at the same address everywhere.
+pub const ENTRY_POINT_V07: Address = address!("0x0000000071727De22E5E9d8BAf0edAc6f37da032");
+
+/// The ERC-4337 v0.8 EntryPoint, which also accepts EIP-7702 accounts.
+pub const ENTRY_POINT_V08: Address = address!("0x4337084D9E255Ff0702461CF8895CE9E3b5Ff108");
+
+/// Stands in for the signature while the bundler estimates gas: the right
+/// length, and a valid ECDSA shape so accounts get as far as `ecrecover`.
+const DUMMY_SIGNATURE: Bytes = bytes!(
+    "fffffffffffffffffffffffffffffff000000000000000000000000000000007aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa1c"
+);
+
+/// Code of an account delegated under EIP-7702 starts with this, followed by
+/// the delegate's address.
+const DELEGATION_PREFIX: [u8; 3] = [0xef, 0x01, 0x00];
+
+/// Sponsors gas for the account's user operations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Paymaster {
+    pub address: Address,
+    /// Whatever the paymaster expects after its gas limits, e.g. a
+    /// sponsorship signature from its service.
+    pub data: Bytes,
+    /// Overrides the bundler's estimate.
+    pub verification_gas_limit: Option<u128>,
+    /// Overrides the bundler's estimate.
+    pub post_op_gas_limit: Option<u128>,
+}
+
+impl Paymaster {
+    pub fn new(address: Address) -> Self {
+        Self {
+            address,
+            data: Bytes::new(),
+            verification_gas_limit: None,
+            post_op_gas_limit: None,
+        }
+    }
+
+    pub fn with_data(mut self, data: Bytes) -> Self {
+        self.data = data;
+        self
+    }
+
+    pub fn with_verification_gas_limit(mut self, gas: u128) -> Self {
+        self.verification_gas_limit = Some(gas);
+        self
+    }
+
+    pub fn with_post_op_gas_limit(mut self, gas: u128) -> Self {
+        self.post_op_gas_limit = Some(gas);
+        self
+    }
+}
+
+/// How the account checks a user operation's signature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccountKind {
+    /// A deployed ERC-4337 account owned by the signer, such as
+    /// SimpleAccount, which checks a personal-sign signature of the user
+    /// operation hash.
+    Erc4337,
+    /// The signer's own address, delegated under EIP-7702 to `delegate`,
+    /// such as Simple7702Account, which checks a raw signature of the hash.
+    Eip7702 { delegate: Address },
+}
+
+/// A user operation as bundlers take it over RPC.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperation {
+    pub sender: Address,
+    pub nonce: U256,
+    pub call_data: Bytes,
+    #[serde(with = "alloy::serde::quantity")]
+    pub call_gas_limit: u128,
+    #[serde(with = "alloy::serde::quantity")]
+    pub verification_gas_limit: u128,
+    #[serde(with = "alloy::serde::quantity")]
+    pub pre_verification_gas: u128,
+    #[serde(with = "alloy::serde::quantity")]
+    pub max_fee_per_gas: u128,
+    #[serde(with = "alloy::serde::quantity")]
+    pub max_priority_fee_per_gas: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paymaster: Option<Address>,
+    #[serde(
+        with = "alloy::serde::quantity::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub paymaster_verification_gas_limit: Option<u128>,
+    #[serde(
+        with = "alloy::serde::quantity::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub paymaster_post_op_gas_limit: Option<u128>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paymaster_data: Option<Bytes>,
+    pub signature: Bytes,
+}
+
+impl UserOperation {
+    /// The form the EntryPoint hashes and executes: gas limits and fees
+    /// packed two to a word, and the paymaster fields concatenated.
+    pub fn pack(&self) -> IEntryPoint::PackedUserOperation {
+        let paymaster_and_data = match self.paymaster {
+            Some(paymaster) => {
+                let mut packed = paymaster.to_vec();
+                packed.extend(
+                    self.paymaster_verification_gas_limit
+                        .unwrap_or_default()
+                        .to_be_bytes(),
+                );
+                packed.extend(
+                    self.paymaster_post_op_gas_limit
+                        .unwrap_or_default()
+                        .to_be_bytes(),
+                );
+                if let Some(data) = &self.paymaster_data {
+                    packed.extend_from_slice(data);
+                }
+                packed.into()
+            }
+            None => Bytes::new(),
+        };
+        IEntryPoint::PackedUserOperation {
+            sender: self.sender,
+            nonce: self.nonce,
+            initCode: Bytes::new(),
+            callData: self.call_data.clone(),
+            accountGasLimits: pack_u128s(self.verification_gas_limit, self.call_gas_limit),
+            preVerificationGas: U256::from(self.pre_verification_gas),
+            gasFees: pack_u128s(self.max_priority_fee_per_gas, self.max_fee_per_gas),
+            paymasterAndData: paymaster_and_data,
+            signature: self.signature.clone(),
+        }
+    }
+}
+
+fn pack_u128s(high: u128, low: u128) -> B256 {
+    B256::from(U256::from(high) << 128 | U256::from(low))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GasEstimate {
+    #[serde(with = "alloy::serde::quantity")]
+    pre_verification_gas: u128,
+    #[serde(with = "alloy::serde::quantity")]
+    verification_gas_limit: u128,
+    #[serde(with = "alloy::serde::quantity")]
+    call_gas_limit: u128,
+    #[serde(default, with = "alloy::serde::quantity::opt")]
+    paymaster_verification_gas_limit: Option<u128>,
+    #[serde(default, with = "alloy::serde::quantity::opt")]
+    paymaster_post_op_gas_limit: Option<u128>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserOperationReceipt {
+    success: bool,
+    #[serde(default)]
+    reason: Option<String>,
+    logs: Vec<Log>,
+    receipt: TransactionReceipt,
+}
+
+/// Sends the client's transactions as user operations from a smart account
+/// through an ERC-4337 bundler, optionally sponsored by a [`Paymaster`].
+///
+/// Each transaction becomes one `execute(to, value, data)` call on the
+/// account. The receipt handed back is the bundle transaction's, with its
+/// logs narrowed to the ones this user operation emitted.
+#[derive(Clone)]
+pub struct SmartAccount {
+    bundler: DynProvider,
+    address: Address,
+    kind: AccountKind,
+    signer: Arc<dyn Signer + Send + Sync>,
+    entry_point: Address,
+    paymaster: Option<Paymaster>,
+    poll_interval: Duration,
+    timeout_blocks: u64,
+}
+
+impl SmartAccount {
+    /// Blocks to wait for a user operation before giving up on it.
+    pub const DEFAULT_TIMEOUT_BLOCKS: u64 = 25;
+
+    /// A deployed ERC-4337 account at `address` owned by `signer`, sending
+    /// through `bundler`, a provider connected to the bundler's RPC.
+    pub fn erc4337(
+        bundler: DynProvider,
+        address: Address,
+        signer: Arc<dyn Signer + Send + Sync>,
+    ) -> Self {
+        Self::new(bundler, address, AccountKind::Erc4337, signer).with_entry_point(ENTRY_POINT_V07)
+    }
+
+    /// The signer's own address, delegated to `delegate` under EIP-7702; see
+    /// [`delegate`](Self::delegate).
+    pub fn eip7702(
+        bundler: DynProvider,
+        delegate: Address,
+        signer: Arc<dyn Signer + Send + Sync>,
+    ) -> Self {
+        let address = signer.address();
+        Self::new(bundler, address, AccountKind::Eip7702 { delegate }, signer)
+            .with_entry_point(ENTRY_POINT_V08)
+    }
+
+    fn new(
+        bundler: DynProvider,
+        address: Address,
+        kind: AccountKind,
+        signer: Arc<dyn Signer + Send + Sync>,
+    ) -> Self {
+        Self {
+            bundler,
+            address,
+            kind,
+            signer,
+            entry_point: ENTRY_POINT_V07,
+            paymaster: None,
+            poll_interval: Duration::from_secs(2),
+            timeout_blocks: Self::DEFAULT_TIMEOUT_BLOCKS,
+        }
+    }
+
+    pub fn with_entry_point(mut self, entry_point: Address) -> Self {
+        self.entry_point = entry_point;
+        self
+    }
+
+    pub fn with_paymaster(mut self, paymaster: Paymaster) -> Self {
+        self.paymaster = Some(paymaster);
+        self
+    }
+
+    /// How often the bundler is asked whether the operation was included.
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    pub fn with_timeout_blocks(mut self, blocks: u64) -> Self {
+        self.timeout_blocks = blocks;
+        self
+    }
+
+    /// The account's address, which owns its bids.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    pub fn kind(&self) -> AccountKind {
+        self.kind
+    }
+
+    /// For an EIP-7702 account, delegates the signer's address to the
+    /// delegate unless it already is, with a type 4 transaction the signer
+    /// sends and pays for itself through `provider`. Returns whether a
+    /// transaction was sent. Does nothing for ERC-4337 accounts.
+    pub async fn delegate<P>(&self, provider: &P) -> Result<bool, Error>
+    where
+        P: Provider,
+    {
+        let AccountKind::Eip7702 { delegate } = self.kind else {
+            return Ok(false);
+        };
+        let code = provider
+            .get_code_at(self.address)
+            .await
+            .map_err(StateError::from)?;
+        if code.starts_with(&DELEGATION_PREFIX) && code[DELEGATION_PREFIX.len()..] == delegate {
+            return Ok(false);
+        }
+
+        let chain_id = provider
+            .get_chain_id()
+            .await
+            .map_err(TransactionError::from)?;
+        let nonce = provider
+            .get_transaction_count(self.address)
+            .await
+            .map_err(TransactionError::from)?;
+        // The authorization is checked after the transaction bumped the nonce.
+        let authorization = Authorization {
+            chain_id: U256::from(chain_id),
+            address: delegate,
+            nonce: nonce + 1,
+        };
+        let signature = self
+            .signer
+            .sign_hash(&authorization.signature_hash())
+            .await
+            .map_err(TransactionError::AccountSignature)?;
+        let tx = TransactionRequest::default()
+            .with_from(self.address)
+            .with_to(self.address)
+            .with_nonce(nonce)
+            .with_authorization_list(vec![authorization.into_signed(signature)]);
+        let receipt = provider
+            .send_transaction(tx)
+            .await
+            .map_err(TransactionError::from)?
+            .get_receipt()
+            .await
+            .map_err(TransactionError::from)?;
+        if !receipt.status() {
+            return Err(TransactionError::Reverted {
+                tx_hash: receipt.transaction_hash,
+            }
+            .into());
+        }
+        info!(account = %self.address, %delegate, tx_hash = %receipt.transaction_hash, "delegated account");
+        Ok(true)
+    }
+
+    /// Sends `tx` as a user operation and waits for `confirmations` of the
+    /// bundle that included it. `provider` reads the chain; the bundler
+    /// only takes the operation.
+    pub async fn send<P>(
+        &self,
+        provider: &P,
+        tx: TransactionRequest,
+        confirmations: u64,
+    ) -> Result<TransactionReceipt, Error>
+    where
+        P: Provider,
+    {
+        let target = tx.to.and_then(|to| to.to().copied());
+        let target = target.ok_or(TransactionError::MissingRecipient)?;
+        let call_data = ISmartAccount::executeCall {
+            target,
+            value: tx.value.unwrap_or_default(),
+            data: tx.input.into_input().unwrap_or_default(),
+        }
+        .abi_encode();
+
+        let entry_point = IEntryPoint::new(self.entry_point, provider);
+        let nonce = entry_point
+            .getNonce(self.address, U192::ZERO)
+            .call()
+            .await
+            .map_err(StateError::from)?;
+        let fees = provider
+            .estimate_eip1559_fees()
+            .await
+            .map_err(TransactionError::from)?;
+
+        let mut op = UserOperation {
+            sender: self.address,
+            nonce,
+            call_data: call_data.into(),
+            max_fee_per_gas: fees.max_fee_per_gas,
+            max_priority_fee_per_gas: fees.max_priority_fee_per_gas,
+            signature: DUMMY_SIGNATURE,
+            ..UserOperation::default()
+        };
+        if let Some(paymaster) = &self.paymaster {
+            op.paymaster = Some(paymaster.address);
+            op.paymaster_data = Some(paymaster.data.clone());
+        }
+
+        let estimate: GasEstimate = self
+            .bundler
+            .raw_request(
+                "eth_estimateUserOperationGas".into(),
+                (&op, self.entry_point),
+            )
+            .await
+            .map_err(TransactionError::from)?;
+        op.pre_verification_gas = estimate.pre_verification_gas;
+        op.verification_gas_limit = estimate.verification_gas_limit;
+        op.call_gas_limit = estimate.call_gas_limit;
+        if let Some(paymaster) = &self.paymaster {
+            op.paymaster_verification_gas_limit = paymaster
+                .verification_gas_limit
+                .or(estimate.paymaster_verification_gas_limit);
+            op.paymaster_post_op_gas_limit = paymaster
+                .post_op_gas_limit
+                .or(estimate.paymaster_post_op_gas_limit);
+        }
+
+        let hash = entry_point
+            .getUserOpHash(op.pack())
+            .call()
+            .await
+            .map_err(StateError::from)?;
+        let signature = match self.kind {
+            AccountKind::Erc4337 => self
+                .signer
+                .sign_message(hash.as_slice())
+                .await
+                .map_err(TransactionError::AccountSignature)?,
+            AccountKind::Eip7702 { .. } => self
+                .signer
+                .sign_hash(&hash)
+                .await
+                .map_err(TransactionError::AccountSignature)?,
+        };
+        op.signature = signature.as_bytes().into();
+
+        let sent: B256 = self
+            .bundler
+            .raw_request("eth_sendUserOperation".into(), (&op, self.entry_point))
+            .await
+            .map_err(TransactionError::from)?;
+        debug!(user_op_hash = %sent, "sent user operation");
+
+        let receipt = self.wait(provider, sent).await?;
+        let mut tx_receipt = receipt.receipt;
+        if !receipt.success {
+            return Err(TransactionError::UserOperationFailed {
+                hash: sent,
+                reason: receipt.reason.unwrap_or_default(),
+            }
+            .into());
+        }
+        // The bundle may carry other operations; keep only this one's logs.
+        if let Some(inner) = tx_receipt.inner.as_receipt_with_bloom_mut() {
+            inner.receipt.logs = receipt.logs;
+        }
+
+        let mined = tx_receipt.block_number.unwrap_or_default();
+        loop {
+            let head = provider
+                .get_block_number()
+                .await
+                .map_err(TransactionError::from)?;
+            if head + 1 >= mined + confirmations {
+                return Ok(tx_receipt);
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Polls the bundler for the operation's receipt until it is included or
+    /// the chain has moved on by the timeout.
+    async fn wait<P>(&self, provider: &P, hash: B256) -> Result<UserOperationReceipt, Error>
+    where
+        P: Provider,
+    {
+        let sent_at = provider
+            .get_block_number()
+            .await
+            .map_err(TransactionError::from)?;
+        loop {
+            let receipt: Option<UserOperationReceipt> = self
+                .bundler
+                .raw_request("eth_getUserOperationReceipt".into(), (hash,))
+                .await
+                .map_err(TransactionError::from)?;
+            if let Some(receipt) = receipt {
+                return Ok(receipt);
+            }
+            let head = provider
+                .get_block_number()
+                .await
+                .map_err(TransactionError::from)?;
+            let blocks = head.saturating_sub(sent_at);
+            if blocks > self.timeout_blocks {
+                return Err(TransactionError::UserOperationTimeout { hash, blocks }.into());
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+}
+
+impl fmt::Debug for SmartAccount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SmartAccount")
+            .field("address", &self.address)
+            .field("kind", &self.kind)
+            .field("entry_point", &self.entry_point)
+            .field("paymaster", &self.paymaster)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packs_gas_limits_and_paymaster_fields() {
+        let paymaster = address!("0x00000000000000000000000000000000000000aa");
+        let op = UserOperation {
+            sender: Address::ZERO,
+            call_gas_limit: 2,
+            verification_gas_limit: 1,
+            max_fee_per_gas: 4,
+            max_priority_fee_per_gas: 3,
+            paymaster: Some(paymaster),
+            paymaster_verification_gas_limit: Some(5),
+            paymaster_post_op_gas_limit: Some(6),
+            paymaster_data: Some(bytes!("beef")),
+            ..UserOperation::default()
+        };
+        let packed = op.pack();
+
+        assert_eq!(
+            U256::from_be_bytes(packed.accountGasLimits.0),
+            U256::from(1) << 128 | U256::from(2)
+        );
+        assert_eq!(
+            U256::from_be_bytes(packed.gasFees.0),
+            U256::from(3) << 128 | U256::from(4)
+        );
+
+        let data = packed.paymasterAndData;
+        assert_eq!(data.len(), 20 + 16 + 16 + 2);
+        assert_eq!(&data[..20], paymaster.as_slice());
+        assert_eq!(u128::from_be_bytes(data[20..36].try_into().unwrap()), 5);
+        assert_eq!(u128::from_be_bytes(data[36..52].try_into().unwrap()), 6);
+        assert_eq!(&data[52..], [0xbe, 0xef]);
+
+        let unsponsored = UserOperation::default().pack();
+        assert!(unsponsored.paymasterAndData.is_empty());
+    }
+}
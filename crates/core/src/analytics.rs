@@ -0,0 +1,436 @@
+use alloy::{
+    primitives::{Address, U256},
+    providers::Provider,
+};
+
+use crate::{
+    client::AuctionClient,
+    error::{AnalyticsError, Error},
+    events::{AuctionEvent, EventProducer},
+    types::{
+        bid::Bid,
+        primitives::{BidId, BlockNumber, CurrencyAmount, Price, TickSpacing, TokenAmount},
+    },
+};
+
+/// What a settled bid actually cost, read back from its `BidSubmitted`,
+/// `BidExited` and `TokensClaimed` events, with the clearing price from the
+/// checkpoint history around it.
+#[derive(Clone, Debug)]
+pub struct BidAnalytics {
+    pub bid_id: BidId,
+    pub owner: Address,
+    pub max_price: Price,
+    pub amount: CurrencyAmount,
+    pub submitted_block: BlockNumber,
+    pub exited_block: BlockNumber,
+    pub claimed_block: Option<BlockNumber>,
+    pub tokens_filled: TokenAmount,
+    pub currency_refunded: CurrencyAmount,
+    /// Clearing price of the last checkpoint at or before submission.
+    pub entry_clearing_price: Option<Price>,
+    /// Clearing price of the last checkpoint at or before the exit.
+    pub exit_clearing_price: Option<Price>,
+    /// Tags the bid was tracked with; events don't carry them, so they are
+    /// empty unless given through [`with_tags`](Self::with_tags).
+    pub tags: Vec<String>,
+}
+
+impl BidAnalytics {
+    /// Reads `bid_id`'s events from `from` onwards and analyzes them.
+    pub async fn fetch<P>(
+        client: &AuctionClient<P>,
+        bid_id: BidId,
+        from: BlockNumber,
+    ) -> Result<Self, Error>
+    where
+        P: Provider + Clone,
+    {
+        let events = EventProducer::new(client.provider().clone(), client.address())
+            .history(from, None)
+            .await?;
+        Ok(Self::from_events(bid_id, &events)?)
+    }
+
+    /// Analyzes `bid_id` from the auction's recorded events, in log order.
+    /// Fails unless the bid was both submitted and exited within them.
+    pub fn from_events(bid_id: BidId, events: &[AuctionEvent]) -> Result<Self, AnalyticsError> {
+        let id = bid_id.as_u256();
+        let is_bid = |event_id: &BidId| event_id.as_u256() == id;
+
+        let (submitted_block, owner, max_price, amount) = events
+            .iter()
+            .find_map(|event| match event {
+                AuctionEvent::BidSubmitted {
+                    block,
+                    bid_id,
+                    owner,
+                    price,
+                    amount,
+                } if is_bid(bid_id) => Some((*block, *owner, *price, *amount)),
+                _ => None,
+            })
+            .ok_or(AnalyticsError::NotSubmitted(id))?;
+        let (exited_block, tokens_filled, currency_refunded) = events
+            .iter()
+            .find_map(|event| match event {
+                AuctionEvent::BidExited {
+                    block,
+                    bid_id,
+                    tokens_filled,
+                    currency_refunded,
+                    ..
+                } if is_bid(bid_id) => Some((*block, *tokens_filled, *currency_refunded)),
+                _ => None,
+            })
+            .ok_or(AnalyticsError::NotExited(id))?;
+        let claimed_block = events.iter().find_map(|event| match event {
+            AuctionEvent::TokensClaimed { block, bid_id, .. } if is_bid(bid_id) => Some(*block),
+            _ => None,
+        });
+
+        let clearing_price_at = |at: BlockNumber| {
+            events
+                .iter()
+                .filter_map(|event| match event {
+                    AuctionEvent::CheckpointUpdated {
+                        block,
+                        clearing_price,
+                        ..
+                    } if *block <= at => Some(*clearing_price),
+                    _ => None,
+                })
+                .next_back()
+        };
+
+        Ok(Self {
+            bid_id,
+            owner,
+            max_price,
+            amount,
+            submitted_block,
+            exited_block,
+            claimed_block,
+            tokens_filled,
+            currency_refunded,
+            entry_clearing_price: clearing_price_at(submitted_block),
+            exit_clearing_price: clearing_price_at(exited_block),
+            tags: Vec::new(),
+        })
+    }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Currency that bought tokens: the amount less the refund.
+    pub fn currency_spent(&self) -> CurrencyAmount {
+        CurrencyAmount::new(
+            self.amount
+                .as_u256()
+                .saturating_sub(self.currency_refunded.as_u256()),
+        )
+    }
+
+    /// Average Q96 price paid per token; `None` when nothing was filled.
+    pub fn realized_price(&self) -> Option<Price> {
+        Price::from_ratio(
+            self.currency_spent().as_u256(),
+            self.tokens_filled.as_u256(),
+        )
+    }
+
+    /// Fraction of the bid's amount that was spent, from 0 to 1.
+    pub fn fill_ratio(&self) -> f64 {
+        let amount = self.amount.as_u256();
+        if amount.is_zero() {
+            return 0.0;
+        }
+        let ppm = self.currency_spent().as_u256() * U256::from(1_000_000) / amount;
+        ppm.to::<u64>() as f64 / 1_000_000.0
+    }
+
+    /// Blocks the bid's currency was locked in the auction, from submission
+    /// to exit.
+    pub fn holding_period(&self) -> u64 {
+        self.exited_block
+            .as_u64()
+            .saturating_sub(self.submitted_block.as_u64())
+    }
+}
+
+/// How an auction's active demand is spread across bidders and prices,
+/// from every bid it received (e.g. through
+/// [`AuctionClient::fetch_all_bids`](crate::AuctionClient::fetch_all_bids)).
+/// Exited bids no longer compete, so they are left out.
+#[derive(Clone, Debug)]
+pub struct MarketStructure {
+    pub bids: usize,
+    pub total_demand: CurrencyAmount,
+    pub clearing_price: Price,
+    /// Demand of bids priced above the clearing price, which keep filling.
+    pub demand_above_clearing: CurrencyAmount,
+    /// Demand of bids at or below it, which stop filling.
+    pub demand_at_or_below_clearing: CurrencyAmount,
+    /// Largest demand first.
+    pub owners: Vec<OwnerDemand>,
+    /// Equal-width max price buckets from the lowest bid to the highest,
+    /// ascending.
+    pub price_histogram: Vec<PriceBucket>,
+}
+
+#[derive(Clone, Debug)]
+pub struct OwnerDemand {
+    pub owner: Address,
+    pub bids: usize,
+    pub demand: CurrencyAmount,
+}
+
+/// Bids with a max price from `low` up to, but not including, `high`.
+#[derive(Clone, Debug)]
+pub struct PriceBucket {
+    pub low: Price,
+    pub high: Price,
+    pub bids: usize,
+    pub demand: CurrencyAmount,
+}
+
+impl MarketStructure {
+    /// Sorts the active `bids` into owners and up to `buckets` price buckets,
+    /// and their demand into above and at or below `clearing_price`.
+    pub fn new(bids: &[Bid], clearing_price: Price, buckets: usize) -> Self {
+        let bids: Vec<_> = bids
+            .iter()
+            .filter(|bid| bid.exited_block.is_none())
+            .cloned()
+            .collect();
+
+        let mut owners: Vec<OwnerDemand> = Vec::new();
+        let mut demand_above_clearing = CurrencyAmount::ZERO;
+        let mut demand_at_or_below_clearing = CurrencyAmount::ZERO;
+        for bid in &bids {
+            if bid.max_price > clearing_price {
+                demand_above_clearing += bid.amount;
+            } else {
+                demand_at_or_below_clearing += bid.amount;
+            }
+            match owners.iter_mut().find(|owner| owner.owner == bid.owner) {
+                Some(owner) => {
+                    owner.bids += 1;
+                    owner.demand += bid.amount;
+                }
+                None => owners.push(OwnerDemand {
+                    owner: bid.owner,
+                    bids: 1,
+                    demand: bid.amount,
+                }),
+            }
+        }
+        owners.sort_by_key(|owner| std::cmp::Reverse(owner.demand.as_u256()));
+
+        Self {
+            bids: bids.len(),
+            total_demand: owners
+                .iter()
+                .fold(CurrencyAmount::ZERO, |total, owner| total + owner.demand),
+            clearing_price,
+            demand_above_clearing,
+            demand_at_or_below_clearing,
+            owners,
+            price_histogram: histogram(&bids, buckets),
+        }
+    }
+
+    /// Owners other than `owner`, largest demand first.
+    pub fn competitors(&self, owner: Address) -> impl Iterator<Item = &OwnerDemand> {
+        self.owners.iter().filter(move |other| other.owner != owner)
+    }
+
+    /// Share of the total demand that `demand` is, from 0 to 1.
+    pub fn share(&self, demand: CurrencyAmount) -> f64 {
+        let total = self.total_demand.as_u256();
+        if total.is_zero() {
+            return 0.0;
+        }
+        let ppm = demand.as_u256().min(total) * U256::from(1_000_000) / total;
+        ppm.to::<u64>() as f64 / 1_000_000.0
+    }
+
+    /// Share of the demand from the `n` largest owners, from 0 to 1.
+    pub fn top_share(&self, n: usize) -> f64 {
+        self.share(
+            self.owners
+                .iter()
+                .take(n)
+                .fold(CurrencyAmount::ZERO, |top, owner| top + owner.demand),
+        )
+    }
+
+    /// How far `price` sits above the clearing price: in whole ticks of
+    /// `tick_spacing`, and as a fraction of the clearing price. `None` at or
+    /// below it.
+    pub fn above_clearing(&self, price: Price, tick_spacing: TickSpacing) -> Option<(U256, f64)> {
+        let gap = price
+            .as_u256()
+            .checked_sub(self.clearing_price.as_u256())
+            .filter(|gap| !gap.is_zero())?;
+        let ticks = gap.checked_div(tick_spacing.as_u256()).unwrap_or_default();
+        let fraction = f64::from(gap) / f64::from(self.clearing_price.as_u256());
+        Some((ticks, fraction))
+    }
+}
+
+fn histogram(bids: &[Bid], buckets: usize) -> Vec<PriceBucket> {
+    let prices = bids.iter().map(|bid| bid.max_price.as_u256());
+    let (Some(min), Some(max)) = (prices.clone().min(), prices.max()) else {
+        return Vec::new();
+    };
+    if buckets == 0 {
+        return Vec::new();
+    }
+    // Rounded up so the highest price lands in the last bucket.
+    let width = (max - min) / U256::from(buckets) + U256::from(1);
+    let count = ((max - min) / width).to::<usize>() + 1;
+
+    let mut histogram: Vec<_> = (0..count)
+        .map(|i| {
+            let low = min + width * U256::from(i);
+            PriceBucket {
+                low: Price::new(low),
+                high: Price::new(low + width),
+                bids: 0,
+                demand: CurrencyAmount::ZERO,
+            }
+        })
+        .collect();
+    for bid in bids {
+        let bucket = &mut histogram[((bid.max_price.as_u256() - min) / width).to::<usize>()];
+        bucket.bids += 1;
+        bucket.demand += bid.amount;
+    }
+    histogram
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::aliases::U24;
+
+    use super::*;
+    use crate::types::primitives::{Mps, Q96};
+
+    fn checkpoint(block: u64, price: u64) -> AuctionEvent {
+        AuctionEvent::CheckpointUpdated {
+            block: BlockNumber::new(block),
+            clearing_price: Price::new(Q96 * U256::from(price)),
+            cumulative_mps: Mps::new(U24::ZERO),
+        }
+    }
+
+    #[test]
+    fn realized_price_and_fill_ratio_come_from_exit_events() {
+        let bid_id = BidId::new(U256::from(7));
+        let events = [
+            checkpoint(10, 1),
+            AuctionEvent::BidSubmitted {
+                block: BlockNumber::new(12),
+                bid_id,
+                owner: Address::repeat_byte(1),
+                price: Price::new(Q96 * U256::from(4)),
+                amount: CurrencyAmount::new(U256::from(1_000)),
+            },
+            checkpoint(20, 2),
+            AuctionEvent::BidExited {
+                block: BlockNumber::new(40),
+                bid_id,
+                owner: Address::repeat_byte(1),
+                tokens_filled: TokenAmount::new(U256::from(300)),
+                currency_refunded: CurrencyAmount::new(U256::from(400)),
+            },
+            checkpoint(50, 3),
+        ];
+
+        let analytics = BidAnalytics::from_events(bid_id, &events).unwrap();
+        assert_eq!(analytics.currency_spent().as_u256(), U256::from(600));
+        assert_eq!(
+            analytics.realized_price().unwrap().as_u256(),
+            Q96 * U256::from(2)
+        );
+        assert_eq!(analytics.fill_ratio(), 0.6);
+        assert_eq!(analytics.holding_period(), 28);
+        assert_eq!(
+            analytics.entry_clearing_price.unwrap().as_u256(),
+            Q96 * U256::from(1)
+        );
+        assert_eq!(
+            analytics.exit_clearing_price.unwrap().as_u256(),
+            Q96 * U256::from(2)
+        );
+        assert!(analytics.claimed_block.is_none());
+
+        assert!(matches!(
+            BidAnalytics::from_events(BidId::new(U256::from(8)), &events),
+            Err(AnalyticsError::NotSubmitted(_))
+        ));
+    }
+
+    #[test]
+    fn market_structure_ranks_owners_and_buckets_prices() {
+        let bid = |owner: u8, price: u64, amount: u64| Bid {
+            id: BidId::new(U256::ZERO),
+            owner: Address::repeat_byte(owner),
+            max_price: Price::new(U256::from(price)),
+            amount: CurrencyAmount::new(U256::from(amount)),
+            start_block: BlockNumber::new(0),
+            start_cumulative_mps: Mps::new(U24::ZERO),
+            exited_block: None,
+            tokens_filled: TokenAmount::ZERO,
+        };
+        let mut exited = bid(4, 50, 5_000);
+        exited.exited_block = Some(BlockNumber::new(9));
+        let bids = [
+            bid(1, 10, 100),
+            bid(2, 15, 600),
+            bid(1, 19, 200),
+            bid(3, 30, 100),
+            exited,
+        ];
+
+        let market = MarketStructure::new(&bids, Price::new(U256::from(15)), 2);
+        assert_eq!(market.bids, 4);
+        assert_eq!(market.total_demand.as_u256(), U256::from(1_000));
+        assert_eq!(market.demand_above_clearing.as_u256(), U256::from(300));
+        assert_eq!(
+            market.demand_at_or_below_clearing.as_u256(),
+            U256::from(700)
+        );
+        assert_eq!(market.owners[0].owner, Address::repeat_byte(2));
+        assert_eq!(market.owners[1].bids, 2);
+        assert_eq!(market.top_share(2), 0.9);
+        let competitor = market.competitors(Address::repeat_byte(2)).next().unwrap();
+        assert_eq!(competitor.owner, Address::repeat_byte(1));
+
+        let spacing = TickSpacing::new(U256::from(2));
+        let (ticks, fraction) = market
+            .above_clearing(Price::new(U256::from(30)), spacing)
+            .unwrap();
+        assert_eq!((ticks, fraction), (U256::from(7), 1.0));
+        assert!(
+            market
+                .above_clearing(Price::new(U256::from(15)), spacing)
+                .is_none()
+        );
+
+        let buckets: Vec<_> = market
+            .price_histogram
+            .iter()
+            .map(|bucket| (bucket.low.as_u256().to::<u64>(), bucket.bids))
+            .collect();
+        assert_eq!(buckets, [(10, 3), (21, 1)]);
+        assert!(
+            MarketStructure::new(&[], Price::ZERO, 4)
+                .price_histogram
+                .is_empty()
+        );
+    }
+}
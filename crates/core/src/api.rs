@@ -0,0 +1,225 @@
+use std::sync::Arc;
+
+use alloy::{primitives::Address, providers::Provider};
+use async_trait::async_trait;
+
+use crate::{
+    client::AuctionClient,
+    error::{Error, StateError},
+    hooks::ValidationHook,
+    types::{
+        action::{
+            CheckpointResult, ClaimParams, ClaimResult, ExitBidParams, ExitPartiallyFilledParams,
+            ExitResult, SubmitBidInput, SubmitBidParams, SubmitBidResult,
+        },
+        bid::{Bid, TrackedBid},
+        checkpoint::Checkpoint,
+        config::AuctionConfig,
+        primitives::{BidId, BlockNumber},
+        state::{
+            AccountBalances, AuctionMetrics, AuctionState, GraduationStatus, TokenDepositStatus,
+        },
+        step::StepSchedule,
+    },
+};
+
+/// Everything the executor needs from an auction: reads, bid preparation and
+/// the four transactions.
+///
+/// [`AuctionClient`] is the on-chain implementation; mocks, recorders and
+/// simulated auctions can implement this to drive an
+/// [`IntentExecutor`](crate::IntentExecutor) and
+/// [`Orchestrator`](crate::Orchestrator) without a node.
+#[async_trait]
+pub trait AuctionApi: Send + Sync {
+    fn config(&self) -> &AuctionConfig;
+
+    fn address(&self) -> Address;
+
+    fn owner(&self) -> Address;
+
+    /// Bids submitted by this owner that the executor should know about.
+    fn tracked_bids(&self) -> Vec<TrackedBid>;
+
+    fn hook(&self) -> &Arc<dyn ValidationHook>;
+
+    /// Refuse to send transactions whose gas estimate exceeds `gas`. Clients
+    /// that never send anything can ignore this.
+    fn set_max_gas_per_tx(&mut self, _gas: u64) {}
+
+    async fn fetch_block_number(&self) -> Result<BlockNumber, Error>;
+
+    async fn fetch_checkpoint(&self) -> Result<Checkpoint, Error>;
+
+    async fn fetch_graduation(&self) -> Result<GraduationStatus, Error>;
+
+    async fn fetch_auction_metrics(&self) -> Result<AuctionMetrics, Error>;
+
+    async fn fetch_token_balance(&self) -> Result<TokenDepositStatus, Error>;
+
+    /// The auction's supply schedule; `None` for clients that cannot read it.
+    async fn fetch_step_schedule(&self) -> Result<Option<StepSchedule>, Error> {
+        Ok(None)
+    }
+
+    /// `block`'s timestamp in unix seconds; `None` for clients that cannot
+    /// read it.
+    async fn fetch_block_timestamp(&self, _block: BlockNumber) -> Result<Option<u64>, Error> {
+        Ok(None)
+    }
+
+    async fn fetch_bids(&self, bid_ids: &[BidId]) -> Result<Vec<Bid>, Error>;
+
+    async fn fetch_nonce(&self, account: Address) -> Result<u64, Error>;
+
+    async fn fetch_balances(&self, account: Address) -> Result<AccountBalances, Error>;
+
+    /// Wei per unit of gas a transaction sent now would pay.
+    async fn fetch_gas_price(&self) -> Result<u128, Error>;
+
+    async fn prepare_bid(
+        &self,
+        input: SubmitBidInput,
+        state: &AuctionState,
+    ) -> Result<SubmitBidParams, Error>;
+
+    async fn submit_bid(&self, params: SubmitBidParams) -> Result<SubmitBidResult, Error>;
+
+    async fn exit_bid(&self, params: ExitBidParams) -> Result<ExitResult, Error>;
+
+    async fn prepare_exit_partially_filled(
+        &self,
+        bid_id: BidId,
+    ) -> Result<ExitPartiallyFilledParams, Error>;
+
+    async fn exit_partially_filled(
+        &self,
+        params: ExitPartiallyFilledParams,
+    ) -> Result<ExitResult, Error>;
+
+    async fn claim(&self, params: ClaimParams) -> Result<ClaimResult, Error>;
+
+    async fn poke_checkpoint(&self) -> Result<CheckpointResult, Error>;
+}
+
+#[async_trait]
+impl<P> AuctionApi for AuctionClient<P>
+where
+    P: Provider + Clone,
+{
+    fn config(&self) -> &AuctionConfig {
+        AuctionClient::config(self)
+    }
+
+    fn address(&self) -> Address {
+        AuctionClient::address(self)
+    }
+
+    fn owner(&self) -> Address {
+        AuctionClient::owner(self)
+    }
+
+    fn tracked_bids(&self) -> Vec<TrackedBid> {
+        AuctionClient::tracked_bids(self)
+    }
+
+    fn hook(&self) -> &Arc<dyn ValidationHook> {
+        AuctionClient::hook(self)
+    }
+
+    fn set_max_gas_per_tx(&mut self, gas: u64) {
+        AuctionClient::set_max_gas_per_tx(self, gas);
+    }
+
+    async fn fetch_block_number(&self) -> Result<BlockNumber, Error> {
+        let block = self
+            .provider()
+            .get_block_number()
+            .await
+            .map_err(StateError::from)?;
+        Ok(BlockNumber::new(block))
+    }
+
+    async fn fetch_block_timestamp(&self, block: BlockNumber) -> Result<Option<u64>, Error> {
+        let block = self
+            .provider()
+            .get_block_by_number(block.as_u64().into())
+            .await
+            .map_err(StateError::from)?;
+        Ok(block.map(|block| block.header.timestamp))
+    }
+
+    async fn fetch_checkpoint(&self) -> Result<Checkpoint, Error> {
+        AuctionClient::fetch_checkpoint(self).await
+    }
+
+    async fn fetch_graduation(&self) -> Result<GraduationStatus, Error> {
+        AuctionClient::fetch_graduation(self).await
+    }
+
+    async fn fetch_token_balance(&self) -> Result<TokenDepositStatus, Error> {
+        AuctionClient::fetch_token_balance(self).await
+    }
+
+    async fn fetch_step_schedule(&self) -> Result<Option<StepSchedule>, Error> {
+        AuctionClient::fetch_step_schedule(self).await.map(Some)
+    }
+
+    async fn fetch_nonce(&self, account: Address) -> Result<u64, Error> {
+        AuctionClient::fetch_nonce(self, account).await
+    }
+
+    async fn fetch_balances(&self, account: Address) -> Result<AccountBalances, Error> {
+        AuctionClient::fetch_balances(self, account).await
+    }
+
+    async fn fetch_gas_price(&self) -> Result<u128, Error> {
+        AuctionClient::fetch_gas_price(self).await
+    }
+
+    async fn fetch_auction_metrics(&self) -> Result<AuctionMetrics, Error> {
+        AuctionClient::fetch_auction_metrics(self).await
+    }
+
+    async fn fetch_bids(&self, bid_ids: &[BidId]) -> Result<Vec<Bid>, Error> {
+        AuctionClient::fetch_bids(self, bid_ids).await
+    }
+
+    async fn prepare_bid(
+        &self,
+        input: SubmitBidInput,
+        state: &AuctionState,
+    ) -> Result<SubmitBidParams, Error> {
+        AuctionClient::prepare_bid(self, input, state).await
+    }
+
+    async fn submit_bid(&self, params: SubmitBidParams) -> Result<SubmitBidResult, Error> {
+        AuctionClient::submit_bid(self, params).await
+    }
+
+    async fn exit_bid(&self, params: ExitBidParams) -> Result<ExitResult, Error> {
+        AuctionClient::exit_bid(self, params).await
+    }
+
+    async fn prepare_exit_partially_filled(
+        &self,
+        bid_id: BidId,
+    ) -> Result<ExitPartiallyFilledParams, Error> {
+        AuctionClient::prepare_exit_partially_filled(self, bid_id).await
+    }
+
+    async fn exit_partially_filled(
+        &self,
+        params: ExitPartiallyFilledParams,
+    ) -> Result<ExitResult, Error> {
+        AuctionClient::exit_partially_filled(self, params).await
+    }
+
+    async fn claim(&self, params: ClaimParams) -> Result<ClaimResult, Error> {
+        AuctionClient::claim(self, params).await
+    }
+
+    async fn poke_checkpoint(&self) -> Result<CheckpointResult, Error> {
+        AuctionClient::poke_checkpoint(self).await
+    }
+}
@@ -0,0 +1,148 @@
+use alloy::{
+    primitives::{Address, B256, Signature, U256, keccak256},
+    signers::{Signer, SignerSync},
+    sol,
+    sol_types::SolStruct,
+};
+use flux_abi::IERC20Permit;
+
+sol! {
+    /// The EIP-2612 message a token holder signs.
+    struct Permit {
+        address owner;
+        address spender;
+        uint256 value;
+        uint256 nonce;
+        uint256 deadline;
+    }
+}
+
+/// An EIP-2612 permit: the holder's signature letting `spender` move `value`
+/// of `token` without an `approve` transaction. Only a contract that takes
+/// permits, such as a bidding router, can use it; the auction itself pulls
+/// currency against a plain allowance.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignedPermit {
+    pub token: Address,
+    pub owner: Address,
+    pub spender: Address,
+    pub value: U256,
+    pub deadline: U256,
+    pub signature: Signature,
+}
+
+impl SignedPermit {
+    /// Signs a permit for `token`, whose `DOMAIN_SEPARATOR()` and the
+    /// signer's `nonces()` were read beforehand.
+    pub fn sign<S>(
+        signer: &S,
+        token: Address,
+        domain_separator: B256,
+        spender: Address,
+        value: U256,
+        nonce: U256,
+        deadline: U256,
+    ) -> Result<Self, alloy::signers::Error>
+    where
+        S: Signer + SignerSync,
+    {
+        let owner = signer.address();
+        let permit = Permit {
+            owner,
+            spender,
+            value,
+            nonce,
+            deadline,
+        };
+        let signature = signer.sign_hash_sync(&permit_digest(domain_separator, &permit))?;
+        Ok(Self {
+            token,
+            owner,
+            spender,
+            value,
+            deadline,
+            signature,
+        })
+    }
+
+    /// The token's `permit` call that spends this signature.
+    pub fn permit_call(&self) -> IERC20Permit::permitCall {
+        IERC20Permit::permitCall {
+            owner: self.owner,
+            spender: self.spender,
+            value: self.value,
+            deadline: self.deadline,
+            v: 27 + u8::from(self.signature.v()),
+            r: self.signature.r().into(),
+            s: self.signature.s().into(),
+        }
+    }
+}
+
+/// The EIP-712 digest of `permit` under a token's domain separator.
+pub fn permit_digest(domain_separator: B256, permit: &Permit) -> B256 {
+    let mut message = [0u8; 66];
+    message[..2].copy_from_slice(&[0x19, 0x01]);
+    message[2..34].copy_from_slice(domain_separator.as_slice());
+    message[34..].copy_from_slice(permit.eip712_hash_struct().as_slice());
+    keccak256(message)
+}
+
+/// How [`ensure_currency_allowance`](crate::AuctionClient::ensure_currency_allowance)
+/// made a bid's currency spendable.
+#[derive(Debug)]
+pub enum Approval {
+    /// The allowance already covers the amount.
+    NotNeeded,
+    /// An `approve` transaction was sent.
+    Approved {
+        spender: Address,
+        tx_hash: B256,
+        gas_used: u64,
+    },
+    /// A permit was signed for the permit router to submit with the bid.
+    Permit(SignedPermit),
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::signers::local::PrivateKeySigner;
+
+    use super::*;
+
+    #[test]
+    fn permit_signature_recovers_to_the_owner() {
+        let signer = PrivateKeySigner::random();
+        let domain_separator = B256::repeat_byte(7);
+        let permit = SignedPermit::sign(
+            &signer,
+            Address::repeat_byte(1),
+            domain_separator,
+            Address::repeat_byte(2),
+            U256::from(1_000),
+            U256::ZERO,
+            U256::from(u64::MAX),
+        )
+        .unwrap();
+
+        let digest = permit_digest(
+            domain_separator,
+            &Permit {
+                owner: permit.owner,
+                spender: permit.spender,
+                value: permit.value,
+                nonce: U256::ZERO,
+                deadline: permit.deadline,
+            },
+        );
+        assert_eq!(
+            permit
+                .signature
+                .recover_address_from_prehash(&digest)
+                .unwrap(),
+            signer.address()
+        );
+        let call = permit.permit_call();
+        assert!(call.v == 27 || call.v == 28);
+    }
+}
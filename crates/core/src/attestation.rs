@@ -0,0 +1,231 @@
+use alloy::{
+    primitives::{Address, B256, Signature, U256},
+    providers::Provider,
+    signers::SignerSync,
+    sol,
+    sol_types::{Eip712Domain, SolStruct, eip712_domain},
+};
+
+use crate::{
+    client::AuctionClient,
+    error::{AttestationError, Error, ValidationError},
+    types::{primitives::BlockNumber, state::GraduationStatus},
+};
+
+/// Version of the attestation layout; bump on any field change.
+pub const ATTESTATION_VERSION: u8 = 1;
+
+sol! {
+    /// EIP-712 form of [`Attestation`], which is what gets signed.
+    struct AuctionAttestation {
+        uint8 version;
+        address auction;
+        uint64 block;
+        uint256 clearingPrice;
+        bool graduated;
+        BidFill[] bids;
+    }
+
+    struct BidFill {
+        uint256 bidId;
+        address owner;
+        uint256 tokensFilled;
+        bytes32 submitTx;
+    }
+}
+
+/// Final outcome of an auction for a set of bids, as reported by the operator.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Attestation {
+    #[cfg_attr(feature = "serde", serde(rename = "v"))]
+    pub version: u8,
+    pub chain_id: u64,
+    pub auction: Address,
+    /// Block the final state was read at.
+    pub block: u64,
+    /// Final clearing price (Q96).
+    pub clearing_price: U256,
+    pub graduated: bool,
+    pub bids: Vec<AttestedBid>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct AttestedBid {
+    pub id: U256,
+    pub owner: Address,
+    pub tokens_filled: U256,
+    /// Submission transaction, when known.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub tx: Option<B256>,
+}
+
+/// An attestation plus the operator's EIP-712 signature over it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SignedAttestation {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub attestation: Attestation,
+    pub signer: Address,
+    pub signature: Signature,
+}
+
+impl Attestation {
+    /// Reads the final state of `client`'s auction for its tracked bids.
+    ///
+    /// Fails with `AuctionNotOver` before `end_block`, since the clearing
+    /// price is not final yet.
+    pub async fn collect<P>(
+        client: &AuctionClient<P>,
+        chain_id: u64,
+        block: BlockNumber,
+    ) -> Result<Self, Error>
+    where
+        P: Provider + Clone,
+    {
+        if block < client.config().end_block {
+            return Err(ValidationError::AuctionNotOver.into());
+        }
+
+        let checkpoint = client.fetch_checkpoint().await?;
+        let graduation = client.fetch_graduation().await?;
+
+        let tracked = client.tracked_bids();
+        let ids: Vec<_> = tracked.iter().map(|bid| bid.id).collect();
+        let bids = client
+            .fetch_bids(&ids)
+            .await?
+            .into_iter()
+            .zip(&tracked)
+            .map(|(bid, tracked)| AttestedBid {
+                id: bid.id.as_u256(),
+                owner: bid.owner,
+                tokens_filled: bid.tokens_filled.as_u256(),
+                tx: (!tracked.tx_hash.is_zero()).then_some(tracked.tx_hash),
+            })
+            .collect();
+
+        Ok(Self {
+            version: ATTESTATION_VERSION,
+            chain_id,
+            auction: client.address(),
+            block: block.as_u64(),
+            clearing_price: checkpoint.clearing_price.as_u256(),
+            graduated: matches!(graduation, GraduationStatus::Graduated),
+            bids,
+        })
+    }
+
+    pub fn domain(&self) -> Eip712Domain {
+        eip712_domain! {
+            name: "Flux Attestation",
+            version: "1",
+            chain_id: self.chain_id,
+        }
+    }
+
+    /// The EIP-712 digest that is signed.
+    pub fn signing_hash(&self) -> B256 {
+        let typed = AuctionAttestation {
+            version: self.version,
+            auction: self.auction,
+            block: self.block,
+            clearingPrice: self.clearing_price,
+            graduated: self.graduated,
+            bids: self
+                .bids
+                .iter()
+                .map(|bid| BidFill {
+                    bidId: bid.id,
+                    owner: bid.owner,
+                    tokensFilled: bid.tokens_filled,
+                    submitTx: bid.tx.unwrap_or_default(),
+                })
+                .collect(),
+        };
+        typed.eip712_signing_hash(&self.domain())
+    }
+
+    pub fn sign<S>(self, signer: &S) -> Result<SignedAttestation, AttestationError>
+    where
+        S: SignerSync,
+    {
+        let hash = self.signing_hash();
+        let signature = signer.sign_hash_sync(&hash)?;
+        let signer = signature.recover_address_from_prehash(&hash)?;
+        Ok(SignedAttestation {
+            attestation: self,
+            signer,
+            signature,
+        })
+    }
+}
+
+impl SignedAttestation {
+    /// Checks that the signature is over this attestation and by `signer`.
+    pub fn verify(&self) -> Result<(), AttestationError> {
+        let recovered = self
+            .signature
+            .recover_address_from_prehash(&self.attestation.signing_hash())?;
+        if recovered != self.signer {
+            return Err(AttestationError::SignerMismatch {
+                expected: self.signer,
+                recovered,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::signers::local::PrivateKeySigner;
+
+    fn attestation() -> Attestation {
+        Attestation {
+            version: ATTESTATION_VERSION,
+            chain_id: 1,
+            auction: Address::repeat_byte(0xaa),
+            block: 100,
+            clearing_price: U256::from(1) << 96,
+            graduated: true,
+            bids: vec![AttestedBid {
+                id: U256::from(7),
+                owner: Address::repeat_byte(0xbb),
+                tokens_filled: U256::from(1_000),
+                tx: None,
+            }],
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn signed_attestation_round_trips_through_json() {
+        let signer = PrivateKeySigner::random();
+        let signed = attestation().sign(&signer).unwrap();
+        assert_eq!(signed.signer, signer.address());
+
+        let json = serde_json::to_string(&signed).unwrap();
+        let parsed: SignedAttestation = serde_json::from_str(&json).unwrap();
+        parsed.verify().unwrap();
+    }
+
+    #[test]
+    fn tampered_attestation_fails_verification() {
+        let signer = PrivateKeySigner::random();
+        let mut signed = attestation().sign(&signer).unwrap();
+        signed.attestation.bids[0].tokens_filled = U256::from(2_000);
+
+        assert!(matches!(
+            signed.verify(),
+            Err(AttestationError::SignerMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,438 @@
+use alloy::primitives::{Address, U256, aliases::U24};
+use tracing::{debug, info};
+
+use crate::{
+    events::AuctionEvent,
+    executor::{EvaluationContext, ExecutorCache, Intent},
+    orchestrator::{HookSchedule, Strategy},
+    types::{
+        chain::ChainProfile,
+        checkpoint::Checkpoint,
+        clock::BlockClock,
+        config::AuctionConfig,
+        primitives::{BidId, BlockNumber, CurrencyAmount, Mps, Price, TokenAmount},
+        state::{AuctionPhase, AuctionState, TokenDepositStatus},
+    },
+};
+
+/// Replays a recorded auction through a [`Strategy`] without sending
+/// anything.
+///
+/// Every block from the first event (or `start_block`) through `claim_block`
+/// is shown to the strategy with the events recorded for it, in the same
+/// order the orchestrator would call its hooks. Submitted bids are checked
+/// the way the auction would check them and then echoed back to the strategy
+/// as `BidSubmitted` events from `owner` on the next block. Fills are
+/// estimated against the final clearing price, see [`SimulatedFill`].
+///
+/// Recorded events typically come from [`EventProducer::history`](crate::EventProducer::history).
+#[derive(Clone, Debug)]
+pub struct Backtest {
+    config: AuctionConfig,
+    events: Vec<AuctionEvent>,
+    owner: Address,
+    chain: ChainProfile,
+}
+
+/// How a simulated bid would have settled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SimulatedFill {
+    /// Priced above the final clearing price; fully filled at it.
+    Filled {
+        tokens: TokenAmount,
+        spent: CurrencyAmount,
+    },
+    /// Priced exactly at the final clearing price; the pro-rata fill depends
+    /// on demand at that tick, which the recorded events do not carry.
+    AtClearing,
+    /// Priced below the final clearing price; refunded. Any fill earned
+    /// before the bid was outbid is not modelled.
+    Outbid,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SimulatedBid {
+    pub id: BidId,
+    pub block: BlockNumber,
+    pub max_price: Price,
+    pub amount: CurrencyAmount,
+    pub fill: SimulatedFill,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct BacktestReport {
+    pub blocks_replayed: u64,
+    pub bids: Vec<SimulatedBid>,
+    /// Bid intents the auction would have rejected (wrong phase, off-tick or
+    /// not above the clearing price), and intents past their deadline.
+    pub rejected: usize,
+    pub exits: usize,
+    pub claims: usize,
+    pub checkpoints: usize,
+    /// Clearing price the fills were estimated at.
+    pub clearing_price: Option<Price>,
+}
+
+/// Simulated bid ids start here so they never collide with recorded ones.
+const SIMULATED_ID_BASE: U256 = U256::from_limbs([0, 0, 0, 1 << 63]);
+
+impl Backtest {
+    pub fn new(config: AuctionConfig, mut events: Vec<AuctionEvent>) -> Self {
+        events.sort_by_key(|event| event.block().as_u64());
+        Self {
+            config,
+            events,
+            owner: Address::ZERO,
+            chain: ChainProfile::default(),
+        }
+    }
+
+    /// Owner the strategy's own bids are attributed to in echoed events.
+    pub fn with_owner(mut self, owner: Address) -> Self {
+        self.owner = owner;
+        self
+    }
+
+    /// Chain whose block time the strategy's time estimates use.
+    pub fn with_chain_profile(mut self, chain: ChainProfile) -> Self {
+        self.chain = chain;
+        self
+    }
+
+    pub async fn run<S: Strategy>(&self, strategy: &mut S) -> BacktestReport {
+        let mut report = BacktestReport::default();
+        let mut hooks = HookSchedule::default();
+        let mut cache = ExecutorCache::new();
+        cache.clock = BlockClock::new(self.chain);
+        let mut echoed = Vec::new();
+        let mut next = 0;
+
+        // Older recordings may start after the deposit; assume it happened.
+        let has_deposit = self
+            .events
+            .iter()
+            .any(|event| matches!(event, AuctionEvent::TokensReceived { .. }));
+        cache.tokens_received = if has_deposit {
+            TokenDepositStatus::NotReceived
+        } else {
+            TokenDepositStatus::Received
+        };
+
+        let first = self
+            .events
+            .first()
+            .map_or(self.config.start_block.as_u64(), |event| {
+                event.block().as_u64().min(self.config.start_block.as_u64())
+            });
+        let claim = self.config.claim_block.as_u64();
+
+        for number in first..=claim {
+            let block = BlockNumber::new(number);
+            let delivered = self.events[next..]
+                .iter()
+                .take_while(|event| event.block() <= block)
+                .count();
+            let mut events = self.events[next..next + delivered].to_vec();
+            next += delivered;
+            events.append(&mut echoed);
+
+            for event in &events {
+                apply(&mut cache, event);
+            }
+            if number >= self.config.end_block.as_u64() {
+                cache.final_checkpoint = cache.latest_checkpoint;
+            }
+
+            let ctx = EvaluationContext {
+                block,
+                phase: AuctionState::compute_phase(&self.config, block, cache.tokens_received),
+                cache: &cache,
+                tracked_bids: report.bids.iter().map(|bid| bid.id).collect(),
+                config: &self.config,
+                events: &events,
+                client: None,
+                chain: self.chain,
+            };
+            let intents = hooks.intents(strategy, &ctx).await;
+            for intent in intents {
+                if let Some(event) = self.simulate(intent, &ctx, &mut report) {
+                    echoed.push(event);
+                }
+            }
+
+            report.blocks_replayed += 1;
+            if strategy.is_complete(&ctx) {
+                break;
+            }
+        }
+
+        // The auction carries on after the strategy is done.
+        for event in &self.events[next..] {
+            apply(&mut cache, event);
+        }
+        let clearing_price = cache
+            .latest_checkpoint
+            .map_or(self.config.floor_price, |checkpoint| {
+                checkpoint.clearing_price
+            });
+        for bid in &mut report.bids {
+            bid.fill = fill_at(bid, clearing_price);
+        }
+        report.clearing_price = Some(clearing_price);
+
+        info!(
+            blocks = report.blocks_replayed,
+            bids = report.bids.len(),
+            rejected = report.rejected,
+            "backtest finished"
+        );
+        report
+    }
+
+    /// Records one intent, returning the event the auction would have emitted
+    /// for an accepted bid.
+    fn simulate(
+        &self,
+        intent: Intent,
+        ctx: &EvaluationContext<'_>,
+        report: &mut BacktestReport,
+    ) -> Option<AuctionEvent> {
+        match intent {
+            Intent::SubmitBid {
+                max_price,
+                amount,
+                owner,
+                ..
+            } => {
+                let clearing_price = ctx
+                    .cache
+                    .latest_checkpoint
+                    .map_or(self.config.floor_price, |checkpoint| {
+                        checkpoint.clearing_price
+                    });
+                let accepted = matches!(ctx.phase, AuctionPhase::Active { .. })
+                    && !amount.is_zero()
+                    && self.config.is_valid_price(max_price)
+                    && max_price > clearing_price;
+                if !accepted {
+                    debug!(
+                        block = ctx.block.as_u64(),
+                        max_price = %max_price.as_u256(),
+                        "simulated bid rejected"
+                    );
+                    report.rejected += 1;
+                    return None;
+                }
+
+                let id = BidId::new(SIMULATED_ID_BASE + U256::from(report.bids.len()));
+                report.bids.push(SimulatedBid {
+                    id,
+                    block: ctx.block,
+                    max_price,
+                    amount,
+                    fill: SimulatedFill::AtClearing,
+                });
+                Some(AuctionEvent::BidSubmitted {
+                    block: ctx.block,
+                    bid_id: id,
+                    owner: owner.unwrap_or(self.owner),
+                    price: max_price,
+                    amount,
+                })
+            }
+            Intent::Exit { .. } => {
+                report.exits += 1;
+                None
+            }
+            Intent::Claim { .. } => {
+                report.claims += 1;
+                None
+            }
+            Intent::Checkpoint => {
+                report.checkpoints += 1;
+                None
+            }
+            Intent::Deadline { intent, execute_by } => {
+                if ctx.block > execute_by {
+                    debug!(
+                        block = ctx.block.as_u64(),
+                        execute_by = execute_by.as_u64(),
+                        "simulated intent expired"
+                    );
+                    report.rejected += 1;
+                    return None;
+                }
+                self.simulate(*intent, ctx, report)
+            }
+        }
+    }
+}
+
+fn apply(cache: &mut ExecutorCache, event: &AuctionEvent) {
+    match *event {
+        AuctionEvent::TokensReceived { .. } => {
+            cache.tokens_received = TokenDepositStatus::Received;
+        }
+        AuctionEvent::CheckpointUpdated {
+            block,
+            clearing_price,
+            cumulative_mps,
+        } => {
+            let prev_block = cache
+                .latest_checkpoint
+                .map_or(BlockNumber::new(0), |checkpoint| checkpoint.block);
+            cache.latest_checkpoint = Some(Checkpoint {
+                block,
+                clearing_price,
+                cumulative_mps,
+                cumulative_mps_per_price: U256::ZERO,
+                prev_block,
+                next_block: BlockNumber::TAIL_SENTINEL,
+            });
+        }
+        AuctionEvent::ClearingPriceUpdated {
+            block,
+            clearing_price,
+        } => {
+            let checkpoint = cache.latest_checkpoint.get_or_insert(Checkpoint {
+                block,
+                clearing_price,
+                cumulative_mps: Mps::new(U24::ZERO),
+                cumulative_mps_per_price: U256::ZERO,
+                prev_block: BlockNumber::new(0),
+                next_block: BlockNumber::TAIL_SENTINEL,
+            });
+            checkpoint.clearing_price = clearing_price;
+        }
+        _ => {}
+    }
+}
+
+fn fill_at(bid: &SimulatedBid, clearing_price: Price) -> SimulatedFill {
+    if bid.max_price < clearing_price {
+        SimulatedFill::Outbid
+    } else if bid.max_price == clearing_price || clearing_price.as_u256().is_zero() {
+        SimulatedFill::AtClearing
+    } else {
+        let tokens = clearing_price
+            .tokens_for(bid.amount)
+            .unwrap_or(TokenAmount::new(U256::MAX));
+        SimulatedFill::Filled {
+            tokens,
+            spent: bid.amount,
+        }
+    }
+}
+
+impl BacktestReport {
+    /// Currency put into accepted bids.
+    pub fn committed(&self) -> CurrencyAmount {
+        CurrencyAmount::new(self.bids.iter().map(|bid| bid.amount.as_u256()).sum())
+    }
+
+    /// Currency spent by filled bids.
+    pub fn spent(&self) -> CurrencyAmount {
+        CurrencyAmount::new(
+            self.bids
+                .iter()
+                .filter_map(|bid| match bid.fill {
+                    SimulatedFill::Filled { spent, .. } => Some(spent.as_u256()),
+                    _ => None,
+                })
+                .sum(),
+        )
+    }
+
+    pub fn tokens_filled(&self) -> TokenAmount {
+        TokenAmount::new(
+            self.bids
+                .iter()
+                .filter_map(|bid| match bid.fill {
+                    SimulatedFill::Filled { tokens, .. } => Some(tokens.as_u256()),
+                    _ => None,
+                })
+                .sum(),
+        )
+    }
+
+    /// Average Q96 price paid across filled bids.
+    pub fn average_price(&self) -> Option<Price> {
+        let tokens = self.tokens_filled().as_u256();
+        Price::from_ratio(self.spent().as_u256(), tokens)
+    }
+
+    /// Fraction of committed currency that was spent, from 0 to 1.
+    pub fn utilization(&self) -> f64 {
+        let committed = self.committed().as_u256();
+        if committed.is_zero() {
+            return 0.0;
+        }
+        let ppm = self.spent().as_u256() * U256::from(1_000_000) / committed;
+        ppm.to::<u64>() as f64 / 1_000_000.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        orchestrator::{Ladder, LadderStrategy},
+        types::{
+            config::fixture,
+            primitives::{Q96, TickSpacing},
+        },
+    };
+
+    fn config() -> AuctionConfig {
+        AuctionConfig {
+            start_block: BlockNumber::new(10),
+            end_block: BlockNumber::new(20),
+            claim_block: BlockNumber::new(25),
+            tick_spacing: TickSpacing::new(Q96),
+            floor_price: Price::new(Q96),
+            max_bid_price: Price::new(Q96 * U256::from(10)),
+            ..fixture::config()
+        }
+    }
+
+    fn price(ticks: u64) -> Price {
+        Price::new(Q96 * U256::from(ticks))
+    }
+
+    #[tokio::test]
+    async fn ladder_fills_above_final_clearing_price() {
+        let config = config();
+        let ladder = Ladder::linear(
+            &config,
+            price(2),
+            price(4),
+            3,
+            CurrencyAmount::new(U256::from(300)),
+        )
+        .unwrap();
+        let events = vec![AuctionEvent::CheckpointUpdated {
+            block: BlockNumber::new(15),
+            clearing_price: price(3),
+            cumulative_mps: Mps::new(U24::from(5_000_000)),
+        }];
+
+        let mut strategy = LadderStrategy::new(ladder);
+        let report = Backtest::new(config, events).run(&mut strategy).await;
+
+        let fills: Vec<_> = report.bids.iter().map(|bid| bid.fill).collect();
+        assert_eq!(
+            fills,
+            [
+                SimulatedFill::Outbid,
+                SimulatedFill::AtClearing,
+                SimulatedFill::Filled {
+                    tokens: TokenAmount::new(U256::from(33)),
+                    spent: CurrencyAmount::new(U256::from(100)),
+                },
+            ]
+        );
+        assert_eq!(report.rejected, 0);
+        assert_eq!(report.committed(), CurrencyAmount::new(U256::from(300)));
+        assert!((report.utilization() - 0.333_333).abs() < 1e-6);
+    }
+}
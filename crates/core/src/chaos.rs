@@ -0,0 +1,300 @@
+//! Fault injection for the RPC transport, for exercising failure handling in
+//! tests and CI.
+//!
+//! [`ChaosLayer`] is a tower layer for the RPC client. It sits between the
+//! provider and the real transport and, with configurable probabilities,
+//! fails requests, hides receipts, flips receipts to reverted, and rewinds the
+//! reported head block. Injections are driven by a seeded PRNG so a failing
+//! run can be reproduced.
+//!
+//! ```ignore
+//! let client = ClientBuilder::default()
+//!     .layer(ChaosLayer::new(ChaosConfig::new(7).with_timeouts(0.1)))
+//!     .http(url);
+//! let provider = ProviderBuilder::new().connect_client(client);
+//! ```
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    task::{Context, Poll},
+};
+
+use alloy::{
+    rpc::json_rpc::{RequestPacket, Response, ResponsePacket, ResponsePayload},
+    transports::{TransportError, TransportErrorKind, TransportFut},
+};
+use serde_json::{Value, value::RawValue};
+use tower::{Layer, Service};
+
+/// Probabilities (0.0..=1.0) for each kind of injected fault.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ChaosConfig {
+    pub seed: u64,
+    /// Any request fails with a transport error.
+    pub timeout: f64,
+    /// `eth_getTransactionReceipt` reports the receipt as not yet available.
+    pub receipt_delay: f64,
+    /// `eth_getTransactionReceipt` reports the transaction as reverted.
+    pub revert: f64,
+    /// `eth_blockNumber` reports a head `reorg_depth` blocks in the past.
+    pub reorg: f64,
+    pub reorg_depth: u64,
+}
+
+impl ChaosConfig {
+    /// No faults; enable them with the `with_*` methods.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            reorg_depth: 1,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_timeouts(mut self, probability: f64) -> Self {
+        self.timeout = probability;
+        self
+    }
+
+    pub fn with_receipt_delays(mut self, probability: f64) -> Self {
+        self.receipt_delay = probability;
+        self
+    }
+
+    pub fn with_reverts(mut self, probability: f64) -> Self {
+        self.revert = probability;
+        self
+    }
+
+    pub fn with_reorgs(mut self, probability: f64, depth: u64) -> Self {
+        self.reorg = probability;
+        self.reorg_depth = depth;
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ChaosLayer {
+    config: ChaosConfig,
+    rng: Arc<AtomicU64>,
+}
+
+impl ChaosLayer {
+    pub fn new(config: ChaosConfig) -> Self {
+        // xorshift has a fixed point at zero.
+        let seed = config.seed.max(1);
+        Self {
+            config,
+            rng: Arc::new(AtomicU64::new(seed)),
+        }
+    }
+}
+
+impl<S> Layer<S> for ChaosLayer {
+    type Service = ChaosService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ChaosService {
+            inner,
+            config: self.config,
+            rng: self.rng.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ChaosService<S> {
+    inner: S,
+    config: ChaosConfig,
+    rng: Arc<AtomicU64>,
+}
+
+impl<S> ChaosService<S> {
+    /// Returns true with probability `p`.
+    fn roll(&self, p: f64) -> bool {
+        if p <= 0.0 {
+            return false;
+        }
+        let previous = self
+            .rng
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |mut x| {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                Some(x)
+            })
+            .unwrap_or_default();
+        // Uniform in [0, 1) from the top 53 bits.
+        let sample = (previous >> 11) as f64 / (1u64 << 53) as f64;
+        sample < p
+    }
+}
+
+impl<S> Service<RequestPacket> for ChaosService<S>
+where
+    S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError>
+        + Send
+        + 'static
+        + Clone,
+    S::Future: Send + 'static,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        if self.roll(self.config.timeout) {
+            return Box::pin(async {
+                Err(TransportErrorKind::custom_str("chaos: injected timeout"))
+            });
+        }
+
+        // Only single requests are tampered with beyond timeouts.
+        let RequestPacket::Single(single) = &request else {
+            return Box::pin(self.inner.call(request));
+        };
+
+        let fault = match single.method() {
+            "eth_getTransactionReceipt" if self.roll(self.config.receipt_delay) => {
+                let response = Response {
+                    id: single.id().clone(),
+                    payload: ResponsePayload::Success(raw(&Value::Null)),
+                };
+                return Box::pin(async move { Ok(ResponsePacket::Single(response)) });
+            }
+            "eth_getTransactionReceipt" if self.roll(self.config.revert) => Fault::Revert,
+            "eth_blockNumber" if self.roll(self.config.reorg) => {
+                Fault::Rewind(self.config.reorg_depth)
+            }
+            _ => Fault::None,
+        };
+
+        let future = self.inner.call(request);
+        Box::pin(async move {
+            let response = future.await?;
+            Ok(match fault {
+                Fault::None => response,
+                Fault::Revert => map_result(response, |receipt| {
+                    if let Value::Object(fields) = receipt {
+                        fields.insert("status".into(), Value::String("0x0".into()));
+                    }
+                }),
+                Fault::Rewind(depth) => map_result(response, |head| {
+                    let rewound = head
+                        .as_str()
+                        .and_then(|hex| u64::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+                        .map(|block| block.saturating_sub(depth));
+                    if let Some(block) = rewound {
+                        *head = Value::String(format!("{block:#x}"));
+                    }
+                }),
+            })
+        })
+    }
+}
+
+enum Fault {
+    None,
+    Revert,
+    Rewind(u64),
+}
+
+/// Applies `edit` to a successful single response's result.
+fn map_result(packet: ResponsePacket, edit: impl FnOnce(&mut Value)) -> ResponsePacket {
+    let ResponsePacket::Single(Response {
+        id,
+        payload: ResponsePayload::Success(result),
+    }) = packet
+    else {
+        return packet;
+    };
+
+    let payload = match serde_json::from_str::<Value>(result.get()) {
+        Ok(mut value) => {
+            edit(&mut value);
+            raw(&value)
+        }
+        Err(_) => result,
+    };
+
+    ResponsePacket::Single(Response {
+        id,
+        payload: ResponsePayload::Success(payload),
+    })
+}
+
+fn raw(value: &Value) -> Box<RawValue> {
+    RawValue::from_string(value.to_string()).expect("serialized JSON is valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::rpc::json_rpc::{Id, Request};
+    use tower::service_fn;
+
+    fn request(method: &'static str) -> RequestPacket {
+        RequestPacket::Single(Request::new(method, Id::Number(1), ()).try_into().unwrap())
+    }
+
+    fn respond(
+        result: &'static str,
+    ) -> impl Service<
+        RequestPacket,
+        Response = ResponsePacket,
+        Error = TransportError,
+        Future = TransportFut<'static>,
+    > + Clone {
+        service_fn(move |_request: RequestPacket| -> TransportFut<'static> {
+            Box::pin(async move {
+                Ok(ResponsePacket::Single(Response {
+                    id: Id::Number(1),
+                    payload: ResponsePayload::Success(
+                        RawValue::from_string(result.into()).unwrap(),
+                    ),
+                }))
+            })
+        })
+    }
+
+    fn result(packet: ResponsePacket) -> Value {
+        match packet {
+            ResponsePacket::Single(Response {
+                payload: ResponsePayload::Success(result),
+                ..
+            }) => serde_json::from_str(result.get()).unwrap(),
+            other => panic!("unexpected response {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn reverts_receipts() {
+        let layer = ChaosLayer::new(ChaosConfig::new(1).with_reverts(1.0));
+        let mut service = layer.layer(respond(r#"{"status":"0x1"}"#));
+
+        let response = service
+            .call(request("eth_getTransactionReceipt"))
+            .await
+            .unwrap();
+        assert_eq!(result(response)["status"], "0x0");
+    }
+
+    #[tokio::test]
+    async fn rewinds_head_and_leaves_other_calls_alone() {
+        let layer = ChaosLayer::new(ChaosConfig::new(1).with_reorgs(1.0, 3));
+        let mut service = layer.layer(respond(r#""0x10""#));
+
+        let head = service.call(request("eth_blockNumber")).await.unwrap();
+        assert_eq!(result(head), "0xd");
+
+        let other = service.call(request("eth_chainId")).await.unwrap();
+        assert_eq!(result(other), "0x10");
+    }
+}
@@ -0,0 +1,197 @@
+use alloy::{eips::BlockNumberOrTag, primitives::Address, providers::Provider};
+use futures::future;
+use tracing::debug;
+
+use crate::{
+    client::AuctionClient,
+    error::{Error, StateError},
+    types::{
+        action::{ClaimParams, ClaimResult},
+        primitives::BidId,
+    },
+};
+
+/// How much gas one claim transaction may use.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClaimGasLimit {
+    PerTx(u64),
+    /// This fraction of the latest block's gas limit, e.g. `0.25`.
+    BlockFraction(f64),
+}
+
+impl ClaimGasLimit {
+    /// The limit in gas, given the latest block's gas limit.
+    pub fn resolve(&self, block_gas_limit: u64) -> u64 {
+        match *self {
+            Self::PerTx(gas) => gas,
+            Self::BlockFraction(fraction) => (block_gas_limit as f64 * fraction) as u64,
+        }
+    }
+}
+
+/// Claim gas as a fixed cost plus a cost per bid, fitted from two estimates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClaimGasModel {
+    pub base: u64,
+    pub per_bid: u64,
+}
+
+impl ClaimGasModel {
+    /// Fits the model to the estimate for one bid and for a batch of `bids`.
+    pub fn from_estimates(single: u64, batch: u64, bids: usize) -> Self {
+        let extra = bids.saturating_sub(1).max(1) as u64;
+        let per_bid = batch.saturating_sub(single).div_ceil(extra).max(1);
+        Self {
+            base: single.saturating_sub(per_bid),
+            per_bid,
+        }
+    }
+
+    pub fn estimate(&self, bids: usize) -> u64 {
+        self.base
+            .saturating_add(self.per_bid.saturating_mul(bids as u64))
+    }
+
+    /// Most bids one transaction can claim within `limit`; at least one,
+    /// since a single claim cannot be split further.
+    pub fn max_bids(&self, limit: u64) -> usize {
+        (limit.saturating_sub(self.base) / self.per_bid).max(1) as usize
+    }
+}
+
+/// Splits claims into transactions that stay under a [`ClaimGasLimit`] and
+/// sends them side by side, with nonces handed out up front so they do not
+/// collide in the mempool.
+///
+/// Without a limit every bid goes into one transaction, as
+/// [`AuctionClient::claim`] would send it.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ClaimBatcher {
+    limit: Option<ClaimGasLimit>,
+}
+
+impl ClaimBatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_gas_limit(mut self, limit: ClaimGasLimit) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// The claims to send for `bid_ids`, all owned by `owner`. Gas is only
+    /// estimated when a limit is set, and the batch is only split when the
+    /// whole of it exceeds the limit.
+    pub async fn plan<P>(
+        &self,
+        client: &AuctionClient<P>,
+        owner: Address,
+        bid_ids: Vec<BidId>,
+    ) -> Result<Vec<ClaimParams>, Error>
+    where
+        P: Provider + Clone,
+    {
+        let claim = |bid_ids: Vec<BidId>| ClaimParams {
+            owner,
+            bid_ids,
+            nonce: None,
+        };
+        let Some(limit) = self.limit else {
+            return Ok(vec![claim(bid_ids)]);
+        };
+        if bid_ids.len() < 2 {
+            return Ok(vec![claim(bid_ids)]);
+        }
+
+        let block_gas_limit = match limit {
+            ClaimGasLimit::PerTx(_) => 0,
+            ClaimGasLimit::BlockFraction(_) => client
+                .provider()
+                .get_block_by_number(BlockNumberOrTag::Latest)
+                .await
+                .map_err(StateError::from)?
+                .map_or(0, |block| block.header.gas_limit),
+        };
+        let limit = limit.resolve(block_gas_limit);
+
+        let whole = claim(bid_ids);
+        let batch = client.estimate_claim_gas(&whole).await?;
+        if batch <= limit {
+            return Ok(vec![whole]);
+        }
+        let single = client
+            .estimate_claim_gas(&claim(whole.bid_ids[..1].to_vec()))
+            .await?;
+        let model = ClaimGasModel::from_estimates(single, batch, whole.bid_ids.len());
+        let max_bids = model.max_bids(limit);
+        debug!(batch, limit, max_bids, "splitting claim");
+
+        Ok(whole
+            .bid_ids
+            .chunks(max_bids)
+            .map(|chunk| claim(chunk.to_vec()))
+            .collect())
+    }
+
+    /// Plans the claims for `bid_ids` and [`send`](Self::send)s them.
+    pub async fn claim<P>(
+        &self,
+        client: &AuctionClient<P>,
+        owner: Address,
+        bid_ids: Vec<BidId>,
+    ) -> Result<Vec<Result<ClaimResult, Error>>, Error>
+    where
+        P: Provider + Clone,
+    {
+        let claims = self.plan(client, owner, bid_ids).await?;
+        Self::send(client, claims).await
+    }
+
+    /// Sends planned claims all at once, each with the next of the client
+    /// owner's nonces. A failed claim does not stop the rest, but leaves a
+    /// nonce gap that holds back the ones after it.
+    pub async fn send<P>(
+        client: &AuctionClient<P>,
+        mut claims: Vec<ClaimParams>,
+    ) -> Result<Vec<Result<ClaimResult, Error>>, Error>
+    where
+        P: Provider + Clone,
+    {
+        if let [_, _, ..] = claims[..] {
+            let first = client.fetch_nonce(client.owner()).await?;
+            for (nonce, claim) in (first..).zip(&mut claims) {
+                claim.nonce = Some(nonce);
+            }
+        }
+        Ok(future::join_all(claims.into_iter().map(|claim| client.claim(claim))).await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn model_fits_estimates_and_caps_bids() {
+        // 60k to claim one bid, 20k for each one after.
+        let model = ClaimGasModel::from_estimates(60_000, 240_000, 10);
+        assert_eq!(
+            model,
+            ClaimGasModel {
+                base: 40_000,
+                per_bid: 20_000
+            }
+        );
+        assert_eq!(model.estimate(10), 240_000);
+        assert_eq!(model.max_bids(100_000), 3);
+        // A limit below one bid still claims one at a time.
+        assert_eq!(model.max_bids(10_000), 1);
+
+        assert_eq!(
+            ClaimGasLimit::BlockFraction(0.25).resolve(30_000_000),
+            7_500_000
+        );
+        assert_eq!(ClaimGasLimit::PerTx(1_000).resolve(30_000_000), 1_000);
+    }
+}
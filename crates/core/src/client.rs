@@ -1,31 +1,75 @@
-use std::sync::Arc;
+use std::{
+    ops::{Bound, Range, RangeBounds},
+    sync::{Arc, PoisonError, RwLock},
+};
+
+use std::collections::HashMap;
 
-use alloy::providers::Provider;
+use alloy::providers::{MULTICALL3_ADDRESS, Provider, bindings::IMulticall3};
 use alloy::{
-    consensus::TxReceipt,
-    primitives::{Address, Bytes, U256},
+    consensus::{Transaction, TxReceipt},
+    contract,
+    eips::BlockId,
+    network::{TransactionBuilder, TransactionResponse},
+    primitives::{Address, B256, Bytes, U256},
+    rpc::types::{Filter, Log, TransactionReceipt, TransactionRequest},
+    signers::{Signer, SignerSync},
+    sol_types::{SolCall, SolEvent, SolValue},
+};
+use flux_abi::{
+    IAuctionStateLens,
+    IContinuousClearingAuction::{self, IContinuousClearingAuctionErrors},
+    IContinuousClearingAuctionFactory, IERC20Minimal, IERC20Permit,
+};
+use futures::{
+    StreamExt, TryStreamExt, future,
+    stream::{self, BoxStream},
 };
-use flux_abi::{IContinuousClearingAuction, IERC20Minimal};
+use tracing::{Span, debug, info, instrument, warn};
 
+#[cfg(feature = "account")]
+use crate::account::SmartAccount;
 use crate::{
-    error::{ConfigError, Error, StateError, TransactionError},
+    approval::{Approval, SignedPermit},
+    error::{ConfigError, Error, StateError, TransactionError, ValidationError},
+    exit_hints::{self, ChainCheckpoints},
     hooks::ValidationHook,
+    journal::{JournalEntry, SendJournal, SendKey},
+    metrics,
+    relay::PrivateRelay,
+    shadow::ShadowFork,
     types::{
         action::{
-            ClaimParams, ClaimResult, ExitBidParams, ExitHints, ExitPartiallyFilledParams,
-            ExitResult, SubmitBidInput, SubmitBidParams, SubmitBidResult,
+            BatchCall, BatchOutcome, BatchResult, CheckpointResult, ClaimParams, ClaimResult,
+            ExitBidParams, ExitHints, ExitPartiallyFilledParams, ExitResult, SlippageGuard,
+            SubmitBidInput, SubmitBidParams, SubmitBidResult, TickHint,
         },
         bid::{Bid, TrackedBid},
         checkpoint::Checkpoint,
         config::AuctionConfig,
+        fill::{ExitEstimate, ExitPreview, ExitVariant, FillEstimate},
+        funding::FundingStatus,
         primitives::{
-            BidId, BlockNumber, CurrencyAddr, CurrencyAmount, HookAddr, Mps, Price, TickSpacing,
+            BidId, BlockNumber, CurrencyAddr, CurrencyAmount, HookAddr, Price, TickSpacing,
             TokenAddr, TokenAmount,
         },
-        state::{AuctionState, GraduationStatus, TokenDepositStatus},
+        risk::RiskViolation,
+        snapshot::AuctionSnapshot,
+        state::{
+            AccountBalances, AuctionMetrics, AuctionState, Competition, GraduationStatus,
+            StateSnapshot, TokenDepositStatus,
+        },
+        step::StepSchedule,
+        tick::{DemandCurve, TickBook, TickDemand},
     },
+    validation,
 };
 
+/// Client for a single auction.
+///
+/// Cloning is cheap: the config and tracked bids are shared, so clones can be
+/// handed to other tasks and see each other's submitted bids.
+#[derive(Clone)]
 pub struct AuctionClient<P>
 where
     P: Provider + Clone,
@@ -34,8 +78,22 @@ where
     auction: Address,
     owner: Address,
     hook: Arc<dyn ValidationHook>,
-    tracked_bids: Vec<TrackedBid>,
-    config: AuctionConfig,
+    tracked_bids: Arc<RwLock<Vec<TrackedBid>>>,
+    config: Arc<AuctionConfig>,
+    max_gas_per_tx: Option<u64>,
+    max_hint_ticks: usize,
+    required_currency_raised: Option<CurrencyAmount>,
+    max_clearing_price_drift: Option<Price>,
+    private_relay: Option<PrivateRelay>,
+    #[cfg(feature = "account")]
+    smart_account: Option<SmartAccount>,
+    lens: Option<Address>,
+    permit_router: Option<Address>,
+    shadow: Option<Arc<ShadowFork>>,
+    journal: Option<Arc<SendJournal>>,
+    fill_checkpoint_gaps: bool,
+    bid_page_size: usize,
+    bid_page_concurrency: usize,
 }
 
 impl<P> AuctionClient<P>
@@ -50,20 +108,165 @@ where
         tracked_bids: Vec<TrackedBid>,
     ) -> Result<Self, Error> {
         let config = Self::fetch_config(&provider, auction).await?;
-        Ok(Self {
+        Ok(Self::from_config(
             provider,
-            auction,
+            config,
             owner,
-            hook: hook.into(),
+            hook,
             tracked_bids,
-            config,
-        })
+        ))
+    }
+
+    /// Builds a client around a config fetched earlier with
+    /// [`fetch_config`](Self::fetch_config), skipping the round trip.
+    pub fn from_config(
+        provider: P,
+        config: AuctionConfig,
+        owner: Address,
+        hook: impl Into<Arc<dyn ValidationHook>>,
+        tracked_bids: Vec<TrackedBid>,
+    ) -> Self {
+        Self {
+            provider,
+            auction: config.address,
+            owner,
+            hook: hook.into(),
+            tracked_bids: Arc::new(RwLock::new(tracked_bids)),
+            config: Arc::new(config),
+            max_gas_per_tx: None,
+            max_hint_ticks: Self::DEFAULT_MAX_HINT_TICKS,
+            required_currency_raised: None,
+            max_clearing_price_drift: None,
+            private_relay: None,
+            shadow: None,
+            journal: None,
+            #[cfg(feature = "account")]
+            smart_account: None,
+            lens: None,
+            permit_router: None,
+            fill_checkpoint_gaps: true,
+            bid_page_size: Self::DEFAULT_BID_PAGE_SIZE,
+            bid_page_concurrency: Self::DEFAULT_BID_PAGE_CONCURRENCY,
+        }
+    }
+
+    /// Ticks [`prepare_bid`](Self::prepare_bid) reads looking for a
+    /// `prevTickPrice` hint before it gives up and submits without one.
+    pub const DEFAULT_MAX_HINT_TICKS: usize = 64;
+
+    /// Bids [`fetch_all_bids`](Self::fetch_all_bids) reads per multicall.
+    pub const DEFAULT_BID_PAGE_SIZE: usize = 100;
+
+    /// Multicalls [`fetch_all_bids`](Self::fetch_all_bids) keeps in flight.
+    pub const DEFAULT_BID_PAGE_CONCURRENCY: usize = 4;
+
+    /// Read at most `ticks` ticks looking for a hint; `0` always submits
+    /// unhinted.
+    pub fn with_max_hint_ticks(mut self, ticks: usize) -> Self {
+        self.max_hint_ticks = ticks;
+        self
+    }
+
+    /// Read `size` bids per multicall in [`fetch_all_bids`](Self::fetch_all_bids).
+    pub fn with_bid_page_size(mut self, size: usize) -> Self {
+        self.bid_page_size = size.max(1);
+        self
+    }
+
+    /// Keep up to `pages` multicalls in flight in
+    /// [`fetch_all_bids`](Self::fetch_all_bids).
+    pub fn with_bid_page_concurrency(mut self, pages: usize) -> Self {
+        self.bid_page_concurrency = pages.max(1);
+        self
+    }
+
+    /// Refuse to send transactions whose gas estimate exceeds `gas`.
+    pub fn with_max_gas_per_tx(mut self, gas: u64) -> Self {
+        self.set_max_gas_per_tx(gas);
+        self
+    }
+
+    /// The auction's graduation threshold, which it does not expose itself;
+    /// see [`fetch_required_currency_raised`](Self::fetch_required_currency_raised).
+    pub fn with_required_currency_raised(mut self, amount: CurrencyAmount) -> Self {
+        self.required_currency_raised = Some(amount);
+        self
+    }
+
+    /// Have [`prepare_bid`](Self::prepare_bid) attach a [`SlippageGuard`],
+    /// so [`submit_bid`](Self::submit_bid) re-reads the checkpoint and refuses
+    /// to send once the clearing price has risen by more than `drift` (Q96).
+    pub fn with_max_clearing_price_drift(mut self, drift: Price) -> Self {
+        self.max_clearing_price_drift = Some(drift);
+        self
+    }
+
+    /// Send the transactions `relay` routes through it instead of the public
+    /// mempool.
+    pub fn with_private_relay(mut self, relay: PrivateRelay) -> Self {
+        self.private_relay = Some(relay);
+        self
+    }
+
+    /// Rehearse every transaction on `fork` at the current block first and
+    /// only send it if it passes; see [`ShadowFork::rehearse`].
+    pub fn with_shadow_fork(mut self, fork: Arc<ShadowFork>) -> Self {
+        self.shadow = Some(fork);
+        self
+    }
+
+    /// Record every transaction in `journal` before sending it, and return
+    /// the receipt of one that already landed instead of sending it again.
+    /// User operations from a smart account are not journaled.
+    pub fn with_journal(mut self, journal: Arc<SendJournal>) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// Send every transaction as a user operation from `account` instead,
+    /// ahead of any private relay. The client's owner should be the
+    /// account's address, which then holds the bids and pays the currency.
+    #[cfg(feature = "account")]
+    pub fn with_smart_account(mut self, account: SmartAccount) -> Self {
+        self.smart_account = Some(account);
+        self
+    }
+
+    /// Read state through the `IAuctionStateLens` at `lens`; see
+    /// [`fetch_state_via_lens`](Self::fetch_state_via_lens).
+    pub fn with_lens(mut self, lens: Address) -> Self {
+        self.lens = Some(lens);
+        self
+    }
+
+    /// A router that submits bids with an EIP-2612 permit for the currency;
+    /// see [`ensure_currency_allowance`](Self::ensure_currency_allowance).
+    pub fn with_permit_router(mut self, router: Address) -> Self {
+        self.permit_router = Some(router);
+        self
+    }
+
+    /// Whether [`prepare_exit_partially_filled`](Self::prepare_exit_partially_filled)
+    /// calls `checkpoint()` when the auction's checkpoints stop short of the
+    /// current block, rather than only warning that the hints may be
+    /// rejected. On by default.
+    pub fn with_checkpoint_gap_fill(mut self, enabled: bool) -> Self {
+        self.fill_checkpoint_gaps = enabled;
+        self
+    }
+
+    pub fn set_max_gas_per_tx(&mut self, gas: u64) {
+        self.max_gas_per_tx = Some(gas);
     }
 
     pub fn config(&self) -> &AuctionConfig {
         &self.config
     }
 
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
     pub fn address(&self) -> Address {
         self.auction
     }
@@ -72,38 +275,327 @@ where
         self.owner
     }
 
-    pub fn tracked_bids(&self) -> impl Iterator<Item = &TrackedBid> {
-        self.tracked_bids.iter()
+    /// Snapshot of the bids tracked by this client and its clones.
+    pub fn tracked_bids(&self) -> Vec<TrackedBid> {
+        self.tracked_bids
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Starts tracking a bid, visible to every clone of this client.
+    /// Tracks `bid` unless its id is tracked already.
+    pub fn track_bid(&self, bid: TrackedBid) {
+        let mut tracked = self
+            .tracked_bids
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        if !tracked
+            .iter()
+            .any(|seen| seen.id.as_u256() == bid.id.as_u256())
+        {
+            tracked.push(bid);
+        }
     }
 
     pub fn hook(&self) -> &Arc<dyn ValidationHook> {
         &self.hook
     }
 
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction))]
     pub async fn fetch_checkpoint(&self) -> Result<Checkpoint, Error> {
         let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
 
-        let (raw, block) = self
-            .provider
-            .multicall()
-            .add(cca.latestCheckpoint())
-            .add(cca.lastCheckpointedBlock())
-            .aggregate()
+        let (raw, block) = metrics::timed(
+            "fetch_checkpoint",
+            self.provider
+                .multicall()
+                .add(cca.latestCheckpoint())
+                .add(cca.lastCheckpointedBlock())
+                .aggregate(),
+        )
+        .await
+        .map_err(StateError::from)?;
+
+        Ok(Checkpoint::from_onchain(BlockNumber::new(block), raw))
+    }
+
+    /// The checkpoint the auction wrote at `block`.
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction, block = block.as_u64()))]
+    pub async fn fetch_checkpoint_at(&self, block: BlockNumber) -> Result<Checkpoint, Error> {
+        let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
+        let raw = metrics::timed(
+            "fetch_checkpoint_at",
+            cca.checkpoints(block.as_u64()).call(),
+        )
+        .await
+        .map_err(StateError::from)?;
+        Ok(Checkpoint::from_onchain(block, raw))
+    }
+
+    /// Estimates how much of `bid` has filled as of `state`, read at `block`.
+    ///
+    /// A bid above the clearing price costs one call for its start
+    /// checkpoint; one at or below it also walks the checkpoints after that
+    /// to find the last one it was still filling at.
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction, bid_id = %bid.id.as_u256()))]
+    pub async fn estimate_fill(
+        &self,
+        bid: &Bid,
+        state: &StateSnapshot,
+        block: BlockNumber,
+    ) -> Result<FillEstimate, Error> {
+        if bid.exited_block.is_some() {
+            return Ok(FillEstimate::exited(bid));
+        }
+        if block >= self.config.end_block
+            && matches!(state.graduation, GraduationStatus::NotGraduated)
+        {
+            return Ok(FillEstimate::full_refund(bid));
+        }
+
+        let start = self.fetch_checkpoint_at(bid.start_block).await?;
+        // An in-the-money bid has been filled at every checkpoint so far.
+        if bid.max_price > state.checkpoint.clearing_price {
+            return Ok(FillEstimate::between(bid, &start, &state.checkpoint));
+        }
+
+        // Otherwise walk forward to the last checkpoint still below the bid's max price.
+        let mut upper = start;
+        while !upper.is_terminal() {
+            let next = self.fetch_checkpoint_at(upper.next_block).await?;
+            if next.clearing_price >= bid.max_price {
+                break;
+            }
+            upper = next;
+        }
+        Ok(FillEstimate::between(bid, &start, &upper))
+    }
+
+    /// Walks the initialized ticks from the floor price up and returns the
+    /// demand resting at each. Costs one call per tick.
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction))]
+    pub async fn fetch_demand_curve(&self) -> Result<DemandCurve, Error> {
+        let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
+        let sentinel = metrics::timed("fetch_demand_curve", cca.MAX_TICK_PTR().call())
+            .await
+            .map_err(StateError::from)?;
+
+        let mut ticks = Vec::new();
+        let mut price = self.config.floor_price.as_u256();
+        while price != sentinel {
+            let tick = metrics::timed("fetch_demand_curve", cca.ticks(price).call())
+                .await
+                .map_err(StateError::from)?;
+            ticks.push(TickDemand::from_q96(
+                Price::new(price),
+                tick.currencyDemandQ96,
+            ));
+            // An uninitialized tick points nowhere; stop rather than loop.
+            if tick.next <= price {
+                break;
+            }
+            price = tick.next;
+        }
+
+        Ok(DemandCurve::new(ticks))
+    }
+
+    /// Number of bids submitted to the auction so far.
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction))]
+    pub async fn fetch_bid_count(&self) -> Result<u64, Error> {
+        let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
+        let next_bid_id = metrics::timed("fetch_bid_count", cca.nextBidId().call())
             .await
             .map_err(StateError::from)?;
+        Ok(next_bid_id.saturating_to())
+    }
+
+    /// Bid count and demand concentration. Walks the whole demand curve, so
+    /// costs one call per initialized tick.
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction))]
+    pub async fn fetch_competition(&self) -> Result<Competition, Error> {
+        let (total_bids, curve) =
+            futures::try_join!(self.fetch_bid_count(), self.fetch_demand_curve())?;
+        Ok(Competition {
+            total_bids,
+            total_demand: curve.total_demand(),
+            concentration: curve.concentration(),
+        })
+    }
+
+    /// Walks up to `depth` initialized ticks starting at
+    /// `nextActiveTickPrice`, the lowest tick still above the clearing price.
+    /// Costs one batched call for the starting point, then one per tick.
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction, depth))]
+    pub async fn fetch_tick_book(&self, depth: usize) -> Result<TickBook, Error> {
+        let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
+        let (sentinel, mut price) = metrics::timed(
+            "fetch_tick_book",
+            self.provider
+                .multicall()
+                .add(cca.MAX_TICK_PTR())
+                .add(cca.nextActiveTickPrice())
+                .aggregate(),
+        )
+        .await
+        .map_err(StateError::from)?;
+
+        let mut ticks = Vec::with_capacity(depth);
+        while price != sentinel {
+            if ticks.len() == depth {
+                return Ok(TickBook::new(ticks, true));
+            }
+            let tick = metrics::timed("fetch_tick_book", cca.ticks(price).call())
+                .await
+                .map_err(StateError::from)?;
+            ticks.push(TickDemand::from_q96(
+                Price::new(price),
+                tick.currencyDemandQ96,
+            ));
+            // An uninitialized tick points nowhere; stop rather than loop.
+            if tick.next <= price {
+                break;
+            }
+            price = tick.next;
+        }
+
+        Ok(TickBook::new(ticks, false))
+    }
+
+    /// Currency raised and tokens cleared as of the latest checkpoint, with
+    /// the graduation threshold if one was given.
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction))]
+    pub async fn fetch_auction_metrics(&self) -> Result<AuctionMetrics, Error> {
+        let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
+        let (currency_raised, total_cleared) = metrics::timed(
+            "fetch_auction_metrics",
+            self.provider
+                .multicall()
+                .add(cca.currencyRaised())
+                .add(cca.totalCleared())
+                .aggregate(),
+        )
+        .await
+        .map_err(StateError::from)?;
+
+        Ok(AuctionMetrics {
+            currency_raised: CurrencyAmount::new(currency_raised),
+            total_cleared: TokenAmount::new(total_cleared),
+            required_currency_raised: self.required_currency_raised,
+        })
+    }
+
+    /// Checkpoint, metrics and graduation in one call.
+    ///
+    /// With a lens the checkpoint is the one the auction would write at the
+    /// current block, so it is never stale. Without one this falls back to a
+    /// multicall of the auction's getters, which return the last checkpoint
+    /// written.
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction, lens = ?self.lens))]
+    pub async fn fetch_state_via_lens(&self) -> Result<StateSnapshot, Error> {
+        let Some(lens) = self.lens else {
+            return self.fetch_state_via_multicall().await;
+        };
 
-        Ok(Checkpoint {
-            block: BlockNumber::new(block),
-            clearing_price: Price::new(raw.clearingPrice),
-            cumulative_mps: Mps::new(raw.cumulativeMps),
-            prev_block: BlockNumber::new(raw.prev),
-            next_block: BlockNumber::new(raw.next),
+        let lens = IAuctionStateLens::new(lens, &self.provider);
+        let (state, block) = metrics::timed(
+            "fetch_state",
+            self.provider
+                .multicall()
+                .add(lens.state(self.auction))
+                .get_block_number()
+                .aggregate(),
+        )
+        .await
+        .map_err(StateError::from)?;
+
+        let raw = state.checkpoint;
+        Ok(StateSnapshot {
+            checkpoint: Checkpoint::from_lens(BlockNumber::new(block.saturating_to()), raw),
+            metrics: AuctionMetrics {
+                currency_raised: CurrencyAmount::new(state.currencyRaised),
+                total_cleared: TokenAmount::new(state.totalCleared),
+                required_currency_raised: self.required_currency_raised,
+            },
+            graduation: if state.isGraduated {
+                GraduationStatus::Graduated
+            } else {
+                GraduationStatus::NotGraduated
+            },
+        })
+    }
+
+    async fn fetch_state_via_multicall(&self) -> Result<StateSnapshot, Error> {
+        let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
+        let (raw, block, currency_raised, total_cleared, graduated) = metrics::timed(
+            "fetch_state",
+            self.provider
+                .multicall()
+                .add(cca.latestCheckpoint())
+                .add(cca.lastCheckpointedBlock())
+                .add(cca.currencyRaised())
+                .add(cca.totalCleared())
+                .add(cca.isGraduated())
+                .aggregate(),
+        )
+        .await
+        .map_err(StateError::from)?;
+
+        Ok(StateSnapshot {
+            checkpoint: Checkpoint::from_onchain(BlockNumber::new(block), raw),
+            metrics: AuctionMetrics {
+                currency_raised: CurrencyAmount::new(currency_raised),
+                total_cleared: TokenAmount::new(total_cleared),
+                required_currency_raised: self.required_currency_raised,
+            },
+            graduation: if graduated {
+                GraduationStatus::Graduated
+            } else {
+                GraduationStatus::NotGraduated
+            },
         })
     }
 
+    /// Reads `requiredCurrencyRaised` from the parameters `factory` logged
+    /// when it created this auction; `None` if it did not create it.
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction, %factory))]
+    pub async fn fetch_required_currency_raised(
+        &self,
+        factory: Address,
+    ) -> Result<Option<CurrencyAmount>, Error> {
+        let filter = Filter::new()
+            .address(factory)
+            .event_signature(IContinuousClearingAuctionFactory::AuctionCreated::SIGNATURE_HASH)
+            .topic1(B256::left_padding_from(self.auction.as_slice()))
+            .to_block(self.config.start_block.as_u64());
+        let logs = self
+            .provider
+            .get_logs(&filter)
+            .await
+            .map_err(StateError::from)?;
+
+        let required = logs.iter().find_map(|log| {
+            let created = log
+                .log_decode::<IContinuousClearingAuctionFactory::AuctionCreated>()
+                .ok()?;
+            let params = IContinuousClearingAuction::AuctionParameters::abi_decode(
+                &created.inner.data.configData,
+            )
+            .ok()?;
+            Some(CurrencyAmount::new(U256::from(
+                params.requiredCurrencyRaised,
+            )))
+        });
+        Ok(required)
+    }
+
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction))]
     pub async fn fetch_graduation(&self) -> Result<GraduationStatus, Error> {
         let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
-        let graduated = cca.isGraduated().call().await.map_err(StateError::from)?;
+        let graduated = metrics::timed("fetch_graduation", cca.isGraduated().call())
+            .await
+            .map_err(StateError::from)?;
 
         Ok(if graduated {
             GraduationStatus::Graduated
@@ -112,11 +604,30 @@ where
         })
     }
 
+    /// The supply schedule, decoded from the `auctionStepsData` the auction
+    /// stores as the code of its `pointer()` contract (SSTORE2, behind a
+    /// leading zero byte).
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction))]
+    pub async fn fetch_step_schedule(&self) -> Result<StepSchedule, Error> {
+        let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
+        let pointer = metrics::timed("fetch_step_schedule", cca.pointer().call())
+            .await
+            .map_err(StateError::from)?;
+        let code = self
+            .provider
+            .get_code_at(pointer)
+            .await
+            .map_err(StateError::from)?;
+        let data = code
+            .get(1..)
+            .ok_or(ConfigError::MalformedSteps("pointer has no code"))?;
+        Ok(StepSchedule::parse(self.config.start_block, data)?)
+    }
+
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction))]
     pub async fn fetch_token_balance(&self) -> Result<TokenDepositStatus, Error> {
         let token = IERC20Minimal::new(self.config.token.as_address(), &self.provider);
-        let balance = token
-            .balanceOf(self.auction)
-            .call()
+        let balance = metrics::timed("fetch_token_balance", token.balanceOf(self.auction).call())
             .await
             .map_err(StateError::from)?;
 
@@ -127,6 +638,179 @@ where
         }
     }
 
+    /// Auction tokens held by `account`.
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction))]
+    pub async fn fetch_token_balance_of(&self, account: Address) -> Result<TokenAmount, Error> {
+        let token = IERC20Minimal::new(self.config.token.as_address(), &self.provider);
+        let balance = metrics::timed("fetch_token_balance_of", token.balanceOf(account).call())
+            .await
+            .map_err(StateError::from)?;
+        Ok(TokenAmount::new(balance))
+    }
+
+    /// The currency's `DOMAIN_SEPARATOR()` and the owner's permit nonce, or
+    /// `None` if the currency does not implement EIP-2612.
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction))]
+    pub async fn fetch_permit_support(&self) -> Result<Option<(B256, U256)>, Error> {
+        let token = IERC20Permit::new(self.config.currency.as_address(), &self.provider);
+        let domain_separator = token.DOMAIN_SEPARATOR();
+        let nonce = token.nonces(self.owner);
+        match futures::try_join!(
+            domain_separator.call().into_future(),
+            nonce.call().into_future()
+        ) {
+            Ok(support) => Ok(Some(support)),
+            Err(contract::Error::TransportError(error)) if !error.is_error_resp() => {
+                Err(StateError::Transport(error).into())
+            }
+            Err(error) => {
+                debug!(%error, "currency does not support permit");
+                Ok(None)
+            }
+        }
+    }
+
+    /// Makes `amount` of the currency spendable for a bid by the owner.
+    ///
+    /// With a [permit router](Self::with_permit_router), a `signer` for the
+    /// owner and a currency that implements EIP-2612, this signs a permit
+    /// valid until `deadline` (a timestamp) for the router to use, and sends
+    /// nothing. Otherwise it sends an `approve` for the auction, unless the
+    /// allowance already covers `amount`. An auction paid in native currency
+    /// is refused, as its bids carry the currency as `msg.value`.
+    #[instrument(skip_all, fields(auction = %self.auction, amount = %amount.as_u256()))]
+    pub async fn ensure_currency_allowance<S>(
+        &self,
+        amount: CurrencyAmount,
+        signer: Option<&S>,
+        deadline: u64,
+    ) -> Result<Approval, Error>
+    where
+        S: Signer + SignerSync,
+    {
+        if self.config.is_native_currency() {
+            return Err(ValidationError::NativeCurrencyApproval.into());
+        }
+        let currency = self.config.currency.as_address();
+        let token = IERC20Permit::new(currency, &self.provider);
+
+        if let (Some(router), Some(signer)) = (self.permit_router, signer) {
+            let allowance = token
+                .allowance(self.owner, router)
+                .call()
+                .await
+                .map_err(StateError::from)?;
+            if allowance >= amount.as_u256() {
+                return Ok(Approval::NotNeeded);
+            }
+            if let Some((domain_separator, nonce)) = self.fetch_permit_support().await? {
+                let permit = SignedPermit::sign(
+                    signer,
+                    currency,
+                    domain_separator,
+                    router,
+                    amount.as_u256(),
+                    nonce,
+                    U256::from(deadline),
+                )
+                .map_err(TransactionError::from)?;
+                return Ok(Approval::Permit(permit));
+            }
+            debug!("currency has no permit; falling back to approve");
+        }
+
+        let allowance = token
+            .allowance(self.owner, self.auction)
+            .call()
+            .await
+            .map_err(StateError::from)?;
+        if allowance >= amount.as_u256() {
+            return Ok(Approval::NotNeeded);
+        }
+        let tx = IERC20Minimal::new(currency, &self.provider)
+            .approve(self.auction, amount.as_u256())
+            .into_transaction_request()
+            .from(self.owner);
+        let receipt = self.send_and_confirm(tx, "approve", 1).await?;
+        Ok(Approval::Approved {
+            spender: self.auction,
+            tx_hash: receipt.transaction_hash,
+            gas_used: receipt.gas_used,
+        })
+    }
+
+    /// `account`'s native balance and its balance in the auction's currency.
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction))]
+    pub async fn fetch_balances(&self, account: Address) -> Result<AccountBalances, Error> {
+        let native = metrics::timed("fetch_balance", self.provider.get_balance(account))
+            .await
+            .map_err(StateError::from)?;
+        let currency = if self.config.is_native_currency() {
+            native
+        } else {
+            let token = IERC20Minimal::new(self.config.currency.as_address(), &self.provider);
+            metrics::timed("fetch_currency_balance", token.balanceOf(account).call())
+                .await
+                .map_err(StateError::from)?
+        };
+        Ok(AccountBalances {
+            native,
+            currency: CurrencyAmount::new(currency),
+        })
+    }
+
+    /// `owner`'s balances, allowance to the auction and the gas price, for
+    /// checking a [`FundingNeed`](crate::FundingNeed) against. A permit
+    /// router's allowance is not looked at.
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction))]
+    pub async fn fetch_funding(&self, owner: Address) -> Result<FundingStatus, Error> {
+        let allowance = async {
+            if self.config.is_native_currency() {
+                return Ok(None);
+            }
+            let token = IERC20Permit::new(self.config.currency.as_address(), &self.provider);
+            let allowance = metrics::timed(
+                "fetch_allowance",
+                token.allowance(owner, self.auction).call(),
+            )
+            .await
+            .map_err(StateError::from)?;
+            Ok::<_, Error>(Some(allowance))
+        };
+        let (balances, allowance, gas_price) = futures::try_join!(
+            self.fetch_balances(owner),
+            allowance,
+            self.fetch_gas_price()
+        )?;
+        Ok(FundingStatus {
+            owner,
+            balances,
+            allowance,
+            gas_price,
+        })
+    }
+
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction))]
+    pub async fn fetch_gas_price(&self) -> Result<u128, Error> {
+        let price = metrics::timed("fetch_gas_price", self.provider.get_gas_price())
+            .await
+            .map_err(StateError::from)?;
+        Ok(price)
+    }
+
+    /// Nonce of `account`'s next transaction, counting ones still pending.
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction))]
+    pub async fn fetch_nonce(&self, account: Address) -> Result<u64, Error> {
+        let nonce = metrics::timed(
+            "fetch_nonce",
+            self.provider.get_transaction_count(account).pending(),
+        )
+        .await
+        .map_err(StateError::from)?;
+        Ok(nonce)
+    }
+
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction, bids = bid_ids.len()))]
     pub async fn fetch_bids(&self, bid_ids: &[BidId]) -> Result<Vec<Bid>, Error> {
         // Might we want to throw here?
         if bid_ids.is_empty() {
@@ -137,12 +821,10 @@ where
 
         if bid_ids.len() == 1 {
             let bid_id = bid_ids[0];
-            let bid_return = cca
-                .bids(bid_id.as_u256())
-                .call()
+            let bid_return = metrics::timed("fetch_bids", cca.bids(bid_id.as_u256()).call())
                 .await
                 .map_err(StateError::from)?;
-            return Ok(vec![Self::decode_bid(bid_id, bid_return)]);
+            return Ok(vec![Bid::from_onchain(bid_id, bid_return)]);
         }
 
         let mut multicall = self.provider.multicall().dynamic();
@@ -151,37 +833,113 @@ where
             multicall = multicall.add_dynamic(cca.bids(bid_id.as_u256()));
         }
 
-        let bid_returns = multicall.aggregate().await.map_err(StateError::from)?;
+        let bid_returns = metrics::timed("fetch_bids", multicall.aggregate())
+            .await
+            .map_err(StateError::from)?;
 
         let bids = bid_ids
             .iter()
-            .zip(bid_returns.into_iter())
-            .map(|(bid_id, bid_return)| Self::decode_bid(*bid_id, bid_return))
+            .zip(bid_returns)
+            .map(|(bid_id, bid_return)| Bid::from_onchain(*bid_id, bid_return))
             .collect();
 
         Ok(bids)
     }
 
-    fn decode_bid(bid_id: BidId, bid_return: IContinuousClearingAuction::Bid) -> Bid {
-        let exited_block = if bid_return.exitedBlock == 0 {
-            None
-        } else {
-            Some(BlockNumber::new(bid_return.exitedBlock))
+    /// Every bid with an id in `range`, clamped to the `nextBidId()` read
+    /// when the stream starts, in id order. Reads them in pages of
+    /// [`with_bid_page_size`](Self::with_bid_page_size) bids, with
+    /// [`with_bid_page_concurrency`](Self::with_bid_page_concurrency) pages
+    /// in flight; the stream ends after the first error.
+    pub fn fetch_all_bids(
+        &self,
+        range: impl RangeBounds<u64>,
+    ) -> BoxStream<'_, Result<Bid, Error>> {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
         };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => u64::MAX,
+        };
+        let page_size = self.bid_page_size as u64;
+
+        stream::once(self.fetch_bid_count())
+            .map_ok(move |count| {
+                let end = end.min(count);
+                let pages = (start..end).step_by(self.bid_page_size).map(move |first| {
+                    let ids: Vec<_> = (first..end.min(first.saturating_add(page_size)))
+                        .map(|id| BidId::new(U256::from(id)))
+                        .collect();
+                    async move { self.fetch_bids(&ids).await }
+                });
+                stream::iter(pages)
+                    .buffered(self.bid_page_concurrency)
+                    .map_ok(|bids| stream::iter(bids.into_iter().map(Ok)))
+                    .try_flatten()
+            })
+            .try_flatten()
+            .take_while({
+                let mut failed = false;
+                move |item| {
+                    let go_on = !failed;
+                    failed |= item.is_err();
+                    future::ready(go_on)
+                }
+            })
+            .boxed()
+    }
 
-        Bid {
-            id: bid_id,
-            owner: bid_return.owner,
-            max_price: Price::new(bid_return.maxPrice),
-            amount: CurrencyAmount::new(bid_return.amountQ96),
-            start_block: BlockNumber::new(bid_return.startBlock),
-            start_cumulative_mps: Mps::new(bid_return.startCumulativeMps),
-            exited_block,
-            tokens_filled: TokenAmount::new(bid_return.tokensFilled),
+    /// Checks that `provider` is on `expected_chain_id`, when given, and that
+    /// `auction` holds a contract answering `MAX_BID_PRICE()`, so a wrong
+    /// address or RPC fails here rather than with a decode error later.
+    #[instrument(level = "debug", skip_all, fields(auction = %auction))]
+    pub async fn verify(
+        provider: &P,
+        auction: Address,
+        expected_chain_id: Option<u64>,
+    ) -> Result<(), Error> {
+        if let Some(expected) = expected_chain_id {
+            let actual = provider.get_chain_id().await.map_err(ConfigError::from)?;
+            if actual != expected {
+                return Err(ConfigError::WrongChain { expected, actual }.into());
+            }
+        }
+
+        let code = provider
+            .get_code_at(auction)
+            .await
+            .map_err(ConfigError::from)?;
+        if code.is_empty() {
+            return Err(ConfigError::WrongContract {
+                address: auction,
+                reason: "no contract code at this address".to_string(),
+            }
+            .into());
+        }
+
+        let cca = IContinuousClearingAuction::new(auction, provider);
+        match cca.MAX_BID_PRICE().call().await {
+            Ok(_) => Ok(()),
+            Err(contract::Error::TransportError(error)) if !error.is_error_resp() => {
+                Err(ConfigError::Transport(error).into())
+            }
+            Err(error) => Err(ConfigError::WrongContract {
+                address: auction,
+                reason: format!("MAX_BID_PRICE() probe failed: {error}"),
+            }
+            .into()),
         }
     }
 
+    /// Reads the auction's immutable parameters after [`verify`](Self::verify)ing
+    /// the address.
+    #[instrument(level = "debug", skip_all, fields(auction = %auction))]
     pub async fn fetch_config(provider: &P, auction: Address) -> Result<AuctionConfig, Error> {
+        Self::verify(provider, auction, None).await?;
         let cca = IContinuousClearingAuction::new(auction, provider);
 
         let (
@@ -231,16 +989,40 @@ where
         input: SubmitBidInput,
         state: &AuctionState,
     ) -> Result<SubmitBidParams, Error> {
-        let prev_tick_price = self.compute_prev_tick_price(input.max_price).await?;
+        let hint = match input.hint {
+            Some(hint) => hint,
+            None => match self
+                .walk_prev_tick_price(input.max_price, Some(self.max_hint_ticks))
+                .await?
+            {
+                Some(prev) => TickHint::PrevTick(prev),
+                None => {
+                    debug!(
+                        max_ticks = self.max_hint_ticks,
+                        "no hint within the tick limit; submitting unhinted"
+                    );
+                    TickHint::Unhinted
+                }
+            },
+        };
         let amount = input.amount;
 
         let mut params = SubmitBidParams {
             max_price: input.max_price,
             amount,
             owner: input.owner,
-            prev_tick_price,
+            from: self.owner,
+            nonce: None,
+            hint,
             hook_data: Bytes::new(),
             value: CurrencyAmount::new(U256::ZERO),
+            slippage: self
+                .max_clearing_price_drift
+                .map(|max_drift| SlippageGuard {
+                    clearing_price: state.checkpoint.clearing_price,
+                    max_drift,
+                }),
+            tags: Vec::new(),
         };
 
         if self.config.is_native_currency() {
@@ -253,87 +1035,260 @@ where
         Ok(params)
     }
 
-    pub async fn submit_bid(&mut self, params: SubmitBidParams) -> Result<SubmitBidResult, Error> {
+    /// Builds the `submitBid` transaction without sending it.
+    ///
+    /// `to`, `from`, `input` and `value` are populated, and the nonce if
+    /// `params` pins one. [`fill_tx`](Self::fill_tx) adds the rest.
+    pub fn build_submit_bid_tx(&self, params: &SubmitBidParams) -> TransactionRequest {
         let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
 
-        let call = cca
-            .submitBid_1(
-                params.max_price.as_u256(),
-                params.amount.as_u128(),
-                params.owner,
-                params.prev_tick_price.as_u256(),
-                params.hook_data,
-            )
-            .value(params.value.as_u256());
+        let tx = match params.hint {
+            TickHint::PrevTick(prev) => cca
+                .submitBid_1(
+                    params.max_price.as_u256(),
+                    params.amount.as_u128(),
+                    params.owner,
+                    prev.as_u256(),
+                    params.hook_data.clone(),
+                )
+                .value(params.value.as_u256())
+                .from(params.from)
+                .into_transaction_request(),
+            TickHint::Unhinted => cca
+                .submitBid_0(
+                    params.max_price.as_u256(),
+                    params.amount.as_u128(),
+                    params.owner,
+                    params.hook_data.clone(),
+                )
+                .value(params.value.as_u256())
+                .from(params.from)
+                .into_transaction_request(),
+        };
+        match params.nonce {
+            Some(nonce) => tx.nonce(nonce),
+            None => tx,
+        }
+    }
 
-        let pending = call.send().await.map_err(TransactionError::from)?;
-        let receipt = pending
-            .with_required_confirmations(3)
-            .get_receipt()
-            .await
-            .map_err(TransactionError::from)?;
+    /// Builds the `exitBid` transaction without sending it.
+    pub fn build_exit_tx(&self, params: &ExitBidParams) -> TransactionRequest {
+        let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
 
-        let receipt_body = receipt
-            .inner
-            .as_receipt()
-            .ok_or(TransactionError::MissingReceipt)?;
+        cca.exitBid(params.bid_id.as_u256())
+            .from(self.owner)
+            .into_transaction_request()
+    }
 
-        if !receipt_body.status() {
-            return Err(TransactionError::Reverted {
-                tx_hash: receipt.transaction_hash,
-            }
-            .into());
-        }
+    /// Builds the `exitPartiallyFilledBid` transaction without sending it.
+    pub fn build_exit_partially_filled_tx(
+        &self,
+        params: &ExitPartiallyFilledParams,
+    ) -> TransactionRequest {
+        let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
+        let outbid_block = params.outbid_block.map_or(0u64, |block| block.as_u64());
 
-        let bid_id = receipt_body
-            .logs()
-            .iter()
-            .find_map(|log| {
-                log.log_decode::<IContinuousClearingAuction::BidSubmitted>()
-                    .ok()
-            })
-            .map(|decoded| BidId::new(decoded.inner.data.id))
-            .ok_or(TransactionError::MissingBidSubmittedEvent)?;
+        cca.exitPartiallyFilledBid(
+            params.bid_id.as_u256(),
+            params.last_fully_filled_checkpoint_block.as_u64(),
+            outbid_block,
+        )
+        .from(self.owner)
+        .into_transaction_request()
+    }
 
-        self.tracked_bids.push(TrackedBid {
-            id: bid_id,
-            tx_hash: receipt.transaction_hash,
-        });
+    /// Builds the `claimTokens` (single bid) or `claimTokensBatch` transaction
+    /// without sending it.
+    pub fn build_claim_tx(&self, params: &ClaimParams) -> TransactionRequest {
+        let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
 
-        Ok(SubmitBidResult {
-            bid_id,
-            tx_hash: receipt.transaction_hash,
-        })
+        let call = if params.bid_ids.len() == 1 {
+            cca.claimTokens(params.bid_ids[0].as_u256())
+                .into_transaction_request()
+        } else {
+            let bid_ids: Vec<_> = params.bid_ids.iter().map(|b| b.as_u256()).collect();
+            cca.claimTokensBatch(params.owner, bid_ids)
+                .into_transaction_request()
+        };
+
+        let call = call.from(self.owner);
+        match params.nonce {
+            Some(nonce) => call.nonce(nonce),
+            None => call,
+        }
     }
 
-    pub async fn exit_bid(&mut self, params: ExitBidParams) -> Result<ExitResult, Error> {
-        let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
+    /// Gas the claim in `params` is estimated to use.
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction, bids = params.bid_ids.len()))]
+    pub async fn estimate_claim_gas(&self, params: &ClaimParams) -> Result<u64, Error> {
+        self.estimate_gas(self.build_claim_tx(params)).await
+    }
 
-        let pending = cca
-            .exitBid(params.bid_id.as_u256())
-            .send()
+    /// Gas `tx`, e.g. from one of the `build_*_tx` methods, is estimated to
+    /// use.
+    pub async fn estimate_gas(&self, tx: TransactionRequest) -> Result<u64, Error> {
+        let gas = metrics::timed("estimate_gas", self.provider.estimate_gas(tx))
             .await
-            .map_err(TransactionError::from)?;
+            .map_err(StateError::from)?;
+        Ok(gas)
+    }
 
-        let receipt = pending
-            .with_required_confirmations(3)
-            .get_receipt()
-            .await
-            .map_err(TransactionError::from)?;
+    /// Builds the `checkpoint` transaction without sending it.
+    pub fn build_checkpoint_tx(&self) -> TransactionRequest {
+        let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
+        cca.checkpoint().into_transaction_request().from(self.owner)
+    }
 
-        let receipt_body = receipt
-            .inner
-            .as_receipt()
-            .ok_or(TransactionError::MissingReceipt)?;
+    /// Completes `tx`, e.g. from one of the `build_*_tx` methods, for
+    /// whoever signs and broadcasts it: the pending nonce of its sender, the
+    /// chain id, a gas limit and EIP-1559 fees are read from the node where
+    /// not already set. A sender left out is the client's owner. Nothing is
+    /// sent.
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction))]
+    pub async fn fill_tx(&self, mut tx: TransactionRequest) -> Result<TransactionRequest, Error> {
+        let from = *tx.from.get_or_insert(self.owner);
+        let nonce = async {
+            match tx.nonce {
+                Some(nonce) => Ok(nonce),
+                None => self.fetch_nonce(from).await,
+            }
+        };
+        let chain_id = async {
+            match tx.chain_id {
+                Some(chain_id) => Ok(chain_id),
+                None => metrics::timed("get_chain_id", self.provider.get_chain_id())
+                    .await
+                    .map_err(|error| StateError::from(error).into()),
+            }
+        };
+        let gas = async {
+            match tx.gas {
+                Some(gas) => Ok(gas),
+                None => self.estimate_gas(tx.clone()).await,
+            }
+        };
+        let fees = async {
+            if tx.gas_price.is_some() || tx.max_fee_per_gas.is_some() {
+                return Ok(None);
+            }
+            metrics::timed("estimate_fees", self.provider.estimate_eip1559_fees())
+                .await
+                .map(Some)
+                .map_err(|error| StateError::from(error).into())
+        };
+        let (nonce, chain_id, gas, fees) = futures::try_join!(nonce, chain_id, gas, fees)?;
 
-        if !receipt_body.status() {
-            return Err(TransactionError::Reverted {
+        let mut tx = tx.nonce(nonce).gas_limit(gas).with_chain_id(chain_id);
+        if let Some(fees) = fees {
+            tx.max_fee_per_gas = Some(fees.max_fee_per_gas);
+            tx.max_priority_fee_per_gas = Some(fees.max_priority_fee_per_gas);
+        }
+        Ok(tx)
+    }
+
+    #[instrument(skip_all, fields(auction = %self.auction, bid_id = tracing::field::Empty))]
+    pub async fn submit_bid(&self, params: SubmitBidParams) -> Result<SubmitBidResult, Error> {
+        validation::validate_value(&params, &self.config)?;
+        if let Some(guard) = params.slippage {
+            self.check_slippage(params.max_price, guard).await?;
+        }
+        let tx = self.build_submit_bid_tx(&params);
+
+        let receipt = self.send_and_confirm(tx, "submit_bid", 3).await?;
+
+        let bid_id = match submitted_bid_id(self.auction, receipt.inner.logs()) {
+            Some(id) => BidId::new(id),
+            None => self.recover_bid_id(&params, &receipt).await?,
+        };
+        Span::current().record("bid_id", tracing::field::display(bid_id.as_u256()));
+
+        // A bid owned by someone other than the wallet that paid for it is
+        // theirs to settle.
+        let beneficiary = (params.owner != params.from).then_some(params.owner);
+        if beneficiary.is_none() {
+            self.track_bid(TrackedBid {
+                id: bid_id,
                 tx_hash: receipt.transaction_hash,
-            }
-            .into());
+                tags: params.tags.clone(),
+            });
         }
 
-        let exit_event = receipt_body
+        Ok(SubmitBidResult {
+            bid_id,
+            amount: params.amount,
+            beneficiary,
+            tx_hash: receipt.transaction_hash,
+            gas_used: receipt.gas_used,
+            effective_gas_price: receipt.effective_gas_price,
+            tags: params.tags,
+        })
+    }
+
+    /// Finds the bid a `submitBid` receipt without a `BidSubmitted` event
+    /// from the auction created, as when a proxy or forwarder in between emits its own events
+    /// instead: the ids handed out in the receipt's block, between
+    /// `nextBidId()` before and after it, are read back and matched on owner,
+    /// price, amount and start block. Without state for that block, bids are
+    /// walked back from the latest id until they start before it.
+    ///
+    /// Of identical bids from the same block, the latest not tracked yet is
+    /// taken.
+    async fn recover_bid_id(
+        &self,
+        params: &SubmitBidParams,
+        receipt: &TransactionReceipt,
+    ) -> Result<BidId, Error> {
+        let block = receipt
+            .block_number
+            .ok_or(TransactionError::MissingBidSubmittedEvent)?;
+        warn!(
+            tx_hash = %receipt.transaction_hash,
+            block,
+            "no BidSubmitted event in the receipt; looking the bid up instead"
+        );
+
+        let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
+        let next_bid_id_at = |block: u64| {
+            let call = cca.nextBidId().block(BlockId::number(block));
+            async move { call.call().await.map(|id| id.saturating_to::<u64>()) }
+        };
+        let (low, high) = match futures::try_join!(
+            next_bid_id_at(block.saturating_sub(1)),
+            next_bid_id_at(block)
+        ) {
+            Ok(range) => range,
+            Err(error) => {
+                debug!(%error, "can't read nextBidId() around the block; walking back from the latest");
+                (0, self.fetch_bid_count().await?)
+            }
+        };
+
+        let tracked = self.tracked_bids();
+        let found = find_submitted_bid(
+            low..high,
+            self.bid_page_size,
+            params,
+            block,
+            &tracked,
+            |ids| async move { self.fetch_bids(&ids).await },
+        )
+        .await?;
+        found.ok_or_else(|| {
+            TransactionError::SubmittedBidNotFound {
+                tx_hash: receipt.transaction_hash,
+            }
+            .into()
+        })
+    }
+
+    #[instrument(skip_all, fields(auction = %self.auction, bid_id = %params.bid_id.as_u256()))]
+    pub async fn exit_bid(&self, params: ExitBidParams) -> Result<ExitResult, Error> {
+        let tx = self.build_exit_tx(&params);
+
+        let receipt = self.send_and_confirm(tx, "exit", 3).await?;
+
+        let exit_event = receipt
+            .inner
             .logs()
             .iter()
             .find_map(|log| {
@@ -351,46 +1306,33 @@ where
             tokens_filled,
             currency_refunded,
             tx_hash: receipt.transaction_hash,
+            gas_used: receipt.gas_used,
+            effective_gas_price: receipt.effective_gas_price,
         })
     }
 
+    /// Sends `exitPartiallyFilledBid`. It is simulated first, and hints the
+    /// auction rejects, e.g. because a checkpoint landed since they were
+    /// computed, are computed again once before anything is sent.
+    #[instrument(skip_all, fields(auction = %self.auction, bid_id = %params.bid_id.as_u256()))]
     pub async fn exit_partially_filled(
-        &mut self,
+        &self,
         params: ExitPartiallyFilledParams,
     ) -> Result<ExitResult, Error> {
-        let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
-
-        let outbid_block = params.outbid_block.map_or(0u64, |block| block.as_u64());
-
-        let pending = cca
-            .exitPartiallyFilledBid(
-                params.bid_id.as_u256(),
-                params.last_fully_filled_checkpoint_block.as_u64(),
-                outbid_block,
-            )
-            .send()
-            .await
-            .map_err(TransactionError::from)?;
-
-        let receipt = pending
-            .with_required_confirmations(3)
-            .get_receipt()
-            .await
-            .map_err(TransactionError::from)?;
-
-        let receipt_body = receipt
-            .inner
-            .as_receipt()
-            .ok_or(TransactionError::MissingReceipt)?;
-
-        if !receipt_body.status() {
-            return Err(TransactionError::Reverted {
-                tx_hash: receipt.transaction_hash,
+        let mut tx = self.build_exit_partially_filled_tx(&params);
+        if let Some(reason) = self.hint_rejection(&tx).await? {
+            warn!(%reason, "auction rejects the exit hints; computing them again");
+            let params = self.prepare_exit_partially_filled(params.bid_id).await?;
+            tx = self.build_exit_partially_filled_tx(&params);
+            if let Some(reason) = self.hint_rejection(&tx).await? {
+                return Err(ValidationError::ExitHintsRejected(reason).into());
             }
-            .into());
         }
 
-        let exit_event = receipt_body
+        let receipt = self.send_and_confirm(tx, "exit", 3).await?;
+
+        let exit_event = receipt
+            .inner
             .logs()
             .iter()
             .find_map(|log| {
@@ -408,70 +1350,649 @@ where
             tokens_filled,
             currency_refunded,
             tx_hash: receipt.transaction_hash,
+            gas_used: receipt.gas_used,
+            effective_gas_price: receipt.effective_gas_price,
         })
     }
 
-    pub async fn claim(&mut self, params: ClaimParams) -> Result<ClaimResult, Error> {
+    #[instrument(skip_all, fields(auction = %self.auction, bids = params.bid_ids.len()))]
+    pub async fn claim(&self, params: ClaimParams) -> Result<ClaimResult, Error> {
+        let tx = self.build_claim_tx(&params);
+
+        let receipt = self.send_and_confirm(tx, "claim", 1).await?;
+
+        let mut found = false;
+        let mut total_tokens = TokenAmount::ZERO;
+
+        for log in receipt.inner.logs() {
+            if let Ok(decoded) = log.log_decode::<IContinuousClearingAuction::TokensClaimed>() {
+                let amount = TokenAmount::new(decoded.inner.data.tokensFilled);
+                total_tokens += amount;
+                found = true;
+            }
+        }
+
+        if !found {
+            return Err(TransactionError::MissingTokensClaimedEvent.into());
+        }
+
+        Ok(ClaimResult {
+            bid_ids: params.bid_ids,
+            total_tokens,
+            tx_hash: receipt.transaction_hash,
+            tx_hashes: vec![receipt.transaction_hash],
+            gas_used: receipt.gas_used,
+            effective_gas_price: receipt.effective_gas_price,
+            skipped: Vec::new(),
+            failed: Vec::new(),
+        })
+    }
+
+    pub fn build_transfer_tokens_tx(&self, to: Address, amount: TokenAmount) -> TransactionRequest {
+        let token = IERC20Minimal::new(self.config.token.as_address(), &self.provider);
+        token
+            .transfer(to, amount.as_u256())
+            .into_transaction_request()
+            .from(self.owner)
+    }
+
+    /// Transfers `amount` of the auction token from the owner to `to`.
+    #[instrument(skip_all, fields(auction = %self.auction, %to))]
+    pub async fn transfer_tokens(&self, to: Address, amount: TokenAmount) -> Result<B256, Error> {
+        let tx = self.build_transfer_tokens_tx(to, amount);
+        let receipt = self.send_and_confirm(tx, "transfer", 1).await?;
+        Ok(receipt.transaction_hash)
+    }
+
+    /// Checks the auction is checkpointed up to the current block, or its end
+    /// block once it is over. Exit hints are computed from the checkpoints
+    /// already stored, while the exit itself checkpoints first, so with a gap
+    /// the hints can point past a checkpoint the exit is about to insert.
+    /// With [gap filling](Self::with_checkpoint_gap_fill) on, this calls
+    /// `checkpoint()` to close the gap, and otherwise warns.
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction))]
+    pub async fn fill_checkpoint_gap(&self) -> Result<(), Error> {
         let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
+        let (last, head) = futures::try_join!(
+            async {
+                cca.lastCheckpointedBlock()
+                    .call()
+                    .await
+                    .map_err(|error| Error::from(StateError::from(error)))
+            },
+            async {
+                self.provider
+                    .get_block_number()
+                    .await
+                    .map_err(|error| Error::from(StateError::from(error)))
+            },
+        )?;
+        let due = head.min(self.config.end_block.as_u64());
+        if last >= due {
+            return Ok(());
+        }
+        if !self.fill_checkpoint_gaps {
+            warn!(
+                last,
+                due, "auction is not checkpointed up to date; exit hints may be rejected"
+            );
+            return Ok(());
+        }
+        info!(
+            last,
+            due, "checkpointing the auction before computing exit hints"
+        );
+        if let Err(error) = self.poke_checkpoint().await {
+            // Past the end block the auction may refuse `checkpoint()`; the
+            // exit's own simulation then catches stale hints.
+            warn!(%error, "could not checkpoint the auction; exit hints may be rejected");
+        }
+        Ok(())
+    }
 
-        let pending = if params.bid_ids.len() == 1 {
-            let bid_id = params.bid_ids[0].as_u256();
-            cca.claimTokens(bid_id)
-                .send()
-                .await
-                .map_err(TransactionError::from)?
-        } else {
-            let bid_ids: Vec<_> = params.bid_ids.iter().map(|b| b.as_u256()).collect();
-            cca.claimTokensBatch(params.owner, bid_ids)
-                .send()
-                .await
-                .map_err(TransactionError::from)?
-        };
+    /// The hint error the auction reverts `tx` with if it were sent now.
+    async fn hint_rejection(&self, tx: &TransactionRequest) -> Result<Option<String>, Error> {
+        const HINT_ERRORS: [&str; 2] = [
+            "InvalidLastFullyFilledCheckpointHint",
+            "InvalidOutbidBlockCheckpointHint",
+        ];
+        match self.provider.call(tx.clone()).await {
+            Ok(_) => Ok(None),
+            Err(error) => match error.as_error_resp().and_then(|resp| resp.as_revert_data()) {
+                Some(data) => {
+                    let reason = revert_reason(&data);
+                    Ok(HINT_ERRORS.contains(&reason.as_str()).then_some(reason))
+                }
+                None => Err(StateError::from(error).into()),
+            },
+        }
+    }
+
+    /// Calls `checkpoint()` so the auction advances to the current block, then
+    /// reads back the new latest checkpoint.
+    #[instrument(skip_all, fields(auction = %self.auction))]
+    pub async fn poke_checkpoint(&self) -> Result<CheckpointResult, Error> {
+        let tx = self.build_checkpoint_tx();
+
+        let receipt = self.send_and_confirm(tx, "checkpoint", 1).await?;
+        let checkpoint = self.fetch_checkpoint().await?;
+
+        Ok(CheckpointResult {
+            checkpoint,
+            tx_hash: receipt.transaction_hash,
+            gas_used: receipt.gas_used,
+            effective_gas_price: receipt.effective_gas_price,
+        })
+    }
+
+    /// Sends exits and claims as one Multicall3 `aggregate3` transaction
+    /// instead of one transaction each.
+    ///
+    /// The batch is simulated first and calls that would revert are reported
+    /// as [`BatchOutcome::Failed`] and left out; the rest are sent with
+    /// `allowFailure`, so one reverting on chain does not take the others down
+    /// with it. Each call's outcome is read back from its events. Calls run in
+    /// the order given, so exit a bid before claiming it.
+    ///
+    /// Multicall3 is the caller the auction sees, which is fine for exits and
+    /// claims: anyone may send them and the funds go to the bid's owner.
+    #[instrument(skip_all, fields(auction = %self.auction, calls = calls.len()))]
+    pub async fn settle_batch(&self, calls: Vec<BatchCall>) -> Result<BatchResult, Error> {
+        let encoded: Vec<IMulticall3::Call3> = calls
+            .iter()
+            .map(|call| IMulticall3::Call3 {
+                target: self.auction,
+                allowFailure: true,
+                callData: self.batch_call_data(call),
+            })
+            .collect();
 
-        let receipt = pending
-            .with_required_confirmations(1)
-            .get_receipt()
+        let simulation = self.aggregate3_tx(encoded.clone());
+        let returned = metrics::timed("simulate_batch", self.provider.call(simulation))
             .await
             .map_err(TransactionError::from)?;
+        let simulated = IMulticall3::aggregate3Call::abi_decode_returns(&returned)
+            .map_err(|error| TransactionError::from(contract::Error::from(error)))?;
+
+        let mut outcomes: Vec<Option<BatchOutcome>> = Vec::with_capacity(calls.len());
+        let mut sent = Vec::new();
+        for (call, result) in encoded.into_iter().zip(&simulated) {
+            if result.success {
+                outcomes.push(None);
+                sent.push(call);
+            } else {
+                outcomes.push(Some(BatchOutcome::Failed {
+                    reason: revert_reason(&result.returnData),
+                }));
+            }
+        }
+
+        if sent.is_empty() {
+            warn!("every call in the batch would revert; nothing sent");
+            return Ok(BatchResult {
+                outcomes: outcomes.into_iter().flatten().collect(),
+                tx_hash: None,
+                gas_used: 0,
+                effective_gas_price: 0,
+            });
+        }
+
+        let kind = if calls
+            .iter()
+            .any(|call| !matches!(call, BatchCall::Claim(_)))
+        {
+            "exit"
+        } else {
+            "claim"
+        };
+        let tx = self.aggregate3_tx(sent);
+        let receipt = self.send_and_confirm(tx, kind, 3).await?;
+
+        let mut exited = HashMap::new();
+        let mut claimed = HashMap::new();
+        for log in receipt.inner.logs() {
+            if log.address() != self.auction {
+                continue;
+            }
+            if let Ok(decoded) = log.log_decode::<IContinuousClearingAuction::BidExited>() {
+                let data = decoded.inner.data;
+                exited.insert(data.bidId, (data.tokensFilled, data.currencyRefunded));
+            } else if let Ok(decoded) =
+                log.log_decode::<IContinuousClearingAuction::TokensClaimed>()
+            {
+                let data = decoded.inner.data;
+                claimed.insert(data.bidId, data.tokensFilled);
+            }
+        }
+
+        let outcomes = calls
+            .iter()
+            .zip(outcomes)
+            .map(|(call, outcome)| {
+                outcome.unwrap_or_else(|| batch_outcome(call, &exited, &claimed))
+            })
+            .collect();
+
+        Ok(BatchResult {
+            outcomes,
+            tx_hash: Some(receipt.transaction_hash),
+            gas_used: receipt.gas_used,
+            effective_gas_price: receipt.effective_gas_price,
+        })
+    }
+
+    fn aggregate3_tx(&self, calls: Vec<IMulticall3::Call3>) -> TransactionRequest {
+        TransactionRequest::default()
+            .to(MULTICALL3_ADDRESS)
+            .from(self.owner)
+            .input(IMulticall3::aggregate3Call { calls }.abi_encode().into())
+    }
+
+    fn batch_call_data(&self, call: &BatchCall) -> Bytes {
+        let tx = match call {
+            BatchCall::Exit(params) => self.build_exit_tx(params),
+            BatchCall::ExitPartiallyFilled(params) => self.build_exit_partially_filled_tx(params),
+            BatchCall::Claim(params) => self.build_claim_tx(params),
+        };
+        tx.input.into_input().unwrap_or_default()
+    }
+
+    /// Re-reads the checkpoint and fails if the clearing price rose by more
+    /// than the guard allows or is no longer below `max_price`.
+    async fn check_slippage(&self, max_price: Price, guard: SlippageGuard) -> Result<(), Error> {
+        let current = self.fetch_checkpoint().await?.clearing_price;
+        let drift = current
+            .as_u256()
+            .saturating_sub(guard.clearing_price.as_u256());
+        if drift > guard.max_drift.as_u256() {
+            warn!(
+                prepared = %guard.clearing_price.as_u256(),
+                current = %current.as_u256(),
+                "clearing price drifted; not sending bid"
+            );
+            return Err(ValidationError::ClearingPriceDrift {
+                prepared: guard.clearing_price.as_u256(),
+                current: current.as_u256(),
+                max_drift: guard.max_drift.as_u256(),
+            }
+            .into());
+        }
+        if current >= max_price {
+            return Err(ValidationError::BidBelowClearingPrice.into());
+        }
+        Ok(())
+    }
+
+    /// Sends `tx` and waits for `confirmations`, failing if it reverted.
+    /// `kind` is the [`Intent::kind`](crate::Intent::kind) the transaction
+    /// carries out, which decides whether it goes through the private relay;
+    /// with a smart account everything goes through its bundler.
+    #[instrument(level = "debug", skip_all, fields(kind, tx_hash = tracing::field::Empty, gas_used = tracing::field::Empty))]
+    async fn send_and_confirm(
+        &self,
+        tx: TransactionRequest,
+        kind: &'static str,
+        confirmations: u64,
+    ) -> Result<TransactionReceipt, Error> {
+        if let Some(limit) = self.max_gas_per_tx {
+            let estimate = metrics::timed("estimate_gas", self.provider.estimate_gas(tx.clone()))
+                .await
+                .map_err(TransactionError::from)?;
+            if estimate > limit {
+                return Err(ValidationError::RiskLimitExceeded(RiskViolation::GasPerTx {
+                    estimate,
+                    limit,
+                })
+                .into());
+            }
+        }
+
+        if let Some(shadow) = &self.shadow {
+            let block = metrics::timed("get_block_number", self.provider.get_block_number())
+                .await
+                .map_err(TransactionError::from)?;
+            let from = tx.from.unwrap_or(self.owner);
+            shadow
+                .rehearse(&tx, from, kind, block, &self.config)
+                .await?;
+        }
+
+        #[cfg(feature = "account")]
+        let receipt = match &self.smart_account {
+            Some(account) => account.send(&self.provider, tx, confirmations).await?,
+            None => self.send_from_owner(tx, kind, confirmations).await?,
+        };
+        #[cfg(not(feature = "account"))]
+        let receipt = self.send_from_owner(tx, kind, confirmations).await?;
 
         let receipt_body = receipt
             .inner
             .as_receipt()
             .ok_or(TransactionError::MissingReceipt)?;
 
+        let span = Span::current();
+        span.record("tx_hash", tracing::field::display(receipt.transaction_hash));
+        span.record("gas_used", receipt.gas_used);
+
         if !receipt_body.status() {
+            warn!(tx_hash = %receipt.transaction_hash, "transaction reverted");
             return Err(TransactionError::Reverted {
                 tx_hash: receipt.transaction_hash,
             }
             .into());
         }
 
-        let mut found = false;
-        let mut total_tokens = TokenAmount::ZERO;
+        info!(
+            tx_hash = %receipt.transaction_hash,
+            gas_used = receipt.gas_used,
+            "transaction confirmed"
+        );
+        Ok(receipt)
+    }
 
-        for log in receipt_body.logs() {
-            if let Ok(decoded) = log.log_decode::<IContinuousClearingAuction::TokensClaimed>() {
-                let amount = TokenAmount::new(decoded.inner.data.tokensFilled);
-                total_tokens += amount;
-                found = true;
+    /// Sends `tx` as an ordinary transaction, through the private relay if it
+    /// routes `kind` and under the journal if there is one.
+    async fn send_from_owner(
+        &self,
+        tx: TransactionRequest,
+        kind: &'static str,
+        confirmations: u64,
+    ) -> Result<TransactionReceipt, Error> {
+        let relay = self
+            .private_relay
+            .as_ref()
+            .filter(|relay| relay.routes(kind));
+        match &self.journal {
+            Some(journal) => {
+                self.send_once(journal, relay, tx, kind, confirmations)
+                    .await
             }
+            None => self.send_via(relay, tx, None, confirmations).await,
         }
+    }
 
-        if !found {
-            return Err(TransactionError::MissingTokensClaimedEvent.into());
+    /// Sends `tx` under its [`SendKey`] in `journal`, pinning a nonce and
+    /// recording it first. If the key was sent before, its receipt is
+    /// returned when it landed, and otherwise its nonce is reused while
+    /// still free, so the earlier attempt and this one can't both land.
+    async fn send_once(
+        &self,
+        journal: &SendJournal,
+        relay: Option<&PrivateRelay>,
+        mut tx: TransactionRequest,
+        kind: &'static str,
+        confirmations: u64,
+    ) -> Result<TransactionReceipt, Error> {
+        let from = tx.from.unwrap_or(self.owner);
+        let key = journal.key(&tx, from);
+        let mut nonce = None;
+        if let Some(entry) = journal.get(key) {
+            if let Some(receipt) = self.find_landed(&entry).await? {
+                info!(
+                    tx_hash = %receipt.transaction_hash,
+                    kind,
+                    "already landed; not sending again"
+                );
+                journal.landed(key, receipt.transaction_hash);
+                return Ok(receipt);
+            }
+            let next = self
+                .provider
+                .get_transaction_count(from)
+                .await
+                .map_err(TransactionError::from)?;
+            if next <= entry.nonce {
+                nonce = Some(entry.nonce);
+            }
         }
+        let nonce = match nonce {
+            Some(nonce) => nonce,
+            None => self
+                .provider
+                .get_transaction_count(from)
+                .pending()
+                .await
+                .map_err(TransactionError::from)?,
+        };
+        let block = self
+            .provider
+            .get_block_number()
+            .await
+            .map_err(TransactionError::from)?;
+        tx.set_nonce(nonce);
+        journal.begin(key, kind, from, nonce, block);
 
-        Ok(ClaimResult {
-            bid_ids: params.bid_ids,
-            total_tokens,
-            tx_hash: receipt.transaction_hash,
-        })
+        let receipt = self.send_via(relay, tx, Some(key), confirmations).await?;
+        if receipt.status() {
+            journal.landed(key, receipt.transaction_hash);
+        }
+        Ok(receipt)
+    }
+
+    /// The successful receipt of a transaction journaled as `entry`: one of
+    /// its recorded hashes, or, if its nonce has been used since, the
+    /// transaction that used it when that is the same one. The scan covers
+    /// a crash after broadcasting but before the hash was recorded.
+    async fn find_landed(&self, entry: &JournalEntry) -> Result<Option<TransactionReceipt>, Error> {
+        for tx_hash in entry.landed.iter().chain(entry.tx_hashes.iter().rev()) {
+            let receipt = self
+                .provider
+                .get_transaction_receipt(*tx_hash)
+                .await
+                .map_err(TransactionError::from)?;
+            if let Some(receipt) = receipt {
+                return Ok(receipt.status().then_some(receipt));
+            }
+        }
+
+        let next = self
+            .provider
+            .get_transaction_count(entry.from)
+            .await
+            .map_err(TransactionError::from)?;
+        if next <= entry.nonce {
+            return Ok(None);
+        }
+        let head = self
+            .provider
+            .get_block_number()
+            .await
+            .map_err(TransactionError::from)?;
+        for number in entry.block..=head {
+            let Some(block) = self
+                .provider
+                .get_block_by_number(number.into())
+                .full()
+                .await
+                .map_err(TransactionError::from)?
+            else {
+                continue;
+            };
+            let Some(tx) = block
+                .transactions
+                .txns()
+                .find(|tx| tx.inner.signer() == entry.from && tx.inner.nonce() == entry.nonce)
+            else {
+                continue;
+            };
+            let digest = SendKey::digest(entry.from, tx.to(), tx.value(), tx.input());
+            if digest != entry.key.digest {
+                return Ok(None);
+            }
+            let receipt = self
+                .provider
+                .get_transaction_receipt(tx.tx_hash())
+                .await
+                .map_err(TransactionError::from)?;
+            return Ok(receipt.filter(|receipt| receipt.status()));
+        }
+        Ok(None)
+    }
+
+    /// Looks up every journaled transaction not yet known to have landed,
+    /// marking those that did and tracking the bids they submitted, e.g.
+    /// after a crash left them unconfirmed. Returns how many landed.
+    pub async fn reconcile_journal(&self) -> Result<usize, Error> {
+        let Some(journal) = &self.journal else {
+            return Ok(0);
+        };
+        let mut landed = 0;
+        for entry in journal.unsettled() {
+            let Some(receipt) = self.find_landed(&entry).await? else {
+                continue;
+            };
+            info!(
+                tx_hash = %receipt.transaction_hash,
+                kind = entry.kind,
+                "journaled transaction landed"
+            );
+            journal.landed(entry.key, receipt.transaction_hash);
+            for log in receipt.inner.logs() {
+                if log.address() == self.auction
+                    && let Ok(decoded) =
+                        log.log_decode::<IContinuousClearingAuction::BidSubmitted>()
+                    && decoded.inner.data.owner == self.owner
+                {
+                    self.track_bid(TrackedBid {
+                        id: BidId::new(decoded.inner.data.id),
+                        tx_hash: receipt.transaction_hash,
+                        tags: Vec::new(),
+                    });
+                }
+            }
+            landed += 1;
+        }
+        Ok(landed)
+    }
+
+    async fn send_via(
+        &self,
+        relay: Option<&PrivateRelay>,
+        tx: TransactionRequest,
+        key: Option<SendKey>,
+        confirmations: u64,
+    ) -> Result<TransactionReceipt, Error> {
+        match relay {
+            Some(relay) => self.send_private(relay, tx, key, confirmations).await,
+            None => self.send_public(tx, key, confirmations).await,
+        }
+    }
+
+    /// Records a broadcast `tx_hash` under `key` when journaling.
+    fn journal_sent(&self, key: Option<SendKey>, tx_hash: B256) {
+        if let (Some(journal), Some(key)) = (&self.journal, key) {
+            journal.sent(key, tx_hash);
+        }
+    }
+
+    async fn send_public(
+        &self,
+        tx: TransactionRequest,
+        key: Option<SendKey>,
+        confirmations: u64,
+    ) -> Result<TransactionReceipt, Error> {
+        let pending = metrics::timed("send_transaction", self.provider.send_transaction(tx))
+            .await
+            .map_err(TransactionError::from)?;
+        self.journal_sent(key, *pending.tx_hash());
+
+        let receipt = metrics::timed(
+            "wait_receipt",
+            pending
+                .with_required_confirmations(confirmations)
+                .get_receipt(),
+        )
+        .await
+        .map_err(TransactionError::from)?;
+        Ok(receipt)
+    }
+
+    /// Sends `tx` through the relay and watches this client's provider for
+    /// it. The nonce is pinned first, so if the relay has not included it
+    /// within its fallback window the public resend replaces it rather than
+    /// landing alongside it.
+    async fn send_private(
+        &self,
+        relay: &PrivateRelay,
+        mut tx: TransactionRequest,
+        key: Option<SendKey>,
+        confirmations: u64,
+    ) -> Result<TransactionReceipt, Error> {
+        if tx.nonce.is_none() {
+            let nonce = self
+                .provider
+                .get_transaction_count(tx.from.unwrap_or(self.owner))
+                .await
+                .map_err(TransactionError::from)?;
+            tx.set_nonce(nonce);
+        }
+
+        let mut blocks = self
+            .provider
+            .watch_blocks()
+            .await
+            .map_err(TransactionError::from)?
+            .into_stream()
+            .flat_map(futures::stream::iter);
+
+        let pending = metrics::timed(
+            "send_private_transaction",
+            relay.provider().send_transaction(tx.clone()),
+        )
+        .await
+        .map_err(TransactionError::from)?;
+        let tx_hash = *pending.tx_hash();
+        self.journal_sent(key, tx_hash);
+        debug!(%tx_hash, "sent through private relay");
+
+        let mut waited = 0;
+        let mut included: Option<TransactionReceipt> = None;
+        while blocks.next().await.is_some() {
+            if included.is_none() {
+                included = self
+                    .provider
+                    .get_transaction_receipt(tx_hash)
+                    .await
+                    .map_err(TransactionError::from)?;
+            }
+            match included.take() {
+                Some(receipt) => {
+                    let mined = receipt.block_number.unwrap_or_default();
+                    let head = self
+                        .provider
+                        .get_block_number()
+                        .await
+                        .map_err(TransactionError::from)?;
+                    if head + 1 >= mined + confirmations {
+                        return Ok(receipt);
+                    }
+                    included = Some(receipt);
+                }
+                None => {
+                    waited += 1;
+                    if waited > relay.fallback_after() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        warn!(
+            %tx_hash,
+            blocks = waited,
+            "private relay did not include transaction; sending publicly"
+        );
+        self.send_public(tx, key, confirmations).await
     }
 
+    /// Exit hints for `bid_id`, after checkpointing the auction if its
+    /// checkpoints stop short of the current block; see
+    /// [`fill_checkpoint_gap`](Self::fill_checkpoint_gap).
     pub async fn prepare_exit_partially_filled(
         &self,
         bid_id: BidId,
     ) -> Result<ExitPartiallyFilledParams, Error> {
+        self.fill_checkpoint_gap().await?;
         let bids = self.fetch_bids(&[bid_id]).await?;
         let bid = bids.first().ok_or(StateError::BidNotFound)?;
 
@@ -484,7 +2005,123 @@ where
         })
     }
 
+    /// Estimates what `exitBid` and `exitPartiallyFilledBid` would each
+    /// return for `bid_id` now, and whether the auction would accept them,
+    /// from the checkpoint history alone.
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction, bid_id = %bid_id.as_u256()))]
+    pub async fn preview_exit(&self, bid_id: BidId) -> Result<ExitPreview, Error> {
+        let bid_ids = [bid_id];
+        let (bids, checkpoint, graduation, tokens_received, block) = futures::try_join!(
+            self.fetch_bids(&bid_ids),
+            self.fetch_checkpoint(),
+            self.fetch_graduation(),
+            self.fetch_token_balance(),
+            async {
+                self.provider
+                    .get_block_number()
+                    .await
+                    .map_err(|error| Error::from(StateError::from(error)))
+            },
+        )?;
+        let bid = bids.first().ok_or(StateError::BidNotFound)?;
+        let state = AuctionState::new(
+            BlockNumber::new(block),
+            checkpoint,
+            graduation,
+            tokens_received,
+            &self.config,
+        );
+
+        let (start, hints) =
+            futures::try_join!(self.fetch_checkpoint_at(bid.start_block), async {
+                self.compute_exit_hints(bid).await
+            })?;
+        let last_fully_filled = self
+            .fetch_checkpoint_at(hints.last_fully_filled_checkpoint_block)
+            .await?;
+
+        let estimate = |upper: &Checkpoint| {
+            if bid.exited_block.is_some() {
+                FillEstimate::exited(bid)
+            } else if matches!(graduation, GraduationStatus::NotGraduated)
+                && state.current_block >= self.config.end_block
+            {
+                FillEstimate::full_refund(bid)
+            } else {
+                FillEstimate::between(bid, &start, upper)
+            }
+        };
+        Ok(ExitPreview {
+            bid_id,
+            exit_bid: ExitEstimate {
+                variant: ExitVariant::ExitBid,
+                fill: estimate(&checkpoint),
+                rejected: validation::validate_exit_bid(bid, &state, &self.config).err(),
+            },
+            exit_partially_filled: ExitEstimate {
+                variant: ExitVariant::ExitPartiallyFilled,
+                fill: estimate(&last_fully_filled),
+                rejected: validation::validate_exit_partially_filled(bid, &state, &self.config)
+                    .err(),
+            },
+            hints,
+        })
+    }
+
+    /// Config, state, up to `tick_depth` ticks above the clearing price and
+    /// `bid_ids`, read together for [`AuctionSnapshot::diff`]. The reads are
+    /// concurrent rather than pinned to one block, so a snapshot taken while
+    /// blocks land can straddle two.
+    #[instrument(level = "debug", skip_all, fields(auction = %self.auction, bids = bid_ids.len()))]
+    pub async fn fetch_snapshot(
+        &self,
+        owner: Option<Address>,
+        bid_ids: &[BidId],
+        tick_depth: usize,
+    ) -> Result<AuctionSnapshot, Error> {
+        let (lens_state, tokens_received, tick_book, bids, block) = futures::try_join!(
+            self.fetch_state_via_lens(),
+            self.fetch_token_balance(),
+            self.fetch_tick_book(tick_depth),
+            self.fetch_bids(bid_ids),
+            async {
+                self.provider
+                    .get_block_number()
+                    .await
+                    .map_err(|error| Error::from(StateError::from(error)))
+            },
+        )?;
+        let state = AuctionState::new(
+            BlockNumber::new(block),
+            lens_state.checkpoint,
+            lens_state.graduation,
+            tokens_received,
+            &self.config,
+        )
+        .with_metrics(lens_state.metrics);
+
+        Ok(AuctionSnapshot {
+            config: (*self.config).clone(),
+            state,
+            tick_book,
+            owner,
+            bids,
+        })
+    }
+
     pub async fn compute_prev_tick_price(&self, max_price: Price) -> Result<Price, Error> {
+        let prev = self.walk_prev_tick_price(max_price, None).await?;
+        // Only a bounded walk gives up.
+        Ok(prev.unwrap_or(self.config.floor_price))
+    }
+
+    /// Follows the tick list towards `max_price`, reading at most `max_ticks`
+    /// ticks; `None` if the limit ran out first.
+    async fn walk_prev_tick_price(
+        &self,
+        max_price: Price,
+        max_ticks: Option<usize>,
+    ) -> Result<Option<Price>, Error> {
         let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
         let mut prev = self.config.floor_price;
 
@@ -498,7 +2135,13 @@ where
             prev = next_active;
         }
 
+        let mut walked = 0;
         loop {
+            if max_ticks.is_some_and(|max| walked >= max) {
+                break Ok(None);
+            }
+            walked += 1;
+
             let tick_return = cca
                 .ticks(prev.as_u256())
                 .call()
@@ -507,69 +2150,288 @@ where
             let next_price = Price::new(tick_return.next);
 
             if next_price >= max_price {
-                break Ok(prev);
+                break Ok(Some(prev));
             }
 
             if next_price == prev {
-                break Ok(prev);
+                break Ok(Some(prev));
             }
 
             prev = next_price;
         }
     }
 
+    /// Exit hints for `bid` from the auction's checkpoints; see
+    /// [`compute_exit_hints`](crate::compute_exit_hints).
     pub async fn compute_exit_hints(&self, bid: &Bid) -> Result<ExitHints, Error> {
-        let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
-        let tail = cca
-            .MAX_BLOCK_NUMBER()
-            .call()
-            .await
-            .map_err(StateError::from)?;
+        let history = ChainCheckpoints::new(self.auction, &self.provider);
+        exit_hints::compute_exit_hints(&history, bid).await
+    }
+}
 
-        let mut last_fully_filled = bid.start_block;
-        let mut current_cp = cca
-            .checkpoints(bid.start_block.as_u64())
-            .call()
-            .await
-            .map_err(StateError::from)?;
+/// Reads the outcome of a call that went out in a batch from the events the
+/// batch emitted.
+fn batch_outcome(
+    call: &BatchCall,
+    exited: &HashMap<U256, (U256, U256)>,
+    claimed: &HashMap<U256, U256>,
+) -> BatchOutcome {
+    let exit = |bid_id: BidId| match exited.get(&bid_id.as_u256()) {
+        Some(&(tokens_filled, currency_refunded)) => BatchOutcome::Exited {
+            bid_id,
+            tokens_filled: TokenAmount::new(tokens_filled),
+            currency_refunded: CurrencyAmount::new(currency_refunded),
+        },
+        None => BatchOutcome::Failed {
+            reason: "reverted in batch".to_string(),
+        },
+    };
+
+    match call {
+        BatchCall::Exit(params) => exit(params.bid_id),
+        BatchCall::ExitPartiallyFilled(params) => exit(params.bid_id),
+        BatchCall::Claim(params) => {
+            let mut bid_ids = Vec::new();
+            let mut total_tokens = TokenAmount::ZERO;
+            for bid_id in &params.bid_ids {
+                if let Some(&tokens) = claimed.get(&bid_id.as_u256()) {
+                    bid_ids.push(*bid_id);
+                    total_tokens += TokenAmount::new(tokens);
+                }
+            }
+            if bid_ids.is_empty() {
+                BatchOutcome::Failed {
+                    reason: "reverted in batch".to_string(),
+                }
+            } else {
+                BatchOutcome::Claimed {
+                    bid_ids,
+                    total_tokens,
+                }
+            }
+        }
+    }
+}
 
-        while current_cp.next != tail {
-            let next_block = BlockNumber::new(current_cp.next);
-            let next_cp = cca
-                .checkpoints(next_block.as_u64())
-                .call()
-                .await
-                .map_err(StateError::from)?;
+/// Names the auction error in `data`, or shows the raw revert data.
+fn revert_reason(data: &Bytes) -> String {
+    data.get(..4)
+        .and_then(|selector| selector.try_into().ok())
+        .and_then(IContinuousClearingAuctionErrors::name_by_selector)
+        .map_or_else(|| format!("reverted with {data}"), str::to_string)
+}
 
-            if next_cp.clearingPrice >= bid.max_price.as_u256() {
-                break;
-            }
+/// Walks the bids with ids in `ids` back from the latest, `page_size` at a
+/// time, for one `params` created in `block` that is not among `tracked`.
+/// Stops at the first page holding a bid from before `block`, as ids are
+/// handed out in block order.
+/// The id of the first `BidSubmitted` event among `logs` emitted by
+/// `auction`; a proxy, or another auction called in the same transaction,
+/// can emit one too.
+fn submitted_bid_id(auction: Address, logs: &[Log]) -> Option<U256> {
+    logs.iter()
+        .filter(|log| log.address() == auction)
+        .find_map(|log| {
+            log.log_decode::<IContinuousClearingAuction::BidSubmitted>()
+                .ok()
+        })
+        .map(|decoded| decoded.inner.data.id)
+}
 
-            last_fully_filled = next_block;
-            current_cp = next_cp;
+async fn find_submitted_bid<F, Fut>(
+    ids: Range<u64>,
+    page_size: usize,
+    params: &SubmitBidParams,
+    block: u64,
+    tracked: &[TrackedBid],
+    mut fetch_bids: F,
+) -> Result<Option<BidId>, Error>
+where
+    F: FnMut(Vec<BidId>) -> Fut,
+    Fut: Future<Output = Result<Vec<Bid>, Error>>,
+{
+    let mut end = ids.end;
+    while end > ids.start {
+        let start = end.saturating_sub(page_size as u64).max(ids.start);
+        let page = (start..end).map(|id| BidId::new(U256::from(id))).collect();
+        let bids = fetch_bids(page).await?;
+
+        let found = bids.iter().rev().find(|bid| {
+            bid.owner == params.owner
+                && bid.max_price == params.max_price
+                && bid.amount == params.amount
+                && bid.start_block.as_u64() == block
+                && !tracked
+                    .iter()
+                    .any(|seen| seen.id.as_u256() == bid.id.as_u256())
+        });
+        if let Some(bid) = found {
+            return Ok(Some(bid.id));
         }
+        if bids.iter().any(|bid| bid.start_block.as_u64() < block) {
+            break;
+        }
+        end = start;
+    }
+    Ok(None)
+}
 
-        let mut outbid_block = None;
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
 
-        while current_cp.next != tail {
-            let next_block = BlockNumber::new(current_cp.next);
-            let next_cp = cca
-                .checkpoints(next_block.as_u64())
-                .call()
-                .await
-                .map_err(StateError::from)?;
+    use alloy::primitives::aliases::U24;
 
-            if next_cp.clearingPrice > bid.max_price.as_u256() {
-                outbid_block = Some(next_block);
-                break;
-            }
+    use super::*;
+    use crate::types::{
+        config::fixture::{amount, price},
+        primitives::Mps,
+    };
+
+    const OWNER: Address = Address::repeat_byte(1);
 
-            current_cp = next_cp;
+    fn params() -> SubmitBidParams {
+        SubmitBidParams {
+            max_price: price(20),
+            amount: amount(100),
+            owner: OWNER,
+            from: OWNER,
+            nonce: None,
+            hint: TickHint::Unhinted,
+            hook_data: Bytes::new(),
+            value: CurrencyAmount::ZERO,
+            slippage: None,
+            tags: Vec::new(),
         }
+    }
 
-        Ok(ExitHints {
-            last_fully_filled_checkpoint_block: last_fully_filled,
-            outbid_block,
+    /// Bid `id` made in `block` that matches [`params`].
+    fn bid(id: u64, block: u64) -> Bid {
+        Bid {
+            id: BidId::new(U256::from(id)),
+            owner: OWNER,
+            max_price: price(20),
+            amount: amount(100),
+            start_block: BlockNumber::new(block),
+            start_cumulative_mps: Mps::new(U24::ZERO),
+            exited_block: None,
+            tokens_filled: TokenAmount::ZERO,
+        }
+    }
+
+    fn tracked(id: u64) -> TrackedBid {
+        TrackedBid {
+            id: BidId::new(U256::from(id)),
+            tx_hash: B256::ZERO,
+            tags: Vec::new(),
+        }
+    }
+
+    /// Finds the bid among `bids`, whose ids are their indices, recording
+    /// the pages read.
+    async fn find(
+        bids: &[Bid],
+        ids: Range<u64>,
+        block: u64,
+        tracked: &[TrackedBid],
+    ) -> (Option<u64>, Vec<Range<u64>>) {
+        let pages = Mutex::new(Vec::new());
+        let found = find_submitted_bid(ids, 2, &params(), block, tracked, |page| {
+            let first = page[0].as_u256().to::<u64>();
+            pages.lock().unwrap().push(first..first + page.len() as u64);
+            let bids = page
+                .iter()
+                .map(|id| bids[id.as_u256().to::<usize>()])
+                .collect();
+            async move { Ok(bids) }
         })
+        .await
+        .unwrap();
+        (
+            found.map(|id| id.as_u256().to::<u64>()),
+            pages.into_inner().unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn matches_owner_price_amount_and_start_block() {
+        let bids = [
+            bid(0, 9),
+            bid(1, 10),
+            Bid {
+                owner: Address::repeat_byte(2),
+                ..bid(2, 10)
+            },
+            Bid {
+                max_price: price(30),
+                ..bid(3, 10)
+            },
+            Bid {
+                amount: amount(50),
+                ..bid(4, 10)
+            },
+            bid(5, 11),
+        ];
+        assert_eq!(find(&bids, 1..5, 10, &[]).await.0, Some(1));
+        assert_eq!(find(&bids, 2..5, 10, &[]).await.0, None);
+    }
+
+    #[tokio::test]
+    async fn of_identical_bids_takes_the_latest_untracked() {
+        let bids = [bid(0, 9), bid(1, 10), bid(2, 10)];
+        assert_eq!(find(&bids, 1..3, 10, &[]).await.0, Some(2));
+        assert_eq!(find(&bids, 1..3, 10, &[tracked(2)]).await.0, Some(1));
+        assert_eq!(
+            find(&bids, 1..3, 10, &[tracked(1), tracked(2)]).await.0,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn walks_back_from_the_latest_until_an_earlier_block() {
+        // Without the ids handed out in block 10, every bid is a candidate.
+        let bids = [
+            bid(0, 8),
+            bid(1, 9),
+            bid(2, 10),
+            bid(3, 11),
+            bid(4, 11),
+            bid(5, 12),
+        ];
+        assert_eq!(
+            find(&bids, 0..6, 10, &[]).await,
+            (Some(2), vec![4..6, 2..4])
+        );
+        // Nothing matches: the walk stops at the page reaching block 9.
+        assert_eq!(
+            find(&bids, 0..6, 10, &[tracked(2)]).await,
+            (None, vec![4..6, 2..4, 0..2])
+        );
+        let bids = [bid(0, 8), bid(1, 9), bid(2, 9), bid(3, 11), bid(4, 12)];
+        assert_eq!(find(&bids, 0..5, 10, &[]).await, (None, vec![3..5, 1..3]));
+    }
+
+    #[test]
+    fn bid_ids_come_only_from_the_auctions_own_events() {
+        let auction = Address::repeat_byte(0xaa);
+        let submitted = |address: Address, id: u64| Log {
+            inner: alloy::primitives::Log {
+                address,
+                data: IContinuousClearingAuction::BidSubmitted {
+                    id: U256::from(id),
+                    owner: OWNER,
+                    price: U256::from(20),
+                    amount: 100,
+                }
+                .encode_log_data(),
+            },
+            ..Log::default()
+        };
+        let logs = [
+            submitted(Address::repeat_byte(0xbb), 1),
+            submitted(auction, 2),
+        ];
+        assert_eq!(submitted_bid_id(auction, &logs), Some(U256::from(2)));
+        assert_eq!(submitted_bid_id(auction, &logs[..1]), None);
     }
 }
@@ -0,0 +1,111 @@
+use alloy::{
+    primitives::{Address, B256},
+    providers::Provider,
+};
+
+use crate::{
+    client::AuctionClient,
+    error::{Error, ValidationError},
+    types::primitives::{TokenAddr, TokenAmount},
+};
+
+/// Moves claimed tokens off the hot wallet, but only to pre-approved
+/// addresses.
+#[derive(Clone, Debug, Default)]
+pub struct ColdStorage {
+    allowlist: Vec<Address>,
+}
+
+/// A transfer that passed the allowlist check but has not been sent.
+#[derive(Clone, Debug)]
+pub struct TransferPlan {
+    pub token: TokenAddr,
+    pub from: Address,
+    pub to: Address,
+    pub amount: TokenAmount,
+}
+
+#[derive(Clone, Debug)]
+pub struct TransferResult {
+    pub plan: TransferPlan,
+    pub tx_hash: B256,
+}
+
+impl ColdStorage {
+    pub fn new(allowlist: Vec<Address>) -> Self {
+        Self { allowlist }
+    }
+
+    pub fn allowlist(&self) -> &[Address] {
+        &self.allowlist
+    }
+
+    /// Fails unless `destination` is on the allowlist. An empty allowlist
+    /// allows nothing.
+    pub fn check(&self, destination: Address) -> Result<(), ValidationError> {
+        if self.allowlist.contains(&destination) {
+            Ok(())
+        } else {
+            Err(ValidationError::DestinationNotAllowed(destination))
+        }
+    }
+
+    /// Plans moving up to `amount` of the auction token from the client's
+    /// owner to `destination`, capped at what the owner actually holds.
+    pub async fn plan<P>(
+        &self,
+        client: &AuctionClient<P>,
+        destination: Address,
+        amount: TokenAmount,
+    ) -> Result<TransferPlan, Error>
+    where
+        P: Provider + Clone,
+    {
+        self.check(destination)?;
+
+        let balance = client.fetch_token_balance_of(client.owner()).await?;
+        let amount = if amount > balance { balance } else { amount };
+        if amount.is_zero() {
+            return Err(ValidationError::NothingToSweep.into());
+        }
+
+        Ok(TransferPlan {
+            token: client.config().token,
+            from: client.owner(),
+            to: destination,
+            amount,
+        })
+    }
+
+    /// Sends a planned transfer, re-checking the destination.
+    pub async fn execute<P>(
+        &self,
+        client: &AuctionClient<P>,
+        plan: TransferPlan,
+    ) -> Result<TransferResult, Error>
+    where
+        P: Provider + Clone,
+    {
+        self.check(plan.to)?;
+        let tx_hash = client.transfer_tokens(plan.to, plan.amount).await?;
+        Ok(TransferResult { plan, tx_hash })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_allowlisted_destinations_pass() {
+        let vault = Address::repeat_byte(0xc0);
+        let cold = ColdStorage::new(vec![vault]);
+
+        assert!(cold.check(vault).is_ok());
+        assert!(matches!(
+            cold.check(Address::repeat_byte(0xee)),
+            Err(ValidationError::DestinationNotAllowed(_))
+        ));
+        assert!(ColdStorage::default().check(vault).is_err());
+    }
+}
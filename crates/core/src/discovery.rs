@@ -0,0 +1,166 @@
+use alloy::{
+    primitives::{Address, B256, U256},
+    providers::Provider,
+    rpc::types::Filter,
+    sol_types::SolEvent,
+};
+use flux_abi::{IContinuousClearingAuction, IContinuousClearingAuctionFactory};
+use tracing::{Instrument, debug_span, warn};
+
+use crate::{
+    error::{Error, StateError},
+    types::primitives::{BidId, BlockNumber},
+};
+
+/// Finds bids and auctions that were created outside of this process.
+#[derive(Clone)]
+pub struct BidDiscovery<P>
+where
+    P: Provider + Clone,
+{
+    provider: P,
+    from_block: BlockNumber,
+    chunk_size: usize,
+}
+
+impl<P> BidDiscovery<P>
+where
+    P: Provider + Clone,
+{
+    pub const DEFAULT_CHUNK_SIZE: usize = 100;
+
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider,
+            from_block: BlockNumber::new(0),
+            chunk_size: Self::DEFAULT_CHUNK_SIZE,
+        }
+    }
+
+    /// Start log scans at `block` instead of genesis.
+    pub fn from_block(mut self, block: BlockNumber) -> Self {
+        self.from_block = block;
+        self
+    }
+
+    /// Read this many bids per multicall when walking bid storage instead of
+    /// [`DEFAULT_CHUNK_SIZE`](Self::DEFAULT_CHUNK_SIZE). At least one.
+    pub fn with_chunk_size(mut self, size: usize) -> Self {
+        self.chunk_size = size.max(1);
+        self
+    }
+
+    /// Returns the ids of every bid submitted to `auction` on behalf of `owner`,
+    /// in submission order.
+    ///
+    /// Scans `BidSubmitted` logs, falling back to walking bid storage if the
+    /// endpoint rejects the log query.
+    pub async fn bids_by_owner(
+        &self,
+        auction: Address,
+        owner: Address,
+    ) -> Result<Vec<BidId>, Error> {
+        match self.bids_by_owner_from_logs(auction, owner).await {
+            Ok(bid_ids) => Ok(bid_ids),
+            Err(error) => {
+                warn!(%auction, %error, "log query failed, walking bid storage instead");
+                self.bids_by_owner_from_storage(auction, owner).await
+            }
+        }
+    }
+
+    /// Finds `owner`'s bids by scanning `BidSubmitted` logs.
+    pub async fn bids_by_owner_from_logs(
+        &self,
+        auction: Address,
+        owner: Address,
+    ) -> Result<Vec<BidId>, Error> {
+        let filter = Filter::new()
+            .address(auction)
+            .event_signature(IContinuousClearingAuction::BidSubmitted::SIGNATURE_HASH)
+            .topic2(B256::left_padding_from(owner.as_slice()))
+            .from_block(self.from_block.as_u64());
+
+        let logs = self
+            .provider
+            .get_logs(&filter)
+            .await
+            .map_err(StateError::from)?;
+
+        let bid_ids = logs
+            .iter()
+            .filter_map(|log| {
+                log.log_decode::<IContinuousClearingAuction::BidSubmitted>()
+                    .ok()
+            })
+            .map(|decoded| BidId::new(decoded.inner.data.id))
+            .collect();
+
+        Ok(bid_ids)
+    }
+
+    /// Finds `owner`'s bids by reading every bid from `0..nextBidId()` in
+    /// chunked multicalls, for endpoints without `eth_getLogs` access.
+    pub async fn bids_by_owner_from_storage(
+        &self,
+        auction: Address,
+        owner: Address,
+    ) -> Result<Vec<BidId>, Error> {
+        let cca = IContinuousClearingAuction::new(auction, &self.provider);
+        let next_bid_id = cca.nextBidId().call().await.map_err(StateError::from)?;
+        let next_bid_id = next_bid_id.saturating_to::<u64>();
+
+        let mut bid_ids = Vec::new();
+        let mut start = 0u64;
+
+        while start < next_bid_id {
+            let end = next_bid_id.min(start + self.chunk_size as u64);
+
+            let mut multicall = self.provider.multicall().dynamic();
+            for id in start..end {
+                multicall = multicall.add_dynamic(cca.bids(U256::from(id)));
+            }
+            let bids = multicall
+                .aggregate()
+                .instrument(debug_span!("bids_chunk", %auction, start, end))
+                .await
+                .map_err(StateError::from)?;
+
+            bid_ids.extend(
+                (start..end)
+                    .zip(bids)
+                    .filter(|(_, bid)| bid.owner == owner)
+                    .map(|(id, _)| BidId::new(U256::from(id))),
+            );
+
+            start = end;
+        }
+
+        Ok(bid_ids)
+    }
+
+    /// Returns every auction created by `factory`, by scanning `AuctionCreated` logs.
+    pub async fn auctions_from_factory(&self, factory: Address) -> Result<Vec<Address>, Error> {
+        let filter = Filter::new()
+            .address(factory)
+            .event_signature(IContinuousClearingAuctionFactory::AuctionCreated::SIGNATURE_HASH)
+            .from_block(self.from_block.as_u64());
+
+        let logs = self
+            .provider
+            .get_logs(&filter)
+            .await
+            .map_err(StateError::from)?;
+
+        let auctions = logs
+            .iter()
+            .filter_map(|log| {
+                log.log_decode::<IContinuousClearingAuctionFactory::AuctionCreated>()
+                    .ok()
+            })
+            .map(|decoded| decoded.inner.data.auction)
+            .collect();
+
+        Ok(auctions)
+    }
+}
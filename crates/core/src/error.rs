@@ -1,11 +1,13 @@
 use alloy::{
     contract,
-    primitives::B256,
+    primitives::{Address, B256, SignatureError, U256},
     providers::{MulticallError, PendingTransactionError},
     transports::TransportError,
 };
 use thiserror::Error;
 
+use crate::types::risk::RiskViolation;
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error(transparent)]
@@ -25,6 +27,18 @@ pub enum Error {
 
     #[error(transparent)]
     BlockStream(#[from] BlockStreamError),
+
+    #[error(transparent)]
+    EventStream(#[from] EventStreamError),
+
+    #[error(transparent)]
+    Analytics(#[from] AnalyticsError),
+
+    #[error(transparent)]
+    Parameters(#[from] ParameterError),
+
+    #[error(transparent)]
+    Shadow(#[from] ShadowError),
 }
 
 #[derive(Debug, Error)]
@@ -37,6 +51,15 @@ pub enum ConfigError {
 
     #[error("multicall failed: {0}")]
     Multicall(#[from] MulticallError),
+
+    #[error("{address} is not a ContinuousClearingAuction: {reason}")]
+    WrongContract { address: Address, reason: String },
+
+    #[error("provider is connected to chain {actual}, expected chain {expected}")]
+    WrongChain { expected: u64, actual: u64 },
+
+    #[error("auction steps data is malformed: {0}")]
+    MalformedSteps(&'static str),
 }
 
 #[derive(Debug, Error)]
@@ -68,6 +91,15 @@ pub enum ValidationError {
     #[error("bid price must be above current clearing price")]
     BidBelowClearingPrice,
 
+    #[error(
+        "clearing price moved from {prepared} to {current} since the bid was prepared, more than the allowed {max_drift}"
+    )]
+    ClearingPriceDrift {
+        prepared: U256,
+        current: U256,
+        max_drift: U256,
+    },
+
     #[error("auction is sold out")]
     AuctionSoldOut,
 
@@ -103,6 +135,33 @@ pub enum ValidationError {
 
     #[error("auction not graduated, use exitBid for full refund")]
     UseExitBidForRefund,
+
+    #[error("{0} is not on the cold storage allowlist")]
+    DestinationNotAllowed(Address),
+
+    #[error("no tokens to sweep")]
+    NothingToSweep,
+
+    #[error("risk limit exceeded: {0}")]
+    RiskLimitExceeded(RiskViolation),
+
+    #[error("{0} is not in the wallet pool")]
+    UnknownWallet(Address),
+
+    #[error("no pooled wallet has budget left for a bid of {amount}")]
+    WalletBudgetExhausted { amount: U256 },
+
+    #[error("insufficient balance: need {needed}, have {available}")]
+    InsufficientBalance { needed: U256, available: U256 },
+
+    #[error("bid sends {value} wei with it, the auction expects {expected}")]
+    ValueMismatch { value: U256, expected: U256 },
+
+    #[error("auction is paid in native currency, which takes no approve or permit")]
+    NativeCurrencyApproval,
+
+    #[error("auction rejects the exit hints even after computing them again: {0}")]
+    ExitHintsRejected(String),
 }
 
 #[derive(Debug, Error)]
@@ -140,6 +199,9 @@ pub enum TransactionError {
     #[error("transaction failed: {0}")]
     Contract(#[from] contract::Error),
 
+    #[error("failed to send transaction: {0}")]
+    Transport(#[from] TransportError),
+
     #[error("pending transaction error: {0}")]
     Pending(#[from] PendingTransactionError),
 
@@ -149,6 +211,11 @@ pub enum TransactionError {
     #[error("BidSubmitted event not found in receipt logs")]
     MissingBidSubmittedEvent,
 
+    #[error(
+        "transaction {tx_hash} has no BidSubmitted event, and no matching bid was found in the auction's storage"
+    )]
+    SubmittedBidNotFound { tx_hash: B256 },
+
     #[error("BidExited event not found in receipt logs")]
     MissingBidExitedEvent,
 
@@ -157,6 +224,55 @@ pub enum TransactionError {
 
     #[error("transaction reverted: {tx_hash:?}")]
     Reverted { tx_hash: B256 },
+
+    #[error("failed to sign permit: {0}")]
+    Permit(#[from] alloy::signers::Error),
+
+    #[error("failed to sign for the smart account: {0}")]
+    AccountSignature(#[source] alloy::signers::Error),
+
+    #[error("transaction has no contract to call through the smart account")]
+    MissingRecipient,
+
+    #[error("user operation {hash} failed: {reason}")]
+    UserOperationFailed { hash: B256, reason: String },
+
+    #[error("user operation {hash} was not included within {blocks} blocks")]
+    UserOperationTimeout { hash: B256, blocks: u64 },
+
+    #[error("transaction {tx_hash} has a receipt, but {reason}; it may have been reorged out")]
+    Unverified { tx_hash: B256, reason: String },
+}
+
+#[derive(Debug, Error)]
+pub enum ShadowError {
+    #[error("failed to start {}: {source}", bin.display())]
+    Spawn {
+        bin: std::path::PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("anvil did not accept connections on port {port} within {timeout:?}")]
+    Startup {
+        port: u16,
+        timeout: std::time::Duration,
+    },
+
+    #[error("fork RPC failed: {0}")]
+    Transport(#[from] TransportError),
+
+    #[error("fork read failed: {0}")]
+    Contract(#[from] contract::Error),
+
+    #[error("fork returned no receipt for {0:?}")]
+    MissingReceipt(B256),
+
+    #[error("{kind} reverted on the fork: {tx_hash:?}")]
+    Reverted { kind: &'static str, tx_hash: B256 },
+
+    #[error("{kind} on the fork looked wrong, not sending it: {reason}")]
+    Insane { kind: &'static str, reason: String },
 }
 
 #[derive(Debug, Error)]
@@ -164,3 +280,166 @@ pub enum BlockStreamError {
     #[error("block stream error: {0}")]
     Transport(#[from] TransportError),
 }
+
+#[derive(Debug, Error)]
+pub enum EventStreamError {
+    #[error("event stream error: {0}")]
+    Transport(#[from] TransportError),
+}
+
+#[derive(Debug, Error)]
+pub enum PriceError {
+    #[error("invalid price {0}")]
+    InvalidPrice(f64),
+
+    #[error("overflow while converting price to Q96")]
+    Overflow,
+}
+
+#[derive(Debug, Error)]
+pub enum StrategyConfigError {
+    #[error("invalid {field} `{value}`: {reason}")]
+    InvalidValue {
+        field: &'static str,
+        value: String,
+        reason: String,
+    },
+
+    #[error("{field} is given in {found}, but the auction's currency is {expected}")]
+    WrongCurrency {
+        field: &'static str,
+        found: String,
+        expected: String,
+    },
+
+    #[error(transparent)]
+    Validation(#[from] ValidationError),
+
+    #[cfg(feature = "script")]
+    #[error(transparent)]
+    Script(#[from] ScriptError),
+}
+
+#[derive(Debug, Error)]
+pub enum AttestationError {
+    #[error("failed to sign attestation: {0}")]
+    Sign(#[from] alloy::signers::Error),
+
+    #[error("invalid attestation signature: {0}")]
+    Signature(#[from] SignatureError),
+
+    #[error("attestation claims signer {expected} but was signed by {recovered}")]
+    SignerMismatch {
+        expected: Address,
+        recovered: Address,
+    },
+}
+
+#[derive(Debug, Error)]
+pub enum AnalyticsError {
+    #[error("no BidSubmitted event for bid {0}")]
+    NotSubmitted(U256),
+
+    #[error("bid {0} has not exited yet")]
+    NotExited(U256),
+}
+
+#[derive(Debug, Error)]
+pub enum ScheduleError {
+    #[error("invalid schedule `{input}`: {reason}")]
+    Invalid { input: String, reason: String },
+}
+
+#[derive(Debug, Error)]
+pub enum ParameterError {
+    #[error("auction has no steps")]
+    NoSteps,
+
+    #[error("step {index} releases over {blocks} blocks, expected 1 to 2^40 - 1")]
+    StepBlocks { index: usize, blocks: u64 },
+
+    #[error(
+        "steps release {total} mps in total, expected {}",
+        crate::types::Mps::FULL
+    )]
+    StepsNotFull { total: u64 },
+
+    #[error("steps end at block {steps_end}, but end block is {end_block}")]
+    EndBlockMismatch { steps_end: u64, end_block: u64 },
+
+    #[error("claim block {claim_block} is before end block {end_block}")]
+    ClaimBeforeEnd { claim_block: u64, end_block: u64 },
+
+    #[error("required currency raised {0} does not fit in uint128")]
+    RequiredCurrencyTooLarge(U256),
+
+    #[error("token is the zero address")]
+    TokenIsZero,
+
+    #[error("token and currency are the same contract; an auction can't sell a token for itself")]
+    TokenIsCurrency,
+
+    #[error("total supply is zero, so there is nothing to sell")]
+    TotalSupplyIsZero,
+
+    #[error("total supply {0} does not fit in uint128")]
+    TotalSupplyTooLarge(U256),
+
+    #[error("tokens recipient is the zero address; unsold tokens would be burned")]
+    TokensRecipientIsZero,
+
+    #[error("funds recipient is the zero address; raised currency would be lost")]
+    FundsRecipientIsZero,
+
+    #[error("end block {end_block} is not after start block {start_block}")]
+    EndNotAfterStart { start_block: u64, end_block: u64 },
+
+    #[error("floor price is zero; bids need a price above a nonzero floor")]
+    FloorPriceIsZero,
+
+    #[error(
+        "tick spacing {0} is below the minimum of {min}",
+        min = crate::types::TickSpacing::MIN
+    )]
+    TickSpacingTooSmall(U256),
+
+    #[error("floor price {floor_price} is not a multiple of tick spacing {tick_spacing}")]
+    FloorPriceNotAtTick {
+        floor_price: U256,
+        tick_spacing: U256,
+    },
+
+    #[error(
+        "the first tick above the floor, {next_tick}, is past the max bid price {max_bid_price}, so no bid could be placed; lower the floor price or tick spacing"
+    )]
+    MaxBidPriceExceeded {
+        next_tick: U256,
+        max_bid_price: U256,
+    },
+
+    #[error("creating the auction reverts with {0}")]
+    CreationReverts(String),
+}
+
+#[cfg(feature = "metrics")]
+#[derive(Debug, Error)]
+pub enum MetricsError {
+    #[error("metrics exporter failed: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[cfg(feature = "script")]
+#[derive(Debug, Error)]
+pub enum ScriptError {
+    #[error("failed to read script {path}: {source}")]
+    Read {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("failed to compile script: {0}")]
+    Compile(#[from] rhai::ParseError),
+
+    #[error("script does not define `fn evaluate(ctx)`")]
+    MissingEvaluate,
+}
@@ -0,0 +1,108 @@
+use alloy::primitives::Address;
+
+use crate::types::primitives::{BidId, BlockNumber, CurrencyAmount, Price};
+
+use super::AuctionEvent;
+
+/// A single bid that added more demand at one tick than the configured threshold.
+#[derive(Clone, Debug)]
+pub struct DemandAlert {
+    pub block: BlockNumber,
+    pub bid_id: BidId,
+    pub owner: Address,
+    pub price: Price,
+    pub amount: CurrencyAmount,
+}
+
+/// Flags `BidSubmitted` events whose amount meets or exceeds a threshold.
+///
+/// Each bid is judged on its own: every one at or over the threshold is
+/// flagged, however recently the last alert was.
+#[derive(Clone, Debug)]
+pub struct DemandAlertDetector {
+    threshold: CurrencyAmount,
+    ignored_owners: Vec<Address>,
+}
+
+impl DemandAlertDetector {
+    pub fn new(threshold: CurrencyAmount) -> Self {
+        Self {
+            threshold,
+            ignored_owners: Vec::new(),
+        }
+    }
+
+    /// Skip bids from `owner`, typically our own wallet.
+    pub fn ignore_owner(mut self, owner: Address) -> Self {
+        self.ignored_owners.push(owner);
+        self
+    }
+
+    pub fn inspect(&self, event: &AuctionEvent) -> Option<DemandAlert> {
+        let AuctionEvent::BidSubmitted {
+            block,
+            bid_id,
+            owner,
+            price,
+            amount,
+        } = event
+        else {
+            return None;
+        };
+
+        if *amount < self.threshold || self.ignored_owners.contains(owner) {
+            return None;
+        }
+
+        Some(DemandAlert {
+            block: *block,
+            bid_id: *bid_id,
+            owner: *owner,
+            price: *price,
+            amount: *amount,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::U256;
+
+    use super::*;
+    use crate::types::config::fixture::{amount, price};
+
+    fn bid(id: u64, owner: Address, value: u64) -> AuctionEvent {
+        AuctionEvent::BidSubmitted {
+            block: BlockNumber::new(10 + id),
+            bid_id: BidId::new(U256::from(id)),
+            owner,
+            price: price(20),
+            amount: amount(value),
+        }
+    }
+
+    #[test]
+    fn flags_every_bid_at_or_over_the_threshold() {
+        let (whale, us) = (Address::repeat_byte(0xa), Address::repeat_byte(0xb));
+        let detector = DemandAlertDetector::new(amount(100)).ignore_owner(us);
+        let events = [
+            bid(1, whale, 99),
+            bid(2, whale, 100),
+            bid(3, whale, 50),
+            bid(4, whale, 500),
+            bid(5, us, 500),
+            AuctionEvent::ClearingPriceUpdated {
+                block: BlockNumber::new(16),
+                clearing_price: price(20),
+            },
+        ];
+
+        // Crossing the threshold once does not hold back the next big bid.
+        let flagged: Vec<_> = events
+            .iter()
+            .filter_map(|event| detector.inspect(event))
+            .map(|alert| (alert.bid_id.as_u256().to::<u64>(), alert.amount))
+            .collect();
+        assert_eq!(flagged, [(2, amount(100)), (4, amount(500))]);
+    }
+}
@@ -0,0 +1,168 @@
+use alloy::{
+    primitives::{Address, U256},
+    rpc::types::Log,
+    sol_types::SolEvent,
+};
+use flux_abi::IContinuousClearingAuction;
+
+use crate::types::primitives::{BidId, BlockNumber, CurrencyAmount, Mps, Price, TokenAmount};
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AuctionEvent {
+    BidSubmitted {
+        block: BlockNumber,
+        bid_id: BidId,
+        owner: Address,
+        price: Price,
+        amount: CurrencyAmount,
+    },
+    BidExited {
+        block: BlockNumber,
+        bid_id: BidId,
+        owner: Address,
+        tokens_filled: TokenAmount,
+        currency_refunded: CurrencyAmount,
+    },
+    TokensClaimed {
+        block: BlockNumber,
+        bid_id: BidId,
+        owner: Address,
+        tokens_filled: TokenAmount,
+    },
+    CheckpointUpdated {
+        block: BlockNumber,
+        clearing_price: Price,
+        cumulative_mps: Mps,
+    },
+    ClearingPriceUpdated {
+        block: BlockNumber,
+        clearing_price: Price,
+    },
+    TokensReceived {
+        block: BlockNumber,
+        total_supply: TokenAmount,
+    },
+    /// The auction moved to a new step of its supply schedule, releasing
+    /// `mps` of the supply per block from `start_block` until `end_block`.
+    AuctionStepRecorded {
+        block: BlockNumber,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+        mps: Mps,
+    },
+}
+
+impl AuctionEvent {
+    /// Decodes a raw auction log, returning `None` for events flux does not track.
+    pub fn decode(log: &Log) -> Option<Self> {
+        let block = BlockNumber::new(log.block_number.unwrap_or_default());
+        let topic = *log.topic0()?;
+
+        let event = match topic {
+            IContinuousClearingAuction::BidSubmitted::SIGNATURE_HASH => {
+                let data = log
+                    .log_decode::<IContinuousClearingAuction::BidSubmitted>()
+                    .ok()?
+                    .inner
+                    .data;
+                Self::BidSubmitted {
+                    block,
+                    bid_id: BidId::new(data.id),
+                    owner: data.owner,
+                    price: Price::new(data.price),
+                    amount: CurrencyAmount::new(U256::from(data.amount)),
+                }
+            }
+            IContinuousClearingAuction::BidExited::SIGNATURE_HASH => {
+                let data = log
+                    .log_decode::<IContinuousClearingAuction::BidExited>()
+                    .ok()?
+                    .inner
+                    .data;
+                Self::BidExited {
+                    block,
+                    bid_id: BidId::new(data.bidId),
+                    owner: data.owner,
+                    tokens_filled: TokenAmount::new(data.tokensFilled),
+                    currency_refunded: CurrencyAmount::new(data.currencyRefunded),
+                }
+            }
+            IContinuousClearingAuction::TokensClaimed::SIGNATURE_HASH => {
+                let data = log
+                    .log_decode::<IContinuousClearingAuction::TokensClaimed>()
+                    .ok()?
+                    .inner
+                    .data;
+                Self::TokensClaimed {
+                    block,
+                    bid_id: BidId::new(data.bidId),
+                    owner: data.owner,
+                    tokens_filled: TokenAmount::new(data.tokensFilled),
+                }
+            }
+            IContinuousClearingAuction::CheckpointUpdated::SIGNATURE_HASH => {
+                let data = log
+                    .log_decode::<IContinuousClearingAuction::CheckpointUpdated>()
+                    .ok()?
+                    .inner
+                    .data;
+                Self::CheckpointUpdated {
+                    block: BlockNumber::new(data.blockNumber.to::<u64>()),
+                    clearing_price: Price::new(data.clearingPrice),
+                    cumulative_mps: Mps::new(data.cumulativeMps),
+                }
+            }
+            IContinuousClearingAuction::ClearingPriceUpdated::SIGNATURE_HASH => {
+                let data = log
+                    .log_decode::<IContinuousClearingAuction::ClearingPriceUpdated>()
+                    .ok()?
+                    .inner
+                    .data;
+                Self::ClearingPriceUpdated {
+                    block: BlockNumber::new(data.blockNumber.to::<u64>()),
+                    clearing_price: Price::new(data.clearingPrice),
+                }
+            }
+            IContinuousClearingAuction::TokensReceived::SIGNATURE_HASH => {
+                let data = log
+                    .log_decode::<IContinuousClearingAuction::TokensReceived>()
+                    .ok()?
+                    .inner
+                    .data;
+                Self::TokensReceived {
+                    block,
+                    total_supply: TokenAmount::new(data.totalSupply),
+                }
+            }
+            IContinuousClearingAuction::AuctionStepRecorded::SIGNATURE_HASH => {
+                let data = log
+                    .log_decode::<IContinuousClearingAuction::AuctionStepRecorded>()
+                    .ok()?
+                    .inner
+                    .data;
+                Self::AuctionStepRecorded {
+                    block,
+                    start_block: BlockNumber::new(data.startBlock.to::<u64>()),
+                    end_block: BlockNumber::new(data.endBlock.to::<u64>()),
+                    mps: Mps::new(data.mps),
+                }
+            }
+            _ => return None,
+        };
+
+        Some(event)
+    }
+
+    pub fn block(&self) -> BlockNumber {
+        match self {
+            Self::BidSubmitted { block, .. }
+            | Self::BidExited { block, .. }
+            | Self::TokensClaimed { block, .. }
+            | Self::CheckpointUpdated { block, .. }
+            | Self::ClearingPriceUpdated { block, .. }
+            | Self::TokensReceived { block, .. }
+            | Self::AuctionStepRecorded { block, .. } => *block,
+        }
+    }
+}
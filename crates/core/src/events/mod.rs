@@ -0,0 +1,7 @@
+mod alert;
+mod event;
+mod producer;
+
+pub use alert::{DemandAlert, DemandAlertDetector};
+pub use event::AuctionEvent;
+pub use producer::{BoxEventStream, EventProducer, EventStream, EventStreamItem};
@@ -0,0 +1,87 @@
+use alloy::{
+    primitives::Address,
+    providers::Provider,
+    rpc::types::Filter,
+    transports::{TransportError, TransportErrorKind},
+};
+use futures::{Stream, StreamExt, stream::BoxStream};
+
+use crate::{error::EventStreamError, types::primitives::BlockNumber};
+
+use super::AuctionEvent;
+
+pub type EventStreamItem = Result<AuctionEvent, EventStreamError>;
+
+pub trait EventStream: Stream<Item = EventStreamItem> + Send + Unpin {}
+
+impl<T> EventStream for T where T: Stream<Item = EventStreamItem> + Send + Unpin {}
+
+pub type BoxEventStream = BoxStream<'static, EventStreamItem>;
+
+#[derive(Clone)]
+pub struct EventProducer<P>
+where
+    P: Provider + Clone,
+{
+    provider: P,
+    auction: Address,
+}
+
+impl<P> EventProducer<P>
+where
+    P: Provider + Clone,
+{
+    pub fn new(provider: P, auction: Address) -> Self {
+        Self { provider, auction }
+    }
+
+    pub async fn into_stream(self) -> Result<BoxEventStream, EventStreamError> {
+        match self.try_subscribe().await {
+            Ok(stream) => Ok(stream),
+            Err(EventStreamError::Transport(err)) => match err {
+                TransportError::Transport(TransportErrorKind::PubsubUnavailable) => {
+                    self.watch().await
+                }
+                other => Err(EventStreamError::Transport(other)),
+            },
+        }
+    }
+
+    /// Fetches the auction's past events from `from` through `to` (or the
+    /// latest block), in log order.
+    pub async fn history(
+        &self,
+        from: BlockNumber,
+        to: Option<BlockNumber>,
+    ) -> Result<Vec<AuctionEvent>, EventStreamError> {
+        let mut filter = self.filter().from_block(from.as_u64());
+        if let Some(to) = to {
+            filter = filter.to_block(to.as_u64());
+        }
+        let logs = self.provider.get_logs(&filter).await?;
+        Ok(logs.iter().filter_map(AuctionEvent::decode).collect())
+    }
+
+    fn filter(&self) -> Filter {
+        Filter::new().address(self.auction)
+    }
+
+    async fn try_subscribe(&self) -> Result<BoxEventStream, EventStreamError> {
+        let subscription = self.provider.subscribe_logs(&self.filter()).await?;
+        let stream = subscription
+            .into_stream()
+            .filter_map(|log| async move { AuctionEvent::decode(&log).map(Ok) })
+            .boxed();
+        Ok(stream)
+    }
+
+    async fn watch(&self) -> Result<BoxEventStream, EventStreamError> {
+        let watcher = self.provider.watch_logs(&self.filter()).await?;
+        let stream = watcher
+            .into_stream()
+            .flat_map(futures::stream::iter)
+            .filter_map(|log| async move { AuctionEvent::decode(&log).map(Ok) })
+            .boxed();
+        Ok(stream)
+    }
+}
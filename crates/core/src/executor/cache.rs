@@ -1,13 +1,28 @@
 use crate::types::{
     checkpoint::Checkpoint,
-    state::{GraduationStatus, TokenDepositStatus},
+    clock::BlockClock,
+    primitives::BlockNumber,
+    state::{AuctionMetrics, GraduationStatus, TokenDepositStatus},
+    step::StepSchedule,
 };
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExecutorCache {
     pub tokens_received: TokenDepositStatus,
     pub graduated: GraduationStatus,
     pub final_checkpoint: Option<Checkpoint>,
+    /// Most recent checkpoint seen by the executor, whether fetched or poked.
+    /// Re-read each block until the end block, so it follows checkpoints
+    /// made by anyone.
+    pub latest_checkpoint: Option<Checkpoint>,
+    /// Raise and clearing totals, re-read each block while bids can land.
+    pub metrics: Option<AuctionMetrics>,
+    /// Supply schedule, read once when the auction starts; `None` if the
+    /// client cannot read it.
+    pub step_schedule: Option<StepSchedule>,
+    /// Block timestamps sampled by the executor, for time-based schedules.
+    pub clock: BlockClock,
 }
 
 impl ExecutorCache {
@@ -16,6 +31,10 @@ impl ExecutorCache {
             tokens_received: TokenDepositStatus::Unknown,
             graduated: GraduationStatus::NotGraduated,
             final_checkpoint: None,
+            latest_checkpoint: None,
+            metrics: None,
+            step_schedule: None,
+            clock: BlockClock::default(),
         }
     }
 
@@ -26,16 +45,20 @@ impl ExecutorCache {
         checkpoint: Option<Checkpoint>,
         past_end_block: bool,
     ) {
-        if let Some(status) = tokens {
-            if matches!(status, TokenDepositStatus::Received) {
-                self.tokens_received = status;
-            }
+        if let Some(status) = tokens
+            && matches!(status, TokenDepositStatus::Received)
+        {
+            self.tokens_received = status;
         }
 
-        if let Some(status) = graduation {
-            if matches!(status, GraduationStatus::Graduated) {
-                self.graduated = status;
-            }
+        if let Some(status) = graduation
+            && matches!(status, GraduationStatus::Graduated)
+        {
+            self.graduated = status;
+        }
+
+        if checkpoint.is_some() {
+            self.latest_checkpoint = checkpoint;
         }
 
         if past_end_block && checkpoint.is_some() && self.final_checkpoint.is_none() {
@@ -51,6 +74,13 @@ impl ExecutorCache {
         !matches!(self.graduated, GraduationStatus::Graduated)
     }
 
+    /// Number of blocks between `block` and the latest cached checkpoint, or
+    /// `None` if no checkpoint has been seen yet.
+    pub fn checkpoint_age(&self, block: BlockNumber) -> Option<u64> {
+        self.latest_checkpoint
+            .map(|cp| block.as_u64().saturating_sub(cp.block.as_u64()))
+    }
+
     pub fn needs_checkpoint(&self, past_end_block: bool) -> bool {
         if past_end_block {
             self.final_checkpoint.is_none()
@@ -1,15 +1,61 @@
-use crate::types::{
-    config::AuctionConfig,
-    primitives::{BidId, BlockNumber},
-    state::AuctionPhase,
+use std::time::Duration;
+
+use alloy::primitives::aliases::U24;
+
+use crate::{
+    api::AuctionApi,
+    events::AuctionEvent,
+    types::{
+        chain::ChainProfile,
+        config::AuctionConfig,
+        primitives::{BidId, BlockNumber, Mps},
+        state::{AuctionPhase, GraduationStatus, RaiseProgress},
+    },
 };
 
 use super::ExecutorCache;
 
+/// What a strategy sees at one block.
+///
+/// Serializes to the layout [`RecordedContext`](crate::RecordedContext)
+/// reads back, without the client.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct EvaluationContext<'a> {
     pub block: BlockNumber,
     pub phase: AuctionPhase,
     pub cache: &'a ExecutorCache,
     pub tracked_bids: Vec<BidId>,
     pub config: &'a AuctionConfig,
+    /// Auction events seen since the previous evaluation, oldest first.
+    pub events: &'a [AuctionEvent],
+    /// Client the executor sends through, for strategies that do their own
+    /// reads. `None` when replaying a [`Backtest`](crate::Backtest).
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub client: Option<&'a dyn AuctionApi>,
+    pub chain: ChainProfile,
+}
+
+impl EvaluationContext<'_> {
+    /// Expected wall clock time from this block until `block`, at the block
+    /// time measured by the executor's [`BlockClock`](crate::BlockClock).
+    pub fn estimated_time_until(&self, block: BlockNumber) -> Duration {
+        let blocks = block.as_u64().saturating_sub(self.block.as_u64());
+        self.cache.clock.duration_of(blocks)
+    }
+
+    /// Whether the auction has raised enough to graduate, as last read.
+    pub fn graduation(&self) -> GraduationStatus {
+        self.cache.graduated
+    }
+
+    /// Currency raised against the graduation requirement, projected to the
+    /// end from the latest checkpoint. `None` until metrics are read, or if
+    /// the requirement is unknown.
+    pub fn raise_progress(&self) -> Option<RaiseProgress> {
+        let sold = self
+            .cache
+            .latest_checkpoint
+            .map_or(Mps::new(U24::ZERO), |checkpoint| checkpoint.cumulative_mps);
+        RaiseProgress::new(self.cache.metrics.as_ref()?, sold)
+    }
 }
@@ -1,52 +1,235 @@
-use alloy::providers::Provider;
+use std::time::Instant;
+
+use alloy::primitives::Address;
+use tracing::{info, instrument, warn};
 
 use crate::{
-    client::AuctionClient,
-    error::{Error, StateError},
+    api::AuctionApi,
+    error::{Error, StateError, TransactionError, ValidationError},
+    events::AuctionEvent,
     types::{
-        action::{ClaimParams, ExitBidParams, SubmitBidInput},
-        bid::BidStatus,
-        primitives::{BidId, BlockNumber, CurrencyAmount, Price},
+        action::{ClaimParams, ClaimResult, ExitBidParams, FailedClaim, SubmitBidInput},
+        bid::{AtmSettlement, Bid, Moneyness},
+        chain::ChainProfile,
+        clock::BlockClock,
+        dust::{DustSkip, DustThreshold},
+        primitives::{BidId, BlockNumber, CurrencyAmount, Price, TokenAmount},
+        risk::{RiskLimits, RiskTracker},
         state::AuctionState,
+        wallet::WalletPool,
     },
     validation,
 };
 
 use super::{EvaluationContext, ExecutorCache, Intent, IntentOutcome, IntentResult};
 
-pub struct IntentExecutor<P>
+pub struct IntentExecutor<C>
 where
-    P: Provider + Clone,
+    C: AuctionApi,
 {
-    client: AuctionClient<P>,
+    client: C,
     cache: ExecutorCache,
+    dust: Option<DustThreshold>,
+    risk: RiskTracker,
+    atm: AtmSettlement,
+    wallets: Option<WalletPool>,
+    /// Intents held back by `atm`, with the block they are due at.
+    deferred: Vec<(BlockNumber, Intent)>,
+    /// Whether the step schedule was asked for, so a failed read is not
+    /// retried every block.
+    step_schedule_requested: bool,
+    /// Blocks an intent is suppressed for after failing; `None` never
+    /// suppresses.
+    failure_cooldown: Option<u64>,
+    /// Recently failed intents, without deadlines, with why they failed and
+    /// the block they may be tried again at.
+    recent_failures: Vec<(Intent, String, BlockNumber)>,
+    /// Tags every submitted bid is tracked with.
+    bid_tags: Vec<String>,
+    /// Whether successes are read back from the auction before they count.
+    verify_after_execute: bool,
 }
 
-impl<P> IntentExecutor<P>
+impl<C> IntentExecutor<C>
 where
-    P: Provider + Clone,
+    C: AuctionApi,
 {
-    pub fn new(client: AuctionClient<P>) -> Self {
+    pub fn new(client: C) -> Self {
         Self {
             client,
             cache: ExecutorCache::new(),
+            dust: None,
+            risk: RiskTracker::default(),
+            atm: AtmSettlement::default(),
+            wallets: None,
+            deferred: Vec::new(),
+            step_schedule_requested: false,
+            failure_cooldown: None,
+            recent_failures: Vec::new(),
+            bid_tags: Vec::new(),
+            verify_after_execute: false,
         }
     }
 
+    /// Enforce `limits` on every intent before it is executed.
+    pub fn with_risk_limits(mut self, limits: RiskLimits) -> Self {
+        if let Some(gas) = limits.max_gas_per_tx {
+            self.client.set_max_gas_per_tx(gas);
+        }
+        self.risk = RiskTracker::new(limits);
+        self
+    }
+
+    /// Replaces the risk limits of a running executor, keeping what was
+    /// spent and sent so far.
+    pub fn set_risk_limits(&mut self, limits: RiskLimits) {
+        // No cap lifts one set earlier.
+        self.client
+            .set_max_gas_per_tx(limits.max_gas_per_tx.unwrap_or(u64::MAX));
+        self.risk.set_limits(limits);
+    }
+
+    pub fn risk(&self) -> &RiskTracker {
+        &self.risk
+    }
+
+    /// Skip exits and claims whose value is below `threshold`.
+    pub fn with_dust_threshold(mut self, threshold: DustThreshold) -> Self {
+        self.dust = Some(threshold);
+        self
+    }
+
+    /// After an intent fails, answer the same intent with
+    /// [`IntentOutcome::Suppressed`] for `blocks` blocks instead of sending
+    /// it again, so a strategy repeating a bad bid every block does not burn
+    /// gas on it. Deadlines are ignored when comparing intents, and the
+    /// orchestrator's retries are suppressed like any other repeat.
+    pub fn with_failure_cooldown(mut self, blocks: u64) -> Self {
+        self.failure_cooldown = Some(blocks);
+        self
+    }
+
+    /// Exit bids at the clearing price according to `policy`.
+    pub fn with_atm_settlement(mut self, policy: AtmSettlement) -> Self {
+        self.atm = policy;
+        self
+    }
+
+    /// Spread bids across the wallets of `pool` instead of sending them all
+    /// from the client's owner. Claims may then cover bids of any of them.
+    pub fn with_wallet_pool(mut self, pool: WalletPool) -> Self {
+        self.wallets = Some(pool);
+        self
+    }
+
+    /// Track every bid this executor submits with `tags`, e.g. the name of
+    /// the strategy deciding them.
+    pub fn with_bid_tags(mut self, tags: Vec<String>) -> Self {
+        self.bid_tags = tags;
+        self
+    }
+
+    /// After a transaction's receipt arrives, read the auction's state back
+    /// (the bid a submission created, the exit or claim it recorded, the
+    /// new checkpoint) and fail the intent unless it matches, so a node
+    /// returning receipts for reorged-out transactions isn't believed.
+    pub fn with_verify_after_execute(mut self, verify: bool) -> Self {
+        self.verify_after_execute = verify;
+        self
+    }
+
+    /// Time estimates for strategies use `chain`'s block time instead of
+    /// mainnet's.
+    pub fn with_chain_profile(mut self, chain: ChainProfile) -> Self {
+        self.cache.clock = BlockClock::new(chain);
+        self
+    }
+
+    pub fn chain_profile(&self) -> &ChainProfile {
+        self.cache.clock.chain()
+    }
+
+    pub fn wallet_pool(&self) -> Option<&WalletPool> {
+        self.wallets.as_ref()
+    }
+
+    /// Removes and returns deferred intents that are due at `block`.
+    pub fn due_intents(&mut self, block: BlockNumber) -> Vec<Intent> {
+        let (due, waiting) = std::mem::take(&mut self.deferred)
+            .into_iter()
+            .partition(|(until, _)| *until <= block);
+        self.deferred = waiting;
+        due.into_iter().map(|(_, intent)| intent).collect()
+    }
+
+    #[instrument(
+        name = "intent",
+        skip_all,
+        fields(auction = %self.client.address(), kind = intent.kind(), block = block.as_u64())
+    )]
     pub async fn execute(&mut self, intent: Intent, block: BlockNumber) -> IntentOutcome {
-        match self.execute_inner(intent.clone(), block).await {
-            Ok(result) => IntentOutcome::Success(result),
-            Err(error) => IntentOutcome::Failed { intent, error },
+        if let Some(execute_by) = intent.execute_by()
+            && block > execute_by
+        {
+            info!(execute_by = execute_by.as_u64(), "intent expired");
+            return IntentOutcome::Expired { intent, execute_by };
+        }
+
+        let inner = intent.clone().into_inner();
+        self.recent_failures.retain(|(_, _, until)| *until > block);
+        if let Some((_, cause, until)) = self
+            .recent_failures
+            .iter()
+            .find(|(failed, _, _)| *failed == inner)
+        {
+            info!(
+                until = until.as_u64(),
+                "intent suppressed after a recent failure"
+            );
+            return IntentOutcome::Suppressed {
+                cause: cause.clone(),
+                until: *until,
+                intent,
+            };
+        }
+
+        match self.execute_inner(inner.clone(), block).await {
+            Ok(outcome) => {
+                match &outcome {
+                    IntentOutcome::Skipped { skipped, .. } => {
+                        info!(skipped = skipped.len(), "intent skipped as dust");
+                    }
+                    IntentOutcome::Deferred { intent, until } => {
+                        info!(until = until.as_u64(), "intent deferred");
+                        self.deferred.push((*until, intent.clone()));
+                    }
+                    _ => {}
+                }
+                outcome
+            }
+            Err(error) => {
+                warn!(%error, "intent failed");
+                if let Some(blocks) = self.failure_cooldown {
+                    let until = BlockNumber::new(block.as_u64().saturating_add(blocks));
+                    self.recent_failures.push((inner, error.to_string(), until));
+                }
+                IntentOutcome::Failed { intent, error }
+            }
         }
     }
 
-    pub fn context(&self, block: BlockNumber) -> EvaluationContext<'_> {
+    pub fn context<'a>(
+        &'a self,
+        block: BlockNumber,
+        events: &'a [AuctionEvent],
+    ) -> EvaluationContext<'a> {
         let phase =
             AuctionState::compute_phase(self.client.config(), block, self.cache.tokens_received);
 
         let tracked_bids: Vec<BidId> = self
             .client
             .tracked_bids()
+            .iter()
             .map(|tracked| tracked.id)
             .collect();
 
@@ -56,14 +239,68 @@ where
             cache: &self.cache,
             tracked_bids,
             config: self.client.config(),
+            events,
+            client: Some(&self.client),
+            chain: *self.cache.clock.chain(),
         }
     }
 
-    pub fn client(&self) -> &AuctionClient<P> {
+    /// Refreshes cached state that the evaluation context depends on.
+    ///
+    /// Until the token deposit is seen the phase cannot advance past
+    /// `PreTokens`, so the balance is re-read each block once the auction
+    /// has started. Auction metrics are re-read every block until the end
+    /// block, then once more so the cache holds the final totals. The latest
+    /// checkpoint and the graduation status are read the same way, so
+    /// strategies see checkpoints made by anyone, not only by this executor,
+    /// and the final checkpoint is cached once the end block has passed. Block
+    /// timestamps are sampled every few blocks from the first one on, for
+    /// the cache's [`BlockClock`].
+    pub async fn refresh(&mut self, block: BlockNumber) -> Result<(), Error> {
+        if self.cache.clock.needs_sample(block) {
+            match self.client.fetch_block_timestamp(block).await {
+                Ok(Some(timestamp)) => self.cache.clock.observe(block, timestamp),
+                Ok(None) => {}
+                Err(error) => warn!(%error, "failed to read the block timestamp"),
+            }
+        }
+        if block < self.client.config().start_block {
+            return Ok(());
+        }
+        if self.cache.needs_token_balance() {
+            let tokens = self.client.fetch_token_balance().await?;
+            let past_end_block = self.is_past_end(block);
+            self.cache.update(Some(tokens), None, None, past_end_block);
+        }
+        let past_end_block = self.is_past_end(block);
+        if !past_end_block || self.cache.metrics.is_none() {
+            self.cache.metrics = Some(self.client.fetch_auction_metrics().await?);
+        }
+        if self.cache.needs_checkpoint(past_end_block) {
+            let checkpoint = self.client.fetch_checkpoint().await?;
+            let graduation = if self.cache.needs_graduation() {
+                Some(self.client.fetch_graduation().await?)
+            } else {
+                None
+            };
+            self.cache
+                .update(None, graduation, Some(checkpoint), past_end_block);
+        }
+        if !self.step_schedule_requested {
+            self.step_schedule_requested = true;
+            match self.client.fetch_step_schedule().await {
+                Ok(schedule) => self.cache.step_schedule = schedule,
+                Err(error) => warn!(%error, "failed to read the step schedule"),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn client(&self) -> &C {
         &self.client
     }
 
-    pub fn client_mut(&mut self) -> &mut AuctionClient<P> {
+    pub fn client_mut(&mut self) -> &mut C {
         &mut self.client
     }
 
@@ -75,13 +312,62 @@ where
         &mut self,
         intent: Intent,
         block: BlockNumber,
-    ) -> Result<IntentResult, Error> {
-        match intent {
-            Intent::SubmitBid { max_price, amount } => {
-                self.execute_submit_bid(max_price, amount, block).await
+    ) -> Result<IntentOutcome, Error> {
+        self.risk
+            .check_tx(Instant::now())
+            .map_err(ValidationError::RiskLimitExceeded)?;
+        let bid_amount = match &intent {
+            Intent::SubmitBid {
+                max_price, amount, ..
+            } => {
+                self.risk
+                    .check_bid(*amount, *max_price)
+                    .map_err(ValidationError::RiskLimitExceeded)?;
+                Some(*amount)
+            }
+            _ => None,
+        };
+
+        let expected = self.verify_after_execute.then(|| intent.clone());
+        let outcome = self.dispatch(intent, block).await?;
+        // Recorded as soon as it lands: a bid that fails to read back may
+        // still be on chain, and must count against the limits.
+        if matches!(outcome, IntentOutcome::Success(_)) {
+            self.risk.record_tx(Instant::now());
+            if let Some(amount) = bid_amount {
+                self.risk.record_spend(amount);
             }
+        }
+        if let (Some(intent), IntentOutcome::Success(result)) = (&expected, &outcome) {
+            self.verify(intent, result).await?;
+        }
+        Ok(outcome)
+    }
+
+    async fn dispatch(
+        &mut self,
+        intent: Intent,
+        block: BlockNumber,
+    ) -> Result<IntentOutcome, Error> {
+        match intent {
+            Intent::SubmitBid {
+                max_price,
+                amount,
+                owner,
+                from,
+            } => self
+                .execute_submit_bid(max_price, amount, owner, from, block)
+                .await
+                .map(IntentOutcome::Success),
             Intent::Exit { bid_id } => self.execute_exit(bid_id, block).await,
             Intent::Claim { bid_ids } => self.execute_claim(bid_ids, block).await,
+            Intent::Checkpoint => self
+                .execute_checkpoint(block)
+                .await
+                .map(IntentOutcome::Success),
+            Intent::Deadline { intent, .. } => {
+                unreachable!("deadline on {} is stripped in `execute`", intent.kind())
+            }
         }
     }
 
@@ -89,8 +375,20 @@ where
         &mut self,
         max_price: Price,
         amount: CurrencyAmount,
+        owner: Option<Address>,
+        from: Option<Address>,
         block: BlockNumber,
     ) -> Result<IntentResult, Error> {
+        let wallet = match &mut self.wallets {
+            Some(pool) => pool.select(from, amount)?,
+            None => match from {
+                Some(from) if from != self.client.owner() => {
+                    return Err(ValidationError::UnknownWallet(from).into());
+                }
+                _ => self.client.owner(),
+            },
+        };
+
         let checkpoint = self.client.fetch_checkpoint().await?;
 
         let tokens_received = if self.cache.needs_token_balance() {
@@ -113,20 +411,49 @@ where
             self.cache.graduated,
             tokens_received,
             self.client.config(),
-        );
+        )
+        .with_metrics(self.cache.metrics.unwrap_or_default())
+        .with_chain(*self.cache.clock.chain());
 
         let input = SubmitBidInput {
             max_price,
             amount,
-            owner: self.client.owner(),
+            owner: owner.unwrap_or(wallet),
+            hint: None,
         };
         validation::validate_submit_bid(&input, &state, self.client.config())?;
 
-        let params = self.client.prepare_bid(input, &state).await?;
+        let mut params = self.client.prepare_bid(input, &state).await?;
+        params.from = wallet;
+        params.tags = self.bid_tags.clone();
+
+        let (balances, gas_price) = futures::try_join!(
+            self.client.fetch_balances(wallet),
+            self.client.fetch_gas_price(),
+        )?;
+        validation::validate_balance(&params, &balances, gas_price)?;
 
         self.client.hook().validate(&params, &state).await?;
 
-        let result = self.client.submit_bid(params).await?;
+        if let Some(pool) = &mut self.wallets {
+            let pending = self.client.fetch_nonce(wallet).await?;
+            params.nonce = Some(pool.next_nonce(wallet, pending));
+        }
+        let result = self.client.submit_bid(params).await;
+        if let Some(pool) = &mut self.wallets {
+            match &result {
+                Ok(submitted) => pool.record_spend(wallet, submitted.amount),
+                Err(_) => pool.reset_nonce(wallet),
+            }
+        }
+        let result = result?;
+        if let Some(beneficiary) = result.beneficiary {
+            warn!(
+                bid_id = %result.bid_id.as_u256(),
+                %beneficiary,
+                "bid placed for another owner; it must be settled with their account"
+            );
+        }
 
         Ok(IntentResult::BidSubmitted(result))
     }
@@ -135,7 +462,7 @@ where
         &mut self,
         bid_id: BidId,
         block: BlockNumber,
-    ) -> Result<IntentResult, Error> {
+    ) -> Result<IntentOutcome, Error> {
         let past_end_block = self.is_past_end(block);
 
         let checkpoint = if self.cache.needs_checkpoint(past_end_block) {
@@ -159,37 +486,54 @@ where
         let bids = self.client.fetch_bids(&[bid_id]).await?;
         let bid = bids.first().ok_or(StateError::BidNotFound)?;
 
+        if let Some(skip) = self.dust.and_then(|dust| dust.check_exit(bid)) {
+            return Ok(IntentOutcome::Skipped {
+                intent: Intent::Exit { bid_id },
+                skipped: vec![skip],
+            });
+        }
+
         let state = AuctionState::new(
             block,
             checkpoint,
             graduation,
             self.cache.tokens_received,
             self.client.config(),
-        );
-
-        let status = bid.status(checkpoint.clearing_price);
+        )
+        .with_metrics(self.cache.metrics.unwrap_or_default())
+        .with_chain(*self.cache.clock.chain());
+
+        let moneyness = bid.moneyness(checkpoint.clearing_price);
+        if matches!(moneyness, Moneyness::ATM)
+            && let Some(until) = self.atm_exit_deferred(block)
+        {
+            return Ok(IntentOutcome::Deferred {
+                intent: Intent::Exit { bid_id },
+                until,
+            });
+        }
 
-        let exit_result = match status {
-            BidStatus::ITM => {
+        let exit_result = match moneyness {
+            Moneyness::ITM => {
                 validation::validate_exit_bid(bid, &state, self.client.config())?;
                 let params = ExitBidParams { bid_id };
                 self.client.exit_bid(params).await?
             }
-            BidStatus::ATM | BidStatus::OTM => {
+            Moneyness::ATM | Moneyness::OTM => {
                 validation::validate_exit_partially_filled(bid, &state, self.client.config())?;
                 let params = self.client.prepare_exit_partially_filled(bid_id).await?;
                 self.client.exit_partially_filled(params).await?
             }
         };
 
-        Ok(IntentResult::BidExited(exit_result))
+        Ok(IntentOutcome::Success(IntentResult::BidExited(exit_result)))
     }
 
     async fn execute_claim(
         &mut self,
         bid_ids: Vec<BidId>,
         block: BlockNumber,
-    ) -> Result<IntentResult, Error> {
+    ) -> Result<IntentOutcome, Error> {
         let past_end_block = self.is_past_end(block);
 
         let graduation = if self.cache.needs_graduation() {
@@ -213,20 +557,451 @@ where
             graduation,
             self.cache.tokens_received,
             self.client.config(),
-        );
+        )
+        .with_metrics(self.cache.metrics.unwrap_or_default())
+        .with_chain(*self.cache.clock.chain());
+
+        // `claimTokensBatch` takes a single owner, and with a wallet pool
+        // each pooled wallet owns its own bids, so those are claimed once
+        // per owner.
+        let groups = match &self.wallets {
+            None => vec![(self.client.owner(), bids)],
+            Some(pool) => {
+                let mut groups: Vec<(Address, Vec<Bid>)> = Vec::new();
+                for bid in bids {
+                    if bid.owner != self.client.owner() && !pool.contains(bid.owner) {
+                        return Err(ValidationError::OwnerMismatch.into());
+                    }
+                    match groups.iter_mut().find(|(owner, _)| *owner == bid.owner) {
+                        Some((_, group)) => group.push(bid),
+                        None => groups.push((bid.owner, vec![bid])),
+                    }
+                }
+                groups
+            }
+        };
+        for (owner, group) in &groups {
+            validation::validate_claim(group, *owner, &state, self.client.config())?;
+        }
 
-        validation::validate_claim(&bids, self.client.owner(), &state, self.client.config())?;
+        let mut claims = Vec::new();
+        let mut skipped = Vec::new();
+        for (owner, group) in groups {
+            let (claimable, group_skipped) =
+                self.filter_dust_claims(group, checkpoint.clearing_price);
+            skipped.extend(group_skipped);
+            if !claimable.is_empty() {
+                claims.push(ClaimParams {
+                    owner,
+                    bid_ids: claimable,
+                    nonce: None,
+                });
+            }
+        }
+        let mut claims = claims.into_iter();
+        let Some(first) = claims.next() else {
+            return Ok(IntentOutcome::Skipped {
+                intent: Intent::Claim { bid_ids },
+                skipped,
+            });
+        };
 
-        let params = ClaimParams {
-            owner: self.client.owner(),
-            bid_ids,
+        // Every owner's claim is sent even if an earlier one fails: those
+        // that land are on chain, so they are reported with the failures
+        // instead of behind the first error.
+        let mut result: Option<ClaimResult> = None;
+        let mut failed = Vec::new();
+        let mut first_error = None;
+        for params in std::iter::once(first).chain(claims) {
+            let (owner, bids) = (params.owner, params.bid_ids.clone());
+            match self.client.claim(params).await {
+                Ok(claimed) => match &mut result {
+                    None => result = Some(claimed),
+                    Some(result) => {
+                        result.bid_ids.extend(claimed.bid_ids);
+                        result.total_tokens = TokenAmount::new(
+                            result.total_tokens.as_u256() + claimed.total_tokens.as_u256(),
+                        );
+                        result.tx_hash = claimed.tx_hash;
+                        result.tx_hashes.extend(claimed.tx_hashes);
+                        result.gas_used += claimed.gas_used;
+                        result.effective_gas_price = claimed.effective_gas_price;
+                    }
+                },
+                Err(error) => {
+                    warn!(%owner, %error, "claim failed");
+                    failed.push(FailedClaim {
+                        owner,
+                        bid_ids: bids,
+                        error: error.to_string(),
+                    });
+                    first_error.get_or_insert(error);
+                }
+            }
+        }
+        let Some(mut result) = result else {
+            return Err(first_error.expect("every claim failed"));
         };
-        let result = self.client.claim(params).await?;
+        result.skipped = skipped;
+        result.failed = failed;
 
-        Ok(IntentResult::TokensClaimed(result))
+        Ok(IntentOutcome::Success(IntentResult::TokensClaimed(result)))
+    }
+
+    /// Checks that what `result` reports for `intent` is what the auction
+    /// now holds.
+    async fn verify(&self, intent: &Intent, result: &IntentResult) -> Result<(), Error> {
+        let unverified = |tx_hash, reason| TransactionError::Unverified { tx_hash, reason };
+        let (tx_hash, bid_ids) = match result {
+            IntentResult::BidSubmitted(submitted) => (submitted.tx_hash, vec![submitted.bid_id]),
+            IntentResult::BidExited(exited) => (exited.tx_hash, vec![exited.bid_id]),
+            IntentResult::TokensClaimed(claimed) => (claimed.tx_hash, claimed.bid_ids.clone()),
+            IntentResult::CheckpointUpdated(updated) => {
+                let latest = self.client.fetch_checkpoint().await?;
+                if latest.block < updated.checkpoint.block {
+                    let reason = format!(
+                        "the latest checkpoint is at block {}, before its block {}",
+                        latest.block.as_u64(),
+                        updated.checkpoint.block.as_u64()
+                    );
+                    return Err(unverified(updated.tx_hash, reason).into());
+                }
+                return Ok(());
+            }
+        };
+
+        let bids = self.client.fetch_bids(&bid_ids).await.map_err(|error| {
+            unverified(tx_hash, format!("its bids can't be read back: {error}"))
+        })?;
+        let mismatch = bids.iter().find_map(|bid| {
+            let id = bid.id.as_u256();
+            match (result, intent) {
+                (
+                    IntentResult::BidSubmitted(submitted),
+                    Intent::SubmitBid {
+                        max_price, owner, ..
+                    },
+                ) => (bid.max_price != *max_price
+                    || bid.amount != submitted.amount
+                    || owner.is_some_and(|owner| owner != bid.owner))
+                .then(|| {
+                    format!(
+                        "bid {id} reads back with max price {}, amount {} and owner {}",
+                        bid.max_price.as_u256(),
+                        bid.amount.as_u256(),
+                        bid.owner
+                    )
+                }),
+                (IntentResult::BidExited(exited), _) => (bid.exited_block.is_none()
+                    || bid.tokens_filled != exited.tokens_filled)
+                    .then(|| format!("bid {id} does not read back as exited with its fill")),
+                (IntentResult::TokensClaimed(_), _) => (!bid.tokens_filled.is_zero())
+                    .then(|| format!("bid {id} still reads back with tokens to claim")),
+                _ => None,
+            }
+        });
+        match mismatch {
+            Some(reason) => Err(unverified(tx_hash, reason).into()),
+            None => Ok(()),
+        }
+    }
+
+    async fn execute_checkpoint(&mut self, block: BlockNumber) -> Result<IntentResult, Error> {
+        let result = self.client.poke_checkpoint().await?;
+
+        let past_end_block = self.is_past_end(block);
+        self.cache
+            .update(None, None, Some(result.checkpoint), past_end_block);
+
+        Ok(IntentResult::CheckpointUpdated(result))
+    }
+
+    fn filter_dust_claims(
+        &self,
+        bids: Vec<Bid>,
+        clearing_price: Price,
+    ) -> (Vec<BidId>, Vec<DustSkip>) {
+        let Some(dust) = self.dust else {
+            return (bids.iter().map(|bid| bid.id).collect(), Vec::new());
+        };
+
+        let mut claimable = Vec::new();
+        let mut skipped = Vec::new();
+        for bid in &bids {
+            match dust.check_claim(bid, clearing_price) {
+                Some(skip) => skipped.push(skip),
+                None => claimable.push(bid.id),
+            }
+        }
+        (claimable, skipped)
+    }
+
+    /// The block an ATM exit requested at `block` has to wait for, if any.
+    fn atm_exit_deferred(&self, block: BlockNumber) -> Option<BlockNumber> {
+        match self.atm {
+            AtmSettlement::ExitImmediately => None,
+            AtmSettlement::WaitForCheckpoints { blocks } => {
+                let config = self.client.config();
+                let until = config
+                    .end_block
+                    .as_u64()
+                    .saturating_add(blocks)
+                    .min(config.claim_block.as_u64());
+                (block.as_u64() < until).then_some(BlockNumber::new(until))
+            }
+        }
     }
 
     fn is_past_end(&self, block: BlockNumber) -> bool {
         block >= self.client.config().end_block
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{Address, U256};
+
+    use super::*;
+    use crate::{
+        executor::mock::MockAuction,
+        types::{primitives::Price, state::GraduationStatus},
+    };
+
+    #[tokio::test]
+    async fn bids_over_the_risk_limit_never_reach_the_client() {
+        let amount = |value: u64| CurrencyAmount::new(U256::from(value));
+        let mut executor = IntentExecutor::new(MockAuction::new())
+            .with_risk_limits(RiskLimits::new().with_max_bid_amount(amount(100)));
+        let block = BlockNumber::new(10);
+
+        let outcome = executor
+            .execute(
+                Intent::SubmitBid {
+                    max_price: Price::new(U256::from(50)),
+                    amount: amount(150),
+                    owner: None,
+                    from: None,
+                },
+                block,
+            )
+            .await;
+        assert!(matches!(
+            outcome,
+            IntentOutcome::Failed {
+                error: Error::Validation(ValidationError::RiskLimitExceeded(_)),
+                ..
+            }
+        ));
+
+        let outcome = executor
+            .execute(
+                Intent::SubmitBid {
+                    max_price: Price::new(U256::from(50)),
+                    amount: amount(100),
+                    owner: None,
+                    from: None,
+                },
+                block,
+            )
+            .await;
+        assert!(matches!(
+            outcome,
+            IntentOutcome::Success(IntentResult::BidSubmitted(_))
+        ));
+        assert_eq!(
+            *executor.client().submitted.lock().unwrap(),
+            [(Price::new(U256::from(50)), amount(100))]
+        );
+        assert_eq!(executor.risk().spent(), amount(100));
+    }
+
+    #[tokio::test]
+    async fn pooled_bids_rotate_through_wallets_with_budget() {
+        let amount = |value: u64| CurrencyAmount::new(U256::from(value));
+        let (a, b) = (Address::repeat_byte(0xa), Address::repeat_byte(0xb));
+        let pool = WalletPool::new()
+            .with_wallet(a, Some(amount(100)))
+            .with_wallet(b, Some(amount(100)));
+        let mut executor = IntentExecutor::new(MockAuction::new()).with_wallet_pool(pool);
+        let bid = |value: u64| Intent::SubmitBid {
+            max_price: Price::new(U256::from(50)),
+            amount: amount(value),
+            owner: None,
+            from: None,
+        };
+
+        for value in [60, 60] {
+            let outcome = executor.execute(bid(value), BlockNumber::new(10)).await;
+            assert!(matches!(
+                outcome,
+                IntentOutcome::Success(IntentResult::BidSubmitted(ref result))
+                    if result.beneficiary.is_none()
+            ));
+        }
+        let spent: Vec<_> = executor
+            .wallet_pool()
+            .unwrap()
+            .wallets()
+            .iter()
+            .map(|wallet| (wallet.address, wallet.spent))
+            .collect();
+        assert_eq!(spent, [(a, amount(60)), (b, amount(60))]);
+
+        let outcome = executor.execute(bid(60), BlockNumber::new(10)).await;
+        assert!(matches!(
+            outcome,
+            IntentOutcome::Failed {
+                error: Error::Validation(ValidationError::WalletBudgetExhausted { .. }),
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn intents_past_their_deadline_expire_unsent() {
+        let mut executor = IntentExecutor::new(MockAuction::new());
+        let bid = Intent::SubmitBid {
+            max_price: Price::new(U256::from(50)),
+            amount: CurrencyAmount::new(U256::from(100)),
+            owner: None,
+            from: None,
+        };
+
+        let outcome = executor
+            .execute(bid.clone().by(BlockNumber::new(9)), BlockNumber::new(10))
+            .await;
+        assert!(matches!(
+            outcome,
+            IntentOutcome::Expired { execute_by, .. } if execute_by == BlockNumber::new(9)
+        ));
+        assert!(executor.client().submitted.lock().unwrap().is_empty());
+
+        let outcome = executor
+            .execute(bid.by(BlockNumber::new(10)), BlockNumber::new(10))
+            .await;
+        assert!(matches!(
+            outcome,
+            IntentOutcome::Success(IntentResult::BidSubmitted(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn repeated_failing_intents_are_suppressed_for_the_cooldown() {
+        let amount = |value: u64| CurrencyAmount::new(U256::from(value));
+        let mut executor = IntentExecutor::new(MockAuction::new())
+            .with_risk_limits(RiskLimits::new().with_max_bid_amount(amount(100)))
+            .with_failure_cooldown(5);
+        let bid = Intent::SubmitBid {
+            max_price: Price::new(U256::from(50)),
+            amount: amount(150),
+            owner: None,
+            from: None,
+        };
+
+        let outcome = executor.execute(bid.clone(), BlockNumber::new(10)).await;
+        assert!(matches!(outcome, IntentOutcome::Failed { .. }));
+        // A deadline does not make it a different intent.
+        let outcome = executor
+            .execute(bid.clone().by(BlockNumber::new(20)), BlockNumber::new(11))
+            .await;
+        assert!(matches!(
+            outcome,
+            IntentOutcome::Suppressed { until, .. } if until == BlockNumber::new(15)
+        ));
+        let outcome = executor.execute(bid, BlockNumber::new(15)).await;
+        assert!(matches!(outcome, IntentOutcome::Failed { .. }));
+    }
+
+    #[tokio::test]
+    async fn verified_submissions_must_read_back() {
+        let bid = Intent::SubmitBid {
+            max_price: Price::new(U256::from(50)),
+            amount: CurrencyAmount::new(U256::from(100)),
+            owner: None,
+            from: None,
+        };
+        let mut executor = IntentExecutor::new(MockAuction::new()).with_verify_after_execute(true);
+        let outcome = executor.execute(bid.clone(), BlockNumber::new(10)).await;
+        assert!(matches!(
+            outcome,
+            IntentOutcome::Success(IntentResult::BidSubmitted(_))
+        ));
+
+        let reorged = MockAuction {
+            reorged: true,
+            ..MockAuction::new()
+        };
+        let mut executor = IntentExecutor::new(reorged).with_verify_after_execute(true);
+        let outcome = executor.execute(bid, BlockNumber::new(10)).await;
+        assert!(matches!(
+            outcome,
+            IntentOutcome::Failed {
+                error: Error::Transaction(TransactionError::Unverified { .. }),
+                ..
+            }
+        ));
+        // It had a receipt, so it counts as spent.
+        assert_eq!(
+            executor.risk().spent(),
+            CurrencyAmount::new(U256::from(100))
+        );
+    }
+
+    #[tokio::test]
+    async fn claims_that_landed_are_reported_with_the_owners_that_failed() {
+        let (a, b) = (Address::repeat_byte(0xa), Address::repeat_byte(0xb));
+        let auction = MockAuction::new();
+        let tokens = |value: u64| TokenAmount::new(U256::from(value));
+        for (id, owner, filled) in [(1, a, 10), (2, b, 20), (3, a, 5)] {
+            let id = BidId::new(U256::from(id));
+            auction.submitted.lock().unwrap().push((
+                auction.config.floor_price,
+                CurrencyAmount::new(U256::from(100)),
+            ));
+            auction.exited.lock().unwrap().push(id);
+            auction
+                .settled
+                .lock()
+                .unwrap()
+                .push((id, owner, tokens(filled)));
+        }
+        *auction.graduation.lock().unwrap() = GraduationStatus::Graduated;
+        auction.refused.lock().unwrap().push(b);
+        auction.checkpoint_at(110, auction.config.floor_price);
+        let pool = WalletPool::new().with_wallet(a, None).with_wallet(b, None);
+        let mut executor = IntentExecutor::new(auction).with_wallet_pool(pool);
+        let block = BlockNumber::new(110);
+        executor.refresh(block).await.unwrap();
+        let ids = |ids: &[u64]| ids.iter().map(|id| BidId::new(U256::from(*id))).collect();
+
+        let outcome = executor
+            .execute(
+                Intent::Claim {
+                    bid_ids: ids(&[1, 2, 3]),
+                },
+                block,
+            )
+            .await;
+        let IntentOutcome::Success(IntentResult::TokensClaimed(result)) = outcome else {
+            panic!("expected a partial claim, got {outcome:?}");
+        };
+        assert_eq!(result.bid_ids, ids(&[1, 3]));
+        assert_eq!(result.total_tokens, tokens(15));
+        assert_eq!(result.tx_hashes, [result.tx_hash]);
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.failed[0].owner, b);
+        assert_eq!(result.failed[0].bid_ids, ids(&[2]));
+        assert_eq!(*executor.client().claimed.lock().unwrap(), ids(&[1, 3]));
+
+        let outcome = executor
+            .execute(Intent::Claim { bid_ids: ids(&[2]) }, block)
+            .await;
+        assert!(matches!(
+            outcome,
+            IntentOutcome::Failed {
+                error: Error::Transaction(TransactionError::Reverted { .. }),
+                ..
+            }
+        ));
+    }
+}
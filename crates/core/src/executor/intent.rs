@@ -1,10 +1,20 @@
-use crate::types::primitives::{BidId, CurrencyAmount, Price};
+use alloy::primitives::Address;
 
-#[derive(Clone, Debug)]
+use crate::types::primitives::{BidId, BlockNumber, CurrencyAmount, Price};
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Intent {
     SubmitBid {
         max_price: Price,
         amount: CurrencyAmount,
+        /// Who the bid is placed for; `None` is the signing account. Another
+        /// owner gets the refund and tokens while the signer pays, and the
+        /// bid is left for that owner to settle.
+        owner: Option<Address>,
+        /// The [`WalletPool`](crate::WalletPool) wallet to pay with; `None`
+        /// lets the pool choose.
+        from: Option<Address>,
     },
     Exit {
         bid_id: BidId,
@@ -12,4 +22,51 @@ pub enum Intent {
     Claim {
         bid_ids: Vec<BidId>,
     },
+    /// Call `checkpoint()` to advance the auction to the current block.
+    Checkpoint,
+    /// `intent` is only worth sending up to `execute_by`. Within a block the
+    /// orchestrator runs the earliest deadlines first, and past it the
+    /// executor drops the intent as [`IntentOutcome::Expired`](super::IntentOutcome::Expired).
+    /// Built with [`Intent::by`].
+    Deadline {
+        intent: Box<Intent>,
+        execute_by: BlockNumber,
+    },
+}
+
+impl Intent {
+    /// Short name used in logs.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Intent::SubmitBid { .. } => "submit_bid",
+            Intent::Exit { .. } => "exit",
+            Intent::Claim { .. } => "claim",
+            Intent::Checkpoint => "checkpoint",
+            Intent::Deadline { intent, .. } => intent.kind(),
+        }
+    }
+
+    /// Must be executed by `block`; replaces any earlier deadline.
+    pub fn by(self, block: BlockNumber) -> Intent {
+        Intent::Deadline {
+            intent: Box::new(self.into_inner()),
+            execute_by: block,
+        }
+    }
+
+    /// Last block the intent may be executed at, if it has a deadline.
+    pub fn execute_by(&self) -> Option<BlockNumber> {
+        match self {
+            Intent::Deadline { execute_by, .. } => Some(*execute_by),
+            _ => None,
+        }
+    }
+
+    /// The intent without its deadline.
+    pub fn into_inner(self) -> Intent {
+        match self {
+            Intent::Deadline { intent, .. } => intent.into_inner(),
+            intent => intent,
+        }
+    }
 }
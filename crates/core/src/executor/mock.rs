@@ -0,0 +1,288 @@
+//! An in-memory [`AuctionApi`] for unit tests of the executor and the
+//! orchestrator.
+
+use std::sync::{Arc, Mutex};
+
+use alloy::primitives::{Address, B256, Bytes, U256, aliases::U24};
+use async_trait::async_trait;
+
+use crate::{
+    api::AuctionApi,
+    error::{Error, StateError, TransactionError},
+    hooks::{NoopHook, ValidationHook},
+    types::{
+        action::{
+            CheckpointResult, ClaimParams, ClaimResult, ExitBidParams, ExitPartiallyFilledParams,
+            ExitResult, SubmitBidInput, SubmitBidParams, SubmitBidResult, TickHint,
+        },
+        bid::{Bid, TrackedBid},
+        checkpoint::Checkpoint,
+        config::{AuctionConfig, fixture},
+        primitives::{BidId, BlockNumber, CurrencyAmount, Mps, Price, TokenAmount},
+        state::{
+            AccountBalances, AuctionMetrics, AuctionState, GraduationStatus, TokenDepositStatus,
+        },
+    },
+};
+
+/// An active auction that records submitted bids, exits, claims and pokes
+/// instead of sending them. Reads answer from the fields, which tests change
+/// between blocks.
+pub(crate) struct MockAuction {
+    pub(crate) config: AuctionConfig,
+    pub(crate) hook: Arc<dyn ValidationHook>,
+    /// The latest checkpoint on chain.
+    pub(crate) checkpoint: Mutex<Checkpoint>,
+    pub(crate) graduation: Mutex<GraduationStatus>,
+    pub(crate) metrics: Mutex<AuctionMetrics>,
+    /// Submitted bids by id, from id 1; all of them are tracked.
+    pub(crate) submitted: Mutex<Vec<(Price, CurrencyAmount)>>,
+    pub(crate) exited: Mutex<Vec<BidId>>,
+    pub(crate) claimed: Mutex<Vec<BidId>>,
+    /// Owner and tokens filled of settled bids; others are owned by
+    /// [`AuctionApi::owner`] and filled nothing.
+    pub(crate) settled: Mutex<Vec<(BidId, Address, TokenAmount)>>,
+    /// Owners whose claims revert.
+    pub(crate) refused: Mutex<Vec<Address>>,
+    pub(crate) pokes: Mutex<u32>,
+    /// Answers reads as if every submission was reorged out after its
+    /// receipt arrived.
+    pub(crate) reorged: bool,
+}
+
+impl MockAuction {
+    pub(crate) fn new() -> Self {
+        let config = fixture::config();
+        Self {
+            checkpoint: Mutex::new(Checkpoint {
+                block: BlockNumber::new(10),
+                clearing_price: config.floor_price,
+                cumulative_mps: Mps::new(U24::ZERO),
+                cumulative_mps_per_price: U256::ZERO,
+                prev_block: BlockNumber::new(0),
+                next_block: BlockNumber::TAIL_SENTINEL,
+            }),
+            config,
+            hook: Arc::new(NoopHook),
+            graduation: Mutex::new(GraduationStatus::NotGraduated),
+            metrics: Mutex::new(AuctionMetrics::default()),
+            submitted: Mutex::new(Vec::new()),
+            exited: Mutex::new(Vec::new()),
+            claimed: Mutex::new(Vec::new()),
+            settled: Mutex::new(Vec::new()),
+            refused: Mutex::new(Vec::new()),
+            pokes: Mutex::new(0),
+            reorged: false,
+        }
+    }
+
+    /// Moves the latest checkpoint to `block` at `clearing_price`, as if
+    /// someone else had checkpointed the auction.
+    pub(crate) fn checkpoint_at(&self, block: u64, clearing_price: Price) {
+        let mut checkpoint = self.checkpoint.lock().unwrap();
+        checkpoint.prev_block = checkpoint.block;
+        checkpoint.block = BlockNumber::new(block);
+        checkpoint.clearing_price = clearing_price;
+    }
+
+    fn exit(&self, bid_id: BidId) -> Result<ExitResult, Error> {
+        let amount = self.fetch_bid(bid_id)?.amount;
+        self.exited.lock().unwrap().push(bid_id);
+        Ok(ExitResult {
+            bid_id,
+            tokens_filled: TokenAmount::ZERO,
+            currency_refunded: amount,
+            tx_hash: B256::ZERO,
+            gas_used: 21_000,
+            effective_gas_price: 1,
+        })
+    }
+
+    fn fetch_bid(&self, id: BidId) -> Result<Bid, Error> {
+        let submitted = self.submitted.lock().unwrap();
+        let index = id.as_u256().to::<usize>() - 1;
+        let (owner, tokens_filled) = self
+            .settled
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(settled, ..)| *settled == id)
+            .map_or((self.owner(), TokenAmount::ZERO), |&(_, owner, filled)| {
+                (owner, filled)
+            });
+        match submitted.get(index) {
+            Some(&(max_price, amount)) if !self.reorged => Ok(Bid {
+                id,
+                owner,
+                max_price,
+                amount,
+                start_block: BlockNumber::new(10),
+                start_cumulative_mps: Mps::new(U24::ZERO),
+                exited_block: self
+                    .exited
+                    .lock()
+                    .unwrap()
+                    .contains(&id)
+                    .then_some(self.checkpoint.lock().unwrap().block),
+                tokens_filled,
+            }),
+            _ => Err(StateError::BidNotFound.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl AuctionApi for MockAuction {
+    fn config(&self) -> &AuctionConfig {
+        &self.config
+    }
+
+    fn address(&self) -> Address {
+        Address::ZERO
+    }
+
+    fn owner(&self) -> Address {
+        Address::repeat_byte(1)
+    }
+
+    fn tracked_bids(&self) -> Vec<TrackedBid> {
+        (1..=self.submitted.lock().unwrap().len())
+            .map(|id| TrackedBid {
+                id: BidId::new(U256::from(id)),
+                tx_hash: B256::ZERO,
+                tags: Vec::new(),
+            })
+            .collect()
+    }
+
+    fn hook(&self) -> &Arc<dyn ValidationHook> {
+        &self.hook
+    }
+
+    async fn fetch_block_number(&self) -> Result<BlockNumber, Error> {
+        Ok(self.checkpoint.lock().unwrap().block)
+    }
+
+    async fn fetch_checkpoint(&self) -> Result<Checkpoint, Error> {
+        Ok(*self.checkpoint.lock().unwrap())
+    }
+
+    async fn fetch_graduation(&self) -> Result<GraduationStatus, Error> {
+        Ok(*self.graduation.lock().unwrap())
+    }
+
+    async fn fetch_token_balance(&self) -> Result<TokenDepositStatus, Error> {
+        Ok(TokenDepositStatus::Received)
+    }
+
+    async fn fetch_auction_metrics(&self) -> Result<AuctionMetrics, Error> {
+        Ok(*self.metrics.lock().unwrap())
+    }
+
+    async fn fetch_bids(&self, bid_ids: &[BidId]) -> Result<Vec<Bid>, Error> {
+        bid_ids.iter().map(|id| self.fetch_bid(*id)).collect()
+    }
+
+    async fn fetch_nonce(&self, _account: Address) -> Result<u64, Error> {
+        Ok(0)
+    }
+
+    async fn fetch_balances(&self, _account: Address) -> Result<AccountBalances, Error> {
+        Ok(AccountBalances {
+            native: U256::MAX,
+            currency: CurrencyAmount::new(U256::MAX),
+        })
+    }
+
+    async fn fetch_gas_price(&self) -> Result<u128, Error> {
+        Ok(1)
+    }
+
+    async fn prepare_bid(
+        &self,
+        input: SubmitBidInput,
+        _state: &AuctionState,
+    ) -> Result<SubmitBidParams, Error> {
+        Ok(SubmitBidParams {
+            max_price: input.max_price,
+            amount: input.amount,
+            owner: input.owner,
+            from: self.owner(),
+            nonce: None,
+            hint: TickHint::PrevTick(self.config.floor_price),
+            hook_data: Bytes::new(),
+            value: CurrencyAmount::new(U256::ZERO),
+            slippage: None,
+            tags: Vec::new(),
+        })
+    }
+
+    async fn submit_bid(&self, params: SubmitBidParams) -> Result<SubmitBidResult, Error> {
+        let mut submitted = self.submitted.lock().unwrap();
+        submitted.push((params.max_price, params.amount));
+        Ok(SubmitBidResult {
+            bid_id: BidId::new(U256::from(submitted.len())),
+            amount: params.amount,
+            beneficiary: (params.owner != params.from).then_some(params.owner),
+            tx_hash: B256::ZERO,
+            gas_used: 21_000,
+            effective_gas_price: 1,
+            tags: params.tags,
+        })
+    }
+
+    async fn exit_bid(&self, params: ExitBidParams) -> Result<ExitResult, Error> {
+        self.exit(params.bid_id)
+    }
+
+    async fn prepare_exit_partially_filled(
+        &self,
+        bid_id: BidId,
+    ) -> Result<ExitPartiallyFilledParams, Error> {
+        let checkpoint = self.checkpoint.lock().unwrap();
+        Ok(ExitPartiallyFilledParams {
+            bid_id,
+            last_fully_filled_checkpoint_block: checkpoint.prev_block,
+            outbid_block: Some(checkpoint.block),
+        })
+    }
+
+    async fn exit_partially_filled(
+        &self,
+        params: ExitPartiallyFilledParams,
+    ) -> Result<ExitResult, Error> {
+        self.exit(params.bid_id)
+    }
+
+    async fn claim(&self, params: ClaimParams) -> Result<ClaimResult, Error> {
+        let bids = self.fetch_bids(&params.bid_ids).await?;
+        let mut claimed = self.claimed.lock().unwrap();
+        let tx_hash = B256::with_last_byte(claimed.len() as u8 + 1);
+        if self.refused.lock().unwrap().contains(&params.owner) {
+            return Err(TransactionError::Reverted { tx_hash }.into());
+        }
+        claimed.extend(&params.bid_ids);
+        Ok(ClaimResult {
+            bid_ids: params.bid_ids,
+            total_tokens: bids
+                .iter()
+                .fold(TokenAmount::ZERO, |total, bid| total + bid.tokens_filled),
+            tx_hash,
+            tx_hashes: vec![tx_hash],
+            gas_used: 21_000,
+            effective_gas_price: 1,
+            skipped: Vec::new(),
+            failed: Vec::new(),
+        })
+    }
+
+    async fn poke_checkpoint(&self) -> Result<CheckpointResult, Error> {
+        *self.pokes.lock().unwrap() += 1;
+        Ok(CheckpointResult {
+            checkpoint: *self.checkpoint.lock().unwrap(),
+            tx_hash: B256::ZERO,
+            gas_used: 21_000,
+            effective_gas_price: 1,
+        })
+    }
+}
@@ -2,6 +2,8 @@ pub mod cache;
 pub mod context;
 pub mod core;
 pub mod intent;
+#[cfg(test)]
+pub(crate) mod mock;
 pub mod outcome;
 
 pub use cache::ExecutorCache;
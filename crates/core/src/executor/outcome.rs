@@ -1,6 +1,12 @@
+use alloy::primitives::{B256, U256};
+
 use crate::{
     error::Error,
-    types::action::{ClaimResult, ExitResult, SubmitBidResult},
+    types::{
+        action::{CheckpointResult, ClaimResult, ExitResult, SubmitBidResult},
+        dust::DustSkip,
+        primitives::BlockNumber,
+    },
 };
 
 use super::Intent;
@@ -8,12 +14,113 @@ use super::Intent;
 #[derive(Debug)]
 pub enum IntentOutcome {
     Success(IntentResult),
-    Failed { intent: Intent, error: Error },
+    Failed {
+        intent: Intent,
+        error: Error,
+    },
+    /// Nothing was sent because every bid in the intent was below the dust threshold.
+    Skipped {
+        intent: Intent,
+        skipped: Vec<DustSkip>,
+    },
+    /// Held back by the executor; it is handed back from
+    /// [`IntentExecutor::due_intents`](super::IntentExecutor::due_intents)
+    /// once `until` is reached.
+    Deferred {
+        intent: Intent,
+        until: BlockNumber,
+    },
+    /// Not executed because the block was already past its deadline.
+    Expired {
+        intent: Intent,
+        execute_by: BlockNumber,
+    },
+    /// Not executed because the same intent failed recently; see
+    /// [`IntentExecutor::with_failure_cooldown`](super::IntentExecutor::with_failure_cooldown).
+    /// `cause` is the earlier failure, and the intent is tried again from
+    /// block `until` on.
+    Suppressed {
+        intent: Intent,
+        cause: String,
+        until: BlockNumber,
+    },
+}
+
+impl IntentOutcome {
+    /// Short name of the outcome, as used in metrics and activity logs.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Success(_) => "success",
+            Self::Failed { .. } => "failed",
+            Self::Skipped { .. } => "skipped",
+            Self::Deferred { .. } => "deferred",
+            Self::Expired { .. } => "expired",
+            Self::Suppressed { .. } => "suppressed",
+        }
+    }
+
+    /// The [`Intent::kind`] this is the outcome of.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Success(result) => result.kind(),
+            Self::Failed { intent, .. }
+            | Self::Skipped { intent, .. }
+            | Self::Deferred { intent, .. }
+            | Self::Expired { intent, .. }
+            | Self::Suppressed { intent, .. } => intent.kind(),
+        }
+    }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum IntentResult {
     BidSubmitted(SubmitBidResult),
     BidExited(ExitResult),
     TokensClaimed(ClaimResult),
+    CheckpointUpdated(CheckpointResult),
+}
+
+impl IntentResult {
+    /// The [`Intent::kind`] that produced this.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::BidSubmitted(_) => "submit_bid",
+            Self::BidExited(_) => "exit",
+            Self::TokensClaimed(_) => "claim",
+            Self::CheckpointUpdated(_) => "checkpoint",
+        }
+    }
+
+    pub fn tx_hash(&self) -> B256 {
+        match self {
+            Self::BidSubmitted(result) => result.tx_hash,
+            Self::BidExited(result) => result.tx_hash,
+            Self::TokensClaimed(result) => result.tx_hash,
+            Self::CheckpointUpdated(result) => result.tx_hash,
+        }
+    }
+
+    pub fn gas_used(&self) -> u64 {
+        match self {
+            Self::BidSubmitted(result) => result.gas_used,
+            Self::BidExited(result) => result.gas_used,
+            Self::TokensClaimed(result) => result.gas_used,
+            Self::CheckpointUpdated(result) => result.gas_used,
+        }
+    }
+
+    pub fn effective_gas_price(&self) -> u128 {
+        match self {
+            Self::BidSubmitted(result) => result.effective_gas_price,
+            Self::BidExited(result) => result.effective_gas_price,
+            Self::TokensClaimed(result) => result.effective_gas_price,
+            Self::CheckpointUpdated(result) => result.effective_gas_price,
+        }
+    }
+
+    /// Wei paid in gas for the transaction.
+    pub fn gas_fee(&self) -> U256 {
+        U256::from(self.gas_used()) * U256::from(self.effective_gas_price())
+    }
 }
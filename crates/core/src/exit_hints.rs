@@ -0,0 +1,194 @@
+use alloy::{primitives::Address, providers::Provider};
+use async_trait::async_trait;
+use flux_abi::IContinuousClearingAuction;
+
+use crate::{
+    error::{Error, StateError},
+    types::{
+        action::ExitHints,
+        bid::Bid,
+        primitives::{BlockNumber, Price},
+    },
+};
+
+/// One checkpoint of an auction's history, as far as exit hints need it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CheckpointLink {
+    pub clearing_price: Price,
+    /// Block of the following checkpoint; the history's
+    /// [`tail`](CheckpointHistory::tail) after the latest one.
+    pub next: BlockNumber,
+}
+
+/// The auction's checkpoints, a list linked from each checkpoint to the
+/// next, that [`compute_exit_hints`] walks.
+#[async_trait]
+pub trait CheckpointHistory: Sync {
+    /// What the latest checkpoint links to as `next`.
+    async fn tail(&self) -> Result<BlockNumber, Error>;
+
+    /// The checkpoint taken at `block`.
+    async fn checkpoint(&self, block: BlockNumber) -> Result<CheckpointLink, Error>;
+}
+
+/// Checkpoints read from the auction contract, one call per checkpoint.
+#[derive(Clone, Debug)]
+pub struct ChainCheckpoints<P> {
+    auction: Address,
+    provider: P,
+}
+
+impl<P> ChainCheckpoints<P>
+where
+    P: Provider,
+{
+    pub fn new(auction: Address, provider: P) -> Self {
+        Self { auction, provider }
+    }
+}
+
+#[async_trait]
+impl<P> CheckpointHistory for ChainCheckpoints<P>
+where
+    P: Provider,
+{
+    async fn tail(&self) -> Result<BlockNumber, Error> {
+        let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
+        let tail = cca
+            .MAX_BLOCK_NUMBER()
+            .call()
+            .await
+            .map_err(StateError::from)?;
+        Ok(BlockNumber::new(tail))
+    }
+
+    async fn checkpoint(&self, block: BlockNumber) -> Result<CheckpointLink, Error> {
+        let cca = IContinuousClearingAuction::new(self.auction, &self.provider);
+        let checkpoint = cca
+            .checkpoints(block.as_u64())
+            .call()
+            .await
+            .map_err(StateError::from)?;
+        Ok(CheckpointLink {
+            clearing_price: Price::new(checkpoint.clearingPrice),
+            next: BlockNumber::new(checkpoint.next),
+        })
+    }
+}
+
+/// The hints `exitPartiallyFilledBid` needs for `bid`: the last checkpoint
+/// whose clearing price was still below the bid's max price, and the first
+/// one after it that went above, if any. A clearing price equal to the max
+/// price ends the fully filled stretch without outbidding the bid.
+pub async fn compute_exit_hints<H>(history: &H, bid: &Bid) -> Result<ExitHints, Error>
+where
+    H: CheckpointHistory + ?Sized,
+{
+    let tail = history.tail().await?;
+
+    let mut last_fully_filled = bid.start_block;
+    let mut current = history.checkpoint(bid.start_block).await?;
+
+    while current.next != tail {
+        let next_block = current.next;
+        let next = history.checkpoint(next_block).await?;
+        if next.clearing_price >= bid.max_price {
+            break;
+        }
+        last_fully_filled = next_block;
+        current = next;
+    }
+
+    let mut outbid_block = None;
+    while current.next != tail {
+        let next_block = current.next;
+        let next = history.checkpoint(next_block).await?;
+        if next.clearing_price > bid.max_price {
+            outbid_block = Some(next_block);
+            break;
+        }
+        current = next;
+    }
+
+    Ok(ExitHints {
+        last_fully_filled_checkpoint_block: last_fully_filled,
+        outbid_block,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use alloy::primitives::{U256, aliases::U24};
+
+    use super::*;
+    use crate::types::primitives::{BidId, CurrencyAmount, Mps, TokenAmount};
+
+    const TAIL: u64 = u64::MAX;
+
+    /// Checkpoints at the given blocks and clearing prices, linked in order.
+    struct Synthetic(BTreeMap<BlockNumber, CheckpointLink>);
+
+    impl Synthetic {
+        fn new(checkpoints: &[(u64, u64)]) -> Self {
+            let links = checkpoints
+                .iter()
+                .enumerate()
+                .map(|(index, &(block, price))| {
+                    let next = checkpoints.get(index + 1).map_or(TAIL, |&(next, _)| next);
+                    let link = CheckpointLink {
+                        clearing_price: Price::new(U256::from(price)),
+                        next: BlockNumber::new(next),
+                    };
+                    (BlockNumber::new(block), link)
+                })
+                .collect();
+            Self(links)
+        }
+    }
+
+    #[async_trait]
+    impl CheckpointHistory for Synthetic {
+        async fn tail(&self) -> Result<BlockNumber, Error> {
+            Ok(BlockNumber::new(TAIL))
+        }
+
+        async fn checkpoint(&self, block: BlockNumber) -> Result<CheckpointLink, Error> {
+            Ok(self.0[&block])
+        }
+    }
+
+    fn bid(start_block: u64, max_price: u64) -> Bid {
+        Bid {
+            id: BidId::new(U256::ZERO),
+            owner: Address::ZERO,
+            max_price: Price::new(U256::from(max_price)),
+            amount: CurrencyAmount::new(U256::from(1_000)),
+            start_block: BlockNumber::new(start_block),
+            start_cumulative_mps: Mps::new(U24::ZERO),
+            exited_block: None,
+            tokens_filled: TokenAmount::ZERO,
+        }
+    }
+
+    #[tokio::test]
+    async fn hints_bracket_the_clearing_price_crossing() {
+        let history = Synthetic::new(&[(10, 50), (20, 80), (30, 100), (40, 100), (50, 120)]);
+
+        let hints = compute_exit_hints(&history, &bid(10, 100)).await.unwrap();
+        assert_eq!(
+            hints.last_fully_filled_checkpoint_block,
+            BlockNumber::new(20)
+        );
+        assert_eq!(hints.outbid_block, Some(BlockNumber::new(50)));
+
+        // The clearing price never reaches 200: filled throughout, never outbid.
+        let hints = compute_exit_hints(&history, &bid(10, 200)).await.unwrap();
+        assert_eq!(
+            hints.last_fully_filled_checkpoint_block,
+            BlockNumber::new(50)
+        );
+        assert_eq!(hints.outbid_block, None);
+    }
+}
@@ -0,0 +1,319 @@
+//! Failover across several RPC endpoints of the same chain.
+//!
+//! [`FailoverLayer`] turns a list of transports into one. Requests go to the
+//! first healthy endpoint in the order given, so the first is the primary.
+//! An endpoint that fails or does not answer within the timeout is benched
+//! for a cooldown and the request moves on to the next one; benched
+//! endpoints are only tried once every healthy one has failed.
+//! [`FailoverService::monitor`] probes every endpoint in the background, so
+//! an outage is noticed and a recovered primary taken back into use without
+//! waiting for real traffic.
+//!
+//! JSON-RPC error responses, such as reverts, are answers rather than
+//! failures and are returned as they are. Resending a raw transaction to
+//! the next endpoint is safe: a signed transaction can only be included once.
+//!
+//! ```ignore
+//! let transports = urls.into_iter().map(Http::new).collect();
+//! let service = FailoverLayer::new().layer(transports);
+//! let client = ClientBuilder::default().transport(service.clone(), false);
+//! tokio::spawn(service.monitor(Duration::from_secs(15)));
+//! ```
+
+use std::{
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use alloy::{
+    rpc::json_rpc::{Id, Request, RequestPacket, ResponsePacket},
+    transports::{TransportError, TransportErrorKind, TransportFut},
+};
+use tower::{Layer, Service};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Copy, Debug)]
+pub struct FailoverLayer {
+    timeout: Duration,
+    cooldown: Duration,
+}
+
+impl FailoverLayer {
+    pub fn new() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+            cooldown: DEFAULT_COOLDOWN,
+        }
+    }
+
+    /// How long an endpoint may take to answer before the request fails
+    /// over.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// How long a failed endpoint is benched before it is preferred again.
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+}
+
+impl Default for FailoverLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<Vec<S>> for FailoverLayer {
+    type Service = FailoverService<S>;
+
+    fn layer(&self, transports: Vec<S>) -> Self::Service {
+        let endpoints = transports
+            .into_iter()
+            .map(|transport| Endpoint {
+                transport,
+                benched_until: Mutex::new(None),
+            })
+            .collect();
+        FailoverService {
+            endpoints: Arc::new(endpoints),
+            timeout: self.timeout,
+            cooldown: self.cooldown,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Endpoint<S> {
+    transport: S,
+    benched_until: Mutex<Option<Instant>>,
+}
+
+impl<S> Endpoint<S> {
+    fn is_healthy(&self, now: Instant) -> bool {
+        self.benched_until
+            .lock()
+            .expect("endpoint lock poisoned")
+            .is_none_or(|until| until <= now)
+    }
+
+    fn bench(&self, cooldown: Duration) {
+        *self.benched_until.lock().expect("endpoint lock poisoned") =
+            Some(Instant::now() + cooldown);
+    }
+
+    fn restore(&self) {
+        *self.benched_until.lock().expect("endpoint lock poisoned") = None;
+    }
+}
+
+impl<S> Endpoint<S>
+where
+    S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError> + Clone,
+    S::Future: Send + 'static,
+{
+    async fn send(
+        &self,
+        request: RequestPacket,
+        timeout: Duration,
+    ) -> Result<ResponsePacket, TransportError> {
+        let future = self.transport.clone().call(request);
+        tokio::time::timeout(timeout, future)
+            .await
+            .unwrap_or_else(|_| {
+                Err(TransportErrorKind::custom_str(
+                    "failover: request timed out",
+                ))
+            })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FailoverService<S> {
+    endpoints: Arc<Vec<Endpoint<S>>>,
+    timeout: Duration,
+    cooldown: Duration,
+}
+
+impl<S> FailoverService<S> {
+    /// Endpoints currently in use, i.e. not benched.
+    pub fn healthy(&self) -> usize {
+        let now = Instant::now();
+        self.endpoints
+            .iter()
+            .filter(|endpoint| endpoint.is_healthy(now))
+            .count()
+    }
+
+    /// Endpoint indices in the order a request tries them: healthy ones as
+    /// configured, then benched ones as a last resort.
+    fn order(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let (healthy, benched): (Vec<_>, Vec<_>) =
+            (0..self.endpoints.len()).partition(|&index| self.endpoints[index].is_healthy(now));
+        healthy.into_iter().chain(benched).collect()
+    }
+}
+
+impl<S> FailoverService<S>
+where
+    S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+    S::Future: Send + 'static,
+{
+    async fn request(self, request: RequestPacket) -> Result<ResponsePacket, TransportError> {
+        let mut last_error = None;
+        for index in self.order() {
+            let endpoint = &self.endpoints[index];
+            match endpoint.send(request.clone(), self.timeout).await {
+                Ok(response) => {
+                    endpoint.restore();
+                    return Ok(response);
+                }
+                Err(error) => {
+                    tracing::warn!(endpoint = index, %error, "rpc endpoint failed, failing over");
+                    endpoint.bench(self.cooldown);
+                    last_error = Some(error);
+                }
+            }
+        }
+        Err(last_error
+            .unwrap_or_else(|| TransportErrorKind::custom_str("failover: no rpc endpoints")))
+    }
+
+    /// Probes every endpoint with `eth_blockNumber`, benching the ones that
+    /// fail and restoring the ones that answer. Returns how many are healthy.
+    pub async fn check_health(&self) -> usize {
+        let probe = RequestPacket::Single(
+            Request::new("eth_blockNumber", Id::Number(0), ())
+                .try_into()
+                .expect("a request without params serializes"),
+        );
+        for (index, endpoint) in self.endpoints.iter().enumerate() {
+            match endpoint.send(probe.clone(), self.timeout).await {
+                Ok(response) if response.is_success() => {
+                    if !endpoint.is_healthy(Instant::now()) {
+                        tracing::info!(endpoint = index, "rpc endpoint recovered");
+                    }
+                    endpoint.restore();
+                }
+                Ok(_) => endpoint.bench(self.cooldown),
+                Err(error) => {
+                    tracing::warn!(endpoint = index, %error, "rpc endpoint failed health check");
+                    endpoint.bench(self.cooldown);
+                }
+            }
+        }
+        self.healthy()
+    }
+
+    /// Runs [`check_health`](Self::check_health) every `interval`, forever.
+    /// Spawn it next to a long-running orchestration.
+    pub async fn monitor(self, interval: Duration) {
+        let mut ticks = tokio::time::interval(interval);
+        loop {
+            ticks.tick().await;
+            if self.check_health().await == 0 {
+                tracing::error!("no rpc endpoint is healthy");
+            }
+        }
+    }
+}
+
+impl<S> Service<RequestPacket> for FailoverService<S>
+where
+    S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        Box::pin(self.clone().request(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use alloy::rpc::json_rpc::{Response, ResponsePayload};
+    use serde_json::value::RawValue;
+    use tower::service_fn;
+
+    use super::*;
+
+    type TestTransport =
+        tower::util::BoxCloneSyncService<RequestPacket, ResponsePacket, TransportError>;
+
+    fn request() -> RequestPacket {
+        RequestPacket::Single(
+            Request::new("eth_chainId", Id::Number(1), ())
+                .try_into()
+                .unwrap(),
+        )
+    }
+
+    /// A transport answering `result`, or failing while `down` is set.
+    fn endpoint(result: &'static str, calls: Arc<AtomicUsize>, down: bool) -> TestTransport {
+        TestTransport::new(service_fn(move |_request: RequestPacket| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            async move {
+                if down {
+                    return Err(TransportErrorKind::custom_str("connection refused"));
+                }
+                Ok(ResponsePacket::Single(Response {
+                    id: Id::Number(1),
+                    payload: ResponsePayload::Success(
+                        RawValue::from_string(result.into()).unwrap(),
+                    ),
+                }))
+            }
+        }))
+    }
+
+    fn result(packet: ResponsePacket) -> String {
+        match packet {
+            ResponsePacket::Single(Response {
+                payload: ResponsePayload::Success(result),
+                ..
+            }) => result.get().to_string(),
+            other => panic!("unexpected response {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn fails_over_and_benches_the_primary() {
+        let primary_calls = Arc::new(AtomicUsize::new(0));
+        let mut service = FailoverLayer::new().layer(vec![
+            endpoint(r#""0x1""#, primary_calls.clone(), true),
+            endpoint(r#""0x2""#, Arc::default(), false),
+        ]);
+
+        assert_eq!(result(service.call(request()).await.unwrap()), r#""0x2""#);
+        assert_eq!(service.healthy(), 1);
+        // Benched, so the next request goes straight to the backup.
+        service.call(request()).await.unwrap();
+        assert_eq!(primary_calls.load(Ordering::Relaxed), 1);
+
+        assert_eq!(service.check_health().await, 1);
+        assert_eq!(primary_calls.load(Ordering::Relaxed), 2);
+    }
+}
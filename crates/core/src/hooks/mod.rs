@@ -1,3 +1,5 @@
+pub mod noop;
 pub mod traits;
 
+pub use noop::NoopHook;
 pub use traits::ValidationHook;
@@ -0,0 +1,8 @@
+use super::ValidationHook;
+
+/// Hook for auctions without a validation hook: sends empty hook data and
+/// accepts every bid.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoopHook;
+
+impl ValidationHook for NoopHook {}
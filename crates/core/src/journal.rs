@@ -0,0 +1,268 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Mutex, MutexGuard},
+};
+
+use alloy::{
+    primitives::{Address, B256, U256, keccak256},
+    rpc::types::TransactionRequest,
+};
+
+/// Deterministic id of a transaction: what it does, from whom, and how many
+/// identical ones landed before it. A restarted process counts those from
+/// the journal it loads, so a transaction its predecessor sent but did not
+/// see land keeps its key, while a new one with the same calldata, such as
+/// another checkpoint poke, gets the next.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SendKey {
+    /// Hash of the sender, target, value and calldata.
+    pub digest: B256,
+    pub occurrence: u32,
+}
+
+impl SendKey {
+    /// Hash of what a transaction does, leaving out its nonce and gas.
+    pub fn digest(from: Address, to: Option<Address>, value: U256, input: &[u8]) -> B256 {
+        let mut bytes = Vec::with_capacity(72 + input.len());
+        bytes.extend_from_slice(from.as_slice());
+        bytes.extend_from_slice(to.unwrap_or_default().as_slice());
+        bytes.extend_from_slice(&value.to_be_bytes::<32>());
+        bytes.extend_from_slice(input);
+        keccak256(bytes)
+    }
+
+    fn of_request(tx: &TransactionRequest, from: Address) -> B256 {
+        Self::digest(
+            from,
+            tx.to.and_then(|kind| kind.to().copied()),
+            tx.value.unwrap_or_default(),
+            tx.input.input().map_or(&[][..], |input| &input[..]),
+        )
+    }
+}
+
+/// A transaction the journal was told about before it was sent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JournalEntry {
+    pub key: SendKey,
+    /// [`Intent::kind`](crate::Intent::kind)-style name of what was sent.
+    pub kind: String,
+    pub from: Address,
+    /// Nonce it was sent with; at most one transaction can land with it.
+    pub nonce: u64,
+    /// Head when it was sent, where a scan for its nonce starts.
+    pub block: u64,
+    /// Every hash broadcast for it, e.g. through a relay and then publicly.
+    pub tx_hashes: Vec<B256>,
+    /// Hash of the transaction that landed successfully, once known.
+    pub landed: Option<B256>,
+}
+
+/// Where a [`SendJournal`] saves its entries on every change, so they
+/// survive a crash between sending a transaction and seeing its receipt.
+///
+/// Saving must not fail the send, so implementations deal with their own
+/// errors.
+pub trait JournalStore: Send + Sync {
+    fn save(&self, entries: &[JournalEntry]);
+}
+
+#[derive(Default)]
+struct Journal {
+    entries: Vec<JournalEntry>,
+    /// Transactions per digest known to have landed, including those of
+    /// the entries loaded at start.
+    landed: HashMap<B256, u32>,
+    store: Option<Box<dyn JournalStore>>,
+}
+
+impl Journal {
+    fn changed(&self) {
+        if let Some(store) = &self.store {
+            store.save(&self.entries);
+        }
+    }
+
+    fn entry_mut(&mut self, key: SendKey) -> Option<&mut JournalEntry> {
+        self.entries.iter_mut().find(|entry| entry.key == key)
+    }
+}
+
+/// Transactions an [`AuctionClient`](crate::AuctionClient) is sending, each
+/// recorded under a [`SendKey`] with its nonce before it goes out.
+///
+/// Before sending, the client looks the key up: a transaction that already
+/// landed is not sent again and its receipt is returned instead, and one
+/// that may still be pending is resent with the same nonce, so only one of
+/// them can land.
+#[derive(Default)]
+pub struct SendJournal {
+    inner: Mutex<Journal>,
+}
+
+impl SendJournal {
+    /// A journal carrying on from `entries` saved by an earlier process.
+    pub fn new(entries: Vec<JournalEntry>) -> Self {
+        let mut landed = HashMap::<B256, u32>::new();
+        for entry in entries.iter().filter(|entry| entry.landed.is_some()) {
+            let occurrence = landed.entry(entry.key.digest).or_default();
+            *occurrence = (*occurrence).max(entry.key.occurrence + 1);
+        }
+        Self {
+            inner: Mutex::new(Journal {
+                entries,
+                landed,
+                store: None,
+            }),
+        }
+    }
+
+    /// Save the entries to `store` on every change.
+    pub fn with_store(self, store: impl JournalStore + 'static) -> Self {
+        self.lock().store = Some(Box::new(store));
+        self
+    }
+
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        self.lock().entries.clone()
+    }
+
+    /// Entries not known to have landed.
+    pub fn unsettled(&self) -> Vec<JournalEntry> {
+        self.lock()
+            .entries
+            .iter()
+            .filter(|entry| entry.landed.is_none())
+            .cloned()
+            .collect()
+    }
+
+    /// The key `tx` from `from` is sent under. It stays the same until a
+    /// transaction with it lands, so retries after a failure find the
+    /// earlier attempt.
+    pub fn key(&self, tx: &TransactionRequest, from: Address) -> SendKey {
+        let digest = SendKey::of_request(tx, from);
+        SendKey {
+            digest,
+            occurrence: self.lock().landed.get(&digest).copied().unwrap_or_default(),
+        }
+    }
+
+    pub fn get(&self, key: SendKey) -> Option<JournalEntry> {
+        self.lock()
+            .entries
+            .iter()
+            .find(|entry| entry.key == key)
+            .cloned()
+    }
+
+    /// Records that `key` is about to be sent with `nonce`. Hashes from
+    /// earlier attempts are kept while the nonce stays the same, since any
+    /// of them may still land.
+    pub fn begin(&self, key: SendKey, kind: &str, from: Address, nonce: u64, block: u64) {
+        let mut journal = self.lock();
+        match journal.entry_mut(key) {
+            Some(entry) => {
+                if entry.nonce != nonce {
+                    entry.tx_hashes.clear();
+                }
+                entry.nonce = nonce;
+                entry.block = block;
+            }
+            None => journal.entries.push(JournalEntry {
+                key,
+                kind: kind.to_string(),
+                from,
+                nonce,
+                block,
+                tx_hashes: Vec::new(),
+                landed: None,
+            }),
+        }
+        journal.changed();
+    }
+
+    /// Records that `tx_hash` was broadcast for `key`.
+    pub fn sent(&self, key: SendKey, tx_hash: B256) {
+        let mut journal = self.lock();
+        if let Some(entry) = journal.entry_mut(key) {
+            entry.tx_hashes.push(tx_hash);
+            journal.changed();
+        }
+    }
+
+    /// Records that `tx_hash` landed for `key`, moving later sends of the
+    /// same transaction on to the next key.
+    pub fn landed(&self, key: SendKey, tx_hash: B256) {
+        let mut journal = self.lock();
+        let occurrence = journal.landed.entry(key.digest).or_default();
+        *occurrence = (*occurrence).max(key.occurrence + 1);
+        if let Some(entry) = journal.entry_mut(key) {
+            entry.landed = Some(tx_hash);
+            journal.changed();
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Journal> {
+        self.inner.lock().expect("journal lock poisoned")
+    }
+}
+
+impl fmt::Debug for SendJournal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SendJournal")
+            .field("entries", &self.lock().entries.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::Bytes;
+
+    use super::*;
+
+    #[test]
+    fn keys_advance_on_landing_and_carry_over_restarts() {
+        let from = Address::repeat_byte(1);
+        let tx = TransactionRequest::default()
+            .to(Address::repeat_byte(2))
+            .input(Bytes::from_static(&[1, 2, 3]).into());
+
+        let journal = SendJournal::default();
+        let first = journal.key(&tx, from);
+        journal.begin(first, "checkpoint", from, 7, 100);
+        journal.sent(first, B256::repeat_byte(1));
+        // A retry before it is seen landing goes under the same key and
+        // keeps the first hash, as the nonce is unchanged.
+        assert_eq!(journal.key(&tx, from), first);
+        journal.begin(first, "checkpoint", from, 7, 101);
+        journal.landed(first, B256::repeat_byte(1));
+        let second = journal.key(&tx, from);
+        assert_eq!(second.occurrence, 1);
+        journal.begin(second, "checkpoint", from, 8, 102);
+        journal.sent(second, B256::repeat_byte(2));
+
+        // A new process sends the same calldata again under a fresh key
+        // rather than finding the landed one, and the send it never saw
+        // land keeps its key so it is not sent twice.
+        let restarted = SendJournal::new(journal.entries());
+        assert_eq!(restarted.key(&tx, from), second);
+        assert_eq!(
+            restarted.get(first).unwrap().landed,
+            Some(B256::repeat_byte(1))
+        );
+        let unsettled = restarted.unsettled();
+        assert_eq!(unsettled.len(), 1);
+        assert_eq!(unsettled[0].key, second);
+        assert_eq!(unsettled[0].tx_hashes, [B256::repeat_byte(2)]);
+
+        restarted.landed(second, B256::repeat_byte(2));
+        let third = restarted.key(&tx, from);
+        assert_eq!(third.occurrence, 2);
+        assert!(restarted.get(third).is_none());
+    }
+}
@@ -1,15 +1,62 @@
+#[cfg(feature = "account")]
+pub mod account;
+pub mod analytics;
+pub mod api;
+pub mod approval;
+pub mod attestation;
+pub mod backtest;
 pub mod blocks;
+#[cfg(feature = "chaos")]
+pub mod chaos;
+pub mod claim_batch;
 pub mod client;
+pub mod cold_storage;
+pub mod discovery;
 pub mod error;
+pub mod events;
 pub mod executor;
+pub mod exit_hints;
+#[cfg(feature = "failover")]
+pub mod failover;
 pub mod hooks;
+pub mod journal;
+pub mod metrics;
+pub mod orchestrator;
+pub mod relay;
+#[cfg(feature = "serde")]
+pub mod replay;
+pub mod report;
+pub mod shadow;
+pub mod sweeper;
 pub mod types;
 pub mod validation;
+pub mod watchlist;
 
+#[cfg(feature = "account")]
+pub use account::*;
+pub use analytics::*;
+pub use api::*;
+pub use approval::*;
+pub use attestation::*;
+pub use backtest::*;
 pub use blocks::*;
+pub use claim_batch::*;
 pub use client::*;
+pub use cold_storage::*;
+pub use discovery::*;
 pub use error::*;
+pub use events::*;
 pub use executor::*;
+pub use exit_hints::*;
 pub use hooks::*;
+pub use journal::*;
+pub use orchestrator::*;
+pub use relay::*;
+#[cfg(feature = "serde")]
+pub use replay::*;
+pub use report::*;
+pub use shadow::*;
+pub use sweeper::*;
 pub use types::*;
 pub use validation::*;
+pub use watchlist::*;
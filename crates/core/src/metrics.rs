@@ -0,0 +1,163 @@
+//! Process-wide Prometheus metrics.
+//!
+//! Recording functions are always available and compile to no-ops unless the
+//! `metrics` feature is enabled, so call sites don't need their own `cfg`s.
+
+use std::{future::IntoFuture, time::Duration};
+
+use crate::{executor::IntentOutcome, types::primitives::Price};
+
+#[cfg(feature = "metrics")]
+pub use exporter::{gather, serve};
+
+/// Counts a finished intent by kind and outcome.
+pub fn record_outcome(outcome: &IntentOutcome) {
+    #[cfg(feature = "metrics")]
+    exporter::record_outcome(outcome);
+    #[cfg(not(feature = "metrics"))]
+    let _ = outcome;
+}
+
+/// Sets the clearing price gauge from the latest checkpoint.
+pub fn set_clearing_price(price: Price) {
+    #[cfg(feature = "metrics")]
+    exporter::CLEARING_PRICE.set(price.to_f64());
+    #[cfg(not(feature = "metrics"))]
+    let _ = price;
+}
+
+/// Sets how many blocks the orchestrator is behind the chain head.
+pub fn set_blocks_behind(blocks: u64) {
+    #[cfg(feature = "metrics")]
+    exporter::BLOCKS_BEHIND.set(i64::try_from(blocks).unwrap_or(i64::MAX));
+    #[cfg(not(feature = "metrics"))]
+    let _ = blocks;
+}
+
+/// Records how long an RPC call took.
+pub fn observe_rpc(call: &'static str, elapsed: Duration) {
+    #[cfg(feature = "metrics")]
+    exporter::RPC_LATENCY
+        .with_label_values(&[call])
+        .observe(elapsed.as_secs_f64());
+    #[cfg(not(feature = "metrics"))]
+    let _ = (call, elapsed);
+}
+
+/// Awaits `fut`, recording its latency under `call`.
+pub(crate) async fn timed<F>(call: &'static str, fut: F) -> F::Output
+where
+    F: IntoFuture,
+{
+    let start = std::time::Instant::now();
+    let output = fut.await;
+    observe_rpc(call, start.elapsed());
+    output
+}
+
+#[cfg(feature = "metrics")]
+mod exporter {
+    use std::{net::SocketAddr, sync::LazyLock};
+
+    use prometheus::{
+        Encoder, Gauge, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, Registry,
+        TextEncoder,
+    };
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use crate::{error::MetricsError, executor::IntentOutcome};
+
+    static REGISTRY: LazyLock<Registry> = LazyLock::new(Registry::new);
+
+    static INTENTS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+        register(IntCounterVec::new(
+            Opts::new(
+                "flux_intents_total",
+                "Intents executed, by kind and outcome",
+            ),
+            &["kind", "outcome"],
+        ))
+    });
+
+    pub(super) static RPC_LATENCY: LazyLock<HistogramVec> = LazyLock::new(|| {
+        register(HistogramVec::new(
+            HistogramOpts::new("flux_rpc_duration_seconds", "RPC call latency")
+                .buckets(vec![0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 15.0, 60.0]),
+            &["call"],
+        ))
+    });
+
+    pub(super) static CLEARING_PRICE: LazyLock<Gauge> = LazyLock::new(|| {
+        register(Gauge::new(
+            "flux_clearing_price",
+            "Clearing price of the latest checkpoint (currency per token, raw units)",
+        ))
+    });
+
+    pub(super) static BLOCKS_BEHIND: LazyLock<IntGauge> = LazyLock::new(|| {
+        register(IntGauge::new(
+            "flux_blocks_behind_head",
+            "Blocks between the chain head and the block being processed",
+        ))
+    });
+
+    fn register<M>(metric: prometheus::Result<M>) -> M
+    where
+        M: prometheus::core::Collector + Clone + 'static,
+    {
+        let metric = metric.expect("metric definition is valid");
+        REGISTRY
+            .register(Box::new(metric.clone()))
+            .expect("metric is registered once");
+        metric
+    }
+
+    pub(super) fn record_outcome(outcome: &IntentOutcome) {
+        INTENTS
+            .with_label_values(&[outcome.kind(), outcome.label()])
+            .inc();
+    }
+
+    /// Renders every metric in the Prometheus text format.
+    pub fn gather() -> String {
+        // Touch the lazies so series show up before their first observation.
+        LazyLock::force(&INTENTS);
+        LazyLock::force(&RPC_LATENCY);
+        LazyLock::force(&CLEARING_PRICE);
+        LazyLock::force(&BLOCKS_BEHIND);
+
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&REGISTRY.gather(), &mut buffer)
+            .expect("text encoding into a Vec cannot fail");
+        String::from_utf8(buffer).expect("prometheus text format is UTF-8")
+    }
+
+    /// Serves `gather()` over plain HTTP on `addr` until the task is dropped.
+    ///
+    /// Every request gets the metrics page regardless of path; this is only
+    /// meant for a Prometheus scraper.
+    pub async fn serve(addr: SocketAddr) -> Result<(), MetricsError> {
+        let listener = TcpListener::bind(addr).await?;
+
+        loop {
+            let (mut stream, _) = listener.accept().await?;
+            tokio::spawn(async move {
+                let mut request = [0u8; 1024];
+                if stream.read(&mut request).await.is_err() {
+                    return;
+                }
+
+                let body = gather();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            });
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use alloy::primitives::{Address, B256, U256};
+
+use crate::{
+    executor::{Intent, IntentOutcome, IntentResult},
+    types::primitives::BlockNumber,
+};
+
+/// Where the orchestrator reports every intent it executes, for an audit
+/// trail of automated activity; see
+/// [`Orchestrator::with_activity_log`](super::Orchestrator::with_activity_log).
+///
+/// Recording must not fail the run, so implementations deal with their own
+/// errors, e.g. by logging them.
+pub trait ActivityLog: Send {
+    fn record(&mut self, record: ActivityRecord);
+}
+
+/// One executed intent and what came of it.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ActivityRecord {
+    /// Unix time in milliseconds when execution finished.
+    pub timestamp_ms: u64,
+    pub block: u64,
+    pub intent: ActivityIntent,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub execute_by: Option<u64>,
+    /// `success`, `failed`, `skipped`, `deferred` or `expired`.
+    pub outcome: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub tx_hash: Option<B256>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub gas_used: Option<u64>,
+    /// Wei paid in gas.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub gas_fee: Option<U256>,
+    /// The auction event the transaction was decoded from.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub event: Option<ActivityEvent>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub error: Option<String>,
+}
+
+/// The intent of an [`ActivityRecord`], without its deadline.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum ActivityIntent {
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    SubmitBid {
+        max_price: U256,
+        amount: U256,
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Option::is_none")
+        )]
+        owner: Option<Address>,
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Option::is_none")
+        )]
+        from: Option<Address>,
+    },
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    Exit {
+        bid_id: U256,
+    },
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    Claim {
+        bid_ids: Vec<U256>,
+    },
+    Checkpoint,
+}
+
+/// What a successful transaction's receipt said happened.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "name", rename_all = "PascalCase"))]
+pub enum ActivityEvent {
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    BidSubmitted {
+        bid_id: U256,
+        amount: U256,
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Vec::is_empty")
+        )]
+        tags: Vec<String>,
+    },
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    BidExited {
+        bid_id: U256,
+        tokens_filled: U256,
+        currency_refunded: U256,
+    },
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    TokensClaimed {
+        bid_ids: Vec<U256>,
+        total_tokens: U256,
+        /// One transaction per owner, when bids of several owners were
+        /// claimed.
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Vec::is_empty")
+        )]
+        tx_hashes: Vec<B256>,
+    },
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    CheckpointUpdated { clearing_price: U256 },
+}
+
+impl ActivityRecord {
+    /// The record of `intent` having been executed at `block` with `outcome`.
+    pub fn new(block: BlockNumber, intent: &Intent, outcome: &IntentOutcome) -> Self {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64);
+        let mut record = Self {
+            timestamp_ms,
+            block: block.as_u64(),
+            intent: ActivityIntent::from(intent),
+            execute_by: intent.execute_by().map(|block| block.as_u64()),
+            outcome: outcome.label().to_string(),
+            tx_hash: None,
+            gas_used: None,
+            gas_fee: None,
+            event: None,
+            error: None,
+        };
+        match outcome {
+            IntentOutcome::Success(result) => {
+                record.tx_hash = Some(result.tx_hash());
+                record.gas_used = Some(result.gas_used());
+                record.gas_fee = Some(result.gas_fee());
+                record.event = Some(ActivityEvent::from(result));
+                // A claim that landed for only some owners is a success
+                // with the rest's errors.
+                if let IntentResult::TokensClaimed(claimed) = result
+                    && !claimed.failed.is_empty()
+                {
+                    let errors: Vec<_> = claimed
+                        .failed
+                        .iter()
+                        .map(|failed| {
+                            format!("claim for {} failed: {}", failed.owner, failed.error)
+                        })
+                        .collect();
+                    record.error = Some(errors.join("; "));
+                }
+            }
+            IntentOutcome::Failed { error, .. } => record.error = Some(error.to_string()),
+            IntentOutcome::Suppressed { cause, .. } => record.error = Some(cause.clone()),
+            _ => {}
+        }
+        record
+    }
+}
+
+impl From<&Intent> for ActivityIntent {
+    fn from(intent: &Intent) -> Self {
+        match intent {
+            Intent::SubmitBid {
+                max_price,
+                amount,
+                owner,
+                from,
+            } => Self::SubmitBid {
+                max_price: max_price.as_u256(),
+                amount: amount.as_u256(),
+                owner: *owner,
+                from: *from,
+            },
+            Intent::Exit { bid_id } => Self::Exit {
+                bid_id: bid_id.as_u256(),
+            },
+            Intent::Claim { bid_ids } => Self::Claim {
+                bid_ids: bid_ids.iter().map(|id| id.as_u256()).collect(),
+            },
+            Intent::Checkpoint => Self::Checkpoint,
+            Intent::Deadline { intent, .. } => Self::from(intent.as_ref()),
+        }
+    }
+}
+
+impl From<&IntentResult> for ActivityEvent {
+    fn from(result: &IntentResult) -> Self {
+        match result {
+            IntentResult::BidSubmitted(submitted) => Self::BidSubmitted {
+                bid_id: submitted.bid_id.as_u256(),
+                amount: submitted.amount.as_u256(),
+                tags: submitted.tags.clone(),
+            },
+            IntentResult::BidExited(exited) => Self::BidExited {
+                bid_id: exited.bid_id.as_u256(),
+                tokens_filled: exited.tokens_filled.as_u256(),
+                currency_refunded: exited.currency_refunded.as_u256(),
+            },
+            IntentResult::TokensClaimed(claimed) => Self::TokensClaimed {
+                bid_ids: claimed.bid_ids.iter().map(|id| id.as_u256()).collect(),
+                total_tokens: claimed.total_tokens.as_u256(),
+                tx_hashes: if claimed.tx_hashes.len() > 1 {
+                    claimed.tx_hashes.clone()
+                } else {
+                    Vec::new()
+                },
+            },
+            IntentResult::CheckpointUpdated(updated) => Self::CheckpointUpdated {
+                clearing_price: updated.checkpoint.clearing_price.as_u256(),
+            },
+        }
+    }
+}
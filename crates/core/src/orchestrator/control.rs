@@ -0,0 +1,334 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use async_trait::async_trait;
+use tracing::info;
+
+use crate::{
+    executor::{EvaluationContext, Intent},
+    types::{
+        checkpoint::Checkpoint,
+        primitives::{BidId, BlockNumber},
+        risk::RiskLimits,
+        state::AuctionPhase,
+    },
+};
+
+use super::Strategy;
+
+/// A human intervening in a running [`Orchestrator`](super::Orchestrator),
+/// sent through the channel given to
+/// [`with_control`](super::Orchestrator::with_control). Commands take
+/// effect at the start of the next block.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OrchestratorCommand {
+    /// Stop calling the strategy. Intents already due, retries and
+    /// injected intents still go out.
+    Pause,
+    /// Call the strategy again. Phase changes and countdowns passed while
+    /// paused fire on the first block after.
+    Resume,
+    /// Execute a one-off intent ahead of the strategy's.
+    Inject(Intent),
+    /// Replace the executor's risk limits, keeping what was spent so far.
+    SetRiskLimits(RiskLimits),
+}
+
+/// What a [`ControlHandle`] keeps across restarts: whether the strategy is
+/// paused and the operator's intents not yet handed to the orchestrator.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ControlState {
+    pub paused: bool,
+    pub queued: Vec<Intent>,
+}
+
+/// Where a [`ControlHandle`] saves its [`ControlState`] on every change, so
+/// a restarted process neither forgets a pause nor sends a queued intent
+/// twice.
+///
+/// Saving must not fail the run, so implementations deal with their own
+/// errors.
+pub trait ControlStore: Send + Sync {
+    fn save(&self, state: &ControlState);
+}
+
+/// What the controlled strategy was last shown.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ControlStatus {
+    pub block: BlockNumber,
+    pub phase: AuctionPhase,
+    pub tracked_bids: Vec<BidId>,
+    pub latest_checkpoint: Option<Checkpoint>,
+    pub paused: bool,
+    pub queued: usize,
+}
+
+#[derive(Default)]
+struct Shared {
+    state: ControlState,
+    seen: Option<ControlStatus>,
+    store: Option<Box<dyn ControlStore>>,
+}
+
+impl Shared {
+    fn changed(&self) {
+        if let Some(store) = &self.store {
+            store.save(&self.state);
+        }
+    }
+}
+
+/// Steers a [`Controlled`] strategy from outside the orchestrator, e.g. from
+/// a request handler. Clones share the same state.
+#[derive(Clone, Default)]
+pub struct ControlHandle {
+    shared: Arc<Mutex<Shared>>,
+}
+
+impl ControlHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A handle picking up where a saved `state` left off.
+    pub fn from_state(state: ControlState) -> Self {
+        let handle = Self::default();
+        handle.lock().state = state;
+        handle
+    }
+
+    /// Save the state to `store` on every change.
+    pub fn with_store(self, store: impl ControlStore + 'static) -> Self {
+        self.lock().store = Some(Box::new(store));
+        self
+    }
+
+    /// Stop calling the strategy. Queued intents still go out.
+    pub fn pause(&self) {
+        let mut shared = self.lock();
+        if !shared.state.paused {
+            shared.state.paused = true;
+            shared.changed();
+            info!("strategy paused");
+        }
+    }
+
+    pub fn resume(&self) {
+        let mut shared = self.lock();
+        if shared.state.paused {
+            shared.state.paused = false;
+            shared.changed();
+            info!("strategy resumed");
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.lock().state.paused
+    }
+
+    /// Queue `intent` for the next block, ahead of the strategy's own.
+    /// Returns how many intents are queued.
+    pub fn submit(&self, intent: Intent) -> usize {
+        let mut shared = self.lock();
+        info!(kind = intent.kind(), "intent queued by operator");
+        shared.state.queued.push(intent);
+        shared.changed();
+        shared.state.queued.len()
+    }
+
+    pub fn state(&self) -> ControlState {
+        self.lock().state.clone()
+    }
+
+    /// The latest block the strategy was shown; `None` before the first.
+    pub fn status(&self) -> Option<ControlStatus> {
+        let shared = self.lock();
+        shared.seen.clone().map(|seen| ControlStatus {
+            paused: shared.state.paused,
+            queued: shared.state.queued.len(),
+            ..seen
+        })
+    }
+
+    fn observe(&self, ctx: &EvaluationContext<'_>) -> (bool, Vec<Intent>) {
+        let mut shared = self.lock();
+        shared.seen = Some(ControlStatus {
+            block: ctx.block,
+            phase: ctx.phase.clone(),
+            tracked_bids: ctx.tracked_bids.clone(),
+            latest_checkpoint: ctx.cache.latest_checkpoint,
+            paused: shared.state.paused,
+            queued: 0,
+        });
+        let queued = std::mem::take(&mut shared.state.queued);
+        if !queued.is_empty() {
+            shared.changed();
+        }
+        (shared.state.paused, queued)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Shared> {
+        self.shared.lock().expect("control lock poisoned")
+    }
+}
+
+impl fmt::Debug for ControlHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ControlHandle")
+            .field("state", &self.lock().state)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Runs the inner strategy under a [`ControlHandle`]: intents submitted
+/// through the handle go out at the next block, and while paused the inner
+/// strategy is not called at all.
+///
+/// A phase change missed while paused is shown to the inner strategy on
+/// resume, with the last phase it saw as `previous`; countdown hooks that
+/// fell into the pause are dropped.
+pub struct Controlled<S> {
+    inner: S,
+    handle: ControlHandle,
+    last_seen: Option<AuctionPhase>,
+}
+
+impl<S> Controlled<S>
+where
+    S: Strategy,
+{
+    pub fn new(inner: S, handle: ControlHandle) -> Self {
+        Self {
+            inner,
+            handle,
+            last_seen: None,
+        }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl<S> Strategy for Controlled<S>
+where
+    S: Strategy,
+{
+    async fn evaluate(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        let (paused, mut intents) = self.handle.observe(ctx);
+        if paused {
+            return intents;
+        }
+        let missed = self
+            .last_seen
+            .as_ref()
+            .is_none_or(|last| !last.same_phase(&ctx.phase));
+        if missed {
+            intents.extend(self.on_phase_change(None, ctx));
+        }
+        intents.extend(self.inner.evaluate(ctx).await);
+        intents
+    }
+
+    fn on_phase_change(
+        &mut self,
+        _previous: Option<&AuctionPhase>,
+        ctx: &EvaluationContext<'_>,
+    ) -> Vec<Intent> {
+        if self.handle.is_paused() {
+            return Vec::new();
+        }
+        let previous = self.last_seen.replace(ctx.phase.clone());
+        self.inner.on_phase_change(previous.as_ref(), ctx)
+    }
+
+    fn end_countdown(&self) -> Vec<u64> {
+        self.inner.end_countdown()
+    }
+
+    fn on_blocks_before_end(&mut self, blocks: u64, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        if self.handle.is_paused() {
+            return Vec::new();
+        }
+        self.inner.on_blocks_before_end(blocks, ctx)
+    }
+
+    fn on_events(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        if self.handle.is_paused() {
+            return Vec::new();
+        }
+        self.inner.on_events(ctx)
+    }
+
+    fn is_complete(&self, ctx: &EvaluationContext<'_>) -> bool {
+        self.inner.is_complete(ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::U256;
+
+    use super::*;
+    use crate::{
+        executor::ExecutorCache,
+        orchestrator::{HookSchedule, SettleStrategy},
+        types::{chain::ChainProfile, config::fixture::config},
+    };
+
+    /// Shows `strategy` one block the way the orchestrator does.
+    async fn block(
+        hooks: &mut HookSchedule,
+        strategy: &mut impl Strategy,
+        block: u64,
+        phase: AuctionPhase,
+    ) -> Vec<Intent> {
+        let config = config();
+        let cache = ExecutorCache::new();
+        let ctx = EvaluationContext {
+            block: BlockNumber::new(block),
+            phase,
+            cache: &cache,
+            tracked_bids: vec![BidId::new(U256::from(7))],
+            config: &config,
+            events: &[],
+            client: None,
+            chain: ChainProfile::BASE,
+        };
+        hooks.intents(strategy, &ctx).await
+    }
+
+    #[tokio::test]
+    async fn paused_strategy_catches_up_on_resume() {
+        let handle = ControlHandle::new();
+        let mut strategy = Controlled::new(SettleStrategy::new(), handle.clone());
+        let mut hooks = HookSchedule::default();
+
+        handle.pause();
+        handle.submit(Intent::Checkpoint);
+        let ended = AuctionPhase::Ended {
+            blocks_until_claim: 10,
+        };
+        assert_eq!(
+            block(&mut hooks, &mut strategy, 100, ended.clone()).await,
+            [Intent::Checkpoint]
+        );
+        let status = handle.status().unwrap();
+        assert!(status.paused);
+        assert_eq!(status.queued, 0);
+
+        handle.resume();
+        // The phase changed while paused, so settling exits now.
+        assert_eq!(
+            block(&mut hooks, &mut strategy, 101, ended).await,
+            [Intent::Exit {
+                bid_id: BidId::new(U256::from(7))
+            }]
+        );
+    }
+}
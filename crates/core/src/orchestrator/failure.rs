@@ -0,0 +1,17 @@
+/// What the orchestrator does when an intent fails.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FailurePolicy {
+    /// Stop the run and return the error; later intents in the block are dropped.
+    #[default]
+    Abort,
+    /// Record the failure and carry on with the block's remaining intents.
+    Skip,
+    /// Record the failure and run the intent again at the next block, ahead
+    /// of the strategy's new intents, up to `max_retries` times.
+    ///
+    /// Meant for strategies that emit an intent once (e.g. a ladder); one that
+    /// re-emits unfinished work every block would get it twice. Intents whose
+    /// transaction landed but did not read back are not retried, as they may
+    /// have taken effect.
+    RetryNextBlock { max_retries: u32 },
+}
@@ -0,0 +1,166 @@
+use alloy::primitives::U256;
+use async_trait::async_trait;
+
+use crate::{
+    error::ValidationError,
+    executor::{EvaluationContext, Intent},
+    types::{
+        config::AuctionConfig,
+        primitives::{CurrencyAmount, Price},
+        state::AuctionPhase,
+    },
+};
+
+use super::Strategy;
+
+/// One planned bid of a ladder.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rung {
+    pub max_price: Price,
+    pub amount: CurrencyAmount,
+}
+
+/// A budget split across several bids at increasing prices.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Ladder {
+    pub rungs: Vec<Rung>,
+}
+
+impl Ladder {
+    /// Spreads `total` evenly over `rungs` prices from `low` to `high`,
+    /// snapped to valid ticks. Rungs that land on the same tick are merged,
+    /// and any rounding remainder goes to the highest rung.
+    pub fn linear(
+        config: &AuctionConfig,
+        low: Price,
+        high: Price,
+        rungs: usize,
+        total: CurrencyAmount,
+    ) -> Result<Self, ValidationError> {
+        if rungs == 0 || total.is_zero() {
+            return Err(ValidationError::AmountTooSmall);
+        }
+        if low > high {
+            return Err(ValidationError::InvalidPrice);
+        }
+
+        let steps = U256::from(rungs.saturating_sub(1).max(1));
+        let span = high.as_u256() - low.as_u256();
+        let mut prices: Vec<Price> = Vec::with_capacity(rungs);
+        for index in 0..rungs {
+            let raw = low.as_u256() + span * U256::from(index) / steps;
+            let price = Price::new(raw).clamp_to_nearest_tick(
+                config.tick_spacing,
+                config.floor_price,
+                config.max_bid_price,
+            );
+            if !config.is_valid_price(price) {
+                return Err(ValidationError::InvalidPrice);
+            }
+            if prices.last() != Some(&price) {
+                prices.push(price);
+            }
+        }
+
+        let count = U256::from(prices.len());
+        let share = total.as_u256() / count;
+        let remainder = total.as_u256() % count;
+        if share.is_zero() {
+            return Err(ValidationError::AmountTooSmall);
+        }
+
+        let last = prices.len() - 1;
+        let rungs = prices
+            .into_iter()
+            .enumerate()
+            .map(|(index, max_price)| Rung {
+                max_price,
+                amount: CurrencyAmount::new(if index == last {
+                    share + remainder
+                } else {
+                    share
+                }),
+            })
+            .collect();
+        Ok(Self { rungs })
+    }
+
+    pub fn total(&self) -> CurrencyAmount {
+        CurrencyAmount::new(
+            self.rungs
+                .iter()
+                .fold(U256::ZERO, |sum, rung| sum + rung.amount.as_u256()),
+        )
+    }
+}
+
+/// Submits every rung of a [`Ladder`] as soon as the auction is active, then
+/// reports completion.
+#[derive(Debug)]
+pub struct LadderStrategy {
+    ladder: Ladder,
+    submitted: bool,
+}
+
+impl LadderStrategy {
+    pub fn new(ladder: Ladder) -> Self {
+        Self {
+            ladder,
+            submitted: false,
+        }
+    }
+
+    pub fn ladder(&self) -> &Ladder {
+        &self.ladder
+    }
+}
+
+#[async_trait]
+impl Strategy for LadderStrategy {
+    async fn evaluate(&mut self, _ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        Vec::new()
+    }
+
+    fn on_phase_change(
+        &mut self,
+        _previous: Option<&AuctionPhase>,
+        ctx: &EvaluationContext<'_>,
+    ) -> Vec<Intent> {
+        if self.submitted || !matches!(ctx.phase, AuctionPhase::Active { .. }) {
+            return Vec::new();
+        }
+
+        self.submitted = true;
+        self.ladder
+            .rungs
+            .iter()
+            .map(|rung| Intent::SubmitBid {
+                max_price: rung.max_price,
+                amount: rung.amount,
+                owner: None,
+                from: None,
+            })
+            .collect()
+    }
+
+    fn is_complete(&self, _ctx: &EvaluationContext<'_>) -> bool {
+        self.submitted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::config::fixture::{config, price};
+
+    #[test]
+    fn linear_ladder_snaps_to_ticks_and_keeps_the_total() {
+        let total = CurrencyAmount::new(U256::from(100));
+        let ladder = Ladder::linear(&config(), price(20), price(55), 4, total).unwrap();
+
+        let prices: Vec<_> = ladder.rungs.iter().map(|rung| rung.max_price).collect();
+        assert_eq!(prices, [price(20), price(30), price(40), price(50)]);
+        assert_eq!(ladder.total(), total);
+        assert_eq!(ladder.rungs[3].amount, CurrencyAmount::new(U256::from(25)));
+    }
+}
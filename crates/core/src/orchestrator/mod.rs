@@ -0,0 +1,45 @@
+pub mod activity;
+pub mod control;
+pub mod failure;
+pub mod ladder;
+pub mod progress;
+pub mod rebid;
+pub mod result;
+pub mod runner;
+mod schedule;
+#[cfg(feature = "script")]
+pub mod script;
+pub mod settle;
+pub mod sizing;
+pub mod staleness;
+pub mod stop_loss;
+pub mod strategy;
+#[cfg(feature = "serde")]
+pub mod strategy_config;
+pub mod twap;
+
+pub use activity::{ActivityEvent, ActivityIntent, ActivityLog, ActivityRecord};
+pub use control::{
+    ControlHandle, ControlState, ControlStatus, ControlStore, Controlled, OrchestratorCommand,
+};
+pub use failure::FailurePolicy;
+pub use ladder::{Ladder, LadderStrategy, Rung};
+pub(crate) use progress::Progress;
+pub use progress::{PROGRESS_CAPACITY, ProgressEvent};
+pub use rebid::AutoRebidStrategy;
+pub use result::{CompletionReason, OrchestratorResult};
+pub use runner::Orchestrator;
+pub(crate) use schedule::HookSchedule;
+#[cfg(feature = "script")]
+pub use script::{ScriptContext, ScriptEvent, ScriptStrategy};
+pub use settle::SettleStrategy;
+pub use sizing::{BidSize, SizeCalculator, SizeLimit};
+pub use staleness::StalenessGuard;
+pub use stop_loss::StopLossStrategy;
+pub use strategy::{
+    All, Any, AutoCheckpoint, Filtered, OnlyIfGraduationLikely, RateLimited, Scheduled, Sequence,
+    Strategy,
+};
+#[cfg(feature = "serde")]
+pub use strategy_config::{StrategyConfig, Units};
+pub use twap::TwapStrategy;
@@ -0,0 +1,146 @@
+use std::mem;
+
+use futures::stream::{self, BoxStream, StreamExt};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::warn;
+
+use crate::{
+    executor::{ExecutorCache, Intent},
+    types::{primitives::BlockNumber, state::AuctionPhase},
+};
+
+use super::ActivityRecord;
+
+/// Events a subscriber can fall behind by before it misses the oldest.
+pub const PROGRESS_CAPACITY: usize = 256;
+
+/// What an [`Orchestrator`](super::Orchestrator) is doing, as it happens;
+/// see [`Orchestrator::subscribe`](super::Orchestrator::subscribe).
+#[derive(Clone, Debug)]
+pub enum ProgressEvent {
+    /// The executor re-read the auction's state for `block`.
+    CacheUpdated {
+        block: BlockNumber,
+        cache: ExecutorCache,
+    },
+    /// The auction entered a new phase at `block`; `from` is `None` for the
+    /// first block handled.
+    PhaseChanged {
+        block: BlockNumber,
+        from: Option<AuctionPhase>,
+        to: AuctionPhase,
+    },
+    IntentStarted {
+        block: BlockNumber,
+        intent: Intent,
+    },
+    /// How an intent went, as the activity log records it.
+    IntentFinished {
+        block: BlockNumber,
+        record: ActivityRecord,
+    },
+    /// Every intent due at `block` was executed; `intents` counts them.
+    BlockProcessed {
+        block: BlockNumber,
+        intents: usize,
+    },
+}
+
+/// Sends [`ProgressEvent`]s to however many subscribers there are.
+#[derive(Debug)]
+pub(crate) struct Progress {
+    sender: broadcast::Sender<ProgressEvent>,
+    phase: Option<AuctionPhase>,
+}
+
+impl Progress {
+    pub(crate) fn new() -> Self {
+        Self {
+            sender: broadcast::channel(PROGRESS_CAPACITY).0,
+            phase: None,
+        }
+    }
+
+    pub(crate) fn subscribe(&self) -> BoxStream<'static, ProgressEvent> {
+        stream::unfold(self.sender.subscribe(), |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => return Some((event, receiver)),
+                    Err(RecvError::Lagged(missed)) => {
+                        warn!(missed, "progress subscriber fell behind; skipping events");
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+        .boxed()
+    }
+
+    /// Whether anyone is listening, so events are only built when needed.
+    pub(crate) fn observed(&self) -> bool {
+        self.sender.receiver_count() > 0
+    }
+
+    pub(crate) fn emit(&self, event: impl FnOnce() -> ProgressEvent) {
+        if self.observed() {
+            // Only fails when the last subscriber left in between.
+            let _ = self.sender.send(event());
+        }
+    }
+
+    /// Emits [`ProgressEvent::PhaseChanged`] when `phase` is a different
+    /// phase from the last block's; the blocks counted down inside a phase
+    /// don't count as a change.
+    pub(crate) fn phase(&mut self, block: BlockNumber, phase: AuctionPhase) {
+        if self
+            .phase
+            .as_ref()
+            .is_some_and(|last| mem::discriminant(last) == mem::discriminant(&phase))
+        {
+            self.phase = Some(phase);
+            return;
+        }
+        let from = self.phase.replace(phase.clone());
+        self.emit(|| ProgressEvent::PhaseChanged {
+            block,
+            from,
+            to: phase,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn phase_changes_skip_the_countdown_within_a_phase() {
+        let mut progress = Progress::new();
+        let events = progress.subscribe();
+        let block = BlockNumber::new;
+        progress.phase(block(1), AuctionPhase::PreTokens);
+        progress.phase(
+            block(2),
+            AuctionPhase::Active {
+                blocks_remaining: 9,
+            },
+        );
+        progress.phase(
+            block(3),
+            AuctionPhase::Active {
+                blocks_remaining: 8,
+            },
+        );
+        progress.phase(block(4), AuctionPhase::Claimable);
+        drop(progress);
+
+        let changes: Vec<_> = events
+            .map(|event| match event {
+                ProgressEvent::PhaseChanged { block, from, .. } => (block.as_u64(), from.is_some()),
+                other => panic!("unexpected {other:?}"),
+            })
+            .collect()
+            .await;
+        assert_eq!(changes, [(1, false), (2, true), (4, true)]);
+    }
+}
@@ -0,0 +1,258 @@
+use alloy::primitives::{Address, U256};
+use async_trait::async_trait;
+use tracing::{debug, info};
+
+use crate::{
+    events::AuctionEvent,
+    executor::{EvaluationContext, Intent},
+    types::{
+        primitives::{BidId, BlockNumber, CurrencyAmount, Price, TokenAmount},
+        state::AuctionPhase,
+    },
+};
+
+use super::{SizeCalculator, Strategy};
+
+/// Replaces `owner`'s bids once the clearing price moves above them.
+///
+/// Bids are learned from `BidSubmitted` events for `owner` (or seeded with
+/// [`with_bid`](Self::with_bid)) and the clearing price from
+/// `ClearingPriceUpdated`/`CheckpointUpdated` events, so this needs the
+/// orchestrator to be run with an event stream. When a bid falls below the
+/// clearing price, a replacement for the same amount is submitted `ticks`
+/// ticks above it, as long as that price is within `max_price` and the
+/// replacement fits in what is left of `budget`. Each bid is replaced at most
+/// once; the replacement is itself watched once its event arrives. The
+/// strategy is complete once the auction has ended.
+///
+/// With [`with_target_tokens`](Self::with_target_tokens), replacements are
+/// sized by a [`SizeCalculator`] to buy that many tokens at their new price
+/// rather than reusing the outbid amount.
+#[derive(Debug)]
+pub struct AutoRebidStrategy {
+    owner: Address,
+    max_price: Price,
+    budget: CurrencyAmount,
+    ticks: u64,
+    target: Option<TokenAmount>,
+    spent: U256,
+    bids: Vec<WatchedBid>,
+    clearing_price: Option<Price>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct WatchedBid {
+    id: BidId,
+    price: Price,
+    amount: CurrencyAmount,
+}
+
+impl AutoRebidStrategy {
+    /// Rebids one tick above the clearing price, spending at most `budget` on
+    /// replacements and never bidding above `max_price`.
+    pub fn new(owner: Address, max_price: Price, budget: CurrencyAmount) -> Self {
+        Self {
+            owner,
+            max_price,
+            budget,
+            ticks: 1,
+            target: None,
+            spent: U256::ZERO,
+            bids: Vec::new(),
+            clearing_price: None,
+        }
+    }
+
+    /// How many ticks above the clearing price replacements are placed.
+    pub fn with_ticks_above(mut self, ticks: u64) -> Self {
+        self.ticks = ticks.max(1);
+        self
+    }
+
+    /// Sizes each replacement to buy `tokens` at its price, as far as the
+    /// budget and remaining supply allow.
+    pub fn with_target_tokens(mut self, tokens: TokenAmount) -> Self {
+        self.target = Some(tokens);
+        self
+    }
+
+    /// Watches a bid placed before the event stream started.
+    pub fn with_bid(mut self, id: BidId, price: Price, amount: CurrencyAmount) -> Self {
+        self.bids.push(WatchedBid { id, price, amount });
+        self
+    }
+
+    /// Currency spent on replacement bids so far.
+    pub fn spent(&self) -> CurrencyAmount {
+        CurrencyAmount::new(self.spent)
+    }
+
+    fn remaining_budget(&self) -> U256 {
+        self.budget.as_u256().saturating_sub(self.spent)
+    }
+
+    fn observe(&mut self, event: &AuctionEvent) {
+        match *event {
+            AuctionEvent::BidSubmitted {
+                bid_id,
+                owner,
+                price,
+                amount,
+                ..
+            } if owner == self.owner => self.bids.push(WatchedBid {
+                id: bid_id,
+                price,
+                amount,
+            }),
+            AuctionEvent::BidExited { bid_id, owner, .. } if owner == self.owner => {
+                self.bids.retain(|bid| bid.id.as_u256() != bid_id.as_u256());
+            }
+            AuctionEvent::ClearingPriceUpdated { clearing_price, .. }
+            | AuctionEvent::CheckpointUpdated { clearing_price, .. } => {
+                self.clearing_price = Some(clearing_price);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[async_trait]
+impl Strategy for AutoRebidStrategy {
+    async fn evaluate(&mut self, _ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        Vec::new()
+    }
+
+    fn on_events(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        for event in ctx.events {
+            self.observe(event);
+        }
+
+        let Some(clearing_price) = self.clearing_price else {
+            return Vec::new();
+        };
+        if !matches!(ctx.phase, AuctionPhase::Active { .. }) {
+            return Vec::new();
+        }
+
+        let (outbid, still_in): (Vec<_>, Vec<_>) =
+            self.bids.iter().partition(|bid| bid.price < clearing_price);
+        if outbid.is_empty() {
+            return Vec::new();
+        }
+        self.bids = still_in;
+
+        let price = ctx
+            .config
+            .nth_tick_above(clearing_price, self.ticks)
+            .filter(|price| *price <= self.max_price);
+        let Some(price) = price else {
+            info!(
+                outbid = outbid.len(),
+                "clearing price is past the rebid ceiling; not replacing"
+            );
+            return Vec::new();
+        };
+
+        let mut intents = Vec::new();
+        for bid in outbid {
+            let amount = match self.target {
+                Some(target) => SizeCalculator::new()
+                    .with_budget(CurrencyAmount::new(self.remaining_budget()))
+                    .size_in(ctx, target, price)
+                    .map_or(U256::ZERO, |size| size.amount.as_u256()),
+                None => bid.amount.as_u256().min(self.remaining_budget()),
+            };
+            if amount.is_zero() {
+                debug!(bid_id = %bid.id.as_u256(), "rebid budget exhausted");
+                continue;
+            }
+            self.spent += amount;
+            info!(
+                bid_id = %bid.id.as_u256(),
+                price = %price.as_u256(),
+                amount = %amount,
+                "replacing outbid bid"
+            );
+            // A replacement is worthless once bidding closes, so it goes
+            // ahead of any settling work near the end.
+            let last_bid_block = BlockNumber::new(ctx.config.end_block.as_u64().saturating_sub(1));
+            intents.push(
+                Intent::SubmitBid {
+                    max_price: price,
+                    amount: CurrencyAmount::new(amount),
+                    owner: None,
+                    from: None,
+                }
+                .by(last_bid_block),
+            );
+        }
+        intents
+    }
+
+    fn is_complete(&self, ctx: &EvaluationContext<'_>) -> bool {
+        matches!(
+            ctx.phase,
+            AuctionPhase::Ended { .. } | AuctionPhase::Claimable
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        executor::ExecutorCache,
+        types::{
+            chain::ChainProfile,
+            config::fixture::{amount, config, price},
+            primitives::BlockNumber,
+        },
+    };
+
+    #[test]
+    fn replaces_outbid_bids_within_budget() {
+        let owner = Address::repeat_byte(1);
+        let mut strategy = AutoRebidStrategy::new(owner, price(200), amount(150))
+            .with_bid(BidId::new(U256::from(1)), price(50), amount(100))
+            .with_bid(BidId::new(U256::from(2)), price(60), amount(100))
+            .with_bid(BidId::new(U256::from(3)), price(90), amount(100));
+
+        let config = config();
+        let cache = ExecutorCache::new();
+        let events = [AuctionEvent::ClearingPriceUpdated {
+            block: BlockNumber::new(10),
+            clearing_price: price(65),
+        }];
+        let ctx = EvaluationContext {
+            block: BlockNumber::new(10),
+            phase: AuctionPhase::Active {
+                blocks_remaining: 90,
+            },
+            cache: &cache,
+            tracked_bids: Vec::new(),
+            config: &config,
+            events: &events,
+            client: None,
+            chain: ChainProfile::default(),
+        };
+
+        let intents = strategy.on_events(&ctx);
+        let submitted: Vec<_> = intents
+            .iter()
+            .map(|intent| match intent.clone().into_inner() {
+                Intent::SubmitBid {
+                    max_price, amount, ..
+                } => (max_price, amount),
+                other => panic!("unexpected intent {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            submitted,
+            [(price(70), amount(100)), (price(70), amount(50))]
+        );
+        assert_eq!(strategy.spent(), amount(150));
+
+        // Already replaced; nothing more to do at the same price.
+        assert!(strategy.on_events(&ctx).is_empty());
+    }
+}
@@ -0,0 +1,66 @@
+use alloy::primitives::U256;
+
+use crate::{
+    executor::IntentOutcome,
+    types::primitives::{BlockNumber, CurrencyAmount},
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CompletionReason {
+    /// The block stream ran out.
+    #[default]
+    StreamEnded,
+    /// The strategy reported it had nothing left to do.
+    StrategyComplete,
+    /// The caller stopped the run; counts cover the blocks handled so far.
+    Cancelled,
+}
+
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrchestratorResult {
+    pub blocks_processed: u64,
+    pub bids_submitted: usize,
+    pub bids_exited: usize,
+    pub claims: usize,
+    pub checkpoints: usize,
+    /// Intents that sent nothing because every bid was below the dust threshold.
+    pub skipped: usize,
+    /// Intents the executor held back to retry later, counted each time.
+    pub deferred: usize,
+    /// Intents dropped because their deadline had passed.
+    pub expired: usize,
+    /// Intents not sent because the same intent had just failed.
+    pub suppressed: usize,
+    /// Blocks whose intents were held back because the block trailed the
+    /// chain head by more than the [`StalenessGuard`](super::StalenessGuard)
+    /// allows.
+    pub stale_blocks: u64,
+    /// Every [`IntentOutcome::Failed`] the failure policy let the run survive,
+    /// with the block it failed at. Retried intents appear once per attempt.
+    /// Not serialized, as errors carry no stable form.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub failed: Vec<(BlockNumber, IntentOutcome)>,
+    /// Gas used by every confirmed transaction.
+    pub gas_used: u64,
+    /// Wei paid in gas by every confirmed transaction.
+    pub gas_fees: U256,
+    /// Currency put into submitted bids.
+    pub currency_committed: CurrencyAmount,
+    /// Currency handed back by exits.
+    pub currency_refunded: CurrencyAmount,
+    pub reason: CompletionReason,
+}
+
+impl OrchestratorResult {
+    /// Currency committed and not refunded. For a native-currency auction
+    /// this is paid in wei on top of [`gas_fees`](Self::gas_fees).
+    pub fn currency_spent(&self) -> CurrencyAmount {
+        CurrencyAmount::new(
+            self.currency_committed
+                .as_u256()
+                .saturating_sub(self.currency_refunded.as_u256()),
+        )
+    }
+}
@@ -0,0 +1,518 @@
+use std::future::{self, Future};
+
+use futures::{FutureExt, StreamExt, stream, stream::BoxStream};
+use tokio::sync::mpsc;
+use tracing::{debug, info, instrument, warn};
+
+#[cfg(feature = "serde")]
+use crate::replay::{Decision, DecisionLog};
+use crate::{
+    api::AuctionApi,
+    blocks::BlockStream,
+    error::{Error, TransactionError},
+    events::{AuctionEvent, EventStream},
+    executor::{Intent, IntentExecutor, IntentOutcome, IntentResult},
+    metrics,
+    types::primitives::BlockNumber,
+};
+
+use super::{
+    ActivityLog, ActivityRecord, CompletionReason, FailurePolicy, HookSchedule,
+    OrchestratorCommand, OrchestratorResult, Progress, ProgressEvent, StalenessGuard, Strategy,
+};
+
+/// Drives a strategy from a block stream, executing its intents as they come.
+pub struct Orchestrator<C, S>
+where
+    C: AuctionApi,
+    S: Strategy,
+{
+    executor: IntentExecutor<C>,
+    strategy: S,
+    hooks: HookSchedule,
+    on_failure: FailurePolicy,
+    /// Failed intents to run again next block, with how often they failed.
+    retries: Vec<(Intent, u32)>,
+    /// Events received but not yet shown to the strategy.
+    pending_events: Vec<AuctionEvent>,
+    activity: Option<Box<dyn ActivityLog>>,
+    #[cfg(feature = "serde")]
+    decisions: Option<Box<dyn DecisionLog>>,
+    commands: Option<mpsc::Receiver<OrchestratorCommand>>,
+    paused: bool,
+    /// Intents injected by commands, for the next block.
+    injected: Vec<Intent>,
+    staleness: Option<StalenessGuard>,
+    progress: Progress,
+}
+
+impl<C, S> Orchestrator<C, S>
+where
+    C: AuctionApi,
+    S: Strategy,
+{
+    pub fn new(executor: IntentExecutor<C>, strategy: S) -> Self {
+        Self {
+            executor,
+            strategy,
+            hooks: HookSchedule::default(),
+            on_failure: FailurePolicy::default(),
+            retries: Vec::new(),
+            pending_events: Vec::new(),
+            activity: None,
+            #[cfg(feature = "serde")]
+            decisions: None,
+            commands: None,
+            paused: false,
+            injected: Vec::new(),
+            staleness: None,
+            progress: Progress::new(),
+        }
+    }
+
+    pub fn with_failure_policy(mut self, policy: FailurePolicy) -> Self {
+        self.on_failure = policy;
+        self
+    }
+
+    /// Record every executed intent and its outcome to `log`.
+    pub fn with_activity_log(mut self, log: impl ActivityLog + 'static) -> Self {
+        self.activity = Some(Box::new(log));
+        self
+    }
+
+    /// Record the context the strategy is shown at every block and the
+    /// intents it returns to `log`, for a later [`Replay`](crate::Replay).
+    #[cfg(feature = "serde")]
+    pub fn with_decision_log(mut self, log: impl DecisionLog + 'static) -> Self {
+        self.decisions = Some(Box::new(log));
+        self
+    }
+
+    /// Take [`OrchestratorCommand`]s from `commands` while running, e.g.
+    /// from a UI or an API handler holding the sender.
+    pub fn with_control(mut self, commands: mpsc::Receiver<OrchestratorCommand>) -> Self {
+        self.commands = Some(commands);
+        self
+    }
+
+    /// Hold every intent back on blocks that trail the chain head by more
+    /// than `guard` allows, until the stream catches up.
+    pub fn with_staleness_guard(mut self, guard: StalenessGuard) -> Self {
+        self.staleness = Some(guard);
+        self
+    }
+
+    /// A stream of [`ProgressEvent`]s for every block handled from now on,
+    /// ending when the orchestrator is dropped. A subscriber that falls
+    /// more than [`PROGRESS_CAPACITY`](super::PROGRESS_CAPACITY) events
+    /// behind misses the oldest ones.
+    pub fn subscribe(&self) -> BoxStream<'static, ProgressEvent> {
+        self.progress.subscribe()
+    }
+
+    /// Whether a [`OrchestratorCommand::Pause`] holds the strategy back.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn executor(&self) -> &IntentExecutor<C> {
+        &self.executor
+    }
+
+    pub fn strategy(&self) -> &S {
+        &self.strategy
+    }
+
+    /// Runs until the stream ends or the strategy reports completion.
+    pub async fn run<B>(&mut self, blocks: B) -> Result<OrchestratorResult, Error>
+    where
+        B: BlockStream,
+    {
+        self.run_with_events(blocks, stream::empty()).await
+    }
+
+    /// Like [`run`](Self::run), but stops with [`CompletionReason::Cancelled`]
+    /// once `cancel` resolves.
+    pub async fn run_until<B, F>(
+        &mut self,
+        blocks: B,
+        cancel: F,
+    ) -> Result<OrchestratorResult, Error>
+    where
+        B: BlockStream,
+        F: Future<Output = ()>,
+    {
+        self.run_with_events_until(blocks, stream::empty(), cancel)
+            .await
+    }
+
+    /// Like [`run`](Self::run), but also collects auction events so the
+    /// strategy sees them in `EvaluationContext::events` at the next block.
+    ///
+    /// Only the block stream ending stops the run; a finished event stream
+    /// just means no more events.
+    pub async fn run_with_events<B, E>(
+        &mut self,
+        blocks: B,
+        events: E,
+    ) -> Result<OrchestratorResult, Error>
+    where
+        B: BlockStream,
+        E: EventStream,
+    {
+        self.run_with_events_until(blocks, events, future::pending())
+            .await
+    }
+
+    /// Like [`run_with_events`](Self::run_with_events), but stops with
+    /// [`CompletionReason::Cancelled`] once `cancel` resolves, e.g. on
+    /// `CancellationToken::cancelled()` or a deadline.
+    ///
+    /// Cancellation is only observed between blocks, so a block's intents are
+    /// never cut off halfway. The result counts everything done so far and the
+    /// client keeps its tracked bids, so a later run can pick them up.
+    #[instrument(name = "orchestrator", skip_all, fields(auction = %self.executor.client().address()))]
+    pub async fn run_with_events_until<B, E, F>(
+        &mut self,
+        blocks: B,
+        events: E,
+        cancel: F,
+    ) -> Result<OrchestratorResult, Error>
+    where
+        B: BlockStream,
+        E: EventStream,
+        F: Future<Output = ()>,
+    {
+        let mut result = OrchestratorResult::default();
+        let mut blocks = blocks.fuse();
+        let mut events = events.fuse();
+        let mut cancel = std::pin::pin!(cancel.fuse());
+
+        loop {
+            let block = futures::select! {
+                () = cancel => {
+                    result.reason = CompletionReason::Cancelled;
+                    info!(blocks = result.blocks_processed, "cancelled");
+                    return Ok(result);
+                }
+                block = blocks.next() => match block {
+                    Some(block) => block?,
+                    None => break,
+                },
+                event = events.next() => {
+                    if let Some(event) = event {
+                        self.pending_events.push(event?);
+                    }
+                    continue;
+                }
+            };
+
+            self.handle_block(block, &mut result).await?;
+
+            let ctx = self.executor.context(block, &[]);
+            if self.strategy.is_complete(&ctx) {
+                result.reason = CompletionReason::StrategyComplete;
+                info!(blocks = result.blocks_processed, "strategy complete");
+                return Ok(result);
+            }
+        }
+
+        result.reason = CompletionReason::StreamEnded;
+        info!(blocks = result.blocks_processed, "block stream ended");
+        Ok(result)
+    }
+
+    /// Evaluates the strategy at `block` and executes its intents in order.
+    /// A failed intent is handled according to the [`FailurePolicy`].
+    #[instrument(name = "block", skip_all, fields(block = block.as_u64()))]
+    pub async fn handle_block(
+        &mut self,
+        block: BlockNumber,
+        result: &mut OrchestratorResult,
+    ) -> Result<(), Error> {
+        self.apply_commands();
+        if let Some(guard) = &self.staleness
+            && let Some(lag) = guard.stale_by(self.executor.client(), block).await
+        {
+            // Retries, injected and deferred intents wait along with the
+            // strategy, which catches up on missed hooks once fresh.
+            warn!(
+                lag,
+                max_lag = guard.max_lag(),
+                "block trails the chain head; holding intents"
+            );
+            result.stale_blocks += 1;
+            return Ok(());
+        }
+        self.executor.refresh(block).await?;
+        self.progress.emit(|| ProgressEvent::CacheUpdated {
+            block,
+            cache: self.executor.cache().clone(),
+        });
+        self.progress
+            .phase(block, self.executor.context(block, &[]).phase);
+
+        let delivered = self
+            .pending_events
+            .iter()
+            .take_while(|event| event.block() <= block)
+            .count();
+
+        // Intents deferred by the executor and retries go first, ahead of
+        // anything new.
+        let mut intents: Vec<(Intent, u32)> = self
+            .executor
+            .due_intents(block)
+            .into_iter()
+            .map(|intent| (intent, 0))
+            .collect();
+        intents.append(&mut self.retries);
+        intents.extend(self.injected.drain(..).map(|intent| (intent, 0)));
+        // Paused, the strategy and its hooks are not called; the hook
+        // schedule fires what was missed once resumed.
+        if !self.paused {
+            let ctx = self
+                .executor
+                .context(block, &self.pending_events[..delivered]);
+            let new = self.hooks.intents(&mut self.strategy, &ctx).await;
+            #[cfg(feature = "serde")]
+            if let Some(log) = &mut self.decisions {
+                log.record(Decision::new(&ctx, &new));
+            }
+            intents.extend(new.into_iter().map(|intent| (intent, 0)));
+        }
+        self.pending_events.drain(..delivered);
+
+        if !intents.is_empty() {
+            debug!(intents = intents.len(), "strategy produced intents");
+        }
+        // Intents with the nearest deadline go first; the sort is stable, so
+        // the order above holds among equal deadlines and those without one.
+        intents.sort_by_key(|(intent, _)| {
+            intent
+                .execute_by()
+                .map_or(u64::MAX, |execute_by| execute_by.as_u64())
+        });
+
+        let executed = intents.len();
+        for (intent, failures) in intents {
+            self.progress.emit(|| ProgressEvent::IntentStarted {
+                block,
+                intent: intent.clone(),
+            });
+            let logged =
+                (self.activity.is_some() || self.progress.observed()).then(|| intent.clone());
+            let outcome = self.executor.execute(intent, block).await;
+            metrics::record_outcome(&outcome);
+            if let Some(intent) = logged {
+                let record = ActivityRecord::new(block, &intent, &outcome);
+                if let Some(log) = &mut self.activity {
+                    log.record(record.clone());
+                }
+                self.progress
+                    .emit(|| ProgressEvent::IntentFinished { block, record });
+            }
+            match outcome {
+                IntentOutcome::Success(outcome) => {
+                    result.gas_used += outcome.gas_used();
+                    result.gas_fees += outcome.gas_fee();
+                    match outcome {
+                        IntentResult::BidSubmitted(submitted) => {
+                            result.bids_submitted += 1;
+                            result.currency_committed += submitted.amount;
+                        }
+                        IntentResult::BidExited(exited) => {
+                            result.bids_exited += 1;
+                            result.currency_refunded += exited.currency_refunded;
+                        }
+                        IntentResult::TokensClaimed(_) => result.claims += 1,
+                        IntentResult::CheckpointUpdated(_) => result.checkpoints += 1,
+                    }
+                }
+                IntentOutcome::Skipped { .. } => result.skipped += 1,
+                IntentOutcome::Deferred { .. } => result.deferred += 1,
+                IntentOutcome::Expired { .. } => result.expired += 1,
+                IntentOutcome::Suppressed { .. } => result.suppressed += 1,
+                IntentOutcome::Failed { intent, error } => {
+                    match self.on_failure {
+                        FailurePolicy::Abort => return Err(error),
+                        FailurePolicy::Skip => {}
+                        // A transaction that landed but failed to read back
+                        // may be on chain; sending it again could double it.
+                        FailurePolicy::RetryNextBlock { .. }
+                            if matches!(
+                                error,
+                                Error::Transaction(TransactionError::Unverified { .. })
+                            ) =>
+                        {
+                            warn!(kind = intent.kind(), %error, "not retrying unverified intent");
+                        }
+                        FailurePolicy::RetryNextBlock { max_retries } => {
+                            if failures < max_retries {
+                                self.retries.push((intent.clone(), failures + 1));
+                            } else {
+                                warn!(
+                                    kind = intent.kind(),
+                                    attempts = failures + 1,
+                                    "giving up on intent"
+                                );
+                            }
+                        }
+                    }
+                    result
+                        .failed
+                        .push((block, IntentOutcome::Failed { intent, error }));
+                }
+            }
+        }
+
+        if let Some(checkpoint) = self.executor.cache().latest_checkpoint {
+            metrics::set_clearing_price(checkpoint.clearing_price);
+        }
+        #[cfg(feature = "metrics")]
+        {
+            let head = self.executor.client().fetch_block_number().await;
+            if let Ok(head) = head {
+                metrics::set_blocks_behind(head.as_u64().saturating_sub(block.as_u64()));
+            }
+        }
+
+        result.blocks_processed += 1;
+        self.progress.emit(|| ProgressEvent::BlockProcessed {
+            block,
+            intents: executed,
+        });
+        Ok(())
+    }
+
+    /// Applies every command received since the last block.
+    fn apply_commands(&mut self) {
+        let Some(commands) = &mut self.commands else {
+            return;
+        };
+        while let Ok(command) = commands.try_recv() {
+            match command {
+                OrchestratorCommand::Pause => {
+                    info!("strategy paused");
+                    self.paused = true;
+                }
+                OrchestratorCommand::Resume => {
+                    info!("strategy resumed");
+                    self.paused = false;
+                }
+                OrchestratorCommand::Inject(intent) => {
+                    info!(kind = intent.kind(), "intent injected");
+                    self.injected.push(intent);
+                }
+                OrchestratorCommand::SetRiskLimits(limits) => {
+                    info!(?limits, "risk limits updated");
+                    self.executor.set_risk_limits(limits);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::U256;
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::{
+        executor::{EvaluationContext, mock::MockAuction},
+        orchestrator::StopLossStrategy,
+        types::{
+            config::fixture::{amount, price},
+            primitives::BidId,
+            state::GraduationStatus,
+        },
+    };
+
+    #[tokio::test]
+    async fn stop_loss_exits_once_the_chain_checkpoints_past_the_ceiling() {
+        let auction = MockAuction::new();
+        auction
+            .submitted
+            .lock()
+            .unwrap()
+            .push((price(20), amount(100)));
+        let mut orchestrator = Orchestrator::new(
+            IntentExecutor::new(auction),
+            StopLossStrategy::new(price(30)),
+        );
+        let mut result = OrchestratorResult::default();
+
+        orchestrator
+            .handle_block(BlockNumber::new(20), &mut result)
+            .await
+            .unwrap();
+        assert_eq!(result.bids_exited, 0);
+
+        // Someone else checkpoints the auction above the ceiling and it
+        // graduates; the bid is outbid.
+        let auction = orchestrator.executor.client();
+        auction.checkpoint_at(21, price(40));
+        *auction.graduation.lock().unwrap() = GraduationStatus::Graduated;
+        orchestrator
+            .handle_block(BlockNumber::new(21), &mut result)
+            .await
+            .unwrap();
+        assert_eq!(result.bids_exited, 1);
+        assert_eq!(result.currency_refunded, amount(100));
+        assert_eq!(
+            *orchestrator.executor.client().exited.lock().unwrap(),
+            [BidId::new(U256::from(1))]
+        );
+    }
+
+    /// Bids once, on its first evaluation.
+    struct BidOnce(bool);
+
+    #[async_trait]
+    impl Strategy for BidOnce {
+        async fn evaluate(&mut self, _ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+            if std::mem::replace(&mut self.0, true) {
+                return Vec::new();
+            }
+            vec![Intent::SubmitBid {
+                max_price: price(20),
+                amount: amount(100),
+                owner: None,
+                from: None,
+            }]
+        }
+    }
+
+    #[tokio::test]
+    async fn unverified_bids_are_not_sent_again() {
+        let auction = MockAuction {
+            reorged: true,
+            ..MockAuction::new()
+        };
+        let executor = IntentExecutor::new(auction).with_verify_after_execute(true);
+        let mut orchestrator = Orchestrator::new(executor, BidOnce(false))
+            .with_failure_policy(FailurePolicy::RetryNextBlock { max_retries: 3 });
+        let mut result = OrchestratorResult::default();
+        for block in [20, 21] {
+            orchestrator
+                .handle_block(BlockNumber::new(block), &mut result)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            orchestrator
+                .executor
+                .client()
+                .submitted
+                .lock()
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(orchestrator.executor.risk().spent(), amount(100));
+    }
+}
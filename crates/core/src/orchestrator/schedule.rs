@@ -0,0 +1,57 @@
+use tracing::info;
+
+use crate::{
+    executor::{EvaluationContext, Intent},
+    types::state::AuctionPhase,
+};
+
+use super::Strategy;
+
+/// Tracks which one-shot [`Strategy`] hooks have fired, so live runs and
+/// backtests call them at the same moments.
+#[derive(Debug, Default)]
+pub(crate) struct HookSchedule {
+    last_phase: Option<AuctionPhase>,
+    fired_countdown: Vec<u64>,
+}
+
+impl HookSchedule {
+    /// Collects the strategy's intents for one block: phase change, countdown
+    /// and event hooks first, then `evaluate`.
+    pub(crate) async fn intents<S: Strategy>(
+        &mut self,
+        strategy: &mut S,
+        ctx: &EvaluationContext<'_>,
+    ) -> Vec<Intent> {
+        let mut intents = Vec::new();
+
+        let phase_changed = self
+            .last_phase
+            .as_ref()
+            .is_none_or(|last| !last.same_phase(&ctx.phase));
+        if phase_changed {
+            info!(phase = ?ctx.phase, "auction phase changed");
+            intents.extend(strategy.on_phase_change(self.last_phase.as_ref(), ctx));
+            self.last_phase = Some(ctx.phase.clone());
+        }
+
+        let block = ctx.block.as_u64();
+        let end = ctx.config.end_block.as_u64();
+        if block < end {
+            let remaining = end - block;
+            for blocks in strategy.end_countdown() {
+                if remaining <= blocks && !self.fired_countdown.contains(&blocks) {
+                    self.fired_countdown.push(blocks);
+                    intents.extend(strategy.on_blocks_before_end(blocks, ctx));
+                }
+            }
+        }
+
+        if !ctx.events.is_empty() {
+            intents.extend(strategy.on_events(ctx));
+        }
+
+        intents.extend(strategy.evaluate(ctx).await);
+        intents
+    }
+}
@@ -0,0 +1,387 @@
+use std::{path::Path, str::FromStr};
+
+use alloy::primitives::{Address, U256};
+use async_trait::async_trait;
+use rhai::{AST, CallFnOptions, Dynamic, Engine, Scope};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::{
+    error::ScriptError,
+    events::AuctionEvent,
+    executor::{EvaluationContext, Intent},
+    types::{
+        config::AuctionConfig,
+        primitives::{BidId, CurrencyAmount, Price},
+        state::AuctionPhase,
+    },
+};
+
+use super::Strategy;
+
+/// Operations a single `evaluate` call may run before it is aborted.
+const DEFAULT_MAX_OPERATIONS: u64 = 1_000_000;
+
+/// A [`Strategy`] whose `evaluate` is a Rhai function, for trying out logic
+/// without recompiling.
+///
+/// The script defines `fn evaluate(ctx)`, which gets a [`ScriptContext`] as
+/// a map and returns an array of intents such as
+/// `#{ kind: "submit_bid", ticks: 3, amount: "1000000" }`, `#{ kind: "exit",
+/// bid_id: 7 }`, `#{ kind: "claim", bid_ids: [7, 8] }` or
+/// `#{ kind: "checkpoint" }`. Large numbers are passed as decimal strings in
+/// both directions; a bid takes either a Q96 `max_price` or `ticks` above
+/// the floor price, an optional `owner` address to bid for someone else
+/// and an optional pooled wallet `from` to pay with. `this` is a map that persists between blocks, so the
+/// script can keep its own state on it.
+///
+/// A script that fails or returns a malformed intent is logged and produces
+/// nothing for that block.
+pub struct ScriptStrategy {
+    engine: Engine,
+    ast: AST,
+    state: Dynamic,
+}
+
+impl ScriptStrategy {
+    pub fn from_source(source: &str) -> Result<Self, ScriptError> {
+        let mut engine = Engine::new();
+        engine.set_max_operations(DEFAULT_MAX_OPERATIONS);
+        let ast = engine.compile(source)?;
+        if !ast
+            .iter_functions()
+            .any(|function| function.name == "evaluate" && function.params.len() == 1)
+        {
+            return Err(ScriptError::MissingEvaluate);
+        }
+
+        Ok(Self {
+            engine,
+            ast,
+            state: Dynamic::from_map(rhai::Map::new()),
+        })
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ScriptError> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(|source| ScriptError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::from_source(&source)
+    }
+
+    /// Caps the operations one `evaluate` call may run, so a runaway script
+    /// cannot stall the orchestrator.
+    pub fn with_max_operations(mut self, operations: u64) -> Self {
+        self.engine.set_max_operations(operations);
+        self
+    }
+
+    /// The script's `this` map as left by the last call.
+    pub fn state(&self) -> &Dynamic {
+        &self.state
+    }
+
+    fn call(&mut self, ctx: &EvaluationContext<'_>) -> Result<Vec<Intent>, String> {
+        let view = rhai::serde::to_dynamic(ScriptContext::new(ctx)).map_err(|e| e.to_string())?;
+        let options = CallFnOptions::new()
+            .eval_ast(false)
+            .bind_this_ptr(&mut self.state);
+        let returned: Dynamic = self
+            .engine
+            .call_fn_with_options(options, &mut Scope::new(), &self.ast, "evaluate", (view,))
+            .map_err(|e| e.to_string())?;
+        if returned.is_unit() {
+            return Ok(Vec::new());
+        }
+
+        let intents: Vec<ScriptIntent> =
+            rhai::serde::from_dynamic(&returned).map_err(|e| e.to_string())?;
+        intents
+            .into_iter()
+            .map(|intent| intent.into_intent(ctx.config))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Strategy for ScriptStrategy {
+    async fn evaluate(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        self.call(ctx).unwrap_or_else(|error| {
+            warn!(block = ctx.block.as_u64(), %error, "strategy script failed");
+            Vec::new()
+        })
+    }
+}
+
+/// What a script's `evaluate` sees of the [`EvaluationContext`].
+#[derive(Debug, Serialize)]
+pub struct ScriptContext {
+    pub block: u64,
+    /// `pre_start`, `pre_tokens`, `active`, `ended` or `claimable`.
+    pub phase: &'static str,
+    /// Blocks until the phase ends, where the phase has a fixed length.
+    pub blocks_left: Option<u64>,
+    /// `blocks_left` as expected seconds at the chain's block time.
+    pub seconds_left: Option<u64>,
+    /// Estimated unix timestamp of `block`, once a block timestamp was read.
+    pub timestamp: Option<u64>,
+    pub floor_price: String,
+    pub tick_spacing: String,
+    pub clearing_price: Option<String>,
+    /// Ticks the clearing price sits above the floor price.
+    pub clearing_ticks: Option<u64>,
+    /// Supply released per block right now, in mps, once the step schedule
+    /// is known.
+    pub mps: Option<u32>,
+    /// Start block of the next step that releases at a different rate.
+    pub next_step_block: Option<u64>,
+    /// The rate of that step, in mps.
+    pub next_step_mps: Option<u32>,
+    pub tracked_bids: Vec<String>,
+    pub events: Vec<ScriptEvent>,
+}
+
+/// One auction event as a script sees it; fields an event lacks are unit.
+#[derive(Debug, Serialize)]
+pub struct ScriptEvent {
+    pub kind: &'static str,
+    pub block: u64,
+    pub bid_id: Option<String>,
+    pub owner: Option<String>,
+    pub price: Option<String>,
+}
+
+impl ScriptContext {
+    fn new(ctx: &EvaluationContext<'_>) -> Self {
+        let (phase, blocks_left) = match ctx.phase {
+            AuctionPhase::PreStart { blocks_until_start } => {
+                ("pre_start", Some(blocks_until_start))
+            }
+            AuctionPhase::PreTokens => ("pre_tokens", None),
+            AuctionPhase::Active { blocks_remaining } => ("active", Some(blocks_remaining)),
+            AuctionPhase::Ended { blocks_until_claim } => ("ended", Some(blocks_until_claim)),
+            AuctionPhase::Claimable => ("claimable", None),
+        };
+        let floor = ctx.config.floor_price.as_u256();
+        let spacing = ctx.config.tick_spacing.as_u256();
+        let clearing = ctx
+            .cache
+            .latest_checkpoint
+            .map(|checkpoint| checkpoint.clearing_price.as_u256());
+        let schedule = ctx.cache.step_schedule.as_ref();
+        let next_step = schedule.and_then(|schedule| schedule.next_change(ctx.block));
+
+        Self {
+            block: ctx.block.as_u64(),
+            phase,
+            blocks_left,
+            seconds_left: blocks_left.map(|blocks| ctx.cache.clock.duration_of(blocks).as_secs()),
+            timestamp: ctx.cache.clock.timestamp_of(ctx.block),
+            floor_price: floor.to_string(),
+            tick_spacing: spacing.to_string(),
+            clearing_price: clearing.map(|price| price.to_string()),
+            clearing_ticks: clearing
+                .filter(|_| !spacing.is_zero())
+                .map(|price| (price.saturating_sub(floor) / spacing).saturating_to()),
+            mps: schedule.map(|schedule| schedule.mps_at(ctx.block).as_u24().to()),
+            next_step_block: next_step.map(|step| step.start_block.as_u64()),
+            next_step_mps: next_step.map(|step| step.mps.as_u24().to()),
+            tracked_bids: ctx
+                .tracked_bids
+                .iter()
+                .map(|id| id.as_u256().to_string())
+                .collect(),
+            events: ctx.events.iter().map(ScriptEvent::new).collect(),
+        }
+    }
+}
+
+impl ScriptEvent {
+    fn new(event: &AuctionEvent) -> Self {
+        let mut view = Self {
+            kind: "",
+            block: event.block().as_u64(),
+            bid_id: None,
+            owner: None,
+            price: None,
+        };
+        match *event {
+            AuctionEvent::BidSubmitted {
+                bid_id,
+                owner,
+                price,
+                ..
+            } => {
+                view.kind = "bid_submitted";
+                view.bid_id = Some(bid_id.as_u256().to_string());
+                view.owner = Some(owner.to_string());
+                view.price = Some(price.as_u256().to_string());
+            }
+            AuctionEvent::BidExited { bid_id, owner, .. } => {
+                view.kind = "bid_exited";
+                view.bid_id = Some(bid_id.as_u256().to_string());
+                view.owner = Some(owner.to_string());
+            }
+            AuctionEvent::TokensClaimed { bid_id, owner, .. } => {
+                view.kind = "tokens_claimed";
+                view.bid_id = Some(bid_id.as_u256().to_string());
+                view.owner = Some(owner.to_string());
+            }
+            AuctionEvent::CheckpointUpdated { clearing_price, .. } => {
+                view.kind = "checkpoint_updated";
+                view.price = Some(clearing_price.as_u256().to_string());
+            }
+            AuctionEvent::ClearingPriceUpdated { clearing_price, .. } => {
+                view.kind = "clearing_price_updated";
+                view.price = Some(clearing_price.as_u256().to_string());
+            }
+            AuctionEvent::TokensReceived { .. } => view.kind = "tokens_received",
+            AuctionEvent::AuctionStepRecorded { .. } => view.kind = "auction_step_recorded",
+        }
+        view
+    }
+}
+
+/// An intent as returned by a script.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum ScriptIntent {
+    SubmitBid {
+        #[serde(default)]
+        max_price: Option<Number>,
+        #[serde(default)]
+        ticks: Option<Number>,
+        amount: Number,
+        #[serde(default)]
+        owner: Option<Address>,
+        #[serde(default)]
+        from: Option<Address>,
+    },
+    Exit {
+        bid_id: Number,
+    },
+    Claim {
+        bid_ids: Vec<Number>,
+    },
+    Checkpoint,
+}
+
+/// A script integer, or a decimal string for values past `i64`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Number {
+    Int(i64),
+    Text(String),
+}
+
+impl Number {
+    fn to_u256(&self) -> Result<U256, String> {
+        match self {
+            Self::Int(value) => u64::try_from(*value)
+                .map(U256::from)
+                .map_err(|_| format!("{value} is negative")),
+            Self::Text(value) => {
+                U256::from_str(value).map_err(|e| format!("invalid number `{value}`: {e}"))
+            }
+        }
+    }
+}
+
+impl ScriptIntent {
+    fn into_intent(self, config: &AuctionConfig) -> Result<Intent, String> {
+        Ok(match self {
+            Self::SubmitBid {
+                max_price,
+                ticks,
+                amount,
+                owner,
+                from,
+            } => {
+                let max_price = match (max_price, ticks) {
+                    (Some(price), None) => price.to_u256()?,
+                    (None, Some(ticks)) => Price::at_ticks_above(
+                        config.floor_price,
+                        config.tick_spacing,
+                        ticks.to_u256()?,
+                    )
+                    .ok_or("ticks put the price past uint256")?
+                    .as_u256(),
+                    _ => return Err("a bid needs exactly one of max_price and ticks".to_string()),
+                };
+                Intent::SubmitBid {
+                    max_price: Price::new(max_price),
+                    amount: CurrencyAmount::new(amount.to_u256()?),
+                    owner,
+                    from,
+                }
+            }
+            Self::Exit { bid_id } => Intent::Exit {
+                bid_id: BidId::new(bid_id.to_u256()?),
+            },
+            Self::Claim { bid_ids } => Intent::Claim {
+                bid_ids: bid_ids
+                    .iter()
+                    .map(|id| id.to_u256().map(BidId::new))
+                    .collect::<Result<_, _>>()?,
+            },
+            Self::Checkpoint => Intent::Checkpoint,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        executor::ExecutorCache,
+        types::{chain::ChainProfile, config::fixture::config, primitives::BlockNumber},
+    };
+
+    #[tokio::test]
+    async fn script_bids_once_and_remembers_it() {
+        let mut strategy = ScriptStrategy::from_source(
+            r#"
+            fn evaluate(ctx) {
+                if ctx.phase != "active" || this.sent == true { return []; }
+                this.sent = true;
+                [#{ kind: "submit_bid", ticks: 2, amount: "1000000000000000000000" }]
+            }
+            "#,
+        )
+        .unwrap();
+
+        let config = config();
+        let cache = ExecutorCache::new();
+        let ctx = EvaluationContext {
+            block: BlockNumber::new(5),
+            phase: AuctionPhase::Active {
+                blocks_remaining: 95,
+            },
+            cache: &cache,
+            tracked_bids: Vec::new(),
+            config: &config,
+            events: &[],
+            client: None,
+            chain: ChainProfile::default(),
+        };
+
+        let intents = strategy.evaluate(&ctx).await;
+        assert!(matches!(
+            intents.as_slice(),
+            [Intent::SubmitBid { max_price, amount, owner: None, from: None }]
+                if *max_price == Price::new(U256::from(30))
+                    && amount.as_u256() == U256::from(10).pow(U256::from(21))
+        ));
+        assert!(strategy.evaluate(&ctx).await.is_empty());
+    }
+
+    #[test]
+    fn rejects_scripts_without_evaluate() {
+        assert!(matches!(
+            ScriptStrategy::from_source("fn other(ctx) { [] }"),
+            Err(ScriptError::MissingEvaluate)
+        ));
+    }
+}
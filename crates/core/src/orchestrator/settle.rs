@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+
+use crate::{
+    executor::{EvaluationContext, Intent},
+    types::state::AuctionPhase,
+};
+
+use super::Strategy;
+
+/// Exits every tracked bid once the auction ends and claims them once the
+/// claim block is reached, then reports completion.
+///
+/// Bids are settled in one go when each phase is entered; the strategy does
+/// not look at bid state, so bids exited elsewhere will make the exit fail.
+#[derive(Debug, Default)]
+pub struct SettleStrategy {
+    exited: bool,
+    claimed: bool,
+}
+
+impl SettleStrategy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn exits(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        self.exited = true;
+        ctx.tracked_bids
+            .iter()
+            .map(|&bid_id| Intent::Exit { bid_id })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Strategy for SettleStrategy {
+    async fn evaluate(&mut self, _ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        Vec::new()
+    }
+
+    fn on_phase_change(
+        &mut self,
+        _previous: Option<&AuctionPhase>,
+        ctx: &EvaluationContext<'_>,
+    ) -> Vec<Intent> {
+        match ctx.phase {
+            AuctionPhase::Ended { .. } if !self.exited => self.exits(ctx),
+            AuctionPhase::Claimable if !self.claimed => {
+                let mut intents = if self.exited {
+                    Vec::new()
+                } else {
+                    self.exits(ctx)
+                };
+                if !ctx.tracked_bids.is_empty() {
+                    intents.push(Intent::Claim {
+                        bid_ids: ctx.tracked_bids.clone(),
+                    });
+                }
+                self.claimed = true;
+                intents
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn is_complete(&self, _ctx: &EvaluationContext<'_>) -> bool {
+        self.claimed
+    }
+}
@@ -0,0 +1,206 @@
+use alloy::primitives::{U256, aliases::U24};
+
+use crate::{
+    executor::EvaluationContext,
+    types::{
+        primitives::{CurrencyAmount, Mps, Price, Q96, TokenAmount, mul_div, mul_div_up},
+        risk::{RiskLimits, RiskViolation},
+    },
+};
+
+/// What held a [`BidSize`] below its target.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum SizeLimit {
+    /// Fewer tokens than the target are left to sell.
+    Supply,
+    Budget,
+    /// [`RiskLimits::max_bid_amount`].
+    BidAmount,
+    /// [`RiskLimits::max_total_spend`], less what was already spent.
+    TotalSpend,
+}
+
+/// A bid amount and the tokens it is expected to buy.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BidSize {
+    pub amount: CurrencyAmount,
+    /// Tokens `amount` buys if the bid clears at the projected price for the
+    /// rest of the auction.
+    pub tokens: TokenAmount,
+    /// The tightest cap below the target, if any.
+    pub limited_by: Option<SizeLimit>,
+}
+
+/// Sizes bids by the tokens they should buy rather than the currency they
+/// commit.
+///
+/// A bid spreads its amount over the supply still to be sold, so at a
+/// clearing price that holds until the end it buys `amount / price` tokens.
+/// The target is capped at the remaining supply, and the amount at the
+/// budget and the [`RiskLimits`] on bid amount and total spend.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SizeCalculator {
+    budget: Option<CurrencyAmount>,
+    limits: RiskLimits,
+    spent: CurrencyAmount,
+}
+
+impl SizeCalculator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Never sizes a bid above `budget`.
+    pub fn with_budget(mut self, budget: CurrencyAmount) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    pub fn with_risk_limits(mut self, limits: RiskLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Currency already committed, counted against the total spend limit.
+    pub fn with_spent(mut self, spent: CurrencyAmount) -> Self {
+        self.spent = spent;
+        self
+    }
+
+    /// Tokens still to be sold once `cumulative_mps` of `total_supply` is.
+    pub fn remaining_supply(total_supply: TokenAmount, cumulative_mps: Mps) -> TokenAmount {
+        let remaining = U256::from(cumulative_mps.remaining().as_u24().to::<u32>());
+        TokenAmount::new(
+            mul_div(total_supply.as_u256(), remaining, U256::from(Mps::FULL)).unwrap_or_default(),
+        )
+    }
+
+    /// The amount to bid at `price` to buy `target` tokens out of what is left
+    /// of `total_supply`. Fails if `price` is above the risk limits' max price.
+    pub fn size(
+        &self,
+        target: TokenAmount,
+        price: Price,
+        total_supply: TokenAmount,
+        cumulative_mps: Mps,
+    ) -> Result<BidSize, RiskViolation> {
+        if let Some(limit) = self.limits.max_price
+            && price > limit
+        {
+            return Err(RiskViolation::Price { price, limit });
+        }
+
+        let supply = Self::remaining_supply(total_supply, cumulative_mps);
+        let (target, mut limited_by) = if target.as_u256() > supply.as_u256() {
+            (supply, Some(SizeLimit::Supply))
+        } else {
+            (target, None)
+        };
+        // Rounded up, so the bid buys at least the target.
+        let mut amount = CurrencyAmount::new(
+            mul_div_up(target.as_u256(), price.as_u256(), Q96).unwrap_or(U256::MAX),
+        );
+
+        let total_spend = self
+            .limits
+            .max_total_spend
+            .map(|limit| CurrencyAmount::new(limit.as_u256().saturating_sub(self.spent.as_u256())));
+        let caps = [
+            (self.budget, SizeLimit::Budget),
+            (self.limits.max_bid_amount, SizeLimit::BidAmount),
+            (total_spend, SizeLimit::TotalSpend),
+        ];
+        for (cap, limit) in caps {
+            if let Some(cap) = cap
+                && cap < amount
+            {
+                amount = cap;
+                limited_by = Some(limit);
+            }
+        }
+
+        let tokens = match limited_by {
+            None | Some(SizeLimit::Supply) => target,
+            Some(_) => price.tokens_for(amount).unwrap_or(target),
+        };
+        Ok(BidSize {
+            amount,
+            tokens,
+            limited_by,
+        })
+    }
+
+    /// [`size`](Self::size) in the auction `ctx` evaluates, as of its latest
+    /// checkpoint.
+    pub fn size_in(
+        &self,
+        ctx: &EvaluationContext<'_>,
+        target: TokenAmount,
+        price: Price,
+    ) -> Result<BidSize, RiskViolation> {
+        let cumulative_mps = ctx
+            .cache
+            .latest_checkpoint
+            .as_ref()
+            .map_or(Mps::new(U24::ZERO), |checkpoint| checkpoint.cumulative_mps);
+        self.size(target, price, ctx.config.total_supply, cumulative_mps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::config::fixture::amount;
+
+    fn tokens(value: u64) -> TokenAmount {
+        TokenAmount::new(U256::from(value))
+    }
+
+    #[test]
+    fn sizes_to_target_within_supply_and_limits() {
+        let price = Price::new(Q96 * U256::from(2));
+        let supply = tokens(1_000);
+        let half_sold = Mps::new(U24::from(Mps::FULL / 2));
+
+        let size = SizeCalculator::new()
+            .size(tokens(100), price, supply, half_sold)
+            .unwrap();
+        assert_eq!(
+            size,
+            BidSize {
+                amount: amount(200),
+                tokens: tokens(100),
+                limited_by: None,
+            }
+        );
+
+        // Only 500 tokens are left to sell.
+        let size = SizeCalculator::new()
+            .size(tokens(800), price, supply, half_sold)
+            .unwrap();
+        assert_eq!(size.amount, amount(1_000));
+        assert_eq!(size.limited_by, Some(SizeLimit::Supply));
+
+        // The tighter of budget and remaining total spend wins.
+        let calculator = SizeCalculator::new()
+            .with_budget(amount(150))
+            .with_risk_limits(RiskLimits::new().with_max_total_spend(amount(300)))
+            .with_spent(amount(180));
+        let size = calculator
+            .size(tokens(100), price, supply, half_sold)
+            .unwrap();
+        assert_eq!(size.amount, amount(120));
+        assert_eq!(size.tokens, tokens(60));
+        assert_eq!(size.limited_by, Some(SizeLimit::TotalSpend));
+
+        let calculator = SizeCalculator::new()
+            .with_risk_limits(RiskLimits::new().with_max_price(Price::new(Q96)));
+        assert!(matches!(
+            calculator.size(tokens(100), price, supply, half_sold),
+            Err(RiskViolation::Price { .. })
+        ));
+    }
+}
@@ -0,0 +1,83 @@
+use alloy::providers::{DynProvider, Provider};
+use tracing::warn;
+
+use crate::{api::AuctionApi, types::primitives::BlockNumber};
+
+/// Holds an [`Orchestrator`](super::Orchestrator)'s intents back while the
+/// block it is handling trails the chain head by more than `max_lag` blocks,
+/// so nothing is decided on a lagging node's stale state.
+///
+/// The head is read from a second endpoint given through
+/// [`with_reference`](Self::with_reference), or else from the client's own
+/// node, which only catches a block stream falling behind its node. When the
+/// head can't be read, the block goes ahead.
+#[derive(Clone, Debug)]
+pub struct StalenessGuard {
+    max_lag: u64,
+    reference: Option<DynProvider>,
+}
+
+impl StalenessGuard {
+    pub fn new(max_lag: u64) -> Self {
+        Self {
+            max_lag,
+            reference: None,
+        }
+    }
+
+    /// Read the chain head from `provider`, ideally another node than the
+    /// one the blocks come from.
+    pub fn with_reference(mut self, provider: DynProvider) -> Self {
+        self.reference = Some(provider);
+        self
+    }
+
+    pub fn max_lag(&self) -> u64 {
+        self.max_lag
+    }
+
+    /// How far `block` trails the head, if that is more than allowed.
+    pub(crate) async fn stale_by<C: AuctionApi>(
+        &self,
+        client: &C,
+        block: BlockNumber,
+    ) -> Option<u64> {
+        let head = match &self.reference {
+            Some(reference) => reference
+                .get_block_number()
+                .await
+                .map_err(|error| error.to_string()),
+            None => client
+                .fetch_block_number()
+                .await
+                .map(|head| head.as_u64())
+                .map_err(|error| error.to_string()),
+        };
+        match head {
+            Ok(head) => self.excess(head, block),
+            Err(error) => {
+                warn!(%error, "can't read the chain head; not checking for lag");
+                None
+            }
+        }
+    }
+
+    fn excess(&self, head: u64, block: BlockNumber) -> Option<u64> {
+        let lag = head.saturating_sub(block.as_u64());
+        (lag > self.max_lag).then_some(lag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_lag_past_the_tolerance_is_stale() {
+        let guard = StalenessGuard::new(2);
+        assert_eq!(guard.excess(102, BlockNumber::new(100)), None);
+        assert_eq!(guard.excess(103, BlockNumber::new(100)), Some(3));
+        // A reference behind the stream is not lag.
+        assert_eq!(guard.excess(90, BlockNumber::new(100)), None);
+    }
+}
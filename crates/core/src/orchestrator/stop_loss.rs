@@ -0,0 +1,257 @@
+use std::collections::HashSet;
+
+use alloy::primitives::U256;
+use async_trait::async_trait;
+use tracing::{debug, info, warn};
+
+use crate::{
+    executor::{EvaluationContext, Intent},
+    types::{
+        bid::{Bid, Moneyness},
+        primitives::{BlockNumber, CurrencyAmount, Price},
+        state::{AuctionPhase, GraduationStatus},
+    },
+};
+
+use super::Strategy;
+
+/// Exits tracked bids the clearing price has caught up with once it passes
+/// `ceiling`, freeing their unfilled currency instead of waiting for the
+/// auction to end.
+///
+/// Outbid bids are exited through `exitPartiallyFilledBid` as soon as the
+/// auction has graduated, which the contract requires of exits before the
+/// end. Bids right at the clearing price can only be exited once the auction
+/// ends, so they are left until then. Bids are read through `ctx.client` on
+/// blocks where the clearing price is above the ceiling, so nothing happens
+/// in a backtest.
+///
+/// With [`with_redeploy`](Self::with_redeploy), each exited bid's amount is
+/// bid again a number of ticks above the clearing price while the auction is
+/// active, out of a separate budget. The strategy is complete once the
+/// auction is claimable.
+#[derive(Debug)]
+pub struct StopLossStrategy {
+    ceiling: Price,
+    redeploy: Option<Redeploy>,
+    exited: HashSet<U256>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Redeploy {
+    budget: CurrencyAmount,
+    ticks: u64,
+    spent: U256,
+}
+
+impl StopLossStrategy {
+    /// Exits bids at or below the clearing price once it is above `ceiling`.
+    pub fn new(ceiling: Price) -> Self {
+        Self {
+            ceiling,
+            redeploy: None,
+            exited: HashSet::new(),
+        }
+    }
+
+    /// Rebids the amount of each exited bid `ticks` ticks above the clearing
+    /// price, spending at most `budget` across all of them.
+    pub fn with_redeploy(mut self, budget: CurrencyAmount, ticks: u64) -> Self {
+        self.redeploy = Some(Redeploy {
+            budget,
+            ticks: ticks.max(1),
+            spent: U256::ZERO,
+        });
+        self
+    }
+
+    /// Currency rebid after exits so far.
+    pub fn redeployed(&self) -> CurrencyAmount {
+        CurrencyAmount::new(self.redeploy.map_or(U256::ZERO, |redeploy| redeploy.spent))
+    }
+
+    /// Exits for `bids` at `clearing_price`, each followed by its
+    /// replacement if redeploying.
+    fn exits(
+        &mut self,
+        ctx: &EvaluationContext<'_>,
+        bids: &[Bid],
+        clearing_price: Price,
+    ) -> Vec<Intent> {
+        let ended = !matches!(ctx.phase, AuctionPhase::Active { .. });
+        let mut intents = Vec::new();
+        for bid in bids {
+            if bid.exited_block.is_some() || self.exited.contains(&bid.id.as_u256()) {
+                continue;
+            }
+            let exitable = match bid.moneyness(clearing_price) {
+                Moneyness::ITM => false,
+                Moneyness::ATM => ended,
+                Moneyness::OTM => true,
+            };
+            if !exitable {
+                continue;
+            }
+
+            self.exited.insert(bid.id.as_u256());
+            info!(
+                bid_id = %bid.id.as_u256(),
+                max_price = %bid.max_price.as_u256(),
+                clearing_price = %clearing_price.as_u256(),
+                "clearing price is past the ceiling; exiting"
+            );
+            intents.push(Intent::Exit { bid_id: bid.id });
+
+            if !ended && let Some(intent) = self.replacement(ctx, bid, clearing_price) {
+                intents.push(intent);
+            }
+        }
+        intents
+    }
+
+    fn replacement(
+        &mut self,
+        ctx: &EvaluationContext<'_>,
+        bid: &Bid,
+        clearing_price: Price,
+    ) -> Option<Intent> {
+        let redeploy = self.redeploy.as_mut()?;
+        let price = ctx.config.nth_tick_above(clearing_price, redeploy.ticks)?;
+        let amount = bid
+            .amount
+            .as_u256()
+            .min(redeploy.budget.as_u256().saturating_sub(redeploy.spent));
+        if amount.is_zero() {
+            debug!(bid_id = %bid.id.as_u256(), "redeploy budget exhausted");
+            return None;
+        }
+        redeploy.spent += amount;
+        let last_bid_block = BlockNumber::new(ctx.config.end_block.as_u64().saturating_sub(1));
+        Some(
+            Intent::SubmitBid {
+                max_price: price,
+                amount: CurrencyAmount::new(amount),
+                owner: None,
+                from: None,
+            }
+            .by(last_bid_block),
+        )
+    }
+}
+
+#[async_trait]
+impl Strategy for StopLossStrategy {
+    async fn evaluate(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        if !matches!(
+            ctx.phase,
+            AuctionPhase::Active { .. } | AuctionPhase::Ended { .. }
+        ) || !matches!(ctx.cache.graduated, GraduationStatus::Graduated)
+        {
+            return Vec::new();
+        }
+        let Some(clearing_price) = ctx
+            .cache
+            .latest_checkpoint
+            .as_ref()
+            .map(|checkpoint| checkpoint.clearing_price)
+            .filter(|price| *price > self.ceiling)
+        else {
+            return Vec::new();
+        };
+        let Some(client) = ctx.client else {
+            return Vec::new();
+        };
+
+        match client.fetch_bids(&ctx.tracked_bids).await {
+            Ok(bids) => self.exits(ctx, &bids, clearing_price),
+            Err(error) => {
+                warn!(%error, "can't read bids to check against the ceiling");
+                Vec::new()
+            }
+        }
+    }
+
+    fn is_complete(&self, ctx: &EvaluationContext<'_>) -> bool {
+        matches!(ctx.phase, AuctionPhase::Claimable)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{Address, aliases::U24};
+
+    use super::*;
+    use crate::{
+        executor::ExecutorCache,
+        types::{
+            chain::ChainProfile,
+            config::fixture::{amount, config, price},
+            primitives::{BidId, Mps, TokenAmount},
+        },
+    };
+
+    fn bid(id: u64, max_price: u64) -> Bid {
+        Bid {
+            id: BidId::new(U256::from(id)),
+            owner: Address::ZERO,
+            max_price: price(max_price),
+            amount: amount(100),
+            start_block: BlockNumber::new(0),
+            start_cumulative_mps: Mps::new(U24::ZERO),
+            exited_block: None,
+            tokens_filled: TokenAmount::new(U256::ZERO),
+        }
+    }
+
+    #[test]
+    fn exits_outbid_bids_and_redeploys_within_budget() {
+        let config = config();
+        let cache = ExecutorCache::new();
+        let ctx = EvaluationContext {
+            block: BlockNumber::new(50),
+            phase: AuctionPhase::Active {
+                blocks_remaining: 50,
+            },
+            cache: &cache,
+            tracked_bids: Vec::new(),
+            config: &config,
+            events: &[],
+            client: None,
+            chain: ChainProfile::default(),
+        };
+        let mut strategy = StopLossStrategy::new(price(50)).with_redeploy(amount(150), 2);
+        let bids = [bid(1, 40), bid(2, 60), bid(3, 70), bid(4, 90)];
+
+        let intents: Vec<_> = strategy
+            .exits(&ctx, &bids, price(60))
+            .into_iter()
+            .map(Intent::into_inner)
+            .collect();
+        // Bid 2 is at the clearing price and waits for the end; bid 4 is
+        // still in the money.
+        assert_eq!(
+            intents,
+            [
+                Intent::Exit {
+                    bid_id: BidId::new(U256::from(1)),
+                },
+                Intent::SubmitBid {
+                    max_price: price(80),
+                    amount: amount(100),
+                    owner: None,
+                    from: None,
+                }
+            ]
+        );
+
+        // Bids 2 and 3 are outbid now, but only 50 of the redeploy budget is
+        // left for them; bid 1 is not exited twice.
+        let kinds: Vec<_> = strategy
+            .exits(&ctx, &bids, price(80))
+            .iter()
+            .map(Intent::kind)
+            .collect();
+        assert_eq!(kinds, ["exit", "submit_bid", "exit"]);
+        assert_eq!(strategy.redeployed(), amount(150));
+    }
+}
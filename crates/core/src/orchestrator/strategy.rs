@@ -0,0 +1,955 @@
+use async_trait::async_trait;
+use tracing::{debug, info};
+
+use crate::{
+    executor::{EvaluationContext, Intent},
+    types::{
+        clock::TimeTarget,
+        primitives::BlockNumber,
+        state::{AuctionPhase, GraduationStatus},
+    },
+};
+
+/// Decides which intents to execute at each block.
+///
+/// Besides `evaluate`, which runs every block, the orchestrator calls the
+/// lifecycle hooks once at well-defined moments. Intents they return execute
+/// before that block's `evaluate` intents.
+///
+/// `evaluate` may read the chain through `ctx.client` and keep whatever
+/// state it needs on `self` between blocks.
+#[async_trait]
+pub trait Strategy: Send {
+    async fn evaluate(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent>;
+
+    /// Called on the first block and whenever the auction phase changes.
+    /// `previous` is `None` on the first block.
+    fn on_phase_change(
+        &mut self,
+        _previous: Option<&AuctionPhase>,
+        _ctx: &EvaluationContext<'_>,
+    ) -> Vec<Intent> {
+        Vec::new()
+    }
+
+    /// Block counts before `end_block` at which `on_blocks_before_end` fires.
+    fn end_countdown(&self) -> Vec<u64> {
+        Vec::new()
+    }
+
+    /// Called once for each `end_countdown` entry, on the first block at most
+    /// `blocks` before the end, even if the exact block was skipped.
+    fn on_blocks_before_end(&mut self, _blocks: u64, _ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        Vec::new()
+    }
+
+    /// Called on blocks where `ctx.events` is non-empty, before `evaluate`.
+    fn on_events(&mut self, _ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        Vec::new()
+    }
+
+    /// Whether the strategy has nothing left to do. The orchestrator stops
+    /// once this returns `true`.
+    fn is_complete(&self, _ctx: &EvaluationContext<'_>) -> bool {
+        false
+    }
+}
+
+#[async_trait]
+impl<S> Strategy for Box<S>
+where
+    S: Strategy + ?Sized,
+{
+    async fn evaluate(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        (**self).evaluate(ctx).await
+    }
+
+    fn on_phase_change(
+        &mut self,
+        previous: Option<&AuctionPhase>,
+        ctx: &EvaluationContext<'_>,
+    ) -> Vec<Intent> {
+        (**self).on_phase_change(previous, ctx)
+    }
+
+    fn end_countdown(&self) -> Vec<u64> {
+        (**self).end_countdown()
+    }
+
+    fn on_blocks_before_end(&mut self, blocks: u64, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        (**self).on_blocks_before_end(blocks, ctx)
+    }
+
+    fn on_events(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        (**self).on_events(ctx)
+    }
+
+    fn is_complete(&self, ctx: &EvaluationContext<'_>) -> bool {
+        (**self).is_complete(ctx)
+    }
+}
+
+/// Wraps a strategy and pokes `checkpoint()` while the auction is active and
+/// the last checkpoint is more than `max_age` blocks old.
+///
+/// The age is that of the latest checkpoint on chain, which the executor
+/// reads every block, so checkpoints made by other bidders count too. The
+/// poke is placed ahead of the inner strategy's intents so they execute
+/// against fresh state. If no checkpoint has been seen yet it is treated as
+/// stale.
+pub struct AutoCheckpoint<S> {
+    inner: S,
+    max_age: u64,
+}
+
+impl<S> AutoCheckpoint<S>
+where
+    S: Strategy,
+{
+    pub fn new(inner: S, max_age: u64) -> Self {
+        Self { inner, max_age }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    fn is_stale(&self, ctx: &EvaluationContext<'_>) -> bool {
+        ctx.cache
+            .checkpoint_age(ctx.block)
+            .is_none_or(|age| age > self.max_age)
+    }
+}
+
+#[async_trait]
+impl<S> Strategy for AutoCheckpoint<S>
+where
+    S: Strategy,
+{
+    async fn evaluate(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        let mut intents = self.inner.evaluate(ctx).await;
+
+        let active = matches!(ctx.phase, AuctionPhase::Active { .. });
+        let already_poking = intents
+            .iter()
+            .any(|intent| matches!(intent, Intent::Checkpoint));
+
+        if active && !already_poking && self.is_stale(ctx) {
+            intents.insert(0, Intent::Checkpoint);
+        }
+
+        intents
+    }
+
+    fn on_phase_change(
+        &mut self,
+        previous: Option<&AuctionPhase>,
+        ctx: &EvaluationContext<'_>,
+    ) -> Vec<Intent> {
+        self.inner.on_phase_change(previous, ctx)
+    }
+
+    fn end_countdown(&self) -> Vec<u64> {
+        self.inner.end_countdown()
+    }
+
+    fn on_blocks_before_end(&mut self, blocks: u64, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        self.inner.on_blocks_before_end(blocks, ctx)
+    }
+
+    fn on_events(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        self.inner.on_events(ctx)
+    }
+
+    fn is_complete(&self, ctx: &EvaluationContext<'_>) -> bool {
+        self.inner.is_complete(ctx)
+    }
+}
+
+/// Holds a strategy back until a wall clock [`TimeTarget`], e.g. `18:00
+/// UTC` or `10m before end`, then runs it as usual.
+///
+/// The target's block is re-estimated every block from the executor's
+/// [`BlockClock`](crate::BlockClock), so it follows the chain's actual pace;
+/// a time of day is pinned to its next occurrence the first time it can be
+/// resolved. Once started, the inner strategy first sees `on_phase_change`
+/// with `previous` of `None` as if it were the first block. Countdown and
+/// event hooks that fire while waiting are dropped.
+pub struct Scheduled<S> {
+    inner: S,
+    target: TimeTarget,
+    started: bool,
+}
+
+impl<S> Scheduled<S>
+where
+    S: Strategy,
+{
+    pub fn new(inner: S, target: TimeTarget) -> Self {
+        Self {
+            inner,
+            target,
+            started: false,
+        }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn has_started(&self) -> bool {
+        self.started
+    }
+
+    /// Starts the inner strategy once the target block is reached, returning
+    /// its first phase change intents; `None` while still waiting.
+    fn start(&mut self, ctx: &EvaluationContext<'_>) -> Option<Vec<Intent>> {
+        if self.started {
+            return Some(Vec::new());
+        }
+        let clock = &ctx.cache.clock;
+        self.target = self.target.pin(clock)?;
+        let block = self.target.block(clock, ctx.config)?;
+        if ctx.block < block {
+            return None;
+        }
+        debug!(target = %self.target, "scheduled strategy started");
+        self.started = true;
+        Some(self.inner.on_phase_change(None, ctx))
+    }
+}
+
+#[async_trait]
+impl<S> Strategy for Scheduled<S>
+where
+    S: Strategy,
+{
+    async fn evaluate(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        let Some(mut intents) = self.start(ctx) else {
+            return Vec::new();
+        };
+        intents.extend(self.inner.evaluate(ctx).await);
+        intents
+    }
+
+    fn on_phase_change(
+        &mut self,
+        previous: Option<&AuctionPhase>,
+        ctx: &EvaluationContext<'_>,
+    ) -> Vec<Intent> {
+        let was_started = self.started;
+        match self.start(ctx) {
+            Some(_) if was_started => self.inner.on_phase_change(previous, ctx),
+            Some(intents) => intents,
+            None => Vec::new(),
+        }
+    }
+
+    fn end_countdown(&self) -> Vec<u64> {
+        self.inner.end_countdown()
+    }
+
+    fn on_blocks_before_end(&mut self, blocks: u64, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        let Some(mut intents) = self.start(ctx) else {
+            return Vec::new();
+        };
+        intents.extend(self.inner.on_blocks_before_end(blocks, ctx));
+        intents
+    }
+
+    fn on_events(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        let Some(mut intents) = self.start(ctx) else {
+            return Vec::new();
+        };
+        intents.extend(self.inner.on_events(ctx));
+        intents
+    }
+
+    fn is_complete(&self, ctx: &EvaluationContext<'_>) -> bool {
+        self.started && self.inner.is_complete(ctx)
+    }
+}
+
+/// Runs strategies one after another, e.g. accumulate then exit.
+///
+/// Only the current stage is called. Once it reports completion the next
+/// stage takes over on the following hook, starting with `on_phase_change`
+/// with `previous` of `None` as if it were the first block. The sequence is
+/// complete when its last stage is.
+pub struct Sequence {
+    stages: Vec<Box<dyn Strategy>>,
+    current: usize,
+}
+
+impl Sequence {
+    pub fn new(first: impl Strategy + 'static) -> Self {
+        Self {
+            stages: vec![Box::new(first)],
+            current: 0,
+        }
+    }
+
+    /// Appends a stage that runs once the previous ones are complete.
+    pub fn then(mut self, stage: impl Strategy + 'static) -> Self {
+        self.stages.push(Box::new(stage));
+        self
+    }
+
+    /// Index of the stage currently running.
+    pub fn stage(&self) -> usize {
+        self.current
+    }
+
+    /// Moves past completed stages, returning whether a new one was entered.
+    fn advance(&mut self, ctx: &EvaluationContext<'_>) -> bool {
+        let start = self.current;
+        while self.current + 1 < self.stages.len() && self.stages[self.current].is_complete(ctx) {
+            self.current += 1;
+            debug!(stage = self.current, "sequence advanced");
+        }
+        self.current != start
+    }
+
+    /// Advances, letting a newly entered stage see the current phase first.
+    fn enter(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        if self.advance(ctx) {
+            self.stages[self.current].on_phase_change(None, ctx)
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[async_trait]
+impl Strategy for Sequence {
+    async fn evaluate(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        let mut intents = self.enter(ctx);
+        intents.extend(self.stages[self.current].evaluate(ctx).await);
+        intents
+    }
+
+    fn on_phase_change(
+        &mut self,
+        previous: Option<&AuctionPhase>,
+        ctx: &EvaluationContext<'_>,
+    ) -> Vec<Intent> {
+        self.advance(ctx);
+        self.stages[self.current].on_phase_change(previous, ctx)
+    }
+
+    fn end_countdown(&self) -> Vec<u64> {
+        self.stages[self.current].end_countdown()
+    }
+
+    fn on_blocks_before_end(&mut self, blocks: u64, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        let mut intents = self.enter(ctx);
+        intents.extend(self.stages[self.current].on_blocks_before_end(blocks, ctx));
+        intents
+    }
+
+    fn on_events(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        let mut intents = self.enter(ctx);
+        intents.extend(self.stages[self.current].on_events(ctx));
+        intents
+    }
+
+    fn is_complete(&self, ctx: &EvaluationContext<'_>) -> bool {
+        self.current + 1 == self.stages.len() && self.stages[self.current].is_complete(ctx)
+    }
+}
+
+/// Lets at most one intent through every `blocks` blocks, across `evaluate`
+/// and every hook. Intents over the limit are dropped, not queued.
+pub struct RateLimited<S> {
+    inner: S,
+    blocks: u64,
+    last: Option<BlockNumber>,
+}
+
+impl<S> RateLimited<S>
+where
+    S: Strategy,
+{
+    pub fn new(inner: S, blocks: u64) -> Self {
+        Self {
+            inner,
+            blocks,
+            last: None,
+        }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    fn limit(&mut self, mut intents: Vec<Intent>, block: BlockNumber) -> Vec<Intent> {
+        if intents.is_empty() {
+            return intents;
+        }
+        let waiting = self
+            .last
+            .is_some_and(|last| block.as_u64().saturating_sub(last.as_u64()) < self.blocks);
+        let keep = usize::from(!waiting);
+        if intents.len() > keep {
+            debug!(
+                dropped = intents.len() - keep,
+                block = block.as_u64(),
+                "rate limit dropped intents"
+            );
+        }
+        intents.truncate(keep);
+        if keep == 1 {
+            self.last = Some(block);
+        }
+        intents
+    }
+}
+
+#[async_trait]
+impl<S> Strategy for RateLimited<S>
+where
+    S: Strategy,
+{
+    async fn evaluate(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        let intents = self.inner.evaluate(ctx).await;
+        self.limit(intents, ctx.block)
+    }
+
+    fn on_phase_change(
+        &mut self,
+        previous: Option<&AuctionPhase>,
+        ctx: &EvaluationContext<'_>,
+    ) -> Vec<Intent> {
+        let intents = self.inner.on_phase_change(previous, ctx);
+        self.limit(intents, ctx.block)
+    }
+
+    fn end_countdown(&self) -> Vec<u64> {
+        self.inner.end_countdown()
+    }
+
+    fn on_blocks_before_end(&mut self, blocks: u64, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        let intents = self.inner.on_blocks_before_end(blocks, ctx);
+        self.limit(intents, ctx.block)
+    }
+
+    fn on_events(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        let intents = self.inner.on_events(ctx);
+        self.limit(intents, ctx.block)
+    }
+
+    fn is_complete(&self, ctx: &EvaluationContext<'_>) -> bool {
+        self.inner.is_complete(ctx)
+    }
+}
+
+/// Calls the inner strategy only in phases `guard` accepts. Outside them it
+/// produces nothing, though its completion is still reported.
+///
+/// The inner `on_phase_change` sees the last phase it was shown as
+/// `previous`, so entering a guarded phase looks like a phase change to it.
+pub struct Filtered<S, F> {
+    inner: S,
+    guard: F,
+    last_seen: Option<AuctionPhase>,
+}
+
+impl<S, F> Filtered<S, F>
+where
+    S: Strategy,
+    F: Fn(&AuctionPhase) -> bool + Send,
+{
+    pub fn new(inner: S, guard: F) -> Self {
+        Self {
+            inner,
+            guard,
+            last_seen: None,
+        }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl<S, F> Strategy for Filtered<S, F>
+where
+    S: Strategy,
+    F: Fn(&AuctionPhase) -> bool + Send,
+{
+    async fn evaluate(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        if !(self.guard)(&ctx.phase) {
+            return Vec::new();
+        }
+        self.inner.evaluate(ctx).await
+    }
+
+    fn on_phase_change(
+        &mut self,
+        _previous: Option<&AuctionPhase>,
+        ctx: &EvaluationContext<'_>,
+    ) -> Vec<Intent> {
+        if !(self.guard)(&ctx.phase) {
+            return Vec::new();
+        }
+        let previous = self.last_seen.replace(ctx.phase.clone());
+        self.inner.on_phase_change(previous.as_ref(), ctx)
+    }
+
+    fn end_countdown(&self) -> Vec<u64> {
+        self.inner.end_countdown()
+    }
+
+    fn on_blocks_before_end(&mut self, blocks: u64, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        if !(self.guard)(&ctx.phase) {
+            return Vec::new();
+        }
+        self.inner.on_blocks_before_end(blocks, ctx)
+    }
+
+    fn on_events(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        if !(self.guard)(&ctx.phase) {
+            return Vec::new();
+        }
+        self.inner.on_events(ctx)
+    }
+
+    fn is_complete(&self, ctx: &EvaluationContext<'_>) -> bool {
+        self.inner.is_complete(ctx)
+    }
+}
+
+/// Drops the inner strategy's bids while the auction is not on course to
+/// graduate, so no currency is locked in an auction that will refund it
+/// only at the end. Exits, claims and checkpoints pass through.
+///
+/// Bids go ahead once the auction has graduated, or while the
+/// [`raise_progress`](EvaluationContext::raise_progress) projection is at
+/// least `threshold` of the requirement, e.g. `1.0` to require being on
+/// course and `0.8` to allow some shortfall. Without a projection, before
+/// any supply is sold or when the requirement is unknown, they go ahead too.
+pub struct OnlyIfGraduationLikely<S> {
+    inner: S,
+    threshold: f64,
+}
+
+impl<S> OnlyIfGraduationLikely<S>
+where
+    S: Strategy,
+{
+    pub fn new(inner: S, threshold: f64) -> Self {
+        Self { inner, threshold }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    fn screen(&self, mut intents: Vec<Intent>, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        if matches!(ctx.graduation(), GraduationStatus::Graduated) {
+            return intents;
+        }
+        let Some(ratio) = ctx
+            .raise_progress()
+            .and_then(|progress| progress.projected_ratio())
+        else {
+            return intents;
+        };
+        if ratio >= self.threshold {
+            return intents;
+        }
+        let before = intents.len();
+        intents.retain(|intent| intent.kind() != "submit_bid");
+        if intents.len() < before {
+            info!(
+                dropped = before - intents.len(),
+                projected = ratio,
+                threshold = self.threshold,
+                "graduation unlikely; holding back bids"
+            );
+        }
+        intents
+    }
+}
+
+#[async_trait]
+impl<S> Strategy for OnlyIfGraduationLikely<S>
+where
+    S: Strategy,
+{
+    async fn evaluate(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        let intents = self.inner.evaluate(ctx).await;
+        self.screen(intents, ctx)
+    }
+
+    fn on_phase_change(
+        &mut self,
+        previous: Option<&AuctionPhase>,
+        ctx: &EvaluationContext<'_>,
+    ) -> Vec<Intent> {
+        let intents = self.inner.on_phase_change(previous, ctx);
+        self.screen(intents, ctx)
+    }
+
+    fn end_countdown(&self) -> Vec<u64> {
+        self.inner.end_countdown()
+    }
+
+    fn on_blocks_before_end(&mut self, blocks: u64, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        let intents = self.inner.on_blocks_before_end(blocks, ctx);
+        self.screen(intents, ctx)
+    }
+
+    fn on_events(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        let intents = self.inner.on_events(ctx);
+        self.screen(intents, ctx)
+    }
+
+    fn is_complete(&self, ctx: &EvaluationContext<'_>) -> bool {
+        self.inner.is_complete(ctx)
+    }
+}
+
+/// Runs several strategies side by side, concatenating their intents in the
+/// order they were added. Complete as soon as any member is.
+pub struct Any {
+    members: Members,
+}
+
+/// Runs several strategies side by side, concatenating their intents in the
+/// order they were added. Members that complete stop being called; the whole
+/// is complete once every member is.
+pub struct All {
+    members: Members,
+}
+
+impl Any {
+    pub fn new() -> Self {
+        Self {
+            members: Members::default(),
+        }
+    }
+
+    pub fn with(mut self, member: impl Strategy + 'static) -> Self {
+        self.members.0.push(Box::new(member));
+        self
+    }
+}
+
+impl Default for Any {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl All {
+    pub fn new() -> Self {
+        Self {
+            members: Members::default(),
+        }
+    }
+
+    pub fn with(mut self, member: impl Strategy + 'static) -> Self {
+        self.members.0.push(Box::new(member));
+        self
+    }
+}
+
+impl Default for All {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Members of an [`Any`] or [`All`], calling every one that `active`
+/// accepts.
+#[derive(Default)]
+struct Members(Vec<Box<dyn Strategy>>);
+
+impl Members {
+    async fn evaluate(
+        &mut self,
+        ctx: &EvaluationContext<'_>,
+        active: fn(&dyn Strategy, &EvaluationContext<'_>) -> bool,
+    ) -> Vec<Intent> {
+        let mut intents = Vec::new();
+        for member in &mut self.0 {
+            if active(member.as_ref(), ctx) {
+                intents.extend(member.evaluate(ctx).await);
+            }
+        }
+        intents
+    }
+
+    fn each(
+        &mut self,
+        ctx: &EvaluationContext<'_>,
+        active: fn(&dyn Strategy, &EvaluationContext<'_>) -> bool,
+        mut hook: impl FnMut(&mut Box<dyn Strategy>) -> Vec<Intent>,
+    ) -> Vec<Intent> {
+        self.0
+            .iter_mut()
+            .filter(|member| active(member.as_ref(), ctx))
+            .flat_map(&mut hook)
+            .collect()
+    }
+
+    fn end_countdown(&self) -> Vec<u64> {
+        let mut countdown: Vec<u64> = self
+            .0
+            .iter()
+            .flat_map(|member| member.end_countdown())
+            .collect();
+        countdown.sort_unstable();
+        countdown.dedup();
+        countdown
+    }
+}
+
+fn always(_: &dyn Strategy, _: &EvaluationContext<'_>) -> bool {
+    true
+}
+
+fn unfinished(member: &dyn Strategy, ctx: &EvaluationContext<'_>) -> bool {
+    !member.is_complete(ctx)
+}
+
+#[async_trait]
+impl Strategy for Any {
+    async fn evaluate(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        self.members.evaluate(ctx, always).await
+    }
+
+    fn on_phase_change(
+        &mut self,
+        previous: Option<&AuctionPhase>,
+        ctx: &EvaluationContext<'_>,
+    ) -> Vec<Intent> {
+        self.members
+            .each(ctx, always, |member| member.on_phase_change(previous, ctx))
+    }
+
+    fn end_countdown(&self) -> Vec<u64> {
+        self.members.end_countdown()
+    }
+
+    fn on_blocks_before_end(&mut self, blocks: u64, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        self.members.each(ctx, always, |member| {
+            if member.end_countdown().contains(&blocks) {
+                member.on_blocks_before_end(blocks, ctx)
+            } else {
+                Vec::new()
+            }
+        })
+    }
+
+    fn on_events(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        self.members
+            .each(ctx, always, |member| member.on_events(ctx))
+    }
+
+    fn is_complete(&self, ctx: &EvaluationContext<'_>) -> bool {
+        self.members.0.iter().any(|member| member.is_complete(ctx))
+    }
+}
+
+#[async_trait]
+impl Strategy for All {
+    async fn evaluate(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        self.members.evaluate(ctx, unfinished).await
+    }
+
+    fn on_phase_change(
+        &mut self,
+        previous: Option<&AuctionPhase>,
+        ctx: &EvaluationContext<'_>,
+    ) -> Vec<Intent> {
+        self.members.each(ctx, unfinished, |member| {
+            member.on_phase_change(previous, ctx)
+        })
+    }
+
+    fn end_countdown(&self) -> Vec<u64> {
+        self.members.end_countdown()
+    }
+
+    fn on_blocks_before_end(&mut self, blocks: u64, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        self.members.each(ctx, unfinished, |member| {
+            if member.end_countdown().contains(&blocks) {
+                member.on_blocks_before_end(blocks, ctx)
+            } else {
+                Vec::new()
+            }
+        })
+    }
+
+    fn on_events(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        self.members
+            .each(ctx, unfinished, |member| member.on_events(ctx))
+    }
+
+    fn is_complete(&self, ctx: &EvaluationContext<'_>) -> bool {
+        self.members.0.iter().all(|member| member.is_complete(ctx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{U256, aliases::U24};
+
+    use super::*;
+    use crate::{
+        executor::{ExecutorCache, IntentExecutor, mock::MockAuction},
+        types::{
+            chain::ChainProfile,
+            config::fixture::config,
+            primitives::{CurrencyAmount, Mps, Price, TokenAmount},
+            state::AuctionMetrics,
+        },
+    };
+
+    /// Pokes a checkpoint on each of its next `left` evaluations.
+    struct Pokes {
+        left: u32,
+    }
+
+    #[async_trait]
+    impl Strategy for Pokes {
+        async fn evaluate(&mut self, _ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+            if self.left == 0 {
+                return Vec::new();
+            }
+            self.left -= 1;
+            vec![Intent::Checkpoint]
+        }
+
+        fn is_complete(&self, _ctx: &EvaluationContext<'_>) -> bool {
+            self.left == 0
+        }
+    }
+
+    /// Number of intents `strategy` produces on each of `blocks`.
+    async fn run(strategy: &mut impl Strategy, blocks: std::ops::Range<u64>) -> Vec<usize> {
+        let config = config();
+        let cache = ExecutorCache::new();
+        let mut counts = Vec::new();
+        for block in blocks {
+            let ctx = EvaluationContext {
+                block: BlockNumber::new(block),
+                phase: AuctionPhase::Active {
+                    blocks_remaining: 100 - block,
+                },
+                cache: &cache,
+                tracked_bids: Vec::new(),
+                config: &config,
+                events: &[],
+                client: None,
+                chain: ChainProfile::default(),
+            };
+            counts.push(strategy.evaluate(&ctx).await.len());
+        }
+        counts
+    }
+
+    #[tokio::test]
+    async fn sequence_moves_on_once_a_stage_completes() {
+        let mut sequence = Sequence::new(Pokes { left: 1 }).then(Pokes { left: 2 });
+        assert_eq!(run(&mut sequence, 0..4).await, [1, 1, 1, 0]);
+        assert_eq!(sequence.stage(), 1);
+    }
+
+    #[tokio::test]
+    async fn rate_limit_spaces_out_intents() {
+        let mut limited = RateLimited::new(Pokes { left: 10 }, 3);
+        assert_eq!(run(&mut limited, 0..7).await, [1, 0, 0, 1, 0, 0, 1]);
+    }
+
+    #[tokio::test]
+    async fn auto_checkpoint_leaves_fresh_chain_checkpoints_alone() {
+        let mut executor = IntentExecutor::new(MockAuction::new());
+        let mut auto = AutoCheckpoint::new(Pokes { left: 0 }, 5);
+        let mut kinds = async |executor: &mut IntentExecutor<MockAuction>, block: u64| {
+            let block = BlockNumber::new(block);
+            executor.refresh(block).await.unwrap();
+            auto.evaluate(&executor.context(block, &[]))
+                .await
+                .iter()
+                .map(Intent::kind)
+                .collect::<Vec<_>>()
+        };
+
+        // The chain's checkpoint at block 10 is too old by block 20.
+        assert_eq!(kinds(&mut executor, 20).await, ["checkpoint"]);
+        // Someone else checkpoints at block 24.
+        executor
+            .client()
+            .checkpoint_at(24, Price::new(U256::from(10)));
+        assert!(kinds(&mut executor, 25).await.is_empty());
+        assert!(kinds(&mut executor, 29).await.is_empty());
+        assert_eq!(kinds(&mut executor, 30).await, ["checkpoint"]);
+    }
+
+    /// Bids and pokes a checkpoint on every evaluation.
+    struct Bids;
+
+    #[async_trait]
+    impl Strategy for Bids {
+        async fn evaluate(&mut self, _ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+            vec![
+                Intent::SubmitBid {
+                    max_price: Price::new(U256::from(20)),
+                    amount: CurrencyAmount::new(U256::from(100)),
+                    owner: None,
+                    from: None,
+                },
+                Intent::Checkpoint,
+            ]
+        }
+    }
+
+    #[tokio::test]
+    async fn graduation_guard_holds_back_bids_when_short() {
+        // 100 raised from half the supply heads for 200 of the 1_000 needed.
+        let auction = MockAuction::new();
+        *auction.metrics.lock().unwrap() = AuctionMetrics {
+            currency_raised: CurrencyAmount::new(U256::from(100)),
+            total_cleared: TokenAmount::new(U256::ZERO),
+            required_currency_raised: Some(CurrencyAmount::new(U256::from(1_000))),
+        };
+        auction.checkpoint.lock().unwrap().cumulative_mps = Mps::new(U24::from(Mps::FULL / 2));
+        let mut executor = IntentExecutor::new(auction);
+        let mut guarded = OnlyIfGraduationLikely::new(Bids, 0.8);
+
+        async fn kinds(
+            strategy: &mut impl Strategy,
+            executor: &mut IntentExecutor<MockAuction>,
+            block: u64,
+        ) -> Vec<&'static str> {
+            let block = BlockNumber::new(block);
+            executor.refresh(block).await.unwrap();
+            strategy
+                .evaluate(&executor.context(block, &[]))
+                .await
+                .iter()
+                .map(Intent::kind)
+                .collect()
+        }
+
+        assert_eq!(kinds(&mut guarded, &mut executor, 50).await, ["checkpoint"]);
+        *executor.client().graduation.lock().unwrap() = GraduationStatus::Graduated;
+        assert_eq!(
+            kinds(&mut guarded, &mut executor, 51).await,
+            ["submit_bid", "checkpoint"]
+        );
+    }
+
+    #[tokio::test]
+    async fn scheduled_waits_for_its_time() {
+        // A minute before the end is five 12s blocks before block 100.
+        let target = "1m before end".parse().unwrap();
+        let mut scheduled = Scheduled::new(Pokes { left: 10 }, target);
+        assert_eq!(run(&mut scheduled, 93..97).await, [0, 0, 1, 1]);
+        assert!(scheduled.has_started());
+    }
+}
@@ -0,0 +1,335 @@
+use alloy::primitives::{Address, U256, utils::parse_units};
+use serde::Deserialize;
+
+use crate::{
+    error::StrategyConfigError,
+    types::{
+        config::AuctionConfig,
+        funding::FundingNeed,
+        primitives::{CurrencyAmount, Price, TokenAmount},
+        risk::RiskLimits,
+    },
+};
+
+use super::{
+    AutoRebidStrategy, Ladder, LadderStrategy, SettleStrategy, StopLossStrategy, Strategy,
+    TwapStrategy,
+};
+
+/// A built-in strategy described in configuration, such as the `[strategy]`
+/// table of the CLI's `bids.toml`.
+///
+/// Prices are currency per token, amounts whole currency units and token
+/// counts whole tokens, all as decimal strings. Amounts may name the currency (`"2 ETH"`), in which case
+/// it must match the auction's.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StrategyConfig {
+    /// A [`LadderStrategy`] spreading `budget` over `levels` bids from
+    /// `start_price` to `end_price`.
+    Ladder {
+        levels: usize,
+        start_price: String,
+        end_price: String,
+        budget: String,
+    },
+    /// An [`AutoRebidStrategy`] replacing outbid bids up to `max_price`.
+    Rebid {
+        max_price: String,
+        budget: String,
+        #[serde(default)]
+        ticks_above: Option<u64>,
+        /// Size replacements to buy this many tokens instead of reusing
+        /// the outbid amount.
+        #[serde(default)]
+        target_tokens: Option<String>,
+    },
+    /// A [`TwapStrategy`] spreading `budget` over `slices` bids across the
+    /// auction, each `premium_bps` above the clearing price.
+    Twap {
+        budget: String,
+        slices: u64,
+        #[serde(default)]
+        premium_bps: u64,
+        /// Never bid above this price; slices wait for the clearing price
+        /// to come back under it.
+        #[serde(default)]
+        max_price: Option<String>,
+        #[serde(default)]
+        max_bid_amount: Option<String>,
+    },
+    /// A [`StopLossStrategy`] exiting bids the clearing price passes once
+    /// it is above `ceiling`.
+    StopLoss {
+        ceiling: String,
+        /// Rebid exited amounts out of this budget.
+        #[serde(default)]
+        redeploy_budget: Option<String>,
+        /// How many ticks above the clearing price rebids go; one if unset.
+        #[serde(default)]
+        ticks_above: Option<u64>,
+    },
+    /// A [`SettleStrategy`].
+    Settle,
+    /// A [`ScriptStrategy`](super::ScriptStrategy) loaded from `path`.
+    #[cfg(feature = "script")]
+    Script { path: std::path::PathBuf },
+}
+
+/// Decimals and symbol needed to read the human units of a
+/// [`StrategyConfig`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Units {
+    pub token_decimals: u8,
+    pub currency_decimals: u8,
+    /// Matched case-insensitively against amounts that name a currency;
+    /// `None` accepts any name.
+    pub currency_symbol: Option<String>,
+    /// Shown after token amounts; `None` if the token has no `symbol()`.
+    pub token_symbol: Option<String>,
+}
+
+impl StrategyConfig {
+    /// Builds the strategy for the auction described by `config`, bidding
+    /// as `owner`.
+    pub fn build(
+        &self,
+        config: &AuctionConfig,
+        units: &Units,
+        owner: Address,
+    ) -> Result<Box<dyn Strategy>, StrategyConfigError> {
+        Ok(match self {
+            Self::Ladder {
+                levels,
+                start_price,
+                end_price,
+                budget,
+            } => {
+                let ladder = Ladder::linear(
+                    config,
+                    units.price("start_price", start_price)?,
+                    units.price("end_price", end_price)?,
+                    *levels,
+                    units.amount("budget", budget)?,
+                )?;
+                Box::new(LadderStrategy::new(ladder))
+            }
+            Self::Rebid {
+                max_price,
+                budget,
+                ticks_above,
+                target_tokens,
+            } => {
+                let mut strategy = AutoRebidStrategy::new(
+                    owner,
+                    units.price("max_price", max_price)?,
+                    units.amount("budget", budget)?,
+                );
+                if let Some(ticks) = ticks_above {
+                    strategy = strategy.with_ticks_above(*ticks);
+                }
+                if let Some(tokens) = target_tokens {
+                    strategy = strategy.with_target_tokens(units.tokens("target_tokens", tokens)?);
+                }
+                Box::new(strategy)
+            }
+            Self::Twap {
+                budget,
+                slices,
+                premium_bps,
+                max_price,
+                max_bid_amount,
+            } => {
+                let mut limits = RiskLimits::new();
+                if let Some(price) = max_price {
+                    limits = limits.with_max_price(units.price("max_price", price)?);
+                }
+                if let Some(amount) = max_bid_amount {
+                    limits = limits.with_max_bid_amount(units.amount("max_bid_amount", amount)?);
+                }
+                Box::new(
+                    TwapStrategy::new(units.amount("budget", budget)?, *slices)
+                        .with_premium_bps(*premium_bps)
+                        .with_risk_limits(limits),
+                )
+            }
+            Self::StopLoss {
+                ceiling,
+                redeploy_budget,
+                ticks_above,
+            } => {
+                let mut strategy = StopLossStrategy::new(units.price("ceiling", ceiling)?);
+                if let Some(budget) = redeploy_budget {
+                    strategy = strategy.with_redeploy(
+                        units.amount("redeploy_budget", budget)?,
+                        ticks_above.unwrap_or(1),
+                    );
+                }
+                Box::new(strategy)
+            }
+            Self::Settle => Box::new(SettleStrategy::new()),
+            #[cfg(feature = "script")]
+            Self::Script { path } => Box::new(super::ScriptStrategy::from_file(path)?),
+        })
+    }
+}
+
+impl StrategyConfig {
+    /// The `kind` the strategy is configured with, e.g. `ladder`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Ladder { .. } => "ladder",
+            Self::Rebid { .. } => "rebid",
+            Self::Twap { .. } => "twap",
+            Self::StopLoss { .. } => "stop_loss",
+            Self::Settle => "settle",
+            #[cfg(feature = "script")]
+            Self::Script { .. } => "script",
+        }
+    }
+
+    /// The currency the strategy commits and how many bids it sends, as
+    /// far as the config says; scripts and settling commit nothing known.
+    pub fn funding_need(&self, units: &Units) -> Result<FundingNeed, StrategyConfigError> {
+        Ok(match self {
+            Self::Ladder { levels, budget, .. } => FundingNeed {
+                currency: units.amount("budget", budget)?,
+                bids: *levels as u64,
+            },
+            Self::Rebid { budget, .. } => FundingNeed {
+                currency: units.amount("budget", budget)?,
+                bids: 1,
+            },
+            Self::Twap { budget, slices, .. } => FundingNeed {
+                currency: units.amount("budget", budget)?,
+                bids: *slices,
+            },
+            Self::StopLoss {
+                redeploy_budget: Some(budget),
+                ..
+            } => FundingNeed {
+                currency: units.amount("redeploy_budget", budget)?,
+                bids: 1,
+            },
+            Self::StopLoss { .. } | Self::Settle => FundingNeed::default(),
+            #[cfg(feature = "script")]
+            Self::Script { .. } => FundingNeed::default(),
+        })
+    }
+}
+
+impl Units {
+    /// Q96 price of `value` currency per whole token, rounded down.
+    pub fn price(&self, field: &'static str, value: &str) -> Result<Price, StrategyConfigError> {
+        let per_token = parse_decimal(field, value, self.currency_decimals)?;
+        Price::per_whole_token(CurrencyAmount::new(per_token), self.token_decimals)
+            .ok_or_else(|| invalid(field, value, "price is too large"))
+    }
+
+    /// Raw token units of `value` whole tokens.
+    pub fn tokens(
+        &self,
+        field: &'static str,
+        value: &str,
+    ) -> Result<TokenAmount, StrategyConfigError> {
+        Ok(TokenAmount::new(parse_decimal(
+            field,
+            value,
+            self.token_decimals,
+        )?))
+    }
+
+    /// Raw currency units of `value`, which may end in the currency's name.
+    pub fn amount(
+        &self,
+        field: &'static str,
+        value: &str,
+    ) -> Result<CurrencyAmount, StrategyConfigError> {
+        let mut parts = value.split_whitespace();
+        let number = parts.next().unwrap_or_default();
+        let symbol = parts.next();
+        if parts.next().is_some() {
+            return Err(invalid(field, value, "expected an amount and a currency"));
+        }
+        if let (Some(found), Some(expected)) = (symbol, &self.currency_symbol)
+            && !found.eq_ignore_ascii_case(expected)
+        {
+            return Err(StrategyConfigError::WrongCurrency {
+                field,
+                found: found.to_string(),
+                expected: expected.clone(),
+            });
+        }
+        Ok(CurrencyAmount::new(parse_decimal(
+            field,
+            number,
+            self.currency_decimals,
+        )?))
+    }
+}
+
+fn parse_decimal(
+    field: &'static str,
+    value: &str,
+    decimals: u8,
+) -> Result<U256, StrategyConfigError> {
+    parse_units(value, decimals)
+        .map_err(|error| invalid(field, value, &error.to_string()))
+        .and_then(|parsed| {
+            if parsed.is_negative() {
+                Err(invalid(field, value, "must not be negative"))
+            } else {
+                Ok(parsed.get_absolute())
+            }
+        })
+}
+
+fn invalid(field: &'static str, value: &str, reason: &str) -> StrategyConfigError {
+    StrategyConfigError::InvalidValue {
+        field,
+        value: value.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::primitives::Q96;
+
+    fn units() -> Units {
+        Units {
+            token_decimals: 18,
+            currency_decimals: 6,
+            currency_symbol: Some("USDC".to_string()),
+            token_symbol: None,
+        }
+    }
+
+    #[test]
+    fn parses_human_units() {
+        let config: StrategyConfig = serde_json::from_str(
+            r#"{"kind": "ladder", "levels": 5, "start_price": "0.01", "end_price": "0.02", "budget": "2 usdc"}"#,
+        )
+        .unwrap();
+        let StrategyConfig::Ladder { budget, .. } = &config else {
+            panic!("expected a ladder, got {config:?}");
+        };
+
+        let units = units();
+        assert_eq!(
+            units.amount("budget", budget).unwrap(),
+            CurrencyAmount::new(U256::from(2_000_000))
+        );
+        // 1 USDC per token is 1e6 raw currency per 1e18 raw tokens.
+        assert_eq!(
+            units.price("start_price", "1").unwrap(),
+            Price::new(Q96 * U256::from(1_000_000) / U256::from(10).pow(U256::from(18)))
+        );
+        assert!(matches!(
+            units.amount("budget", "2 ETH"),
+            Err(StrategyConfigError::WrongCurrency { .. })
+        ));
+        assert!(units.amount("budget", "-1").is_err());
+    }
+}
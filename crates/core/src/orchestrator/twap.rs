@@ -0,0 +1,267 @@
+use alloy::primitives::U256;
+use async_trait::async_trait;
+use tracing::{debug, info};
+
+use crate::{
+    executor::{EvaluationContext, Intent},
+    types::{
+        config::AuctionConfig,
+        primitives::{BlockNumber, CurrencyAmount, Price, TokenAmount},
+        risk::RiskLimits,
+        state::AuctionPhase,
+    },
+};
+
+use super::{SizeCalculator, Strategy};
+
+const BPS: u64 = 10_000;
+
+/// Spreads `budget` over `slices` bids at even block intervals from the
+/// auction's start block to its end block, buying at something close to the
+/// time-weighted clearing price instead of all at once.
+///
+/// Each slice is bid at the first tick above the clearing price raised by
+/// [`with_premium_bps`](Self::with_premium_bps), and is capped by the
+/// [`RiskLimits`] and by what is left of the supply. Slices whose block was
+/// missed, or that were skipped because the price was past the limit, roll
+/// into the next one rather than being sent in a burst, and the last slice
+/// spends whatever is left. The strategy is complete once every slice is
+/// sent or the auction has ended.
+#[derive(Debug)]
+pub struct TwapStrategy {
+    budget: CurrencyAmount,
+    slices: u64,
+    premium_bps: u64,
+    limits: RiskLimits,
+    sent: u64,
+    spent: U256,
+}
+
+impl TwapStrategy {
+    /// Splits `budget` into `slices` bids at the clearing price.
+    pub fn new(budget: CurrencyAmount, slices: u64) -> Self {
+        Self {
+            budget,
+            slices: slices.max(1),
+            premium_bps: 0,
+            limits: RiskLimits::new(),
+            sent: 0,
+            spent: U256::ZERO,
+        }
+    }
+
+    /// Bids each slice this many basis points above the clearing price.
+    pub fn with_premium_bps(mut self, bps: u64) -> Self {
+        self.premium_bps = bps;
+        self
+    }
+
+    pub fn with_risk_limits(mut self, limits: RiskLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Currency committed by the slices sent so far.
+    pub fn spent(&self) -> CurrencyAmount {
+        CurrencyAmount::new(self.spent)
+    }
+
+    /// Slices whose block is at or before `block`: the first at the start
+    /// block, the rest evenly spaced up to the end block.
+    fn due(&self, config: &AuctionConfig, block: BlockNumber) -> u64 {
+        let start = config.start_block.as_u64();
+        let Some(elapsed) = block.as_u64().checked_sub(start) else {
+            return 0;
+        };
+        let window = config.end_block.as_u64().saturating_sub(start);
+        if window == 0 {
+            return self.slices;
+        }
+        let due = u128::from(elapsed) * u128::from(self.slices) / u128::from(window) + 1;
+        due.min(u128::from(self.slices)) as u64
+    }
+
+    /// The first tick above `clearing_price` plus the premium.
+    fn price(&self, config: &AuctionConfig, clearing_price: Price) -> Option<Price> {
+        let raised = clearing_price
+            .as_u256()
+            .checked_mul(U256::from(BPS + self.premium_bps))?
+            / U256::from(BPS);
+        config.nth_tick_above(Price::new(raised), 1)
+    }
+}
+
+#[async_trait]
+impl Strategy for TwapStrategy {
+    async fn evaluate(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+        if !matches!(ctx.phase, AuctionPhase::Active { .. }) {
+            return Vec::new();
+        }
+        let due = self.due(ctx.config, ctx.block);
+        if due <= self.sent {
+            return Vec::new();
+        }
+
+        let clearing_price = ctx
+            .cache
+            .latest_checkpoint
+            .as_ref()
+            .map_or(ctx.config.floor_price, |checkpoint| {
+                checkpoint.clearing_price
+            });
+        let Some(price) = self.price(ctx.config, clearing_price) else {
+            info!(
+                slice = due,
+                "no tick above the clearing price; holding the slice"
+            );
+            return Vec::new();
+        };
+
+        let remaining = self.budget.as_u256().saturating_sub(self.spent);
+        let share = remaining * U256::from(due - self.sent) / U256::from(self.slices - self.sent);
+        // Sized against the whole remaining supply, so only the budget share,
+        // the risk limits and the supply itself cap the amount.
+        let size = SizeCalculator::new()
+            .with_budget(CurrencyAmount::new(share))
+            .with_risk_limits(self.limits)
+            .with_spent(self.spent())
+            .size_in(ctx, TokenAmount::new(U256::MAX), price);
+        let size = match size {
+            Ok(size) => size,
+            Err(violation) => {
+                info!(slice = due, %violation, "holding the slice");
+                return Vec::new();
+            }
+        };
+
+        self.sent = due;
+        if size.amount.is_zero() {
+            debug!(
+                slice = due,
+                "nothing left to buy or spend; skipping the slice"
+            );
+            return Vec::new();
+        }
+        self.spent += size.amount.as_u256();
+        info!(
+            slice = due,
+            slices = self.slices,
+            price = %price.as_u256(),
+            amount = %size.amount.as_u256(),
+            "sending TWAP slice"
+        );
+        let last_bid_block = BlockNumber::new(ctx.config.end_block.as_u64().saturating_sub(1));
+        vec![
+            Intent::SubmitBid {
+                max_price: price,
+                amount: size.amount,
+                owner: None,
+                from: None,
+            }
+            .by(last_bid_block),
+        ]
+    }
+
+    fn is_complete(&self, ctx: &EvaluationContext<'_>) -> bool {
+        self.sent == self.slices
+            || matches!(
+                ctx.phase,
+                AuctionPhase::Ended { .. } | AuctionPhase::Claimable
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        executor::{ExecutorCache, IntentExecutor, mock::MockAuction},
+        types::{
+            chain::ChainProfile,
+            config::fixture::{self, amount},
+            primitives::Q96,
+        },
+    };
+
+    fn config() -> AuctionConfig {
+        AuctionConfig {
+            // Enough that the supply never caps a slice at these prices.
+            total_supply: TokenAmount::new(Q96 * U256::from(1_000_000)),
+            ..fixture::config()
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_slices_on_schedule_and_rolls_missed_ones_forward() {
+        let config = config();
+        let cache = ExecutorCache::new();
+        let mut strategy = TwapStrategy::new(amount(1_000), 4)
+            .with_premium_bps(1_000)
+            .with_risk_limits(RiskLimits::new().with_max_bid_amount(amount(400)));
+
+        let mut bids = Vec::new();
+        for block in [0, 10, 60, 99] {
+            let ctx = EvaluationContext {
+                block: BlockNumber::new(block),
+                phase: AuctionPhase::Active {
+                    blocks_remaining: 100 - block,
+                },
+                cache: &cache,
+                tracked_bids: Vec::new(),
+                config: &config,
+                events: &[],
+                client: None,
+                chain: ChainProfile::default(),
+            };
+            for intent in strategy.evaluate(&ctx).await {
+                match intent.into_inner() {
+                    Intent::SubmitBid {
+                        max_price, amount, ..
+                    } => bids.push((block, max_price.as_u256(), amount)),
+                    other => panic!("unexpected intent {other:?}"),
+                }
+            }
+        }
+
+        // The floor of 10 plus 10% is 11, so slices go in at the tick of 20.
+        // The slices at 25 and 50 are both due by block 60, and their 500 is
+        // cut to the 400 bid limit; the last slice picks up the rest.
+        let twenty = U256::from(20);
+        assert_eq!(
+            bids,
+            [
+                (0, twenty, amount(250)),
+                (60, twenty, amount(400)),
+                (99, twenty, amount(350)),
+            ]
+        );
+        assert_eq!(strategy.spent(), amount(1_000));
+    }
+
+    #[tokio::test]
+    async fn prices_slices_off_the_latest_checkpoint_on_chain() {
+        let auction = MockAuction {
+            config: config(),
+            ..MockAuction::new()
+        };
+        let mut executor = IntentExecutor::new(auction);
+        let mut strategy = TwapStrategy::new(amount(1_000), 2);
+        let mut prices = Vec::new();
+        for block in [0, 50] {
+            if block == 50 {
+                executor.client().checkpoint_at(49, fixture::price(50));
+            }
+            let block = BlockNumber::new(block);
+            executor.refresh(block).await.unwrap();
+            for intent in strategy.evaluate(&executor.context(block, &[])).await {
+                if let Intent::SubmitBid { max_price, .. } = intent.into_inner() {
+                    prices.push(max_price);
+                }
+            }
+        }
+
+        // A tick above the floor, then above the price someone else's
+        // checkpoint moved the auction to.
+        assert_eq!(prices, [fixture::price(20), fixture::price(60)]);
+    }
+}
@@ -0,0 +1,69 @@
+use std::fmt;
+
+use alloy::providers::DynProvider;
+
+/// Sends transactions through a private relay such as Flashbots Protect, so
+/// they stay out of the public mempool until they are included.
+///
+/// Only the intent kinds it is set up for go through the relay; everything
+/// else is broadcast publicly. A transaction the relay has not landed within
+/// the fallback window is sent again through the public mempool.
+#[derive(Clone)]
+pub struct PrivateRelay {
+    provider: DynProvider,
+    kinds: Vec<&'static str>,
+    fallback_after: u64,
+}
+
+impl PrivateRelay {
+    /// Blocks to wait for the relay before sending publicly. Flashbots
+    /// Protect gives up on a transaction after 25 blocks.
+    pub const DEFAULT_FALLBACK_BLOCKS: u64 = 25;
+
+    /// `provider` is connected to the relay's RPC endpoint and signs as the
+    /// client's owner. Only bids are routed through it until
+    /// [`for_intents`](Self::for_intents) says otherwise.
+    pub fn new(provider: DynProvider) -> Self {
+        Self {
+            provider,
+            kinds: vec!["submit_bid"],
+            fallback_after: Self::DEFAULT_FALLBACK_BLOCKS,
+        }
+    }
+
+    /// Routes the given [`Intent::kind`](crate::Intent::kind)s through the
+    /// relay instead of the default.
+    pub fn for_intents(mut self, kinds: &[&'static str]) -> Self {
+        self.kinds = kinds.to_vec();
+        self
+    }
+
+    /// Send publicly once the relay has left the transaction out of `blocks`
+    /// blocks.
+    pub fn with_fallback_after(mut self, blocks: u64) -> Self {
+        self.fallback_after = blocks;
+        self
+    }
+
+    pub fn provider(&self) -> &DynProvider {
+        &self.provider
+    }
+
+    pub fn fallback_after(&self) -> u64 {
+        self.fallback_after
+    }
+
+    /// Whether transactions for intents of `kind` go through the relay.
+    pub fn routes(&self, kind: &str) -> bool {
+        self.kinds.contains(&kind)
+    }
+}
+
+impl fmt::Debug for PrivateRelay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrivateRelay")
+            .field("kinds", &self.kinds)
+            .field("fallback_after", &self.fallback_after)
+            .finish_non_exhaustive()
+    }
+}
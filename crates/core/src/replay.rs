@@ -0,0 +1,219 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    events::AuctionEvent,
+    executor::{EvaluationContext, ExecutorCache, Intent},
+    orchestrator::{HookSchedule, Strategy},
+    types::{
+        chain::ChainProfile,
+        config::AuctionConfig,
+        primitives::{BidId, BlockNumber},
+        state::AuctionPhase,
+    },
+};
+
+/// Where the orchestrator reports the decision it took at every block, so
+/// the run can later be fed to a [`Replay`]; see
+/// [`Orchestrator::with_decision_log`](crate::Orchestrator::with_decision_log).
+///
+/// Like [`ActivityLog`](crate::ActivityLog), recording must not fail the
+/// run, so implementations deal with their own errors.
+pub trait DecisionLog: Send {
+    fn record(&mut self, decision: Decision);
+}
+
+/// An [`EvaluationContext`] that owns its data, as read back from a
+/// recording. Its fields match what an `EvaluationContext` serializes to.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecordedContext {
+    pub block: BlockNumber,
+    pub phase: AuctionPhase,
+    pub cache: ExecutorCache,
+    pub tracked_bids: Vec<BidId>,
+    pub config: AuctionConfig,
+    pub events: Vec<AuctionEvent>,
+    pub chain: ChainProfile,
+}
+
+impl RecordedContext {
+    pub fn new(ctx: &EvaluationContext<'_>) -> Self {
+        Self {
+            block: ctx.block,
+            phase: ctx.phase.clone(),
+            cache: ctx.cache.clone(),
+            tracked_bids: ctx.tracked_bids.clone(),
+            config: ctx.config.clone(),
+            events: ctx.events.to_vec(),
+            chain: ctx.chain,
+        }
+    }
+
+    /// The context as the strategy was shown it. There is no client, so
+    /// strategies that do their own reads see what they see in a
+    /// [`Backtest`](crate::Backtest).
+    pub fn context(&self) -> EvaluationContext<'_> {
+        EvaluationContext {
+            block: self.block,
+            phase: self.phase.clone(),
+            cache: &self.cache,
+            tracked_bids: self.tracked_bids.clone(),
+            config: &self.config,
+            events: &self.events,
+            client: None,
+            chain: self.chain,
+        }
+    }
+}
+
+/// What a strategy was shown at one block and the intents it returned.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Decision {
+    pub context: RecordedContext,
+    pub intents: Vec<Intent>,
+}
+
+impl Decision {
+    pub fn new(ctx: &EvaluationContext<'_>, intents: &[Intent]) -> Self {
+        Self {
+            context: RecordedContext::new(ctx),
+            intents: intents.to_vec(),
+        }
+    }
+
+    pub fn block(&self) -> BlockNumber {
+        self.context.block
+    }
+}
+
+/// A block where the replayed strategy decided differently.
+#[derive(Clone, Debug)]
+pub struct DecisionDiff {
+    pub block: BlockNumber,
+    pub recorded: Vec<Intent>,
+    pub replayed: Vec<Intent>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ReplayReport {
+    pub blocks_replayed: usize,
+    pub diffs: Vec<DecisionDiff>,
+}
+
+impl ReplayReport {
+    /// Whether the strategy decided exactly as recorded at every block.
+    pub fn is_identical(&self) -> bool {
+        self.diffs.is_empty()
+    }
+}
+
+/// Feeds the contexts of a recorded run back through a strategy, possibly a
+/// modified one, and reports every block where its intents differ from the
+/// recorded ones.
+///
+/// Hooks fire as they did live, so a strategy that depends only on its
+/// context reproduces the recording exactly. Nothing is executed; the
+/// recorded contexts already carry the outcomes the live run saw.
+#[derive(Clone, Debug)]
+pub struct Replay {
+    decisions: Vec<Decision>,
+}
+
+impl Replay {
+    pub fn new(mut decisions: Vec<Decision>) -> Self {
+        decisions.sort_by_key(Decision::block);
+        Self { decisions }
+    }
+
+    pub fn decisions(&self) -> &[Decision] {
+        &self.decisions
+    }
+
+    pub async fn run<S: Strategy>(&self, strategy: &mut S) -> ReplayReport {
+        let mut report = ReplayReport::default();
+        let mut hooks = HookSchedule::default();
+        for decision in &self.decisions {
+            let ctx = decision.context.context();
+            let replayed = hooks.intents(strategy, &ctx).await;
+            if replayed != decision.intents {
+                report.diffs.push(DecisionDiff {
+                    block: decision.block(),
+                    recorded: decision.intents.clone(),
+                    replayed,
+                });
+            }
+            report.blocks_replayed += 1;
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::U256;
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::types::config::fixture::config;
+
+    /// Pokes a checkpoint on every `every`th block.
+    struct Poke {
+        every: u64,
+    }
+
+    #[async_trait]
+    impl Strategy for Poke {
+        async fn evaluate(&mut self, ctx: &EvaluationContext<'_>) -> Vec<Intent> {
+            if ctx.block.as_u64().is_multiple_of(self.every) {
+                vec![Intent::Checkpoint.by(BlockNumber::new(ctx.block.as_u64() + 1))]
+            } else {
+                Vec::new()
+            }
+        }
+    }
+
+    /// Records `strategy` over `blocks` the way a live run would, through
+    /// JSON.
+    async fn record(strategy: &mut impl Strategy, blocks: std::ops::Range<u64>) -> Vec<Decision> {
+        let config = config();
+        let cache = ExecutorCache::new();
+        let mut hooks = HookSchedule::default();
+        let mut decisions = Vec::new();
+        for block in blocks {
+            let ctx = EvaluationContext {
+                block: BlockNumber::new(block),
+                phase: AuctionPhase::Active {
+                    blocks_remaining: 100 - block,
+                },
+                cache: &cache,
+                tracked_bids: vec![BidId::new(U256::from(7))],
+                config: &config,
+                events: &[],
+                client: None,
+                chain: ChainProfile::BASE,
+            };
+            let intents = hooks.intents(strategy, &ctx).await;
+            let json = serde_json::to_string(&Decision::new(&ctx, &intents)).unwrap();
+            decisions.push(serde_json::from_str(&json).unwrap());
+        }
+        decisions
+    }
+
+    #[tokio::test]
+    async fn replays_recorded_decisions_and_diffs_changes() {
+        let replay = Replay::new(record(&mut Poke { every: 2 }, 0..6).await);
+        assert_eq!(replay.decisions()[0].context.chain, ChainProfile::BASE);
+
+        let same = replay.run(&mut Poke { every: 2 }).await;
+        assert_eq!(same.blocks_replayed, 6);
+        assert!(same.is_identical());
+
+        let changed = replay.run(&mut Poke { every: 3 }).await;
+        let blocks: Vec<u64> = changed
+            .diffs
+            .iter()
+            .map(|diff| diff.block.as_u64())
+            .collect();
+        assert_eq!(blocks, [2, 3, 4]);
+        assert!(changed.diffs[1].recorded.is_empty());
+    }
+}
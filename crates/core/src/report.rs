@@ -0,0 +1,406 @@
+use std::collections::BTreeMap;
+
+use alloy::{
+    primitives::{Address, U256},
+    providers::Provider,
+};
+
+use crate::{
+    client::AuctionClient,
+    error::{Error, ValidationError},
+    events::{AuctionEvent, EventProducer},
+    types::{
+        config::AuctionConfig,
+        primitives::{BlockNumber, CurrencyAmount, Mps, Price, mul_div},
+        state::{GraduationStatus, StateSnapshot},
+    },
+};
+
+/// Post-mortem of an ended auction: how it cleared, how its supply was
+/// released step by step, where demand sat, and optionally how one owner's
+/// bids fared. Amounts are raw units and prices Q96.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct AuctionReport {
+    pub auction: Address,
+    pub start_block: u64,
+    pub end_block: u64,
+    pub total_supply: U256,
+    pub clearing_price: U256,
+    pub currency_raised: U256,
+    pub total_cleared: U256,
+    pub graduated: bool,
+    /// `BidSubmitted` events seen.
+    pub total_bids: u64,
+    /// In schedule order.
+    pub steps: Vec<StepReport>,
+    /// Highest demand first.
+    pub top_ticks: Vec<TickReport>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub participation: Option<Participation>,
+}
+
+/// One step of the supply schedule.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct StepReport {
+    pub start_block: u64,
+    pub end_block: u64,
+    /// Thousandths of a basis point of the supply released per block.
+    pub mps: u32,
+    /// Tokens released for sale over the step.
+    pub supply: U256,
+    /// Clearing price at the step's last checkpoint, if one was written in it.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub clearing_price: Option<U256>,
+}
+
+/// Currency bid at one tick over the whole auction.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TickReport {
+    pub price: U256,
+    pub demand: U256,
+    pub bids: u64,
+}
+
+/// How one owner's bids did.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Participation {
+    pub owner: Address,
+    pub bids: u64,
+    pub exited: u64,
+    pub claimed: u64,
+    /// Currency put into the bids.
+    pub committed: U256,
+    /// Currency the exited bids spent on tokens.
+    pub spent: U256,
+    pub refunded: U256,
+    pub tokens_filled: U256,
+    /// Average Q96 price paid; `None` when nothing was filled.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub average_price: Option<U256>,
+    /// Fraction of the currency raised that came from these bids, 0 to 1.
+    pub share_of_raised: f64,
+}
+
+impl AuctionReport {
+    /// Reads the final state of `client`'s auction and its events from
+    /// `from` onwards, reporting on `owner`'s bids if given.
+    ///
+    /// Fails with `AuctionNotOver` before `end_block`, like
+    /// [`Attestation::collect`](crate::Attestation::collect).
+    pub async fn collect<P>(
+        client: &AuctionClient<P>,
+        block: BlockNumber,
+        from: BlockNumber,
+        owner: Option<Address>,
+        top_ticks: usize,
+    ) -> Result<Self, Error>
+    where
+        P: Provider + Clone,
+    {
+        if block < client.config().end_block {
+            return Err(ValidationError::AuctionNotOver.into());
+        }
+
+        let producer = EventProducer::new(client.provider().clone(), client.address());
+        let (checkpoint, metrics, graduation, events) = futures::try_join!(
+            client.fetch_checkpoint(),
+            client.fetch_auction_metrics(),
+            client.fetch_graduation(),
+            async { Ok(producer.history(from, None).await?) },
+        )?;
+        let snapshot = StateSnapshot {
+            checkpoint,
+            metrics,
+            graduation,
+        };
+
+        Ok(Self::from_history(
+            client.config(),
+            &snapshot,
+            &events,
+            owner,
+            top_ticks,
+        ))
+    }
+
+    /// Builds the report from the final `snapshot` and the auction's events,
+    /// in log order.
+    pub fn from_history(
+        config: &AuctionConfig,
+        snapshot: &StateSnapshot,
+        events: &[AuctionEvent],
+        owner: Option<Address>,
+        top_ticks: usize,
+    ) -> Self {
+        let total_bids = events
+            .iter()
+            .filter(|event| matches!(event, AuctionEvent::BidSubmitted { .. }))
+            .count() as u64;
+
+        Self {
+            auction: config.address,
+            start_block: config.start_block.as_u64(),
+            end_block: config.end_block.as_u64(),
+            total_supply: config.total_supply.as_u256(),
+            clearing_price: snapshot.checkpoint.clearing_price.as_u256(),
+            currency_raised: snapshot.metrics.currency_raised.as_u256(),
+            total_cleared: snapshot.metrics.total_cleared.as_u256(),
+            graduated: matches!(snapshot.graduation, GraduationStatus::Graduated),
+            total_bids,
+            steps: steps(config, events),
+            top_ticks: top_ticks_by_demand(events, top_ticks),
+            participation: owner
+                .map(|owner| participation(owner, events, snapshot.metrics.currency_raised)),
+        }
+    }
+}
+
+fn steps(config: &AuctionConfig, events: &[AuctionEvent]) -> Vec<StepReport> {
+    let full = U256::from(Mps::FULL);
+    events
+        .iter()
+        .filter_map(|event| match *event {
+            AuctionEvent::AuctionStepRecorded {
+                start_block,
+                end_block,
+                mps,
+                ..
+            } => Some((start_block, end_block, mps)),
+            _ => None,
+        })
+        .map(|(start_block, end_block, mps)| {
+            let blocks = end_block.as_u64().saturating_sub(start_block.as_u64());
+            let released = U256::from(mps.as_u24()) * U256::from(blocks);
+            let clearing_price = events
+                .iter()
+                .filter_map(|event| match *event {
+                    AuctionEvent::CheckpointUpdated {
+                        block,
+                        clearing_price,
+                        ..
+                    } if block >= start_block && block <= end_block => Some(clearing_price),
+                    _ => None,
+                })
+                .next_back();
+            StepReport {
+                start_block: start_block.as_u64(),
+                end_block: end_block.as_u64(),
+                mps: mps.as_u24().to::<u32>(),
+                supply: mul_div(config.total_supply.as_u256(), released, full).unwrap_or(U256::MAX),
+                clearing_price: clearing_price.map(|price| price.as_u256()),
+            }
+        })
+        .collect()
+}
+
+fn top_ticks_by_demand(events: &[AuctionEvent], count: usize) -> Vec<TickReport> {
+    let mut ticks: BTreeMap<U256, (U256, u64)> = BTreeMap::new();
+    for event in events {
+        if let AuctionEvent::BidSubmitted { price, amount, .. } = event {
+            let tick = ticks.entry(price.as_u256()).or_default();
+            tick.0 += amount.as_u256();
+            tick.1 += 1;
+        }
+    }
+
+    let mut ticks: Vec<_> = ticks
+        .into_iter()
+        .map(|(price, (demand, bids))| TickReport {
+            price,
+            demand,
+            bids,
+        })
+        .collect();
+    // Ties go to the higher price.
+    ticks.sort_by(|a, b| b.demand.cmp(&a.demand).then(b.price.cmp(&a.price)));
+    ticks.truncate(count);
+    ticks
+}
+
+fn participation(
+    owner: Address,
+    events: &[AuctionEvent],
+    currency_raised: CurrencyAmount,
+) -> Participation {
+    let mut amounts = BTreeMap::new();
+    let mut stats = Participation {
+        owner,
+        bids: 0,
+        exited: 0,
+        claimed: 0,
+        committed: U256::ZERO,
+        spent: U256::ZERO,
+        refunded: U256::ZERO,
+        tokens_filled: U256::ZERO,
+        average_price: None,
+        share_of_raised: 0.0,
+    };
+    for event in events {
+        match *event {
+            AuctionEvent::BidSubmitted {
+                bid_id,
+                owner: bidder,
+                amount,
+                ..
+            } if bidder == owner => {
+                stats.bids += 1;
+                stats.committed += amount.as_u256();
+                amounts.insert(bid_id.as_u256(), amount.as_u256());
+            }
+            AuctionEvent::BidExited {
+                bid_id,
+                owner: bidder,
+                tokens_filled,
+                currency_refunded,
+                ..
+            } if bidder == owner => {
+                stats.exited += 1;
+                stats.refunded += currency_refunded.as_u256();
+                stats.tokens_filled += tokens_filled.as_u256();
+                let amount = amounts.get(&bid_id.as_u256()).copied().unwrap_or_default();
+                stats.spent += amount.saturating_sub(currency_refunded.as_u256());
+            }
+            AuctionEvent::TokensClaimed { owner: bidder, .. } if bidder == owner => {
+                stats.claimed += 1;
+            }
+            _ => {}
+        }
+    }
+
+    stats.average_price =
+        Price::from_ratio(stats.spent, stats.tokens_filled).map(|price| price.as_u256());
+    let raised = currency_raised.as_u256();
+    if !raised.is_zero() {
+        let ppm = mul_div(stats.spent, U256::from(1_000_000), raised).unwrap_or_default();
+        stats.share_of_raised = ppm.saturating_to::<u64>() as f64 / 1_000_000.0;
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::aliases::U24;
+
+    use super::*;
+    use crate::types::{
+        checkpoint::Checkpoint,
+        config::fixture,
+        primitives::{BidId, Q96, TickSpacing, TokenAddr, TokenAmount},
+        state::AuctionMetrics,
+    };
+
+    fn config() -> AuctionConfig {
+        AuctionConfig {
+            address: Address::repeat_byte(0xaa),
+            start_block: BlockNumber::new(100),
+            end_block: BlockNumber::new(200),
+            claim_block: BlockNumber::new(210),
+            total_supply: TokenAmount::new(U256::from(1_000_000)),
+            tick_spacing: TickSpacing::new(Q96),
+            floor_price: Price::new(Q96),
+            max_bid_price: Price::new(Q96 * U256::from(100)),
+            token: TokenAddr::new(Address::repeat_byte(0xbb)),
+            ..fixture::config()
+        }
+    }
+
+    fn bid(id: u64, owner: u8, ticks: u64, amount: u64) -> AuctionEvent {
+        AuctionEvent::BidSubmitted {
+            block: BlockNumber::new(110 + id),
+            bid_id: BidId::new(U256::from(id)),
+            owner: Address::repeat_byte(owner),
+            price: Price::new(Q96 * U256::from(ticks)),
+            amount: CurrencyAmount::new(U256::from(amount)),
+        }
+    }
+
+    #[test]
+    fn reports_steps_ticks_and_participation() {
+        let events = [
+            AuctionEvent::AuctionStepRecorded {
+                block: BlockNumber::new(90),
+                start_block: BlockNumber::new(100),
+                end_block: BlockNumber::new(150),
+                mps: Mps::new(U24::from(100_000)),
+            },
+            bid(1, 1, 3, 500),
+            bid(2, 2, 5, 2_000),
+            bid(3, 1, 3, 700),
+            AuctionEvent::CheckpointUpdated {
+                block: BlockNumber::new(140),
+                clearing_price: Price::new(Q96 * U256::from(2)),
+                cumulative_mps: Mps::new(U24::from(4_000_000)),
+            },
+            AuctionEvent::BidExited {
+                block: BlockNumber::new(205),
+                bid_id: BidId::new(U256::from(1)),
+                owner: Address::repeat_byte(1),
+                tokens_filled: TokenAmount::new(U256::from(200)),
+                currency_refunded: CurrencyAmount::new(U256::from(100)),
+            },
+        ];
+        let snapshot = StateSnapshot {
+            checkpoint: Checkpoint {
+                block: BlockNumber::new(200),
+                clearing_price: Price::new(Q96 * U256::from(2)),
+                cumulative_mps: Mps::new(U24::from(Mps::FULL)),
+                cumulative_mps_per_price: U256::ZERO,
+                prev_block: BlockNumber::new(140),
+                next_block: BlockNumber::TAIL_SENTINEL,
+            },
+            metrics: AuctionMetrics {
+                currency_raised: CurrencyAmount::new(U256::from(4_000)),
+                total_cleared: TokenAmount::new(U256::from(2_000)),
+                required_currency_raised: None,
+            },
+            graduation: GraduationStatus::Graduated,
+        };
+
+        let report = AuctionReport::from_history(
+            &config(),
+            &snapshot,
+            &events,
+            Some(Address::repeat_byte(1)),
+            1,
+        );
+
+        assert_eq!(report.total_bids, 3);
+        assert!(report.graduated);
+        assert_eq!(report.steps.len(), 1);
+        // 1% per block for 50 blocks is half the supply.
+        assert_eq!(report.steps[0].supply, U256::from(500_000));
+        assert_eq!(report.steps[0].clearing_price, Some(Q96 * U256::from(2)));
+        assert_eq!(
+            report.top_ticks,
+            vec![TickReport {
+                price: Q96 * U256::from(5),
+                demand: U256::from(2_000),
+                bids: 1,
+            }]
+        );
+
+        let mine = report.participation.unwrap();
+        assert_eq!((mine.bids, mine.exited), (2, 1));
+        assert_eq!(mine.committed, U256::from(1_200));
+        assert_eq!(mine.spent, U256::from(400));
+        assert_eq!(mine.average_price, Some(Q96 * U256::from(2)));
+        assert_eq!(mine.share_of_raised, 0.1);
+    }
+}
@@ -0,0 +1,365 @@
+use std::{
+    net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream},
+    path::PathBuf,
+    process::{Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use alloy::{
+    primitives::{Address, I256, U256},
+    providers::{Provider, RootProvider, ext::AnvilApi},
+    rpc::types::{Log, TransactionRequest, anvil::Forking},
+};
+use flux_abi::{IContinuousClearingAuction, IERC20Minimal};
+use tokio::sync::Mutex;
+use tracing::{debug, instrument};
+
+use crate::{error::ShadowError, types::config::AuctionConfig};
+
+/// Builder for an anvil node forking a live chain, which [`ShadowFork`]
+/// rehearses transactions on.
+#[derive(Clone, Debug)]
+pub struct AnvilFork {
+    bin: PathBuf,
+    fork_url: String,
+    startup_timeout: Duration,
+}
+
+impl AnvilFork {
+    /// Environment variable overriding the `anvil` binary.
+    pub const BIN_ENV: &'static str = "ANVIL_BIN";
+
+    /// Forks `fork_url` with `$ANVIL_BIN`, or `anvil` from `PATH`.
+    pub fn new(fork_url: impl Into<String>) -> Self {
+        Self {
+            bin: std::env::var_os(Self::BIN_ENV).map_or_else(|| "anvil".into(), PathBuf::from),
+            fork_url: fork_url.into(),
+            startup_timeout: Duration::from_secs(30),
+        }
+    }
+
+    pub fn with_bin(mut self, bin: impl Into<PathBuf>) -> Self {
+        self.bin = bin.into();
+        self
+    }
+
+    pub fn with_startup_timeout(mut self, timeout: Duration) -> Self {
+        self.startup_timeout = timeout;
+        self
+    }
+
+    /// Starts anvil on a free local port and blocks until it accepts
+    /// connections.
+    pub fn spawn(&self) -> Result<AnvilProcess, ShadowError> {
+        let spawn_error = |source| ShadowError::Spawn {
+            bin: self.bin.clone(),
+            source,
+        };
+        let port = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .and_then(|listener| listener.local_addr())
+            .map_err(spawn_error)?
+            .port();
+
+        let child = Command::new(&self.bin)
+            .arg("--fork-url")
+            .arg(&self.fork_url)
+            .arg("--port")
+            .arg(port.to_string())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(spawn_error)?;
+        let process = AnvilProcess { child, port };
+
+        let address = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+        let started = Instant::now();
+        while TcpStream::connect_timeout(&address, Duration::from_millis(100)).is_err() {
+            if started.elapsed() > self.startup_timeout {
+                return Err(ShadowError::Startup {
+                    port,
+                    timeout: self.startup_timeout,
+                });
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+
+        Ok(process)
+    }
+}
+
+/// A running anvil node, killed when dropped.
+#[derive(Debug)]
+pub struct AnvilProcess {
+    child: Child,
+    port: u16,
+}
+
+impl AnvilProcess {
+    /// HTTP RPC endpoint of the node.
+    pub fn endpoint(&self) -> String {
+        format!("http://127.0.0.1:{}", self.port)
+    }
+}
+
+impl Drop for AnvilProcess {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// How a rehearsed transaction moved the sender's balances, after its gas.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ForkDiff {
+    pub currency: I256,
+    pub tokens: I256,
+    pub gas_cost: U256,
+}
+
+/// An anvil fork that each transaction is sent to first, reset to the live
+/// chain's current block every time, so a transaction that reverts or moves
+/// the sender's balances differently from what the auction's events say is
+/// never broadcast.
+#[derive(Debug)]
+pub struct ShadowFork {
+    provider: RootProvider,
+    fork_url: String,
+    // One rehearsal at a time, since each one resets the fork.
+    lock: Mutex<()>,
+    _process: AnvilProcess,
+}
+
+impl ShadowFork {
+    /// Starts `anvil` and connects to it.
+    pub fn spawn(anvil: &AnvilFork) -> Result<Self, ShadowError> {
+        let process = anvil.spawn()?;
+        let url = process
+            .endpoint()
+            .parse()
+            .expect("a local URL with a port parses");
+        Ok(Self {
+            provider: RootProvider::new_http(url),
+            fork_url: anvil.fork_url.clone(),
+            lock: Mutex::new(()),
+            _process: process,
+        })
+    }
+
+    /// Sends `tx` from `from` on a fork of `block` and checks the result:
+    /// it must not revert, a bid, exit or claim must emit the auction's
+    /// event for it, and the sender's currency and token balances must move
+    /// by exactly what those events say was paid, refunded and claimed.
+    #[instrument(skip_all, fields(kind = kind, block = block))]
+    pub async fn rehearse(
+        &self,
+        tx: &TransactionRequest,
+        from: Address,
+        kind: &'static str,
+        block: u64,
+        config: &AuctionConfig,
+    ) -> Result<ForkDiff, ShadowError> {
+        let _guard = self.lock.lock().await;
+        self.provider
+            .anvil_reset(Some(Forking {
+                json_rpc_url: Some(self.fork_url.clone()),
+                block_number: Some(block),
+            }))
+            .await?;
+        self.provider.anvil_impersonate_account(from).await?;
+
+        let mut tx = tx.clone();
+        tx.from = Some(from);
+        tx.nonce = None;
+
+        let before = self.balances(from, config).await?;
+        let tx_hash = self
+            .provider
+            .anvil_send_impersonated_transaction(tx)
+            .await?;
+        let receipt = self
+            .provider
+            .get_transaction_receipt(tx_hash)
+            .await?
+            .ok_or(ShadowError::MissingReceipt(tx_hash))?;
+        if !receipt.inner.status() {
+            return Err(ShadowError::Reverted { kind, tx_hash });
+        }
+        let after = self.balances(from, config).await?;
+
+        let diff = ForkDiff {
+            currency: I256::from_raw(after.0) - I256::from_raw(before.0),
+            tokens: I256::from_raw(after.1) - I256::from_raw(before.1),
+            gas_cost: U256::from(receipt.gas_used) * U256::from(receipt.effective_gas_price),
+        };
+        let fills = Fills::from_logs(receipt.inner.logs(), config.address, from);
+        check(kind, &diff, &fills, config.is_native_currency())
+            .map_err(|reason| ShadowError::Insane { kind, reason })?;
+        debug!(
+            currency = %diff.currency,
+            tokens = %diff.tokens,
+            gas_cost = %diff.gas_cost,
+            "rehearsal on the fork passed"
+        );
+        Ok(diff)
+    }
+
+    /// `account`'s currency and token balances on the fork.
+    async fn balances(
+        &self,
+        account: Address,
+        config: &AuctionConfig,
+    ) -> Result<(U256, U256), ShadowError> {
+        let currency = if config.is_native_currency() {
+            self.provider.get_balance(account).await?
+        } else {
+            IERC20Minimal::new(config.currency.as_address(), &self.provider)
+                .balanceOf(account)
+                .call()
+                .await?
+        };
+        let tokens = IERC20Minimal::new(config.token.as_address(), &self.provider)
+            .balanceOf(account)
+            .call()
+            .await?;
+        Ok((currency, tokens))
+    }
+}
+
+/// What the auction's events in a receipt say moved to or from the sender.
+#[derive(Clone, Copy, Debug, Default)]
+struct Fills {
+    bids: usize,
+    exits: usize,
+    claims: usize,
+    /// Currency committed by every bid submitted, whoever owns them.
+    paid: U256,
+    /// Currency refunded to the sender's own bids.
+    refunded: U256,
+    /// Tokens claimed to the sender.
+    claimed: U256,
+    /// Exits that returned neither tokens nor currency.
+    empty_exits: usize,
+}
+
+impl Fills {
+    fn from_logs(logs: &[Log], auction: Address, from: Address) -> Self {
+        let mut fills = Self::default();
+        for log in logs.iter().filter(|log| log.address() == auction) {
+            if let Ok(bid) = log.log_decode::<IContinuousClearingAuction::BidSubmitted>() {
+                fills.bids += 1;
+                fills.paid += U256::from(bid.inner.data.amount);
+            } else if let Ok(exit) = log.log_decode::<IContinuousClearingAuction::BidExited>() {
+                let data = exit.inner.data;
+                fills.exits += 1;
+                if data.tokensFilled.is_zero() && data.currencyRefunded.is_zero() {
+                    fills.empty_exits += 1;
+                }
+                if data.owner == from {
+                    fills.refunded += data.currencyRefunded;
+                }
+            } else if let Ok(claim) = log.log_decode::<IContinuousClearingAuction::TokensClaimed>()
+            {
+                fills.claims += 1;
+                if claim.inner.data.owner == from {
+                    fills.claimed += claim.inner.data.tokensFilled;
+                }
+            }
+        }
+        fills
+    }
+}
+
+/// Whether `diff` agrees with `fills` for a transaction of `kind`. Kinds
+/// other than bids, exits and claims only have to succeed.
+fn check(kind: &str, diff: &ForkDiff, fills: &Fills, native: bool) -> Result<(), String> {
+    let emitted = match kind {
+        "submit_bid" => fills.bids,
+        "exit" => fills.exits,
+        "claim" => fills.claims,
+        _ => return Ok(()),
+    };
+    if emitted == 0 {
+        return Err("the auction emitted no event for it".to_string());
+    }
+    if fills.empty_exits > 0 {
+        return Err("an exit returned neither tokens nor currency".to_string());
+    }
+
+    let currency = if native {
+        diff.currency + I256::from_raw(diff.gas_cost)
+    } else {
+        diff.currency
+    };
+    let expected = I256::from_raw(fills.refunded) - I256::from_raw(fills.paid);
+    if currency != expected {
+        return Err(format!(
+            "currency balance moved by {currency}, the auction's events say {expected}"
+        ));
+    }
+    let expected = I256::from_raw(fills.claimed);
+    if diff.tokens != expected {
+        return Err(format!(
+            "token balance moved by {}, the auction's events say {expected}",
+            diff.tokens
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int(value: i64) -> I256 {
+        I256::try_from(value).unwrap()
+    }
+
+    #[test]
+    fn check_matches_balances_against_events() {
+        let bid = Fills {
+            bids: 1,
+            paid: U256::from(100),
+            ..Fills::default()
+        };
+        let paid = ForkDiff {
+            currency: int(-130),
+            tokens: I256::ZERO,
+            gas_cost: U256::from(30),
+        };
+        // Gas only counts against a native currency.
+        assert_eq!(check("submit_bid", &paid, &bid, true), Ok(()));
+        assert!(check("submit_bid", &paid, &bid, false).is_err());
+
+        let exit = Fills {
+            exits: 1,
+            refunded: U256::from(40),
+            ..Fills::default()
+        };
+        let refunded = ForkDiff {
+            currency: int(40),
+            ..ForkDiff::default()
+        };
+        assert_eq!(check("exit", &refunded, &exit, false), Ok(()));
+        // An exit that emitted nothing, or emptied out, is held back.
+        assert!(check("exit", &refunded, &Fills::default(), false).is_err());
+        let empty = Fills {
+            exits: 1,
+            empty_exits: 1,
+            ..Fills::default()
+        };
+        assert!(check("exit", &ForkDiff::default(), &empty, false).is_err());
+
+        let claim = Fills {
+            claims: 1,
+            claimed: U256::from(7),
+            ..Fills::default()
+        };
+        let short = ForkDiff {
+            tokens: int(5),
+            ..ForkDiff::default()
+        };
+        assert!(check("claim", &short, &claim, false).is_err());
+        assert_eq!(check("approve", &short, &Fills::default(), false), Ok(()));
+    }
+}
@@ -0,0 +1,237 @@
+use std::sync::Arc;
+
+use alloy::{primitives::Address, providers::Provider};
+
+use crate::{
+    claim_batch::{ClaimBatcher, ClaimGasLimit},
+    client::AuctionClient,
+    discovery::BidDiscovery,
+    error::{Error, StateError},
+    hooks::{NoopHook, ValidationHook},
+    types::{
+        action::ClaimResult,
+        config::AuctionConfig,
+        dust::{DustSkip, DustThreshold},
+        primitives::{BidId, BlockNumber, Price, TokenAmount},
+        state::GraduationStatus,
+    },
+};
+
+/// Bids in one auction that are ready to be claimed in a single transaction.
+#[derive(Clone, Debug)]
+pub struct ClaimPlan {
+    pub auction: Address,
+    pub bid_ids: Vec<BidId>,
+    /// Tokens the claim of `bid_ids` pays out.
+    pub tokens: TokenAmount,
+    /// Claimable bids left out because they were below the dust threshold.
+    pub skipped: Vec<DustSkip>,
+}
+
+#[derive(Debug)]
+pub struct SweepResult {
+    pub auction: Address,
+    pub skipped: Vec<DustSkip>,
+    /// One per claim transaction; empty when every claimable bid in the
+    /// auction was dust.
+    pub claims: Vec<Result<ClaimResult, Error>>,
+}
+
+/// Claims every exited-but-unclaimed bid of one owner across several auctions,
+/// using one `claimTokensBatch` transaction per auction, or several when a
+/// [`ClaimGasLimit`] splits them.
+pub struct ClaimSweeper<P>
+where
+    P: Provider + Clone,
+{
+    provider: P,
+    owner: Address,
+    clients: Vec<AuctionClient<P>>,
+    discovery: BidDiscovery<P>,
+    dust: Option<DustThreshold>,
+    batcher: ClaimBatcher,
+}
+
+impl<P> ClaimSweeper<P>
+where
+    P: Provider + Clone,
+{
+    pub async fn new(provider: P, owner: Address, auctions: &[Address]) -> Result<Self, Error> {
+        let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+
+        let mut clients = Vec::with_capacity(auctions.len());
+        for &auction in auctions {
+            clients.push(
+                AuctionClient::new(provider.clone(), auction, owner, hook.clone(), Vec::new())
+                    .await?,
+            );
+        }
+
+        Ok(Self {
+            discovery: BidDiscovery::new(provider.clone()),
+            provider,
+            owner,
+            clients,
+            dust: None,
+            batcher: ClaimBatcher::new(),
+        })
+    }
+
+    pub fn with_discovery(mut self, discovery: BidDiscovery<P>) -> Self {
+        self.discovery = discovery;
+        self
+    }
+
+    /// Split each auction's claim into transactions under `limit`.
+    pub fn with_claim_gas_limit(mut self, limit: ClaimGasLimit) -> Self {
+        self.batcher = self.batcher.with_gas_limit(limit);
+        self
+    }
+
+    /// Leave bids whose claimable tokens are worth less than `threshold` unclaimed.
+    pub fn with_dust_threshold(mut self, threshold: DustThreshold) -> Self {
+        self.dust = Some(threshold);
+        self
+    }
+
+    /// Finds claimable bids without sending anything.
+    ///
+    /// Auctions whose claim block has not been reached, or which did not
+    /// graduate, are skipped.
+    pub async fn plan(&self) -> Result<Vec<ClaimPlan>, Error> {
+        let current_block = BlockNumber::new(
+            self.provider
+                .get_block_number()
+                .await
+                .map_err(StateError::from)?,
+        );
+
+        let mut plans = Vec::new();
+
+        for client in &self.clients {
+            if current_block < client.config().claim_block {
+                continue;
+            }
+
+            if client.fetch_graduation().await? != GraduationStatus::Graduated {
+                continue;
+            }
+
+            let bid_ids = self
+                .discovery
+                .bids_by_owner(client.address(), self.owner)
+                .await?;
+            let bids = client.fetch_bids(&bid_ids).await?;
+
+            let clearing_price = match self.dust {
+                Some(_) => client.fetch_checkpoint().await?.clearing_price,
+                None => Price::ZERO,
+            };
+
+            let mut claimable = Vec::new();
+            let mut tokens = TokenAmount::ZERO;
+            let mut skipped = Vec::new();
+            for bid in bids
+                .iter()
+                .filter(|bid| bid.owner == self.owner && bid.needs_claim())
+            {
+                match self
+                    .dust
+                    .and_then(|dust| dust.check_claim(bid, clearing_price))
+                {
+                    Some(skip) => skipped.push(skip),
+                    None => {
+                        claimable.push(bid.id);
+                        tokens += bid.tokens_filled;
+                    }
+                }
+            }
+
+            if !claimable.is_empty() || !skipped.is_empty() {
+                plans.push(ClaimPlan {
+                    auction: client.address(),
+                    bid_ids: claimable,
+                    tokens,
+                    skipped,
+                });
+            }
+        }
+
+        Ok(plans)
+    }
+
+    /// Plans and submits the claim transactions of every auction with
+    /// claimable bids.
+    ///
+    /// A failure in one auction does not stop the others.
+    pub async fn sweep(&self) -> Result<Vec<SweepResult>, Error> {
+        let plans = self.plan().await?;
+        Ok(self.execute(plans).await)
+    }
+
+    /// Submits the claim transactions of `plans`, as returned by
+    /// [`plan`](Self::plan); a failure in one auction does not stop the
+    /// others.
+    pub async fn execute(&self, plans: Vec<ClaimPlan>) -> Vec<SweepResult> {
+        let mut results = Vec::with_capacity(plans.len());
+
+        for plan in plans {
+            let Some(client) = self.client(plan.auction) else {
+                continue;
+            };
+
+            if plan.bid_ids.is_empty() {
+                results.push(SweepResult {
+                    auction: plan.auction,
+                    skipped: plan.skipped,
+                    claims: Vec::new(),
+                });
+                continue;
+            }
+
+            let claims = self
+                .batcher
+                .claim(client, self.owner, plan.bid_ids)
+                .await
+                .unwrap_or_else(|error| vec![Err(error)]);
+
+            results.push(SweepResult {
+                auction: plan.auction,
+                skipped: plan.skipped,
+                claims,
+            });
+        }
+
+        results
+    }
+
+    /// Config of a swept auction.
+    pub fn config(&self, auction: Address) -> Option<&AuctionConfig> {
+        self.client(auction).map(AuctionClient::config)
+    }
+
+    /// Gas the claim transactions of `plan` are estimated to use together.
+    pub async fn estimate_gas(&self, plan: &ClaimPlan) -> Result<u64, Error> {
+        let Some(client) = self.client(plan.auction) else {
+            return Ok(0);
+        };
+        if plan.bid_ids.is_empty() {
+            return Ok(0);
+        }
+        let mut gas = 0;
+        for claim in self
+            .batcher
+            .plan(client, self.owner, plan.bid_ids.clone())
+            .await?
+        {
+            gas += client.estimate_claim_gas(&claim).await?;
+        }
+        Ok(gas)
+    }
+
+    fn client(&self, auction: Address) -> Option<&AuctionClient<P>> {
+        self.clients
+            .iter()
+            .find(|client| client.address() == auction)
+    }
+}
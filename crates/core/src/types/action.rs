@@ -1,21 +1,54 @@
 use alloy::primitives::{Address, B256, Bytes};
 
-use super::primitives::{BidId, BlockNumber, CurrencyAmount, Price, TokenAmount};
+use super::{
+    checkpoint::Checkpoint,
+    dust::DustSkip,
+    primitives::{BidId, BlockNumber, CurrencyAmount, Price, TokenAmount},
+};
 
 #[derive(Clone, Debug)]
 pub struct SubmitBidInput {
     pub max_price: Price,
     pub amount: CurrencyAmount,
     pub owner: Address,
+    /// Forces a `submitBid` overload; `None` lets `prepare_bid` choose.
+    pub hint: Option<TickHint>,
+}
+
+/// Which `submitBid` overload a bid is sent through.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TickHint {
+    /// Pass `prevTickPrice`, the initialized tick the bid's tick follows, so
+    /// the contract inserts it without searching.
+    PrevTick(Price),
+    /// Leave the search for the insertion point to the contract, which costs
+    /// gas per tick walked instead of calls here.
+    Unhinted,
+}
+
+/// The clearing price a bid was prepared against and how far it may rise
+/// before the bid is no longer sent.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SlippageGuard {
+    pub clearing_price: Price,
+    pub max_drift: Price,
 }
 
 pub struct SubmitBidParams {
     pub max_price: Price,
     pub amount: CurrencyAmount,
     pub owner: Address,
-    pub prev_tick_price: Price,
+    /// The signing wallet that sends and pays for the bid.
+    pub from: Address,
+    /// Nonce to send with; `None` leaves it to the provider.
+    pub nonce: Option<u64>,
+    pub hint: TickHint,
     pub hook_data: Bytes,
     pub value: CurrencyAmount,
+    /// Re-check the clearing price right before sending; `None` sends as is.
+    pub slippage: Option<SlippageGuard>,
+    /// Tracked with the bid; see [`TrackedBid::tags`](super::bid::TrackedBid::tags).
+    pub tags: Vec<String>,
 }
 
 pub struct ExitBidParams {
@@ -28,6 +61,7 @@ pub struct ExitPartiallyFilledParams {
     pub outbid_block: Option<BlockNumber>,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct ExitHints {
     pub last_fully_filled_checkpoint_block: BlockNumber,
     pub outbid_block: Option<BlockNumber>,
@@ -36,25 +70,117 @@ pub struct ExitHints {
 pub struct ClaimParams {
     pub owner: Address,
     pub bid_ids: Vec<BidId>,
+    /// Nonce to send with; `None` leaves it to the provider.
+    pub nonce: Option<u64>,
+}
+
+/// A settlement call [`AuctionClient::settle_batch`](crate::AuctionClient::settle_batch)
+/// can bundle with others into one Multicall3 transaction.
+pub enum BatchCall {
+    Exit(ExitBidParams),
+    ExitPartiallyFilled(ExitPartiallyFilledParams),
+    Claim(ClaimParams),
+}
+
+/// What became of one [`BatchCall`].
+#[derive(Debug)]
+pub enum BatchOutcome {
+    Exited {
+        bid_id: BidId,
+        tokens_filled: TokenAmount,
+        currency_refunded: CurrencyAmount,
+    },
+    Claimed {
+        bid_ids: Vec<BidId>,
+        total_tokens: TokenAmount,
+    },
+    /// The call reverted, in simulation or in the batch; the rest went ahead
+    /// without it.
+    Failed { reason: String },
+}
+
+#[derive(Debug)]
+pub struct BatchResult {
+    /// One per call, in the order they were given.
+    pub outcomes: Vec<BatchOutcome>,
+    /// `None` when every call failed in simulation and nothing was sent.
+    pub tx_hash: Option<B256>,
+    pub gas_used: u64,
+    /// Wei paid per unit of gas.
+    pub effective_gas_price: u128,
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SubmitBidResult {
     pub bid_id: BidId,
+    /// Currency committed to the bid.
+    pub amount: CurrencyAmount,
+    /// Set when the bid was placed for someone other than the wallet that
+    /// paid for it: its refund and tokens go to them, and the client that
+    /// sent it neither tracks nor claims it.
+    pub beneficiary: Option<Address>,
     pub tx_hash: B256,
+    pub gas_used: u64,
+    /// Wei paid per unit of gas.
+    pub effective_gas_price: u128,
+    /// Tags the bid was submitted with.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExitResult {
     pub bid_id: BidId,
     pub tokens_filled: TokenAmount,
     pub currency_refunded: CurrencyAmount,
     pub tx_hash: B256,
+    pub gas_used: u64,
+    /// Wei paid per unit of gas.
+    pub effective_gas_price: u128,
 }
 
+/// A claim of bids with several owners is sent as one transaction per owner;
+/// `tx_hash` is then the last of them and `gas_used` their total. If some of
+/// them fail, the result covers those that landed and lists the rest in
+/// `failed`.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ClaimResult {
+    /// Bids claimed by the transactions that landed.
     pub bid_ids: Vec<BidId>,
     pub total_tokens: TokenAmount,
     pub tx_hash: B256,
+    /// Every transaction that landed, in the order sent.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tx_hashes: Vec<B256>,
+    pub gas_used: u64,
+    /// Wei paid per unit of gas.
+    pub effective_gas_price: u128,
+    /// Bids left unclaimed because they were below the dust threshold.
+    pub skipped: Vec<DustSkip>,
+    /// Owners whose claim failed after another owner's had landed.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub failed: Vec<FailedClaim>,
+}
+
+/// One owner's claim that failed within a [`ClaimResult`]; its bids are
+/// still unclaimed.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FailedClaim {
+    pub owner: Address,
+    pub bid_ids: Vec<BidId>,
+    pub error: String,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CheckpointResult {
+    pub checkpoint: Checkpoint,
+    pub tx_hash: B256,
+    pub gas_used: u64,
+    /// Wei paid per unit of gas.
+    pub effective_gas_price: u128,
 }
@@ -0,0 +1,107 @@
+use std::fmt;
+
+use alloy::primitives::{U256, utils::format_units};
+
+use super::primitives::{Price, Q96};
+
+/// A raw amount with the decimals and symbol to show it in, e.g.
+/// `1500000` with 6 decimals and `USDC` shows as `1.5 USDC`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DisplayAmount {
+    raw: U256,
+    decimals: u8,
+    symbol: String,
+}
+
+impl DisplayAmount {
+    pub fn new(raw: U256, decimals: u8, symbol: impl Into<String>) -> Self {
+        Self {
+            raw,
+            decimals,
+            symbol: symbol.into(),
+        }
+    }
+
+    /// A Q96 `price` as currency per whole token, to the nearest raw unit,
+    /// with a symbol like `USDC/FOO`. Prices set from a human figure are
+    /// rounded down into Q96, so rounding down again would show `0.499999`
+    /// for a price of `0.5`.
+    pub fn price(
+        price: Price,
+        token_decimals: u8,
+        currency_decimals: u8,
+        symbol: impl Into<String>,
+    ) -> Self {
+        let per_token = U256::from(10)
+            .checked_pow(U256::from(token_decimals))
+            .and_then(|one_token| one_token.checked_mul(price.as_u256()))
+            .and_then(|scaled| scaled.checked_add(Q96 / U256::from(2)))
+            .map_or(U256::MAX, |scaled| scaled / Q96);
+        Self::new(per_token, currency_decimals, symbol)
+    }
+
+    pub fn raw(&self) -> U256 {
+        self.raw
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// The amount in whole units without trailing zeros, e.g. `1.5`.
+    pub fn amount(&self) -> String {
+        let Ok(formatted) = format_units(self.raw, self.decimals) else {
+            return self.raw.to_string();
+        };
+        match formatted.split_once('.') {
+            Some((whole, fraction)) => match fraction.trim_end_matches('0') {
+                "" => whole.to_string(),
+                fraction => format!("{whole}.{fraction}"),
+            },
+            None => formatted,
+        }
+    }
+}
+
+impl fmt::Display for DisplayAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.symbol.is_empty() {
+            write!(f, "{}", self.amount())
+        } else {
+            write!(f, "{} {}", self.amount(), self.symbol)
+        }
+    }
+}
+
+/// `{"raw": "0x...", "amount": "1.5", "symbol": "USDC"}`, so readers of JSON
+/// output get both forms.
+#[cfg(feature = "serde")]
+impl serde::Serialize for DisplayAmount {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut amount = serializer.serialize_struct("DisplayAmount", 3)?;
+        amount.serialize_field("raw", &self.raw)?;
+        amount.serialize_field("amount", &self.amount())?;
+        amount.serialize_field("symbol", &self.symbol)?;
+        amount.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shows_whole_units_with_the_symbol() {
+        let usdc = |raw: u64| DisplayAmount::new(U256::from(raw), 6, "USDC").to_string();
+        assert_eq!(usdc(1_500_000), "1.5 USDC");
+        assert_eq!(usdc(2_000_000), "2 USDC");
+        assert_eq!(usdc(1), "0.000001 USDC");
+
+        // Half a USDC per 18-decimal token.
+        let price = Price::new(Q96 * U256::from(500_000) / U256::from(10).pow(U256::from(18)));
+        let shown = DisplayAmount::price(price, 18, 6, "USDC/FOO");
+        assert_eq!(shown.to_string(), "0.5 USDC/FOO");
+    }
+}
@@ -1,19 +1,170 @@
+use std::fmt;
+
 use alloy::primitives::{Address, B256};
+use flux_abi::IContinuousClearingAuction;
 
-use super::primitives::{BidId, BlockNumber, CurrencyAmount, Mps, Price, TokenAmount};
+use super::{
+    config::AuctionConfig,
+    primitives::{BidId, BlockNumber, CurrencyAmount, Mps, Price, TokenAmount},
+    state::GraduationStatus,
+};
 
-pub enum BidStatus {
+/// Where a bid's max price sits against the clearing price.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Moneyness {
+    /// Above the clearing price: filled in full as long as it stays there.
     ITM,
+    /// Exactly at the clearing price: filled partially, pro rata.
     ATM,
+    /// Below the clearing price: outbid, no longer filling.
     OTM,
 }
 
-pub enum BidLifecycle {
-    Active,
+impl Moneyness {
+    pub fn of(max_price: Price, clearing_price: Price) -> Self {
+        if max_price > clearing_price {
+            Moneyness::ITM
+        } else if max_price == clearing_price {
+            Moneyness::ATM
+        } else {
+            Moneyness::OTM
+        }
+    }
+}
+
+/// Where a bid is in its life: the auction's progress, then the bid's own
+/// exit and claim, with its moneyness while that still matters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BidLifecycleStatus {
+    /// The auction has not started.
+    NotStarted,
+    /// The auction is running and the bid is in the book.
+    Active(Moneyness),
+    /// The auction ended without graduating (yet); exiting refunds the bid in
+    /// full.
+    NotGraduated,
+    /// The auction ended and graduated; the bid still has to exit.
+    NeedsExit(Moneyness),
+    /// Exited with tokens that wait for the claim block.
     Exited { block: BlockNumber },
-    Claimed,
+    /// Exited with tokens that can be claimed now.
+    Claimable,
+    /// Exited with nothing left to claim: claimed, or never filled.
+    Settled,
+}
+
+/// What a bid's [`BidLifecycleStatus`] depends on besides the bid itself.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuctionProgress {
+    pub block: BlockNumber,
+    pub start_block: BlockNumber,
+    pub end_block: BlockNumber,
+    pub claim_block: BlockNumber,
+    pub clearing_price: Price,
+    pub graduation: GraduationStatus,
+}
+
+impl AuctionProgress {
+    pub fn new(
+        block: BlockNumber,
+        config: &AuctionConfig,
+        clearing_price: Price,
+        graduation: GraduationStatus,
+    ) -> Self {
+        Self {
+            block,
+            start_block: config.start_block,
+            end_block: config.end_block,
+            claim_block: config.claim_block,
+            clearing_price,
+            graduation,
+        }
+    }
+}
+
+impl BidLifecycleStatus {
+    /// The status of a bid with `max_price` that exited at `exited_block`,
+    /// if it has, holding `tokens_filled`.
+    pub fn derive(
+        max_price: Price,
+        exited_block: Option<BlockNumber>,
+        tokens_filled: TokenAmount,
+        progress: &AuctionProgress,
+    ) -> Self {
+        let graduated = matches!(progress.graduation, GraduationStatus::Graduated);
+        if let Some(block) = exited_block {
+            return if tokens_filled.is_zero() {
+                Self::Settled
+            } else if graduated && progress.block >= progress.claim_block {
+                Self::Claimable
+            } else {
+                Self::Exited { block }
+            };
+        }
+
+        let moneyness = Moneyness::of(max_price, progress.clearing_price);
+        if progress.block < progress.start_block {
+            Self::NotStarted
+        } else if progress.block < progress.end_block {
+            Self::Active(moneyness)
+        } else if graduated {
+            Self::NeedsExit(moneyness)
+        } else {
+            Self::NotGraduated
+        }
+    }
+
+    /// Nothing is left to do for the bid.
+    pub fn is_settled(self) -> bool {
+        matches!(self, Self::Settled)
+    }
+}
+
+impl fmt::Display for Moneyness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Moneyness::ITM => "in the money",
+            Moneyness::ATM => "at the clearing price",
+            Moneyness::OTM => "outbid",
+        })
+    }
+}
+
+impl fmt::Display for BidLifecycleStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotStarted => f.write_str("not started"),
+            Self::Active(moneyness) => write!(f, "active, {moneyness}"),
+            Self::NotGraduated => f.write_str("not graduated"),
+            Self::NeedsExit(moneyness) => write!(f, "needs exit, {moneyness}"),
+            Self::Exited { block } => write!(f, "exited at {}", block.as_u64()),
+            Self::Claimable => f.write_str("claimable"),
+            Self::Settled => f.write_str("settled"),
+        }
+    }
+}
+
+/// How the executor exits bids sitting exactly at the clearing price.
+///
+/// ATM bids are only partially filled, and keep filling for as long as the
+/// price holds, so unlike OTM bids there can be a reason to hold off.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AtmSettlement {
+    /// Partially exit as soon as an exit is requested, like an OTM bid.
+    #[default]
+    ExitImmediately,
+    /// Defer the exit until `blocks` blocks after `end_block` (but no later
+    /// than `claim_block`), leaving time for later checkpoints to settle the
+    /// final fill first.
+    WaitForCheckpoints { blocks: u64 },
 }
 
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Bid {
     pub id: BidId,
     pub owner: Address,
@@ -26,22 +177,32 @@ pub struct Bid {
 }
 
 impl Bid {
-    pub fn status(&self, clearing_price: Price) -> BidStatus {
-        if self.max_price > clearing_price {
-            BidStatus::ITM
-        } else if self.max_price == clearing_price {
-            BidStatus::ATM
-        } else {
-            BidStatus::OTM
+    /// Decodes the auction's `bids(id)` return value. The contract keeps the
+    /// amount shifted left by 96 bits; `amount` is in plain currency units.
+    pub fn from_onchain(id: BidId, raw: IContinuousClearingAuction::Bid) -> Self {
+        Self {
+            id,
+            owner: raw.owner,
+            max_price: Price::new(raw.maxPrice),
+            amount: CurrencyAmount::new(raw.amountQ96 >> 96),
+            start_block: BlockNumber::new(raw.startBlock),
+            start_cumulative_mps: Mps::new(raw.startCumulativeMps),
+            exited_block: (raw.exitedBlock != 0).then(|| BlockNumber::new(raw.exitedBlock)),
+            tokens_filled: TokenAmount::new(raw.tokensFilled),
         }
     }
 
-    pub fn lifecycle(&self) -> BidLifecycle {
-        match self.exited_block {
-            None => BidLifecycle::Active,
-            Some(_) if self.tokens_filled.is_zero() => BidLifecycle::Claimed,
-            Some(block) => BidLifecycle::Exited { block },
-        }
+    pub fn moneyness(&self, clearing_price: Price) -> Moneyness {
+        Moneyness::of(self.max_price, clearing_price)
+    }
+
+    pub fn lifecycle_status(&self, progress: &AuctionProgress) -> BidLifecycleStatus {
+        BidLifecycleStatus::derive(
+            self.max_price,
+            self.exited_block,
+            self.tokens_filled,
+            progress,
+        )
     }
 
     pub fn needs_exit(&self) -> bool {
@@ -51,9 +212,90 @@ impl Bid {
     pub fn needs_claim(&self) -> bool {
         self.exited_block.is_some() && !self.tokens_filled.is_zero()
     }
+
+    /// Tokens that have been filled and exited but not yet claimed.
+    pub fn unclaimed_tokens(&self) -> TokenAmount {
+        if self.exited_block.is_some() {
+            self.tokens_filled
+        } else {
+            TokenAmount::ZERO
+        }
+    }
 }
 
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TrackedBid {
     pub id: BidId,
     pub tx_hash: B256,
+    /// Labels saying what placed the bid, such as `strategy=ladder` or
+    /// `campaign=launch`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tags: Vec<String>,
+}
+
+impl TrackedBid {
+    /// Whether the bid carries every one of `tags`.
+    pub fn has_tags(&self, tags: &[String]) -> bool {
+        tags.iter().all(|tag| self.tags.contains(tag))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::U256;
+
+    use super::*;
+
+    #[test]
+    fn lifecycle_follows_the_auction_then_the_bid() {
+        let progress = |block: u64, graduation| AuctionProgress {
+            block: BlockNumber::new(block),
+            start_block: BlockNumber::new(10),
+            end_block: BlockNumber::new(20),
+            claim_block: BlockNumber::new(30),
+            clearing_price: Price::new(U256::from(100)),
+            graduation,
+        };
+        let status = |max_price: u64, exited: Option<u64>, tokens: u64, block, graduation| {
+            BidLifecycleStatus::derive(
+                Price::new(U256::from(max_price)),
+                exited.map(BlockNumber::new),
+                TokenAmount::new(U256::from(tokens)),
+                &progress(block, graduation),
+            )
+        };
+        use GraduationStatus::{Graduated, NotGraduated};
+
+        assert_eq!(
+            status(150, None, 0, 5, NotGraduated),
+            BidLifecycleStatus::NotStarted
+        );
+        assert_eq!(
+            status(100, None, 0, 15, NotGraduated),
+            BidLifecycleStatus::Active(Moneyness::ATM)
+        );
+        assert_eq!(
+            status(150, None, 0, 25, NotGraduated),
+            BidLifecycleStatus::NotGraduated
+        );
+        assert_eq!(
+            status(50, None, 0, 25, Graduated),
+            BidLifecycleStatus::NeedsExit(Moneyness::OTM)
+        );
+        assert_eq!(
+            status(150, Some(26), 7, 27, Graduated),
+            BidLifecycleStatus::Exited {
+                block: BlockNumber::new(26)
+            }
+        );
+        assert_eq!(
+            status(150, Some(26), 7, 30, Graduated),
+            BidLifecycleStatus::Claimable
+        );
+        assert_eq!(
+            status(150, Some(26), 0, 30, Graduated),
+            BidLifecycleStatus::Settled
+        );
+    }
 }
@@ -0,0 +1,169 @@
+use std::time::Duration;
+
+use alloy::{
+    primitives::{Address, address},
+    providers::MULTICALL3_ADDRESS,
+};
+
+use super::primitives::BlockNumber;
+
+/// What differs between the chains an auction can run on: how fast blocks
+/// come, how deep a block must be before it is treated as final, and where
+/// Multicall3 lives.
+///
+/// Strategies that count blocks should convert through
+/// [`estimated_time_until`](Self::estimated_time_until) when they mean wall
+/// clock time; 100 blocks are twenty minutes on mainnet but 25 seconds on
+/// Arbitrum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChainProfile {
+    pub chain_id: u64,
+    pub name: &'static str,
+    pub block_time: Duration,
+    /// Blocks after which a block is unlikely to be reorged out.
+    pub finality_depth: u64,
+    pub native_symbol: &'static str,
+    pub multicall3: Address,
+}
+
+impl ChainProfile {
+    pub const ETHEREUM: Self = Self::new(1, "ethereum", 12_000, 64, "ETH");
+    pub const SEPOLIA: Self = Self::new(11_155_111, "sepolia", 12_000, 64, "ETH");
+    pub const OPTIMISM: Self = Self::new(10, "optimism", 2_000, 450, "ETH");
+    pub const BASE: Self = Self::new(8453, "base", 2_000, 450, "ETH");
+    pub const BASE_SEPOLIA: Self = Self::new(84_532, "base-sepolia", 2_000, 450, "ETH");
+    pub const UNICHAIN: Self = Self::new(130, "unichain", 1_000, 900, "ETH");
+    pub const UNICHAIN_SEPOLIA: Self = Self::new(1301, "unichain-sepolia", 1_000, 900, "ETH");
+    pub const ARBITRUM: Self = Self::new(42_161, "arbitrum", 250, 3_600, "ETH");
+    pub const ARBITRUM_SEPOLIA: Self = Self::new(421_614, "arbitrum-sepolia", 250, 3_600, "ETH");
+    pub const POLYGON: Self = Self::new(137, "polygon", 2_000, 128, "POL");
+    pub const ZKSYNC: Self = Self {
+        multicall3: address!("0xF9cda624FBC7e059355ce98a31693d299FACd963"),
+        ..Self::new(324, "zksync", 1_000, 900, "ETH")
+    };
+    pub const ANVIL: Self = Self::new(31_337, "anvil", 1_000, 1, "ETH");
+
+    const KNOWN: [Self; 12] = [
+        Self::ETHEREUM,
+        Self::SEPOLIA,
+        Self::OPTIMISM,
+        Self::BASE,
+        Self::BASE_SEPOLIA,
+        Self::UNICHAIN,
+        Self::UNICHAIN_SEPOLIA,
+        Self::ARBITRUM,
+        Self::ARBITRUM_SEPOLIA,
+        Self::POLYGON,
+        Self::ZKSYNC,
+        Self::ANVIL,
+    ];
+
+    const fn new(
+        chain_id: u64,
+        name: &'static str,
+        block_time_ms: u64,
+        finality_depth: u64,
+        native_symbol: &'static str,
+    ) -> Self {
+        Self {
+            chain_id,
+            name,
+            block_time: Duration::from_millis(block_time_ms),
+            finality_depth,
+            native_symbol,
+            multicall3: MULTICALL3_ADDRESS,
+        }
+    }
+
+    /// The profile of `chain_id`, or mainnet timing under that id for chains
+    /// that are not known.
+    pub fn for_chain(chain_id: u64) -> Self {
+        Self::KNOWN
+            .into_iter()
+            .find(|profile| profile.chain_id == chain_id)
+            .unwrap_or(Self {
+                chain_id,
+                name: "unknown",
+                ..Self::ETHEREUM
+            })
+    }
+
+    pub fn is_known(&self) -> bool {
+        Self::KNOWN
+            .iter()
+            .any(|profile| profile.chain_id == self.chain_id)
+    }
+
+    /// Expected wall clock time for `blocks` blocks to be produced.
+    pub fn duration_of(&self, blocks: u64) -> Duration {
+        let millis = (self.block_time.as_millis() as u64).saturating_mul(blocks);
+        Duration::from_millis(millis)
+    }
+
+    /// Blocks expected within `duration`, rounded down.
+    pub fn blocks_in(&self, duration: Duration) -> u64 {
+        let block_time = self.block_time.as_millis().max(1);
+        (duration.as_millis() / block_time) as u64
+    }
+
+    /// Expected time from `current` until `target` is produced; zero once it
+    /// has been.
+    pub fn estimated_time_until(&self, current: BlockNumber, target: BlockNumber) -> Duration {
+        self.duration_of(target.as_u64().saturating_sub(current.as_u64()))
+    }
+}
+
+impl Default for ChainProfile {
+    fn default() -> Self {
+        Self::ETHEREUM
+    }
+}
+
+/// Serialized as its chain id; deserializing looks the profile up again with
+/// [`for_chain`](ChainProfile::for_chain).
+#[cfg(feature = "serde")]
+impl serde::Serialize for ChainProfile {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.chain_id)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ChainProfile {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <u64 as serde::Deserialize>::deserialize(deserializer).map(Self::for_chain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_blocks_take_different_time_per_chain() {
+        let now = BlockNumber::new(1_000);
+        let end = BlockNumber::new(1_100);
+        assert_eq!(
+            ChainProfile::for_chain(1).estimated_time_until(now, end),
+            Duration::from_secs(1_200)
+        );
+        assert_eq!(
+            ChainProfile::for_chain(42_161).estimated_time_until(now, end),
+            Duration::from_secs(25)
+        );
+        assert_eq!(
+            ChainProfile::ARBITRUM.blocks_in(Duration::from_secs(60)),
+            240
+        );
+        assert_eq!(
+            ChainProfile::BASE.estimated_time_until(end, now),
+            Duration::ZERO
+        );
+
+        let unknown = ChainProfile::for_chain(999_999);
+        assert!(!unknown.is_known());
+        assert_eq!(unknown.chain_id, 999_999);
+        assert_eq!(unknown.block_time, ChainProfile::ETHEREUM.block_time);
+        assert_ne!(ChainProfile::ZKSYNC.multicall3, MULTICALL3_ADDRESS);
+    }
+}
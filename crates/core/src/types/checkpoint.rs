@@ -1,15 +1,47 @@
+use alloy::primitives::U256;
+use flux_abi::{IAuctionStateLens, IContinuousClearingAuction};
+
 use super::primitives::{BlockNumber, Mps, Price};
 
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Checkpoint {
     pub block: BlockNumber,
     pub clearing_price: Price,
     pub cumulative_mps: Mps,
+    /// Sum of `mps / clearing_price` over the auction so far, in Q96; what
+    /// fill estimates are computed from. Zero where it is not known, as in
+    /// backtests.
+    pub cumulative_mps_per_price: U256,
     pub prev_block: BlockNumber,
     pub next_block: BlockNumber,
 }
 
 impl Checkpoint {
+    /// Decodes the auction's checkpoint at `block`.
+    pub fn from_onchain(block: BlockNumber, raw: IContinuousClearingAuction::Checkpoint) -> Self {
+        Self {
+            block,
+            clearing_price: Price::new(raw.clearingPrice),
+            cumulative_mps: Mps::new(raw.cumulativeMps),
+            cumulative_mps_per_price: raw.cumulativeMpsPerPrice,
+            prev_block: BlockNumber::new(raw.prev),
+            next_block: BlockNumber::new(raw.next),
+        }
+    }
+
+    /// Decodes the checkpoint the lens computed for `block`.
+    pub fn from_lens(block: BlockNumber, raw: IAuctionStateLens::Checkpoint) -> Self {
+        Self {
+            block,
+            clearing_price: Price::new(raw.clearingPrice),
+            cumulative_mps: Mps::new(raw.cumulativeMps),
+            cumulative_mps_per_price: raw.cumulativeMpsPerPrice,
+            prev_block: BlockNumber::new(raw.prev),
+            next_block: BlockNumber::new(raw.next),
+        }
+    }
+
     pub fn remaining_mps(&self) -> Mps {
         self.cumulative_mps.remaining()
     }
@@ -0,0 +1,306 @@
+use std::{collections::VecDeque, fmt, str::FromStr, time::Duration};
+
+use crate::error::ScheduleError;
+
+use super::{chain::ChainProfile, config::AuctionConfig, primitives::BlockNumber};
+
+/// Block timestamps kept for estimating the actual block time.
+const SAMPLES: usize = 16;
+
+/// Blocks between timestamp samples once the clock has one.
+const SAMPLE_INTERVAL: u64 = 10;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Maps blocks to wall clock time from a few recent block timestamps.
+///
+/// Until two samples are seen the chain profile's nominal block time is
+/// used; after that the average over the retained samples, so estimates
+/// correct themselves as real blocks arrive faster or slower than nominal.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockClock {
+    chain: ChainProfile,
+    samples: VecDeque<(BlockNumber, u64)>,
+}
+
+impl BlockClock {
+    pub fn new(chain: ChainProfile) -> Self {
+        Self {
+            chain,
+            samples: VecDeque::with_capacity(SAMPLES),
+        }
+    }
+
+    pub fn chain(&self) -> &ChainProfile {
+        &self.chain
+    }
+
+    /// Records `block`'s timestamp, in unix seconds. Blocks at or below the
+    /// latest sample are ignored.
+    pub fn observe(&mut self, block: BlockNumber, timestamp: u64) {
+        if self.latest().is_some_and(|(latest, _)| block <= latest) {
+            return;
+        }
+        if self.samples.len() == SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((block, timestamp));
+    }
+
+    /// Whether `block`'s timestamp is worth reading for drift correction.
+    pub fn needs_sample(&self, block: BlockNumber) -> bool {
+        self.latest()
+            .is_none_or(|(latest, _)| block.as_u64() >= latest.as_u64() + SAMPLE_INTERVAL)
+    }
+
+    /// Most recent block and timestamp observed.
+    pub fn latest(&self) -> Option<(BlockNumber, u64)> {
+        self.samples.back().copied()
+    }
+
+    /// Average block time over the samples, or the chain's nominal one.
+    pub fn block_time(&self) -> Duration {
+        match (self.samples.front(), self.samples.back()) {
+            (Some(&(first, first_ts)), Some(&(last, last_ts))) if last > first => {
+                let blocks = last.as_u64() - first.as_u64();
+                let millis = last_ts.saturating_sub(first_ts) * 1_000 / blocks;
+                Duration::from_millis(millis.max(1))
+            }
+            _ => self.chain.block_time,
+        }
+    }
+
+    /// Expected wall clock time for `blocks` blocks to be produced.
+    pub fn duration_of(&self, blocks: u64) -> Duration {
+        Duration::from_millis(self.block_time_ms().saturating_mul(blocks))
+    }
+
+    /// Blocks expected within `duration`, rounded down.
+    pub fn blocks_in(&self, duration: Duration) -> u64 {
+        (duration.as_millis() / self.block_time_ms() as u128) as u64
+    }
+
+    /// Estimated timestamp of `block`, extrapolated from the latest sample.
+    pub fn timestamp_of(&self, block: BlockNumber) -> Option<u64> {
+        let (latest, latest_ts) = self.latest()?;
+        let block_time = self.block_time_ms();
+        let millis = if block >= latest {
+            let ahead = block.as_u64() - latest.as_u64();
+            (latest_ts * 1_000).saturating_add(ahead.saturating_mul(block_time))
+        } else {
+            let behind = latest.as_u64() - block.as_u64();
+            (latest_ts * 1_000).saturating_sub(behind.saturating_mul(block_time))
+        };
+        Some(millis / 1_000)
+    }
+
+    /// Estimated first block at or after `timestamp`.
+    pub fn block_at(&self, timestamp: u64) -> Option<BlockNumber> {
+        let (latest, latest_ts) = self.latest()?;
+        let block_time = self.block_time_ms();
+        let block = if timestamp >= latest_ts {
+            let blocks = ((timestamp - latest_ts) * 1_000).div_ceil(block_time);
+            latest.as_u64().saturating_add(blocks)
+        } else {
+            let blocks = (latest_ts - timestamp) * 1_000 / block_time;
+            latest.as_u64().saturating_sub(blocks)
+        };
+        Some(BlockNumber::new(block))
+    }
+
+    fn block_time_ms(&self) -> u64 {
+        (self.block_time().as_millis() as u64).max(1)
+    }
+}
+
+impl Default for BlockClock {
+    fn default() -> Self {
+        Self::new(ChainProfile::default())
+    }
+}
+
+/// A point in wall clock time a schedule waits for, such as `18:00 UTC` or
+/// `10m before end`.
+///
+/// Parsed from:
+/// - `HH:MM` or `HH:MM UTC`: the next such time of day, in UTC
+/// - `@<unix seconds>`: an absolute time
+/// - `<duration> before end` / `<duration> after start`, with durations like
+///   `90s`, `10m` or `1h30m`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeTarget {
+    At(u64),
+    /// Seconds after midnight UTC.
+    TimeOfDay(u32),
+    BeforeEnd(Duration),
+    AfterStart(Duration),
+}
+
+impl TimeTarget {
+    /// The block the target is expected at. Relative targets only need the
+    /// block time; absolute ones need a timestamp sample. Re-resolving as
+    /// the clock learns keeps the estimate on the chain's actual pace.
+    pub fn block(&self, clock: &BlockClock, config: &AuctionConfig) -> Option<BlockNumber> {
+        match *self {
+            Self::At(timestamp) => clock.block_at(timestamp),
+            Self::TimeOfDay(_) => self.pin(clock)?.block(clock, config),
+            Self::BeforeEnd(duration) => Some(BlockNumber::new(
+                config
+                    .end_block
+                    .as_u64()
+                    .saturating_sub(clock.blocks_in(duration)),
+            )),
+            Self::AfterStart(duration) => Some(BlockNumber::new(
+                config
+                    .start_block
+                    .as_u64()
+                    .saturating_add(clock.blocks_in(duration)),
+            )),
+        }
+    }
+
+    /// Fixes a time of day to its next occurrence after the clock's latest
+    /// sample, so it does not roll over to the following day once passed.
+    /// Other targets are returned as they are.
+    pub fn pin(&self, clock: &BlockClock) -> Option<Self> {
+        match *self {
+            Self::TimeOfDay(seconds) => {
+                let (_, now) = clock.latest()?;
+                let today = now - now % SECONDS_PER_DAY + u64::from(seconds);
+                let next = if today > now {
+                    today
+                } else {
+                    today + SECONDS_PER_DAY
+                };
+                Some(Self::At(next))
+            }
+            target => Some(target),
+        }
+    }
+}
+
+impl FromStr for TimeTarget {
+    type Err = ScheduleError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| ScheduleError::Invalid {
+            input: input.to_string(),
+            reason: reason.to_string(),
+        };
+        let trimmed = input.trim();
+
+        if let Some(timestamp) = trimmed.strip_prefix('@') {
+            return timestamp
+                .parse()
+                .map(Self::At)
+                .map_err(|_| invalid("expected unix seconds after `@`"));
+        }
+        if let Some(duration) = trimmed.strip_suffix("before end") {
+            return parse_duration(duration.trim())
+                .map(Self::BeforeEnd)
+                .ok_or_else(|| invalid("expected a duration like `10m`"));
+        }
+        if let Some(duration) = trimmed.strip_suffix("after start") {
+            return parse_duration(duration.trim())
+                .map(Self::AfterStart)
+                .ok_or_else(|| invalid("expected a duration like `10m`"));
+        }
+
+        let time = trimmed.strip_suffix("UTC").unwrap_or(trimmed).trim();
+        let (hours, minutes) = time.split_once(':').ok_or_else(|| {
+            invalid("expected `HH:MM UTC`, `@<unix seconds>` or `<duration> before end`")
+        })?;
+        match (hours.parse::<u32>(), minutes.parse::<u32>()) {
+            (Ok(hours), Ok(minute)) if hours < 24 && minute < 60 && minutes.len() == 2 => {
+                Ok(Self::TimeOfDay(hours * 3_600 + minute * 60))
+            }
+            _ => Err(invalid("time of day is out of range")),
+        }
+    }
+}
+
+impl fmt::Display for TimeTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::At(timestamp) => write!(f, "@{timestamp}"),
+            Self::TimeOfDay(seconds) => {
+                write!(f, "{:02}:{:02} UTC", seconds / 3_600, seconds % 3_600 / 60)
+            }
+            Self::BeforeEnd(duration) => write!(f, "{}s before end", duration.as_secs()),
+            Self::AfterStart(duration) => write!(f, "{}s after start", duration.as_secs()),
+        }
+    }
+}
+
+/// Parses durations such as `45s`, `10m` or `1h30m`.
+fn parse_duration(input: &str) -> Option<Duration> {
+    if input.is_empty() {
+        return None;
+    }
+    let mut seconds = 0u64;
+    let mut digits = String::new();
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let unit = match c {
+            's' => 1,
+            'm' => 60,
+            'h' => 3_600,
+            'd' => SECONDS_PER_DAY,
+            _ => return None,
+        };
+        let value: u64 = std::mem::take(&mut digits).parse().ok()?;
+        seconds = seconds.checked_add(value.checked_mul(unit)?)?;
+    }
+    digits.is_empty().then(|| Duration::from_secs(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clock_corrects_block_time_from_samples() {
+        let mut clock = BlockClock::new(ChainProfile::ETHEREUM);
+        assert_eq!(clock.block_at(1_000), None);
+
+        clock.observe(BlockNumber::new(100), 1_000);
+        assert_eq!(clock.block_at(1_120), Some(BlockNumber::new(110)));
+        // Blocks came every 10s rather than 12s.
+        clock.observe(BlockNumber::new(110), 1_100);
+        assert_eq!(clock.block_time(), Duration::from_secs(10));
+        assert_eq!(clock.block_at(1_200), Some(BlockNumber::new(120)));
+        assert_eq!(clock.timestamp_of(BlockNumber::new(105)), Some(1_050));
+        assert!(!clock.needs_sample(BlockNumber::new(115)));
+        assert!(clock.needs_sample(BlockNumber::new(120)));
+    }
+
+    #[test]
+    fn parses_and_pins_time_targets() {
+        assert_eq!(
+            "18:00 UTC".parse::<TimeTarget>().unwrap(),
+            TimeTarget::TimeOfDay(18 * 3_600)
+        );
+        assert_eq!(
+            "1h30m before end".parse::<TimeTarget>().unwrap(),
+            TimeTarget::BeforeEnd(Duration::from_secs(5_400))
+        );
+        assert_eq!(
+            "@1700000000".parse::<TimeTarget>().unwrap(),
+            TimeTarget::At(1_700_000_000)
+        );
+        assert!("25:00".parse::<TimeTarget>().is_err());
+        assert!("10x before end".parse::<TimeTarget>().is_err());
+
+        let mut clock = BlockClock::new(ChainProfile::BASE);
+        // 1970-01-02 19:00 UTC: 18:00 has passed, so it is the next day's.
+        clock.observe(BlockNumber::new(1), SECONDS_PER_DAY + 19 * 3_600);
+        assert_eq!(
+            TimeTarget::TimeOfDay(18 * 3_600).pin(&clock),
+            Some(TimeTarget::At(2 * SECONDS_PER_DAY + 18 * 3_600))
+        );
+    }
+}
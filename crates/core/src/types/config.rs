@@ -1,9 +1,11 @@
-use alloy::primitives::Address;
+use alloy::primitives::{Address, U256};
 
 use super::primitives::{
     BlockNumber, CurrencyAddr, HookAddr, Price, TickSpacing, TokenAddr, TokenAmount,
 };
 
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AuctionConfig {
     pub address: Address,
     pub start_block: BlockNumber,
@@ -28,4 +30,148 @@ impl AuctionConfig {
     pub fn is_native_currency(&self) -> bool {
         self.currency.is_native()
     }
+
+    /// Every price a bid can be placed at, from the first tick above the
+    /// floor up to the max bid price.
+    pub fn tick_iter(&self) -> TickIter {
+        self.ticks_between(self.floor_price, self.max_bid_price)
+    }
+
+    /// The valid bid prices within `low..=high`, lowest first.
+    pub fn ticks_between(&self, low: Price, high: Price) -> TickIter {
+        let spacing = self.tick_spacing.as_u256();
+        TickIter {
+            next: self.first_tick_at_or_above(low).map(|tick| tick.as_u256()),
+            last: high.as_u256().min(self.max_bid_price.as_u256()),
+            spacing,
+        }
+    }
+
+    /// The lowest valid bid price strictly above `price`, or `None` if there
+    /// is none below the max bid price.
+    pub fn next_tick_above(&self, price: Price) -> Option<Price> {
+        self.nth_tick_above(price, 1)
+    }
+
+    /// The `n`th valid bid price above `price`, counting the next tick as
+    /// one. With the clearing price this is the bid `n` ticks in the money.
+    pub fn nth_tick_above(&self, price: Price, n: u64) -> Option<Price> {
+        let spacing = self.tick_spacing.as_u256();
+        let next = price
+            .as_u256()
+            .checked_div(spacing)?
+            .checked_add(U256::from(1))?
+            .checked_mul(spacing)?;
+        let next = self.first_tick_at_or_above(Price::new(next))?;
+        let tick = spacing
+            .checked_mul(U256::from(n.checked_sub(1)?))
+            .and_then(|offset| next.as_u256().checked_add(offset))
+            .map(Price::new)?;
+        (tick <= self.max_bid_price).then_some(tick)
+    }
+
+    /// The lowest valid bid price at or above `price`.
+    fn first_tick_at_or_above(&self, price: Price) -> Option<Price> {
+        let spacing = self.tick_spacing.as_u256();
+        let lowest = self
+            .floor_price
+            .as_u256()
+            .checked_div(spacing)?
+            .checked_add(U256::from(1))?
+            .checked_mul(spacing)?;
+        let rem = price.as_u256() % spacing;
+        let aligned = if rem.is_zero() {
+            price.as_u256()
+        } else {
+            price.as_u256().checked_add(spacing - rem)?
+        };
+        let tick = Price::new(aligned.max(lowest));
+        (tick <= self.max_bid_price).then_some(tick)
+    }
+}
+
+/// Ascending valid bid prices, from [`AuctionConfig::tick_iter`] and
+/// [`AuctionConfig::ticks_between`].
+#[derive(Clone, Debug)]
+pub struct TickIter {
+    next: Option<U256>,
+    last: U256,
+    spacing: U256,
+}
+
+impl Iterator for TickIter {
+    type Item = Price;
+
+    fn next(&mut self) -> Option<Price> {
+        let tick = self.next.filter(|tick| *tick <= self.last)?;
+        self.next = tick.checked_add(self.spacing);
+        Some(Price::new(tick))
+    }
+}
+
+/// Values shared by unit tests across the crate.
+#[cfg(test)]
+pub(crate) mod fixture {
+    use super::*;
+    use crate::types::primitives::CurrencyAmount;
+
+    /// An auction over blocks 0 to 100, claimable at 110, selling 1000
+    /// tokens on ticks of 10 from a floor of 10 up to 1000. Tests change
+    /// what they need with struct update syntax.
+    pub(crate) fn config() -> AuctionConfig {
+        AuctionConfig {
+            address: Address::ZERO,
+            start_block: BlockNumber::new(0),
+            end_block: BlockNumber::new(100),
+            claim_block: BlockNumber::new(110),
+            total_supply: TokenAmount::new(U256::from(1_000)),
+            tick_spacing: TickSpacing::new(U256::from(10)),
+            floor_price: Price::new(U256::from(10)),
+            max_bid_price: Price::new(U256::from(1_000)),
+            currency: CurrencyAddr::new(Address::ZERO),
+            token: TokenAddr::new(Address::ZERO),
+            validation_hook: HookAddr::new(Address::ZERO),
+        }
+    }
+
+    pub(crate) fn price(value: u64) -> Price {
+        Price::new(U256::from(value))
+    }
+
+    pub(crate) fn amount(value: u64) -> CurrencyAmount {
+        CurrencyAmount::new(U256::from(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        fixture::{self, price},
+        *,
+    };
+
+    fn config() -> AuctionConfig {
+        AuctionConfig {
+            max_bid_price: price(55),
+            ..fixture::config()
+        }
+    }
+
+    #[test]
+    fn ticks_stay_between_floor_and_max_bid_price() {
+        let config = config();
+        let ticks: Vec<_> = config.tick_iter().collect();
+        assert_eq!(ticks, [price(20), price(30), price(40), price(50)]);
+        assert!(ticks.iter().all(|&tick| config.is_valid_price(tick)));
+
+        let between: Vec<_> = config.ticks_between(price(25), price(45)).collect();
+        assert_eq!(between, [price(30), price(40)]);
+        assert_eq!(config.ticks_between(price(51), price(60)).count(), 0);
+
+        assert_eq!(config.next_tick_above(price(0)), Some(price(20)));
+        assert_eq!(config.next_tick_above(price(40)), Some(price(50)));
+        assert_eq!(config.nth_tick_above(price(43), 2), None);
+        assert_eq!(config.nth_tick_above(price(23), 3), Some(price(50)));
+        assert_eq!(config.next_tick_above(price(50)), None);
+    }
 }
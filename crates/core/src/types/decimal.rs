@@ -0,0 +1,87 @@
+use alloy::primitives::U256;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+
+use crate::error::PriceError;
+
+use super::primitives::Price;
+
+/// Strongly-typed Q96 price (currency per token).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceQ96(pub U256);
+
+pub type PriceResult<T> = Result<T, PriceError>;
+
+/// Convert a human price (currency_per_token) to Q96.
+///
+/// - `price_human`: expressed as currency per token (e.g. 0.5 USDC per TOKEN)
+/// - `token_decimals` / `currency_decimals`: ERC-20 decimals of token and currency.
+///
+/// The float only goes as far as its shortest decimal form; the scaling is
+/// done in U256 and rounds down.
+pub fn q96_from_ratio(
+    price_human: f64,
+    token_decimals: u8,
+    currency_decimals: u8,
+) -> PriceResult<U256> {
+    let p = Decimal::from_f64(price_human)
+        .filter(|p| !p.is_sign_negative())
+        .ok_or(PriceError::InvalidPrice(price_human))?;
+
+    // p = mantissa / 10^scale currency per token, so one raw token unit costs
+    // mantissa * 10^currency_decimals / 10^(scale + token_decimals) raw currency.
+    let pow10 = |exp: u32| U256::from(10).checked_pow(U256::from(exp));
+    let currency = pow10(u32::from(currency_decimals))
+        .and_then(|scale| scale.checked_mul(U256::from(p.mantissa().unsigned_abs())))
+        .ok_or(PriceError::Overflow)?;
+    let tokens = pow10(p.scale() + u32::from(token_decimals)).ok_or(PriceError::Overflow)?;
+
+    Price::from_ratio(currency, tokens)
+        .map(|price| price.as_u256())
+        .ok_or(PriceError::Overflow)
+}
+
+/// Convert a Q96 price back to a human float (for display only).
+pub fn ratio_from_q96(price_q96: U256, token_decimals: u8, currency_decimals: u8) -> f64 {
+    let scale = 10f64.powi(i32::from(token_decimals) - i32::from(currency_decimals));
+    Price::new(price_q96).to_f64() * scale
+}
+
+impl PriceQ96 {
+    pub fn from_ratio(
+        price_human: f64,
+        token_decimals: u8,
+        currency_decimals: u8,
+    ) -> PriceResult<Self> {
+        Ok(Self(q96_from_ratio(
+            price_human,
+            token_decimals,
+            currency_decimals,
+        )?))
+    }
+
+    pub fn to_ratio(&self, token_decimals: u8, currency_decimals: u8) -> f64 {
+        ratio_from_q96(self.0, token_decimals, currency_decimals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::primitives::Q96;
+
+    #[test]
+    fn scales_by_the_currency_per_token_unit() {
+        // 0.5 USDC (6 decimals) per 18-decimal token: 5e5 raw currency per
+        // 1e18 raw token.
+        let q96 = q96_from_ratio(0.5, 18, 6).unwrap();
+        assert_eq!(
+            q96,
+            Q96 * U256::from(500_000) / U256::from(10).pow(U256::from(18))
+        );
+        assert!((ratio_from_q96(q96, 18, 6) - 0.5).abs() < 1e-9);
+
+        assert_eq!(q96_from_ratio(2.0, 18, 18).unwrap(), Q96 * U256::from(2));
+        assert!(q96_from_ratio(-1.0, 18, 18).is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use alloy::primitives::{U256, U512};
+
+use super::{
+    bid::Bid,
+    primitives::{BidId, CurrencyAmount, Price},
+};
+
+/// Minimum value, in auction currency, worth spending gas to exit or claim.
+///
+/// Most useful for native-currency auctions, where the threshold is directly
+/// comparable to the gas cost of the transaction.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DustThreshold {
+    pub min_value: CurrencyAmount,
+}
+
+/// A bid left untouched because its value was below the dust threshold.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DustSkip {
+    pub bid_id: BidId,
+    pub value: CurrencyAmount,
+}
+
+impl DustThreshold {
+    pub fn new(min_value: CurrencyAmount) -> Self {
+        Self { min_value }
+    }
+
+    /// Upper bound on what exiting `bid` can return: refund plus the value of
+    /// filled tokens never exceeds the amount originally bid.
+    pub fn exit_value(bid: &Bid) -> CurrencyAmount {
+        bid.amount
+    }
+
+    /// Value of the tokens a claim would release, at `clearing_price`.
+    pub fn claim_value(bid: &Bid, clearing_price: Price) -> CurrencyAmount {
+        let value: U512 = bid
+            .tokens_filled
+            .as_u256()
+            .widening_mul(clearing_price.as_u256());
+        CurrencyAmount::new(U256::saturating_from(value >> 96))
+    }
+
+    pub fn is_dust(&self, value: CurrencyAmount) -> bool {
+        value < self.min_value
+    }
+
+    pub fn check_exit(&self, bid: &Bid) -> Option<DustSkip> {
+        let value = Self::exit_value(bid);
+        self.is_dust(value).then_some(DustSkip {
+            bid_id: bid.id,
+            value,
+        })
+    }
+
+    pub fn check_claim(&self, bid: &Bid, clearing_price: Price) -> Option<DustSkip> {
+        let value = Self::claim_value(bid, clearing_price);
+        self.is_dust(value).then_some(DustSkip {
+            bid_id: bid.id,
+            value,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{Address, aliases::U24};
+    use flux_abi::IContinuousClearingAuction;
+
+    use super::*;
+
+    #[test]
+    fn exit_value_is_in_plain_currency_units() {
+        // As `bids(id)` returns it: 1000 currency units, shifted left by 96.
+        let raw = IContinuousClearingAuction::Bid {
+            startBlock: 10,
+            startCumulativeMps: U24::ZERO,
+            exitedBlock: 0,
+            maxPrice: U256::from(1) << 96,
+            owner: Address::repeat_byte(1),
+            amountQ96: U256::from(1_000) << 96,
+            tokensFilled: U256::ZERO,
+        };
+        let bid = Bid::from_onchain(BidId::new(U256::from(7)), raw);
+        assert_eq!(DustThreshold::exit_value(&bid).as_u256(), U256::from(1_000));
+
+        let threshold = |min: u64| DustThreshold::new(CurrencyAmount::new(U256::from(min)));
+        assert!(threshold(1_000).check_exit(&bid).is_none());
+        let skip = threshold(1_001).check_exit(&bid).unwrap();
+        assert_eq!(skip.value.as_u256(), U256::from(1_000));
+    }
+}
@@ -0,0 +1,233 @@
+use std::fmt;
+
+use alloy::primitives::U256;
+
+use super::{
+    action::ExitHints,
+    bid::Bid,
+    checkpoint::Checkpoint,
+    primitives::{BidId, CurrencyAmount, Q96, TokenAmount, mul_div, mul_div_up},
+};
+use crate::error::ValidationError;
+
+/// Estimated fill state of a bid.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FillEstimate {
+    /// Tokens filled so far.
+    pub tokens_filled: TokenAmount,
+    /// Currency spent so far.
+    pub currency_spent: CurrencyAmount,
+    /// Currency still held by the auction on behalf of the bid.
+    pub currency_locked: CurrencyAmount,
+    /// Currency that would be refunded if the bid were exited now.
+    pub projected_refund: CurrencyAmount,
+}
+
+impl FillEstimate {
+    /// Estimate the fill of an active bid between its start checkpoint and `upper`.
+    ///
+    /// Mirrors the contract's fully-filled accounting: the bid spends its
+    /// amount pro-rata over the mps remaining after submission, buying tokens
+    /// at every clearing price in between. `upper` should be the last
+    /// checkpoint at which the bid was still strictly above the clearing
+    /// price; partial fills at exactly the clearing price are not included.
+    pub fn between(bid: &Bid, start: &Checkpoint, upper: &Checkpoint) -> Self {
+        let amount = bid.amount.as_u256();
+        let amount_q96 = amount * Q96;
+        let mps_remaining = bid.start_cumulative_mps.remaining().as_u24().to::<u32>();
+        if mps_remaining == 0 {
+            return Self::full_refund(bid);
+        }
+
+        let mps_delta = upper
+            .cumulative_mps
+            .as_u24()
+            .to::<u32>()
+            .saturating_sub(start.cumulative_mps.as_u24().to::<u32>());
+        let mps_per_price_delta = upper
+            .cumulative_mps_per_price
+            .saturating_sub(start.cumulative_mps_per_price);
+
+        // Both are bounded by the bid's amount; zero if nothing was left to sell.
+        let currency_spent_q96 =
+            mul_div_up(amount_q96, U256::from(mps_delta), U256::from(mps_remaining))
+                .unwrap_or_default()
+                .min(amount_q96);
+        let tokens_filled = mul_div(
+            amount_q96,
+            mps_per_price_delta,
+            Q96 * Q96 * U256::from(mps_remaining),
+        )
+        .unwrap_or_default();
+
+        let currency_spent = currency_spent_q96.div_ceil(Q96).min(amount);
+        let refund = (amount_q96 - currency_spent_q96) / Q96;
+
+        Self {
+            tokens_filled: TokenAmount::new(tokens_filled),
+            currency_spent: CurrencyAmount::new(currency_spent),
+            currency_locked: CurrencyAmount::new(amount - currency_spent),
+            projected_refund: CurrencyAmount::new(refund),
+        }
+    }
+
+    /// Fill state of a bid that has already exited: nothing is locked and
+    /// nothing further is refunded.
+    pub fn exited(bid: &Bid) -> Self {
+        Self {
+            tokens_filled: bid.tokens_filled,
+            currency_spent: CurrencyAmount::ZERO,
+            currency_locked: CurrencyAmount::ZERO,
+            projected_refund: CurrencyAmount::ZERO,
+        }
+    }
+
+    /// Fill state of a bid in an auction that failed to graduate: the whole
+    /// amount is refunded on exit.
+    pub fn full_refund(bid: &Bid) -> Self {
+        Self {
+            tokens_filled: TokenAmount::ZERO,
+            currency_spent: CurrencyAmount::ZERO,
+            currency_locked: bid.amount,
+            projected_refund: bid.amount,
+        }
+    }
+}
+
+/// The auction's two ways out of a bid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExitVariant {
+    /// `exitBid`, for bids above the final clearing price or in an auction
+    /// that did not graduate.
+    ExitBid,
+    /// `exitPartiallyFilledBid`, for bids at or below the clearing price.
+    ExitPartiallyFilled,
+}
+
+/// What one [`ExitVariant`] would return if sent now.
+#[derive(Debug)]
+pub struct ExitEstimate {
+    pub variant: ExitVariant,
+    pub fill: FillEstimate,
+    /// Why the auction would reject the call now; `None` if it would go
+    /// through.
+    pub rejected: Option<ValidationError>,
+}
+
+/// Both exits of a bid side by side, estimated from the checkpoint history
+/// without sending anything.
+///
+/// The partial exit's estimate leaves out tokens filled at exactly the
+/// clearing price, so it may undercount tokens and overcount the refund.
+#[derive(Debug)]
+pub struct ExitPreview {
+    pub bid_id: BidId,
+    pub exit_bid: ExitEstimate,
+    pub exit_partially_filled: ExitEstimate,
+    /// The hints `exitPartiallyFilledBid` would be sent with.
+    pub hints: ExitHints,
+}
+
+impl ExitPreview {
+    /// The exit the auction would accept now, if any.
+    pub fn recommended(&self) -> Option<&ExitEstimate> {
+        [&self.exit_bid, &self.exit_partially_filled]
+            .into_iter()
+            .find(|estimate| estimate.rejected.is_none())
+    }
+}
+
+impl fmt::Display for ExitVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::ExitBid => "exitBid",
+            Self::ExitPartiallyFilled => "exitPartiallyFilledBid",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{Address, aliases::U24};
+
+    use super::*;
+    use crate::types::primitives::{BidId, BlockNumber, Mps, Price};
+
+    const MPS: u32 = Mps::FULL;
+
+    fn bid(amount: u64, start_cumulative_mps: u32) -> Bid {
+        Bid {
+            id: BidId::new(U256::ZERO),
+            owner: Address::ZERO,
+            max_price: Price::new(Q96 * U256::from(10)),
+            amount: CurrencyAmount::new(U256::from(amount)),
+            start_block: BlockNumber::new(0),
+            start_cumulative_mps: Mps::new(U24::from(start_cumulative_mps)),
+            exited_block: None,
+            tokens_filled: TokenAmount::ZERO,
+        }
+    }
+
+    fn checkpoint(price: u64, cumulative_mps: u32, mps_per_price: U256) -> Checkpoint {
+        Checkpoint {
+            block: BlockNumber::new(0),
+            clearing_price: Price::new(Q96 * U256::from(price)),
+            cumulative_mps: Mps::new(U24::from(cumulative_mps)),
+            cumulative_mps_per_price: mps_per_price,
+            prev_block: BlockNumber::new(0),
+            next_block: BlockNumber::TAIL_SENTINEL,
+        }
+    }
+
+    #[test]
+    fn half_elapsed_at_constant_price_spends_half() {
+        let bid = bid(1_000, 0);
+        let start = checkpoint(2, 0, U256::ZERO);
+        // cumulativeMpsPerPrice grows by (mps << 192) / priceQ96.
+        let upper = checkpoint(2, MPS / 2, U256::from(MPS / 2) * Q96 / U256::from(2));
+
+        let estimate = FillEstimate::between(&bid, &start, &upper);
+
+        assert_eq!(estimate.currency_spent.as_u256(), U256::from(500));
+        assert_eq!(estimate.currency_locked.as_u256(), U256::from(500));
+        assert_eq!(estimate.projected_refund.as_u256(), U256::from(500));
+        assert_eq!(estimate.tokens_filled.as_u256(), U256::from(250));
+    }
+
+    #[test]
+    fn bid_submitted_mid_auction_spreads_over_remaining_mps() {
+        let bid = bid(1_000, MPS / 2);
+        let start = checkpoint(1, MPS / 2, U256::ZERO);
+        let upper = checkpoint(1, MPS, U256::from(MPS / 2) * Q96);
+
+        let estimate = FillEstimate::between(&bid, &start, &upper);
+
+        assert_eq!(estimate.currency_spent.as_u256(), U256::from(1_000));
+        assert!(estimate.currency_locked.is_zero());
+        assert_eq!(estimate.tokens_filled.as_u256(), U256::from(1_000));
+    }
+
+    #[test]
+    fn recommends_the_exit_that_would_go_through() {
+        let bid = bid(1_000, 0);
+        let estimate = |variant, rejected| ExitEstimate {
+            variant,
+            fill: FillEstimate::full_refund(&bid),
+            rejected,
+        };
+        let preview = ExitPreview {
+            bid_id: bid.id,
+            exit_bid: estimate(ExitVariant::ExitBid, Some(ValidationError::BidNotITM)),
+            exit_partially_filled: estimate(ExitVariant::ExitPartiallyFilled, None),
+            hints: ExitHints {
+                last_fully_filled_checkpoint_block: BlockNumber::new(0),
+                outbid_block: None,
+            },
+        };
+        assert_eq!(
+            preview.recommended().map(|estimate| estimate.variant),
+            Some(ExitVariant::ExitPartiallyFilled)
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt;
+
+use alloy::primitives::{Address, U256};
+
+use super::{primitives::CurrencyAmount, state::AccountBalances};
+use crate::validation::SUBMIT_BID_GAS;
+
+/// What a strategy will spend: the currency it commits across its bids and
+/// how many bids it sends to do so.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FundingNeed {
+    pub currency: CurrencyAmount,
+    pub bids: u64,
+}
+
+impl FundingNeed {
+    /// Native currency for gas at `gas_price`: every bid plus one more
+    /// transaction to settle, each at [`SUBMIT_BID_GAS`].
+    pub fn gas_cost(&self, gas_price: u128) -> U256 {
+        U256::from(self.bids + 1) * U256::from(SUBMIT_BID_GAS) * U256::from(gas_price)
+    }
+}
+
+/// An owner's balances and allowance in one auction, against which a
+/// [`FundingNeed`] is checked.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FundingStatus {
+    pub owner: Address,
+    pub balances: AccountBalances,
+    /// Currency the auction may pull from the owner; `None` when the
+    /// currency is native and needs no approval.
+    pub allowance: Option<U256>,
+    pub gas_price: u128,
+}
+
+/// Something that would stop a strategy from sending its bids.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FundingWarning {
+    Currency {
+        needed: U256,
+        available: U256,
+    },
+    /// The bids would need an `approve` first; only a warning for
+    /// strategies that do not approve on their own.
+    Allowance {
+        needed: U256,
+        allowance: U256,
+    },
+    Gas {
+        needed: U256,
+        available: U256,
+    },
+}
+
+impl FundingStatus {
+    /// What stands in the way of `need`, in the order it would be hit.
+    pub fn warnings(&self, need: &FundingNeed) -> Vec<FundingWarning> {
+        let mut warnings = Vec::new();
+        let currency = need.currency.as_u256();
+        let available = self.balances.currency.as_u256();
+        if available < currency {
+            warnings.push(FundingWarning::Currency {
+                needed: currency,
+                available,
+            });
+        }
+        if let Some(allowance) = self.allowance
+            && allowance < currency
+        {
+            warnings.push(FundingWarning::Allowance {
+                needed: currency,
+                allowance,
+            });
+        }
+        // Bids in the native currency are paid from the same balance as gas.
+        let mut gas = need.gas_cost(self.gas_price);
+        if self.allowance.is_none() {
+            gas += currency;
+        }
+        if self.balances.native < gas {
+            warnings.push(FundingWarning::Gas {
+                needed: gas,
+                available: self.balances.native,
+            });
+        }
+        warnings
+    }
+}
+
+impl fmt::Display for FundingWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Currency { needed, available } => {
+                write!(
+                    f,
+                    "currency balance {available} is below the {needed} to bid"
+                )
+            }
+            Self::Allowance { needed, allowance } => {
+                write!(
+                    f,
+                    "allowance {allowance} to the auction is below the {needed} to bid"
+                )
+            }
+            Self::Gas { needed, available } => {
+                write!(
+                    f,
+                    "native balance {available} is below the {needed} needed for gas"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(native: u64, currency: u64, allowance: Option<u64>) -> FundingStatus {
+        FundingStatus {
+            owner: Address::ZERO,
+            balances: AccountBalances {
+                native: U256::from(native),
+                currency: CurrencyAmount::new(U256::from(currency)),
+            },
+            allowance: allowance.map(U256::from),
+            gas_price: 1,
+        }
+    }
+
+    #[test]
+    fn warns_about_whatever_blocks_the_bids() {
+        let need = FundingNeed {
+            currency: CurrencyAmount::new(U256::from(1_000)),
+            bids: 3,
+        };
+        let gas = 4 * SUBMIT_BID_GAS;
+
+        assert!(status(gas, 1_000, Some(1_000)).warnings(&need).is_empty());
+        assert_eq!(
+            status(gas - 1, 999, Some(0)).warnings(&need),
+            [
+                FundingWarning::Currency {
+                    needed: U256::from(1_000),
+                    available: U256::from(999),
+                },
+                FundingWarning::Allowance {
+                    needed: U256::from(1_000),
+                    allowance: U256::ZERO,
+                },
+                FundingWarning::Gas {
+                    needed: U256::from(gas),
+                    available: U256::from(gas - 1),
+                },
+            ]
+        );
+        // Native currency: the bids and their gas come out of one balance.
+        assert_eq!(
+            status(gas, gas, None).warnings(&need),
+            [FundingWarning::Gas {
+                needed: U256::from(gas + 1_000),
+                available: U256::from(gas),
+            }]
+        );
+    }
+}
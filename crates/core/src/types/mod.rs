@@ -1,13 +1,41 @@
 pub mod action;
+pub mod amount;
 pub mod bid;
+pub mod chain;
 pub mod checkpoint;
+pub mod clock;
 pub mod config;
+#[cfg(feature = "decimal")]
+pub mod decimal;
+pub mod dust;
+pub mod fill;
+pub mod funding;
+pub mod params;
 pub mod primitives;
+pub mod risk;
+pub mod snapshot;
 pub mod state;
+pub mod step;
+pub mod tick;
+pub mod wallet;
 
 pub use action::*;
+pub use amount::*;
 pub use bid::*;
+pub use chain::*;
 pub use checkpoint::*;
+pub use clock::*;
 pub use config::*;
+#[cfg(feature = "decimal")]
+pub use decimal::*;
+pub use dust::*;
+pub use fill::*;
+pub use funding::*;
+pub use params::*;
 pub use primitives::*;
+pub use risk::*;
+pub use snapshot::*;
 pub use state::*;
+pub use step::*;
+pub use tick::*;
+pub use wallet::*;
@@ -0,0 +1,338 @@
+use alloy::{
+    primitives::{Address, Bytes, U256},
+    sol_types::{SolError, SolValue},
+};
+use flux_abi::{
+    IContinuousClearingAuction::{self, IContinuousClearingAuctionErrors},
+    IContinuousClearingAuctionFactory::IContinuousClearingAuctionFactoryErrors,
+};
+
+use crate::error::ParameterError;
+
+use super::primitives::{
+    BlockNumber, CurrencyAddr, CurrencyAmount, HookAddr, Mps, Price, TickSpacing, TokenAmount,
+};
+
+/// Largest block delta a packed step can hold (`uint40`).
+const MAX_STEP_BLOCKS: u64 = (1 << 40) - 1;
+
+/// One entry of a supply schedule being deployed: `mps` released every
+/// block for `blocks` blocks.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StepSpec {
+    pub mps: Mps,
+    pub blocks: u64,
+}
+
+/// The constructor arguments of a ContinuousClearingAuction, as passed to
+/// the factory's `initializeDistribution` and `getAuctionAddress` in
+/// `configData`.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuctionParameters {
+    pub currency: CurrencyAddr,
+    pub tokens_recipient: Address,
+    pub funds_recipient: Address,
+    pub start_block: BlockNumber,
+    pub end_block: BlockNumber,
+    pub claim_block: BlockNumber,
+    pub tick_spacing: TickSpacing,
+    pub validation_hook: HookAddr,
+    pub floor_price: Price,
+    pub required_currency_raised: CurrencyAmount,
+    pub steps: Vec<StepSpec>,
+}
+
+impl AuctionParameters {
+    /// Checks that the steps release exactly [`Mps::FULL`] and end at
+    /// `end_block`, and that claims open no earlier than the end.
+    pub fn validate(&self) -> Result<(), ParameterError> {
+        match self.schedule_issues().into_iter().next() {
+            Some(issue) => Err(issue),
+            None => Ok(()),
+        }
+    }
+
+    /// Everything [`validate`](Self::validate) checks, with every problem
+    /// found rather than the first.
+    fn schedule_issues(&self) -> Vec<ParameterError> {
+        if self.steps.is_empty() {
+            return vec![ParameterError::NoSteps];
+        }
+
+        let mut issues = Vec::new();
+        let mut total = 0u64;
+        let mut blocks = 0u64;
+        for (index, step) in self.steps.iter().enumerate() {
+            if step.blocks == 0 || step.blocks > MAX_STEP_BLOCKS {
+                issues.push(ParameterError::StepBlocks {
+                    index,
+                    blocks: step.blocks,
+                });
+            }
+            let mps = step.mps.as_u24().to::<u64>();
+            total = total.saturating_add(mps.saturating_mul(step.blocks));
+            blocks = blocks.saturating_add(step.blocks);
+        }
+        if total != u64::from(Mps::FULL) {
+            issues.push(ParameterError::StepsNotFull { total });
+        }
+
+        let steps_end = self.start_block.as_u64().saturating_add(blocks);
+        if steps_end != self.end_block.as_u64() {
+            issues.push(ParameterError::EndBlockMismatch {
+                steps_end,
+                end_block: self.end_block.as_u64(),
+            });
+        }
+        if self.claim_block < self.end_block {
+            issues.push(ParameterError::ClaimBeforeEnd {
+                claim_block: self.claim_block.as_u64(),
+                end_block: self.end_block.as_u64(),
+            });
+        }
+        issues
+    }
+
+    /// The packed `auctionStepsData`: per step a `uint24` mps followed by a
+    /// `uint40` block delta, the layout
+    /// [`StepSchedule::parse`](super::StepSchedule::parse) reads back.
+    pub fn encode_steps(&self) -> Bytes {
+        self.steps
+            .iter()
+            .flat_map(|step| {
+                let mut packed = step.mps.as_u24().to_be_bytes::<3>().to_vec();
+                packed.extend_from_slice(&step.blocks.to_be_bytes()[3..]);
+                packed
+            })
+            .collect()
+    }
+
+    /// Validates the parameters and ABI-encodes them as `configData`.
+    pub fn encode_config_data(&self) -> Result<Bytes, ParameterError> {
+        self.validate()?;
+        let required = self.required_currency_raised.as_u256();
+        let required_currency_raised = u128::try_from(required)
+            .map_err(|_| ParameterError::RequiredCurrencyTooLarge(required))?;
+
+        let params = IContinuousClearingAuction::AuctionParameters {
+            currency: self.currency.as_address(),
+            tokensRecipient: self.tokens_recipient,
+            fundsRecipient: self.funds_recipient,
+            startBlock: self.start_block.as_u64(),
+            endBlock: self.end_block.as_u64(),
+            claimBlock: self.claim_block.as_u64(),
+            tickSpacing: self.tick_spacing.as_u256(),
+            validationHook: self.validation_hook.as_address(),
+            floorPrice: self.floor_price.as_u256(),
+            requiredCurrencyRaised: required_currency_raised,
+            auctionStepsData: self.encode_steps(),
+        };
+        Ok(params.abi_encode().into())
+    }
+}
+
+/// Every misconfiguration the factory or the auction's constructor would
+/// revert on when `params` are used to sell `total_supply` of `token`,
+/// checked the way the contracts do, so it is caught before any gas is
+/// spent. Empty when nothing is wrong.
+///
+/// The auction's max bid price is derived on chain from the supply, so
+/// whether the floor leaves room for a bid below it is left to a simulated
+/// creation; see [`ParameterError::from_revert`].
+pub fn lint_auction_params(
+    params: &AuctionParameters,
+    token: Address,
+    total_supply: TokenAmount,
+) -> Vec<ParameterError> {
+    let mut issues = Vec::new();
+    if token.is_zero() {
+        issues.push(ParameterError::TokenIsZero);
+    }
+    if token == params.currency.as_address() {
+        issues.push(ParameterError::TokenIsCurrency);
+    }
+    if total_supply.is_zero() {
+        issues.push(ParameterError::TotalSupplyIsZero);
+    }
+    if u128::try_from(total_supply.as_u256()).is_err() {
+        issues.push(ParameterError::TotalSupplyTooLarge(total_supply.as_u256()));
+    }
+    if params.tokens_recipient.is_zero() {
+        issues.push(ParameterError::TokensRecipientIsZero);
+    }
+    if params.funds_recipient.is_zero() {
+        issues.push(ParameterError::FundsRecipientIsZero);
+    }
+    if params.end_block <= params.start_block {
+        issues.push(ParameterError::EndNotAfterStart {
+            start_block: params.start_block.as_u64(),
+            end_block: params.end_block.as_u64(),
+        });
+    }
+    issues.extend(params.schedule_issues());
+    let required = params.required_currency_raised.as_u256();
+    if u128::try_from(required).is_err() {
+        issues.push(ParameterError::RequiredCurrencyTooLarge(required));
+    }
+
+    let tick_spacing = params.tick_spacing.as_u256();
+    if params.floor_price.as_u256().is_zero() {
+        issues.push(ParameterError::FloorPriceIsZero);
+    }
+    if tick_spacing < U256::from(TickSpacing::MIN) {
+        issues.push(ParameterError::TickSpacingTooSmall(tick_spacing));
+    } else if !params.floor_price.is_aligned(params.tick_spacing) {
+        issues.push(ParameterError::FloorPriceNotAtTick {
+            floor_price: params.floor_price.as_u256(),
+            tick_spacing,
+        });
+    }
+    issues
+}
+
+impl ParameterError {
+    /// The custom error the contracts revert with for this problem, if they
+    /// check for it.
+    pub fn contract_error(&self) -> Option<&'static str> {
+        Some(match self {
+            Self::NoSteps | Self::StepsNotFull { .. } => "InvalidStepDataMps",
+            Self::StepBlocks { blocks: 0, .. } => "StepBlockDeltaCannotBeZero",
+            Self::EndBlockMismatch { .. } => "InvalidEndBlockGivenStepData",
+            Self::ClaimBeforeEnd { .. } => "ClaimBlockIsBeforeEndBlock",
+            Self::TokenIsZero => "TokenIsAddressZero",
+            Self::TokenIsCurrency => "TokenAndCurrencyCannotBeTheSame",
+            Self::TotalSupplyIsZero => "TotalSupplyIsZero",
+            Self::TotalSupplyTooLarge(_) => "InvalidTokenAmount",
+            Self::TokensRecipientIsZero => "TokensRecipientIsZero",
+            Self::FundsRecipientIsZero => "FundsRecipientIsZero",
+            Self::EndNotAfterStart { .. } => "InvalidEndBlock",
+            Self::FloorPriceIsZero => "FloorPriceIsZero",
+            Self::TickSpacingTooSmall(_) => "TickSpacingTooSmall",
+            Self::FloorPriceNotAtTick { .. } => "TickPriceNotAtBoundary",
+            Self::MaxBidPriceExceeded { .. } => "FloorPriceAndTickSpacingGreaterThanMaxBidPrice",
+            Self::StepBlocks { .. }
+            | Self::RequiredCurrencyTooLarge(_)
+            | Self::CreationReverts(_) => return None,
+        })
+    }
+
+    /// The problem behind `data`, the revert data of a simulated
+    /// `initializeDistribution`.
+    pub fn from_revert(data: &[u8]) -> Self {
+        if let Ok(error) =
+            IContinuousClearingAuction::FloorPriceAndTickSpacingGreaterThanMaxBidPrice::abi_decode(
+                data,
+            )
+        {
+            return Self::MaxBidPriceExceeded {
+                next_tick: error.nextTick,
+                max_bid_price: error.maxBidPrice,
+            };
+        }
+        let name = data
+            .get(..4)
+            .and_then(|selector| selector.try_into().ok())
+            .and_then(|selector| {
+                IContinuousClearingAuctionErrors::name_by_selector(selector)
+                    .or_else(|| IContinuousClearingAuctionFactoryErrors::name_by_selector(selector))
+            });
+        Self::CreationReverts(name.map_or_else(
+            || format!("revert data {}", Bytes::copy_from_slice(data)),
+            str::to_string,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{U256, aliases::U24};
+
+    use super::*;
+    use crate::types::{StepSchedule, primitives::Q96};
+
+    fn params(steps: &[(u32, u64)]) -> AuctionParameters {
+        AuctionParameters {
+            currency: CurrencyAddr::new(Address::ZERO),
+            tokens_recipient: Address::repeat_byte(1),
+            funds_recipient: Address::repeat_byte(2),
+            start_block: BlockNumber::new(100),
+            end_block: BlockNumber::new(175),
+            claim_block: BlockNumber::new(180),
+            tick_spacing: TickSpacing::new(Q96),
+            validation_hook: HookAddr::new(Address::ZERO),
+            floor_price: Price::new(Q96),
+            required_currency_raised: CurrencyAmount::new(U256::from(1_000)),
+            steps: steps
+                .iter()
+                .map(|&(mps, blocks)| StepSpec {
+                    mps: Mps::new(U24::from(mps)),
+                    blocks,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn config_data_round_trips_through_the_abi() {
+        let auction = params(&[(100_000, 50), (200_000, 25)]);
+        let data = auction.encode_config_data().unwrap();
+
+        let decoded = IContinuousClearingAuction::AuctionParameters::abi_decode(&data).unwrap();
+        assert_eq!(decoded.endBlock, 175);
+        assert_eq!(decoded.requiredCurrencyRaised, 1_000);
+        let schedule = StepSchedule::parse(auction.start_block, &decoded.auctionStepsData).unwrap();
+        assert_eq!(schedule.end_block(), Some(BlockNumber::new(175)));
+        assert_eq!(schedule.steps().len(), 2);
+
+        assert!(matches!(
+            params(&[(100_000, 50)]).encode_config_data(),
+            Err(ParameterError::StepsNotFull { total: 5_000_000 })
+        ));
+        assert!(matches!(
+            params(&[(100_000, 100)]).validate(),
+            Err(ParameterError::EndBlockMismatch { steps_end: 200, .. })
+        ));
+    }
+
+    #[test]
+    fn lint_reports_every_problem_with_its_contract_error() {
+        let token = Address::repeat_byte(9);
+        let supply = TokenAmount::new(U256::from(1_000));
+        let auction = params(&[(100_000, 50), (200_000, 25)]);
+        assert!(lint_auction_params(&auction, token, supply).is_empty());
+
+        let mut broken = auction;
+        broken.claim_block = BlockNumber::new(170);
+        broken.funds_recipient = Address::ZERO;
+        broken.floor_price = Price::new(Q96 + U256::from(1));
+        let errors: Vec<_> = lint_auction_params(&broken, token, supply)
+            .iter()
+            .map(|issue| issue.contract_error())
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                Some("FundsRecipientIsZero"),
+                Some("ClaimBlockIsBeforeEndBlock"),
+                Some("TickPriceNotAtBoundary"),
+            ]
+        );
+
+        let revert = IContinuousClearingAuction::FloorPriceAndTickSpacingGreaterThanMaxBidPrice {
+            nextTick: U256::from(3),
+            maxBidPrice: U256::from(2),
+        }
+        .abi_encode();
+        assert!(matches!(
+            ParameterError::from_revert(&revert),
+            ParameterError::MaxBidPriceExceeded { .. }
+        ));
+        let revert = IContinuousClearingAuction::TokensNotReceived {}.abi_encode();
+        assert!(matches!(
+            ParameterError::from_revert(&revert),
+            ParameterError::CreationReverts(name) if name == "TokensNotReceived"
+        ));
+    }
+}
@@ -1,8 +1,32 @@
 use std::ops::{Add, AddAssign};
 
-use alloy::primitives::{Address, U256, aliases::U24};
+use alloy::primitives::{Address, U256, U512, aliases::U24, ruint::UintTryFrom};
+
+/// 2^96, used for Uniswap-style Q96 fixed point prices.
+pub const Q96: U256 = U256::from_limbs([0, 1 << (96 - 64), 0, 0]);
+
+/// `a * b / denominator` rounded down, without overflowing in between.
+/// `None` if `denominator` is zero or the result does not fit in a U256.
+pub fn mul_div(a: U256, b: U256, denominator: U256) -> Option<U256> {
+    if denominator.is_zero() {
+        return None;
+    }
+    let product: U512 = a.widening_mul(b);
+    U256::uint_try_from(product / U512::from(denominator)).ok()
+}
+
+/// [`mul_div`] rounded up.
+pub fn mul_div_up(a: U256, b: U256, denominator: U256) -> Option<U256> {
+    if denominator.is_zero() {
+        return None;
+    }
+    let product: U512 = a.widening_mul(b);
+    U256::uint_try_from(product.div_ceil(U512::from(denominator))).ok()
+}
 
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct TickSpacing(U256);
 
 impl TickSpacing {
@@ -22,6 +46,8 @@ impl TickSpacing {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Price(U256);
 
 impl Price {
@@ -39,6 +65,62 @@ impl Price {
         self.0 % tick_spacing.0 == U256::ZERO
     }
 
+    /// `currency` raw units per `tokens` raw units as a Q96 price, rounded
+    /// down. `None` for zero tokens or a price past `U256::MAX`.
+    pub fn from_ratio(currency: U256, tokens: U256) -> Option<Self> {
+        mul_div(currency, Q96, tokens).map(Self)
+    }
+
+    /// The Q96 price of `currency` raw units per whole token of
+    /// `token_decimals`, rounded down.
+    pub fn per_whole_token(currency: CurrencyAmount, token_decimals: u8) -> Option<Self> {
+        let one_token = U256::from(10).checked_pow(U256::from(token_decimals))?;
+        Self::from_ratio(currency.0, one_token)
+    }
+
+    /// Currency `tokens` cost at this price, rounded down.
+    pub fn currency_for(&self, tokens: TokenAmount) -> Option<CurrencyAmount> {
+        mul_div(tokens.0, self.0, Q96).map(CurrencyAmount)
+    }
+
+    /// Tokens `currency` buys at this price, rounded down; `None` at a zero
+    /// price.
+    pub fn tokens_for(&self, currency: CurrencyAmount) -> Option<TokenAmount> {
+        mul_div(currency.0, Q96, self.0).map(TokenAmount)
+    }
+
+    /// How many ticks of `tick_spacing` this price sits above `floor`; `None`
+    /// if it is below the floor or between ticks.
+    pub fn ticks_above(&self, floor: Price, tick_spacing: TickSpacing) -> Option<U256> {
+        let offset = self.0.checked_sub(floor.0)?;
+        let ticks = offset.checked_div(tick_spacing.0)?;
+        (offset % tick_spacing.0).is_zero().then_some(ticks)
+    }
+
+    /// The price `ticks` ticks of `tick_spacing` above `floor`; `None` on
+    /// overflow.
+    pub fn at_ticks_above(floor: Price, tick_spacing: TickSpacing, ticks: U256) -> Option<Self> {
+        tick_spacing
+            .0
+            .checked_mul(ticks)
+            .and_then(|offset| floor.0.checked_add(offset))
+            .map(Self)
+    }
+
+    /// The highest tick at or below this price, counting ticks from `floor`;
+    /// `None` below the floor or for a zero spacing.
+    pub fn align_down(&self, floor: Price, tick_spacing: TickSpacing) -> Option<Self> {
+        let offset = self.0.checked_sub(floor.0)?;
+        let rem = offset.checked_rem(tick_spacing.0)?;
+        Some(Self(self.0 - rem))
+    }
+
+    /// Raw currency units per raw token unit as a float, for display and
+    /// metrics only.
+    pub fn to_f64(&self) -> f64 {
+        f64::from(self.0) / f64::from(Q96)
+    }
+
     pub fn clamp_to_nearest_tick(
         &self,
         tick_spacing: TickSpacing,
@@ -65,7 +147,9 @@ impl Price {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct CurrencyAmount(U256);
 
 impl CurrencyAmount {
@@ -88,7 +172,23 @@ impl CurrencyAmount {
     }
 }
 
+impl Add for CurrencyAmount {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for CurrencyAmount {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct TokenAmount(U256);
 
 impl TokenAmount {
@@ -121,7 +221,9 @@ impl AddAssign for TokenAmount {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct BidId(U256);
 
 impl BidId {
@@ -135,6 +237,8 @@ impl BidId {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct BlockNumber(u64);
 
 impl BlockNumber {
@@ -150,6 +254,8 @@ impl BlockNumber {
 }
 
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Mps(U24);
 
 impl Mps {
@@ -174,6 +280,8 @@ impl Mps {
 }
 
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct CurrencyAddr(Address);
 
 impl CurrencyAddr {
@@ -191,6 +299,8 @@ impl CurrencyAddr {
 }
 
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct TokenAddr(Address);
 
 impl TokenAddr {
@@ -204,6 +314,8 @@ impl TokenAddr {
 }
 
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct HookAddr(Address);
 
 impl HookAddr {
@@ -219,3 +331,50 @@ impl HookAddr {
         self.0 != Address::ZERO
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mul_div_keeps_intermediate_precision() {
+        assert_eq!(mul_div(U256::MAX, Q96, Q96), Some(U256::MAX));
+        assert_eq!(mul_div(U256::MAX, U256::from(2), U256::from(1)), None);
+        assert_eq!(mul_div(U256::from(1), U256::from(1), U256::ZERO), None);
+        assert_eq!(
+            mul_div_up(U256::from(7), U256::from(1), U256::from(2)),
+            Some(U256::from(4))
+        );
+    }
+
+    #[test]
+    fn ticks_round_trip_from_the_floor() {
+        let floor = Price::new(U256::from(100));
+        let spacing = TickSpacing::new(U256::from(10));
+        let price = Price::at_ticks_above(floor, spacing, U256::from(3)).unwrap();
+        assert_eq!(price.as_u256(), U256::from(130));
+        assert_eq!(price.ticks_above(floor, spacing), Some(U256::from(3)));
+        assert_eq!(
+            Price::new(U256::from(135)).ticks_above(floor, spacing),
+            None
+        );
+        assert_eq!(
+            Price::new(U256::from(135)).align_down(floor, spacing),
+            Some(price)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_amounts_and_addresses_as_hex() {
+        let price = Price::new(U256::from(255));
+        assert_eq!(serde_json::to_string(&price).unwrap(), r#""0xff""#);
+        assert_eq!(serde_json::from_str::<Price>(r#""0xff""#).unwrap(), price);
+
+        let currency = CurrencyAddr::new(Address::repeat_byte(0xab));
+        assert_eq!(
+            serde_json::to_string(&currency).unwrap(),
+            format!(r#""0x{}""#, "ab".repeat(20))
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    time::{Duration, Instant},
+};
+
+use alloy::primitives::U256;
+
+use super::primitives::{CurrencyAmount, Price};
+
+const HOUR: Duration = Duration::from_secs(60 * 60);
+
+/// Hard limits the executor enforces on every intent, whatever the strategy
+/// asks for. Unset limits are not enforced.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RiskLimits {
+    /// Total currency committed across all submitted bids.
+    pub max_total_spend: Option<CurrencyAmount>,
+    pub max_bid_amount: Option<CurrencyAmount>,
+    pub max_price: Option<Price>,
+    /// Gas estimate above which a transaction is not sent.
+    pub max_gas_per_tx: Option<u64>,
+    pub max_txs_per_hour: Option<u32>,
+}
+
+impl RiskLimits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_total_spend(mut self, amount: CurrencyAmount) -> Self {
+        self.max_total_spend = Some(amount);
+        self
+    }
+
+    pub fn with_max_bid_amount(mut self, amount: CurrencyAmount) -> Self {
+        self.max_bid_amount = Some(amount);
+        self
+    }
+
+    pub fn with_max_price(mut self, price: Price) -> Self {
+        self.max_price = Some(price);
+        self
+    }
+
+    pub fn with_max_gas_per_tx(mut self, gas: u64) -> Self {
+        self.max_gas_per_tx = Some(gas);
+        self
+    }
+
+    pub fn with_max_txs_per_hour(mut self, txs: u32) -> Self {
+        self.max_txs_per_hour = Some(txs);
+        self
+    }
+}
+
+/// Which limit an intent would break, and by how much.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RiskViolation {
+    TotalSpend {
+        spent: CurrencyAmount,
+        requested: CurrencyAmount,
+        limit: CurrencyAmount,
+    },
+    BidAmount {
+        amount: CurrencyAmount,
+        limit: CurrencyAmount,
+    },
+    Price {
+        price: Price,
+        limit: Price,
+    },
+    GasPerTx {
+        estimate: u64,
+        limit: u64,
+    },
+    TxRate {
+        sent_last_hour: u32,
+        limit: u32,
+    },
+}
+
+impl fmt::Display for RiskViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TotalSpend {
+                spent,
+                requested,
+                limit,
+            } => write!(
+                f,
+                "bid of {} on top of {} spent exceeds total spend limit {}",
+                requested.as_u256(),
+                spent.as_u256(),
+                limit.as_u256()
+            ),
+            Self::BidAmount { amount, limit } => write!(
+                f,
+                "bid amount {} exceeds limit {}",
+                amount.as_u256(),
+                limit.as_u256()
+            ),
+            Self::Price { price, limit } => write!(
+                f,
+                "bid price {} exceeds limit {}",
+                price.as_u256(),
+                limit.as_u256()
+            ),
+            Self::GasPerTx { estimate, limit } => {
+                write!(
+                    f,
+                    "gas estimate {estimate} exceeds per-transaction limit {limit}"
+                )
+            }
+            Self::TxRate {
+                sent_last_hour,
+                limit,
+            } => write!(
+                f,
+                "{sent_last_hour} transactions sent in the last hour, limit is {limit}"
+            ),
+        }
+    }
+}
+
+/// Running totals checked against [`RiskLimits`].
+#[derive(Clone, Debug, Default)]
+pub struct RiskTracker {
+    limits: RiskLimits,
+    spent: U256,
+    sent: VecDeque<Instant>,
+}
+
+impl RiskTracker {
+    pub fn new(limits: RiskLimits) -> Self {
+        Self {
+            limits,
+            spent: U256::ZERO,
+            sent: VecDeque::new(),
+        }
+    }
+
+    pub fn limits(&self) -> &RiskLimits {
+        &self.limits
+    }
+
+    /// Enforces `limits` from now on. What was spent and sent so far still
+    /// counts against them.
+    pub fn set_limits(&mut self, limits: RiskLimits) {
+        self.limits = limits;
+    }
+
+    /// Currency committed to bids so far.
+    pub fn spent(&self) -> CurrencyAmount {
+        CurrencyAmount::new(self.spent)
+    }
+
+    /// Checks whether another transaction may be sent at `now`.
+    pub fn check_tx(&mut self, now: Instant) -> Result<(), RiskViolation> {
+        let Some(limit) = self.limits.max_txs_per_hour else {
+            return Ok(());
+        };
+        while self
+            .sent
+            .front()
+            .is_some_and(|&sent| now.duration_since(sent) >= HOUR)
+        {
+            self.sent.pop_front();
+        }
+
+        let sent_last_hour = u32::try_from(self.sent.len()).unwrap_or(u32::MAX);
+        if sent_last_hour >= limit {
+            return Err(RiskViolation::TxRate {
+                sent_last_hour,
+                limit,
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks a bid against the amount, price and total spend limits.
+    pub fn check_bid(&self, amount: CurrencyAmount, price: Price) -> Result<(), RiskViolation> {
+        if let Some(limit) = self.limits.max_bid_amount
+            && amount > limit
+        {
+            return Err(RiskViolation::BidAmount { amount, limit });
+        }
+
+        if let Some(limit) = self.limits.max_price
+            && price > limit
+        {
+            return Err(RiskViolation::Price { price, limit });
+        }
+
+        if let Some(limit) = self.limits.max_total_spend
+            && self.spent.saturating_add(amount.as_u256()) > limit.as_u256()
+        {
+            return Err(RiskViolation::TotalSpend {
+                spent: self.spent(),
+                requested: amount,
+                limit,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn record_tx(&mut self, now: Instant) {
+        self.sent.push_back(now);
+    }
+
+    pub fn record_spend(&mut self, amount: CurrencyAmount) {
+        self.spent = self.spent.saturating_add(amount.as_u256());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::config::fixture::amount;
+
+    #[test]
+    fn total_spend_accumulates_across_bids() {
+        let price = Price::new(U256::from(10));
+        let mut tracker = RiskTracker::new(RiskLimits::new().with_max_total_spend(amount(100)));
+
+        tracker.check_bid(amount(60), price).unwrap();
+        tracker.record_spend(amount(60));
+        assert_eq!(
+            tracker.check_bid(amount(50), price),
+            Err(RiskViolation::TotalSpend {
+                spent: amount(60),
+                requested: amount(50),
+                limit: amount(100),
+            })
+        );
+        tracker.check_bid(amount(40), price).unwrap();
+
+        tracker.set_limits(RiskLimits::new().with_max_total_spend(amount(80)));
+        assert!(tracker.check_bid(amount(40), price).is_err());
+        tracker.check_bid(amount(20), price).unwrap();
+    }
+
+    #[test]
+    fn tx_rate_window_slides() {
+        let start = Instant::now();
+        let mut tracker = RiskTracker::new(RiskLimits::new().with_max_txs_per_hour(2));
+
+        tracker.record_tx(start);
+        tracker.record_tx(start + Duration::from_secs(60));
+        assert!(matches!(
+            tracker.check_tx(start + Duration::from_secs(120)),
+            Err(RiskViolation::TxRate {
+                sent_last_hour: 2,
+                ..
+            })
+        ));
+        tracker.check_tx(start + HOUR).unwrap();
+    }
+}
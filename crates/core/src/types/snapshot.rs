@@ -0,0 +1,289 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use alloy::primitives::{Address, U256};
+
+use super::{bid::Bid, config::AuctionConfig, state::AuctionState, tick::TickBook};
+
+/// Everything a strategy could have based a decision on at one block: the
+/// auction's config and state, the ticks above the clearing price and one
+/// owner's bids. Saved to compare with a later one through
+/// [`diff`](Self::diff).
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuctionSnapshot {
+    pub config: AuctionConfig,
+    pub state: AuctionState,
+    pub tick_book: TickBook,
+    /// Whose bids `bids` are; `None` when no owner was given.
+    pub owner: Option<Address>,
+    pub bids: Vec<Bid>,
+}
+
+/// One value that differs between two [`AuctionSnapshot`]s; `None` where it
+/// only exists in one of them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SnapshotChange {
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl AuctionSnapshot {
+    /// What changed from this snapshot to `later`, config first, then state,
+    /// ticks by ascending price and bids by id.
+    pub fn diff(&self, later: &AuctionSnapshot) -> Vec<SnapshotChange> {
+        let mut changes = Changes::default();
+
+        let (a, b) = (&self.config, &later.config);
+        changes.value("config.address", a.address, b.address);
+        changes.value(
+            "config.start_block",
+            a.start_block.as_u64(),
+            b.start_block.as_u64(),
+        );
+        changes.value(
+            "config.end_block",
+            a.end_block.as_u64(),
+            b.end_block.as_u64(),
+        );
+        changes.value(
+            "config.claim_block",
+            a.claim_block.as_u64(),
+            b.claim_block.as_u64(),
+        );
+        changes.value(
+            "config.total_supply",
+            a.total_supply.as_u256(),
+            b.total_supply.as_u256(),
+        );
+        changes.value(
+            "config.floor_price",
+            a.floor_price.as_u256(),
+            b.floor_price.as_u256(),
+        );
+        changes.value(
+            "config.tick_spacing",
+            a.tick_spacing.as_u256(),
+            b.tick_spacing.as_u256(),
+        );
+
+        let (a, b) = (&self.state, &later.state);
+        changes.value("block", a.current_block.as_u64(), b.current_block.as_u64());
+        changes.value("phase", format!("{:?}", a.phase), format!("{:?}", b.phase));
+        changes.value(
+            "graduation",
+            format!("{:?}", a.graduation),
+            format!("{:?}", b.graduation),
+        );
+        changes.value(
+            "tokens_received",
+            format!("{:?}", a.tokens_received),
+            format!("{:?}", b.tokens_received),
+        );
+        let (ca, cb) = (&a.checkpoint, &b.checkpoint);
+        changes.value("checkpoint.block", ca.block.as_u64(), cb.block.as_u64());
+        changes.value(
+            "checkpoint.clearing_price",
+            ca.clearing_price.as_u256(),
+            cb.clearing_price.as_u256(),
+        );
+        changes.value(
+            "checkpoint.cumulative_mps",
+            ca.cumulative_mps.as_u24(),
+            cb.cumulative_mps.as_u24(),
+        );
+        changes.value(
+            "metrics.currency_raised",
+            a.metrics.currency_raised.as_u256(),
+            b.metrics.currency_raised.as_u256(),
+        );
+        changes.value(
+            "metrics.total_cleared",
+            a.metrics.total_cleared.as_u256(),
+            b.metrics.total_cleared.as_u256(),
+        );
+
+        let ticks = |book: &TickBook| -> BTreeMap<U256, String> {
+            book.levels
+                .iter()
+                .map(|level| (level.price.as_u256(), level.demand.as_u256().to_string()))
+                .collect()
+        };
+        changes.keyed("tick", &ticks(&self.tick_book), &ticks(&later.tick_book));
+        changes.value(
+            "tick_book.truncated",
+            self.tick_book.truncated,
+            later.tick_book.truncated,
+        );
+
+        changes.value(
+            "owner",
+            format!("{:?}", self.owner),
+            format!("{:?}", later.owner),
+        );
+        let bids = |bids: &[Bid]| -> BTreeMap<U256, String> {
+            bids.iter()
+                .map(|bid| (bid.id.as_u256(), describe_bid(bid)))
+                .collect()
+        };
+        changes.keyed("bid", &bids(&self.bids), &bids(&later.bids));
+
+        changes.0
+    }
+}
+
+fn describe_bid(bid: &Bid) -> String {
+    let exited = bid
+        .exited_block
+        .map_or("no".to_string(), |block| block.as_u64().to_string());
+    format!(
+        "max_price={} amount={} tokens_filled={} exited={exited}",
+        bid.max_price.as_u256(),
+        bid.amount.as_u256(),
+        bid.tokens_filled.as_u256()
+    )
+}
+
+#[derive(Default)]
+struct Changes(Vec<SnapshotChange>);
+
+impl Changes {
+    fn value(&mut self, field: &str, before: impl ToString, after: impl ToString) {
+        let (before, after) = (before.to_string(), after.to_string());
+        if before != after {
+            self.0.push(SnapshotChange {
+                field: field.to_string(),
+                before: Some(before),
+                after: Some(after),
+            });
+        }
+    }
+
+    /// Entries added, removed or changed between two maps keyed by id or
+    /// price, as `prefix key`.
+    fn keyed(
+        &mut self,
+        prefix: &str,
+        before: &BTreeMap<U256, String>,
+        after: &BTreeMap<U256, String>,
+    ) {
+        let keys: BTreeSet<_> = before.keys().chain(after.keys()).collect();
+        for key in keys {
+            let (a, b) = (before.get(key), after.get(key));
+            if a != b {
+                self.0.push(SnapshotChange {
+                    field: format!("{prefix} {key}"),
+                    before: a.cloned(),
+                    after: b.cloned(),
+                });
+            }
+        }
+    }
+}
+
+impl fmt::Display for SnapshotChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let side = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.field,
+            side(&self.before),
+            side(&self.after)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::aliases::U24;
+
+    use super::*;
+    use crate::types::{
+        checkpoint::Checkpoint,
+        config::fixture,
+        primitives::{BidId, BlockNumber, CurrencyAmount, Mps, Price, TokenAmount},
+        state::{GraduationStatus, TokenDepositStatus},
+        tick::TickDemand,
+    };
+
+    fn snapshot(block: u64, ticks: &[(u64, u64)], bids: &[(u64, u64)]) -> AuctionSnapshot {
+        let config = AuctionConfig {
+            start_block: BlockNumber::new(10),
+            ..fixture::config()
+        };
+        let checkpoint = Checkpoint {
+            block: BlockNumber::new(block),
+            clearing_price: Price::new(U256::from(10)),
+            cumulative_mps: Mps::new(U24::ZERO),
+            cumulative_mps_per_price: U256::ZERO,
+            prev_block: BlockNumber::new(0),
+            next_block: BlockNumber::TAIL_SENTINEL,
+        };
+        let state = AuctionState::new(
+            BlockNumber::new(block),
+            checkpoint,
+            GraduationStatus::NotGraduated,
+            TokenDepositStatus::Received,
+            &config,
+        );
+        let tick_book = TickBook::new(
+            ticks
+                .iter()
+                .map(|&(price, demand)| TickDemand {
+                    price: Price::new(U256::from(price)),
+                    demand: CurrencyAmount::new(U256::from(demand)),
+                })
+                .collect(),
+            false,
+        );
+        let bids = bids
+            .iter()
+            .map(|&(id, tokens_filled)| Bid {
+                id: BidId::new(U256::from(id)),
+                owner: Address::ZERO,
+                max_price: Price::new(U256::from(50)),
+                amount: CurrencyAmount::new(U256::from(100)),
+                start_block: BlockNumber::new(10),
+                start_cumulative_mps: Mps::new(U24::ZERO),
+                exited_block: None,
+                tokens_filled: TokenAmount::new(U256::from(tokens_filled)),
+            })
+            .collect();
+        AuctionSnapshot {
+            config,
+            state,
+            tick_book,
+            owner: Some(Address::ZERO),
+            bids,
+        }
+    }
+
+    #[test]
+    fn diff_reports_changed_added_and_removed_entries() {
+        let before = snapshot(20, &[(20, 100), (30, 50)], &[(1, 0)]);
+        let after = snapshot(21, &[(20, 150), (40, 10)], &[(1, 0), (2, 0)]);
+
+        let fields: Vec<_> = before
+            .diff(&after)
+            .into_iter()
+            .map(|change| change.to_string())
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                "block: 20 -> 21",
+                "phase: Active { blocks_remaining: 80 } -> Active { blocks_remaining: 79 }",
+                "checkpoint.block: 20 -> 21",
+                "tick 20: 100 -> 150",
+                "tick 30: 50 -> -",
+                "tick 40: - -> 10",
+                "bid 2: - -> max_price=50 amount=100 tokens_filled=0 exited=no",
+            ]
+        );
+        assert!(before.diff(&before).is_empty());
+    }
+}
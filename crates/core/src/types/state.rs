@@ -1,10 +1,16 @@
+use std::time::Duration;
+
+use alloy::primitives::U256;
+
 use super::{
+    chain::ChainProfile,
     checkpoint::Checkpoint,
     config::AuctionConfig,
-    primitives::{BlockNumber, CurrencyAmount},
+    primitives::{BlockNumber, CurrencyAmount, Mps, TokenAmount, mul_div},
 };
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GraduationStatus {
     #[default]
     NotGraduated,
@@ -12,6 +18,7 @@ pub enum GraduationStatus {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TokenDepositStatus {
     #[default]
     Unknown,
@@ -20,6 +27,7 @@ pub enum TokenDepositStatus {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AuctionPhase {
     PreStart { blocks_until_start: u64 },
     PreTokens,
@@ -28,14 +36,165 @@ pub enum AuctionPhase {
     Claimable,
 }
 
+/// How far the auction is towards graduating, as of the latest checkpoint.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuctionMetrics {
+    pub currency_raised: CurrencyAmount,
+    pub total_cleared: TokenAmount,
+    /// The auction graduates once it raises this much; `None` if not known.
+    pub required_currency_raised: Option<CurrencyAmount>,
+}
+
+/// Checkpoint, metrics and graduation read together; see
+/// [`AuctionClient::fetch_state_via_lens`](crate::AuctionClient::fetch_state_via_lens).
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateSnapshot {
+    pub checkpoint: Checkpoint,
+    pub metrics: AuctionMetrics,
+    pub graduation: GraduationStatus,
+}
+
+/// How contested an auction is: how many bids it has taken and how its
+/// demand is spread over the ticks.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Competition {
+    /// Bids submitted so far, i.e. `nextBidId()`.
+    pub total_bids: u64,
+    /// Demand resting across every tick.
+    pub total_demand: CurrencyAmount,
+    /// See [`DemandCurve::concentration`](super::tick::DemandCurve::concentration).
+    pub concentration: Option<f64>,
+}
+
+impl Competition {
+    /// Fraction (0 to 1) of all bids that `count` bids make up.
+    pub fn bid_share(&self, count: u64) -> Option<f64> {
+        (self.total_bids > 0).then(|| (count as f64 / self.total_bids as f64).min(1.0))
+    }
+
+    /// Fraction (0 to 1) of the resting demand that `amount` makes up.
+    pub fn demand_share(&self, amount: CurrencyAmount) -> Option<f64> {
+        let total = self.total_demand.as_u256();
+        if total.is_zero() {
+            return None;
+        }
+        let ppm = amount.as_u256().min(total) * U256::from(1_000_000) / total;
+        Some(ppm.to::<u64>() as f64 / 1_000_000.0)
+    }
+}
+
+/// What an account holds to pay for a bid with.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountBalances {
+    /// Native balance, which pays for gas.
+    pub native: U256,
+    /// Balance in the auction's currency; the native balance again when the
+    /// currency is native.
+    pub currency: CurrencyAmount,
+}
+
+impl Default for AuctionMetrics {
+    fn default() -> Self {
+        Self {
+            currency_raised: CurrencyAmount::ZERO,
+            total_cleared: TokenAmount::ZERO,
+            required_currency_raised: None,
+        }
+    }
+}
+
+impl AuctionMetrics {
+    /// Currency raised as a fraction of what graduation requires; 1.0 or more
+    /// means the auction would graduate if it ended now. `None` when the
+    /// requirement is unknown.
+    pub fn graduation_progress(&self) -> Option<f64> {
+        let required = self.required_currency_raised?.as_u256();
+        if required.is_zero() {
+            return Some(1.0);
+        }
+        let ppm = self
+            .currency_raised
+            .as_u256()
+            .saturating_mul(U256::from(1_000_000))
+            / required;
+        Some(ppm.saturating_to::<u64>() as f64 / 1_000_000.0)
+    }
+}
+
+/// Currency raised against what graduation requires, and what the auction
+/// is on course to raise by the end.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RaiseProgress {
+    pub raised: CurrencyAmount,
+    pub required: CurrencyAmount,
+    /// The raise if the rest of the supply sells for what the supply sold
+    /// so far did on average; `None` before any is sold.
+    pub projected: Option<CurrencyAmount>,
+}
+
+impl RaiseProgress {
+    /// Progress from `metrics` once `sold` of the supply is sold; `None`
+    /// when the requirement is unknown.
+    ///
+    /// The projection carries the raise per unit of supply forward, so it
+    /// counts thin demand that leaves supply uncleared, and stays low while
+    /// the clearing price is still rising.
+    pub fn new(metrics: &AuctionMetrics, sold: Mps) -> Option<Self> {
+        let sold = U256::from(sold.as_u24().to::<u32>());
+        let projected = if sold.is_zero() {
+            None
+        } else {
+            mul_div(
+                metrics.currency_raised.as_u256(),
+                U256::from(Mps::FULL),
+                sold,
+            )
+            .map(CurrencyAmount::new)
+        };
+        Some(Self {
+            raised: metrics.currency_raised,
+            required: metrics.required_currency_raised?,
+            projected,
+        })
+    }
+
+    /// Projected raise as a fraction of the requirement; 1.0 or more means
+    /// the auction is on course to graduate.
+    pub fn projected_ratio(&self) -> Option<f64> {
+        let projected = self.projected?.as_u256();
+        let required = self.required.as_u256();
+        if required.is_zero() {
+            return Some(1.0);
+        }
+        let ppm = projected.saturating_mul(U256::from(1_000_000)) / required;
+        Some(ppm.saturating_to::<u64>() as f64 / 1_000_000.0)
+    }
+}
+
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AuctionState {
     pub current_block: BlockNumber,
     pub phase: AuctionPhase,
     pub checkpoint: Checkpoint,
     pub graduation: GraduationStatus,
     pub tokens_received: TokenDepositStatus,
-    pub currency_raised: CurrencyAmount,
+    /// Zero until set with [`with_metrics`](Self::with_metrics).
+    pub metrics: AuctionMetrics,
+    /// Mainnet timing until set with [`with_chain`](Self::with_chain).
+    pub chain: ChainProfile,
+}
+
+impl AuctionPhase {
+    /// Whether both phases are the same variant, ignoring block counts.
+    pub fn same_phase(&self, other: &AuctionPhase) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
 }
 
 impl AuctionState {
@@ -52,10 +211,31 @@ impl AuctionState {
             checkpoint,
             graduation,
             tokens_received,
-            currency_raised: CurrencyAmount::ZERO,
+            metrics: AuctionMetrics::default(),
+            chain: ChainProfile::default(),
         }
     }
 
+    pub fn with_metrics(mut self, metrics: AuctionMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    pub fn with_chain(mut self, chain: ChainProfile) -> Self {
+        self.chain = chain;
+        self
+    }
+
+    pub fn graduation_progress(&self) -> Option<f64> {
+        self.metrics.graduation_progress()
+    }
+
+    /// Expected wall clock time until `block`, e.g. the end block, at the
+    /// chain's block time.
+    pub fn estimated_time_until(&self, block: BlockNumber) -> Duration {
+        self.chain.estimated_time_until(self.current_block, block)
+    }
+
     pub fn compute_phase(
         config: &AuctionConfig,
         current_block: BlockNumber,
@@ -100,3 +280,41 @@ impl AuctionState {
         graduated && active
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::aliases::U24;
+
+    use super::*;
+
+    #[test]
+    fn graduation_progress_needs_the_requirement() {
+        let raised = |raised: u64, required: Option<u64>| AuctionMetrics {
+            currency_raised: CurrencyAmount::new(U256::from(raised)),
+            total_cleared: TokenAmount::new(U256::ZERO),
+            required_currency_raised: required.map(|r| CurrencyAmount::new(U256::from(r))),
+        };
+
+        assert_eq!(raised(250, Some(1_000)).graduation_progress(), Some(0.25));
+        assert_eq!(raised(1_500, Some(1_000)).graduation_progress(), Some(1.5));
+        assert_eq!(raised(0, Some(0)).graduation_progress(), Some(1.0));
+        assert_eq!(raised(250, None).graduation_progress(), None);
+
+        // 250 raised from a quarter of the supply heads for 1_000.
+        let quarter = Mps::new(U24::from(Mps::FULL / 4));
+        let progress = RaiseProgress::new(&raised(250, Some(2_000)), quarter).unwrap();
+        assert_eq!(
+            progress.projected,
+            Some(CurrencyAmount::new(U256::from(1_000)))
+        );
+        assert_eq!(progress.projected_ratio(), Some(0.5));
+        let nothing_sold = Mps::new(U24::ZERO);
+        assert_eq!(
+            RaiseProgress::new(&raised(0, Some(2_000)), nothing_sold)
+                .unwrap()
+                .projected_ratio(),
+            None
+        );
+        assert!(RaiseProgress::new(&raised(250, None), quarter).is_none());
+    }
+}
@@ -0,0 +1,188 @@
+use alloy::primitives::{U256, aliases::U24};
+
+use crate::error::ConfigError;
+
+use super::primitives::{BlockNumber, Mps, TokenAmount, mul_div};
+
+/// Bytes per packed step in `auctionStepsData`: a `uint24` mps followed by a
+/// `uint40` block delta.
+const STEP_LEN: usize = 8;
+
+/// One range of the supply schedule: `mps` of the total supply is released
+/// every block from `start_block` until `end_block` (exclusive).
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuctionStep {
+    pub mps: Mps,
+    pub start_block: BlockNumber,
+    pub end_block: BlockNumber,
+}
+
+impl AuctionStep {
+    pub fn contains(&self, block: BlockNumber) -> bool {
+        self.start_block <= block && block < self.end_block
+    }
+
+    pub fn blocks(&self) -> u64 {
+        self.end_block
+            .as_u64()
+            .saturating_sub(self.start_block.as_u64())
+    }
+}
+
+/// How an auction releases its supply over time, decoded from its
+/// `auctionStepsData`.
+///
+/// Strategies see it as
+/// [`ExecutorCache::step_schedule`](crate::ExecutorCache::step_schedule) and
+/// can use [`next_change`](Self::next_change) to anticipate supply cliffs,
+/// blocks where emission jumps or stops.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StepSchedule {
+    steps: Vec<AuctionStep>,
+}
+
+impl StepSchedule {
+    /// Decodes packed steps starting at `start_block`, in schedule order.
+    pub fn parse(start_block: BlockNumber, data: &[u8]) -> Result<Self, ConfigError> {
+        if !data.len().is_multiple_of(STEP_LEN) {
+            return Err(ConfigError::MalformedSteps(
+                "length is not a multiple of 8 bytes",
+            ));
+        }
+
+        let mut start = start_block.as_u64();
+        let mut steps = Vec::with_capacity(data.len() / STEP_LEN);
+        for chunk in data.chunks_exact(STEP_LEN) {
+            let mps = U24::from_be_slice(&chunk[..3]);
+            let delta = U256::from_be_slice(&chunk[3..]).to::<u64>();
+            if delta == 0 {
+                return Err(ConfigError::MalformedSteps("step with zero blocks"));
+            }
+            let end = start
+                .checked_add(delta)
+                .ok_or(ConfigError::MalformedSteps("step ends past u64 blocks"))?;
+            steps.push(AuctionStep {
+                mps: Mps::new(mps),
+                start_block: BlockNumber::new(start),
+                end_block: BlockNumber::new(end),
+            });
+            start = end;
+        }
+        Ok(Self { steps })
+    }
+
+    pub fn steps(&self) -> &[AuctionStep] {
+        &self.steps
+    }
+
+    pub fn step_at(&self, block: BlockNumber) -> Option<&AuctionStep> {
+        self.steps.iter().find(|step| step.contains(block))
+    }
+
+    /// Supply released per block at `block`, in mps; zero outside the
+    /// schedule.
+    pub fn mps_at(&self, block: BlockNumber) -> Mps {
+        self.step_at(block)
+            .map_or(Mps::new(U24::ZERO), |step| step.mps)
+    }
+
+    /// Tokens of `total_supply` released at `block`, rounded down.
+    pub fn emitted_at(&self, block: BlockNumber, total_supply: TokenAmount) -> TokenAmount {
+        let mps = U256::from(self.mps_at(block).as_u24());
+        TokenAmount::new(
+            mul_div(total_supply.as_u256(), mps, U256::from(Mps::FULL)).unwrap_or_default(),
+        )
+    }
+
+    /// Tokens of `total_supply` released from `from` until `to`
+    /// (exclusive), rounded down.
+    pub fn released_between(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+        total_supply: TokenAmount,
+    ) -> TokenAmount {
+        let mps: u64 = self
+            .steps
+            .iter()
+            .map(|step| {
+                let start = step.start_block.max(from).as_u64();
+                let end = step.end_block.min(to).as_u64();
+                end.saturating_sub(start) * step.mps.as_u24().to::<u64>()
+            })
+            .sum();
+        TokenAmount::new(
+            mul_div(
+                total_supply.as_u256(),
+                U256::from(mps),
+                U256::from(Mps::FULL),
+            )
+            .unwrap_or_default(),
+        )
+    }
+
+    /// The first step after `block` that releases at a different rate than
+    /// `block` does, i.e. the next supply cliff.
+    pub fn next_change(&self, block: BlockNumber) -> Option<&AuctionStep> {
+        let current = self.mps_at(block).as_u24();
+        self.steps
+            .iter()
+            .find(|step| step.start_block > block && step.mps.as_u24() != current)
+    }
+
+    pub fn end_block(&self) -> Option<BlockNumber> {
+        self.steps.last().map(|step| step.end_block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packed(steps: &[(u32, u64)]) -> Vec<u8> {
+        steps
+            .iter()
+            .flat_map(|&(mps, delta)| {
+                let mut step = mps.to_be_bytes()[1..].to_vec();
+                step.extend_from_slice(&delta.to_be_bytes()[3..]);
+                step
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_packed_steps_and_finds_cliffs() {
+        // 1% a block for 50 blocks, then 2% a block for 25 blocks.
+        let data = packed(&[(100_000, 50), (200_000, 25)]);
+        let schedule = StepSchedule::parse(BlockNumber::new(100), &data).unwrap();
+
+        assert_eq!(schedule.steps().len(), 2);
+        assert_eq!(schedule.end_block(), Some(BlockNumber::new(175)));
+        let supply = TokenAmount::new(U256::from(1_000_000));
+        assert_eq!(
+            schedule.emitted_at(BlockNumber::new(120), supply).as_u256(),
+            U256::from(10_000)
+        );
+        assert_eq!(
+            schedule.emitted_at(BlockNumber::new(175), supply).as_u256(),
+            U256::ZERO
+        );
+        assert_eq!(
+            schedule
+                .released_between(BlockNumber::new(100), BlockNumber::new(175), supply)
+                .as_u256(),
+            U256::from(1_000_000)
+        );
+
+        let cliff = schedule.next_change(BlockNumber::new(120)).unwrap();
+        assert_eq!(cliff.start_block, BlockNumber::new(150));
+        assert!(schedule.next_change(BlockNumber::new(160)).is_none());
+
+        assert!(matches!(
+            StepSchedule::parse(BlockNumber::new(0), &data[..7]),
+            Err(ConfigError::MalformedSteps(_))
+        ));
+    }
+}
@@ -0,0 +1,204 @@
+use alloy::primitives::U256;
+
+use super::primitives::{CurrencyAmount, Price, Q96, TokenAmount};
+
+/// Currency demand resting at one initialized tick.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TickDemand {
+    pub price: Price,
+    pub demand: CurrencyAmount,
+}
+
+impl TickDemand {
+    /// Converts the contract's Q96 demand to currency units, rounding down.
+    pub fn from_q96(price: Price, currency_demand_q96: U256) -> Self {
+        Self {
+            price,
+            demand: CurrencyAmount::new(currency_demand_q96 / Q96),
+        }
+    }
+}
+
+/// Initialized ticks in ascending price order, as walked from the floor.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DemandCurve {
+    pub ticks: Vec<TickDemand>,
+}
+
+impl DemandCurve {
+    pub fn new(mut ticks: Vec<TickDemand>) -> Self {
+        ticks.sort_by_key(|tick| tick.price.as_u256());
+        Self { ticks }
+    }
+
+    /// Total demand from bids priced at or above `price`.
+    pub fn demand_at_or_above(&self, price: Price) -> CurrencyAmount {
+        let total = self
+            .ticks
+            .iter()
+            .filter(|tick| tick.price >= price)
+            .fold(U256::ZERO, |sum, tick| {
+                sum.saturating_add(tick.demand.as_u256())
+            });
+        CurrencyAmount::new(total)
+    }
+
+    /// Demand across every tick.
+    pub fn total_demand(&self) -> CurrencyAmount {
+        self.demand_at_or_above(Price::new(U256::ZERO))
+    }
+
+    /// Herfindahl index of demand across ticks, from 0 to 1: close to 0 when
+    /// demand is spread over many ticks, 1 when it all rests at one. `None`
+    /// without any demand.
+    pub fn concentration(&self) -> Option<f64> {
+        let total = self.total_demand().as_u256();
+        if total.is_zero() {
+            return None;
+        }
+        let index = self
+            .ticks
+            .iter()
+            .map(|tick| {
+                let share = tick.demand.as_u256() * U256::from(1_000_000) / total;
+                (share.to::<u64>() as f64 / 1_000_000.0).powi(2)
+            })
+            .sum();
+        Some(index)
+    }
+
+    /// Expected fill fraction (0 to 1) of bids resting exactly at
+    /// `clearing_price`, if the auction cleared all of `total_supply` there.
+    ///
+    /// Demand strictly above the clearing price fills first; ATM bids share
+    /// what is left pro rata. `None` if no demand rests at that price.
+    pub fn fill_fraction_at(
+        &self,
+        clearing_price: Price,
+        total_supply: TokenAmount,
+    ) -> Option<f64> {
+        let at_price = self
+            .ticks
+            .iter()
+            .find(|tick| tick.price == clearing_price)?
+            .demand
+            .as_u256();
+        if at_price.is_zero() {
+            return None;
+        }
+
+        let supply_value = clearing_price
+            .currency_for(total_supply)
+            .map_or(U256::MAX, |value| value.as_u256());
+        let above = self
+            .demand_at_or_above(clearing_price)
+            .as_u256()
+            .saturating_sub(at_price);
+        let left = supply_value.saturating_sub(above).min(at_price);
+        let ppm = left * U256::from(1_000_000) / at_price;
+        Some(ppm.to::<u64>() as f64 / 1_000_000.0)
+    }
+}
+
+/// One initialized tick of a [`TickBook`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TickLevel {
+    pub price: Price,
+    pub demand: CurrencyAmount,
+    /// Demand at this tick and every level below it in the book.
+    pub cumulative: CurrencyAmount,
+}
+
+/// Initialized ticks from `nextActiveTickPrice` up, in ascending price order.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TickBook {
+    pub levels: Vec<TickLevel>,
+    /// Initialized ticks exist above the last level but were not fetched.
+    pub truncated: bool,
+}
+
+impl TickBook {
+    pub fn new(mut ticks: Vec<TickDemand>, truncated: bool) -> Self {
+        ticks.sort_by_key(|tick| tick.price.as_u256());
+        let mut cumulative = U256::ZERO;
+        let levels = ticks
+            .into_iter()
+            .map(|tick| {
+                cumulative = cumulative.saturating_add(tick.demand.as_u256());
+                TickLevel {
+                    price: tick.price,
+                    demand: tick.demand,
+                    cumulative: CurrencyAmount::new(cumulative),
+                }
+            })
+            .collect();
+        Self { levels, truncated }
+    }
+
+    /// Demand across every level in the book.
+    pub fn total_demand(&self) -> CurrencyAmount {
+        self.levels
+            .last()
+            .map_or(CurrencyAmount::new(U256::ZERO), |level| level.cumulative)
+    }
+
+    /// The level at exactly `price`, if that tick is in the book.
+    pub fn level(&self, price: Price) -> Option<&TickLevel> {
+        self.levels.iter().find(|level| level.price == price)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick(price: u64, demand: u64) -> TickDemand {
+        TickDemand {
+            price: Price::new(Q96 * U256::from(price)),
+            demand: CurrencyAmount::new(U256::from(demand)),
+        }
+    }
+
+    #[test]
+    fn atm_bids_share_what_is_left_above() {
+        let curve = DemandCurve::new(vec![tick(1, 500), tick(2, 400), tick(3, 300)]);
+        let supply = TokenAmount::new(U256::from(300));
+        let at = |price: u64| Price::new(Q96 * U256::from(price));
+
+        // 300 tokens at 2 is worth 600; 300 above, 300 of 400 at the tick.
+        assert_eq!(curve.fill_fraction_at(at(2), supply), Some(0.75));
+        // Demand above already covers the supply.
+        assert_eq!(
+            curve.fill_fraction_at(at(1), TokenAmount::new(U256::from(100))),
+            Some(0.0)
+        );
+        assert_eq!(curve.fill_fraction_at(at(4), supply), None);
+    }
+
+    #[test]
+    fn concentration_is_one_at_a_single_tick() {
+        let spread = DemandCurve::new(vec![tick(1, 250), tick(2, 250), tick(3, 500)]);
+        assert_eq!(spread.total_demand().as_u256(), U256::from(1000));
+        assert_eq!(spread.concentration(), Some(0.375));
+
+        let single = DemandCurve::new(vec![tick(1, 0), tick(2, 700)]);
+        assert_eq!(single.concentration(), Some(1.0));
+        assert_eq!(DemandCurve::default().concentration(), None);
+    }
+
+    #[test]
+    fn tick_book_accumulates_upward() {
+        let book = TickBook::new(vec![tick(3, 300), tick(1, 500), tick(2, 400)], false);
+        let cumulative: Vec<_> = book
+            .levels
+            .iter()
+            .map(|level| level.cumulative.as_u256().to::<u64>())
+            .collect();
+        assert_eq!(cumulative, [500, 900, 1200]);
+        assert_eq!(book.total_demand(), CurrencyAmount::new(U256::from(1200)));
+    }
+}
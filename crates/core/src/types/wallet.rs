@@ -0,0 +1,173 @@
+use alloy::primitives::Address;
+
+use super::primitives::CurrencyAmount;
+use crate::error::ValidationError;
+
+/// Several signing wallets that bids are spread across, for hooks that cap
+/// what one address may bid.
+///
+/// Each bid goes to the wallet its intent names, or else to the next wallet
+/// in turn that has budget left for it. The pool hands out nonces itself so
+/// bids from one wallet sent back to back do not collide. Every wallet must
+/// be a signer of the client's provider.
+#[derive(Clone, Debug, Default)]
+pub struct WalletPool {
+    wallets: Vec<PoolWallet>,
+    next: usize,
+}
+
+/// One wallet of a [`WalletPool`] and what it has spent so far.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PoolWallet {
+    pub address: Address,
+    /// Currency this wallet may commit across all its bids; `None` is
+    /// unlimited.
+    pub budget: Option<CurrencyAmount>,
+    pub spent: CurrencyAmount,
+    /// Nonce of the wallet's next transaction, once one has been handed out.
+    next_nonce: Option<u64>,
+}
+
+impl PoolWallet {
+    fn can_spend(&self, amount: CurrencyAmount) -> bool {
+        self.budget
+            .is_none_or(|budget| self.spent.as_u256() + amount.as_u256() <= budget.as_u256())
+    }
+}
+
+impl WalletPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `address`, allowed to commit at most `budget` if one is given.
+    pub fn with_wallet(mut self, address: Address, budget: Option<CurrencyAmount>) -> Self {
+        self.wallets.push(PoolWallet {
+            address,
+            budget,
+            spent: CurrencyAmount::ZERO,
+            next_nonce: None,
+        });
+        self
+    }
+
+    pub fn wallets(&self) -> &[PoolWallet] {
+        &self.wallets
+    }
+
+    pub fn contains(&self, address: Address) -> bool {
+        self.wallets.iter().any(|wallet| wallet.address == address)
+    }
+
+    /// The wallet to send a bid of `amount` from: `requested` if given,
+    /// otherwise the next one round-robin that can still afford it.
+    pub fn select(
+        &mut self,
+        requested: Option<Address>,
+        amount: CurrencyAmount,
+    ) -> Result<Address, ValidationError> {
+        if let Some(address) = requested {
+            let wallet = self
+                .wallet(address)
+                .ok_or(ValidationError::UnknownWallet(address))?;
+            return if wallet.can_spend(amount) {
+                Ok(address)
+            } else {
+                Err(ValidationError::WalletBudgetExhausted {
+                    amount: amount.as_u256(),
+                })
+            };
+        }
+
+        let count = self.wallets.len();
+        let index = (0..count)
+            .map(|offset| (self.next + offset) % count)
+            .find(|&index| self.wallets[index].can_spend(amount))
+            .ok_or(ValidationError::WalletBudgetExhausted {
+                amount: amount.as_u256(),
+            })?;
+        self.next = (index + 1) % count;
+        Ok(self.wallets[index].address)
+    }
+
+    /// Counts `amount` against `address`'s budget once its bid has landed.
+    pub fn record_spend(&mut self, address: Address, amount: CurrencyAmount) {
+        if let Some(wallet) = self.wallet_mut(address) {
+            wallet.spent += amount;
+        }
+    }
+
+    /// The nonce for `address`'s next transaction, given the chain's
+    /// `pending` count: whichever is higher of that and the last nonce
+    /// handed out plus one.
+    pub fn next_nonce(&mut self, address: Address, pending: u64) -> u64 {
+        let Some(wallet) = self.wallet_mut(address) else {
+            return pending;
+        };
+        let nonce = wallet.next_nonce.map_or(pending, |next| next.max(pending));
+        wallet.next_nonce = Some(nonce + 1);
+        nonce
+    }
+
+    /// Forgets the nonces handed out to `address`, after a send that may
+    /// not have used its nonce, so the next one comes from the chain.
+    pub fn reset_nonce(&mut self, address: Address) {
+        if let Some(wallet) = self.wallet_mut(address) {
+            wallet.next_nonce = None;
+        }
+    }
+
+    fn wallet(&self, address: Address) -> Option<&PoolWallet> {
+        self.wallets.iter().find(|wallet| wallet.address == address)
+    }
+
+    fn wallet_mut(&mut self, address: Address) -> Option<&mut PoolWallet> {
+        self.wallets
+            .iter_mut()
+            .find(|wallet| wallet.address == address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::config::fixture::amount;
+
+    #[test]
+    fn round_robin_skips_wallets_out_of_budget() {
+        let a = Address::repeat_byte(1);
+        let b = Address::repeat_byte(2);
+        let mut pool = WalletPool::new()
+            .with_wallet(a, Some(amount(100)))
+            .with_wallet(b, None);
+
+        assert_eq!(pool.select(None, amount(60)).unwrap(), a);
+        pool.record_spend(a, amount(60));
+        assert_eq!(pool.select(None, amount(60)).unwrap(), b);
+        // `a` only has 40 left, so `b` takes this one too.
+        assert_eq!(pool.select(None, amount(60)).unwrap(), b);
+        assert_eq!(pool.select(Some(a), amount(40)).unwrap(), a);
+        assert!(matches!(
+            pool.select(Some(a), amount(41)),
+            Err(ValidationError::WalletBudgetExhausted { .. })
+        ));
+        assert!(matches!(
+            pool.select(Some(Address::ZERO), amount(1)),
+            Err(ValidationError::UnknownWallet(_))
+        ));
+    }
+
+    #[test]
+    fn nonces_count_up_per_wallet() {
+        let a = Address::repeat_byte(1);
+        let b = Address::repeat_byte(2);
+        let mut pool = WalletPool::new().with_wallet(a, None).with_wallet(b, None);
+
+        assert_eq!(pool.next_nonce(a, 5), 5);
+        // The first bid is still pending, so the chain reports 5 again.
+        assert_eq!(pool.next_nonce(a, 5), 6);
+        assert_eq!(pool.next_nonce(b, 0), 0);
+        pool.reset_nonce(a);
+        assert_eq!(pool.next_nonce(a, 6), 6);
+    }
+}
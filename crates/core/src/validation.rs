@@ -1,12 +1,15 @@
-use alloy::primitives::Address;
+use alloy::primitives::{Address, U256};
 
 use crate::{
     error::ValidationError,
     types::{
-        action::SubmitBidInput,
-        bid::{Bid, BidStatus},
+        action::{SubmitBidInput, SubmitBidParams},
+        bid::{Bid, Moneyness},
         config::AuctionConfig,
-        state::{AuctionPhase, AuctionState, GraduationStatus, TokenDepositStatus},
+        primitives::CurrencyAmount,
+        state::{
+            AccountBalances, AuctionPhase, AuctionState, GraduationStatus, TokenDepositStatus,
+        },
     },
 };
 
@@ -58,6 +61,60 @@ pub fn validate_submit_bid(
     Ok(())
 }
 
+/// Checks a bid sends exactly its amount as `msg.value` when the auction
+/// is paid in native currency, and nothing when it is paid in an ERC-20.
+pub fn validate_value(
+    params: &SubmitBidParams,
+    config: &AuctionConfig,
+) -> Result<(), ValidationError> {
+    let expected = if config.is_native_currency() {
+        params.amount
+    } else {
+        CurrencyAmount::ZERO
+    };
+    if params.value != expected {
+        return Err(ValidationError::ValueMismatch {
+            value: params.value.as_u256(),
+            expected: expected.as_u256(),
+        });
+    }
+    Ok(())
+}
+
+/// Gas assumed for a `submitBid` when checking the sender can afford one; a
+/// generous bound, since estimating the real call fails when it cannot.
+pub const SUBMIT_BID_GAS: u64 = 500_000;
+
+/// Checks the sender of `params` holds enough to pay for the bid and its
+/// gas at `gas_price` wei, before it is sent only to revert.
+pub fn validate_balance(
+    params: &SubmitBidParams,
+    balances: &AccountBalances,
+    gas_price: u128,
+) -> Result<(), ValidationError> {
+    let gas_cost = U256::from(SUBMIT_BID_GAS) * U256::from(gas_price);
+    let amount = params.amount.as_u256();
+    let (native_needed, currency_needed) = if params.value.is_zero() {
+        (gas_cost, amount)
+    } else {
+        (params.value.as_u256() + gas_cost, U256::ZERO)
+    };
+
+    if balances.currency.as_u256() < currency_needed {
+        return Err(ValidationError::InsufficientBalance {
+            needed: currency_needed,
+            available: balances.currency.as_u256(),
+        });
+    }
+    if balances.native < native_needed {
+        return Err(ValidationError::InsufficientBalance {
+            needed: native_needed,
+            available: balances.native,
+        });
+    }
+    Ok(())
+}
+
 pub fn validate_exit_bid(
     bid: &Bid,
     state: &AuctionState,
@@ -75,8 +132,8 @@ pub fn validate_exit_bid(
     }
 
     if matches!(state.graduation, GraduationStatus::Graduated) {
-        let status = bid.status(state.checkpoint.clearing_price);
-        if !matches!(status, BidStatus::ITM) {
+        let moneyness = bid.moneyness(state.checkpoint.clearing_price);
+        if !matches!(moneyness, Moneyness::ITM) {
             return Err(ValidationError::BidNotITM);
         }
     }
@@ -95,17 +152,17 @@ pub fn validate_exit_partially_filled(
 
     let is_graduated = matches!(state.graduation, GraduationStatus::Graduated);
     let is_ended = state.current_block >= config.end_block;
-    let status = bid.status(state.checkpoint.clearing_price);
+    let moneyness = bid.moneyness(state.checkpoint.clearing_price);
 
     match (is_graduated, is_ended) {
         (true, false) => {
-            if !matches!(status, BidStatus::OTM) {
+            if !matches!(moneyness, Moneyness::OTM) {
                 return Err(ValidationError::BidNotOutbid);
             }
         }
 
         (true, true) => {
-            if matches!(status, BidStatus::ITM) {
+            if matches!(moneyness, Moneyness::ITM) {
                 return Err(ValidationError::BidIsITM);
             }
         }
@@ -155,3 +212,85 @@ pub fn validate_claim(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::Bytes;
+
+    use super::*;
+    use crate::types::{
+        action::TickHint,
+        config::fixture,
+        primitives::{CurrencyAddr, Price, TokenAddr},
+    };
+
+    fn params(value: u64) -> SubmitBidParams {
+        SubmitBidParams {
+            max_price: Price::new(U256::from(50)),
+            amount: CurrencyAmount::new(U256::from(1_000)),
+            owner: Address::ZERO,
+            from: Address::ZERO,
+            nonce: None,
+            hint: TickHint::Unhinted,
+            hook_data: Bytes::new(),
+            value: CurrencyAmount::new(U256::from(value)),
+            slippage: None,
+            tags: Vec::new(),
+        }
+    }
+
+    fn balances(native: u64, currency: u64) -> AccountBalances {
+        AccountBalances {
+            native: U256::from(native),
+            currency: CurrencyAmount::new(U256::from(currency)),
+        }
+    }
+
+    #[test]
+    fn bids_need_their_amount_plus_gas() {
+        let gas = SUBMIT_BID_GAS;
+        // ERC-20 currency: the amount in currency, gas in native.
+        assert!(validate_balance(&params(0), &balances(gas, 1_000), 1).is_ok());
+        assert!(matches!(
+            validate_balance(&params(0), &balances(gas, 999), 1),
+            Err(ValidationError::InsufficientBalance { .. })
+        ));
+        assert!(matches!(
+            validate_balance(&params(0), &balances(gas - 1, 1_000), 1),
+            Err(ValidationError::InsufficientBalance { .. })
+        ));
+
+        // Native currency: both come out of the native balance.
+        let total = gas + 1_000;
+        assert!(validate_balance(&params(1_000), &balances(total, total), 1).is_ok());
+        assert!(matches!(
+            validate_balance(&params(1_000), &balances(total - 1, total - 1), 1),
+            Err(ValidationError::InsufficientBalance { needed, .. })
+                if needed == U256::from(total)
+        ));
+    }
+
+    #[test]
+    fn native_bids_send_exactly_their_amount() {
+        let config = |currency: Address| AuctionConfig {
+            max_bid_price: Price::new(U256::from(100)),
+            currency: CurrencyAddr::new(currency),
+            token: TokenAddr::new(Address::repeat_byte(2)),
+            ..fixture::config()
+        };
+        let native = config(Address::ZERO);
+        let erc20 = config(Address::repeat_byte(1));
+
+        assert!(validate_value(&params(1_000), &native).is_ok());
+        assert!(validate_value(&params(0), &erc20).is_ok());
+        assert!(matches!(
+            validate_value(&params(999), &native),
+            Err(ValidationError::ValueMismatch { expected, .. }) if expected == U256::from(1_000)
+        ));
+        // Value sent to an ERC-20 auction would be stuck in the contract.
+        assert!(matches!(
+            validate_value(&params(1_000), &erc20),
+            Err(ValidationError::ValueMismatch { .. })
+        ));
+    }
+}
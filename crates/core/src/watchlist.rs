@@ -0,0 +1,305 @@
+use std::{fmt, sync::Arc};
+
+use alloy::{primitives::Address, providers::Provider};
+use futures::{StreamExt, future, stream::BoxStream};
+
+use crate::{
+    blocks::BlockStream,
+    client::AuctionClient,
+    error::Error,
+    hooks::{NoopHook, ValidationHook},
+    types::{
+        config::AuctionConfig,
+        primitives::BlockNumber,
+        state::{GraduationStatus, TokenDepositStatus},
+    },
+};
+
+/// A milestone an auction passed, in the order they normally come.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum PhaseTransition {
+    /// The auction holds its whole supply, so bids can clear once started.
+    TokensReceived,
+    Started,
+    /// The auction raised enough to graduate.
+    Graduated,
+    Ended,
+    /// Tokens can be claimed.
+    Claimable,
+}
+
+/// A [`PhaseTransition`] seen at `block`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WatchlistEvent {
+    pub auction: Address,
+    pub block: BlockNumber,
+    pub transition: PhaseTransition,
+}
+
+/// What is known about one watched auction so far.
+#[derive(Clone, Debug)]
+pub struct WatchedAuction {
+    config: AuctionConfig,
+    last_block: Option<BlockNumber>,
+    tokens_received: bool,
+    graduated: bool,
+}
+
+impl WatchedAuction {
+    pub fn new(config: AuctionConfig) -> Self {
+        Self {
+            config,
+            last_block: None,
+            tokens_received: false,
+            graduated: false,
+        }
+    }
+
+    pub fn config(&self) -> &AuctionConfig {
+        &self.config
+    }
+
+    /// Whether the token deposit still has to be read at the next block.
+    pub fn needs_tokens(&self) -> bool {
+        !self.tokens_received
+    }
+
+    /// Whether graduation still has to be read at `block`: once started and
+    /// until it graduates or can no longer change after the end.
+    pub fn needs_graduation(&self, block: BlockNumber) -> bool {
+        !self.graduated
+            && block >= self.config.start_block
+            && self
+                .last_block
+                .is_none_or(|last| last < self.config.end_block)
+    }
+
+    /// Records what was read at `block` and returns the milestones passed
+    /// since the previous block. The first block only sets the baseline, so
+    /// a restarted watcher does not announce what happened before it.
+    pub fn observe(
+        &mut self,
+        block: BlockNumber,
+        tokens: Option<TokenDepositStatus>,
+        graduation: Option<GraduationStatus>,
+    ) -> Vec<PhaseTransition> {
+        let tokens_received = self.tokens_received || tokens == Some(TokenDepositStatus::Received);
+        let graduated = self.graduated || graduation == Some(GraduationStatus::Graduated);
+        let Some(last) = self.last_block.replace(block) else {
+            self.tokens_received = tokens_received;
+            self.graduated = graduated;
+            return Vec::new();
+        };
+        if block <= last {
+            self.last_block = Some(last);
+            return Vec::new();
+        }
+
+        let passed = |milestone: BlockNumber| last < milestone && block >= milestone;
+        let mut transitions = Vec::new();
+        if tokens_received && !self.tokens_received {
+            transitions.push(PhaseTransition::TokensReceived);
+        }
+        if passed(self.config.start_block) {
+            transitions.push(PhaseTransition::Started);
+        }
+        if graduated && !self.graduated {
+            transitions.push(PhaseTransition::Graduated);
+        }
+        if passed(self.config.end_block) {
+            transitions.push(PhaseTransition::Ended);
+        }
+        if passed(self.config.claim_block) {
+            transitions.push(PhaseTransition::Claimable);
+        }
+        self.tokens_received = tokens_received;
+        self.graduated = graduated;
+        transitions
+    }
+}
+
+/// Follows several auctions and reports when each passes a
+/// [`PhaseTransition`], e.g. so a notifier can say the moment claiming
+/// opens.
+///
+/// Start, end and claim follow from the block number alone; the token
+/// deposit and graduation are read at every block until they happen.
+pub struct AuctionWatchlist<P>
+where
+    P: Provider + Clone,
+{
+    clients: Vec<(AuctionClient<P>, WatchedAuction)>,
+}
+
+impl<P> AuctionWatchlist<P>
+where
+    P: Provider + Clone + 'static,
+{
+    pub async fn new(provider: P, auctions: &[Address]) -> Result<Self, Error> {
+        let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+        let mut clients = Vec::with_capacity(auctions.len());
+        for &auction in auctions {
+            let client = AuctionClient::new(
+                provider.clone(),
+                auction,
+                Address::ZERO,
+                hook.clone(),
+                Vec::new(),
+            )
+            .await?;
+            let watched = WatchedAuction::new(client.config().clone());
+            clients.push((client, watched));
+        }
+        Ok(Self { clients })
+    }
+
+    pub fn auctions(&self) -> impl Iterator<Item = &WatchedAuction> {
+        self.clients.iter().map(|(_, watched)| watched)
+    }
+
+    /// Reads what changed at `block` and returns each auction's transitions.
+    /// An auction that could not be read is reported as an error and tried
+    /// again at the next block; the others carry on.
+    pub async fn on_block(&mut self, block: BlockNumber) -> Vec<Result<WatchlistEvent, Error>> {
+        let observed =
+            future::join_all(self.clients.iter_mut().map(|(client, watched)| async move {
+                let auction = client.address();
+                let tokens = async {
+                    if watched.needs_tokens() {
+                        client.fetch_token_balance().await.map(Some)
+                    } else {
+                        Ok(None)
+                    }
+                };
+                let graduation = async {
+                    if watched.needs_graduation(block) {
+                        client.fetch_graduation().await.map(Some)
+                    } else {
+                        Ok(None)
+                    }
+                };
+                let (tokens, graduation) = futures::try_join!(tokens, graduation)?;
+                let transitions = watched.observe(block, tokens, graduation);
+                Ok::<_, Error>(
+                    transitions
+                        .into_iter()
+                        .map(move |transition| WatchlistEvent {
+                            auction,
+                            block,
+                            transition,
+                        }),
+                )
+            }))
+            .await;
+
+        observed
+            .into_iter()
+            .flat_map(|result| match result {
+                Ok(events) => events.map(Ok).collect(),
+                Err(error) => vec![Err(error)],
+            })
+            .collect()
+    }
+
+    /// The transitions seen along `blocks`, ending with them. Block stream
+    /// errors are skipped; see [`on_block`](Self::on_block) for auction ones.
+    pub fn into_stream<S>(self, blocks: S) -> BoxStream<'static, Result<WatchlistEvent, Error>>
+    where
+        S: BlockStream + 'static,
+    {
+        futures::stream::unfold((self, blocks), |(mut watchlist, mut blocks)| async move {
+            loop {
+                match blocks.next().await? {
+                    Ok(block) => {
+                        let events = watchlist.on_block(block).await;
+                        return Some((futures::stream::iter(events), (watchlist, blocks)));
+                    }
+                    Err(error) => tracing::warn!(%error, "watchlist block stream error"),
+                }
+            }
+        })
+        .flatten()
+        .boxed()
+    }
+}
+
+impl fmt::Display for PhaseTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::TokensReceived => "tokens received",
+            Self::Started => "started",
+            Self::Graduated => "graduated",
+            Self::Ended => "ended",
+            Self::Claimable => "claimable",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::config::fixture;
+
+    fn watched() -> WatchedAuction {
+        WatchedAuction::new(AuctionConfig {
+            start_block: BlockNumber::new(10),
+            end_block: BlockNumber::new(20),
+            claim_block: BlockNumber::new(30),
+            ..fixture::config()
+        })
+    }
+
+    #[test]
+    fn reports_each_milestone_once() {
+        let mut watched = watched();
+        let not_received = Some(TokenDepositStatus::NotReceived);
+        let not_graduated = Some(GraduationStatus::NotGraduated);
+        assert!(
+            watched
+                .observe(BlockNumber::new(5), not_received, None)
+                .is_empty()
+        );
+        assert_eq!(
+            watched.observe(
+                BlockNumber::new(10),
+                Some(TokenDepositStatus::Received),
+                not_graduated
+            ),
+            [PhaseTransition::TokensReceived, PhaseTransition::Started]
+        );
+        assert!(!watched.needs_tokens());
+        assert_eq!(
+            watched.observe(
+                BlockNumber::new(15),
+                None,
+                Some(GraduationStatus::Graduated)
+            ),
+            [PhaseTransition::Graduated]
+        );
+        assert!(!watched.needs_graduation(BlockNumber::new(16)));
+        // Blocks skipped over still count.
+        assert_eq!(
+            watched.observe(BlockNumber::new(31), None, None),
+            [PhaseTransition::Ended, PhaseTransition::Claimable]
+        );
+        assert!(watched.observe(BlockNumber::new(32), None, None).is_empty());
+    }
+
+    #[test]
+    fn first_block_is_the_baseline() {
+        let mut watched = watched();
+        let received = Some(TokenDepositStatus::Received);
+        assert!(
+            watched
+                .observe(BlockNumber::new(25), received, None)
+                .is_empty()
+        );
+        assert_eq!(
+            watched.observe(BlockNumber::new(30), None, None),
+            [PhaseTransition::Claimable]
+        );
+    }
+}
@@ -0,0 +1,24 @@
+//! Single dependency for the flux crates, kept at one version.
+//!
+//! `flux::abi` and `flux::core` are always available; the CLI library and
+//! the anvil test fixtures sit behind the `cli` and `testing` features.
+//! Most programs only need [`prelude`].
+pub use flux_abi as abi;
+pub use flux_core as core;
+
+#[cfg(feature = "cli")]
+pub use flux_cli as cli;
+
+#[cfg(feature = "testing")]
+pub use flux_testing as testing;
+
+/// The types needed to connect to an auction and run a strategy against it.
+pub mod prelude {
+    pub use flux_core::{
+        AuctionApi, AuctionClient, AuctionConfig, AuctionEvent, AuctionPhase, AutoCheckpoint,
+        BidId, BlockNumber, BlockProducer, CurrencyAmount, Error, EvaluationContext, EventProducer,
+        FailurePolicy, Intent, IntentExecutor, IntentOutcome, NoopHook, Orchestrator,
+        OrchestratorResult, Price, RiskLimits, SettleStrategy, Strategy, TokenAmount,
+        ValidationHook,
+    };
+}
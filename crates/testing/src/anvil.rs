@@ -0,0 +1,126 @@
+use std::{
+    net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream},
+    path::PathBuf,
+    process::{Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::error::FixtureError;
+
+/// Private keys of the first accounts anvil funds, from its default
+/// `test test ... junk` mnemonic.
+pub const DEV_KEYS: [&str; 3] = [
+    "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+    "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+    "0x5de4111afa1a4b94908f83103eb1f1706367c2e68ca870fc3fb9a804cdab365a",
+];
+
+/// Builder for a local anvil node.
+#[derive(Clone, Debug)]
+pub struct Anvil {
+    bin: PathBuf,
+    block_time: Option<u64>,
+    startup_timeout: Duration,
+}
+
+impl Default for Anvil {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Anvil {
+    /// Environment variable overriding the `anvil` binary.
+    pub const BIN_ENV: &'static str = "ANVIL_BIN";
+
+    /// Uses `$ANVIL_BIN`, or `anvil` from `PATH`. Blocks are mined only on
+    /// transactions and [`Fixture::mine`](crate::Fixture::mine).
+    pub fn new() -> Self {
+        Self {
+            bin: std::env::var_os(Self::BIN_ENV).map_or_else(|| "anvil".into(), PathBuf::from),
+            block_time: None,
+            startup_timeout: Duration::from_secs(10),
+        }
+    }
+
+    pub fn with_bin(mut self, bin: impl Into<PathBuf>) -> Self {
+        self.bin = bin.into();
+        self
+    }
+
+    /// Mine a block every `seconds` instead of only on demand.
+    pub fn with_block_time(mut self, seconds: u64) -> Self {
+        self.block_time = Some(seconds);
+        self
+    }
+
+    pub fn with_startup_timeout(mut self, timeout: Duration) -> Self {
+        self.startup_timeout = timeout;
+        self
+    }
+
+    /// Starts anvil on a free local port and waits until it accepts
+    /// connections.
+    pub fn spawn(&self) -> Result<AnvilInstance, FixtureError> {
+        let spawn_error = |source| FixtureError::Spawn {
+            bin: self.bin.clone(),
+            source,
+        };
+        let port = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .and_then(|listener| listener.local_addr())
+            .map_err(spawn_error)?
+            .port();
+
+        let mut command = Command::new(&self.bin);
+        command
+            .arg("--port")
+            .arg(port.to_string())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        if let Some(seconds) = self.block_time {
+            command.arg("--block-time").arg(seconds.to_string());
+        }
+        let child = command.spawn().map_err(spawn_error)?;
+        let instance = AnvilInstance { child, port };
+
+        let address = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+        let started = Instant::now();
+        while TcpStream::connect_timeout(&address, Duration::from_millis(100)).is_err() {
+            if started.elapsed() > self.startup_timeout {
+                return Err(FixtureError::Startup {
+                    port,
+                    timeout: self.startup_timeout,
+                });
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+
+        Ok(instance)
+    }
+}
+
+/// A running anvil node, killed when dropped.
+#[derive(Debug)]
+pub struct AnvilInstance {
+    child: Child,
+    port: u16,
+}
+
+impl AnvilInstance {
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// HTTP RPC endpoint of the node.
+    pub fn endpoint(&self) -> String {
+        format!("http://127.0.0.1:{}", self.port)
+    }
+}
+
+impl Drop for AnvilInstance {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
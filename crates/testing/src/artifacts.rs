@@ -0,0 +1,102 @@
+use std::{fs, path::Path};
+
+use alloy::primitives::Bytes;
+
+use crate::error::FixtureError;
+
+/// Creation bytecode for the contracts a [`Fixture`](crate::Fixture) deploys.
+///
+/// The contracts are not vendored here; build them with Foundry from the CCA
+/// repository and point [`Artifacts::DIR_ENV`] at the output directory.
+#[derive(Clone, Debug)]
+pub struct Artifacts {
+    pub factory: Bytes,
+    pub lens: Bytes,
+    /// An ERC20 with an open `mint(address,uint256)` and no constructor
+    /// arguments.
+    pub token: Bytes,
+}
+
+impl Artifacts {
+    /// Environment variable naming the artifacts directory.
+    pub const DIR_ENV: &'static str = "FLUX_CCA_ARTIFACTS";
+
+    pub const FACTORY: &'static str = "ContinuousClearingAuctionFactory";
+    pub const LENS: &'static str = "AuctionStateLens";
+    pub const TOKEN: &'static str = "MockERC20";
+
+    /// Loads artifacts from `$FLUX_CCA_ARTIFACTS`.
+    pub fn from_env() -> Result<Self, FixtureError> {
+        let dir = std::env::var_os(Self::DIR_ENV)
+            .ok_or(FixtureError::ArtifactsNotConfigured(Self::DIR_ENV))?;
+        Self::from_dir(dir)
+    }
+
+    /// Loads artifacts from a Foundry `out/` directory (`<Name>.sol/<Name>.json`)
+    /// or a flat directory of `<Name>.json` files.
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<Self, FixtureError> {
+        let dir = dir.as_ref();
+        Ok(Self {
+            factory: read_bytecode(dir, Self::FACTORY)?,
+            lens: read_bytecode(dir, Self::LENS)?,
+            token: read_bytecode(dir, Self::TOKEN)?,
+        })
+    }
+}
+
+fn read_bytecode(dir: &Path, name: &str) -> Result<Bytes, FixtureError> {
+    let nested = dir.join(format!("{name}.sol")).join(format!("{name}.json"));
+    let path = if nested.exists() {
+        nested
+    } else {
+        dir.join(format!("{name}.json"))
+    };
+    let invalid = |reason: String| FixtureError::Artifact {
+        path: path.clone(),
+        reason,
+    };
+
+    let contents = fs::read_to_string(&path).map_err(|error| invalid(error.to_string()))?;
+    let json: serde_json::Value =
+        serde_json::from_str(&contents).map_err(|error| invalid(error.to_string()))?;
+
+    // Foundry nests the hex under `bytecode.object`; other tools use a string.
+    let bytecode = &json["bytecode"];
+    let hex = bytecode["object"]
+        .as_str()
+        .or_else(|| bytecode.as_str())
+        .ok_or_else(|| invalid("no `bytecode` field".to_string()))?;
+    hex.parse()
+        .map_err(|_| invalid("`bytecode` is not hex".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_foundry_and_flat_layouts() {
+        let dir = std::env::temp_dir().join(format!("flux-artifacts-{}", std::process::id()));
+        let nested = dir.join("MockERC20.sol");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(
+            nested.join("MockERC20.json"),
+            r#"{"bytecode":{"object":"0x6001"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("AuctionStateLens.json"),
+            r#"{"bytecode":"0x6002"}"#,
+        )
+        .unwrap();
+
+        let read = |name| read_bytecode(&dir, name).map(|code| code.to_vec());
+        assert_eq!(read(Artifacts::TOKEN).unwrap(), [0x60, 0x01]);
+        assert_eq!(read(Artifacts::LENS).unwrap(), [0x60, 0x02]);
+        assert!(matches!(
+            read(Artifacts::FACTORY),
+            Err(FixtureError::Artifact { path, .. }) if path == dir.join("ContinuousClearingAuctionFactory.json")
+        ));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
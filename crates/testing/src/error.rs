@@ -0,0 +1,48 @@
+use std::{io, path::PathBuf, time::Duration};
+
+use alloy::{
+    contract,
+    providers::PendingTransactionError,
+    transports::{RpcError, TransportErrorKind},
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum FixtureError {
+    #[error("failed to start anvil ({bin}): {source}")]
+    Spawn {
+        bin: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("anvil did not accept connections on port {port} within {timeout:?}")]
+    Startup { port: u16, timeout: Duration },
+
+    #[error("failed to read artifact {path}: {reason}")]
+    Artifact { path: PathBuf, reason: String },
+
+    #[error("${0} is not set; point it at the compiled CCA artifacts")]
+    ArtifactsNotConfigured(&'static str),
+
+    #[error("a {duration}-block auction cannot release supply evenly; use a divisor of 10,000,000")]
+    UnevenSchedule { duration: u64 },
+
+    #[error("deployment of {0} produced no contract address")]
+    NoContractAddress(&'static str),
+
+    #[error("factory did not emit AuctionCreated")]
+    AuctionNotCreated,
+
+    #[error("rpc error: {0}")]
+    Transport(#[from] RpcError<TransportErrorKind>),
+
+    #[error("contract call failed: {0}")]
+    Contract(#[from] contract::Error),
+
+    #[error("pending transaction error: {0}")]
+    Pending(#[from] PendingTransactionError),
+
+    #[error("invalid signer key: {0}")]
+    Signer(#[from] alloy::signers::local::LocalSignerError),
+}
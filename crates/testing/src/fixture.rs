@@ -0,0 +1,307 @@
+use alloy::{
+    network::TransactionBuilder,
+    primitives::{Address, B256, Bytes, U256},
+    providers::{DynProvider, Provider, ProviderBuilder, ext::AnvilApi},
+    rpc::types::TransactionRequest,
+    signers::local::PrivateKeySigner,
+    sol,
+    sol_types::SolValue,
+};
+use flux_abi::{IContinuousClearingAuction, IContinuousClearingAuctionFactory, IERC20Minimal};
+
+use crate::{
+    anvil::{Anvil, AnvilInstance, DEV_KEYS},
+    artifacts::Artifacts,
+    error::FixtureError,
+};
+
+sol! {
+    #[sol(rpc)]
+    interface IMintableERC20 {
+        function mint(address to, uint256 amount) external;
+    }
+}
+
+/// Total mps an auction releases over its lifetime.
+const TOTAL_MPS: u64 = 10_000_000;
+
+/// Q96 fixed point one, the default floor price and tick spacing.
+const Q96: U256 = U256::from_limbs([0, 1 << 32, 0, 0]);
+
+/// Shape of an auction created by [`Fixture::create_auction`]. Supply is
+/// released evenly over the auction.
+#[derive(Clone, Debug)]
+pub struct AuctionParams {
+    /// `Address::ZERO` for the native currency.
+    pub currency: Address,
+    pub total_supply: u128,
+    /// Blocks between creation and `start_block`.
+    pub start_delay: u64,
+    /// Blocks from `start_block` to `end_block`; must divide 10,000,000.
+    pub duration: u64,
+    /// Blocks from `end_block` to `claim_block`.
+    pub claim_delay: u64,
+    pub tick_spacing: U256,
+    pub floor_price: U256,
+    pub required_currency_raised: u128,
+}
+
+impl Default for AuctionParams {
+    fn default() -> Self {
+        Self {
+            currency: Address::ZERO,
+            total_supply: 1_000_000 * 10u128.pow(18),
+            start_delay: 5,
+            duration: 100,
+            claim_delay: 10,
+            tick_spacing: Q96,
+            floor_price: Q96,
+            required_currency_raised: 0,
+        }
+    }
+}
+
+impl AuctionParams {
+    pub fn with_currency(mut self, currency: Address) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    pub fn with_total_supply(mut self, total_supply: u128) -> Self {
+        self.total_supply = total_supply;
+        self
+    }
+
+    pub fn with_schedule(mut self, start_delay: u64, duration: u64, claim_delay: u64) -> Self {
+        self.start_delay = start_delay;
+        self.duration = duration;
+        self.claim_delay = claim_delay;
+        self
+    }
+
+    pub fn with_prices(mut self, floor_price: U256, tick_spacing: U256) -> Self {
+        self.floor_price = floor_price;
+        self.tick_spacing = tick_spacing;
+        self
+    }
+
+    pub fn with_required_currency_raised(mut self, amount: u128) -> Self {
+        self.required_currency_raised = amount;
+        self
+    }
+
+    /// Packed `(uint24 mps, uint40 blockDelta)` steps for an even release.
+    fn steps_data(&self) -> Result<Bytes, FixtureError> {
+        if self.duration == 0 || !TOTAL_MPS.is_multiple_of(self.duration) {
+            return Err(FixtureError::UnevenSchedule {
+                duration: self.duration,
+            });
+        }
+        let mps = TOTAL_MPS / self.duration;
+        let mut data = Vec::with_capacity(8);
+        data.extend_from_slice(&mps.to_be_bytes()[5..]);
+        data.extend_from_slice(&self.duration.to_be_bytes()[3..]);
+        Ok(data.into())
+    }
+}
+
+/// An anvil node with the CCA factory, state lens and a mintable test token
+/// deployed by the first dev account.
+pub struct Fixture {
+    anvil: AnvilInstance,
+    provider: DynProvider,
+    deployer: Address,
+    factory: Address,
+    lens: Address,
+    token: Address,
+    auctions: u64,
+}
+
+impl Fixture {
+    /// Starts anvil with default settings and deploys `artifacts` to it.
+    pub async fn deploy(artifacts: &Artifacts) -> Result<Self, FixtureError> {
+        Self::deploy_on(Anvil::new().spawn()?, artifacts).await
+    }
+
+    pub async fn deploy_on(
+        anvil: AnvilInstance,
+        artifacts: &Artifacts,
+    ) -> Result<Self, FixtureError> {
+        let provider = connect(&anvil.endpoint(), DEV_KEYS[0]).await?;
+        let deployer: PrivateKeySigner = DEV_KEYS[0].parse()?;
+
+        let factory = deploy(&provider, "factory", &artifacts.factory).await?;
+        let lens = deploy(&provider, "lens", &artifacts.lens).await?;
+        let token = deploy(&provider, "token", &artifacts.token).await?;
+
+        Ok(Self {
+            anvil,
+            provider,
+            deployer: deployer.address(),
+            factory,
+            lens,
+            token,
+            auctions: 0,
+        })
+    }
+
+    /// Provider signing as the deployer.
+    pub fn provider(&self) -> &DynProvider {
+        &self.provider
+    }
+
+    pub fn endpoint(&self) -> String {
+        self.anvil.endpoint()
+    }
+
+    pub fn deployer(&self) -> Address {
+        self.deployer
+    }
+
+    pub fn factory(&self) -> Address {
+        self.factory
+    }
+
+    pub fn lens(&self) -> Address {
+        self.lens
+    }
+
+    pub fn token(&self) -> Address {
+        self.token
+    }
+
+    /// A provider signing as another funded dev account, e.g. a bidder.
+    pub async fn connect_as(&self, key: &str) -> Result<DynProvider, FixtureError> {
+        connect(&self.anvil.endpoint(), key).await
+    }
+
+    /// Mints test tokens to `to`.
+    pub async fn mint(&self, to: Address, amount: U256) -> Result<(), FixtureError> {
+        IMintableERC20::new(self.token, &self.provider)
+            .mint(to, amount)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+        Ok(())
+    }
+
+    /// Creates an auction selling the test token, mints its supply into it and
+    /// notifies it, so it is ready for bids once `start_block` is reached.
+    pub async fn create_auction(
+        &mut self,
+        params: &AuctionParams,
+    ) -> Result<Address, FixtureError> {
+        let start_block = self.block_number().await? + params.start_delay;
+        let end_block = start_block + params.duration;
+        let config = IContinuousClearingAuction::AuctionParameters {
+            currency: params.currency,
+            tokensRecipient: self.deployer,
+            fundsRecipient: self.deployer,
+            startBlock: start_block,
+            endBlock: end_block,
+            claimBlock: end_block + params.claim_delay,
+            tickSpacing: params.tick_spacing,
+            validationHook: Address::ZERO,
+            floorPrice: params.floor_price,
+            requiredCurrencyRaised: params.required_currency_raised,
+            auctionStepsData: params.steps_data()?,
+        };
+
+        self.auctions += 1;
+        let receipt = IContinuousClearingAuctionFactory::new(self.factory, &self.provider)
+            .initializeDistribution(
+                self.token,
+                U256::from(params.total_supply),
+                config.abi_encode().into(),
+                B256::from(U256::from(self.auctions)),
+            )
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+        let auction = receipt
+            .decoded_log::<IContinuousClearingAuctionFactory::AuctionCreated>()
+            .ok_or(FixtureError::AuctionNotCreated)?
+            .auction;
+
+        let supply = U256::from(params.total_supply);
+        self.mint(self.deployer, supply).await?;
+        IERC20Minimal::new(self.token, &self.provider)
+            .transfer(auction, supply)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+        IContinuousClearingAuction::new(auction, &self.provider)
+            .onTokensReceived()
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        Ok(auction)
+    }
+
+    pub async fn block_number(&self) -> Result<u64, FixtureError> {
+        Ok(self.provider.get_block_number().await?)
+    }
+
+    /// Mines `blocks` empty blocks.
+    pub async fn mine(&self, blocks: u64) -> Result<(), FixtureError> {
+        self.provider.anvil_mine(Some(blocks), None).await?;
+        Ok(())
+    }
+
+    /// Mines until the chain head is at least `block`.
+    pub async fn mine_to(&self, block: u64) -> Result<(), FixtureError> {
+        let head = self.block_number().await?;
+        if block > head {
+            self.mine(block - head).await?;
+        }
+        Ok(())
+    }
+}
+
+async fn connect(endpoint: &str, key: &str) -> Result<DynProvider, FixtureError> {
+    let signer: PrivateKeySigner = key.parse()?;
+    Ok(ProviderBuilder::new()
+        .wallet(signer)
+        .connect(endpoint)
+        .await?
+        .erased())
+}
+
+async fn deploy(
+    provider: &DynProvider,
+    name: &'static str,
+    code: &Bytes,
+) -> Result<Address, FixtureError> {
+    let tx = TransactionRequest::default().with_deploy_code(code.clone());
+    provider
+        .send_transaction(tx)
+        .await?
+        .get_receipt()
+        .await?
+        .contract_address
+        .ok_or(FixtureError::NoContractAddress(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn even_schedule_packs_one_step() {
+        let params = AuctionParams::default().with_schedule(0, 100, 0);
+        let data = params.steps_data().unwrap();
+        // mps = 100_000 (0x0186a0), blockDelta = 100 (0x64).
+        assert_eq!(data.to_vec(), [0x01, 0x86, 0xa0, 0, 0, 0, 0, 0x64]);
+
+        let uneven = AuctionParams::default().with_schedule(0, 3, 0);
+        assert!(matches!(
+            uneven.steps_data(),
+            Err(FixtureError::UnevenSchedule { duration: 3 })
+        ));
+    }
+}
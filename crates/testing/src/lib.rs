@@ -0,0 +1,14 @@
+//! Local-chain fixtures for end-to-end tests.
+//!
+//! Spawns `anvil`, deploys the CCA factory, state lens and a mintable test
+//! token from compiled artifacts, and creates auctions that are funded and
+//! ready to bid on. Tests then drive time forward with [`Fixture::mine`].
+pub mod anvil;
+pub mod artifacts;
+pub mod error;
+pub mod fixture;
+
+pub use anvil::*;
+pub use artifacts::*;
+pub use error::*;
+pub use fixture::*;
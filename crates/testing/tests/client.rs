@@ -0,0 +1,65 @@
+//! End-to-end checks of the auction client against a local anvil node; see
+//! `orchestrator.rs` for what they need.
+use std::sync::Arc;
+
+use alloy::{
+    eips::Encodable2718,
+    network::{EthereumWallet, TransactionBuilder},
+    providers::Provider,
+    signers::local::PrivateKeySigner,
+};
+use flux_core::{AuctionClient, NoopHook, ValidationHook};
+use flux_testing::{Artifacts, AuctionParams, DEV_KEYS, Fixture};
+
+#[tokio::test]
+#[ignore = "needs anvil and $FLUX_CCA_ARTIFACTS"]
+async fn filled_transactions_can_be_signed_and_broadcast_elsewhere() {
+    let artifacts = Artifacts::from_env().unwrap();
+    let mut fixture = Fixture::deploy(&artifacts).await.unwrap();
+    let auction = fixture
+        .create_auction(&AuctionParams::default())
+        .await
+        .unwrap();
+
+    let bidder: PrivateKeySigner = DEV_KEYS[1].parse().unwrap();
+    let provider = fixture.connect_as(DEV_KEYS[1]).await.unwrap();
+    let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+    let client = AuctionClient::new(
+        provider.clone(),
+        auction,
+        bidder.address(),
+        hook,
+        Vec::new(),
+    )
+    .await
+    .unwrap();
+    fixture
+        .mine_to(client.config().start_block.as_u64())
+        .await
+        .unwrap();
+
+    let tx = client.fill_tx(client.build_checkpoint_tx()).await.unwrap();
+    assert_eq!(
+        tx.nonce,
+        Some(
+            provider
+                .get_transaction_count(bidder.address())
+                .await
+                .unwrap()
+        )
+    );
+    assert_eq!(tx.chain_id, Some(provider.get_chain_id().await.unwrap()));
+    assert!(tx.gas.is_some());
+    assert!(tx.max_fee_per_gas.is_some() && tx.max_priority_fee_per_gas.is_some());
+
+    // Signed with a wallet flux knows nothing of and sent raw.
+    let envelope = tx.build(&EthereumWallet::from(bidder)).await.unwrap();
+    let receipt = provider
+        .send_raw_transaction(&envelope.encoded_2718())
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+    assert!(receipt.status());
+}
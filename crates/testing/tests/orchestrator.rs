@@ -0,0 +1,63 @@
+//! End-to-end runs against a local anvil node. These need `anvil` on `PATH`
+//! (or `$ANVIL_BIN`) and compiled contracts in `$FLUX_CCA_ARTIFACTS`:
+//!
+//!     cargo test -p flux-testing -- --ignored
+use std::sync::Arc;
+
+use alloy::{primitives::U256, signers::local::PrivateKeySigner};
+use flux_core::{
+    AuctionClient, BlockNumber, CurrencyAmount, IntentExecutor, Ladder, LadderStrategy, NoopHook,
+    Orchestrator, OrchestratorResult, Price, ProgressEvent, Q96, ValidationHook,
+};
+use flux_testing::{Artifacts, AuctionParams, DEV_KEYS, Fixture};
+use futures::StreamExt;
+
+#[tokio::test]
+#[ignore = "needs anvil and $FLUX_CCA_ARTIFACTS"]
+async fn ladder_is_submitted_when_the_auction_starts() {
+    let artifacts = Artifacts::from_env().unwrap();
+    let mut fixture = Fixture::deploy(&artifacts).await.unwrap();
+    let auction = fixture
+        .create_auction(&AuctionParams::default())
+        .await
+        .unwrap();
+
+    let bidder: PrivateKeySigner = DEV_KEYS[1].parse().unwrap();
+    let provider = fixture.connect_as(DEV_KEYS[1]).await.unwrap();
+    let hook: Arc<dyn ValidationHook> = Arc::new(NoopHook);
+    let client = AuctionClient::new(provider, auction, bidder.address(), hook, Vec::new())
+        .await
+        .unwrap();
+
+    let price = |ticks: u64| Price::new(Q96 * U256::from(ticks));
+    let total = CurrencyAmount::new(U256::from(3) * U256::from(10).pow(U256::from(18)));
+    let ladder = Ladder::linear(client.config(), price(2), price(4), 3, total).unwrap();
+    let start = client.config().start_block;
+
+    let mut orchestrator =
+        Orchestrator::new(IntentExecutor::new(client), LadderStrategy::new(ladder));
+    let progress = orchestrator.subscribe();
+    let mut result = OrchestratorResult::default();
+    fixture.mine_to(start.as_u64()).await.unwrap();
+    orchestrator
+        .handle_block(BlockNumber::new(start.as_u64()), &mut result)
+        .await
+        .unwrap();
+
+    assert_eq!(result.bids_submitted, 3);
+    assert_eq!(result.currency_committed, total);
+    assert!(result.gas_used > 0 && !result.gas_fees.is_zero());
+    assert_eq!(orchestrator.executor().client().tracked_bids().len(), 3);
+
+    drop(orchestrator);
+    let events: Vec<_> = progress.collect().await;
+    let finished = events
+        .iter()
+        .filter(|event| matches!(event, ProgressEvent::IntentFinished { record, .. } if record.outcome == "success"))
+        .count();
+    assert_eq!(finished, 3);
+    assert!(matches!(
+        events.last(),
+        Some(ProgressEvent::BlockProcessed { intents: 3, .. })
+    ));
+}